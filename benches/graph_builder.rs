@@ -0,0 +1,121 @@
+//! Benchmark for building the cross-workspace graph
+//!
+//! Run with `cargo bench --bench graph_builder`. The workspaces are built in
+//! memory, so the benchmark measures the graph builder rather than manifest
+//! parsing.
+
+use std::collections::{BTreeSet, HashMap};
+use std::hint::black_box;
+use std::path::PathBuf;
+
+use cargo_ferris_wheel::analyzer::{CrateMember, Dependency, WorkspaceInfo};
+use cargo_ferris_wheel::graph::DependencyGraphBuilder;
+use criterion::{Criterion, criterion_group, criterion_main};
+
+const WORKSPACES: usize = 100;
+const CRATES_PER_WORKSPACE: usize = 10;
+const DEPENDENCIES_PER_CRATE: usize = 12;
+
+struct Monorepo {
+    workspaces: HashMap<PathBuf, WorkspaceInfo>,
+    crate_to_workspaces: HashMap<String, BTreeSet<PathBuf>>,
+    crate_path_to_workspace: HashMap<PathBuf, PathBuf>,
+    crate_to_paths: HashMap<String, Vec<PathBuf>>,
+}
+
+fn crate_name(workspace: usize, member: usize) -> String {
+    format!("ws{workspace}-crate{member}")
+}
+
+fn dependencies(workspace: usize, member: usize, offset: usize) -> Vec<Dependency> {
+    (0..DEPENDENCIES_PER_CRATE)
+        .map(|i| {
+            let to_workspace = (workspace + offset + i * 7) % WORKSPACES;
+            Dependency::builder()
+                .with_name(crate_name(
+                    to_workspace,
+                    (member + i) % CRATES_PER_WORKSPACE,
+                ))
+                .build()
+                .unwrap()
+        })
+        .collect()
+}
+
+/// Workspaces whose crates each depend on crates of a dozen other workspaces,
+/// through normal, dev, build and target-specific dependencies
+fn monorepo() -> Monorepo {
+    let mut repo = Monorepo {
+        workspaces: HashMap::new(),
+        crate_to_workspaces: HashMap::new(),
+        crate_path_to_workspace: HashMap::new(),
+        crate_to_paths: HashMap::new(),
+    };
+
+    for workspace in 0..WORKSPACES {
+        let root = PathBuf::from(format!("/repo/ws{workspace}"));
+        let members = (0..CRATES_PER_WORKSPACE)
+            .map(|member| {
+                let name = crate_name(workspace, member);
+                let path = root.join(&name);
+                repo.crate_to_workspaces
+                    .entry(name.clone())
+                    .or_default()
+                    .insert(root.clone());
+                repo.crate_path_to_workspace
+                    .insert(path.clone(), root.clone());
+                repo.crate_to_paths
+                    .entry(name.clone())
+                    .or_default()
+                    .push(path.clone());
+
+                CrateMember::builder()
+                    .with_name(name)
+                    .with_path(path)
+                    .with_dependencies(dependencies(workspace, member, 1))
+                    .with_dev_dependencies(dependencies(workspace, member, 2))
+                    .with_build_dependencies(dependencies(workspace, member, 3))
+                    .with_target_dependencies(HashMap::from([(
+                        "cfg(unix)".to_string(),
+                        dependencies(workspace, member, 4),
+                    )]))
+                    .build()
+                    .unwrap()
+            })
+            .collect();
+
+        repo.workspaces.insert(
+            root,
+            WorkspaceInfo::builder()
+                .with_name(format!("ws{workspace}"))
+                .with_members(members)
+                .build()
+                .unwrap(),
+        );
+    }
+
+    repo
+}
+
+fn build_cross_workspace_graph(c: &mut Criterion) {
+    let repo = monorepo();
+
+    c.bench_function("build_cross_workspace_graph", |b| {
+        b.iter(|| {
+            let mut builder = DependencyGraphBuilder::new(false, false, false);
+            builder
+                .build_cross_workspace_graph(
+                    &repo.workspaces,
+                    &repo.crate_to_workspaces,
+                    &repo.crate_path_to_workspace,
+                    &repo.crate_to_paths,
+                    None,
+                )
+                .unwrap();
+            black_box(builder.graph().edge_count())
+        })
+    });
+}
+
+criterion_group!(benches, build_cross_workspace_graph);
+criterion_main!(benches);
@@ -1,15 +1,25 @@
-use std::collections::{BTreeSet, HashMap};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 use std::path::{Path, PathBuf};
+use std::sync::Arc;
 
 use console::style;
 use miette::{Diagnostic, Result, WrapErr};
 use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
-use super::DependencyClassifier;
+use super::cache::AnalysisCache;
+use super::cargo_metadata::{self, CargoPackage};
+use super::{
+    AnalyzerBackend, CrateMetadata, DependencyClassifier, FeatureSelection, MetadataProvider,
+    WorkspaceNames,
+};
+use crate::common::Deadline;
 use crate::progress::ProgressReporter;
-use crate::toml_parser::CargoToml;
-use crate::workspace_discovery::{WorkspaceDiscovery, WorkspaceRoot};
+use crate::timings::{Phase, Timer};
+use crate::toml_parser::{CargoToml, DetailedDependency};
+use crate::utils::canonical::canonicalize_or_self;
+use crate::workspace_discovery::{WorkspaceDiscovery, WorkspaceMember, WorkspaceRoot};
 
 #[derive(Error, Debug, Diagnostic)]
 pub enum CrateMemberBuilderError {
@@ -41,13 +51,36 @@ pub struct WorkspaceAnalyzer {
     crate_to_workspaces: CrateWorkspaceMap,
     crate_path_to_workspace: CratePathToWorkspaceMap,
     crate_to_paths: HashMap<String, Vec<PathBuf>>,
+    metadata_providers: Vec<Arc<dyn MetadataProvider>>,
+    backend: AnalyzerBackend,
+    features: FeatureSelection,
+    deadline: Deadline,
+    max_workspaces: Option<usize>,
+    excluded_paths: Vec<glob::Pattern>,
+    excluded_crates: Vec<glob::Pattern>,
+    follow_symlinks: bool,
+    /// Print the suspicious manifest constructs discovery finds
+    manifest_warnings: bool,
+    cache_file: Option<PathBuf>,
+    workspace_names: WorkspaceNames,
+    skipped_workspaces: usize,
 }
 
-#[derive(Debug, Clone)]
+/// An analyzed crate together with the workspace it belongs to
+#[derive(Debug, Clone, Copy)]
+pub struct AnalyzedCrate<'a> {
+    pub workspace_path: &'a Path,
+    pub workspace: &'a WorkspaceInfo,
+    pub member: &'a CrateMember,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct WorkspaceInfo {
     name: String,
     members: Vec<CrateMember>,
     is_standalone: bool,
+    parent: Option<PathBuf>,
+    children: Vec<PathBuf>,
 }
 
 impl WorkspaceInfo {
@@ -66,6 +99,21 @@ impl WorkspaceInfo {
     pub fn is_standalone(&self) -> bool {
         self.is_standalone
     }
+
+    /// Path of the workspace this one is nested in, if any
+    pub fn parent(&self) -> Option<&Path> {
+        self.parent.as_deref()
+    }
+
+    /// Paths of the workspaces nested directly inside this one, sorted
+    pub fn children(&self) -> &[PathBuf] {
+        &self.children
+    }
+
+    /// Whether this is a meta workspace that only groups nested workspaces
+    pub fn is_meta(&self) -> bool {
+        self.members.is_empty() && !self.children.is_empty()
+    }
 }
 
 #[derive(Error, Debug, Diagnostic)]
@@ -90,6 +138,8 @@ pub struct WorkspaceInfoBuilder {
     name: Option<String>,
     members: Option<Vec<CrateMember>>,
     is_standalone: Option<bool>,
+    parent: Option<PathBuf>,
+    children: Vec<PathBuf>,
 }
 
 impl WorkspaceInfoBuilder {
@@ -98,6 +148,8 @@ impl WorkspaceInfoBuilder {
             name: None,
             members: None,
             is_standalone: None,
+            parent: None,
+            children: Vec::new(),
         }
     }
 
@@ -116,6 +168,16 @@ impl WorkspaceInfoBuilder {
         self
     }
 
+    pub fn with_parent(mut self, parent: impl Into<PathBuf>) -> Self {
+        self.parent = Some(parent.into());
+        self
+    }
+
+    pub fn with_children(mut self, children: Vec<PathBuf>) -> Self {
+        self.children = children;
+        self
+    }
+
     pub fn build(self) -> Result<WorkspaceInfo, WorkspaceInfoBuilderError> {
         Ok(WorkspaceInfo {
             name: self.name.ok_or(WorkspaceInfoBuilderError::MissingName)?,
@@ -123,11 +185,13 @@ impl WorkspaceInfoBuilder {
                 .members
                 .ok_or(WorkspaceInfoBuilderError::MissingMembers)?,
             is_standalone: self.is_standalone.unwrap_or(false),
+            parent: self.parent,
+            children: self.children,
         })
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CrateMember {
     name: String,
     path: PathBuf,
@@ -135,6 +199,7 @@ pub struct CrateMember {
     dev_dependencies: Vec<Dependency>,
     build_dependencies: Vec<Dependency>,
     target_dependencies: HashMap<String, Vec<Dependency>>,
+    metadata: CrateMetadata,
 }
 
 impl CrateMember {
@@ -166,6 +231,11 @@ impl CrateMember {
     pub fn path(&self) -> &PathBuf {
         &self.path
     }
+
+    /// Metadata collected by the analyzer's metadata providers
+    pub fn metadata(&self) -> &CrateMetadata {
+        &self.metadata
+    }
 }
 
 #[derive(Default)]
@@ -176,6 +246,7 @@ pub struct CrateMemberBuilder {
     dev_dependencies: Vec<Dependency>,
     build_dependencies: Vec<Dependency>,
     target_dependencies: HashMap<String, Vec<Dependency>>,
+    metadata: CrateMetadata,
 }
 
 impl CrateMemberBuilder {
@@ -209,6 +280,11 @@ impl CrateMemberBuilder {
         self
     }
 
+    pub fn with_metadata(mut self, metadata: CrateMetadata) -> Self {
+        self.metadata = metadata;
+        self
+    }
+
     pub fn add_dependency(mut self, dep: Dependency) -> Self {
         self.dependencies.push(dep);
         self
@@ -222,16 +298,21 @@ impl CrateMemberBuilder {
             dev_dependencies: self.dev_dependencies,
             build_dependencies: self.build_dependencies,
             target_dependencies: self.target_dependencies,
+            metadata: self.metadata,
         })
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Dependency {
     name: String,
+    rename: Option<String>,
     target: Option<String>,
     path: Option<PathBuf>,
     is_workspace: bool,
+    is_optional: bool,
+    features: Vec<String>,
+    default_features: bool,
 }
 
 impl Dependency {
@@ -239,10 +320,17 @@ impl Dependency {
         DependencyBuilder::default()
     }
 
+    /// Name of the package the dependency refers to
     pub fn name(&self) -> &str {
         &self.name
     }
 
+    /// Name the dependency is declared under in the manifest, which differs
+    /// from [`Dependency::name`] when it is renamed with `package`
+    pub fn declared_name(&self) -> &str {
+        self.rename.as_deref().unwrap_or(&self.name)
+    }
+
     pub fn target(&self) -> Option<&str> {
         self.target.as_deref()
     }
@@ -251,17 +339,40 @@ impl Dependency {
         self.path.as_ref()
     }
 
+    /// Whether the path is relative to the workspace root, because the
+    /// dependency is inherited with `workspace = true` or redirected by
+    /// `[patch]`/`[replace]`
     pub fn is_workspace(&self) -> bool {
         self.is_workspace
     }
+
+    /// Whether the dependency is only enabled through a feature
+    pub fn is_optional(&self) -> bool {
+        self.is_optional
+    }
+
+    /// Features requested on the dependency, including ones inherited from
+    /// `[workspace.dependencies]`
+    pub fn features(&self) -> &[String] {
+        &self.features
+    }
+
+    /// Whether the dependency's `default` feature is requested
+    pub fn default_features(&self) -> bool {
+        self.default_features
+    }
 }
 
 #[derive(Default)]
 pub struct DependencyBuilder {
     name: Option<String>,
+    rename: Option<String>,
     target: Option<String>,
     path: Option<PathBuf>,
     is_workspace: bool,
+    is_optional: bool,
+    features: Vec<String>,
+    default_features: Option<bool>,
 }
 
 #[derive(Error, Debug, Diagnostic)]
@@ -278,9 +389,13 @@ impl From<&Dependency> for DependencyBuilder {
     fn from(dep: &Dependency) -> Self {
         Self {
             name: Some(dep.name().to_string()),
+            rename: dep.rename.clone(),
             target: dep.target().map(|t| t.to_string()),
             path: dep.path().cloned(),
             is_workspace: dep.is_workspace(),
+            is_optional: dep.is_optional(),
+            features: dep.features().to_vec(),
+            default_features: Some(dep.default_features()),
         }
     }
 }
@@ -291,6 +406,13 @@ impl DependencyBuilder {
         self
     }
 
+    /// Set the name the dependency is declared under, when it differs from
+    /// the package name
+    pub fn with_rename(mut self, rename: impl Into<String>) -> Self {
+        self.rename = Some(rename.into());
+        self
+    }
+
     pub fn with_target(mut self, target: impl Into<String>) -> Self {
         self.target = Some(target.into());
         self
@@ -306,12 +428,31 @@ impl DependencyBuilder {
         self
     }
 
+    pub fn with_is_optional(mut self, is_optional: bool) -> Self {
+        self.is_optional = is_optional;
+        self
+    }
+
+    pub fn with_features(mut self, features: Vec<String>) -> Self {
+        self.features = features;
+        self
+    }
+
+    pub fn with_default_features(mut self, default_features: bool) -> Self {
+        self.default_features = Some(default_features);
+        self
+    }
+
     pub fn build(self) -> Result<Dependency, DependencyBuilderError> {
         Ok(Dependency {
             name: self.name.ok_or(DependencyBuilderError::MissingName)?,
+            rename: self.rename,
             target: self.target,
             path: self.path,
             is_workspace: self.is_workspace,
+            is_optional: self.is_optional,
+            features: self.features,
+            default_features: self.default_features.unwrap_or(true),
         })
     }
 }
@@ -329,9 +470,124 @@ impl WorkspaceAnalyzer {
             crate_to_workspaces: HashMap::new(),
             crate_path_to_workspace: HashMap::new(),
             crate_to_paths: HashMap::new(),
+            metadata_providers: Vec::new(),
+            backend: AnalyzerBackend::default(),
+            features: FeatureSelection::default(),
+            deadline: Deadline::never(),
+            max_workspaces: None,
+            excluded_paths: Vec::new(),
+            excluded_crates: Vec::new(),
+            follow_symlinks: false,
+            manifest_warnings: false,
+            cache_file: None,
+            workspace_names: WorkspaceNames::default(),
+            skipped_workspaces: 0,
         }
     }
 
+    /// Annotate every analyzed crate using `providers`, in order
+    pub fn with_metadata_providers(mut self, providers: Vec<Arc<dyn MetadataProvider>>) -> Self {
+        self.metadata_providers = providers;
+        self
+    }
+
+    /// Read crate dependencies with `backend`
+    ///
+    /// With [`AnalyzerBackend::Metadata`], workspaces that `cargo metadata`
+    /// rejects fall back to manifest parsing with a warning.
+    pub fn with_backend(mut self, backend: AnalyzerBackend) -> Self {
+        self.backend = backend;
+        self
+    }
+
+    /// Only analyze optional dependencies enabled by `features`
+    pub fn with_features(mut self, features: FeatureSelection) -> Self {
+        self.features = features;
+        self
+    }
+
+    /// Stop analyzing further workspaces once `deadline` has passed
+    ///
+    /// Workspaces analyzed before the deadline are kept; the rest are counted
+    /// in [`WorkspaceAnalyzer::skipped_workspaces`].
+    pub fn with_deadline(mut self, deadline: Deadline) -> Self {
+        self.deadline = deadline;
+        self
+    }
+
+    /// Abort discovery once more than `max_workspaces` workspaces are found
+    ///
+    /// A runaway count usually means discovery reached vendored sources or
+    /// test fixtures; failing early beats analyzing all of them.
+    pub fn with_max_workspaces(mut self, max_workspaces: Option<usize>) -> Self {
+        self.max_workspaces = max_workspaces;
+        self
+    }
+
+    /// Skip directories matching `excluded_paths` during discovery
+    ///
+    /// Patterns are matched against paths relative to each discovered path,
+    /// e.g. `vendor` or `**/fixtures`.
+    pub fn with_excluded_paths(mut self, excluded_paths: Vec<glob::Pattern>) -> Self {
+        self.excluded_paths = excluded_paths;
+        self
+    }
+
+    /// Rename discovered workspaces to their display names in `names`
+    pub fn with_workspace_names(mut self, names: WorkspaceNames) -> Self {
+        self.workspace_names = names;
+        self
+    }
+
+    /// Leave out crates whose name matches one of `excluded_crates`
+    ///
+    /// Excluded crates are dropped from their workspace after analysis, so
+    /// nothing depends on them and they depend on nothing. A workspace whose
+    /// crates are all excluded is dropped as well.
+    pub fn with_excluded_crates(mut self, excluded_crates: Vec<glob::Pattern>) -> Self {
+        self.excluded_crates = excluded_crates;
+        self
+    }
+
+    /// Descend into symlinked directories during discovery
+    ///
+    /// Workspaces are identified by their canonical path, so one reachable
+    /// through several symlinks is still analyzed once.
+    pub fn with_follow_symlinks(mut self, follow_symlinks: bool) -> Self {
+        self.follow_symlinks = follow_symlinks;
+        self
+    }
+
+    /// Report suspicious manifest constructs found during discovery, such as
+    /// a dependency setting both `path` and `git`
+    pub fn with_manifest_warnings(mut self, manifest_warnings: bool) -> Self {
+        self.manifest_warnings = manifest_warnings;
+        self
+    }
+
+    /// Reuse analyses of workspaces whose manifests are unchanged from the
+    /// cache file [`default_cache_file`](super::default_cache_file) picks,
+    /// and update it
+    ///
+    /// The cache is not used while metadata providers are set, since their
+    /// metadata can depend on more than the manifests.
+    pub fn with_cache(mut self, enabled: bool) -> Self {
+        self.cache_file = enabled.then(super::default_cache_file).flatten();
+        self
+    }
+
+    /// Like [`WorkspaceAnalyzer::with_cache`], with the cache at `path`
+    pub fn with_cache_file(mut self, path: impl Into<PathBuf>) -> Self {
+        self.cache_file = Some(path.into());
+        self
+    }
+
+    /// Number of discovered workspaces left unanalyzed because the deadline
+    /// passed
+    pub fn skipped_workspaces(&self) -> usize {
+        self.skipped_workspaces
+    }
+
     pub fn workspaces(&self) -> &HashMap<PathBuf, WorkspaceInfo> {
         &self.workspaces
     }
@@ -348,6 +604,28 @@ impl WorkspaceAnalyzer {
         &self.crate_to_paths
     }
 
+    /// Every analyzed crate with the workspace it belongs to
+    ///
+    /// Crates are yielded by workspace path, then in member order.
+    pub fn crates(&self) -> impl Iterator<Item = AnalyzedCrate<'_>> {
+        let mut workspaces: Vec<(&PathBuf, &WorkspaceInfo)> = self.workspaces.iter().collect();
+        workspaces.sort_by_key(|(path, _)| *path);
+
+        workspaces.into_iter().flat_map(|(path, workspace)| {
+            workspace.members().iter().map(move |member| AnalyzedCrate {
+                workspace_path: path,
+                workspace,
+                member,
+            })
+        })
+    }
+
+    /// Analyzed crates that are not part of a `[workspace]`
+    pub fn standalone_crates(&self) -> impl Iterator<Item = AnalyzedCrate<'_>> {
+        self.crates()
+            .filter(|krate| krate.workspace.is_standalone())
+    }
+
     pub fn discover_workspaces(
         &mut self,
         paths: &[PathBuf],
@@ -358,10 +636,23 @@ impl WorkspaceAnalyzer {
         }
 
         // Discover workspace roots
-        let workspace_roots = self.discover_workspace_roots(paths, progress.as_deref())?;
+        let discovery_timer = Timer::start(Phase::Discovery);
+        let workspace_roots = match self.discover_workspace_roots(paths, progress.as_deref()) {
+            Ok(roots) => roots,
+            Err(e) => {
+                if let Some(p) = progress.as_mut() {
+                    p.abort_discovery();
+                }
+                return Err(e);
+            }
+        };
+
+        drop(discovery_timer);
 
         // Process workspaces and collect errors
+        let parsing_timer = Timer::start(Phase::Parsing);
         let (results, errors) = self.process_workspaces_parallel(workspace_roots);
+        drop(parsing_timer);
 
         // Report any errors that occurred during processing
         self.report_processing_errors(&errors);
@@ -384,7 +675,10 @@ impl WorkspaceAnalyzer {
         paths: &[PathBuf],
         progress: Option<&ProgressReporter>,
     ) -> Result<Vec<WorkspaceRoot>> {
-        let mut discovery = WorkspaceDiscovery::new();
+        let mut discovery = WorkspaceDiscovery::new()
+            .with_max_workspaces(self.max_workspaces)
+            .with_excluded_paths(self.excluded_paths.clone())
+            .with_follow_symlinks(self.follow_symlinks);
         let roots = discovery
             .discover_all(paths, progress)
             .wrap_err("Failed to discover workspaces")?;
@@ -393,20 +687,45 @@ impl WorkspaceAnalyzer {
         for warning in discovery.warnings() {
             eprintln!("{} {}", style("⚠").yellow(), warning);
         }
+        if self.manifest_warnings {
+            for warning in discovery.manifest_warnings() {
+                eprintln!("{} {}", style("⚠").yellow(), warning);
+            }
+        }
 
         Ok(roots)
     }
 
     fn process_workspaces_parallel(
-        &self,
+        &mut self,
         workspace_roots: Vec<WorkspaceRoot>,
     ) -> (ParallelProcessResults, Vec<(String, miette::Error)>) {
+        let total = workspace_roots.len();
+        let cache_file = self
+            .cache_file
+            .clone()
+            .filter(|_| self.metadata_providers.is_empty());
+        let mut cache = cache_file
+            .as_ref()
+            .map(|path| AnalysisCache::load(path, &self.cache_settings()));
+
         let (successes, errors): (Vec<_>, Vec<_>) = workspace_roots
             .into_par_iter()
+            .filter(|_| !self.deadline.is_expired())
             .map(|root| {
                 let name = root.name().to_string();
+                let files = manifest_files(&root);
+                if let Some(mut info) = cache
+                    .as_ref()
+                    .and_then(|cache| cache.get(root.path(), &files))
+                {
+                    // Names and nesting depend on discovery, not manifests
+                    info.name = root.name().to_string();
+                    info.parent = root.parent().cloned();
+                    return Ok(((root.path().clone(), info), files));
+                }
                 match self.process_workspace_root_parallel(root) {
-                    Ok(result) => Ok(result),
+                    Ok(result) => Ok((result, files)),
                     Err(e) => Err((name, e)),
                 }
             })
@@ -415,9 +734,73 @@ impl WorkspaceAnalyzer {
                 Err(e) => rayon::iter::Either::Right(e),
             });
 
+        self.skipped_workspaces = total - successes.len() - errors.len();
+
+        let successes = successes
+            .into_iter()
+            .map(|((path, info), files)| {
+                if let Some(cache) = cache.as_mut() {
+                    cache.insert(path.clone(), &files, info.clone());
+                }
+                (path, info)
+            })
+            .collect();
+        if let (Some(cache), Some(path)) = (cache.as_mut(), &cache_file)
+            && let Err(e) = cache.write(path)
+        {
+            eprintln!(
+                "{} Failed to write the analysis cache {}: {}",
+                style("⚠").yellow(),
+                path.display(),
+                e
+            );
+        }
+
         (successes, errors)
     }
 
+    /// The settings that shape an analysis, which cached analyses must have
+    /// been made with
+    fn cache_settings(&self) -> String {
+        let backend = match self.backend {
+            AnalyzerBackend::Manifest => "manifest",
+            AnalyzerBackend::Metadata => "metadata",
+        };
+        let features = match &self.features {
+            FeatureSelection::All => "all".to_string(),
+            FeatureSelection::Selected {
+                features,
+                default_features,
+            } => {
+                // Feature order does not change the analysis
+                let mut features = features.clone();
+                features.sort();
+                features.dedup();
+                format!(
+                    "[{}] default-features={default_features}",
+                    features.join(",")
+                )
+            }
+        };
+        format!("backend={backend} features={features}")
+    }
+
+    /// The settings that shape discovery and analysis together, which a
+    /// graph cached from them must have been built with
+    pub(crate) fn graph_settings(&self) -> String {
+        let excluded_paths: Vec<&str> = self
+            .excluded_paths
+            .iter()
+            .map(glob::Pattern::as_str)
+            .collect();
+        format!(
+            "{} exclude-paths=[{}] follow-symlinks={}",
+            self.cache_settings(),
+            excluded_paths.join(","),
+            self.follow_symlinks
+        )
+    }
+
     fn report_processing_errors(&self, errors: &[(String, miette::Error)]) {
         for (workspace_name, error) in errors {
             eprintln!(
@@ -431,16 +814,22 @@ impl WorkspaceAnalyzer {
 
     fn merge_results(&mut self, results: ParallelProcessResults) {
         for (workspace_path, mut info) in results {
-            let workspace_key = workspace_path
-                .canonicalize()
-                .unwrap_or_else(|_| workspace_path.clone());
+            if !self.excluded_crates.is_empty() && !info.members.is_empty() {
+                info.members.retain(|member| {
+                    !self
+                        .excluded_crates
+                        .iter()
+                        .any(|pattern| pattern.matches(&member.name))
+                });
+                if info.members.is_empty() {
+                    continue;
+                }
+            }
+            let workspace_key = canonicalize_or_self(&workspace_path);
 
             // Populate crate lookups from the workspace info
             for member in &mut info.members {
-                let crate_path = member
-                    .path
-                    .canonicalize()
-                    .unwrap_or_else(|_| member.path.clone());
+                let crate_path = canonicalize_or_self(&member.path);
 
                 member.path = crate_path.clone();
 
@@ -464,8 +853,36 @@ impl WorkspaceAnalyzer {
                     .insert(crate_path, workspace_key.clone());
             }
 
+            info.parent = info.parent.map(|parent| canonicalize_or_self(&parent));
+            info.name = self.workspace_names.display(&info.name).to_string();
+
             self.workspaces.insert(workspace_key, info);
         }
+
+        self.link_nested_workspaces();
+    }
+
+    /// Fill in each workspace's children from the parents found during
+    /// discovery, dropping links to workspaces that failed to process
+    fn link_nested_workspaces(&mut self) {
+        let mut children: HashMap<PathBuf, Vec<PathBuf>> = HashMap::new();
+        for (path, info) in &self.workspaces {
+            if let Some(parent) = &info.parent
+                && self.workspaces.contains_key(parent)
+            {
+                children
+                    .entry(parent.clone())
+                    .or_default()
+                    .push(path.clone());
+            }
+        }
+
+        let known: HashSet<PathBuf> = self.workspaces.keys().cloned().collect();
+        for (path, info) in &mut self.workspaces {
+            info.parent = info.parent.take().filter(|parent| known.contains(parent));
+            info.children = children.remove(path).unwrap_or_default();
+            info.children.sort();
+        }
     }
 
     fn report_discovery_stats(&self) {
@@ -501,16 +918,37 @@ impl WorkspaceAnalyzer {
         &self,
         root: WorkspaceRoot,
     ) -> Result<WorkspaceProcessResult> {
+        if self.backend == AnalyzerBackend::Metadata {
+            match cargo_metadata::workspace_packages(
+                root.path(),
+                &self.features,
+                root.patched_dependencies(),
+            ) {
+                Ok(packages) => return Ok(self.process_cargo_packages(&root, packages)),
+                Err(e) => eprintln!(
+                    "{} {}; falling back to manifest parsing for workspace '{}'",
+                    style("⚠").yellow(),
+                    e,
+                    root.name()
+                ),
+            }
+        }
+
+        let root_package = root_package_member(&root)?;
+
         // Process members in parallel and collect both results and errors
         let results: Vec<Result<CrateMember>> = root
             .members()
-            .par_iter()
+            .iter()
+            .chain(root_package.as_ref())
+            .par_bridge()
             .map(|member| {
                 self.analyze_crate_member(
                     member.name(),
                     member.path(),
                     member.cargo_toml(),
                     root.workspace_dependencies(),
+                    root.patched_dependencies(),
                     root.path(),
                 )
                 .wrap_err_with(|| format!("Failed to analyze crate '{}'", member.name()))
@@ -537,21 +975,67 @@ impl WorkspaceAnalyzer {
             name: root.name().to_string(),
             members,
             is_standalone: root.is_standalone(),
+            parent: root.parent().cloned(),
+            children: Vec::new(),
         };
 
         Ok((root.path().clone(), workspace_info))
     }
 
+    fn process_cargo_packages(
+        &self,
+        root: &WorkspaceRoot,
+        packages: Vec<CargoPackage>,
+    ) -> WorkspaceProcessResult {
+        let members = packages
+            .into_par_iter()
+            .map(|package| CrateMember {
+                metadata: self.collect_metadata(&package.name, &package.path),
+                name: package.name,
+                path: package.path,
+                dependencies: package.dependencies.dependencies().to_vec(),
+                dev_dependencies: package.dependencies.dev_dependencies().to_vec(),
+                build_dependencies: package.dependencies.build_dependencies().to_vec(),
+                target_dependencies: package.dependencies.target_dependencies().clone(),
+            })
+            .collect();
+
+        let workspace_info = WorkspaceInfo {
+            name: root.name().to_string(),
+            members,
+            is_standalone: root.is_standalone(),
+            parent: root.parent().cloned(),
+            children: Vec::new(),
+        };
+
+        (root.path().clone(), workspace_info)
+    }
+
     fn analyze_crate_member(
         &self,
         crate_name: &str,
         crate_path: &Path,
         cargo_toml: &CargoToml,
-        workspace_deps: &HashMap<String, PathBuf>,
+        workspace_deps: &HashMap<String, DetailedDependency>,
+        patched_deps: &HashMap<String, PathBuf>,
         _workspace_root: &Path,
     ) -> Result<CrateMember> {
         // Use the new DependencyClassifier to simplify dependency classification
-        let classifier = DependencyClassifier::classify_from_toml(cargo_toml, workspace_deps);
+        let mut classifier =
+            DependencyClassifier::classify_from_toml(cargo_toml, workspace_deps, patched_deps);
+        let optional: HashSet<&str> = classifier
+            .all_dependencies()
+            .filter(|dep| dep.is_optional())
+            .map(Dependency::declared_name)
+            .collect();
+        let no_features = BTreeMap::new();
+        if let Some(enabled) = self.features.enabled_optional_dependencies(
+            crate_name,
+            cargo_toml.features.as_ref().unwrap_or(&no_features),
+            &optional,
+        ) {
+            classifier.retain(|dep| !dep.is_optional() || enabled.contains(dep.declared_name()));
+        }
 
         Ok(CrateMember {
             name: crate_name.to_string(),
@@ -560,8 +1044,69 @@ impl WorkspaceAnalyzer {
             dev_dependencies: classifier.dev_dependencies().to_vec(),
             build_dependencies: classifier.build_dependencies().to_vec(),
             target_dependencies: classifier.target_dependencies().clone(),
+            metadata: self.collect_metadata(crate_name, crate_path),
         })
     }
+
+    fn collect_metadata(&self, crate_name: &str, crate_path: &Path) -> CrateMetadata {
+        let manifest_path = crate_path.join("Cargo.toml");
+        let mut metadata = CrateMetadata::new();
+        for provider in &self.metadata_providers {
+            match provider.provide(crate_name, &manifest_path) {
+                Ok(values) => metadata.extend(values),
+                Err(e) => eprintln!(
+                    "{} Failed to collect metadata for crate '{}': {}",
+                    style("⚠").yellow(),
+                    crate_name,
+                    e
+                ),
+            }
+        }
+        metadata
+    }
+}
+
+/// The files an analysis of the workspace at `root` is made from: its
+/// manifest and lock file, and the manifests of its members
+fn manifest_files(root: &WorkspaceRoot) -> Vec<PathBuf> {
+    let mut files = vec![
+        root.path().join("Cargo.toml"),
+        root.path().join("Cargo.lock"),
+    ];
+    files.extend(
+        root.members()
+            .iter()
+            .map(|member| member.path().join("Cargo.toml")),
+    );
+    files.sort();
+    files.dedup();
+    files
+}
+
+/// The package declared by a workspace's root manifest, when the root is
+/// both `[workspace]` and `[package]`
+///
+/// Member patterns rarely name the root itself, so discovery leaves the root
+/// package out; without it, the root package's dependencies would be lost.
+fn root_package_member(root: &WorkspaceRoot) -> Result<Option<WorkspaceMember>> {
+    if root.is_standalone() || root.members().iter().any(|m| m.path() == root.path()) {
+        return Ok(None);
+    }
+
+    let manifest_path = root.path().join("Cargo.toml");
+    let cargo_toml = CargoToml::parse_file(&manifest_path)
+        .wrap_err_with(|| format!("Failed to parse {}", manifest_path.display()))?;
+    let Some(package) = cargo_toml.package.clone() else {
+        return Ok(None);
+    };
+
+    WorkspaceMember::builder()
+        .path(root.path().clone())
+        .name(package.name)
+        .cargo_toml(cargo_toml)
+        .build()
+        .map(Some)
+        .wrap_err_with(|| format!("Failed to build root package of '{}'", root.name()))
 }
 
 #[cfg(test)]
@@ -650,6 +1195,127 @@ crate-a = { path = "../crate-a" }
         assert_eq!(crate_b.dev_dependencies.len(), 1); // crate-a
     }
 
+    #[test]
+    fn test_cache_reuses_unchanged_workspaces() {
+        let temp = create_test_workspace();
+        let cache_dir = TempDir::new().unwrap();
+        let cache_file = cache_dir.path().join("cache");
+        let analyze = || {
+            let mut analyzer = WorkspaceAnalyzer::new().with_cache_file(&cache_file);
+            analyzer
+                .discover_workspaces(&[temp.path().to_path_buf()], None)
+                .unwrap();
+            let ws = analyzer.workspaces().values().next().unwrap();
+            let crate_b = ws.members.iter().find(|m| m.name == "crate-b").unwrap();
+            (crate_b.dependencies.len(), crate_b.dev_dependencies.len())
+        };
+        assert_eq!(analyze(), (0, 1));
+        assert!(cache_file.is_file());
+
+        // An edit that keeps the size and modification time goes unnoticed,
+        // showing the cached analysis is used
+        let manifest = temp.path().join("my-workspace/crate-b/Cargo.toml");
+        let modified = fs::metadata(&manifest).unwrap().modified().unwrap();
+        let content = fs::read_to_string(&manifest).unwrap();
+        fs::write(
+            &manifest,
+            content.replace("[dev-dependencies]", "[dependencies]    "),
+        )
+        .unwrap();
+        let file = fs::File::options().write(true).open(&manifest).unwrap();
+        file.set_modified(modified).unwrap();
+        assert_eq!(analyze(), (0, 1));
+
+        file.set_modified(modified + std::time::Duration::from_secs(1))
+            .unwrap();
+        assert_eq!(analyze(), (1, 0));
+    }
+
+    #[test]
+    fn test_cache_settings_are_explicit() {
+        let selected = |features: &[&str]| FeatureSelection::Selected {
+            features: features.iter().map(|f| f.to_string()).collect(),
+            default_features: false,
+        };
+
+        assert_eq!(
+            WorkspaceAnalyzer::new().cache_settings(),
+            "backend=manifest features=all"
+        );
+        assert_eq!(
+            WorkspaceAnalyzer::new()
+                .with_backend(AnalyzerBackend::Metadata)
+                .with_features(selected(&["serde", "async"]))
+                .cache_settings(),
+            "backend=metadata features=[async,serde] default-features=false"
+        );
+        assert_eq!(
+            WorkspaceAnalyzer::new()
+                .with_features(selected(&["async", "serde"]))
+                .cache_settings(),
+            WorkspaceAnalyzer::new()
+                .with_features(selected(&["serde", "async"]))
+                .cache_settings()
+        );
+    }
+
+    #[test]
+    fn test_crate_iterators() {
+        let temp = create_test_workspace();
+        let tool = temp.path().join("tool");
+        fs::create_dir_all(&tool).unwrap();
+        fs::write(tool.join("Cargo.toml"), "[package]\nname = \"tool\"\n").unwrap();
+        fs::write(tool.join("Cargo.lock"), "# lock").unwrap();
+        let mut analyzer = WorkspaceAnalyzer::new();
+
+        analyzer
+            .discover_workspaces(&[temp.path().to_path_buf()], None)
+            .unwrap();
+
+        let crates: Vec<(&str, &str)> = analyzer
+            .crates()
+            .map(|krate| (krate.workspace.name(), krate.member.name()))
+            .collect();
+        assert_eq!(
+            crates,
+            vec![
+                ("my-workspace", "crate-a"),
+                ("my-workspace", "crate-b"),
+                ("tool", "tool"),
+            ]
+        );
+
+        let standalone: Vec<AnalyzedCrate<'_>> = analyzer.standalone_crates().collect();
+        assert_eq!(standalone.len(), 1);
+        assert_eq!(standalone[0].member.name(), "tool");
+        assert!(standalone[0].workspace_path.ends_with("tool"));
+    }
+
+    #[test]
+    fn test_excluded_crates_are_left_out() {
+        let temp = create_test_workspace();
+        let tool = temp.path().join("tool-fixtures");
+        fs::create_dir_all(&tool).unwrap();
+        fs::write(
+            tool.join("Cargo.toml"),
+            "[package]\nname = \"tool-fixtures\"\n",
+        )
+        .unwrap();
+        fs::write(tool.join("Cargo.lock"), "# lock").unwrap();
+        let mut analyzer = WorkspaceAnalyzer::new().with_excluded_crates(vec![
+            glob::Pattern::new("*-fixtures").unwrap(),
+            glob::Pattern::new("crate-b").unwrap(),
+        ]);
+
+        analyzer
+            .discover_workspaces(&[temp.path().to_path_buf()], None)
+            .unwrap();
+
+        let crates: Vec<&str> = analyzer.crates().map(|krate| krate.member.name()).collect();
+        assert_eq!(crates, vec!["crate-a"]);
+        assert!(!analyzer.crate_to_workspace().contains_key("crate-b"));
+    }
+
     #[test]
     fn test_duplicate_crate_names_map_to_multiple_workspaces() {
         let temp = TempDir::new().unwrap();
@@ -735,4 +1401,241 @@ members = ["shared"]
             );
         }
     }
+
+    #[derive(Debug)]
+    struct OwnerProvider;
+
+    impl MetadataProvider for OwnerProvider {
+        fn name(&self) -> &str {
+            "owners"
+        }
+
+        fn provide(
+            &self,
+            crate_name: &str,
+            manifest_path: &Path,
+        ) -> Result<CrateMetadata, crate::error::FerrisWheelError> {
+            assert!(manifest_path.ends_with(format!("{crate_name}/Cargo.toml")));
+            Ok(CrateMetadata::from([(
+                "owner".to_string(),
+                format!("team-{crate_name}"),
+            )]))
+        }
+    }
+
+    #[test]
+    fn test_metadata_providers_annotate_crates() {
+        let temp = create_test_workspace();
+        let mut analyzer = WorkspaceAnalyzer::new().with_metadata_providers(vec![
+            Arc::new(super::super::ManifestMetadataProvider),
+            Arc::new(OwnerProvider),
+        ]);
+
+        analyzer
+            .discover_workspaces(&[temp.path().to_path_buf()], None)
+            .unwrap();
+
+        let ws = analyzer.workspaces().values().next().unwrap();
+        for member in ws.members() {
+            assert_eq!(
+                member.metadata()["owner"],
+                format!("team-{}", member.name())
+            );
+        }
+    }
+
+    #[test]
+    fn test_feature_selection_excludes_disabled_optional_dependencies() {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path();
+
+        fs::create_dir_all(root.join("ws/app")).unwrap();
+        fs::create_dir_all(root.join("ws/telemetry")).unwrap();
+        fs::write(
+            root.join("ws/Cargo.toml"),
+            "[workspace]\nmembers = [\"app\", \"telemetry\"]\n",
+        )
+        .unwrap();
+        fs::write(
+            root.join("ws/app/Cargo.toml"),
+            r#"
+[package]
+name = "app"
+
+[dependencies]
+telemetry = { path = "../telemetry", optional = true }
+
+[features]
+tracing = ["dep:telemetry"]
+"#,
+        )
+        .unwrap();
+        fs::write(
+            root.join("ws/telemetry/Cargo.toml"),
+            "[package]\nname = \"telemetry\"\n",
+        )
+        .unwrap();
+
+        let app_dependencies = |features: FeatureSelection| {
+            let mut analyzer = WorkspaceAnalyzer::new().with_features(features);
+            analyzer
+                .discover_workspaces(&[root.to_path_buf()], None)
+                .unwrap();
+            let ws = analyzer.workspaces().values().next().unwrap();
+            let app = ws.members().iter().find(|m| m.name() == "app").unwrap();
+            app.dependencies().len()
+        };
+
+        assert_eq!(app_dependencies(FeatureSelection::All), 1);
+        assert_eq!(
+            app_dependencies(FeatureSelection::Selected {
+                features: vec![],
+                default_features: true,
+            }),
+            0
+        );
+        assert_eq!(
+            app_dependencies(FeatureSelection::Selected {
+                features: vec!["tracing".to_string()],
+                default_features: true,
+            }),
+            1
+        );
+    }
+
+    #[test]
+    fn test_patched_dependencies_are_resolved() {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path();
+
+        fs::create_dir_all(root.join("app/cli")).unwrap();
+        fs::create_dir_all(root.join("lib")).unwrap();
+        fs::write(
+            root.join("app/Cargo.toml"),
+            r#"
+[workspace]
+members = ["cli"]
+
+[patch.crates-io]
+lib = { path = "../lib" }
+"#,
+        )
+        .unwrap();
+        fs::write(
+            root.join("app/cli/Cargo.toml"),
+            "[package]\nname = \"cli\"\n\n[dependencies]\nlib = \"1.0\"\n",
+        )
+        .unwrap();
+        fs::write(root.join("lib/Cargo.toml"), "[package]\nname = \"lib\"\n").unwrap();
+
+        let mut analyzer = WorkspaceAnalyzer::new();
+        analyzer
+            .discover_workspaces(&[root.to_path_buf()], None)
+            .unwrap();
+
+        let app = analyzer
+            .workspaces()
+            .values()
+            .find(|ws| ws.name() == "app")
+            .unwrap();
+        let cli = &app.members()[0];
+        assert_eq!(cli.dependencies().len(), 1);
+        assert_eq!(cli.dependencies()[0].name(), "lib");
+        assert_eq!(cli.dependencies()[0].path(), Some(&PathBuf::from("../lib")));
+        assert!(cli.dependencies()[0].is_workspace());
+    }
+
+    #[test]
+    fn test_root_package_is_a_member() {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path();
+
+        fs::create_dir_all(root.join("app/cli")).unwrap();
+        fs::create_dir_all(root.join("lib")).unwrap();
+        fs::write(
+            root.join("app/Cargo.toml"),
+            r#"
+[workspace]
+members = ["cli"]
+
+[package]
+name = "app"
+
+[dependencies]
+cli = { path = "cli" }
+lib = { path = "../lib" }
+"#,
+        )
+        .unwrap();
+        fs::write(
+            root.join("app/cli/Cargo.toml"),
+            "[package]\nname = \"cli\"\n",
+        )
+        .unwrap();
+        fs::write(root.join("lib/Cargo.toml"), "[package]\nname = \"lib\"\n").unwrap();
+        fs::write(root.join("lib/Cargo.lock"), "# lock file").unwrap();
+
+        let mut analyzer = WorkspaceAnalyzer::new();
+        analyzer
+            .discover_workspaces(&[root.to_path_buf()], None)
+            .unwrap();
+
+        let app = analyzer
+            .workspaces()
+            .values()
+            .find(|ws| ws.name() == "app")
+            .unwrap();
+        let mut names: Vec<_> = app.members().iter().map(|m| m.name()).collect();
+        names.sort();
+        assert_eq!(names, vec!["app", "cli"]);
+
+        let package = app.members().iter().find(|m| m.name() == "app").unwrap();
+        let mut deps: Vec<_> = package.dependencies().iter().map(|d| d.name()).collect();
+        deps.sort();
+        assert_eq!(deps, vec!["cli", "lib"]);
+        assert!(analyzer.crate_to_workspace().contains_key("app"));
+    }
+
+    #[test]
+    fn test_meta_workspace_hierarchy() {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path().canonicalize().unwrap();
+
+        fs::write(
+            root.join("Cargo.toml"),
+            "[workspace]\nmembers = [\"platform\", \"products\"]\n",
+        )
+        .unwrap();
+        for (dir, member) in [("platform", "core"), ("products", "web")] {
+            fs::create_dir_all(root.join(dir).join(member)).unwrap();
+            fs::write(
+                root.join(dir).join("Cargo.toml"),
+                format!("[workspace]\nmembers = [\"{member}\"]\n"),
+            )
+            .unwrap();
+            fs::write(
+                root.join(dir).join(member).join("Cargo.toml"),
+                format!("[package]\nname = \"{member}\"\n"),
+            )
+            .unwrap();
+        }
+
+        let mut analyzer = WorkspaceAnalyzer::new();
+        analyzer
+            .discover_workspaces(std::slice::from_ref(&root), None)
+            .unwrap();
+
+        let meta = &analyzer.workspaces()[&root];
+        assert!(meta.is_meta());
+        assert_eq!(meta.parent(), None);
+        assert_eq!(
+            meta.children(),
+            &[root.join("platform"), root.join("products")]
+        );
+
+        let platform = &analyzer.workspaces()[&root.join("platform")];
+        assert!(!platform.is_meta());
+        assert_eq!(platform.parent(), Some(root.as_path()));
+        assert!(platform.children().is_empty());
+    }
 }
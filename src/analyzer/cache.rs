@@ -0,0 +1,302 @@
+//! On-disk cache of workspace analyses
+//!
+//! Analyzing a workspace means classifying the dependencies of every crate in
+//! it, or running `cargo metadata` with the metadata backend. The cache keeps
+//! each workspace's result along with the size and modification time of the
+//! manifests it came from, so later runs only re-analyze workspaces whose
+//! manifests changed. The whole cache is dropped when it was written by
+//! another version of ferris-wheel or with other analysis settings.
+//!
+//! The cache lives in the Cargo target directory when one is configured
+//! through `CARGO_TARGET_DIR` or `build.target-dir`, so `cargo clean` clears
+//! it, and in the user's cache directory otherwise. It is never written into
+//! the analyzed repository unless its Cargo configuration says so.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+
+use serde::{Deserialize, Serialize};
+
+use super::WorkspaceInfo;
+use crate::error::FerrisWheelError;
+
+/// Cache file used by the commands: `ferris-wheel/analysis-cache.json` in
+/// the configured Cargo target directory, or in the user's cache directory;
+/// `None` if there is neither
+pub fn default_cache_file() -> Option<PathBuf> {
+    let cwd = std::env::current_dir().ok();
+    let dir = cwd
+        .as_deref()
+        .and_then(|cwd| configured_target_dir(cwd, cargo_home().as_deref()))
+        .or_else(user_cache_dir)?;
+    Some(dir.join("ferris-wheel").join("analysis-cache.json"))
+}
+
+/// The target directory Cargo would use from `start` if one is configured:
+/// `CARGO_TARGET_DIR`, `CARGO_BUILD_TARGET_DIR`, or `build.target-dir` in
+/// the nearest `.cargo/config.toml` above `start`, then in `cargo_home`
+fn configured_target_dir(start: &Path, cargo_home: Option<&Path>) -> Option<PathBuf> {
+    for var in ["CARGO_TARGET_DIR", "CARGO_BUILD_TARGET_DIR"] {
+        if let Some(dir) = std::env::var_os(var).filter(|dir| !dir.is_empty()) {
+            return Some(start.join(dir));
+        }
+    }
+
+    // Relative directories are relative to the directory holding `.cargo`
+    let config_dirs = start
+        .ancestors()
+        .map(|dir| (dir.join(".cargo"), dir.to_path_buf()))
+        .chain(cargo_home.map(|home| (home.to_path_buf(), home.to_path_buf())));
+    for (config_dir, base) in config_dirs {
+        for name in ["config.toml", "config"] {
+            let Ok(content) = fs::read_to_string(config_dir.join(name)) else {
+                continue;
+            };
+            let target_dir = toml::from_str::<toml::Table>(&content)
+                .ok()
+                .and_then(|config| {
+                    config
+                        .get("build")?
+                        .get("target-dir")?
+                        .as_str()
+                        .map(PathBuf::from)
+                });
+            if let Some(target_dir) = target_dir {
+                return Some(base.join(target_dir));
+            }
+        }
+    }
+    None
+}
+
+/// `$CARGO_HOME`, or `.cargo` in the home directory
+fn cargo_home() -> Option<PathBuf> {
+    std::env::var_os("CARGO_HOME")
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| home_dir().map(|home| home.join(".cargo")))
+}
+
+/// `$XDG_CACHE_HOME`, `~/.cache`, or `%LOCALAPPDATA%` on Windows
+fn user_cache_dir() -> Option<PathBuf> {
+    std::env::var_os("XDG_CACHE_HOME")
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| home_dir().map(|home| home.join(".cache")))
+        .or_else(|| std::env::var_os("LOCALAPPDATA").map(PathBuf::from))
+}
+
+fn home_dir() -> Option<PathBuf> {
+    std::env::var_os("HOME")
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+}
+
+/// Size and modification time of a file an analysis was made from
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct FileStamp {
+    path: PathBuf,
+    len: u64,
+    modified_nanos: u128,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CacheEntry {
+    files: Vec<FileStamp>,
+    workspace: WorkspaceInfo,
+}
+
+/// Cached analyses by workspace root
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct AnalysisCache {
+    version: String,
+    settings: String,
+    entries: HashMap<PathBuf, CacheEntry>,
+}
+
+impl AnalysisCache {
+    /// Load the cache at `path` for analyses made with `settings`
+    ///
+    /// A missing or unreadable cache, or one written by another version or
+    /// with other settings, starts out empty.
+    pub fn load(path: &Path, settings: &str) -> Self {
+        fs::read(path)
+            .ok()
+            .and_then(|content| serde_json::from_slice::<Self>(&content).ok())
+            .filter(|cache| {
+                cache.version == env!("CARGO_PKG_VERSION") && cache.settings == settings
+            })
+            .unwrap_or_else(|| Self {
+                version: env!("CARGO_PKG_VERSION").to_string(),
+                settings: settings.to_string(),
+                entries: HashMap::new(),
+            })
+    }
+
+    /// The cached analysis of the workspace at `root`, as long as none of
+    /// `files` changed, appeared or disappeared since it was stored
+    pub fn get(&self, root: &Path, files: &[PathBuf]) -> Option<WorkspaceInfo> {
+        let entry = self.entries.get(root)?;
+        (entry.files == stamp_files(files)).then(|| entry.workspace.clone())
+    }
+
+    /// Store the analysis of the workspace at `root`, made from `files`
+    pub fn insert(&mut self, root: PathBuf, files: &[PathBuf], workspace: WorkspaceInfo) {
+        let files = stamp_files(files);
+        self.entries.insert(root, CacheEntry { files, workspace });
+    }
+
+    /// Write the cache to `path`, creating its directory and leaving out
+    /// workspaces whose root manifest is gone
+    ///
+    /// The file is replaced atomically, so concurrent runs never read a
+    /// partial cache.
+    pub fn write(&mut self, path: &Path) -> Result<(), FerrisWheelError> {
+        self.entries
+            .retain(|root, _| root.join("Cargo.toml").is_file());
+
+        if let Some(dir) = path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+            fs::create_dir_all(dir)?;
+        }
+
+        let temp = path.with_file_name(format!(
+            "{}.{}.tmp",
+            path.file_name()
+                .map(|name| name.to_string_lossy())
+                .unwrap_or_default(),
+            std::process::id()
+        ));
+        fs::write(&temp, serde_json::to_vec(self)?)?;
+        fs::rename(&temp, path).inspect_err(|_| {
+            let _ = fs::remove_file(&temp);
+        })?;
+        Ok(())
+    }
+}
+
+/// Stamps of those of `files` that exist
+pub(crate) fn stamp_files(files: &[PathBuf]) -> Vec<FileStamp> {
+    files
+        .iter()
+        .filter_map(|path| {
+            let metadata = fs::metadata(path).ok()?;
+            let modified_nanos = metadata
+                .modified()
+                .ok()
+                .and_then(|modified| modified.duration_since(UNIX_EPOCH).ok())
+                .map_or(0, |since_epoch| since_epoch.as_nanos());
+            Some(FileStamp {
+                path: path.clone(),
+                len: metadata.len(),
+                modified_nanos,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use tempfile::TempDir;
+
+    use super::*;
+
+    #[test]
+    fn test_cache_round_trip_and_invalidation() {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path().join("ws");
+        fs::create_dir_all(&root).unwrap();
+        let manifest = root.join("Cargo.toml");
+        fs::write(&manifest, "[workspace]\n").unwrap();
+        let files = vec![manifest.clone(), root.join("Cargo.lock")];
+        let cache_path = temp.path().join("ferris-wheel/analysis-cache.json");
+
+        let workspace = WorkspaceInfo::builder()
+            .with_name("ws")
+            .with_members(Vec::new())
+            .build()
+            .unwrap();
+        let mut cache = AnalysisCache::load(&cache_path, "manifest");
+        assert!(cache.get(&root, &files).is_none());
+        cache.insert(root.clone(), &files, workspace);
+        cache.write(&cache_path).unwrap();
+
+        let cache = AnalysisCache::load(&cache_path, "manifest");
+        assert_eq!(cache.get(&root, &files).unwrap().name(), "ws");
+        // Other settings start from scratch
+        assert!(
+            AnalysisCache::load(&cache_path, "metadata")
+                .get(&root, &files)
+                .is_none()
+        );
+
+        // A file that appears invalidates the entry, as does an edit
+        fs::write(root.join("Cargo.lock"), "").unwrap();
+        assert!(cache.get(&root, &files).is_none());
+        fs::remove_file(root.join("Cargo.lock")).unwrap();
+        fs::write(&manifest, "[workspace]\nmembers = []\n").unwrap();
+        assert!(cache.get(&root, &files).is_none());
+    }
+
+    #[test]
+    fn test_build_target_dir_is_read_from_cargo_config() {
+        // Only meaningful where the environment does not pick the directory
+        if ["CARGO_TARGET_DIR", "CARGO_BUILD_TARGET_DIR"]
+            .iter()
+            .any(|var| std::env::var_os(var).is_some())
+        {
+            return;
+        }
+        let temp = TempDir::new().unwrap();
+        let repo = temp.path().join("repo");
+        let nested = repo.join("crates/core");
+        fs::create_dir_all(&nested).unwrap();
+        let home = temp.path().join("cargo-home");
+        fs::create_dir_all(&home).unwrap();
+
+        assert_eq!(configured_target_dir(&nested, Some(&home)), None);
+
+        fs::write(
+            home.join("config.toml"),
+            "[build]\ntarget-dir = \"/shared/target\"\n",
+        )
+        .unwrap();
+        assert_eq!(
+            configured_target_dir(&nested, Some(&home)),
+            Some(PathBuf::from("/shared/target"))
+        );
+
+        fs::create_dir_all(repo.join(".cargo")).unwrap();
+        fs::write(
+            repo.join(".cargo/config.toml"),
+            "[build]\ntarget-dir = \"out\"\n",
+        )
+        .unwrap();
+        assert_eq!(
+            configured_target_dir(&nested, Some(&home)),
+            Some(repo.join("out"))
+        );
+    }
+
+    #[test]
+    fn test_cache_drops_removed_workspaces() {
+        let temp = TempDir::new().unwrap();
+        let cache_path = temp.path().join("analysis-cache.json");
+        let workspace = WorkspaceInfo::builder()
+            .with_name("gone")
+            .with_members(Vec::new())
+            .build()
+            .unwrap();
+
+        let mut cache = AnalysisCache::load(&cache_path, "");
+        cache.insert(temp.path().join("gone"), &[], workspace);
+        cache.write(&cache_path).unwrap();
+
+        assert!(
+            AnalysisCache::load(&cache_path, "")
+                .get(&temp.path().join("gone"), &[])
+                .is_none()
+        );
+    }
+}
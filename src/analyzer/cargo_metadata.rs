@@ -0,0 +1,268 @@
+//! `cargo metadata` analyzer backend
+//!
+//! By default the analyzer reads `Cargo.toml` files itself. The metadata
+//! backend asks Cargo instead, running `cargo metadata --no-deps` once per
+//! workspace, so renamed packages, `workspace = true` inheritance and target
+//! tables are resolved exactly the way Cargo resolves them. It is slower and
+//! only works for manifests Cargo itself accepts.
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use serde::Deserialize;
+
+use super::{Dependency, DependencyClassifier, FeatureSelection};
+use crate::error::FerrisWheelError;
+
+/// How the analyzer reads crate dependencies
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum AnalyzerBackend {
+    /// Parse `Cargo.toml` files directly
+    #[default]
+    Manifest,
+    /// Ask `cargo metadata`, matching Cargo's semantics exactly
+    Metadata,
+}
+
+/// A workspace member as reported by Cargo
+pub(super) struct CargoPackage {
+    pub name: String,
+    pub path: PathBuf,
+    pub dependencies: DependencyClassifier,
+}
+
+#[derive(Deserialize)]
+struct Metadata {
+    packages: Vec<Package>,
+    workspace_members: Vec<String>,
+}
+
+#[derive(Deserialize)]
+struct Package {
+    id: String,
+    name: String,
+    manifest_path: PathBuf,
+    dependencies: Vec<PackageDependency>,
+    features: BTreeMap<String, Vec<String>>,
+}
+
+#[derive(Deserialize)]
+struct PackageDependency {
+    /// The package name, even when the dependency is renamed
+    name: String,
+    /// The name the dependency is declared under, when renamed
+    rename: Option<String>,
+    optional: bool,
+    features: Vec<String>,
+    uses_default_features: bool,
+    kind: Option<String>,
+    target: Option<String>,
+    /// Absolute path of a path dependency
+    path: Option<PathBuf>,
+}
+
+/// Members of the workspace at `workspace_root` and their path dependencies
+/// enabled by `features`
+///
+/// `cargo metadata --no-deps` does not resolve `[patch]` or `[replace]`, so
+/// `patched` redirects, relative to `workspace_root`, are applied here.
+pub(super) fn workspace_packages(
+    workspace_root: &Path,
+    features: &FeatureSelection,
+    patched: &HashMap<String, PathBuf>,
+) -> Result<Vec<CargoPackage>, FerrisWheelError> {
+    let error = |message: String| FerrisWheelError::CargoMetadataError {
+        path: workspace_root.to_path_buf(),
+        message,
+    };
+
+    let cargo = std::env::var_os("CARGO").unwrap_or_else(|| "cargo".into());
+    let output = Command::new(cargo)
+        .args([
+            "metadata",
+            "--format-version",
+            "1",
+            "--no-deps",
+            "--manifest-path",
+        ])
+        .arg(workspace_root.join("Cargo.toml"))
+        .output()
+        .map_err(|e| error(format!("Failed to run cargo: {e}")))?;
+
+    if !output.status.success() {
+        return Err(error(
+            String::from_utf8_lossy(&output.stderr).trim().to_string(),
+        ));
+    }
+
+    let metadata: Metadata = serde_json::from_slice(&output.stdout)
+        .map_err(|e| error(format!("Unexpected output: {e}")))?;
+
+    Ok(metadata
+        .packages
+        .into_iter()
+        .filter(|package| metadata.workspace_members.contains(&package.id))
+        .map(|package| CargoPackage {
+            path: package
+                .manifest_path
+                .parent()
+                .map(Path::to_path_buf)
+                .unwrap_or_default(),
+            dependencies: classify(&package, features, workspace_root, patched),
+            name: package.name,
+        })
+        .collect())
+}
+
+/// Sort path dependencies the same way
+/// [`DependencyClassifier::classify_from_toml`] does; other dependencies cannot
+/// point into the monorepo
+fn classify(
+    package: &Package,
+    features: &FeatureSelection,
+    workspace_root: &Path,
+    patched: &HashMap<String, PathBuf>,
+) -> DependencyClassifier {
+    // Features refer to dependencies by the name they are declared under
+    let declared: Vec<&str> = package
+        .dependencies
+        .iter()
+        .map(|dep| dep.rename.as_deref().unwrap_or(&dep.name))
+        .collect();
+    let optional: HashSet<&str> = package
+        .dependencies
+        .iter()
+        .zip(&declared)
+        .filter(|(dep, _)| dep.optional)
+        .map(|(_, name)| *name)
+        .collect();
+    let enabled =
+        features.enabled_optional_dependencies(&package.name, &package.features, &optional);
+
+    let mut classifier = DependencyClassifier::new();
+
+    for (dep, declared) in package.dependencies.iter().zip(&declared) {
+        let Some(path) = dep.path.clone().or_else(|| {
+            patched
+                .get(&dep.name)
+                .map(|patch| workspace_root.join(patch))
+        }) else {
+            continue;
+        };
+        if dep.optional
+            && enabled
+                .as_ref()
+                .is_some_and(|enabled| !enabled.contains(*declared))
+        {
+            continue;
+        }
+
+        let mut builder = Dependency::builder()
+            .with_name(&dep.name)
+            .with_path(path)
+            .with_is_optional(dep.optional)
+            .with_features(dep.features.clone())
+            .with_default_features(dep.uses_default_features);
+        if let Some(rename) = &dep.rename {
+            builder = builder.with_rename(rename);
+        }
+        if let Some(target) = &dep.target {
+            builder = builder.with_target(target);
+        }
+        let Ok(dependency) = builder.build() else {
+            continue;
+        };
+
+        match (&dep.target, dep.kind.as_deref()) {
+            (Some(target), _) => classifier
+                .target_dependencies_mut()
+                .entry(target.clone())
+                .or_default()
+                .push(dependency),
+            (None, Some("dev")) => classifier.dev_dependencies_mut().push(dependency),
+            (None, Some("build")) => classifier.build_dependencies_mut().push(dependency),
+            (None, _) => classifier.dependencies_mut().push(dependency),
+        }
+    }
+
+    classifier
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use tempfile::TempDir;
+
+    use super::*;
+
+    fn write(root: &Path, path: &str, content: &str) {
+        let path = root.join(path);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, content).unwrap();
+    }
+
+    #[test]
+    fn test_workspace_packages_follow_cargo_semantics() {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path();
+        write(
+            root,
+            "Cargo.toml",
+            r#"
+[workspace]
+members = ["app", "core", "sys"]
+resolver = "2"
+
+[workspace.dependencies]
+core = { path = "core" }
+"#,
+        );
+        write(
+            root,
+            "app/Cargo.toml",
+            r#"
+[package]
+name = "app"
+version = "0.1.0"
+edition = "2021"
+
+[dependencies]
+renamed-core = { package = "core", path = "../core" }
+
+[dev-dependencies]
+core = { workspace = true }
+
+[target.'cfg(unix)'.dependencies]
+sys = { path = "../sys" }
+"#,
+        );
+        write(root, "app/src/lib.rs", "");
+        write(
+            root,
+            "core/Cargo.toml",
+            "[package]\nname = \"core\"\nversion = \"0.1.0\"\nedition = \"2021\"\n",
+        );
+        write(root, "core/src/lib.rs", "");
+        write(
+            root,
+            "sys/Cargo.toml",
+            "[package]\nname = \"sys\"\nversion = \"0.1.0\"\nedition = \"2021\"\n",
+        );
+        write(root, "sys/src/lib.rs", "");
+
+        let packages = workspace_packages(root, &FeatureSelection::All, &HashMap::new()).unwrap();
+
+        let app = packages.iter().find(|p| p.name == "app").unwrap();
+        let normal = app.dependencies.dependencies();
+        assert_eq!(normal.len(), 1);
+        assert_eq!(normal[0].name(), "core");
+        assert_eq!(app.dependencies.dev_dependencies()[0].name(), "core");
+        assert_eq!(
+            app.dependencies.target_dependencies()["cfg(unix)"][0].target(),
+            Some("cfg(unix)")
+        );
+        assert_eq!(packages.len(), 3);
+    }
+}
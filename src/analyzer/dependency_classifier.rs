@@ -9,6 +9,7 @@ use std::collections::HashMap;
 use crate::analyzer::{Dependency, DependencyBuilderError};
 use crate::toml_parser::{
     CargoToml, Dependency as TomlDependency, DependencyType as TomlDependencyType,
+    DetailedDependency,
 };
 
 /// Classifies dependencies from a parsed Cargo.toml into categorized vectors
@@ -40,6 +41,26 @@ impl DependencyClassifier {
         &self.target_dependencies
     }
 
+    /// Iterates over dependencies of every type
+    pub fn all_dependencies(&self) -> impl Iterator<Item = &Dependency> {
+        self.dependencies
+            .iter()
+            .chain(&self.dev_dependencies)
+            .chain(&self.build_dependencies)
+            .chain(self.target_dependencies.values().flatten())
+    }
+
+    /// Keeps only the dependencies for which `keep` returns true
+    pub fn retain(&mut self, mut keep: impl FnMut(&Dependency) -> bool) {
+        self.dependencies.retain(&mut keep);
+        self.dev_dependencies.retain(&mut keep);
+        self.build_dependencies.retain(&mut keep);
+        for deps in self.target_dependencies.values_mut() {
+            deps.retain(&mut keep);
+        }
+        self.target_dependencies.retain(|_, deps| !deps.is_empty());
+    }
+
     /// Gets mutable access to normal dependencies
     pub fn dependencies_mut(&mut self) -> &mut Vec<Dependency> {
         &mut self.dependencies
@@ -79,28 +100,40 @@ impl DependencyClassifier {
     }
 
     /// Classify dependencies from a CargoToml
+    ///
+    /// `workspace = true` entries are resolved against `workspace_deps`,
+    /// inheriting their path and features. Dependencies without a path of
+    /// their own are still classified when `patched_deps` redirects them to a
+    /// local path; like workspace dependencies, that path is relative to the
+    /// workspace root.
     pub fn classify_from_toml(
         cargo_toml: &CargoToml,
-        workspace_deps: &HashMap<String, std::path::PathBuf>,
+        workspace_deps: &HashMap<String, DetailedDependency>,
+        patched_deps: &HashMap<String, std::path::PathBuf>,
     ) -> Self {
         let mut classifier = Self::new();
 
         for (dep_name, dep, dep_type) in cargo_toml.get_all_dependencies() {
-            if !Self::is_relevant_dependency(&dep_name, &dep, workspace_deps) {
-                continue;
-            }
+            let is_workspace = CargoToml::is_workspace_dependency(&dep);
+            let dep = match workspace_deps.get(&dep_name) {
+                Some(workspace) if is_workspace => CargoToml::inherit_dependency(&dep, workspace),
+                _ => dep,
+            };
 
-            let dependency_path = if CargoToml::is_workspace_dependency(&dep) {
-                workspace_deps.get(&dep_name).cloned()
-            } else {
-                CargoToml::extract_path(&dep).map(std::path::PathBuf::from)
+            let (dependency_path, is_workspace) = match CargoToml::extract_path(&dep) {
+                Some(path) => (std::path::PathBuf::from(path), is_workspace),
+                None => match patched_deps.get(CargoToml::package_name(&dep_name, &dep)) {
+                    Some(path) => (path.clone(), true),
+                    None => continue,
+                },
             };
 
             if let Ok(dependency) = Self::create_dependency(
                 &dep_name,
+                &dep,
                 &dep_type,
-                dependency_path,
-                CargoToml::is_workspace_dependency(&dep),
+                Some(dependency_path),
+                is_workspace,
             ) {
                 classifier.add_dependency(dependency, dep_type);
             }
@@ -109,30 +142,28 @@ impl DependencyClassifier {
         classifier
     }
 
-    /// Check if a dependency is relevant (i.e., is a path or workspace
-    /// dependency)
-    fn is_relevant_dependency(
-        dep_name: &str,
-        dep: &TomlDependency,
-        workspace_deps: &HashMap<String, std::path::PathBuf>,
-    ) -> bool {
-        if CargoToml::is_workspace_dependency(dep) {
-            workspace_deps.contains_key(dep_name)
-        } else {
-            CargoToml::extract_path(dep).is_some()
-        }
-    }
-
-    /// Create a Dependency struct from name and type
+    /// Create a Dependency struct from the name it is declared under, its
+    /// manifest entry and type
+    ///
+    /// Renamed dependencies are named after the package they refer to.
     fn create_dependency(
         dep_name: &str,
+        dep: &TomlDependency,
         dep_type: &TomlDependencyType,
         path: Option<std::path::PathBuf>,
         is_workspace: bool,
     ) -> Result<Dependency, DependencyBuilderError> {
+        let package = CargoToml::package_name(dep_name, dep);
         let mut builder = Dependency::builder()
-            .with_name(dep_name)
-            .with_is_workspace(is_workspace);
+            .with_name(package)
+            .with_is_workspace(is_workspace)
+            .with_is_optional(CargoToml::is_optional_dependency(dep))
+            .with_features(CargoToml::dependency_features(dep))
+            .with_default_features(CargoToml::uses_default_features(dep));
+
+        if package != dep_name {
+            builder = builder.with_rename(dep_name);
+        }
 
         if let Some(path) = path {
             builder = builder.with_path(path);
@@ -198,6 +229,7 @@ mod tests {
     fn test_create_dependency_normal() {
         let dep = DependencyClassifier::create_dependency(
             "test-crate",
+            &TomlDependency::Simple("1.0".to_string()),
             &TomlDependencyType::Normal,
             None,
             false,
@@ -211,6 +243,7 @@ mod tests {
     fn test_create_dependency_with_target() {
         let dep = DependencyClassifier::create_dependency(
             "test-crate",
+            &TomlDependency::Simple("1.0".to_string()),
             &TomlDependencyType::Target("wasm32-unknown-unknown".to_string()),
             None,
             false,
@@ -220,6 +253,34 @@ mod tests {
         assert_eq!(dep.target(), Some("wasm32-unknown-unknown"));
     }
 
+    #[test]
+    fn test_renamed_dependencies_target_the_package() {
+        let cargo_toml: CargoToml = toml::from_str(
+            r#"
+[package]
+name = "app"
+
+[dependencies]
+core = { package = "atlas-core", path = "../core", optional = true }
+sdk = { workspace = true }
+"#,
+        )
+        .unwrap();
+        let workspace_deps: HashMap<String, DetailedDependency> =
+            toml::from_str(r#"sdk = { package = "atlas-sdk", path = "sdk" }"#).unwrap();
+
+        let classifier =
+            DependencyClassifier::classify_from_toml(&cargo_toml, &workspace_deps, &HashMap::new());
+        let mut deps: Vec<(&str, &str)> = classifier
+            .dependencies()
+            .iter()
+            .map(|dep| (dep.name(), dep.declared_name()))
+            .collect();
+        deps.sort();
+
+        assert_eq!(deps, [("atlas-core", "core"), ("atlas-sdk", "sdk")]);
+    }
+
     #[test]
     fn test_add_dependencies() {
         let mut classifier = DependencyClassifier::new();
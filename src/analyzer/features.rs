@@ -0,0 +1,171 @@
+//! Feature-aware dependency selection
+//!
+//! Optional dependencies only exist in a build when a feature turns them on.
+//! By default every optional dependency is analyzed, so cycles hidden behind
+//! any feature are reported. A [`FeatureSelection`] narrows the analysis to
+//! the dependencies Cargo would actually activate for a set of features.
+//!
+//! Features are resolved per crate: `dep:name`, implicit features named after
+//! optional dependencies and `name/feature` entries activate dependencies of
+//! the crate declaring them, but features enabled on a dependency are not
+//! propagated into that dependency's own optional dependencies.
+
+use std::collections::{BTreeMap, HashSet};
+
+/// Features considered enabled while analyzing
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum FeatureSelection {
+    /// Every optional dependency is analyzed
+    #[default]
+    All,
+    /// Only optional dependencies activated by these features
+    Selected {
+        /// Feature names, or `crate/feature` to enable a feature of one crate
+        features: Vec<String>,
+        /// Whether each crate's `default` feature is enabled
+        default_features: bool,
+    },
+}
+
+impl FeatureSelection {
+    /// Optional dependencies of the crate `crate_name` enabled by this
+    /// selection, by the name they are declared under
+    ///
+    /// `features` is the crate's `[features]` table and `optional` the names
+    /// of its optional dependencies. Returns `None` when every optional
+    /// dependency is enabled.
+    pub fn enabled_optional_dependencies(
+        &self,
+        crate_name: &str,
+        features: &BTreeMap<String, Vec<String>>,
+        optional: &HashSet<&str>,
+    ) -> Option<HashSet<String>> {
+        let FeatureSelection::Selected {
+            features: requested,
+            default_features,
+        } = self
+        else {
+            return None;
+        };
+
+        // An optional dependency only gets an implicit feature of its own
+        // name when no feature refers to it with `dep:`
+        let explicit: HashSet<&str> = features
+            .values()
+            .flatten()
+            .filter_map(|value| value.strip_prefix("dep:"))
+            .collect();
+        let implicit = |name: &str| optional.contains(name) && !explicit.contains(name);
+
+        let mut pending: Vec<&str> = requested
+            .iter()
+            .filter_map(|feature| match feature.split_once('/') {
+                Some((owner, feature)) => (owner == crate_name).then_some(feature),
+                None => Some(feature.as_str()),
+            })
+            .collect();
+        if *default_features {
+            pending.push("default");
+        }
+
+        let mut seen = HashSet::new();
+        let mut enabled = HashSet::new();
+        while let Some(feature) = pending.pop() {
+            if !seen.insert(feature) {
+                continue;
+            }
+
+            if let Some(dependency) = feature.strip_prefix("dep:") {
+                enabled.insert(dependency.to_string());
+            } else if let Some((dependency, _)) = feature.split_once('/') {
+                // `name?/feature` only applies when `name` is enabled elsewhere
+                if optional.contains(dependency) {
+                    enabled.insert(dependency.to_string());
+                    if implicit(dependency) {
+                        pending.push(dependency);
+                    }
+                }
+            } else if let Some(values) = features.get(feature) {
+                pending.extend(values.iter().map(String::as_str));
+            } else if implicit(feature) {
+                enabled.insert(feature.to_string());
+            }
+        }
+
+        Some(enabled)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn features(entries: &[(&str, &[&str])]) -> BTreeMap<String, Vec<String>> {
+        entries
+            .iter()
+            .map(|(name, values)| {
+                (
+                    name.to_string(),
+                    values.iter().map(|value| value.to_string()).collect(),
+                )
+            })
+            .collect()
+    }
+
+    fn enabled(selection: &FeatureSelection, crate_name: &str) -> Vec<String> {
+        let table = features(&[
+            ("default", &["tracing"]),
+            ("tracing", &["dep:telemetry"]),
+            ("server", &["http/tls", "cache?/redis"]),
+        ]);
+        let optional = HashSet::from(["telemetry", "http", "cache", "metrics"]);
+
+        let mut enabled: Vec<String> = selection
+            .enabled_optional_dependencies(crate_name, &table, &optional)
+            .unwrap()
+            .into_iter()
+            .collect();
+        enabled.sort();
+        enabled
+    }
+
+    #[test]
+    fn test_all_features_enable_everything() {
+        assert_eq!(
+            FeatureSelection::All.enabled_optional_dependencies(
+                "app",
+                &BTreeMap::new(),
+                &HashSet::from(["telemetry"])
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn test_selected_features_activate_optional_dependencies() {
+        let defaults = FeatureSelection::Selected {
+            features: vec![],
+            default_features: true,
+        };
+        assert_eq!(enabled(&defaults, "app"), ["telemetry"]);
+
+        let server = FeatureSelection::Selected {
+            features: vec!["server".to_string(), "metrics".to_string()],
+            default_features: false,
+        };
+        assert_eq!(enabled(&server, "app"), ["http", "metrics"]);
+
+        let scoped = FeatureSelection::Selected {
+            features: vec!["other/server".to_string(), "app/tracing".to_string()],
+            default_features: false,
+        };
+        assert_eq!(enabled(&scoped, "app"), ["telemetry"]);
+
+        // `telemetry` is referenced with `dep:`, so it has no implicit feature
+        let implicit = FeatureSelection::Selected {
+            features: vec!["telemetry".to_string()],
+            default_features: false,
+        };
+        assert!(enabled(&implicit, "app").is_empty());
+    }
+}
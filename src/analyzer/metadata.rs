@@ -0,0 +1,244 @@
+//! Crate metadata providers
+//!
+//! A [`MetadataProvider`] annotates each analyzed crate with free-form
+//! key-value pairs, such as owners, service tiers or build times pulled from
+//! internal systems. Providers run while the analyzer processes crates, and
+//! the merged result is available from [`CrateMember::metadata`].
+//!
+//! [`CrateMember::metadata`]: super::CrateMember::metadata
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use crate::error::FerrisWheelError;
+
+/// Key-value metadata attached to a crate
+pub type CrateMetadata = BTreeMap<String, String>;
+
+/// A source of crate metadata
+///
+/// Providers are called once per crate, in parallel, so implementations must
+/// be thread-safe. When several providers return the same key, the provider
+/// registered last wins.
+pub trait MetadataProvider: Send + Sync + fmt::Debug {
+    /// Short name used in warnings
+    fn name(&self) -> &str;
+
+    /// Metadata for the crate `crate_name` whose manifest is at
+    /// `manifest_path`
+    fn provide(
+        &self,
+        crate_name: &str,
+        manifest_path: &Path,
+    ) -> Result<CrateMetadata, FerrisWheelError>;
+}
+
+/// Reads the `[package.metadata.ferris-wheel]` table of each crate manifest
+///
+/// Strings, numbers and booleans are used as-is; arrays of them are joined
+/// with `", "`. Nested tables are ignored.
+#[derive(Debug, Clone, Default)]
+pub struct ManifestMetadataProvider;
+
+impl MetadataProvider for ManifestMetadataProvider {
+    fn name(&self) -> &str {
+        "manifest"
+    }
+
+    fn provide(
+        &self,
+        _crate_name: &str,
+        manifest_path: &Path,
+    ) -> Result<CrateMetadata, FerrisWheelError> {
+        let content = std::fs::read_to_string(manifest_path).map_err(|e| {
+            FerrisWheelError::FileReadError {
+                path: manifest_path.to_path_buf(),
+                source: e,
+            }
+        })?;
+        let manifest: toml::Value =
+            toml::from_str(&content).map_err(|e| FerrisWheelError::MetadataError {
+                provider: self.name().to_string(),
+                message: format!("Invalid manifest {}: {e}", manifest_path.display()),
+            })?;
+
+        let Some(table) = manifest
+            .get("package")
+            .and_then(|package| package.get("metadata"))
+            .and_then(|metadata| metadata.get("ferris-wheel"))
+            .and_then(toml::Value::as_table)
+        else {
+            return Ok(CrateMetadata::new());
+        };
+
+        Ok(table
+            .iter()
+            .filter_map(|(key, value)| {
+                let value = match value {
+                    toml::Value::Array(items) => items
+                        .iter()
+                        .filter_map(scalar_to_string)
+                        .collect::<Vec<_>>()
+                        .join(", "),
+                    value => scalar_to_string(value)?,
+                };
+                Some((key.clone(), value))
+            })
+            .collect())
+    }
+}
+
+fn scalar_to_string(value: &toml::Value) -> Option<String> {
+    match value {
+        toml::Value::String(s) => Some(s.clone()),
+        toml::Value::Integer(i) => Some(i.to_string()),
+        toml::Value::Float(f) => Some(f.to_string()),
+        toml::Value::Boolean(b) => Some(b.to_string()),
+        _ => None,
+    }
+}
+
+/// Runs an external program for each crate
+///
+/// The program is invoked as `<command...> <crate-name> <manifest-path>` from
+/// `dir` and prints one `key=value` pair per line. Blank lines and lines
+/// starting with `#` are ignored; a non-zero exit status is an error.
+#[derive(Debug, Clone)]
+pub struct CommandMetadataProvider {
+    command: Vec<String>,
+    dir: PathBuf,
+}
+
+impl CommandMetadataProvider {
+    pub fn new(command: Vec<String>, dir: PathBuf) -> Self {
+        Self { command, dir }
+    }
+}
+
+impl MetadataProvider for CommandMetadataProvider {
+    fn name(&self) -> &str {
+        self.command.first().map_or("command", String::as_str)
+    }
+
+    fn provide(
+        &self,
+        crate_name: &str,
+        manifest_path: &Path,
+    ) -> Result<CrateMetadata, FerrisWheelError> {
+        let error = |message: String| FerrisWheelError::MetadataError {
+            provider: self.name().to_string(),
+            message,
+        };
+
+        let (program, args) = self
+            .command
+            .split_first()
+            .ok_or_else(|| error("No command configured".to_string()))?;
+
+        let output = Command::new(program)
+            .args(args)
+            .arg(crate_name)
+            .arg(manifest_path)
+            .current_dir(&self.dir)
+            .output()
+            .map_err(|e| error(format!("Failed to run: {e}")))?;
+
+        if !output.status.success() {
+            return Err(error(format!(
+                "Exited with {} for crate '{crate_name}': {}",
+                output.status,
+                String::from_utf8_lossy(&output.stderr).trim()
+            )));
+        }
+
+        String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .map(|line| {
+                line.split_once('=')
+                    .map(|(key, value)| (key.trim().to_string(), value.trim().to_string()))
+                    .ok_or_else(|| error(format!("Expected `key=value`, got '{line}'")))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use tempfile::TempDir;
+
+    use super::*;
+
+    #[test]
+    fn test_manifest_metadata() {
+        let temp = TempDir::new().unwrap();
+        let manifest = temp.path().join("Cargo.toml");
+        fs::write(
+            &manifest,
+            r#"
+[package]
+name = "billing"
+
+[package.metadata.ferris-wheel]
+owner = "payments"
+tier = 1
+critical = true
+oncall = ["alice", "bob"]
+
+[package.metadata.ferris-wheel.nested]
+ignored = "yes"
+"#,
+        )
+        .unwrap();
+
+        let metadata = ManifestMetadataProvider
+            .provide("billing", &manifest)
+            .unwrap();
+
+        assert_eq!(
+            metadata,
+            CrateMetadata::from([
+                ("critical".to_string(), "true".to_string()),
+                ("oncall".to_string(), "alice, bob".to_string()),
+                ("owner".to_string(), "payments".to_string()),
+                ("tier".to_string(), "1".to_string()),
+            ])
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_command_metadata() {
+        let temp = TempDir::new().unwrap();
+        let provider = CommandMetadataProvider::new(
+            vec![
+                "sh".to_string(),
+                "-c".to_string(),
+                "printf '# owners\\nowner = team-%s\\n\\nmanifest=%s\\n' \"$1\" \"$2\"".to_string(),
+                "sh".to_string(),
+            ],
+            temp.path().to_path_buf(),
+        );
+
+        let metadata = provider
+            .provide("billing", Path::new("billing/Cargo.toml"))
+            .unwrap();
+
+        assert_eq!(metadata["owner"], "team-billing");
+        assert_eq!(metadata["manifest"], "billing/Cargo.toml");
+
+        let failing = CommandMetadataProvider::new(
+            vec!["sh".to_string(), "-c".to_string(), "exit 3".to_string()],
+            temp.path().to_path_buf(),
+        );
+        assert!(matches!(
+            failing.provide("billing", Path::new("Cargo.toml")),
+            Err(FerrisWheelError::MetadataError { .. })
+        ));
+    }
+}
@@ -12,6 +12,15 @@
 //!   build, target)
 //! - **WorkspaceInfo**: Contains metadata about a discovered workspace
 //! - **CrateMember**: Represents a crate within a workspace
+//! - **AnalyzedCrate**: A crate together with its workspace, as yielded by
+//!   [`WorkspaceAnalyzer::crates`]
+//! - **MetadataProvider**: Annotates crates with key-value metadata during
+//!   analysis
+//! - **FeatureSelection**: Which optional dependencies are enabled by features
+//! - **TargetPlatform**: Which target-specific dependencies apply to a target
+//!   triple
+//! - **AnalyzerBackend**: Whether dependencies are read from manifests directly
+//!   or from `cargo metadata`
 //!
 //! ## Example
 //!
@@ -52,9 +61,24 @@
 //! # }
 //! ```
 
+mod cache;
+mod cargo_metadata;
 mod dependency_classifier;
+mod features;
+mod metadata;
+mod names;
+mod target;
 
+pub use cache::default_cache_file;
+pub(crate) use cache::{FileStamp, stamp_files};
+pub use cargo_metadata::AnalyzerBackend;
 pub use dependency_classifier::DependencyClassifier;
+pub use features::FeatureSelection;
+pub use metadata::{
+    CommandMetadataProvider, CrateMetadata, ManifestMetadataProvider, MetadataProvider,
+};
+pub use names::WorkspaceNames;
+pub use target::TargetPlatform;
 
 // Re-export the main analyzer types
 mod analyzer_impl;
@@ -0,0 +1,48 @@
+//! Workspace display names
+//!
+//! Workspaces are named after their root package or directory, which is not
+//! always the name an organization knows them by. The `[names]` section of
+//! `.ferris-wheel.toml` maps those derived names to display names, and the
+//! analyzer renames workspaces as they are discovered, so every report and
+//! graph uses the display names.
+
+use std::collections::BTreeMap;
+
+use schemars::JsonSchema;
+use serde::Deserialize;
+
+/// Display names by derived workspace name (`[names]`)
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, JsonSchema)]
+#[serde(transparent)]
+pub struct WorkspaceNames(BTreeMap<String, String>);
+
+impl WorkspaceNames {
+    pub fn new(names: BTreeMap<String, String>) -> Self {
+        Self(names)
+    }
+
+    /// The display name of the workspace derived as `name`
+    pub fn display<'a>(&'a self, name: &'a str) -> &'a str {
+        self.0.get(name).map_or(name, String::as_str)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display_falls_back_to_derived_name() {
+        let names: WorkspaceNames =
+            toml::from_str("\"billing-rs\" = \"Billing\"\nledger = \"Ledger Service\"").unwrap();
+
+        assert_eq!(names.display("billing-rs"), "Billing");
+        assert_eq!(names.display("ledger"), "Ledger Service");
+        assert_eq!(names.display("auth"), "auth");
+        assert!(WorkspaceNames::default().is_empty());
+    }
+}
@@ -0,0 +1,106 @@
+//! Target platform evaluation
+//!
+//! Dependencies declared under `[target.'cfg(...)'.dependencies]` or
+//! `[target.<triple>.dependencies]` only exist when building for a matching
+//! platform. By default every target-specific dependency is analyzed; a
+//! [`TargetPlatform`] keeps only the ones Cargo would use when building for
+//! one target triple, so platform-specific cycles can be examined per
+//! platform.
+//!
+//! Only target predicates (`unix`, `target_os`, `target_arch`, ...) are
+//! evaluated. Predicates that depend on the build rather than the platform,
+//! such as `test`, `debug_assertions` or `feature`, are treated as unset, as
+//! Cargo does for target tables.
+
+use std::fmt;
+use std::str::FromStr;
+
+use cfg_expr::targets::{TargetInfo, get_builtin_target_by_triple};
+use cfg_expr::{Expression, Predicate};
+
+/// A target triple that target-specific dependencies are evaluated against
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TargetPlatform {
+    info: &'static TargetInfo,
+}
+
+impl TargetPlatform {
+    /// Look up one of rustc's built-in target triples
+    pub fn from_triple(triple: &str) -> Option<Self> {
+        get_builtin_target_by_triple(triple).map(|info| Self { info })
+    }
+
+    /// The target triple, e.g. `x86_64-unknown-linux-gnu`
+    pub fn triple(&self) -> &'static str {
+        self.info.triple.as_str()
+    }
+
+    /// Whether a dependency declared under `[target.<spec>]` is used when
+    /// building for this platform
+    ///
+    /// `spec` is either a `cfg(...)` expression or a target triple. Specs
+    /// that fail to parse never match.
+    pub fn matches(&self, spec: &str) -> bool {
+        let spec = spec.trim();
+        if !spec.starts_with("cfg(") {
+            return spec == self.triple();
+        }
+
+        match Expression::parse(spec) {
+            Ok(expression) => expression.eval(|predicate| match predicate {
+                Predicate::Target(target) => target.matches(self.info),
+                _ => false,
+            }),
+            Err(_) => false,
+        }
+    }
+}
+
+impl FromStr for TargetPlatform {
+    type Err = String;
+
+    fn from_str(triple: &str) -> Result<Self, Self::Err> {
+        Self::from_triple(triple).ok_or_else(|| {
+            format!("unknown target triple '{triple}' (see `rustc --print target-list`)")
+        })
+    }
+}
+
+impl fmt::Display for TargetPlatform {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.triple())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cfg_expressions_are_evaluated_for_the_target() {
+        let linux: TargetPlatform = "x86_64-unknown-linux-gnu".parse().unwrap();
+        let windows: TargetPlatform = "x86_64-pc-windows-msvc".parse().unwrap();
+        let wasm: TargetPlatform = "wasm32-unknown-unknown".parse().unwrap();
+
+        assert!(linux.matches("cfg(unix)"));
+        assert!(!windows.matches("cfg(unix)"));
+        assert!(windows.matches("cfg(windows)"));
+        assert!(linux.matches(r#"cfg(all(target_os = "linux", target_pointer_width = "64"))"#));
+        assert!(wasm.matches(r#"cfg(target_arch = "wasm32")"#));
+        assert!(!wasm.matches(r#"cfg(not(target_arch = "wasm32"))"#));
+        assert!(linux.matches(r#"cfg(any(windows, target_os = "linux"))"#));
+    }
+
+    #[test]
+    fn test_triples_and_build_predicates() {
+        let linux: TargetPlatform = "x86_64-unknown-linux-gnu".parse().unwrap();
+
+        assert!(linux.matches("x86_64-unknown-linux-gnu"));
+        assert!(!linux.matches("aarch64-apple-darwin"));
+        assert!(!linux.matches("cfg(test)"));
+        assert!(!linux.matches(r#"cfg(feature = "std")"#));
+        assert!(!linux.matches("cfg(unix"));
+        assert!("x86_64-unknown-nonsense".parse::<TargetPlatform>().is_err());
+        assert_eq!(linux.to_string(), "x86_64-unknown-linux-gnu");
+    }
+}
@@ -1,8 +1,12 @@
 use std::path::PathBuf;
+use std::time::Duration;
 
-use clap::{Parser, Subcommand};
+use clap::{Command, CommandFactory, Parser, Subcommand};
+use clap_complete::Shell;
 
-use crate::common::{CommonArgs, CycleDisplayArgs, FormatArgs};
+use crate::common::{CommonArgs, CycleDisplayArgs, FormatArgs, OutputArgs, ReportFormatArgs};
+use crate::detector::CycleSeverity;
+use crate::graph::{HypotheticalEdge, ThemeChoice, WorkspaceGrouping};
 
 #[derive(Parser)]
 #[command(
@@ -16,6 +20,41 @@ pub struct CargoArgs {
     pub command: CargoCommand,
 }
 
+impl CargoArgs {
+    /// The command line definition, reading each flag's environment variable
+    /// under the short `FERRIS_WHEEL_` prefix when the `CARGO_FERRIS_WHEEL_`
+    /// one is unset
+    pub fn command_with_env() -> Command {
+        prefer_short_env(Self::command(), &|name| std::env::var_os(name).is_some())
+    }
+}
+
+/// Point the flags of `command` and its subcommands at the short-prefixed
+/// variable of each environment variable that `is_set` reports unset while
+/// its short form is set
+fn prefer_short_env(command: Command, is_set: &dyn Fn(&str) -> bool) -> Command {
+    use crate::constants::env::{PREFIX, SHORT_PREFIX};
+
+    command
+        .mut_args(|arg| {
+            let names = arg
+                .get_env()
+                .and_then(|env| env.to_str())
+                .and_then(|env| env.strip_prefix(PREFIX))
+                .map(|suffix| {
+                    (
+                        format!("{PREFIX}{suffix}"),
+                        format!("{SHORT_PREFIX}{suffix}"),
+                    )
+                });
+            match names {
+                Some((long, short)) if !is_set(&long) && is_set(&short) => arg.env(short),
+                _ => arg,
+            }
+        })
+        .mut_subcommands(|subcommand| prefer_short_env(subcommand, is_set))
+}
+
 #[derive(Subcommand)]
 pub enum CargoCommand {
     #[command(name = "ferris-wheel")]
@@ -34,6 +73,23 @@ pub enum CargoCommand {
 pub struct Cli {
     #[command(subcommand)]
     pub command: Commands,
+
+    /// Print how long each phase took (discovery, parsing, graph build,
+    /// detection, reporting); with `--timings=FILE`, also write them as JSON
+    #[arg(
+        long,
+        global = true,
+        value_name = "FILE",
+        num_args = 0..=1,
+        require_equals = true,
+        env = "CARGO_FERRIS_WHEEL_TIMINGS"
+    )]
+    pub timings: Option<Option<PathBuf>>,
+
+    /// Print without colors, even on a terminal; colors are also off when
+    /// output is piped or `NO_COLOR` is set
+    #[arg(long, global = true, env = "CARGO_FERRIS_WHEEL_NO_COLOR")]
+    pub no_color: bool,
 }
 
 #[derive(Subcommand)]
@@ -49,26 +105,181 @@ pub enum Commands {
                       command scans all Cargo.toml files in your workspace, builds a dependency \
                       graph, and uses Tarjan's algorithm to find strongly connected components \
                       (cycles). By default, it checks for cycles between workspaces, but can also \
-                      check for cycles within individual workspaces using --intra-workspace."
+                      check for cycles within individual workspaces using --intra-workspace, or \
+                      between individual crates across the whole repository using --level crate."
     )]
     Inspect {
         #[command(flatten)]
         common: CommonArgs,
 
         #[command(flatten)]
-        format: FormatArgs,
+        format: ReportFormatArgs,
 
         #[command(flatten)]
         cycle_display: CycleDisplayArgs,
 
         /// Exit with error code if cycles found
-        #[arg(long, env = "CARGO_FERRIS_WHEEL_ERROR_ON_CYCLES")]
+        #[arg(
+            long,
+            overrides_with = "no_error_on_cycles",
+            env = "CARGO_FERRIS_WHEEL_ERROR_ON_CYCLES"
+        )]
         error_on_cycles: bool,
 
+        /// Exit successfully despite cycles even if `[defaults]` sets
+        /// `error-on-cycles`
+        #[arg(long, overrides_with = "error_on_cycles")]
+        no_error_on_cycles: bool,
+
+        /// Exit with error code only if more than this many failing cycles
+        /// are found
+        #[arg(
+            long,
+            value_name = "N",
+            conflicts_with_all = ["kind_matrix", "watch", "baseline", "fail_fast"],
+            env = "CARGO_FERRIS_WHEEL_MAX_CYCLES"
+        )]
+        max_cycles: Option<usize>,
+
+        /// Exit with error code only if a failing cycle is more severe than
+        /// this
+        #[arg(
+            long,
+            value_enum,
+            value_name = "LEVEL",
+            conflicts_with_all = ["kind_matrix", "watch", "baseline", "fail_fast"],
+            env = "CARGO_FERRIS_WHEEL_MAX_SEVERITY"
+        )]
+        max_severity: Option<CycleSeverity>,
+
+        /// Fail on every dev-dependency that crosses a workspace boundary,
+        /// whether or not it forms a cycle
+        #[arg(long, env = "CARGO_FERRIS_WHEEL_DENY_CROSS_WORKSPACE_DEV_DEPS")]
+        deny_cross_workspace_dev_deps: bool,
+
         /// Check for cycles within workspaces (intra-workspace) instead of
         /// between workspaces
         #[arg(long, env = "CARGO_FERRIS_WHEEL_INTRA_WORKSPACE")]
         intra_workspace: bool,
+
+        /// Granularity of cycle detection: workspace-to-workspace or
+        /// crate-to-crate (within and across workspaces)
+        #[arg(
+            long,
+            value_enum,
+            default_value = "workspace",
+            conflicts_with = "intra_workspace",
+            env = "CARGO_FERRIS_WHEEL_LEVEL"
+        )]
+        level: CycleLevel,
+
+        /// Trend ledger (JSON Lines) to compare this run against and append
+        /// it to; fails if layer violations grew since the last entry
+        #[arg(long, value_name = "FILE", env = "CARGO_FERRIS_WHEEL_LEDGER")]
+        ledger: Option<PathBuf>,
+
+        /// Only fail on cycles missing from this baseline file, as written by
+        /// --write-baseline
+        #[arg(
+            long,
+            value_name = "FILE",
+            conflicts_with_all = ["write_baseline", "kind_matrix"],
+            env = "CARGO_FERRIS_WHEEL_BASELINE"
+        )]
+        baseline: Option<PathBuf>,
+
+        /// Record the cycles found in this baseline file, so later runs with
+        /// --baseline only fail on new cycles
+        #[arg(long, value_name = "FILE", conflicts_with = "kind_matrix")]
+        write_baseline: Option<PathBuf>,
+
+        /// Stream analysis events as JSON lines to this UNIX socket, named
+        /// pipe or file
+        #[arg(
+            long,
+            value_name = "PATH",
+            conflicts_with = "kind_matrix",
+            env = "CARGO_FERRIS_WHEEL_EVENTS"
+        )]
+        events: Option<PathBuf>,
+
+        /// Add a dependency that does not exist yet and report whether it
+        /// would create a cycle (can be repeated)
+        #[arg(
+            long,
+            value_name = "FROM_CRATE:TO_CRATE",
+            value_delimiter = ',',
+            env = "CARGO_FERRIS_WHEEL_WHAT_IF"
+        )]
+        what_if: Vec<HypotheticalEdge>,
+
+        /// Stop after this long (e.g. 60s, 5m) and report what was analyzed
+        /// so far, marked as partial; exits with code 124
+        #[arg(
+            long,
+            value_name = "DURATION",
+            value_parser = crate::common::parse_duration,
+            env = "CARGO_FERRIS_WHEEL_TIMEOUT"
+        )]
+        timeout: Option<Duration>,
+
+        /// Report cycles separately for normal, normal+dev, normal+build and
+        /// all dependencies, building the graph only once
+        #[arg(
+            long,
+            conflicts_with_all = ["exclude_dev", "exclude_build"],
+            env = "CARGO_FERRIS_WHEEL_KIND_MATRIX"
+        )]
+        kind_matrix: bool,
+
+        /// Keep running and re-check whenever a Cargo.toml or Cargo.lock
+        /// changes, printing what changed since the previous check
+        #[arg(
+            long,
+            conflicts_with_all = ["kind_matrix", "ledger", "write_baseline", "timeout"],
+            env = "CARGO_FERRIS_WHEEL_WATCH"
+        )]
+        watch: bool,
+
+        /// Stop at the first cycle that fails the run and exit with an error,
+        /// skipping the rest of the graph (implies --error-on-cycles)
+        #[arg(
+            long,
+            conflicts_with_all = [
+                "kind_matrix",
+                "watch",
+                "ledger",
+                "baseline",
+                "write_baseline"
+            ],
+            env = "CARGO_FERRIS_WHEEL_FAIL_FAST"
+        )]
+        fail_fast: bool,
+
+        /// Save the workspace graph to this file, so `spectacle --from-cache`
+        /// can render it without analyzing the repository again
+        #[arg(
+            long,
+            value_name = "FILE",
+            conflicts_with_all = ["intra_workspace", "what_if", "kind_matrix", "watch"],
+            env = "CARGO_FERRIS_WHEEL_SAVE_GRAPH"
+        )]
+        save_graph: Option<PathBuf>,
+
+        /// Render the report with this Tera template instead of --format;
+        /// the template sees the JSON report (see `schema report`)
+        #[arg(
+            long,
+            value_name = "FILE",
+            conflicts_with_all = ["output_dir", "kind_matrix", "watch"],
+            env = "CARGO_FERRIS_WHEEL_TEMPLATE"
+        )]
+        template: Option<PathBuf>,
+
+        /// Also warn about suspicious manifest constructs, such as a
+        /// dependency setting both `path` and `git`
+        #[arg(long, env = "CARGO_FERRIS_WHEEL_MANIFEST_WARNINGS")]
+        manifest_warnings: bool,
     },
 
     /// Create a spectacular visualization of your dependency carnival
@@ -97,9 +308,8 @@ pub enum Commands {
         )]
         format: GraphFormat,
 
-        /// Output file (stdout if not specified)
-        #[arg(short, long, env = "CARGO_FERRIS_WHEEL_OUTPUT")]
-        output: Option<PathBuf>,
+        #[command(flatten)]
+        output: OutputArgs,
 
         /// Highlight cycles in the graph
         #[arg(
@@ -112,6 +322,92 @@ pub enum Commands {
         /// Include crate-level details
         #[arg(long, env = "CARGO_FERRIS_WHEEL_SHOW_CRATES")]
         show_crates: bool,
+
+        /// Layout direction for Mermaid diagrams
+        #[arg(
+            long,
+            value_enum,
+            default_value = "td",
+            env = "CARGO_FERRIS_WHEEL_MERMAID_DIRECTION"
+        )]
+        direction: MermaidDirection,
+
+        /// Deprecated: use --theme, whose `dark` preset and `mermaid` key
+        /// set the Mermaid theme
+        #[arg(
+            long,
+            value_enum,
+            hide = true,
+            env = "CARGO_FERRIS_WHEEL_MERMAID_THEME"
+        )]
+        mermaid_theme: Option<MermaidTheme>,
+
+        /// Edge label verbosity for Mermaid, DOT and D2 graphs (follows
+        /// --show-crates if unset)
+        #[arg(long, value_enum, env = "CARGO_FERRIS_WHEEL_EDGE_LABELS")]
+        edge_labels: Option<EdgeLabels>,
+
+        /// Nest workspaces inside the meta workspaces that contain them
+        /// (Mermaid and DOT; replaces name-prefix grouping)
+        #[arg(long, env = "CARGO_FERRIS_WHEEL_NEST_WORKSPACES")]
+        nest_workspaces: bool,
+
+        /// Group workspaces into Mermaid subgraphs and DOT clusters by name
+        /// `prefix` (Mermaid's default), `directory`, `layer` from the config
+        /// file, or `regex:<PATTERN>` (first capture group)
+        #[arg(
+            long,
+            value_name = "STRATEGY",
+            conflicts_with = "nest_workspaces",
+            env = "CARGO_FERRIS_WHEEL_GROUP_BY"
+        )]
+        group_by: Option<WorkspaceGrouping>,
+
+        /// Draw each workspace as a Mermaid subgraph of its crates, connected
+        /// by crate-to-crate dependencies
+        #[arg(
+            long,
+            conflicts_with_all = ["nest_workspaces", "group_by"],
+            env = "CARGO_FERRIS_WHEEL_CRATE_SUBGRAPHS"
+        )]
+        crate_subgraphs: bool,
+
+        /// Color palette for Mermaid, DOT and D2 graphs, and Mermaid's own
+        /// theme: `light`, `dark`, `colorblind`, or a TOML file shaped like
+        /// the `[theme]` config section (the config file's `[theme]`, then
+        /// `light`, if unset)
+        #[arg(long, value_name = "THEME", env = "CARGO_FERRIS_WHEEL_THEME")]
+        theme: Option<ThemeChoice>,
+
+        /// Collapse each cycle into a single node, rendering the acyclic
+        /// condensation graph
+        #[arg(long, env = "CARGO_FERRIS_WHEEL_CONDENSED")]
+        condensed: bool,
+
+        /// Leave out every workspace and dependency that is not part of a
+        /// cycle
+        #[arg(long, env = "CARGO_FERRIS_WHEEL_CYCLES_ONLY")]
+        cycles_only: bool,
+
+        /// Only draw the workspaces within --depth hops of this workspace,
+        /// following dependencies in both directions
+        #[arg(long, value_name = "WORKSPACE", env = "CARGO_FERRIS_WHEEL_FOCUS")]
+        focus: Option<String>,
+
+        /// Number of hops around the --focus workspace to draw
+        #[arg(
+            long,
+            value_name = "N",
+            default_value = "1",
+            requires = "focus",
+            env = "CARGO_FERRIS_WHEEL_DEPTH"
+        )]
+        depth: usize,
+
+        /// Render the graph saved in this file by `inspect --save-graph`,
+        /// rebuilding and saving it again if it is missing or out of date
+        #[arg(long, value_name = "FILE", env = "CARGO_FERRIS_WHEEL_FROM_CACHE")]
+        from_cache: Option<PathBuf>,
     },
 
     /// Put a spotlight on cycles involving a specific crate
@@ -183,6 +479,379 @@ pub enum Commands {
         format: FormatArgs,
     },
 
+    /// Trace the route from one ride to another
+    ///
+    /// Prints the shortest dependency chains from one workspace to another,
+    /// with the crate dependencies used at each hop.
+    #[command(
+        long_about = "Find the shortest dependency chains from one workspace to another. Each hop \
+                      lists the crate-level dependencies that connect the two workspaces, which \
+                      helps explain how an unexpected transitive dependency arises. When several \
+                      chains share the shortest length, all of them are shown up to --max-paths."
+    )]
+    Path {
+        /// Workspace the chain starts from
+        #[arg(
+            long,
+            value_name = "WORKSPACE_NAME",
+            env = "CARGO_FERRIS_WHEEL_PATH_FROM"
+        )]
+        from: String,
+
+        /// Workspace the chain ends at
+        #[arg(
+            long,
+            value_name = "WORKSPACE_NAME",
+            env = "CARGO_FERRIS_WHEEL_PATH_TO"
+        )]
+        to: String,
+
+        /// Maximum number of shortest chains to show
+        #[arg(long, default_value_t = 10, env = "CARGO_FERRIS_WHEEL_MAX_PATHS")]
+        max_paths: usize,
+
+        #[command(flatten)]
+        common: CommonArgs,
+
+        #[command(flatten)]
+        format: FormatArgs,
+    },
+
+    /// Ask why one ride is hooked up to another
+    ///
+    /// Prints the shortest crate-to-crate dependency chain from one workspace
+    /// to another.
+    #[command(
+        long_about = "Explain how one workspace ends up depending on another. Every crate is \
+                      treated as its own node, so the chain shows exactly which crates link the \
+                      two workspaces, including hops through crates of intermediate workspaces \
+                      and crates within the same workspace."
+    )]
+    Why {
+        /// Workspace the chain starts from
+        #[arg(value_name = "FROM", env = "CARGO_FERRIS_WHEEL_WHY_FROM")]
+        from: String,
+
+        /// Workspace the chain ends at
+        #[arg(value_name = "TO", env = "CARGO_FERRIS_WHEEL_WHY_TO")]
+        to: String,
+
+        #[command(flatten)]
+        common: CommonArgs,
+
+        #[command(flatten)]
+        format: FormatArgs,
+    },
+
+    /// See which rides share a track between two stations
+    ///
+    /// Lists every crate-level dependency from one workspace into another,
+    /// the dependencies in the opposite direction, and a summary of both.
+    #[command(
+        long_about = "List every crate-level dependency from crates of one workspace on crates of \
+                      another, with dependency kinds, enabled features and the manifest line \
+                      declaring each one. Dependencies in the opposite direction are listed as \
+                      well, followed by a summary of both directions, for reviewing the boundary \
+                      between two workspaces."
+    )]
+    Between {
+        /// Workspace whose dependencies are listed
+        #[arg(
+            long,
+            value_name = "WORKSPACE",
+            env = "CARGO_FERRIS_WHEEL_BETWEEN_FROM"
+        )]
+        from: String,
+
+        /// Workspace the listed dependencies point into
+        #[arg(long, value_name = "WORKSPACE", env = "CARGO_FERRIS_WHEEL_BETWEEN_TO")]
+        to: String,
+
+        #[command(flatten)]
+        common: CommonArgs,
+
+        #[command(flatten)]
+        format: FormatArgs,
+    },
+
+    /// Follow every track from one car to another
+    ///
+    /// Lists the crate-level dependency chains from one crate to another,
+    /// shortest first, with the dependency kinds of each hop.
+    #[command(
+        long_about = "List the dependency chains from one crate to another at crate level, \
+                      shortest first, annotating each hop with its dependency kinds. Chains never \
+                      visit a crate twice. Crates may be named as CRATE or WORKSPACE/CRATE when \
+                      several workspaces hold a crate of the same name. Shows the --max-paths \
+                      shortest chains, or every chain with --all."
+    )]
+    Chain {
+        /// Crate the chains start from
+        #[arg(long, value_name = "CRATE", env = "CARGO_FERRIS_WHEEL_CHAIN_FROM")]
+        from: String,
+
+        /// Crate the chains end at
+        #[arg(long, value_name = "CRATE", env = "CARGO_FERRIS_WHEEL_CHAIN_TO")]
+        to: String,
+
+        /// Maximum number of chains to show, shortest first
+        #[arg(
+            long,
+            default_value_t = 10,
+            conflicts_with = "all",
+            env = "CARGO_FERRIS_WHEEL_MAX_PATHS"
+        )]
+        max_paths: usize,
+
+        /// Show every chain instead of only the shortest ones
+        #[arg(long)]
+        all: bool,
+
+        #[command(flatten)]
+        common: CommonArgs,
+
+        #[command(flatten)]
+        format: FormatArgs,
+    },
+
+    /// Keep every rider on their own level of the carousel
+    ///
+    /// Checks workspace dependencies against the declared architecture layers
+    /// and fails when any of them points to a higher layer.
+    #[command(
+        long_about = "Check every workspace dependency against the layers declared in the \
+                      [layers] section of .ferris-wheel.toml, lowest first. A workspace may \
+                      depend on its own layer or any layer below it; each dependency pointing to \
+                      a higher layer is listed with the crate edges behind it and fails the \
+                      command. Pass --layers to check an ad-hoc chain such as 'core < services-* \
+                      < app-*' instead, where each layer holds the workspaces matching its name."
+    )]
+    Carousel {
+        /// Layer chain to enforce instead of the configured layers, lowest
+        /// first (e.g. 'core < services-* < app-*')
+        #[arg(long, value_name = "CHAIN", env = "CARGO_FERRIS_WHEEL_LAYERS")]
+        layers: Option<String>,
+
+        #[command(flatten)]
+        common: CommonArgs,
+
+        #[command(flatten)]
+        format: FormatArgs,
+    },
+
+    /// Sweep up the tickets nobody rode with
+    ///
+    /// Lists internal dependencies declared in Cargo.toml that the crate's
+    /// sources never reference.
+    #[command(
+        long_about = "Find dependencies on crates of the analyzed workspaces that are declared in \
+                      a crate's Cargo.toml but never referenced in its sources. A dependency \
+                      counts as used when its name starts a path (dep::item), or appears in a use \
+                      or extern crate item, in any .rs file of the crate. Stale dependencies are \
+                      a leading cause of accidental cycles; the command fails when it finds any."
+    )]
+    Sweep {
+        #[command(flatten)]
+        common: CommonArgs,
+
+        #[command(flatten)]
+        format: FormatArgs,
+    },
+
+    /// Read the scoreboard of every ride
+    ///
+    /// Computes fan-in, fan-out, instability, dependency depth and cycle size
+    /// for each workspace.
+    #[command(
+        long_about = "Compute architectural health metrics for every workspace of the dependency \
+                      graph: fan-in (workspaces depending on it), fan-out (workspaces it depends \
+                      on), instability (fan-out / (fan-in + fan-out)), depth (longest chain of \
+                      workspace dependencies below it) and the size of the strongly connected \
+                      component it belongs to. Outputs a table, JSON or CSV for tracking over \
+                      time."
+    )]
+    Stats {
+        #[command(flatten)]
+        common: CommonArgs,
+
+        /// Output format
+        #[arg(
+            short,
+            long,
+            value_enum,
+            default_value = crate::constants::output::DEFAULT_FORMAT,
+            env = "CARGO_FERRIS_WHEEL_FORMAT"
+        )]
+        format: StatsFormat,
+
+        #[command(flatten)]
+        output: OutputArgs,
+    },
+
+    /// Write up the changes between two shows
+    ///
+    /// Summarizes structural changes between two git revisions (new and
+    /// removed workspaces, added and removed cross-workspace dependencies,
+    /// cycles introduced and fixed) as Markdown for release notes.
+    #[command(
+        long_about = "Compare the workspace dependency structure at two git revisions and \
+                      summarize the differences as Markdown. Each revision is checked out into a \
+                      temporary worktree and analyzed like the inspect command. Omit --to to \
+                      compare against the current working tree."
+    )]
+    Changelog {
+        /// Base revision (tag, branch or commit)
+        #[arg(long, value_name = "REV", env = "CARGO_FERRIS_WHEEL_FROM")]
+        from: String,
+
+        /// Revision to compare against (defaults to the working tree)
+        #[arg(long, value_name = "REV", env = "CARGO_FERRIS_WHEEL_TO")]
+        to: Option<String>,
+
+        #[command(flatten)]
+        common: CommonArgs,
+
+        #[command(flatten)]
+        output: OutputArgs,
+    },
+
+    /// Compare two shows ride by ride
+    ///
+    /// Reports the cycles introduced and fixed, and the cross-workspace
+    /// dependencies added and removed, between two directories or git
+    /// revisions. Fails when a cycle was introduced.
+    #[command(
+        long_about = "Compare dependency cycles between two analyses and fail when the newer one \
+                      introduces a cycle, the building block for \"no new cycles\" CI gates. Each \
+                      side is a directory, analyzed as a whole, or a git revision, checked out \
+                      into a temporary worktree and analyzed at the given paths. Omit --to to \
+                      compare against the current working tree. Added and removed cross-workspace \
+                      dependencies are listed as well."
+    )]
+    Diff {
+        /// Baseline: a directory, or a git revision (tag, branch or commit)
+        #[arg(long, value_name = "DIR|REV", env = "CARGO_FERRIS_WHEEL_DIFF_FROM")]
+        from: String,
+
+        /// Directory or revision to compare against the baseline (defaults to
+        /// the working tree)
+        #[arg(long, value_name = "DIR|REV", env = "CARGO_FERRIS_WHEEL_DIFF_TO")]
+        to: Option<String>,
+
+        #[command(flatten)]
+        common: CommonArgs,
+
+        #[command(flatten)]
+        format: FormatArgs,
+    },
+
+    /// Take a ride through the fairground
+    ///
+    /// Opens an interactive terminal explorer for browsing workspaces, their
+    /// dependencies and the cycles between them.
+    #[command(
+        long_about = "Explore the workspace dependency graph interactively. Browse workspaces, \
+                      expand their cross-workspace dependencies, jump from a workspace into the \
+                      cycles it belongs to, and toggle dev, build and target-specific \
+                      dependencies on and off to see how the cycles change. The exclusion flags \
+                      set the initial filters."
+    )]
+    Tui {
+        #[command(flatten)]
+        common: CommonArgs,
+    },
+
+    /// Call in the mechanic
+    ///
+    /// Turns the suggested break points of every cycle into concrete
+    /// Cargo.toml edits and prints them as patches.
+    #[command(
+        long_about = "Suggest concrete manifest edits that break every dependency cycle. For each \
+                      suggested break point, the Cargo.toml declarations behind it are removed, \
+                      moved to [dev-dependencies] or made optional behind a feature, depending on \
+                      the strategy. The edits are printed as unified diffs that `git apply` \
+                      accepts, and can be written to one patch file per cycle. Nothing is changed \
+                      on disk unless --write-patches is given, and even then only patch files are \
+                      written."
+    )]
+    Fix {
+        /// How to break each dependency behind a break point
+        #[arg(
+            long,
+            value_enum,
+            default_value = "auto",
+            env = "CARGO_FERRIS_WHEEL_FIX_STRATEGY"
+        )]
+        strategy: FixStrategy,
+
+        /// Write one patch file per cycle into this directory
+        #[arg(long, value_name = "DIR")]
+        write_patches: Option<PathBuf>,
+
+        #[command(flatten)]
+        common: CommonArgs,
+
+        /// Output format
+        #[arg(
+            short,
+            long,
+            value_enum,
+            default_value = crate::constants::output::DEFAULT_FORMAT,
+            env = "CARGO_FERRIS_WHEEL_FORMAT"
+        )]
+        format: FixFormat,
+
+        #[command(flatten)]
+        output: OutputArgs,
+    },
+
+    /// Teach your shell the names of the rides
+    ///
+    /// Prints a completion script for the given shell, covering every
+    /// subcommand, flag and value.
+    #[command(
+        long_about = "Print a shell completion script to stdout. The script completes the \
+                      subcommands, flags and flag values of `cargo-ferris-wheel ferris-wheel`. \
+                      Save it wherever your shell loads completions from, for example \
+                      ~/.local/share/bash-completion/completions/cargo-ferris-wheel for bash."
+    )]
+    Completions {
+        /// Shell to generate completions for
+        #[arg(value_enum)]
+        shell: Shell,
+    },
+
+    /// Print the program for every ride
+    ///
+    /// Generates man pages for ferris-wheel and each of its subcommands.
+    #[command(
+        long_about = "Generate man pages in roff format. Without --out-dir, the page for \
+                      cargo-ferris-wheel itself is printed to stdout. With --out-dir, one page \
+                      per subcommand is written as well, named like cargo-ferris-wheel-inspect.1, \
+                      ready to be installed into a man1 directory."
+    )]
+    Manpages {
+        /// Write the pages for ferris-wheel and every subcommand into this
+        /// directory
+        #[arg(long, value_name = "DIR")]
+        out_dir: Option<PathBuf>,
+    },
+
+    /// Print the rulebook for every souvenir
+    ///
+    /// Emits the JSON Schema of a JSON report or of the configuration file.
+    #[command(
+        long_about = "Print a JSON Schema document describing the JSON report of inspect, the \
+                      JSON report of ripples, or the .ferris-wheel.toml configuration file. \
+                      Downstream tooling can validate output against it or generate typed \
+                      clients, and editors can use the config schema for completion."
+    )]
+    Schema {
+        /// Document to describe
+        #[arg(value_enum)]
+        document: SchemaDocument,
+    },
+
     /// Discover the ripple effects from changed files
     ///
     /// Analyzes which workspaces and crates are affected by changes to specific
@@ -198,13 +867,44 @@ pub enum Commands {
     Ripples {
         /// List of changed files
         #[arg(
-            required = true,
+            required_unless_present_any = ["since", "merge_base", "files_from"],
             value_name = "FILES",
             help = "Files that have changed",
             env = "CARGO_FERRIS_WHEEL_FILES"
         )]
         files: Vec<String>,
 
+        /// Ask git for the changed files instead: everything changed since
+        /// this revision, including uncommitted and untracked files
+        #[arg(
+            long,
+            value_name = "REV",
+            conflicts_with_all = ["files", "merge_base"],
+            env = "CARGO_FERRIS_WHEEL_SINCE"
+        )]
+        since: Option<String>,
+
+        /// Like --since, starting from the merge base of this revision and
+        /// HEAD, so only the changes of the current branch count
+        #[arg(
+            long,
+            value_name = "REV",
+            conflicts_with = "files",
+            env = "CARGO_FERRIS_WHEEL_MERGE_BASE"
+        )]
+        merge_base: Option<String>,
+
+        /// Read the changed files from this file, or from stdin with `-`,
+        /// one per line or NUL-delimited as `git diff -z --name-only` prints
+        /// them
+        #[arg(
+            long = "files",
+            value_name = "PATH",
+            conflicts_with_all = ["files", "since", "merge_base"],
+            env = "CARGO_FERRIS_WHEEL_FILES_FROM"
+        )]
+        files_from: Option<PathBuf>,
+
         /// Include crate-level information in output
         #[arg(long)]
         show_crates: bool,
@@ -213,20 +913,144 @@ pub enum Commands {
         #[arg(long, env = "CARGO_FERRIS_WHEEL_DIRECT_ONLY")]
         direct_only: bool,
 
+        /// When a workspace root Cargo.toml only changed entries of
+        /// `[workspace.dependencies]`, affect just the members inheriting
+        /// them instead of the whole workspace
+        ///
+        /// The manifest is compared with the revision given to --since or
+        /// --merge-base, or with HEAD.
+        #[arg(long, env = "CARGO_FERRIS_WHEEL_NARROW_WORKSPACE_MANIFESTS")]
+        narrow_workspace_manifests: bool,
+
+        /// Follow reverse dependencies at most this many steps: 0 keeps only
+        /// the crates containing the changed files, 1 adds their direct
+        /// dependents
+        #[arg(
+            long,
+            value_name = "N",
+            conflicts_with = "direct_only",
+            env = "CARGO_FERRIS_WHEEL_MAX_DEPTH"
+        )]
+        max_depth: Option<usize>,
+
+        /// Cargo subcommand the `cargo-commands` format runs, e.g. `clippy`
+        /// or `nextest run`
+        #[arg(
+            long,
+            value_name = "SUBCOMMAND",
+            default_value = "test",
+            env = "CARGO_FERRIS_WHEEL_CARGO_SUBCOMMAND"
+        )]
+        cargo_subcommand: String,
+
         /// Exclude dev-dependencies from analysis
-        #[arg(long, env = "CARGO_FERRIS_WHEEL_EXCLUDE_DEV")]
+        #[arg(
+            long,
+            overrides_with = "no_exclude_dev",
+            env = "CARGO_FERRIS_WHEEL_EXCLUDE_DEV"
+        )]
         exclude_dev: bool,
 
+        /// Keep dev-dependencies from analysis even if `[defaults]` excludes
+        /// them
+        #[arg(long, overrides_with = "exclude_dev")]
+        no_exclude_dev: bool,
+
         /// Exclude build-dependencies from analysis
-        #[arg(long, env = "CARGO_FERRIS_WHEEL_EXCLUDE_BUILD")]
+        #[arg(
+            long,
+            overrides_with = "no_exclude_build",
+            env = "CARGO_FERRIS_WHEEL_EXCLUDE_BUILD"
+        )]
         exclude_build: bool,
 
+        /// Keep build-dependencies from analysis even if `[defaults]` excludes
+        /// them
+        #[arg(long, overrides_with = "exclude_build")]
+        no_exclude_build: bool,
+
         /// Exclude target-specific dependencies
-        #[arg(long, env = "CARGO_FERRIS_WHEEL_EXCLUDE_TARGET")]
+        #[arg(
+            long,
+            overrides_with = "no_exclude_target",
+            env = "CARGO_FERRIS_WHEEL_EXCLUDE_TARGET"
+        )]
         exclude_target: bool,
 
+        /// Keep target-specific dependencies even if `[defaults]` excludes them
+        #[arg(long, overrides_with = "exclude_target")]
+        no_exclude_target: bool,
+
+        /// Evaluate `[target.'cfg(...)']` dependencies for this target triple,
+        /// keeping only those that apply to it
+        #[arg(
+            long,
+            value_name = "TRIPLE",
+            conflicts_with = "exclude_target",
+            env = "CARGO_FERRIS_WHEEL_TARGET"
+        )]
+        target: Option<crate::analyzer::TargetPlatform>,
+
+        /// Abort with a diagnostic once discovery finds more than this many
+        /// workspaces, usually a sign it reached vendored sources or fixtures
+        #[arg(long, value_name = "N", env = "CARGO_FERRIS_WHEEL_MAX_WORKSPACES")]
+        max_workspaces: Option<usize>,
+
+        /// Skip directories matching this glob during discovery, relative to
+        /// the current directory (repeatable, e.g. `vendor` or `**/fixtures`)
+        #[arg(
+            long = "exclude-path",
+            value_name = "GLOB",
+            value_delimiter = ',',
+            env = "CARGO_FERRIS_WHEEL_EXCLUDE_PATH"
+        )]
+        exclude_paths: Vec<glob::Pattern>,
+
+        /// Leave crates whose name matches this glob out of the analysis, as if
+        /// they did not exist (repeatable, e.g. `*-fixtures` or `example-*`)
+        #[arg(
+            long = "exclude-crate",
+            value_name = "GLOB",
+            value_delimiter = ',',
+            env = "CARGO_FERRIS_WHEEL_EXCLUDE_CRATE"
+        )]
+        exclude_crates: Vec<glob::Pattern>,
+
+        /// Descend into symlinked directories during discovery; workspaces
+        /// reachable through several paths are still analyzed once
+        #[arg(long, env = "CARGO_FERRIS_WHEEL_FOLLOW_SYMLINKS")]
+        follow_symlinks: bool,
+
+        /// Analyze every workspace from scratch, without reading or updating
+        /// the analysis cache
+        #[arg(long, overrides_with = "cache", env = "CARGO_FERRIS_WHEEL_NO_CACHE")]
+        no_cache: bool,
+
+        /// Use the analysis cache even if `[defaults]` turns it off
+        #[arg(long, overrides_with = "no_cache")]
+        cache: bool,
+
+        /// Profile from .ferris-wheel.toml to apply (auto-detected from the
+        /// current git branch if not specified)
+        #[arg(long, value_name = "NAME", env = "CARGO_FERRIS_WHEEL_PROFILE")]
+        profile: Option<String>,
+
+        /// Output format
+        #[arg(
+            short,
+            long,
+            value_enum,
+            default_value = crate::constants::output::DEFAULT_FORMAT,
+            env = "CARGO_FERRIS_WHEEL_FORMAT"
+        )]
+        format: RipplesFormat,
+
         #[command(flatten)]
-        format: FormatArgs,
+        output: OutputArgs,
+
+        /// Workspace display names from the project configuration
+        #[arg(skip)]
+        workspace_names: crate::analyzer::WorkspaceNames,
     },
 }
 
@@ -239,10 +1063,301 @@ pub enum OutputFormat {
     GitHub,
 }
 
+/// Output formats of the `ripples` command
+#[derive(Clone, Copy, Debug, PartialEq, clap::ValueEnum)]
+pub enum RipplesFormat {
+    Human,
+    Json,
+    Junit,
+    #[value(name = "github")]
+    GitHub,
+    /// Deduplicated `-p <crate>` arguments for cargo, split across lines so
+    /// each line stays within OS argument limits
+    CargoArgs,
+    /// Ready-to-run cargo invocations, one per workspace, split like
+    /// `cargo-args`
+    CargoCommands,
+    /// One cargo package spec per line
+    PackageSpecs,
+    /// A GitHub Actions job matrix with one entry per affected workspace
+    #[value(name = "github-matrix")]
+    GitHubMatrix,
+    /// An indented tree from each changed crate through its dependents
+    Tree,
+}
+
+/// Output formats of the `stats` command
+#[derive(Clone, Copy, Debug, PartialEq, clap::ValueEnum)]
+pub enum StatsFormat {
+    Human,
+    Json,
+    /// One row per workspace with a header line
+    Csv,
+}
+
+/// Output formats of the `fix` command
+#[derive(Clone, Copy, Debug, PartialEq, clap::ValueEnum)]
+pub enum FixFormat {
+    Human,
+    Json,
+    /// Only the unified diffs, ready to pipe into `git apply`
+    Patch,
+}
+
+/// How the `fix` command breaks a dependency
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum FixStrategy {
+    /// Remove dev dependencies; move normal dependencies to
+    /// [dev-dependencies] when dev dependencies are excluded from the
+    /// analysis, and feature-gate the rest
+    #[default]
+    Auto,
+    /// Remove every dependency
+    Remove,
+    /// Move normal dependencies to [dev-dependencies] and remove the rest
+    Dev,
+    /// Make normal and build dependencies optional behind a feature, and
+    /// remove dev dependencies, which cannot be optional
+    FeatureGate,
+}
+
+/// Granularity of the graph used for cycle detection
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum CycleLevel {
+    /// One node per workspace
+    #[default]
+    Workspace,
+    /// One node per crate, with edges within and across workspaces
+    Crate,
+}
+
 #[derive(Clone, Copy, Debug, clap::ValueEnum)]
 pub enum GraphFormat {
     Ascii,
     Mermaid,
     Dot,
     D2,
+    /// Canonical adjacency JSON for storing in-repo and diffing
+    Adjacency,
+    /// Self-contained HTML page that animates each cycle step by step,
+    /// showing why no valid build order exists
+    Html,
+    /// PNG image laid out by Graphviz `dot` or `d2`, whichever is installed
+    Png,
+    /// PDF document laid out by Graphviz `dot` or `d2`, whichever is installed
+    Pdf,
+}
+
+/// Layout direction of a Mermaid flowchart
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum MermaidDirection {
+    /// Top to bottom
+    #[default]
+    #[value(name = "td")]
+    TopDown,
+    /// Left to right
+    #[value(name = "lr")]
+    LeftRight,
+    /// Bottom to top
+    #[value(name = "bt")]
+    BottomTop,
+    /// Right to left
+    #[value(name = "rl")]
+    RightLeft,
+}
+
+impl MermaidDirection {
+    /// The direction keyword used in a Mermaid `graph` declaration
+    pub fn as_mermaid(&self) -> &'static str {
+        match self {
+            MermaidDirection::TopDown => "TD",
+            MermaidDirection::LeftRight => "LR",
+            MermaidDirection::BottomTop => "BT",
+            MermaidDirection::RightLeft => "RL",
+        }
+    }
+}
+
+/// Documents the `schema` command describes
+#[derive(Clone, Copy, Debug, PartialEq, Eq, clap::ValueEnum)]
+pub enum SchemaDocument {
+    /// The JSON report of `inspect`
+    Report,
+    /// The JSON report of `ripples`
+    Ripples,
+    /// The `.ferris-wheel.toml` configuration file
+    Config,
+}
+
+/// Built-in Mermaid themes
+#[derive(
+    Clone, Copy, Debug, PartialEq, Eq, clap::ValueEnum, serde::Deserialize, schemars::JsonSchema,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum MermaidTheme {
+    Default,
+    Neutral,
+    Dark,
+    Forest,
+    Base,
+}
+
+impl MermaidTheme {
+    /// The theme name understood by Mermaid's init directive
+    pub fn as_mermaid(&self) -> &'static str {
+        match self {
+            MermaidTheme::Default => "default",
+            MermaidTheme::Neutral => "neutral",
+            MermaidTheme::Dark => "dark",
+            MermaidTheme::Forest => "forest",
+            MermaidTheme::Base => "base",
+        }
+    }
+}
+
+/// How much detail to put on graph edge labels
+#[derive(Clone, Copy, Debug, PartialEq, Eq, clap::ValueEnum)]
+pub enum EdgeLabels {
+    /// No edge labels
+    None,
+    /// Dependency type icon and edge count
+    Compact,
+    /// Crate-to-crate pairs
+    Full,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn env_of(command: &Command, path: &[&str], id: &str) -> Option<String> {
+        let command = path.iter().fold(command, |command, name| {
+            command.find_subcommand(name).unwrap()
+        });
+        command
+            .get_arguments()
+            .find(|arg| arg.get_id() == id)
+            .and_then(|arg| arg.get_env())
+            .map(|env| env.to_string_lossy().into_owned())
+    }
+
+    #[test]
+    fn test_short_env_prefix_is_used_when_long_one_is_unset() {
+        let set = [
+            "FERRIS_WHEEL_FORMAT",
+            "FERRIS_WHEEL_EXCLUDE_DEV",
+            "CARGO_FERRIS_WHEEL_EXCLUDE_DEV",
+        ];
+        let command = prefer_short_env(CargoArgs::command(), &|name| set.contains(&name));
+        let inspect = ["ferris-wheel", "inspect"];
+
+        assert_eq!(
+            env_of(&command, &inspect, "format").as_deref(),
+            Some("FERRIS_WHEEL_FORMAT")
+        );
+        // The long prefix wins when both are set
+        assert_eq!(
+            env_of(&command, &inspect, "exclude_dev").as_deref(),
+            Some("CARGO_FERRIS_WHEEL_EXCLUDE_DEV")
+        );
+        assert_eq!(
+            env_of(&command, &inspect, "exclude_build").as_deref(),
+            Some("CARGO_FERRIS_WHEEL_EXCLUDE_BUILD")
+        );
+    }
+
+    #[test]
+    fn test_no_color_is_accepted_after_the_command() {
+        let no_color = |args: &[&str]| {
+            let CargoCommand::FerrisWheel(cli) = CargoArgs::try_parse_from(args).unwrap().command;
+            cli.no_color
+        };
+
+        assert!(no_color(&[
+            "cargo",
+            "ferris-wheel",
+            "inspect",
+            "--no-color"
+        ]));
+        assert!(no_color(&[
+            "cargo",
+            "ferris-wheel",
+            "--no-color",
+            "inspect"
+        ]));
+        assert!(!no_color(&["cargo", "ferris-wheel", "inspect"]));
+    }
+
+    #[test]
+    fn test_report_commands_take_output() {
+        let output = |args: &[&str]| {
+            let CargoCommand::FerrisWheel(cli) = CargoArgs::try_parse_from(args).unwrap().command;
+            match cli.command {
+                Commands::Why { format, .. } => format.output.output,
+                Commands::Stats { output, .. }
+                | Commands::Fix { output, .. }
+                | Commands::Changelog { output, .. } => output.output,
+                _ => unreachable!(),
+            }
+        };
+
+        for args in [
+            &["why", "a", "b"][..],
+            &["stats"],
+            &["fix"],
+            &["changelog", "--from", "main"],
+        ] {
+            let args = [&["cargo", "ferris-wheel"], args, &["-o", "report.txt"]].concat();
+            assert_eq!(output(&args), Some(PathBuf::from("report.txt")));
+        }
+    }
+
+    #[test]
+    fn test_inspect_takes_several_formats() {
+        let formats = |args: &[&str]| {
+            let CargoCommand::FerrisWheel(cli) = CargoArgs::try_parse_from(args).unwrap().command;
+            let Commands::Inspect { format, .. } = cli.command else {
+                unreachable!()
+            };
+            (format.format, format.output_dir)
+        };
+
+        assert_eq!(
+            formats(&[
+                "cargo",
+                "ferris-wheel",
+                "inspect",
+                "--format",
+                "json",
+                "--format",
+                "junit",
+                "--output-dir",
+                "reports",
+            ]),
+            (
+                vec![OutputFormat::Json, OutputFormat::Junit],
+                Some(PathBuf::from("reports"))
+            )
+        );
+        assert_eq!(
+            formats(&["cargo", "ferris-wheel", "inspect", "-f", "human,github"]).0,
+            [OutputFormat::Human, OutputFormat::GitHub]
+        );
+        assert_eq!(
+            formats(&["cargo", "ferris-wheel", "inspect"]).0,
+            [OutputFormat::Human]
+        );
+        assert!(
+            CargoArgs::try_parse_from([
+                "cargo",
+                "ferris-wheel",
+                "inspect",
+                "--output-dir",
+                "reports",
+                "--output",
+                "report.txt",
+            ])
+            .is_err()
+        );
+    }
 }
@@ -1,52 +1,87 @@
 //! Ripples command implementation
 
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::io::Read;
 use std::path::{Path, PathBuf};
+use std::sync::Arc;
 
 use miette::{Result, WrapErr};
 use petgraph::graph::{DiGraph, NodeIndex};
 use petgraph::visit::EdgeRef;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 use crate::analyzer::{CratePathToWorkspaceMap, Dependency, WorkspaceInfo};
 use crate::cli::Commands;
-use crate::common::FromCommand;
-use crate::config::AffectedConfig;
+use crate::common::{ConfigBuilder, FromCommand};
+use crate::config::{AffectedConfig, ChangedSince, ProjectConfig};
 use crate::dependency_filter::DependencyFilter;
 use crate::error::FerrisWheelError;
+use crate::git;
+use crate::graph::{DependencyEdge, DependencyType};
+use crate::timings::{Phase, Timer};
+use crate::utils::canonical::{canonicalize, canonicalize_or_self};
 
 /// JSON output structure for affected analysis
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
 pub struct AffectedJsonReport {
     pub affected_crates: Vec<AffectedCrate>,
     pub affected_workspaces: Vec<AffectedWorkspace>,
     pub directly_affected_crates: Vec<String>,
     pub directly_affected_workspaces: Vec<AffectedWorkspace>,
+    /// Crates that need to be rebuilt, reached through normal and build
+    /// dependencies only
+    #[serde(default)]
+    pub affected_for_build: Vec<String>,
+    /// Crates whose tests need to run again: `affected_for_build` plus the
+    /// crates dev-depending on them
+    #[serde(default)]
+    pub affected_for_tests: Vec<String>,
 }
 
-#[derive(Debug, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Serialize, Deserialize, PartialEq, JsonSchema)]
 pub struct AffectedWorkspace {
     pub name: String,
     pub path: String,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
 pub struct AffectedCrate {
     pub name: String,
     pub workspace: String,
     pub is_directly_affected: bool,
     pub is_standalone: bool,
+    /// How the crate came to be affected: a directly changed crate, then
+    /// each crate depending on the one before, ending with this crate
+    #[serde(default)]
+    pub chain: Vec<String>,
+}
+
+/// GitHub Actions job matrix with one entry per affected workspace
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub struct GitHubMatrix {
+    pub include: Vec<GitHubMatrixEntry>,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub struct GitHubMatrixEntry {
+    pub workspace: String,
+    /// Workspace directory, relative to the directory ripples ran in
+    pub path: String,
 }
 
 #[derive(Clone, Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
 pub(crate) struct CrateId {
-    name: String,
+    name: Arc<str>,
     path: PathBuf,
 }
 
 impl CrateId {
-    fn new(name: String, path: PathBuf) -> Self {
-        Self { name, path }
+    fn new(name: &str, path: PathBuf) -> Self {
+        Self {
+            name: Arc::from(name),
+            path,
+        }
     }
 
     pub fn name(&self) -> &str {
@@ -59,27 +94,66 @@ impl CrateId {
 }
 
 impl FromCommand for AffectedConfig {
-    fn from_command(command: Commands) -> Result<Self, FerrisWheelError> {
+    fn from_command(
+        command: Commands,
+        _project: Option<&ProjectConfig>,
+    ) -> Result<Self, FerrisWheelError> {
         match command {
             Commands::Ripples {
                 files,
+                since,
+                merge_base,
+                files_from,
                 show_crates,
                 direct_only,
+                narrow_workspace_manifests,
+                max_depth,
+                cargo_subcommand,
                 exclude_dev,
+                no_exclude_dev: _,
                 exclude_build,
+                no_exclude_build: _,
                 exclude_target,
+                no_exclude_target: _,
+                target,
+                max_workspaces,
+                exclude_paths,
+                exclude_crates,
+                follow_symlinks,
+                no_cache,
+                cache: _,
+                profile: _,
                 format,
+                output,
+                workspace_names,
             } => AffectedConfig::builder()
                 .with_files(files)
+                .with_changed_since(
+                    since
+                        .map(ChangedSince::Revision)
+                        .or(merge_base.map(ChangedSince::MergeBase)),
+                )
+                .with_files_from(files_from)
                 .with_show_crates(show_crates)
                 .with_direct_only(direct_only)
+                .with_narrow_workspace_manifests(narrow_workspace_manifests)
+                .with_max_depth(max_depth)
+                .with_cargo_subcommand(cargo_subcommand)
                 .with_paths(vec![
                     std::env::current_dir().unwrap_or_else(|_| PathBuf::from(".")),
                 ])
-                .with_format(format.format)
+                .with_format(format)
+                .with_output(output.output)
                 .with_exclude_dev(exclude_dev)
                 .with_exclude_build(exclude_build)
                 .with_exclude_target(exclude_target)
+                .with_target(target)
+                .with_max_workspaces(max_workspaces)
+                .with_exclude_paths(exclude_paths)
+                .with_exclude_crates(exclude_crates)
+                .with_follow_symlinks(follow_symlinks)
+                .with_no_cache(no_cache)
+                .with_workspace_names(workspace_names)
                 .build(),
             _ => Err(FerrisWheelError::ConfigurationError {
                 message: "Invalid command type for AffectedConfig".to_string(),
@@ -91,8 +165,8 @@ impl FromCommand for AffectedConfig {
 crate::impl_try_from_command!(AffectedConfig);
 
 /// Execute the ripples command
-pub fn execute_affected_command(command: Commands) -> Result<()> {
-    let config = AffectedConfig::from_command(command)
+pub fn execute_affected_command(command: Commands, project: Option<&ProjectConfig>) -> Result<()> {
+    let config = AffectedConfig::from_command(command, project)
         .wrap_err("Failed to parse ripples command configuration")?;
 
     use crate::executors::CommandExecutor;
@@ -100,6 +174,52 @@ pub fn execute_affected_command(command: Commands) -> Result<()> {
     AffectedExecutor::execute(config)
 }
 
+/// Ask git in `dir` which files changed since `since`, relative to `dir`
+pub fn changed_files_from_git(
+    dir: &Path,
+    since: &ChangedSince,
+) -> Result<Vec<String>, FerrisWheelError> {
+    git::changed_files(dir, &base_revision(dir, since)?)
+}
+
+/// The revision in `dir` that changes are counted from
+pub fn base_revision(dir: &Path, since: &ChangedSince) -> Result<String, FerrisWheelError> {
+    match since {
+        ChangedSince::Revision(rev) => Ok(rev.clone()),
+        ChangedSince::MergeBase(rev) => git::merge_base(dir, rev),
+    }
+}
+
+/// Read a list of changed files from `path`, or from stdin when it is `-`
+pub fn read_file_list(path: &Path) -> Result<Vec<String>, FerrisWheelError> {
+    let content = if path == Path::new("-") {
+        let mut content = String::new();
+        std::io::stdin().read_to_string(&mut content)?;
+        content
+    } else {
+        std::fs::read_to_string(path).map_err(|e| FerrisWheelError::FileReadError {
+            path: path.to_path_buf(),
+            source: e,
+        })?
+    };
+    Ok(parse_file_list(&content))
+}
+
+/// Split a file list on NUL bytes when it has any, as printed by `git diff
+/// -z`, and on newlines otherwise
+fn parse_file_list(content: &str) -> Vec<String> {
+    let files: Vec<&str> = if content.contains('\0') {
+        content.split('\0').collect()
+    } else {
+        content.lines().collect()
+    };
+    files
+        .into_iter()
+        .filter(|file| !file.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
 /// Analysis of affected crates and workspaces based on changed files
 pub struct AffectedAnalysis {
     /// Map from crate identifier to its workspace path
@@ -109,9 +229,12 @@ pub struct AffectedAnalysis {
     /// Map from workspace path to workspace info
     workspaces: HashMap<PathBuf, WorkspaceInfo>,
     /// Crate-level dependency graph keyed by crate identifier
-    crate_graph: DiGraph<CrateId, ()>,
+    crate_graph: DiGraph<CrateId, DependencyType>,
     /// Map from crate identifier to node index in the graph
     crate_node_indices: HashMap<CrateId, NodeIndex>,
+    /// Revision to compare changed workspace root manifests with, to narrow
+    /// their impact down to the members inheriting changed dependencies
+    manifest_base: Option<String>,
 }
 
 impl AffectedAnalysis {
@@ -124,18 +247,20 @@ impl AffectedAnalysis {
         crate_path_to_workspace: &CratePathToWorkspaceMap,
         filter: DependencyFilter,
     ) -> Result<Self, FerrisWheelError> {
+        let _timer = Timer::start(Phase::GraphBuild);
         let mut crate_graph = DiGraph::new();
         let mut crate_node_indices = HashMap::new();
         let mut crate_workspace_index = HashMap::new();
         let mut crate_path_index = HashMap::new();
         let mut crate_ids_by_name: HashMap<String, Vec<CrateId>> = HashMap::new();
+        let mut workspace_names: HashMap<CrateId, String> = HashMap::new();
 
         // First pass: create nodes for all crates and build proper mappings
         for (workspace_path, workspace_info) in workspaces {
             let workspace_path = workspace_path.clone();
             for member in workspace_info.members() {
                 let crate_path = member.path().to_path_buf();
-                let crate_id = CrateId::new(member.name().to_string(), crate_path.clone());
+                let crate_id = CrateId::new(member.name(), crate_path.clone());
                 let node_idx = crate_graph.add_node(crate_id.clone());
                 crate_node_indices.insert(crate_id.clone(), node_idx);
 
@@ -147,6 +272,13 @@ impl AffectedAnalysis {
                         .unwrap_or_else(|| workspace_path.clone()),
                 );
 
+                let workspace_name = crate_path_to_workspace
+                    .get(&crate_path)
+                    .and_then(|path| workspaces.get(path))
+                    .unwrap_or(workspace_info)
+                    .name();
+                workspace_names.insert(crate_id.clone(), workspace_name.to_string());
+
                 crate_path_index.insert(crate_path.clone(), crate_id.clone());
 
                 crate_ids_by_name
@@ -172,31 +304,28 @@ impl AffectedAnalysis {
                     crate_node_indices: &crate_node_indices,
                     crate_ids_by_name: &crate_ids_by_name,
                     crate_path_index: &crate_path_index,
+                    workspace_names: &workspace_names,
                     workspace_path: workspace_path.as_path(),
+                    filter: &filter,
                 };
 
-                connect_dependencies(member.dependencies(), true, from_idx, &from_id, &mut ctx);
-
-                connect_dependencies(
-                    member.dev_dependencies(),
-                    filter.include_dev(),
-                    from_idx,
-                    &from_id,
-                    &mut ctx,
-                );
-
-                connect_dependencies(
-                    member.build_dependencies(),
-                    filter.include_build(),
-                    from_idx,
-                    &from_id,
-                    &mut ctx,
-                );
+                for (deps, dep_type) in [
+                    (member.dependencies(), DependencyType::Normal),
+                    (member.dev_dependencies(), DependencyType::Dev),
+                    (member.build_dependencies(), DependencyType::Build),
+                ] {
+                    connect_dependencies(deps, dep_type, None, from_idx, &from_id, &mut ctx)?;
+                }
 
-                if filter.include_target() {
-                    for deps in member.target_dependencies().values() {
-                        connect_dependencies(deps, true, from_idx, &from_id, &mut ctx);
-                    }
+                for (target, deps) in member.target_dependencies() {
+                    connect_dependencies(
+                        deps,
+                        DependencyType::Normal,
+                        Some(target),
+                        from_idx,
+                        &from_id,
+                        &mut ctx,
+                    )?;
                 }
             }
         }
@@ -207,9 +336,18 @@ impl AffectedAnalysis {
             workspaces: workspaces.clone(),
             crate_graph,
             crate_node_indices,
+            manifest_base: None,
         })
     }
 
+    /// Compare changed workspace root manifests with their contents at
+    /// `rev`: when only `[workspace.dependencies]` changed, just the members
+    /// inheriting the changed entries are directly affected
+    pub fn with_manifest_base(mut self, rev: Option<String>) -> Self {
+        self.manifest_base = rev;
+        self
+    }
+
     /// Handle workspace-level Cargo files (Cargo.toml or Cargo.lock)
     fn handle_workspace_cargo_file(
         &self,
@@ -224,19 +362,23 @@ impl AffectedAnalysis {
             } else {
                 cwd.join(ws_path)
             };
-            let abs_ws_path = abs_ws_path.canonicalize().unwrap_or(abs_ws_path);
+            let abs_ws_path = canonicalize_or_self(&abs_ws_path);
 
             // Check if the Cargo file is directly in the workspace root
             if let Some(parent) = abs_file.parent()
                 && parent == abs_ws_path
             {
                 // This is a workspace-level Cargo file
-                // Mark all crates in this workspace as directly affected
+                let changed_dependencies = self.changed_workspace_dependencies(abs_file);
+                // Mark the crates in this workspace as directly affected: all
+                // of them, or those inheriting changed workspace dependencies
                 for (crate_id, crate_ws_path) in &self.crate_workspace_index {
-                    let crate_ws_abs = crate_ws_path
-                        .canonicalize()
-                        .unwrap_or_else(|_| crate_ws_path.clone());
-                    if crate_ws_abs == abs_ws_path {
+                    let crate_ws_abs = canonicalize_or_self(crate_ws_path);
+                    if crate_ws_abs == abs_ws_path
+                        && changed_dependencies
+                            .as_ref()
+                            .is_none_or(|keys| inherits_any(crate_id.path(), keys))
+                    {
                         directly_affected_crates.insert(crate_id.clone());
                     }
                 }
@@ -246,17 +388,40 @@ impl AffectedAnalysis {
         false
     }
 
+    /// Keys of `[workspace.dependencies]` that changed in the workspace root
+    /// manifest `abs_file` since the manifest base, or `None` when there is
+    /// no base, the file is not a manifest, or anything else changed too
+    fn changed_workspace_dependencies(&self, abs_file: &Path) -> Option<BTreeSet<String>> {
+        let rev = self.manifest_base.as_ref()?;
+        if abs_file.file_name()? != "Cargo.toml" {
+            return None;
+        }
+        let new = std::fs::read_to_string(abs_file).ok()?;
+        let old = git::show_file(abs_file.parent()?, rev, "Cargo.toml").ok()?;
+        workspace_dependency_changes(&old, &new)
+    }
+
     /// Analyze which crates and workspaces are affected by the given files
-    pub fn analyze_affected_files(&self, files: &[String]) -> AffectedResult {
+    ///
+    /// Reverse dependencies propagate at most `max_depth` steps from the
+    /// crates containing the files: `Some(0)` keeps only those crates,
+    /// `Some(1)` adds their direct dependents, and `None` follows the full
+    /// transitive closure.
+    pub fn analyze_affected_files(
+        &self,
+        files: &[String],
+        max_depth: Option<usize>,
+    ) -> AffectedResult {
         let mut directly_affected_crates: HashSet<CrateId> = HashSet::new();
         let mut unmatched_files = Vec::new();
 
         // Get current directory once for efficiency
         let cwd = std::env::current_dir().unwrap_or_default();
 
-        // Map files to crates
-        for file in files {
-            let file_path = PathBuf::from(file);
+        // Map files to crates, counting both sides of renames so the crate
+        // that lost a file is affected too
+        for file in files.iter().flat_map(|file| expand_renames(file)) {
+            let file_path = PathBuf::from(&file);
 
             // Normalize the file path to absolute and resolve symlinks
             let abs_file = if file_path.is_absolute() {
@@ -264,7 +429,7 @@ impl AffectedAnalysis {
             } else {
                 cwd.join(&file_path)
             };
-            let abs_file = abs_file.canonicalize().unwrap_or(abs_file);
+            let abs_file = resolve_path(&abs_file);
 
             // Check if this is a Cargo.lock or Cargo.toml file
             let filename = abs_file.file_name().and_then(|f| f.to_str());
@@ -280,17 +445,39 @@ impl AffectedAnalysis {
             if let Some(crate_id) = self.find_crate_for_file(&abs_file) {
                 directly_affected_crates.insert(crate_id);
             } else {
-                unmatched_files.push(file.clone());
+                unmatched_files.push(file);
             }
         }
 
         // Find all crates affected by reverse dependencies
-        let mut all_affected_crates = directly_affected_crates.clone();
-        for crate_id in directly_affected_crates.iter() {
-            if let Some(&node_idx) = self.crate_node_indices.get(crate_id) {
-                self.find_reverse_dependencies(node_idx, &mut all_affected_crates);
+        let (depths, affected_via) =
+            self.find_reverse_dependencies(&directly_affected_crates, max_depth, |_| true);
+        let all_affected_crates: HashSet<CrateId> = depths.into_keys().collect();
+
+        // Dev-dependencies only reach the tests of the crate declaring them,
+        // so they end propagation for builds and are followed one step for
+        // tests
+        let (build_depths, _) =
+            self.find_reverse_dependencies(&directly_affected_crates, max_depth, |kind| {
+                *kind != DependencyType::Dev
+            });
+        let mut affected_for_tests: HashSet<CrateId> = build_depths.keys().cloned().collect();
+        for (crate_id, &depth) in &build_depths {
+            if max_depth.is_some_and(|max| depth >= max) {
+                continue;
+            }
+            let Some(&node_idx) = self.crate_node_indices.get(crate_id) else {
+                continue;
+            };
+            for edge in self
+                .crate_graph
+                .edges_directed(node_idx, petgraph::Direction::Incoming)
+                .filter(|edge| *edge.weight() == DependencyType::Dev)
+            {
+                affected_for_tests.insert(self.crate_graph[edge.source()].clone());
             }
         }
+        let affected_for_build: HashSet<CrateId> = build_depths.into_keys().collect();
 
         let directly_affected_workspaces: HashSet<String> = directly_affected_crates
             .iter()
@@ -305,32 +492,69 @@ impl AffectedAnalysis {
         AffectedResult {
             directly_affected_crates,
             all_affected_crates,
+            affected_for_build,
+            affected_for_tests,
+            affected_via,
             directly_affected_workspaces,
             all_affected_workspaces,
             unmatched_files,
         }
     }
 
-    fn find_reverse_dependencies(&self, node_idx: NodeIndex, affected: &mut HashSet<CrateId>) {
+    /// The crates in `directly_affected` plus everything depending on them
+    /// through dependencies of the kinds `follow` accepts, at most
+    /// `max_depth` dependency steps away when given
+    ///
+    /// Returns the distance of each affected crate from the directly affected
+    /// ones, and the dependency through which each indirectly affected crate
+    /// was reached.
+    fn find_reverse_dependencies(
+        &self,
+        directly_affected: &HashSet<CrateId>,
+        max_depth: Option<usize>,
+        follow: impl Fn(&DependencyType) -> bool,
+    ) -> (HashMap<CrateId, usize>, HashMap<CrateId, CrateId>) {
         use petgraph::Direction;
 
-        for edge in self
-            .crate_graph
-            .edges_directed(node_idx, Direction::Incoming)
-        {
-            let source_idx = edge.source();
-            let source_crate = self.crate_graph[source_idx].clone();
-            if affected.insert(source_crate.clone()) {
-                // Recursively find more reverse dependencies
-                self.find_reverse_dependencies(source_idx, affected);
+        // Breadth-first, so each crate is reached at its shortest distance
+        let mut affected: HashMap<CrateId, usize> = directly_affected
+            .iter()
+            .map(|crate_id| (crate_id.clone(), 0))
+            .collect();
+        let mut via = HashMap::new();
+        let mut frontier: Vec<NodeIndex> = directly_affected
+            .iter()
+            .filter_map(|crate_id| self.crate_node_indices.get(crate_id).copied())
+            .collect();
+        let mut depth = 0;
+        while !frontier.is_empty() && max_depth.is_none_or(|max| depth < max) {
+            // Crates reachable through several dependencies at the same
+            // distance are attributed to the first of them by name and path
+            frontier.sort_by(|a, b| self.crate_graph[*a].cmp(&self.crate_graph[*b]));
+            let mut next = Vec::new();
+            for node_idx in frontier {
+                for edge in self
+                    .crate_graph
+                    .edges_directed(node_idx, Direction::Incoming)
+                    .filter(|edge| follow(edge.weight()))
+                {
+                    let dependent = &self.crate_graph[edge.source()];
+                    if !affected.contains_key(dependent) {
+                        affected.insert(dependent.clone(), depth + 1);
+                        via.insert(dependent.clone(), self.crate_graph[node_idx].clone());
+                        next.push(edge.source());
+                    }
+                }
             }
+            frontier = next;
+            depth += 1;
         }
+
+        (affected, via)
     }
 
     fn find_crate_for_file(&self, abs_file: &Path) -> Option<CrateId> {
-        let canonical = abs_file
-            .canonicalize()
-            .unwrap_or_else(|_| abs_file.to_path_buf());
+        let canonical = resolve_path(abs_file);
 
         let mut best_match: Option<(usize, CrateId)> = None;
 
@@ -363,37 +587,57 @@ impl AffectedAnalysis {
 }
 
 struct DependencyGraphContext<'a> {
-    crate_graph: &'a mut DiGraph<CrateId, ()>,
+    crate_graph: &'a mut DiGraph<CrateId, DependencyType>,
     crate_node_indices: &'a HashMap<CrateId, NodeIndex>,
     crate_ids_by_name: &'a HashMap<String, Vec<CrateId>>,
     crate_path_index: &'a HashMap<PathBuf, CrateId>,
+    workspace_names: &'a HashMap<CrateId, String>,
     workspace_path: &'a Path,
+    filter: &'a DependencyFilter,
 }
 
 fn connect_dependencies(
     deps: &[Dependency],
-    include: bool,
+    dep_type: DependencyType,
+    target: Option<&str>,
     from_idx: NodeIndex,
     from_id: &CrateId,
     ctx: &mut DependencyGraphContext<'_>,
-) {
-    if !include {
-        return;
+) -> Result<(), FerrisWheelError> {
+    if !ctx.filter.includes_kind(&dep_type) || !ctx.filter.includes_target(target) {
+        return Ok(());
     }
 
     for dep in deps {
-        if let Some(to_idx) = resolve_dependency_crate_id(
+        let Some(target_id) = resolve_dependency_crate_id(
             dep,
             from_id,
             ctx.workspace_path,
             ctx.crate_ids_by_name,
             ctx.crate_path_index,
-        )
-        .and_then(|target_id| ctx.crate_node_indices.get(&target_id).copied())
-        {
-            ctx.crate_graph.add_edge(from_idx, to_idx, ());
+        ) else {
+            continue;
+        };
+        let Some(&to_idx) = ctx.crate_node_indices.get(&target_id) else {
+            continue;
+        };
+
+        let edge = DependencyEdge::builder()
+            .with_from_crate(from_id.name())
+            .with_to_crate(target_id.name())
+            .with_dependency_type(dep_type)
+            .with_target(target.or(dep.target()))
+            .build()?;
+        if ctx.filter.includes(
+            &ctx.workspace_names[from_id],
+            &ctx.workspace_names[&target_id],
+            &edge,
+        ) {
+            ctx.crate_graph.add_edge(from_idx, to_idx, dep_type);
         }
     }
+
+    Ok(())
 }
 
 fn resolve_dependency_crate_id(
@@ -416,7 +660,7 @@ fn resolve_dependency_crate_id(
             base.join(dep_path)
         };
 
-        let canonical = absolute.canonicalize().unwrap_or_else(|_| absolute.clone());
+        let canonical = canonicalize_or_self(&absolute);
 
         crate_path_index
             .get(&canonical)
@@ -449,12 +693,34 @@ fn resolve_dependency_crate_id(
 pub struct AffectedResult {
     pub(crate) directly_affected_crates: HashSet<CrateId>,
     pub(crate) all_affected_crates: HashSet<CrateId>,
+    /// Crates whose build is affected, reached through normal and build
+    /// dependencies only
+    pub(crate) affected_for_build: HashSet<CrateId>,
+    /// Crates whose tests are affected: those whose build is affected, plus
+    /// their dev-dependents
+    pub(crate) affected_for_tests: HashSet<CrateId>,
+    /// For each indirectly affected crate, the affected crate it depends on
+    /// that brought it in
+    pub(crate) affected_via: HashMap<CrateId, CrateId>,
     pub(crate) directly_affected_workspaces: HashSet<String>,
     pub(crate) all_affected_workspaces: HashSet<String>,
     pub(crate) unmatched_files: Vec<String>,
 }
 
 impl AffectedResult {
+    /// The crates from a directly changed crate to `crate_id`, each
+    /// depending on the one before
+    pub(crate) fn chain<'a>(&'a self, crate_id: &'a CrateId) -> Vec<&'a CrateId> {
+        let mut chain = vec![crate_id];
+        let mut current = crate_id;
+        while let Some(dependency) = self.affected_via.get(current) {
+            chain.push(dependency);
+            current = dependency;
+        }
+        chain.reverse();
+        chain
+    }
+
     pub fn to_json_report(&self, analysis: &AffectedAnalysis) -> AffectedJsonReport {
         let mut affected_crates = Vec::new();
 
@@ -473,6 +739,11 @@ impl AffectedResult {
                 workspace: workspace_name,
                 is_directly_affected: self.directly_affected_crates.contains(crate_id),
                 is_standalone,
+                chain: self
+                    .chain(crate_id)
+                    .into_iter()
+                    .map(|id| id.name().to_string())
+                    .collect(),
             });
         }
 
@@ -537,10 +808,334 @@ impl AffectedResult {
             affected_workspaces,
             directly_affected_crates,
             directly_affected_workspaces,
+            affected_for_build: sorted_names(&self.affected_for_build),
+            affected_for_tests: sorted_names(&self.affected_for_tests),
         }
     }
 }
 
+/// Turn crate names into `-p <crate>` arguments for cargo
+///
+/// Names are sorted and deduplicated. The arguments are split into lines of
+/// at most `max_line_length` bytes (a single overlong argument gets a line of
+/// its own), so each line can be spliced into one cargo invocation.
+pub fn cargo_package_args<'a>(
+    crate_names: impl IntoIterator<Item = &'a str>,
+    max_line_length: usize,
+) -> Vec<String> {
+    let mut names: Vec<&str> = crate_names.into_iter().collect();
+    names.sort_unstable();
+    names.dedup();
+
+    let mut lines: Vec<String> = Vec::new();
+    for name in names {
+        let arg = format!("-p {name}");
+        match lines.last_mut() {
+            Some(line) if line.len() + 1 + arg.len() <= max_line_length => {
+                line.push(' ');
+                line.push_str(&arg);
+            }
+            _ => lines.push(arg),
+        }
+    }
+
+    lines
+}
+
+/// Keys of `[workspace.dependencies]` whose entries differ between the
+/// manifests `old` and `new`, or `None` when anything else differs too
+fn workspace_dependency_changes(old: &str, new: &str) -> Option<BTreeSet<String>> {
+    let mut old: toml::Table = toml::from_str(old).ok()?;
+    let mut new: toml::Table = toml::from_str(new).ok()?;
+    let old_dependencies = take_workspace_dependencies(&mut old);
+    let new_dependencies = take_workspace_dependencies(&mut new);
+    if old != new {
+        return None;
+    }
+
+    Some(
+        old_dependencies
+            .keys()
+            .chain(new_dependencies.keys())
+            .filter(|key| old_dependencies.get(*key) != new_dependencies.get(*key))
+            .cloned()
+            .collect(),
+    )
+}
+
+fn take_workspace_dependencies(manifest: &mut toml::Table) -> toml::Table {
+    manifest
+        .get_mut("workspace")
+        .and_then(toml::Value::as_table_mut)
+        .and_then(|workspace| workspace.remove("dependencies"))
+        .and_then(|dependencies| match dependencies {
+            toml::Value::Table(table) => Some(table),
+            _ => None,
+        })
+        .unwrap_or_default()
+}
+
+/// Whether the manifest of the crate in `crate_dir` inherits any of `keys`
+/// from the workspace with `workspace = true`, in any dependency table
+///
+/// A manifest that cannot be read counts as inheriting them.
+fn inherits_any(crate_dir: &Path, keys: &BTreeSet<String>) -> bool {
+    let Some(manifest) = std::fs::read_to_string(crate_dir.join("Cargo.toml"))
+        .ok()
+        .and_then(|content| toml::from_str::<toml::Table>(&content).ok())
+    else {
+        return true;
+    };
+
+    let targets = manifest
+        .get("target")
+        .and_then(toml::Value::as_table)
+        .into_iter()
+        .flat_map(|targets| targets.values().filter_map(toml::Value::as_table));
+    std::iter::once(&manifest).chain(targets).any(|scope| {
+        ["dependencies", "dev-dependencies", "build-dependencies"]
+            .iter()
+            .filter_map(|table| scope.get(*table).and_then(toml::Value::as_table))
+            .flat_map(|table| table.iter())
+            .any(|(key, entry)| {
+                keys.contains(key)
+                    && entry.get("workspace").and_then(toml::Value::as_bool) == Some(true)
+            })
+    })
+}
+
+/// The paths named by `file`, which may use git's rename notation `old =>
+/// new` or `dir/{old => new}/file` as printed by `git diff --stat` and
+/// `--numstat`
+pub(crate) fn expand_renames(file: &str) -> Vec<String> {
+    let Some((before, after)) = file.split_once(" => ") else {
+        return vec![file.to_string()];
+    };
+
+    match (before.rsplit_once('{'), after.split_once('}')) {
+        (Some((prefix, old)), Some((new, suffix))) => [old, new]
+            .iter()
+            // An empty side, as in `{ => src}/lib.rs`, leaves a double slash
+            .map(|middle| format!("{prefix}{middle}{suffix}").replace("//", "/"))
+            .collect(),
+        _ => vec![before.to_string(), after.to_string()],
+    }
+}
+
+/// Resolve symlinks in the absolute `path`, also when it no longer exists,
+/// like the old side of a rename: the deepest existing ancestor is
+/// canonicalized and the rest of the path appended to it
+fn resolve_path(path: &Path) -> PathBuf {
+    path.ancestors()
+        .find_map(|ancestor| {
+            let canonical = canonicalize(ancestor)?;
+            let rest = path.strip_prefix(ancestor).ok()?;
+            Some(if rest.as_os_str().is_empty() {
+                canonical
+            } else {
+                canonical.join(rest)
+            })
+        })
+        .unwrap_or_else(|| path.to_path_buf())
+}
+
+/// Crate names sorted and deduplicated, for deterministic reports
+fn sorted_names(crates: &HashSet<CrateId>) -> Vec<String> {
+    let mut names: Vec<String> = crates
+        .iter()
+        .map(|crate_id| crate_id.name().to_string())
+        .collect();
+    names.sort();
+    names.dedup();
+    names
+}
+
+/// Turn crates into ready-to-run `cargo <subcommand>` invocations
+///
+/// A cargo invocation can only select packages of one workspace, so there is
+/// one invocation per workspace, pointed at the workspace manifest with
+/// `--manifest-path` (relative to `base` when it lies below it). Invocations
+/// whose `-p` arguments exceed `max_line_length` bytes are split like
+/// [`cargo_package_args`]. Workspaces are ordered by manifest path.
+pub(crate) fn cargo_commands(
+    analysis: &AffectedAnalysis,
+    crates: &HashSet<CrateId>,
+    subcommand: &str,
+    base: &Path,
+    max_line_length: usize,
+) -> Vec<String> {
+    let mut by_workspace: BTreeMap<&Path, Vec<&str>> = BTreeMap::new();
+    for crate_id in crates {
+        if let Some(workspace_path) = analysis.crate_workspace_index.get(crate_id) {
+            by_workspace
+                .entry(workspace_path.as_path())
+                .or_default()
+                .push(crate_id.name());
+        }
+    }
+
+    let mut commands = Vec::new();
+    for (workspace_path, names) in by_workspace {
+        let manifest = workspace_path.join("Cargo.toml");
+        let manifest = manifest.strip_prefix(base).unwrap_or(&manifest);
+        let prefix = format!(
+            "cargo {subcommand} --manifest-path {}",
+            shell_quote(&manifest.to_string_lossy())
+        );
+        let budget = max_line_length.saturating_sub(prefix.len() + 1);
+        for args in cargo_package_args(names, budget) {
+            commands.push(format!("{prefix} {args}"));
+        }
+    }
+
+    commands
+}
+
+/// Build a GitHub Actions job matrix with one entry per workspace of
+/// `crates`, ordered by path
+///
+/// Paths are relative to `base` when they lie below it (`.` for `base`
+/// itself), so jobs can use them as their working directory.
+pub(crate) fn github_matrix(
+    analysis: &AffectedAnalysis,
+    crates: &HashSet<CrateId>,
+    base: &Path,
+) -> GitHubMatrix {
+    let workspace_paths: BTreeSet<&PathBuf> = crates
+        .iter()
+        .filter_map(|crate_id| analysis.crate_workspace_index.get(crate_id))
+        .collect();
+
+    let include = workspace_paths
+        .into_iter()
+        .filter_map(|workspace_path| {
+            let workspace = analysis.workspaces.get(workspace_path)?;
+            let path = match workspace_path.strip_prefix(base) {
+                Ok(relative) if relative.as_os_str().is_empty() => ".".to_string(),
+                Ok(relative) => relative.display().to_string(),
+                Err(_) => workspace_path.display().to_string(),
+            };
+            Some(GitHubMatrixEntry {
+                workspace: workspace.name().to_string(),
+                path,
+            })
+        })
+        .collect();
+
+    GitHubMatrix { include }
+}
+
+/// Render the impact of a change as an indented tree per directly changed
+/// crate, with each affected crate under the dependency it was reached
+/// through
+///
+/// Every crate appears once, on the shortest route from a changed crate, the
+/// same route its `chain` follows. With `direct_only`, only the changed crates
+/// are listed.
+pub(crate) fn impact_tree(
+    result: &AffectedResult,
+    analysis: &AffectedAnalysis,
+    direct_only: bool,
+) -> String {
+    let mut dependents: HashMap<&CrateId, Vec<&CrateId>> = HashMap::new();
+    if !direct_only {
+        for (crate_id, dependency) in &result.affected_via {
+            dependents.entry(dependency).or_default().push(crate_id);
+        }
+    }
+
+    let label = |crate_id: &CrateId| {
+        let workspace = analysis
+            .workspace_name(crate_id)
+            .unwrap_or_else(|| "unknown".to_string());
+        let scope = if result.affected_for_build.contains(crate_id) {
+            ""
+        } else if result.affected_for_tests.contains(crate_id) {
+            " [tests only]"
+        } else {
+            " [through dev-dependencies of dependencies]"
+        };
+        format!("{} ({workspace}){scope}", crate_id.name())
+    };
+    let sorted = |crates: &mut Vec<&CrateId>| {
+        crates.sort_by_cached_key(|crate_id| (crate_id.name().to_string(), label(crate_id)));
+    };
+
+    let mut roots: Vec<&CrateId> = result.directly_affected_crates.iter().collect();
+    sorted(&mut roots);
+
+    let mut lines = Vec::new();
+    // Depth-first, with the prefix each child line continues from
+    let mut stack: Vec<(&CrateId, String, String)> = roots
+        .into_iter()
+        .rev()
+        .map(|root| (root, String::new(), String::new()))
+        .collect();
+    while let Some((crate_id, branch, indent)) = stack.pop() {
+        lines.push(format!("{branch}{}", label(crate_id)));
+
+        let mut children = dependents.get(crate_id).cloned().unwrap_or_default();
+        sorted(&mut children);
+        let last = children.len().saturating_sub(1);
+        for (i, child) in children.into_iter().enumerate().rev() {
+            let (branch, continuation) = if i == last {
+                ("└── ", "    ")
+            } else {
+                ("├── ", "│   ")
+            };
+            stack.push((
+                child,
+                format!("{indent}{branch}"),
+                format!("{indent}{continuation}"),
+            ));
+        }
+    }
+    lines.join("\n")
+}
+
+/// Turn crates into cargo package specs, one per line
+///
+/// Crates are named by their package name, unless another of the crates has
+/// the same name, in which case the `path+file://` form of the spec keeps them
+/// apart.
+pub(crate) fn package_specs(crates: &HashSet<CrateId>) -> Vec<String> {
+    let mut name_counts: HashMap<&str, usize> = HashMap::new();
+    for crate_id in crates {
+        *name_counts.entry(crate_id.name()).or_default() += 1;
+    }
+
+    let mut specs: Vec<String> = crates
+        .iter()
+        .map(|crate_id| {
+            if name_counts[crate_id.name()] > 1 {
+                format!(
+                    "path+file://{}#{}",
+                    crate_id.path().display(),
+                    crate_id.name()
+                )
+            } else {
+                crate_id.name().to_string()
+            }
+        })
+        .collect();
+    specs.sort();
+    specs
+}
+
+/// Quote `value` for POSIX shells if it contains anything but plain path
+/// characters
+fn shell_quote(value: &str) -> String {
+    let plain = !value.is_empty()
+        && value
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '/' | '.' | '_' | '-' | '+' | ':'));
+    if plain {
+        value.to_string()
+    } else {
+        format!("'{}'", value.replace('\'', r"'\''"))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::fs;
@@ -762,7 +1357,7 @@ version = "0.1.0"
             "{}/workspace-a/phoenix-v2-api/src/lib.rs",
             temp.path().display()
         )];
-        let result_a = analysis.analyze_affected_files(&files_a);
+        let result_a = analysis.analyze_affected_files(&files_a, None);
 
         assert!(contains_crate(
             &result_a.directly_affected_crates,
@@ -788,7 +1383,7 @@ version = "0.1.0"
             "{}/workspace-b/phoenix-v2-api/src/main.rs",
             temp.path().display()
         )];
-        let result_b = analysis.analyze_affected_files(&files_b);
+        let result_b = analysis.analyze_affected_files(&files_b, None);
 
         assert!(contains_crate(
             &result_b.directly_affected_crates,
@@ -826,7 +1421,7 @@ version = "0.1.0"
             ),
         ];
 
-        let result = analysis.analyze_affected_files(&files);
+        let result = analysis.analyze_affected_files(&files, None);
 
         assert_eq!(
             count_crate(&result.directly_affected_crates, "phoenix-v2-api"),
@@ -846,7 +1441,7 @@ version = "0.1.0"
             "{}/my-workspace/crate-b/src/lib.rs",
             temp.path().display()
         )];
-        let result = analysis.analyze_affected_files(&files);
+        let result = analysis.analyze_affected_files(&files, None);
 
         // crate-b should be directly affected
         assert!(contains_crate(&result.directly_affected_crates, "crate-b"));
@@ -868,7 +1463,7 @@ version = "0.1.0"
             "/tmp/some-random-file.rs".to_string(),
             format!("{}/README.md", temp.path().display()),
         ];
-        let result = analysis.analyze_affected_files(&files);
+        let result = analysis.analyze_affected_files(&files, None);
 
         assert_eq!(result.unmatched_files.len(), 2);
         assert!(result.directly_affected_crates.is_empty());
@@ -886,7 +1481,7 @@ version = "0.1.0"
 
         // Use relative paths
         let files = vec!["my-workspace/crate-a/src/lib.rs".to_string()];
-        let result = analysis.analyze_affected_files(&files);
+        let result = analysis.analyze_affected_files(&files, None);
 
         assert!(contains_crate(&result.directly_affected_crates, "crate-a"));
 
@@ -909,7 +1504,7 @@ version = "0.1.0"
                 temp.path().display()
             ),
         ];
-        let result = analysis.analyze_affected_files(&files);
+        let result = analysis.analyze_affected_files(&files, None);
 
         let json_report = result.to_json_report(&analysis);
 
@@ -939,7 +1534,7 @@ version = "0.1.0"
             format!("{}/my-workspace/crate-a/src/lib.rs", temp.path().display()),
             format!("{}/my-workspace/crate-a/src/main.rs", temp.path().display()),
         ];
-        let result = analysis.analyze_affected_files(&files);
+        let result = analysis.analyze_affected_files(&files, None);
 
         // Should only count crate-a once
         assert_eq!(result.directly_affected_crates.len(), 1);
@@ -956,7 +1551,7 @@ version = "0.1.0"
             "{}/workspace-b/consumer-crate/src/lib.rs",
             temp.path().display()
         )];
-        let result = analysis.analyze_affected_files(&files);
+        let result = analysis.analyze_affected_files(&files, None);
 
         // Only consumer-crate should be directly affected
         assert!(contains_crate(
@@ -971,6 +1566,266 @@ version = "0.1.0"
         assert!(!result.all_affected_workspaces.contains("workspace-a"));
     }
 
+    #[test]
+    fn test_max_depth_limits_reverse_dependencies() {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path().join("chain");
+        fs::create_dir_all(&root).unwrap();
+        fs::write(
+            root.join("Cargo.toml"),
+            "[workspace]\nmembers = [\"base\", \"mid\", \"top\", \"app\"]\n",
+        )
+        .unwrap();
+        // app -> top -> mid -> base, and top also depends on base directly
+        for (name, deps) in [
+            ("base", vec![]),
+            ("mid", vec!["base"]),
+            ("top", vec!["mid", "base"]),
+            ("app", vec!["top"]),
+        ] {
+            fs::create_dir_all(root.join(name).join("src")).unwrap();
+            let mut manifest = format!("[package]\nname = \"{name}\"\n\n[dependencies]\n");
+            for dep in deps {
+                manifest.push_str(&format!("{dep} = {{ path = \"../{dep}\" }}\n"));
+            }
+            fs::write(root.join(name).join("Cargo.toml"), manifest).unwrap();
+            fs::write(root.join(name).join("src/lib.rs"), "").unwrap();
+        }
+
+        let analysis = build_test_analysis(temp.path());
+        let files = vec![format!("{}/base/src/lib.rs", root.display())];
+        let affected = |max_depth| {
+            let mut names: Vec<String> = analysis
+                .analyze_affected_files(&files, max_depth)
+                .all_affected_crates
+                .iter()
+                .map(|id| id.name().to_string())
+                .collect();
+            names.sort();
+            names
+        };
+
+        assert_eq!(affected(Some(0)), vec!["base"]);
+        // top is one step away through its direct dependency on base
+        assert_eq!(affected(Some(1)), vec!["base", "mid", "top"]);
+        assert_eq!(affected(Some(2)), vec!["app", "base", "mid", "top"]);
+        assert_eq!(affected(None), affected(Some(2)));
+
+        let report = analysis
+            .analyze_affected_files(&files, None)
+            .to_json_report(&analysis);
+        let chains: Vec<(&str, Vec<&str>)> = report
+            .affected_crates
+            .iter()
+            .map(|c| {
+                (
+                    c.name.as_str(),
+                    c.chain.iter().map(String::as_str).collect(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            chains,
+            vec![
+                ("app", vec!["base", "top", "app"]),
+                ("base", vec!["base"]),
+                ("mid", vec!["base", "mid"]),
+                ("top", vec!["base", "top"]),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_deep_dependency_chain() {
+        use crate::analyzer::{CrateMember, Dependency};
+
+        // c1 depends on c0, c2 on c1, and so on, 10k crates deep
+        const DEPTH: usize = 10_000;
+        let root = PathBuf::from("/chain");
+        let members = (0..DEPTH)
+            .map(|i| {
+                let mut member = CrateMember::builder()
+                    .with_name(format!("c{i}"))
+                    .with_path(root.join(format!("c{i}")));
+                if i > 0 {
+                    member = member.add_dependency(
+                        Dependency::builder()
+                            .with_name(format!("c{}", i - 1))
+                            .build()
+                            .unwrap(),
+                    );
+                }
+                member.build().unwrap()
+            })
+            .collect();
+        let workspace = WorkspaceInfo::builder()
+            .with_name("chain")
+            .with_members(members)
+            .build()
+            .unwrap();
+        let analysis = AffectedAnalysis::new(
+            &HashMap::from([(root.clone(), workspace)]),
+            &HashMap::new(),
+            DependencyFilter::default(),
+        )
+        .unwrap();
+
+        let files = vec!["/chain/c0/src/lib.rs".to_string()];
+        let result = analysis.analyze_affected_files(&files, None);
+        assert_eq!(result.all_affected_crates.len(), DEPTH);
+        let last = result
+            .all_affected_crates
+            .iter()
+            .find(|crate_id| crate_id.name() == format!("c{}", DEPTH - 1))
+            .unwrap();
+        assert_eq!(result.chain(last).len(), DEPTH);
+
+        let result = analysis.analyze_affected_files(&files, Some(100));
+        assert_eq!(result.all_affected_crates.len(), 101);
+    }
+
+    #[test]
+    fn test_dev_dependents_only_affect_tests() {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path().join("kinds");
+        fs::create_dir_all(&root).unwrap();
+        fs::write(
+            root.join("Cargo.toml"),
+            "[workspace]\nmembers = [\"base\", \"lib\", \"tester\", \"app\"]\n",
+        )
+        .unwrap();
+        // app -> tester -(dev)-> lib -> base
+        for (name, table, deps) in [
+            ("base", "dependencies", vec![]),
+            ("lib", "dependencies", vec!["base"]),
+            ("tester", "dev-dependencies", vec!["lib"]),
+            ("app", "dependencies", vec!["tester"]),
+        ] {
+            fs::create_dir_all(root.join(name).join("src")).unwrap();
+            let mut manifest = format!("[package]\nname = \"{name}\"\n\n[{table}]\n");
+            for dep in deps {
+                manifest.push_str(&format!("{dep} = {{ path = \"../{dep}\" }}\n"));
+            }
+            fs::write(root.join(name).join("Cargo.toml"), manifest).unwrap();
+            fs::write(root.join(name).join("src/lib.rs"), "").unwrap();
+        }
+
+        let analysis = build_test_analysis(temp.path());
+        let files = vec![format!("{}/base/src/lib.rs", root.display())];
+        let report = analysis
+            .analyze_affected_files(&files, None)
+            .to_json_report(&analysis);
+
+        assert_eq!(report.affected_crates.len(), 4);
+        assert_eq!(report.affected_for_build, vec!["base", "lib"]);
+        // app builds against tester without its dev-dependencies
+        assert_eq!(report.affected_for_tests, vec!["base", "lib", "tester"]);
+
+        // A dev-dependent beyond the depth limit is not reached
+        let report = analysis
+            .analyze_affected_files(&files, Some(1))
+            .to_json_report(&analysis);
+        assert_eq!(report.affected_for_tests, vec!["base", "lib"]);
+    }
+
+    #[test]
+    fn test_parse_file_list() {
+        assert_eq!(
+            parse_file_list("a/src/lib.rs\r\nb/src/main.rs\n\n"),
+            vec!["a/src/lib.rs", "b/src/main.rs"]
+        );
+        // With NUL delimiters, newlines and spaces belong to the names
+        assert_eq!(
+            parse_file_list("a/src/lib.rs\0b/odd name\n.rs\0"),
+            vec!["a/src/lib.rs", "b/odd name\n.rs"]
+        );
+        assert!(parse_file_list("").is_empty());
+    }
+
+    #[test]
+    fn test_workspace_dependency_changes() {
+        let old = "[workspace]\nmembers = [\"a\"]\n\n[workspace.dependencies]\nserde = \
+                   \"1.0\"\nlog = \"0.4\"\n";
+
+        let new = old.replace(
+            "serde = \"1.0\"",
+            "serde = { version = \"1.0\", features = [] }",
+        );
+        assert_eq!(
+            workspace_dependency_changes(old, &(new + "anyhow = \"1\"\n")),
+            Some(BTreeSet::from(["anyhow".to_string(), "serde".to_string()]))
+        );
+        // Formatting alone changes nothing
+        assert_eq!(
+            workspace_dependency_changes(old, &old.replace("\n\n", "\n# deps\n")),
+            Some(BTreeSet::new())
+        );
+        assert_eq!(
+            workspace_dependency_changes(old, &old.replace("[\"a\"]", "[\"a\", \"b\"]")),
+            None
+        );
+    }
+
+    #[test]
+    fn test_inherits_any() {
+        let temp = TempDir::new().unwrap();
+        fs::write(
+            temp.path().join("Cargo.toml"),
+            "[package]\nname = \"a\"\n\n[dependencies]\nserde = { workspace = true }\nlog = \
+             \"0.4\"\n\n[target.'cfg(unix)'.dev-dependencies]\nlibc = { workspace = true }\n",
+        )
+        .unwrap();
+        let keys = |keys: &[&str]| keys.iter().map(|key| key.to_string()).collect();
+
+        assert!(inherits_any(temp.path(), &keys(&["serde"])));
+        assert!(inherits_any(temp.path(), &keys(&["libc"])));
+        // Declared locally rather than inherited
+        assert!(!inherits_any(temp.path(), &keys(&["log", "anyhow"])));
+        assert!(inherits_any(&temp.path().join("missing"), &keys(&["log"])));
+    }
+
+    #[test]
+    fn test_expand_renames() {
+        assert_eq!(expand_renames("a/src/lib.rs"), vec!["a/src/lib.rs"]);
+        assert_eq!(
+            expand_renames("a/src/old.rs => b/src/new.rs"),
+            vec!["a/src/old.rs", "b/src/new.rs"]
+        );
+        assert_eq!(
+            expand_renames("crates/{core => api}/src/lib.rs"),
+            vec!["crates/core/src/lib.rs", "crates/api/src/lib.rs"]
+        );
+        assert_eq!(
+            expand_renames("api/{ => src}/lib.rs"),
+            vec!["api/lib.rs", "api/src/lib.rs"]
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_renamed_files_affect_both_crates() {
+        let temp = create_simple_test_workspace();
+        let analysis = build_test_analysis(temp.path());
+        let link = temp.path().join("link");
+        std::os::unix::fs::symlink(temp.path(), &link).unwrap();
+
+        // The old path no longer exists and is only reachable via a symlink
+        let files = vec![format!(
+            "{}/my-workspace/{{crate-a/src/gone.rs => crate-b/src/moved.rs}}",
+            link.display()
+        )];
+        let result = analysis.analyze_affected_files(&files, Some(0));
+
+        assert!(result.unmatched_files.is_empty());
+        let mut names: Vec<&str> = result
+            .directly_affected_crates
+            .iter()
+            .map(CrateId::name)
+            .collect();
+        names.sort();
+        assert_eq!(names, vec!["crate-a", "crate-b"]);
+    }
+
     fn create_mixed_workspace_and_standalone() -> TempDir {
         let temp = TempDir::new().unwrap();
         let root = temp.path();
@@ -1103,7 +1958,7 @@ version = "0.1.0"
             "{}/standalone-test-crate/src/lib.rs",
             temp.path().display()
         )];
-        let result = analysis.analyze_affected_files(&files);
+        let result = analysis.analyze_affected_files(&files, None);
         let json_report = result.to_json_report(&analysis);
 
         // Should have one affected crate
@@ -1126,7 +1981,7 @@ version = "0.1.0"
             "{}/real-workspace/crate-a/src/lib.rs",
             temp.path().display()
         )];
-        let result = analysis.analyze_affected_files(&files);
+        let result = analysis.analyze_affected_files(&files, None);
         let json_report = result.to_json_report(&analysis);
 
         // Should have two affected crates (crate-a and crate-b due to reverse deps)
@@ -1158,7 +2013,7 @@ version = "0.1.0"
             ),
             format!("{}/another-standalone/src/lib.rs", temp.path().display()),
         ];
-        let result = analysis.analyze_affected_files(&files);
+        let result = analysis.analyze_affected_files(&files, None);
         let json_report = result.to_json_report(&analysis);
 
         // Should have multiple affected crates
@@ -1202,7 +2057,7 @@ version = "0.1.0"
             "{}/standalone-test-crate/src/lib.rs",
             temp.path().display()
         )];
-        let result = analysis.analyze_affected_files(&files);
+        let result = analysis.analyze_affected_files(&files, None);
         let json_report = result.to_json_report(&analysis);
 
         let standalone_crate = json_report
@@ -1225,7 +2080,7 @@ version = "0.1.0"
             "{}/real-workspace/crate-a/src/lib.rs",
             temp.path().display()
         )];
-        let result = analysis.analyze_affected_files(&files);
+        let result = analysis.analyze_affected_files(&files, None);
         let json_report = result.to_json_report(&analysis);
 
         let workspace_crate = json_report
@@ -1249,7 +2104,7 @@ version = "0.1.0"
             "{}/standalone-test-crate/src/lib.rs",
             temp.path().display()
         )];
-        let result = analysis.analyze_affected_files(&files);
+        let result = analysis.analyze_affected_files(&files, None);
         let json_report = result.to_json_report(&analysis);
 
         // Test that the JSON report can be serialized and includes the is_standalone
@@ -1281,7 +2136,7 @@ version = "0.1.0"
             format!("{}/my-workspace/crate-a/Cargo.toml", temp.path().display()),
             format!("{}/my-workspace/crate-b/Cargo.toml", temp.path().display()),
         ];
-        let result = analysis.analyze_affected_files(&files);
+        let result = analysis.analyze_affected_files(&files, None);
 
         // Both crates should be directly affected by their Cargo.toml files
         assert_eq!(result.directly_affected_crates.len(), 2);
@@ -1299,7 +2154,7 @@ version = "0.1.0"
 
         // Test workspace-level Cargo.toml
         let files = vec![format!("{}/my-workspace/Cargo.toml", temp.path().display())];
-        let result = analysis.analyze_affected_files(&files);
+        let result = analysis.analyze_affected_files(&files, None);
 
         // Workspace Cargo.toml should affect all workspace members
         assert_eq!(result.directly_affected_crates.len(), 2);
@@ -1319,7 +2174,7 @@ version = "0.1.0"
             format!("{}/real-workspace/Cargo.lock", temp.path().display()),
             format!("{}/standalone-test-crate/Cargo.lock", temp.path().display()),
         ];
-        let result = analysis.analyze_affected_files(&files);
+        let result = analysis.analyze_affected_files(&files, None);
 
         // Standalone crate's Cargo.lock should map to the crate
         assert!(contains_crate(
@@ -1342,7 +2197,7 @@ version = "0.1.0"
 
         // Test that changing Cargo.lock at workspace root affects all members
         let files = vec![format!("{}/my-workspace/Cargo.lock", temp.path().display())];
-        let result = analysis.analyze_affected_files(&files);
+        let result = analysis.analyze_affected_files(&files, None);
 
         // All workspace members should be directly affected
         assert!(contains_crate(&result.directly_affected_crates, "crate-a"));
@@ -1363,7 +2218,7 @@ version = "0.1.0"
 
         // Test that changing workspace Cargo.toml affects all members
         let files = vec![format!("{}/my-workspace/Cargo.toml", temp.path().display())];
-        let result = analysis.analyze_affected_files(&files);
+        let result = analysis.analyze_affected_files(&files, None);
 
         // All workspace members should be directly affected
         assert!(contains_crate(&result.directly_affected_crates, "crate-a"));
@@ -1387,7 +2242,7 @@ version = "0.1.0"
             "{}/standalone-test-crate/Cargo.lock",
             temp.path().display()
         )];
-        let result = analysis.analyze_affected_files(&files);
+        let result = analysis.analyze_affected_files(&files, None);
 
         // Only the standalone crate should be affected
         assert!(contains_crate(
@@ -1414,7 +2269,7 @@ version = "0.1.0"
             "{}/my-workspace/crate-b/Cargo.toml",
             temp.path().display()
         )];
-        let result = analysis.analyze_affected_files(&files);
+        let result = analysis.analyze_affected_files(&files, None);
 
         // crate-b should be directly affected
         assert!(contains_crate(&result.directly_affected_crates, "crate-b"));
@@ -1438,7 +2293,7 @@ version = "0.1.0"
             format!("{}/my-workspace/Cargo.lock", temp.path().display()),
             format!("{}/my-workspace/crate-a/Cargo.toml", temp.path().display()),
         ];
-        let result = analysis.analyze_affected_files(&files);
+        let result = analysis.analyze_affected_files(&files, None);
 
         // All crates should be directly affected
         assert!(contains_crate(&result.directly_affected_crates, "crate-a"));
@@ -1541,7 +2396,7 @@ name = "outer-crate"
             "{}/outer-workspace/inner-workspace/Cargo.lock",
             root.display()
         )];
-        let result = analysis.analyze_affected_files(&files);
+        let result = analysis.analyze_affected_files(&files, None);
 
         assert!(contains_crate(
             &result.directly_affected_crates,
@@ -1557,4 +2412,145 @@ name = "outer-crate"
         ));
         assert_eq!(result.directly_affected_crates.len(), 2);
     }
+
+    #[test]
+    fn test_cargo_commands_and_package_specs() {
+        let temp = create_test_workspace_with_duplicates();
+        let analysis = build_test_analysis(temp.path());
+        let files = vec![
+            format!(
+                "{}/workspace-a/phoenix-v2-api/src/lib.rs",
+                temp.path().display()
+            ),
+            format!(
+                "{}/workspace-b/phoenix-v2-api/src/main.rs",
+                temp.path().display()
+            ),
+        ];
+        let result = analysis.analyze_affected_files(&files, None);
+        let base = temp.path().canonicalize().unwrap();
+
+        assert_eq!(
+            cargo_commands(&analysis, &result.all_affected_crates, "test", &base, 1000),
+            vec![
+                "cargo test --manifest-path workspace-a/Cargo.toml -p other-crate -p \
+                 phoenix-v2-api",
+                "cargo test --manifest-path workspace-b/Cargo.toml -p consumer-crate -p \
+                 phoenix-v2-api",
+            ]
+        );
+        assert_eq!(
+            cargo_commands(
+                &analysis,
+                &result.directly_affected_crates,
+                "nextest run",
+                &base,
+                75
+            ),
+            vec![
+                "cargo nextest run --manifest-path workspace-a/Cargo.toml -p phoenix-v2-api",
+                "cargo nextest run --manifest-path workspace-b/Cargo.toml -p phoenix-v2-api",
+            ]
+        );
+
+        let specs = package_specs(&result.all_affected_crates);
+        assert_eq!(specs.len(), 4);
+        assert_eq!(specs[0], "consumer-crate");
+        assert_eq!(specs[1], "other-crate");
+        assert!(specs[2].starts_with("path+file:///"));
+        assert!(specs[2].ends_with("/workspace-a/phoenix-v2-api#phoenix-v2-api"));
+        assert!(specs[3].ends_with("/workspace-b/phoenix-v2-api#phoenix-v2-api"));
+    }
+
+    #[test]
+    fn test_github_matrix() {
+        let temp = create_test_workspace_with_duplicates();
+        let analysis = build_test_analysis(temp.path());
+        let files = vec![format!(
+            "{}/workspace-b/consumer-crate/src/lib.rs",
+            temp.path().display()
+        )];
+        let result = analysis.analyze_affected_files(&files, None);
+        let base = temp.path().canonicalize().unwrap();
+
+        let matrix = github_matrix(&analysis, &result.all_affected_crates, &base);
+        assert_eq!(
+            serde_json::to_string(&matrix).unwrap(),
+            r#"{"include":[{"workspace":"workspace-b","path":"workspace-b"}]}"#
+        );
+
+        let matrix = github_matrix(
+            &analysis,
+            &result.all_affected_crates,
+            &base.join("workspace-b"),
+        );
+        assert_eq!(matrix.include[0].path, ".");
+
+        let matrix = github_matrix(&analysis, &HashSet::new(), &base);
+        assert!(matrix.include.is_empty());
+    }
+
+    #[test]
+    fn test_impact_tree() {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path().join("tree");
+        fs::create_dir_all(&root).unwrap();
+        fs::write(
+            root.join("Cargo.toml"),
+            "[workspace]\nmembers = [\"base\", \"mid\", \"top\", \"app\", \"cli\"]\n",
+        )
+        .unwrap();
+        for (name, deps) in [
+            ("base", vec![]),
+            ("mid", vec!["base"]),
+            ("top", vec!["mid"]),
+            ("app", vec!["top"]),
+            ("cli", vec!["base"]),
+        ] {
+            fs::create_dir_all(root.join(name).join("src")).unwrap();
+            let mut manifest = format!("[package]\nname = \"{name}\"\n\n[dependencies]\n");
+            for dep in deps {
+                manifest.push_str(&format!("{dep} = {{ path = \"../{dep}\" }}\n"));
+            }
+            fs::write(root.join(name).join("Cargo.toml"), manifest).unwrap();
+            fs::write(root.join(name).join("src/lib.rs"), "").unwrap();
+        }
+
+        let analysis = build_test_analysis(temp.path());
+        let files = vec![format!("{}/base/src/lib.rs", root.display())];
+        let result = analysis.analyze_affected_files(&files, None);
+
+        assert_eq!(
+            impact_tree(&result, &analysis, false),
+            "base (tree)\n├── cli (tree)\n└── mid (tree)\n    └── top (tree)\n        └── app \
+             (tree)"
+        );
+        assert_eq!(impact_tree(&result, &analysis, true), "base (tree)");
+    }
+
+    #[test]
+    fn test_shell_quote() {
+        assert_eq!(shell_quote("core/Cargo.toml"), "core/Cargo.toml");
+        assert_eq!(shell_quote("my apps/Cargo.toml"), "'my apps/Cargo.toml'");
+        assert_eq!(shell_quote("it's"), r"'it'\''s'");
+    }
+
+    #[test]
+    fn test_cargo_package_args_are_deduplicated_and_chunked() {
+        let names = ["core", "api", "core", "cli", "utils"];
+
+        assert_eq!(
+            cargo_package_args(names, 1000),
+            vec!["-p api -p cli -p core -p utils"]
+        );
+        assert_eq!(
+            cargo_package_args(names, 16),
+            vec!["-p api -p cli", "-p core -p utils"]
+        );
+        assert_eq!(
+            cargo_package_args(["a-very-long-crate-name"], 8),
+            vec!["-p a-very-long-crate-name"]
+        );
+        assert!(cargo_package_args([], 1000).is_empty());
+    }
 }
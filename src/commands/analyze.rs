@@ -4,11 +4,14 @@ use miette::{Result, WrapErr};
 
 use crate::cli::Commands;
 use crate::common::{ConfigBuilder, FromCommand};
-use crate::config::AnalyzeCrateConfig;
+use crate::config::{AnalyzeCrateConfig, ProjectConfig};
 use crate::error::FerrisWheelError;
 
 impl FromCommand for AnalyzeCrateConfig {
-    fn from_command(command: Commands) -> Result<Self, FerrisWheelError> {
+    fn from_command(
+        command: Commands,
+        project: Option<&ProjectConfig>,
+    ) -> Result<Self, FerrisWheelError> {
         match command {
             Commands::Spotlight {
                 crate_name,
@@ -16,16 +19,33 @@ impl FromCommand for AnalyzeCrateConfig {
                 format,
                 cycle_display,
                 intra_workspace,
-            } => AnalyzeCrateConfig::builder()
-                .with_crate_name(crate_name)
-                .with_paths(common.get_paths())
-                .with_format(format.format)
-                .with_exclude_dev(common.exclude_dev)
-                .with_exclude_build(common.exclude_build)
-                .with_exclude_target(common.exclude_target)
-                .with_max_cycles(cycle_display.max_cycles)
-                .with_intra_workspace(intra_workspace)
-                .build(),
+            } => {
+                let paths = common.get_paths();
+
+                AnalyzeCrateConfig::builder()
+                    .with_crate_name(crate_name)
+                    .with_paths(paths)
+                    .with_format(format.format)
+                    .with_output(format.output.output)
+                    .with_exclude_dev(common.exclude_dev)
+                    .with_exclude_build(common.exclude_build)
+                    .with_exclude_target(common.exclude_target)
+                    .with_target(common.target)
+                    .with_max_workspaces(common.max_workspaces)
+                    .with_exclude_paths(common.exclude_paths.clone())
+                    .with_exclude_crates(common.exclude_crates.clone())
+                    .with_follow_symlinks(common.follow_symlinks)
+                    .with_no_cache(common.no_cache)
+                    .with_analyzer(common.analyzer_config())
+                    .with_workspace_names(common.workspace_names.clone())
+                    .with_verbosity(cycle_display.verbosity())
+                    .with_elementary_cycles(cycle_display.elementary_cycles())
+                    .with_intra_workspace(intra_workspace)
+                    .with_url_template(project.and_then(|p| p.url_template()))
+                    .with_severity_model(project.map(|p| p.severity).unwrap_or_default())
+                    .with_effort_model(project.map(|p| p.effort).unwrap_or_default())
+                    .build()
+            }
             _ => Err(FerrisWheelError::ConfigurationError {
                 message: "Invalid command type for AnalyzeCrateConfig".to_string(),
             }),
@@ -37,8 +57,8 @@ crate::impl_try_from_command!(AnalyzeCrateConfig);
 
 /// Execute the spotlight command for analyzing cycles involving a specific
 /// crate
-pub fn execute_analyze_command(command: Commands) -> Result<()> {
-    let config = AnalyzeCrateConfig::from_command(command)
+pub fn execute_analyze_command(command: Commands, project: Option<&ProjectConfig>) -> Result<()> {
+    let config = AnalyzeCrateConfig::from_command(command, project)
         .wrap_err("Failed to parse spotlight command configuration")?;
 
     use crate::executors::CommandExecutor;
@@ -0,0 +1,595 @@
+//! Between command implementation
+
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::fmt::Write;
+use std::path::PathBuf;
+
+use miette::{Result, WrapErr};
+use petgraph::graph::DiGraph;
+use petgraph::visit::EdgeRef;
+use serde::Serialize;
+
+use super::why::{split_node, workspace_crates};
+use crate::analyzer::{CrateMember, WorkspaceInfo};
+use crate::cli::Commands;
+use crate::common::{ConfigBuilder, FromCommand};
+use crate::config::{BetweenConfig, ProjectConfig};
+use crate::detector::CycleEdge;
+use crate::error::FerrisWheelError;
+use crate::graph::{DependencyEdge, WorkspaceNode};
+use crate::reports::links::ManifestLinks;
+use crate::utils::string::pluralize;
+
+impl FromCommand for BetweenConfig {
+    fn from_command(
+        command: Commands,
+        _project: Option<&ProjectConfig>,
+    ) -> Result<Self, FerrisWheelError> {
+        match command {
+            Commands::Between {
+                from,
+                to,
+                common,
+                format,
+            } => BetweenConfig::builder()
+                .with_from(from)
+                .with_to(to)
+                .with_paths(common.get_paths())
+                .with_format(format.format)
+                .with_output(format.output.output)
+                .with_exclude_dev(common.exclude_dev)
+                .with_exclude_build(common.exclude_build)
+                .with_exclude_target(common.exclude_target)
+                .with_target(common.target)
+                .with_max_workspaces(common.max_workspaces)
+                .with_exclude_paths(common.exclude_paths.clone())
+                .with_exclude_crates(common.exclude_crates.clone())
+                .with_follow_symlinks(common.follow_symlinks)
+                .with_no_cache(common.no_cache)
+                .with_analyzer(common.analyzer_config())
+                .with_workspace_names(common.workspace_names.clone())
+                .build(),
+            _ => Err(FerrisWheelError::ConfigurationError {
+                message: "Invalid command type for BetweenConfig".to_string(),
+            }),
+        }
+    }
+}
+
+crate::impl_try_from_command!(BetweenConfig);
+
+/// Execute the between command for listing the dependencies between two
+/// workspaces
+pub fn execute_between_command(command: Commands, project: Option<&ProjectConfig>) -> Result<()> {
+    let config = BetweenConfig::from_command(command, project)
+        .wrap_err("Failed to parse between command configuration")?;
+
+    use crate::executors::CommandExecutor;
+    use crate::executors::between::BetweenExecutor;
+    BetweenExecutor::execute(config)
+}
+
+/// A crate of one workspace depending on a crate of another
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct CrateDependency {
+    pub from_crate: String,
+    pub to_crate: String,
+    /// Dependency kinds, with the target for target-specific dependencies
+    pub dependency_types: Vec<String>,
+    /// Features the dependency enables
+    pub features: Vec<String>,
+    /// The manifest line declaring the dependency
+    pub manifest: Option<String>,
+}
+
+/// Counts for the dependencies in one direction
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct DirectionSummary {
+    pub dependencies: usize,
+    /// Distinct depending crates
+    pub from_crates: usize,
+    /// Distinct crates depended on
+    pub to_crates: usize,
+    pub by_type: BTreeMap<String, usize>,
+}
+
+impl DirectionSummary {
+    fn of(dependencies: &[CrateDependency]) -> Self {
+        let mut by_type = BTreeMap::new();
+        for dependency in dependencies {
+            for dependency_type in &dependency.dependency_types {
+                *by_type.entry(dependency_type.clone()).or_default() += 1;
+            }
+        }
+
+        Self {
+            dependencies: dependencies.len(),
+            from_crates: dependencies
+                .iter()
+                .map(|d| &d.from_crate)
+                .collect::<BTreeSet<_>>()
+                .len(),
+            to_crates: dependencies
+                .iter()
+                .map(|d| &d.to_crate)
+                .collect::<BTreeSet<_>>()
+                .len(),
+            by_type,
+        }
+    }
+}
+
+/// Every crate-level dependency between two workspaces, in both directions
+#[derive(Debug, Clone, Serialize)]
+pub struct WorkspaceBoundary {
+    pub from: String,
+    pub to: String,
+    /// Dependencies of `from` on `to`
+    pub uses: Vec<CrateDependency>,
+    /// Dependencies of `to` on `from`
+    pub used_by: Vec<CrateDependency>,
+}
+
+impl WorkspaceBoundary {
+    /// Collect the dependencies between workspaces `from` and `to`
+    ///
+    /// `graph` must be a crate graph as built by
+    /// [`DependencyGraphBuilder::build_crate_graph`](crate::graph::DependencyGraphBuilder::build_crate_graph);
+    /// `workspaces` supplies the features and manifests of each dependency.
+    pub fn between(
+        graph: &DiGraph<WorkspaceNode, DependencyEdge>,
+        workspaces: &HashMap<PathBuf, WorkspaceInfo>,
+        from: &str,
+        to: &str,
+    ) -> Result<Self, FerrisWheelError> {
+        if from == to {
+            return Err(FerrisWheelError::ConfigurationError {
+                message: format!("--from and --to must name different workspaces (got '{from}')"),
+            });
+        }
+        workspace_crates(graph, from)?;
+        workspace_crates(graph, to)?;
+
+        let links = ManifestLinks::from_workspaces(workspaces);
+        Ok(Self {
+            from: from.to_string(),
+            to: to.to_string(),
+            uses: crate_dependencies(graph, workspaces, &links, from, to)?,
+            used_by: crate_dependencies(graph, workspaces, &links, to, from)?,
+        })
+    }
+
+    pub fn uses_summary(&self) -> DirectionSummary {
+        DirectionSummary::of(&self.uses)
+    }
+
+    pub fn used_by_summary(&self) -> DirectionSummary {
+        DirectionSummary::of(&self.used_by)
+    }
+}
+
+fn crate_dependencies(
+    graph: &DiGraph<WorkspaceNode, DependencyEdge>,
+    workspaces: &HashMap<PathBuf, WorkspaceInfo>,
+    links: &ManifestLinks,
+    from: &str,
+    to: &str,
+) -> Result<Vec<CrateDependency>, FerrisWheelError> {
+    let mut grouped: BTreeMap<(String, String), BTreeSet<String>> = BTreeMap::new();
+    for edge in graph.edge_references() {
+        let (from_workspace, from_crate) = split_node(&graph[edge.source()]);
+        let (to_workspace, to_crate) = split_node(&graph[edge.target()]);
+        if from_workspace != from || to_workspace != to {
+            continue;
+        }
+
+        let dependency_type = match edge.weight().target() {
+            Some(target) => format!("{} ({target})", edge.weight().dependency_type().as_str()),
+            None => edge.weight().dependency_type().as_str().to_string(),
+        };
+        grouped
+            .entry((from_crate.to_string(), to_crate.to_string()))
+            .or_default()
+            .insert(dependency_type);
+    }
+
+    grouped
+        .into_iter()
+        .map(|((from_crate, to_crate), dependency_types)| {
+            let features = workspaces
+                .values()
+                .filter(|ws| ws.name() == from)
+                .flat_map(|ws| ws.members())
+                .filter(|member| member.name() == from_crate)
+                .flat_map(|member| enabled_features(member, &to_crate))
+                .collect::<BTreeSet<_>>()
+                .into_iter()
+                .collect();
+
+            let edge = CycleEdge::builder()
+                .from_workspace(from)
+                .to_workspace(to)
+                .from_crate(&from_crate)
+                .to_crate(&to_crate)
+                .dependency_type("Normal")
+                .build()?;
+            let manifest = links
+                .edge_location(&edge)
+                .map(|location| location.member.to_string());
+
+            Ok(CrateDependency {
+                from_crate,
+                to_crate,
+                dependency_types: dependency_types.into_iter().collect(),
+                features,
+                manifest,
+            })
+        })
+        .collect()
+}
+
+/// Features `member` enables on its dependencies named `crate_name`
+fn enabled_features(member: &CrateMember, crate_name: &str) -> Vec<String> {
+    member
+        .dependencies()
+        .iter()
+        .chain(member.dev_dependencies())
+        .chain(member.build_dependencies())
+        .chain(member.target_dependencies().values().flatten())
+        .filter(|dep| dep.name() == crate_name)
+        .flat_map(|dep| dep.features().iter().cloned())
+        .collect()
+}
+
+/// JSON output structure for the between command
+#[derive(Debug, Serialize)]
+struct BetweenJsonReport<'a> {
+    #[serde(flatten)]
+    boundary: &'a WorkspaceBoundary,
+    summary: BetweenJsonSummary,
+}
+
+#[derive(Debug, Serialize)]
+struct BetweenJsonSummary {
+    uses: DirectionSummary,
+    used_by: DirectionSummary,
+}
+
+/// Report generator for the dependencies between two workspaces
+pub struct BetweenReportGenerator;
+
+impl BetweenReportGenerator {
+    pub fn new() -> Self {
+        Self
+    }
+
+    pub fn generate_human_report(
+        &self,
+        boundary: &WorkspaceBoundary,
+    ) -> Result<String, FerrisWheelError> {
+        let mut output = String::new();
+
+        for (from, to, dependencies) in [
+            (&boundary.from, &boundary.to, &boundary.uses),
+            (&boundary.to, &boundary.from, &boundary.used_by),
+        ] {
+            if dependencies.is_empty() {
+                writeln!(output, "\n🔗 {from} → {to}: none")?;
+                continue;
+            }
+
+            writeln!(
+                output,
+                "\n🔗 {from} → {to}: {} crate {}\n",
+                dependencies.len(),
+                dependency_noun(dependencies.len())
+            )?;
+            for dependency in dependencies {
+                write!(
+                    output,
+                    "  📦 {} → {} ({})",
+                    dependency.from_crate,
+                    dependency.to_crate,
+                    dependency.dependency_types.join(", ")
+                )?;
+                if !dependency.features.is_empty() {
+                    write!(output, " [features: {}]", dependency.features.join(", "))?;
+                }
+                writeln!(output)?;
+                if let Some(manifest) = &dependency.manifest {
+                    writeln!(output, "     {manifest}")?;
+                }
+            }
+        }
+
+        writeln!(output, "\n📊 Summary:")?;
+        for (from, to, summary) in [
+            (&boundary.from, &boundary.to, boundary.uses_summary()),
+            (&boundary.to, &boundary.from, boundary.used_by_summary()),
+        ] {
+            writeln!(output, "  {from} → {to}: {}", describe(&summary))?;
+        }
+
+        Ok(output)
+    }
+
+    pub fn generate_json_report(
+        &self,
+        boundary: &WorkspaceBoundary,
+    ) -> Result<String, FerrisWheelError> {
+        let report = BetweenJsonReport {
+            boundary,
+            summary: BetweenJsonSummary {
+                uses: boundary.uses_summary(),
+                used_by: boundary.used_by_summary(),
+            },
+        };
+
+        Ok(serde_json::to_string_pretty(&report)?)
+    }
+
+    pub fn generate_junit_report(
+        &self,
+        boundary: &WorkspaceBoundary,
+    ) -> Result<String, FerrisWheelError> {
+        let mut output = String::new();
+
+        writeln!(output, r#"<?xml version="1.0" encoding="UTF-8"?>"#)?;
+        writeln!(
+            output,
+            r#"<testsuites name="workspace-between" tests="2" failures="0">"#
+        )?;
+        writeln!(
+            output,
+            r#"  <testsuite name="between-analysis" tests="2" failures="0">"#
+        )?;
+        for (from, to, dependencies) in [
+            (&boundary.from, &boundary.to, &boundary.uses),
+            (&boundary.to, &boundary.from, &boundary.used_by),
+        ] {
+            writeln!(
+                output,
+                r#"    <testcase name="{from}-uses-{to}" classname="ferris-wheel">"#
+            )?;
+            for dependency in dependencies {
+                writeln!(
+                    output,
+                    "  {}/{} -&gt; {}/{} ({})",
+                    from,
+                    dependency.from_crate,
+                    to,
+                    dependency.to_crate,
+                    dependency.dependency_types.join(", ")
+                )?;
+            }
+            writeln!(output, r#"    </testcase>"#)?;
+        }
+        writeln!(output, r#"  </testsuite>"#)?;
+        writeln!(output, r#"</testsuites>"#)?;
+
+        Ok(output)
+    }
+
+    pub fn generate_github_report(
+        &self,
+        boundary: &WorkspaceBoundary,
+    ) -> Result<String, FerrisWheelError> {
+        let mut output = String::new();
+
+        for (from, to, summary) in [
+            (&boundary.from, &boundary.to, boundary.uses_summary()),
+            (&boundary.to, &boundary.from, boundary.used_by_summary()),
+        ] {
+            writeln!(
+                output,
+                "::notice title=Workspace Boundary::{from} → {to}: {}",
+                describe(&summary)
+            )?;
+        }
+
+        Ok(output)
+    }
+}
+
+impl Default for BetweenReportGenerator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// One-line description of a direction's summary
+fn describe(summary: &DirectionSummary) -> String {
+    if summary.dependencies == 0 {
+        return "none".to_string();
+    }
+
+    let by_type = summary
+        .by_type
+        .iter()
+        .map(|(dependency_type, count)| format!("{dependency_type} {count}"))
+        .collect::<Vec<_>>()
+        .join(", ");
+    format!(
+        "{} {} from {} {} on {} {} ({by_type})",
+        summary.dependencies,
+        dependency_noun(summary.dependencies),
+        summary.from_crates,
+        pluralize("crate", summary.from_crates),
+        summary.to_crates,
+        pluralize("crate", summary.to_crates)
+    )
+}
+
+fn dependency_noun(count: usize) -> &'static str {
+    if count == 1 {
+        "dependency"
+    } else {
+        "dependencies"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analyzer::Dependency;
+    use crate::graph::DependencyType;
+
+    fn workspaces() -> HashMap<PathBuf, WorkspaceInfo> {
+        let member = |name: &str, dependencies: Vec<Dependency>| {
+            CrateMember::builder()
+                .with_name(name)
+                .with_path(format!("/repo/{name}"))
+                .with_dependencies(dependencies)
+                .build()
+                .unwrap()
+        };
+        let dependency = |name: &str, features: &[&str]| {
+            Dependency::builder()
+                .with_name(name)
+                .with_features(features.iter().map(|f| f.to_string()).collect())
+                .build()
+                .unwrap()
+        };
+
+        HashMap::from([
+            (
+                PathBuf::from("/repo/sdk"),
+                WorkspaceInfo::builder()
+                    .with_name("sdk")
+                    .with_members(vec![
+                        member("sdk-client", vec![dependency("core-types", &["serde"])]),
+                        member("sdk-macros", vec![]),
+                    ])
+                    .build()
+                    .unwrap(),
+            ),
+            (
+                PathBuf::from("/repo/core"),
+                WorkspaceInfo::builder()
+                    .with_name("core")
+                    .with_members(vec![member("core-types", vec![])])
+                    .build()
+                    .unwrap(),
+            ),
+        ])
+    }
+
+    fn crate_graph() -> DiGraph<WorkspaceNode, DependencyEdge> {
+        let mut graph = DiGraph::new();
+        let node = |graph: &mut DiGraph<WorkspaceNode, DependencyEdge>, workspace, name: &str| {
+            graph.add_node(
+                WorkspaceNode::builder()
+                    .with_name(format!("{workspace}/{name}"))
+                    .with_crates(vec![name.to_string()])
+                    .build()
+                    .unwrap(),
+            )
+        };
+        let client = node(&mut graph, "sdk", "sdk-client");
+        let macros = node(&mut graph, "sdk", "sdk-macros");
+        let types = node(&mut graph, "core", "core-types");
+
+        for (from, to, from_crate, to_crate, dependency_type, target) in [
+            (
+                client,
+                types,
+                "sdk-client",
+                "core-types",
+                DependencyType::Normal,
+                None,
+            ),
+            (
+                client,
+                types,
+                "sdk-client",
+                "core-types",
+                DependencyType::Dev,
+                None,
+            ),
+            (
+                macros,
+                types,
+                "sdk-macros",
+                "core-types",
+                DependencyType::Normal,
+                Some("cfg(unix)"),
+            ),
+            (
+                types,
+                macros,
+                "core-types",
+                "sdk-macros",
+                DependencyType::Build,
+                None,
+            ),
+        ] {
+            graph.add_edge(
+                from,
+                to,
+                DependencyEdge::builder()
+                    .with_from_crate(from_crate)
+                    .with_to_crate(to_crate)
+                    .with_dependency_type(dependency_type)
+                    .with_target(target)
+                    .build()
+                    .unwrap(),
+            );
+        }
+
+        graph
+    }
+
+    #[test]
+    fn test_dependencies_are_listed_in_both_directions() {
+        let boundary =
+            WorkspaceBoundary::between(&crate_graph(), &workspaces(), "sdk", "core").unwrap();
+
+        assert_eq!(boundary.uses.len(), 2);
+        assert_eq!(boundary.uses[0].from_crate, "sdk-client");
+        assert_eq!(boundary.uses[0].dependency_types, vec!["Dev", "Normal"]);
+        assert_eq!(boundary.uses[0].features, vec!["serde"]);
+        assert_eq!(
+            boundary.uses[0].manifest.as_deref(),
+            Some("/repo/sdk-client/Cargo.toml")
+        );
+        assert_eq!(
+            boundary.uses[1].dependency_types,
+            vec!["Normal (cfg(unix))"]
+        );
+
+        assert_eq!(boundary.used_by.len(), 1);
+        assert_eq!(boundary.used_by[0].to_crate, "sdk-macros");
+
+        let summary = boundary.uses_summary();
+        assert_eq!(summary.dependencies, 2);
+        assert_eq!(summary.from_crates, 2);
+        assert_eq!(summary.to_crates, 1);
+
+        assert!(WorkspaceBoundary::between(&crate_graph(), &workspaces(), "sdk", "sdk").is_err());
+        assert!(
+            WorkspaceBoundary::between(&crate_graph(), &workspaces(), "sdk", "missing").is_err()
+        );
+    }
+
+    #[test]
+    fn test_reports() {
+        let boundary =
+            WorkspaceBoundary::between(&crate_graph(), &workspaces(), "sdk", "core").unwrap();
+        let generator = BetweenReportGenerator::new();
+
+        let human = generator.generate_human_report(&boundary).unwrap();
+        assert!(human.contains("🔗 sdk → core: 2 crate dependencies"));
+        assert!(human.contains("  📦 sdk-client → core-types (Dev, Normal) [features: serde]"));
+        assert!(human.contains("🔗 core → sdk: 1 crate dependency\n"));
+        assert!(human.contains(
+            "  sdk → core: 2 dependencies from 2 crates on 1 crate (Dev 1, Normal 1, Normal \
+             (cfg(unix)) 1)"
+        ));
+
+        let json: serde_json::Value =
+            serde_json::from_str(&generator.generate_json_report(&boundary).unwrap()).unwrap();
+        assert_eq!(json["from"], "sdk");
+        assert_eq!(json["uses"][0]["features"][0], "serde");
+        assert_eq!(json["summary"]["used_by"]["dependencies"], 1);
+    }
+}
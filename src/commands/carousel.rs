@@ -0,0 +1,383 @@
+//! Carousel command implementation
+
+use std::fmt::Write;
+
+use miette::{Result, WrapErr};
+use petgraph::graph::DiGraph;
+use petgraph::visit::EdgeRef;
+use serde::Serialize;
+
+use crate::cli::Commands;
+use crate::common::{ConfigBuilder, FromCommand};
+use crate::config::{CarouselConfig, ProjectConfig};
+use crate::detector::{LayerModel, LayerViolation};
+use crate::error::FerrisWheelError;
+use crate::graph::{DependencyEdge, WorkspaceNode};
+use crate::utils::string::pluralize;
+
+impl FromCommand for CarouselConfig {
+    fn from_command(
+        command: Commands,
+        project: Option<&ProjectConfig>,
+    ) -> Result<Self, FerrisWheelError> {
+        match command {
+            Commands::Carousel {
+                layers,
+                common,
+                format,
+            } => {
+                let paths = common.get_paths();
+                let layers = match layers {
+                    Some(chain) => LayerModel::from_chain(&chain),
+                    None => project
+                        .map(|project| project.layers.clone())
+                        .unwrap_or_default(),
+                };
+                if layers.is_empty() {
+                    return Err(FerrisWheelError::ConfigurationError {
+                        message: format!(
+                            "No layers declared; add a [layers] section to {} or pass --layers",
+                            crate::constants::config::FILE_NAME
+                        ),
+                    });
+                }
+
+                CarouselConfig::builder()
+                    .with_layers(layers)
+                    .with_paths(paths)
+                    .with_format(format.format)
+                    .with_output(format.output.output)
+                    .with_exclude_dev(common.exclude_dev)
+                    .with_exclude_build(common.exclude_build)
+                    .with_exclude_target(common.exclude_target)
+                    .with_target(common.target)
+                    .with_max_workspaces(common.max_workspaces)
+                    .with_exclude_paths(common.exclude_paths.clone())
+                    .with_exclude_crates(common.exclude_crates.clone())
+                    .with_follow_symlinks(common.follow_symlinks)
+                    .with_no_cache(common.no_cache)
+                    .with_analyzer(common.analyzer_config())
+                    .with_workspace_names(common.workspace_names.clone())
+                    .build()
+            }
+            _ => Err(FerrisWheelError::ConfigurationError {
+                message: "Invalid command type for CarouselConfig".to_string(),
+            }),
+        }
+    }
+}
+
+crate::impl_try_from_command!(CarouselConfig);
+
+/// Execute the carousel command for enforcing the declared architecture
+/// layers
+pub fn execute_carousel_command(command: Commands, project: Option<&ProjectConfig>) -> Result<()> {
+    let config = CarouselConfig::from_command(command, project)
+        .wrap_err("Failed to parse carousel command configuration")?;
+
+    use crate::executors::CommandExecutor;
+    use crate::executors::carousel::CarouselExecutor;
+    CarouselExecutor::execute(config)
+}
+
+/// A crate dependency pointing from a lower layer to a higher one
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct UpwardDependency {
+    pub from_workspace: String,
+    pub to_workspace: String,
+    pub from_crate: String,
+    pub to_crate: String,
+    pub dependency_type: String,
+}
+
+/// Result of checking a workspace graph against the declared layers
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct LayerReport {
+    /// Layer names, lowest first
+    pub layers: Vec<String>,
+    pub violations: Vec<LayerViolation>,
+    /// Crate dependencies behind the violations, sorted by workspace and
+    /// crate names
+    pub dependencies: Vec<UpwardDependency>,
+    /// Workspaces that match no layer and were not checked
+    pub unassigned: Vec<String>,
+}
+
+impl LayerReport {
+    /// Check every dependency in a workspace graph against `layers`
+    pub fn check(
+        layers: &LayerModel,
+        graph: &DiGraph<WorkspaceNode, DependencyEdge>,
+    ) -> Result<Self, FerrisWheelError> {
+        let violations = layers.violations(graph)?;
+
+        let mut dependencies: Vec<UpwardDependency> = graph
+            .edge_references()
+            .filter_map(|edge| {
+                let from = graph[edge.source()].name();
+                let to = graph[edge.target()].name();
+                violations
+                    .iter()
+                    .any(|v| v.from_workspace == from && v.to_workspace == to)
+                    .then(|| UpwardDependency {
+                        from_workspace: from.to_string(),
+                        to_workspace: to.to_string(),
+                        from_crate: edge.weight().from_crate().to_string(),
+                        to_crate: edge.weight().to_crate().to_string(),
+                        dependency_type: edge.weight().dependency_type().as_str().to_string(),
+                    })
+            })
+            .collect();
+        dependencies.sort_by(|a, b| {
+            (
+                &a.from_workspace,
+                &a.to_workspace,
+                &a.from_crate,
+                &a.to_crate,
+            )
+                .cmp(&(
+                    &b.from_workspace,
+                    &b.to_workspace,
+                    &b.from_crate,
+                    &b.to_crate,
+                ))
+        });
+
+        Ok(Self {
+            layers: layers.order.clone(),
+            violations,
+            dependencies,
+            unassigned: layers.unassigned(graph)?,
+        })
+    }
+
+    pub fn has_violations(&self) -> bool {
+        !self.violations.is_empty()
+    }
+
+    /// Crate dependencies behind one violation
+    fn dependencies_of<'a>(
+        &'a self,
+        violation: &'a LayerViolation,
+    ) -> impl Iterator<Item = &'a UpwardDependency> {
+        self.dependencies.iter().filter(|dependency| {
+            dependency.from_workspace == violation.from_workspace
+                && dependency.to_workspace == violation.to_workspace
+        })
+    }
+}
+
+/// Report generator for layer enforcement results
+pub struct CarouselReportGenerator;
+
+impl CarouselReportGenerator {
+    pub fn new() -> Self {
+        Self
+    }
+
+    pub fn generate_human_report(&self, report: &LayerReport) -> Result<String, FerrisWheelError> {
+        let mut output = String::new();
+
+        writeln!(output, "\n🎠 Layers: {}", report.layers.join(" < "))?;
+
+        if report.has_violations() {
+            writeln!(
+                output,
+                "\n🚫 {} layer {}:\n",
+                report.violations.len(),
+                pluralize("violation", report.violations.len())
+            )?;
+            for violation in &report.violations {
+                writeln!(
+                    output,
+                    "  → {} ({}) → {} ({}): {} crate {}",
+                    violation.from_workspace,
+                    violation.from_layer,
+                    violation.to_workspace,
+                    violation.to_layer,
+                    violation.edges,
+                    pluralize("edge", violation.edges)
+                )?;
+                for dependency in report.dependencies_of(violation) {
+                    writeln!(
+                        output,
+                        "      📦 {} → {} ({})",
+                        dependency.from_crate, dependency.to_crate, dependency.dependency_type
+                    )?;
+                }
+            }
+        } else {
+            writeln!(output, "\n✅ No dependency points to a higher layer")?;
+        }
+
+        if !report.unassigned.is_empty() {
+            writeln!(
+                output,
+                "\nℹ️  Not assigned to a layer: {}",
+                report.unassigned.join(", ")
+            )?;
+        }
+
+        Ok(output)
+    }
+
+    pub fn generate_json_report(&self, report: &LayerReport) -> Result<String, FerrisWheelError> {
+        Ok(serde_json::to_string_pretty(report)?)
+    }
+
+    pub fn generate_junit_report(&self, report: &LayerReport) -> Result<String, FerrisWheelError> {
+        let mut output = String::new();
+        let failures = if report.has_violations() { 1 } else { 0 };
+
+        writeln!(output, r#"<?xml version="1.0" encoding="UTF-8"?>"#)?;
+        writeln!(
+            output,
+            r#"<testsuites name="workspace-layers" tests="1" failures="{failures}">"#
+        )?;
+        writeln!(
+            output,
+            r#"  <testsuite name="layer-analysis" tests="1" failures="{failures}">"#
+        )?;
+        writeln!(
+            output,
+            r#"    <testcase name="check-layers" classname="ferris-wheel">"#
+        )?;
+        if report.has_violations() {
+            writeln!(
+                output,
+                r#"      <failure message="Dependencies point to a higher layer">"#
+            )?;
+            for violation in &report.violations {
+                writeln!(
+                    output,
+                    "{} ({}) -&gt; {} ({}): {} crate {}",
+                    violation.from_workspace,
+                    violation.from_layer,
+                    violation.to_workspace,
+                    violation.to_layer,
+                    violation.edges,
+                    pluralize("edge", violation.edges)
+                )?;
+            }
+            writeln!(output, r#"      </failure>"#)?;
+        }
+        writeln!(output, r#"    </testcase>"#)?;
+        writeln!(output, r#"  </testsuite>"#)?;
+        writeln!(output, r#"</testsuites>"#)?;
+
+        Ok(output)
+    }
+
+    pub fn generate_github_report(&self, report: &LayerReport) -> Result<String, FerrisWheelError> {
+        let mut output = String::new();
+
+        if !report.has_violations() {
+            writeln!(
+                output,
+                "::notice title=Layers::No dependency points to a higher layer"
+            )?;
+        }
+        for violation in &report.violations {
+            writeln!(
+                output,
+                "::error title=Layer Violation::{} ({}) → {} ({}): {} crate {}",
+                violation.from_workspace,
+                violation.from_layer,
+                violation.to_workspace,
+                violation.to_layer,
+                violation.edges,
+                pluralize("edge", violation.edges)
+            )?;
+        }
+
+        Ok(output)
+    }
+}
+
+impl Default for CarouselReportGenerator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+    use crate::graph::DependencyType;
+
+    fn graph(edges: &[(&str, &str, &str, &str)]) -> DiGraph<WorkspaceNode, DependencyEdge> {
+        let mut graph = DiGraph::new();
+        let mut nodes = HashMap::new();
+        for &(from_ws, from_crate, to_ws, to_crate) in edges {
+            for name in [from_ws, to_ws] {
+                nodes.entry(name).or_insert_with(|| {
+                    graph.add_node(
+                        WorkspaceNode::builder()
+                            .with_name(name.to_string())
+                            .with_crates(vec![format!("{name}-lib")])
+                            .build()
+                            .unwrap(),
+                    )
+                });
+            }
+            graph.add_edge(
+                nodes[from_ws],
+                nodes[to_ws],
+                DependencyEdge::builder()
+                    .with_from_crate(from_crate)
+                    .with_to_crate(to_crate)
+                    .with_dependency_type(DependencyType::Normal)
+                    .build()
+                    .unwrap(),
+            );
+        }
+        graph
+    }
+
+    #[test]
+    fn test_upward_dependencies_are_listed_by_crate() {
+        let graph = graph(&[
+            ("app-web", "web", "core", "core-types"),
+            ("core", "core-types", "app-web", "web-api"),
+            ("core", "core-io", "app-web", "web"),
+            ("scripts", "scripts", "app-web", "web"),
+        ]);
+
+        let report = LayerReport::check(&LayerModel::from_chain("core < app-*"), &graph).unwrap();
+
+        assert!(report.has_violations());
+        assert_eq!(report.violations.len(), 1);
+        let crates: Vec<_> = report
+            .dependencies
+            .iter()
+            .map(|d| (d.from_crate.as_str(), d.to_crate.as_str()))
+            .collect();
+        assert_eq!(crates, vec![("core-io", "web"), ("core-types", "web-api")]);
+        assert_eq!(report.unassigned, vec!["scripts"]);
+
+        let generator = CarouselReportGenerator::new();
+        let human = generator.generate_human_report(&report).unwrap();
+        assert!(human.contains("core (core) → app-web (app-*): 2 crate edges"));
+        assert!(human.contains("📦 core-io → web (Normal)"));
+        let junit = generator.generate_junit_report(&report).unwrap();
+        assert!(junit.contains(r#"failures="1""#));
+        let github = generator.generate_github_report(&report).unwrap();
+        assert!(github.starts_with("::error title=Layer Violation::"));
+    }
+
+    #[test]
+    fn test_downward_dependencies_pass() {
+        let graph = graph(&[("app-web", "web", "core", "core-types")]);
+
+        let report = LayerReport::check(&LayerModel::from_chain("core < app-*"), &graph).unwrap();
+
+        assert!(!report.has_violations());
+        assert!(report.dependencies.is_empty());
+        let human = CarouselReportGenerator::new()
+            .generate_human_report(&report)
+            .unwrap();
+        assert!(human.contains("No dependency points to a higher layer"));
+    }
+}
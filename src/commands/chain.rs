@@ -0,0 +1,376 @@
+//! Chain command implementation
+
+use std::fmt::Write;
+
+use miette::{Result, WrapErr};
+use petgraph::graph::{DiGraph, NodeIndex};
+use serde::Serialize;
+
+use super::why::{CrateLink, crate_links, split_node};
+use crate::cli::Commands;
+use crate::common::{ConfigBuilder, FromCommand};
+use crate::config::{ChainConfig, ProjectConfig};
+use crate::error::FerrisWheelError;
+use crate::graph::{DependencyEdge, WorkspaceNode, simple_paths};
+use crate::utils::string::pluralize;
+
+impl FromCommand for ChainConfig {
+    fn from_command(
+        command: Commands,
+        _project: Option<&ProjectConfig>,
+    ) -> Result<Self, FerrisWheelError> {
+        match command {
+            Commands::Chain {
+                from,
+                to,
+                max_paths,
+                all,
+                common,
+                format,
+            } => ChainConfig::builder()
+                .with_from(from)
+                .with_to(to)
+                .with_max_paths((!all).then_some(max_paths))
+                .with_paths(common.get_paths())
+                .with_format(format.format)
+                .with_output(format.output.output)
+                .with_exclude_dev(common.exclude_dev)
+                .with_exclude_build(common.exclude_build)
+                .with_exclude_target(common.exclude_target)
+                .with_target(common.target)
+                .with_max_workspaces(common.max_workspaces)
+                .with_exclude_paths(common.exclude_paths.clone())
+                .with_exclude_crates(common.exclude_crates.clone())
+                .with_follow_symlinks(common.follow_symlinks)
+                .with_no_cache(common.no_cache)
+                .with_analyzer(common.analyzer_config())
+                .with_workspace_names(common.workspace_names.clone())
+                .build(),
+            _ => Err(FerrisWheelError::ConfigurationError {
+                message: "Invalid command type for ChainConfig".to_string(),
+            }),
+        }
+    }
+}
+
+crate::impl_try_from_command!(ChainConfig);
+
+/// Execute the chain command for listing the dependency chains between two
+/// crates
+pub fn execute_chain_command(command: Commands, project: Option<&ProjectConfig>) -> Result<()> {
+    let config = ChainConfig::from_command(command, project)
+        .wrap_err("Failed to parse chain command configuration")?;
+
+    use crate::executors::CommandExecutor;
+    use crate::executors::chain::ChainExecutor;
+    ChainExecutor::execute(config)
+}
+
+/// Find the dependency chains from crate `from` to crate `to`, shortest
+/// first
+///
+/// `graph` must be a crate graph as built by
+/// [`DependencyGraphBuilder::build_crate_graph`](crate::graph::DependencyGraphBuilder::build_crate_graph).
+/// Crates are named as `crate` or `workspace/crate`. At most `limit` chains
+/// are returned, or every chain when `limit` is `None`.
+pub fn find_crate_chains(
+    graph: &DiGraph<WorkspaceNode, DependencyEdge>,
+    from: &str,
+    to: &str,
+    limit: Option<usize>,
+) -> Result<Vec<Vec<CrateLink>>, FerrisWheelError> {
+    let sources = crate_nodes(graph, from)?;
+    let targets = crate_nodes(graph, to)?;
+    if sources.iter().any(|source| targets.contains(source)) {
+        return Err(FerrisWheelError::ConfigurationError {
+            message: format!("--from and --to must name different crates (got '{from}')"),
+        });
+    }
+
+    Ok(simple_paths(graph, &sources, &targets, limit)
+        .iter()
+        .map(|path| crate_links(graph, path))
+        .collect())
+}
+
+/// Crate graph nodes named `name`, either by crate name alone or as
+/// `workspace/crate`
+fn crate_nodes(
+    graph: &DiGraph<WorkspaceNode, DependencyEdge>,
+    name: &str,
+) -> Result<Vec<NodeIndex>, FerrisWheelError> {
+    let nodes: Vec<NodeIndex> = graph
+        .node_indices()
+        .filter(|&idx| graph[idx].name() == name || split_node(&graph[idx]).1 == name)
+        .collect();
+
+    if nodes.is_empty() {
+        return Err(FerrisWheelError::ConfigurationError {
+            message: format!("Unknown crate '{name}'"),
+        });
+    }
+
+    Ok(nodes)
+}
+
+/// JSON output structure for the chain command
+#[derive(Debug, Serialize)]
+struct ChainJsonReport<'a> {
+    from: &'a str,
+    to: &'a str,
+    depends: bool,
+    chains: &'a [Vec<CrateLink>],
+}
+
+/// Report generator for crate-level dependency chains
+pub struct ChainReportGenerator<'a> {
+    from: &'a str,
+    to: &'a str,
+}
+
+impl<'a> ChainReportGenerator<'a> {
+    pub fn new(from: &'a str, to: &'a str) -> Self {
+        Self { from, to }
+    }
+
+    pub fn generate_human_report(
+        &self,
+        chains: &[Vec<CrateLink>],
+    ) -> Result<String, FerrisWheelError> {
+        let mut output = String::new();
+
+        if chains.is_empty() {
+            writeln!(output, "\n✅ {} does not depend on {}", self.from, self.to)?;
+            return Ok(output);
+        }
+
+        writeln!(
+            output,
+            "\n⛓️  {} {} from {} to {}:",
+            chains.len(),
+            pluralize("chain", chains.len()),
+            self.from,
+            self.to
+        )?;
+
+        for (i, links) in chains.iter().enumerate() {
+            writeln!(
+                output,
+                "\n  Chain {} ({} {}):",
+                i + 1,
+                links.len(),
+                pluralize("hop", links.len())
+            )?;
+            if let Some(first) = links.first() {
+                writeln!(
+                    output,
+                    "    📦 {}/{}",
+                    first.from_workspace, first.from_crate
+                )?;
+            }
+            for link in links {
+                writeln!(output, "      ↓ {}", link.dependency_types.join(", "))?;
+                writeln!(output, "    📦 {}/{}", link.to_workspace, link.to_crate)?;
+            }
+        }
+
+        Ok(output)
+    }
+
+    pub fn generate_json_report(
+        &self,
+        chains: &[Vec<CrateLink>],
+    ) -> Result<String, FerrisWheelError> {
+        let report = ChainJsonReport {
+            from: self.from,
+            to: self.to,
+            depends: !chains.is_empty(),
+            chains,
+        };
+
+        Ok(serde_json::to_string_pretty(&report)?)
+    }
+
+    pub fn generate_junit_report(
+        &self,
+        chains: &[Vec<CrateLink>],
+    ) -> Result<String, FerrisWheelError> {
+        let mut output = String::new();
+
+        writeln!(output, r#"<?xml version="1.0" encoding="UTF-8"?>"#)?;
+        writeln!(
+            output,
+            r#"<testsuites name="crate-chains" tests="1" failures="0">"#
+        )?;
+        writeln!(
+            output,
+            r#"  <testsuite name="chain-analysis" tests="1" failures="0">"#
+        )?;
+        writeln!(
+            output,
+            r#"    <testcase name="list-crate-chains" classname="ferris-wheel">"#
+        )?;
+        if chains.is_empty() {
+            writeln!(output, "{} does not depend on {}", self.from, self.to)?;
+        }
+        for links in chains {
+            writeln!(output, "{}", describe(links).replace('→', "-&gt;"))?;
+        }
+        writeln!(output, r#"    </testcase>"#)?;
+        writeln!(output, r#"  </testsuite>"#)?;
+        writeln!(output, r#"</testsuites>"#)?;
+
+        Ok(output)
+    }
+
+    pub fn generate_github_report(
+        &self,
+        chains: &[Vec<CrateLink>],
+    ) -> Result<String, FerrisWheelError> {
+        let mut output = String::new();
+
+        if chains.is_empty() {
+            writeln!(
+                output,
+                "::notice title=Dependency Chain::{} does not depend on {}",
+                self.from, self.to
+            )?;
+        }
+        for links in chains {
+            writeln!(
+                output,
+                "::notice title=Dependency Chain::{}",
+                describe(links)
+            )?;
+        }
+
+        Ok(output)
+    }
+}
+
+/// One-line description of a chain, e.g. `a/x → (Normal) b/y`
+fn describe(links: &[CrateLink]) -> String {
+    let mut description = links
+        .first()
+        .map(|link| format!("{}/{}", link.from_workspace, link.from_crate))
+        .unwrap_or_default();
+    for link in links {
+        description.push_str(&format!(
+            " → ({}) {}/{}",
+            link.dependency_types.join(", "),
+            link.to_workspace,
+            link.to_crate
+        ));
+    }
+    description
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::DependencyType;
+
+    fn create_crate_graph() -> DiGraph<WorkspaceNode, DependencyEdge> {
+        let mut graph = DiGraph::new();
+        let nodes: Vec<NodeIndex> = [
+            ("tools", "tools-cli"),
+            ("tools", "tools-lib"),
+            ("utils", "utils-fs"),
+            ("core", "core-api"),
+            ("core", "core-types"),
+        ]
+        .iter()
+        .map(|(workspace, name)| {
+            graph.add_node(
+                WorkspaceNode::builder()
+                    .with_name(format!("{workspace}/{name}"))
+                    .with_crates(vec![name.to_string()])
+                    .build()
+                    .unwrap(),
+            )
+        })
+        .collect();
+
+        // tools-cli -> tools-lib -> utils-fs -> core-types
+        // tools-cli -> core-api -> core-types
+        for (from, to, dependency_type) in [
+            (0, 1, DependencyType::Normal),
+            (1, 2, DependencyType::Normal),
+            (1, 2, DependencyType::Dev),
+            (2, 4, DependencyType::Build),
+            (0, 3, DependencyType::Normal),
+            (3, 4, DependencyType::Normal),
+        ] {
+            graph.add_edge(
+                nodes[from],
+                nodes[to],
+                DependencyEdge::builder()
+                    .with_from_crate(graph[nodes[from]].crates()[0].as_str())
+                    .with_to_crate(graph[nodes[to]].crates()[0].as_str())
+                    .with_dependency_type(dependency_type)
+                    .build()
+                    .unwrap(),
+            );
+        }
+
+        graph
+    }
+
+    #[test]
+    fn test_chains_are_listed_shortest_first() {
+        let graph = create_crate_graph();
+
+        let chains = find_crate_chains(&graph, "tools-cli", "core/core-types", None).unwrap();
+
+        let descriptions: Vec<String> = chains.iter().map(|links| describe(links)).collect();
+        assert_eq!(
+            descriptions,
+            vec![
+                "tools/tools-cli → (Normal) core/core-api → (Normal) core/core-types",
+                "tools/tools-cli → (Normal) tools/tools-lib → (Dev, Normal) utils/utils-fs → \
+                 (Build) core/core-types",
+            ]
+        );
+
+        assert_eq!(
+            find_crate_chains(&graph, "tools-cli", "core-types", Some(1))
+                .unwrap()
+                .len(),
+            1
+        );
+        assert!(
+            find_crate_chains(&graph, "core-types", "tools-cli", None)
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_unknown_and_identical_crates_are_rejected() {
+        let graph = create_crate_graph();
+
+        assert!(find_crate_chains(&graph, "missing", "core-types", None).is_err());
+        assert!(find_crate_chains(&graph, "core-types", "core/core-types", None).is_err());
+    }
+
+    #[test]
+    fn test_reports() {
+        let graph = create_crate_graph();
+        let chains = find_crate_chains(&graph, "tools-cli", "core-types", None).unwrap();
+        let generator = ChainReportGenerator::new("tools-cli", "core-types");
+
+        let human = generator.generate_human_report(&chains).unwrap();
+        assert!(human.contains("2 chains from tools-cli to core-types"));
+        assert!(human.contains("Chain 2 (3 hops):"));
+        assert!(human.contains("↓ Dev, Normal"));
+
+        let json: serde_json::Value =
+            serde_json::from_str(&generator.generate_json_report(&chains).unwrap()).unwrap();
+        assert_eq!(json["depends"], true);
+        assert_eq!(json["chains"].as_array().unwrap().len(), 2);
+        assert_eq!(json["chains"][0][0]["to_crate"], "core-api");
+
+        let none = generator.generate_human_report(&[]).unwrap();
+        assert!(none.contains("tools-cli does not depend on core-types"));
+    }
+}
@@ -0,0 +1,374 @@
+//! Changelog command implementation
+
+use std::collections::BTreeSet;
+use std::fmt::Write;
+
+use miette::{Result, WrapErr};
+use petgraph::graph::DiGraph;
+use petgraph::visit::EdgeRef;
+use serde::Serialize;
+
+use crate::cli::Commands;
+use crate::common::{ConfigBuilder, FromCommand};
+use crate::config::{ChangelogConfig, ProjectConfig};
+use crate::detector::WorkspaceCycle;
+use crate::error::FerrisWheelError;
+use crate::graph::{DependencyEdge, WorkspaceNode};
+
+impl FromCommand for ChangelogConfig {
+    fn from_command(
+        command: Commands,
+        _project: Option<&ProjectConfig>,
+    ) -> Result<Self, FerrisWheelError> {
+        match command {
+            Commands::Changelog {
+                from,
+                to,
+                common,
+                output,
+            } => ChangelogConfig::builder()
+                .with_from(from)
+                .with_to(to)
+                .with_paths(common.get_paths())
+                .with_output(output.output)
+                .with_exclude_dev(common.exclude_dev)
+                .with_exclude_build(common.exclude_build)
+                .with_exclude_target(common.exclude_target)
+                .with_target(common.target)
+                .with_max_workspaces(common.max_workspaces)
+                .with_exclude_paths(common.exclude_paths.clone())
+                .with_exclude_crates(common.exclude_crates.clone())
+                .with_follow_symlinks(common.follow_symlinks)
+                .with_no_cache(common.no_cache)
+                .with_analyzer(common.analyzer_config())
+                .with_workspace_names(common.workspace_names.clone())
+                .build(),
+            _ => Err(FerrisWheelError::ConfigurationError {
+                message: "Invalid command type for ChangelogConfig".to_string(),
+            }),
+        }
+    }
+}
+
+crate::impl_try_from_command!(ChangelogConfig);
+
+/// Execute the changelog command for summarizing structural changes
+pub fn execute_changelog_command(command: Commands, project: Option<&ProjectConfig>) -> Result<()> {
+    let config = ChangelogConfig::from_command(command, project)
+        .wrap_err("Failed to parse changelog command configuration")?;
+
+    use crate::executors::CommandExecutor;
+    use crate::executors::changelog::ChangelogExecutor;
+    ChangelogExecutor::execute(config)
+}
+
+/// A cross-workspace dependency, identified by its endpoints and kind
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+pub struct StructuralEdge {
+    pub from_workspace: String,
+    pub to_workspace: String,
+    pub from_crate: String,
+    pub to_crate: String,
+    pub dependency_type: String,
+}
+
+/// The workspace structure of the repository at one point in time
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct StructureSnapshot {
+    workspaces: BTreeSet<String>,
+    edges: BTreeSet<StructuralEdge>,
+    cycles: BTreeSet<Vec<String>>,
+}
+
+impl StructureSnapshot {
+    /// Capture the workspaces, edges and cycles of a workspace graph
+    pub fn from_graph(
+        graph: &DiGraph<WorkspaceNode, DependencyEdge>,
+        cycles: &[WorkspaceCycle],
+    ) -> Self {
+        let workspaces = graph
+            .node_indices()
+            .map(|idx| graph[idx].name().to_string())
+            .collect();
+
+        let edges = graph
+            .edge_references()
+            .map(|edge| StructuralEdge {
+                from_workspace: graph[edge.source()].name().to_string(),
+                to_workspace: graph[edge.target()].name().to_string(),
+                from_crate: edge.weight().from_crate().to_string(),
+                to_crate: edge.weight().to_crate().to_string(),
+                dependency_type: edge.weight().dependency_type().as_str().to_lowercase(),
+            })
+            .collect();
+
+        let cycles = cycles
+            .iter()
+            .map(|cycle| {
+                cycle
+                    .workspace_names()
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect()
+            })
+            .collect();
+
+        Self {
+            workspaces,
+            edges,
+            cycles,
+        }
+    }
+
+    /// Cycles of the snapshot, each as its workspace names
+    pub fn cycles(&self) -> &BTreeSet<Vec<String>> {
+        &self.cycles
+    }
+
+    /// Compute what changed going from `self` to `newer`
+    pub fn diff(&self, newer: &StructureSnapshot) -> StructuralChanges {
+        StructuralChanges {
+            added_workspaces: newer
+                .workspaces
+                .difference(&self.workspaces)
+                .cloned()
+                .collect(),
+            removed_workspaces: self
+                .workspaces
+                .difference(&newer.workspaces)
+                .cloned()
+                .collect(),
+            added_edges: newer.edges.difference(&self.edges).cloned().collect(),
+            removed_edges: self.edges.difference(&newer.edges).cloned().collect(),
+            introduced_cycles: newer.cycles.difference(&self.cycles).cloned().collect(),
+            fixed_cycles: self.cycles.difference(&newer.cycles).cloned().collect(),
+        }
+    }
+}
+
+/// Structural differences between two snapshots
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct StructuralChanges {
+    pub added_workspaces: Vec<String>,
+    pub removed_workspaces: Vec<String>,
+    pub added_edges: Vec<StructuralEdge>,
+    pub removed_edges: Vec<StructuralEdge>,
+    pub introduced_cycles: Vec<Vec<String>>,
+    pub fixed_cycles: Vec<Vec<String>>,
+}
+
+impl StructuralChanges {
+    pub fn is_empty(&self) -> bool {
+        self.added_workspaces.is_empty()
+            && self.removed_workspaces.is_empty()
+            && self.added_edges.is_empty()
+            && self.removed_edges.is_empty()
+            && self.introduced_cycles.is_empty()
+            && self.fixed_cycles.is_empty()
+    }
+
+    /// Render the changes as a Markdown section for release notes
+    pub fn to_markdown(&self, from: &str, to: &str) -> Result<String, FerrisWheelError> {
+        let mut output = String::new();
+
+        writeln!(output, "## Workspace dependency changes ({from}...{to})")?;
+
+        if self.is_empty() {
+            writeln!(output, "\n_No structural changes._")?;
+            return Ok(output);
+        }
+
+        write_list(
+            &mut output,
+            "New workspaces",
+            self.added_workspaces.iter().map(|ws| format!("`{ws}`")),
+        )?;
+        write_list(
+            &mut output,
+            "Removed workspaces",
+            self.removed_workspaces.iter().map(|ws| format!("`{ws}`")),
+        )?;
+        write_list(
+            &mut output,
+            "Added cross-workspace dependencies",
+            self.added_edges.iter().map(format_edge),
+        )?;
+        write_list(
+            &mut output,
+            "Removed cross-workspace dependencies",
+            self.removed_edges.iter().map(format_edge),
+        )?;
+        write_list(
+            &mut output,
+            "Cycles introduced",
+            self.introduced_cycles.iter().map(|c| format_cycle(c)),
+        )?;
+        write_list(
+            &mut output,
+            "Cycles fixed",
+            self.fixed_cycles.iter().map(|c| format_cycle(c)),
+        )?;
+
+        Ok(output)
+    }
+
+    /// Render the changes as a short indented list, one change per line
+    pub fn to_delta(&self) -> Result<String, FerrisWheelError> {
+        let mut output = String::new();
+
+        if self.is_empty() {
+            writeln!(output, "  No dependency changes")?;
+            return Ok(output);
+        }
+
+        for cycle in &self.introduced_cycles {
+            writeln!(output, "  ❌ New cycle: {}", cycle.join(" ↔ "))?;
+        }
+        for cycle in &self.fixed_cycles {
+            writeln!(output, "  🎉 Fixed cycle: {}", cycle.join(" ↔ "))?;
+        }
+        for workspace in &self.added_workspaces {
+            writeln!(output, "  ➕ Workspace {workspace}")?;
+        }
+        for workspace in &self.removed_workspaces {
+            writeln!(output, "  ➖ Workspace {workspace}")?;
+        }
+        for edge in &self.added_edges {
+            writeln!(output, "  ➕ {}", describe_edge(edge))?;
+        }
+        for edge in &self.removed_edges {
+            writeln!(output, "  ➖ {}", describe_edge(edge))?;
+        }
+
+        Ok(output)
+    }
+}
+
+fn write_list(
+    output: &mut String,
+    heading: &str,
+    items: impl Iterator<Item = String>,
+) -> Result<(), FerrisWheelError> {
+    let items: Vec<String> = items.collect();
+    if items.is_empty() {
+        return Ok(());
+    }
+
+    writeln!(output, "\n### {heading}\n")?;
+    for item in items {
+        writeln!(output, "- {item}")?;
+    }
+    Ok(())
+}
+
+fn format_edge(edge: &StructuralEdge) -> String {
+    format!(
+        "`{}` → `{}` (`{}` → `{}`, {})",
+        edge.from_workspace,
+        edge.to_workspace,
+        edge.from_crate,
+        edge.to_crate,
+        edge.dependency_type
+    )
+}
+
+/// One-line description of an edge, e.g. `app → core (app-web → core-api,
+/// normal)`
+pub(crate) fn describe_edge(edge: &StructuralEdge) -> String {
+    format!(
+        "{} → {} ({} → {}, {})",
+        edge.from_workspace,
+        edge.to_workspace,
+        edge.from_crate,
+        edge.to_crate,
+        edge.dependency_type
+    )
+}
+
+fn format_cycle(workspaces: &[String]) -> String {
+    workspaces
+        .iter()
+        .map(|ws| format!("`{ws}`"))
+        .collect::<Vec<_>>()
+        .join(" ↔ ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn edge(from_ws: &str, to_ws: &str, from: &str, to: &str) -> StructuralEdge {
+        StructuralEdge {
+            from_workspace: from_ws.to_string(),
+            to_workspace: to_ws.to_string(),
+            from_crate: from.to_string(),
+            to_crate: to.to_string(),
+            dependency_type: "normal".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_diff_reports_added_and_removed_structure() {
+        let old = StructureSnapshot {
+            workspaces: ["core", "legacy"].iter().map(|s| s.to_string()).collect(),
+            edges: [edge("legacy", "core", "old", "core-api")]
+                .into_iter()
+                .collect(),
+            cycles: [vec!["core".to_string(), "legacy".to_string()]]
+                .into_iter()
+                .collect(),
+        };
+        let new = StructureSnapshot {
+            workspaces: ["apps", "core"].iter().map(|s| s.to_string()).collect(),
+            edges: [edge("apps", "core", "app", "core-api")]
+                .into_iter()
+                .collect(),
+            cycles: BTreeSet::new(),
+        };
+
+        let changes = old.diff(&new);
+
+        assert_eq!(changes.added_workspaces, vec!["apps"]);
+        assert_eq!(changes.removed_workspaces, vec!["legacy"]);
+        assert_eq!(
+            changes.added_edges,
+            vec![edge("apps", "core", "app", "core-api")]
+        );
+        assert_eq!(
+            changes.removed_edges,
+            vec![edge("legacy", "core", "old", "core-api")]
+        );
+        assert!(changes.introduced_cycles.is_empty());
+        assert_eq!(
+            changes.fixed_cycles,
+            vec![vec!["core".to_string(), "legacy".to_string()]]
+        );
+
+        let markdown = changes.to_markdown("v1.2", "v1.3").unwrap();
+        assert!(markdown.starts_with("## Workspace dependency changes (v1.2...v1.3)\n"));
+        assert!(markdown.contains("### New workspaces\n\n- `apps`\n"));
+        assert!(markdown.contains("- `apps` → `core` (`app` → `core-api`, normal)"));
+        assert!(markdown.contains("### Cycles fixed\n\n- `core` ↔ `legacy`\n"));
+        assert!(!markdown.contains("### Cycles introduced"));
+
+        assert_eq!(
+            changes.to_delta().unwrap(),
+            "  🎉 Fixed cycle: core ↔ legacy\n\x20 ➕ Workspace apps\n\x20 ➖ Workspace \
+             legacy\n\x20 ➕ apps → core (app → core-api, normal)\n\x20 ➖ legacy → core (old → \
+             core-api, normal)\n"
+        );
+    }
+
+    #[test]
+    fn test_no_changes_markdown() {
+        let snapshot = StructureSnapshot::default();
+        let changes = snapshot.diff(&snapshot);
+
+        assert!(changes.is_empty());
+        assert_eq!(
+            changes.to_markdown("a", "b").unwrap(),
+            "## Workspace dependency changes (a...b)\n\n_No structural changes._\n"
+        );
+        assert_eq!(changes.to_delta().unwrap(), "  No dependency changes\n");
+    }
+}
@@ -4,28 +4,129 @@ use miette::{Result, WrapErr};
 
 use crate::cli::Commands;
 use crate::common::{ConfigBuilder, FromCommand};
-use crate::config::CheckCyclesConfig;
+use crate::config::{CheckCyclesConfig, ProjectConfig};
+use crate::detector::FailureBudget;
 use crate::error::FerrisWheelError;
+use crate::graph::DependencyType;
 
 impl FromCommand for CheckCyclesConfig {
-    fn from_command(command: Commands) -> Result<Self, FerrisWheelError> {
+    fn from_command(
+        command: Commands,
+        project: Option<&ProjectConfig>,
+    ) -> Result<Self, FerrisWheelError> {
         match command {
             Commands::Inspect {
                 common,
                 format,
                 cycle_display,
                 error_on_cycles,
+                no_error_on_cycles: _,
+                max_cycles,
+                max_severity,
+                deny_cross_workspace_dev_deps,
                 intra_workspace,
-            } => CheckCyclesConfig::builder()
-                .with_paths(common.get_paths())
-                .with_format(format.format)
-                .with_error_on_cycles(error_on_cycles)
-                .with_exclude_dev(common.exclude_dev)
-                .with_exclude_build(common.exclude_build)
-                .with_exclude_target(common.exclude_target)
-                .with_max_cycles(cycle_display.max_cycles)
-                .with_intra_workspace(intra_workspace)
-                .build(),
+                level,
+                ledger,
+                baseline,
+                write_baseline,
+                events,
+                what_if,
+                timeout,
+                kind_matrix,
+                watch,
+                fail_fast,
+                save_graph,
+                template,
+                manifest_warnings,
+            } => {
+                let paths = common.get_paths();
+                let profile = common.profile.clone();
+
+                let selected = match project {
+                    Some(project) => project.select_profile(profile.as_deref())?,
+                    None if profile.is_some() => {
+                        return Err(FerrisWheelError::ConfigurationError {
+                            message: format!(
+                                "Profile '{}' requested but no {} was found",
+                                profile.unwrap_or_default(),
+                                crate::constants::config::FILE_NAME
+                            ),
+                        });
+                    }
+                    None => None,
+                };
+                let rules = selected.map(|(_, rules)| rules.clone()).unwrap_or_default();
+                let policy = project
+                    .map(ProjectConfig::dependency_policy)
+                    .transpose()?
+                    .unwrap_or_default();
+
+                CheckCyclesConfig::builder()
+                    .with_paths(paths)
+                    .with_formats(format.format)
+                    .with_output_dir(format.output_dir)
+                    .with_output(format.output.output)
+                    .with_error_on_cycles(rules.error_on_cycles.unwrap_or(error_on_cycles))
+                    .with_budget(FailureBudget {
+                        max_cycles: rules.max_cycles.or(max_cycles),
+                        max_severity: rules.max_severity.or(max_severity),
+                    })
+                    .with_deny_cross_workspace_dev_deps(
+                        rules.deny_cross_workspace_dev_deps.unwrap_or(
+                            deny_cross_workspace_dev_deps
+                                || project.is_some_and(|p| p.rules.deny_cross_workspace_dev_deps),
+                        ),
+                    )
+                    .with_exclude_dev(
+                        rules
+                            .exclude_dev
+                            .unwrap_or(common.exclude_dev || policy.ignores(DependencyType::Dev)),
+                    )
+                    .with_exclude_build(
+                        rules.exclude_build.unwrap_or(
+                            common.exclude_build || policy.ignores(DependencyType::Build),
+                        ),
+                    )
+                    .with_exclude_target(rules.exclude_target.unwrap_or(common.exclude_target))
+                    .with_target(common.target)
+                    .with_max_workspaces(common.max_workspaces)
+                    .with_exclude_paths(common.exclude_paths.clone())
+                    .with_exclude_crates(common.exclude_crates.clone())
+                    .with_follow_symlinks(common.follow_symlinks)
+                    .with_no_cache(common.no_cache)
+                    .with_analyzer(common.analyzer_config())
+                    .with_workspace_names(common.workspace_names.clone())
+                    .with_verbosity(cycle_display.verbosity())
+                    .with_elementary_cycles(cycle_display.elementary_cycles())
+                    .with_intra_workspace(intra_workspace)
+                    .with_level(level)
+                    .with_url_template(project.and_then(|p| p.url_template()))
+                    .with_profile(selected.map(|(name, _)| name.to_string()))
+                    .with_grade_weights(project.map(|p| p.grade).unwrap_or_default())
+                    .with_severity_model(project.map(|p| p.severity).unwrap_or_default())
+                    .with_effort_model(project.map(|p| p.effort).unwrap_or_default())
+                    .with_allowlist(
+                        project
+                            .map(ProjectConfig::allowlist)
+                            .transpose()?
+                            .unwrap_or_default(),
+                    )
+                    .with_policy(policy)
+                    .with_layer_model(project.map(|p| p.layers.clone()).unwrap_or_default())
+                    .with_ledger(ledger)
+                    .with_baseline(baseline)
+                    .with_write_baseline(write_baseline)
+                    .with_events(events)
+                    .with_what_if(what_if)
+                    .with_timeout(timeout)
+                    .with_kind_matrix(kind_matrix)
+                    .with_watch(watch)
+                    .with_fail_fast(fail_fast)
+                    .with_save_graph(save_graph)
+                    .with_template(template)
+                    .with_manifest_warnings(manifest_warnings)
+                    .build()
+            }
             _ => Err(FerrisWheelError::ConfigurationError {
                 message: "Invalid command type for CheckCyclesConfig".to_string(),
             }),
@@ -36,8 +137,8 @@ impl FromCommand for CheckCyclesConfig {
 crate::impl_try_from_command!(CheckCyclesConfig);
 
 /// Execute the inspect command for detecting workspace dependency cycles
-pub fn execute_check_command(command: Commands) -> Result<()> {
-    let config = CheckCyclesConfig::from_command(command)
+pub fn execute_check_command(command: Commands, project: Option<&ProjectConfig>) -> Result<()> {
+    let config = CheckCyclesConfig::from_command(command, project)
         .wrap_err("Failed to parse inspect command configuration")?;
 
     use crate::executors::CommandExecutor;
@@ -0,0 +1,88 @@
+//! Completions command implementation
+
+use clap::CommandFactory;
+use clap_complete::Shell;
+use miette::{Result, WrapErr};
+
+use crate::cli::{CargoArgs, Commands};
+use crate::common::{ConfigBuilder, FromCommand};
+use crate::config::{CompletionsConfig, ProjectConfig};
+use crate::error::FerrisWheelError;
+
+/// Name of the installed binary the completions are registered for
+pub const BIN_NAME: &str = "cargo-ferris-wheel";
+
+impl FromCommand for CompletionsConfig {
+    fn from_command(
+        command: Commands,
+        _project: Option<&ProjectConfig>,
+    ) -> Result<Self, FerrisWheelError> {
+        match command {
+            Commands::Completions { shell } => {
+                CompletionsConfig::builder().with_shell(shell).build()
+            }
+            _ => Err(FerrisWheelError::ConfigurationError {
+                message: "Invalid command type for CompletionsConfig".to_string(),
+            }),
+        }
+    }
+}
+
+crate::impl_try_from_command!(CompletionsConfig);
+
+/// Execute the completions command for printing a shell completion script
+pub fn execute_completions_command(
+    command: Commands,
+    project: Option<&ProjectConfig>,
+) -> Result<()> {
+    let config = CompletionsConfig::from_command(command, project)
+        .wrap_err("Failed to parse completions command configuration")?;
+
+    use crate::executors::CommandExecutor;
+    use crate::executors::completions::CompletionsExecutor;
+    CompletionsExecutor::execute(config)
+}
+
+/// The completion script for `shell`
+pub fn generate_completions(shell: Shell) -> String {
+    let mut command = CargoArgs::command();
+    let mut out = Vec::new();
+    clap_complete::generate(shell, &mut command, BIN_NAME, &mut out);
+    String::from_utf8_lossy(&out).into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_completions_cover_subcommands() {
+        let bash = generate_completions(Shell::Bash);
+        assert!(bash.contains("-o default cargo-ferris-wheel"));
+        for subcommand in [
+            "ferris-wheel",
+            "inspect",
+            "carousel",
+            "spectacle",
+            "completions",
+        ] {
+            assert!(bash.contains(subcommand), "missing {subcommand}");
+        }
+
+        let fish = generate_completions(Shell::Fish);
+        assert!(fish.contains("-c cargo-ferris-wheel"));
+        assert!(fish.contains("__fish_seen_subcommand_from inspect"));
+    }
+
+    #[test]
+    fn test_from_command() {
+        let config =
+            CompletionsConfig::from_command(Commands::Completions { shell: Shell::Zsh }, None)
+                .unwrap();
+        assert_eq!(config.shell, Shell::Zsh);
+
+        assert!(
+            CompletionsConfig::from_command(Commands::Manpages { out_dir: None }, None).is_err()
+        );
+    }
+}
@@ -1,6 +1,6 @@
 //! Lineup command implementation
 
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::fmt::Write;
 use std::path::{Path, PathBuf};
 
@@ -9,10 +9,10 @@ use petgraph::graph::{DiGraph, NodeIndex};
 use petgraph::visit::{EdgeRef, IntoNodeReferences};
 use serde::{Deserialize, Serialize};
 
-use crate::analyzer::{CrateWorkspaceMap, WorkspaceInfo};
+use crate::analyzer::{CrateMetadata, CrateWorkspaceMap, WorkspaceInfo};
 use crate::cli::Commands;
 use crate::common::{ConfigBuilder, FromCommand};
-use crate::config::WorkspaceDepsConfig;
+use crate::config::{ProjectConfig, WorkspaceDepsConfig};
 use crate::error::FerrisWheelError;
 use crate::graph::{DependencyEdge, WorkspaceNode};
 
@@ -31,10 +31,16 @@ pub struct WorkspaceDepsEntry {
     pub reverse: bool,
     pub transitive: bool,
     pub is_standalone: bool,
+    /// Metadata of the workspace's crates, by crate name
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub crate_metadata: BTreeMap<String, CrateMetadata>,
 }
 
 impl FromCommand for WorkspaceDepsConfig {
-    fn from_command(command: Commands) -> Result<Self, FerrisWheelError> {
+    fn from_command(
+        command: Commands,
+        project: Option<&ProjectConfig>,
+    ) -> Result<Self, FerrisWheelError> {
         match command {
             Commands::Lineup {
                 workspace,
@@ -42,16 +48,32 @@ impl FromCommand for WorkspaceDepsConfig {
                 transitive,
                 common,
                 format,
-            } => WorkspaceDepsConfig::builder()
-                .with_workspace(workspace)
-                .with_reverse(reverse)
-                .with_transitive(transitive)
-                .with_paths(common.get_paths())
-                .with_format(format.format)
-                .with_exclude_dev(common.exclude_dev)
-                .with_exclude_build(common.exclude_build)
-                .with_exclude_target(common.exclude_target)
-                .build(),
+            } => {
+                let paths = common.get_paths();
+
+                WorkspaceDepsConfig::builder()
+                    .with_workspace(workspace)
+                    .with_reverse(reverse)
+                    .with_transitive(transitive)
+                    .with_paths(paths)
+                    .with_format(format.format)
+                    .with_output(format.output.output)
+                    .with_exclude_dev(common.exclude_dev)
+                    .with_exclude_build(common.exclude_build)
+                    .with_exclude_target(common.exclude_target)
+                    .with_target(common.target)
+                    .with_max_workspaces(common.max_workspaces)
+                    .with_exclude_paths(common.exclude_paths.clone())
+                    .with_exclude_crates(common.exclude_crates.clone())
+                    .with_follow_symlinks(common.follow_symlinks)
+                    .with_no_cache(common.no_cache)
+                    .with_analyzer(common.analyzer_config())
+                    .with_workspace_names(common.workspace_names.clone())
+                    .with_metadata_providers(
+                        project.map(|p| p.metadata_providers()).unwrap_or_default(),
+                    )
+                    .build()
+            }
             _ => Err(FerrisWheelError::ConfigurationError {
                 message: "Invalid command type for WorkspaceDepsConfig".to_string(),
             }),
@@ -62,8 +84,8 @@ impl FromCommand for WorkspaceDepsConfig {
 crate::impl_try_from_command!(WorkspaceDepsConfig);
 
 /// Execute the lineup command for analyzing workspace dependencies
-pub fn execute_deps_command(command: Commands) -> Result<()> {
-    let config = WorkspaceDepsConfig::from_command(command)
+pub fn execute_deps_command(command: Commands, project: Option<&ProjectConfig>) -> Result<()> {
+    let config = WorkspaceDepsConfig::from_command(command, project)
         .wrap_err("Failed to parse lineup command configuration")?;
 
     use crate::executors::CommandExecutor;
@@ -91,6 +113,7 @@ struct WorkspaceReportEntry {
     name: String,
     path: Option<PathBuf>,
     is_standalone: bool,
+    crate_metadata: BTreeMap<String, CrateMetadata>,
 }
 
 impl WorkspaceDependencyAnalysis {
@@ -404,6 +427,14 @@ impl WorkspaceDepsReportGenerator {
                 writeln!(output, "  📍 Path: {}", workspace_path.display())?;
             }
 
+            for (crate_name, metadata) in &workspace.crate_metadata {
+                let pairs: Vec<String> = metadata
+                    .iter()
+                    .map(|(key, value)| format!("{key}={value}"))
+                    .collect();
+                writeln!(output, "  🏷️  {}: {}", crate_name, pairs.join(", "))?;
+            }
+
             let deps = self.dependencies_for_entry(analysis, &workspace);
 
             if self.reverse {
@@ -472,6 +503,7 @@ impl WorkspaceDepsReportGenerator {
                 reverse: self.reverse,
                 transitive: self.transitive,
                 is_standalone: workspace.is_standalone,
+                crate_metadata: workspace.crate_metadata,
             });
         }
 
@@ -579,6 +611,12 @@ impl WorkspaceDepsReportGenerator {
                 name: workspace.name().to_string(),
                 path: Some(path.clone()),
                 is_standalone: workspace.is_standalone(),
+                crate_metadata: workspace
+                    .members()
+                    .iter()
+                    .filter(|member| !member.metadata().is_empty())
+                    .map(|member| (member.name().to_string(), member.metadata().clone()))
+                    .collect(),
             })
             .collect();
 
@@ -591,6 +629,7 @@ impl WorkspaceDepsReportGenerator {
                 name: filter.clone(),
                 path: None,
                 is_standalone: false,
+                crate_metadata: BTreeMap::new(),
             });
         }
 
@@ -701,7 +740,17 @@ mod tests {
             path_a.clone(),
             WorkspaceInfo::builder()
                 .with_name("workspace-a")
-                .with_members(vec![])
+                .with_members(vec![
+                    crate::analyzer::CrateMember::builder()
+                        .with_name("crate-a")
+                        .with_path("/test/workspace-a/crate-a")
+                        .with_metadata(CrateMetadata::from([(
+                            "owner".to_string(),
+                            "payments".to_string(),
+                        )]))
+                        .build()
+                        .unwrap(),
+                ])
                 .build()
                 .unwrap(),
         );
@@ -816,6 +865,16 @@ mod tests {
         let workspace_deps = json["workspaces"].as_array().unwrap();
         assert!(!workspace_deps.is_empty());
         assert!(workspace_deps[0]["path"].is_string());
+
+        // Crate metadata is only included for workspaces that have some
+        assert_eq!(
+            workspace_deps[0]["crate_metadata"]["crate-a"]["owner"],
+            "payments"
+        );
+        assert!(workspace_deps[1].get("crate_metadata").is_none());
+
+        let human = generator.generate_human_report(&mut analysis).unwrap();
+        assert!(human.contains("  🏷️  crate-a: owner=payments"));
     }
 
     #[test]
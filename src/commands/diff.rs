@@ -0,0 +1,314 @@
+//! Diff command implementation
+
+use std::fmt::Write;
+
+use miette::{Result, WrapErr};
+use serde::Serialize;
+
+use super::changelog::{StructuralChanges, StructuralEdge, describe_edge};
+use crate::cli::Commands;
+use crate::common::{ConfigBuilder, FromCommand};
+use crate::config::{DiffConfig, DiffSide, ProjectConfig};
+use crate::error::FerrisWheelError;
+use crate::utils::string::pluralize;
+
+impl FromCommand for DiffConfig {
+    fn from_command(
+        command: Commands,
+        _project: Option<&ProjectConfig>,
+    ) -> Result<Self, FerrisWheelError> {
+        match command {
+            Commands::Diff {
+                from,
+                to,
+                common,
+                format,
+            } => DiffConfig::builder()
+                .with_from(DiffSide::parse(&from))
+                .with_to(to.as_deref().map_or(DiffSide::WorkingTree, DiffSide::parse))
+                .with_paths(common.get_paths())
+                .with_format(format.format)
+                .with_output(format.output.output)
+                .with_exclude_dev(common.exclude_dev)
+                .with_exclude_build(common.exclude_build)
+                .with_exclude_target(common.exclude_target)
+                .with_target(common.target)
+                .with_max_workspaces(common.max_workspaces)
+                .with_exclude_paths(common.exclude_paths.clone())
+                .with_exclude_crates(common.exclude_crates.clone())
+                .with_follow_symlinks(common.follow_symlinks)
+                .with_no_cache(common.no_cache)
+                .with_analyzer(common.analyzer_config())
+                .with_workspace_names(common.workspace_names.clone())
+                .build(),
+            _ => Err(FerrisWheelError::ConfigurationError {
+                message: "Invalid command type for DiffConfig".to_string(),
+            }),
+        }
+    }
+}
+
+crate::impl_try_from_command!(DiffConfig);
+
+/// Execute the diff command for comparing the cycles of two analyses
+pub fn execute_diff_command(command: Commands, project: Option<&ProjectConfig>) -> Result<()> {
+    let config = DiffConfig::from_command(command, project)
+        .wrap_err("Failed to parse diff command configuration")?;
+
+    use crate::executors::CommandExecutor;
+    use crate::executors::diff::DiffExecutor;
+    DiffExecutor::execute(config)
+}
+
+/// JSON output structure for the diff command
+#[derive(Debug, Serialize)]
+struct DiffJsonReport<'a> {
+    from: &'a str,
+    to: &'a str,
+    introduced_cycles: &'a [Vec<String>],
+    fixed_cycles: &'a [Vec<String>],
+    added_edges: &'a [StructuralEdge],
+    removed_edges: &'a [StructuralEdge],
+}
+
+/// Report generator for the differences between two analyses
+pub struct DiffReportGenerator<'a> {
+    from: &'a str,
+    to: &'a str,
+}
+
+impl<'a> DiffReportGenerator<'a> {
+    pub fn new(from: &'a str, to: &'a str) -> Self {
+        Self { from, to }
+    }
+
+    pub fn generate_human_report(
+        &self,
+        changes: &StructuralChanges,
+    ) -> Result<String, FerrisWheelError> {
+        let mut output = String::new();
+
+        writeln!(output, "\n🔀 {} → {}", self.from, self.to)?;
+
+        if changes.introduced_cycles.is_empty() {
+            writeln!(output, "\n✅ No new cycles")?;
+        } else {
+            writeln!(
+                output,
+                "\n❌ {} new {}:",
+                changes.introduced_cycles.len(),
+                pluralize("cycle", changes.introduced_cycles.len())
+            )?;
+            for cycle in &changes.introduced_cycles {
+                writeln!(output, "  {}", cycle.join(" ↔ "))?;
+            }
+        }
+
+        if !changes.fixed_cycles.is_empty() {
+            writeln!(
+                output,
+                "\n🎉 {} fixed {}:",
+                changes.fixed_cycles.len(),
+                pluralize("cycle", changes.fixed_cycles.len())
+            )?;
+            for cycle in &changes.fixed_cycles {
+                writeln!(output, "  {}", cycle.join(" ↔ "))?;
+            }
+        }
+
+        for (symbol, verb, edges) in [
+            ("➕", "added", &changes.added_edges),
+            ("➖", "removed", &changes.removed_edges),
+        ] {
+            if edges.is_empty() {
+                continue;
+            }
+            writeln!(
+                output,
+                "\n{symbol} {} {verb} cross-workspace {}:",
+                edges.len(),
+                dependency_noun(edges.len())
+            )?;
+            for edge in edges {
+                writeln!(output, "  {}", describe_edge(edge))?;
+            }
+        }
+
+        Ok(output)
+    }
+
+    pub fn generate_json_report(
+        &self,
+        changes: &StructuralChanges,
+    ) -> Result<String, FerrisWheelError> {
+        let report = DiffJsonReport {
+            from: self.from,
+            to: self.to,
+            introduced_cycles: &changes.introduced_cycles,
+            fixed_cycles: &changes.fixed_cycles,
+            added_edges: &changes.added_edges,
+            removed_edges: &changes.removed_edges,
+        };
+
+        Ok(serde_json::to_string_pretty(&report)?)
+    }
+
+    pub fn generate_junit_report(
+        &self,
+        changes: &StructuralChanges,
+    ) -> Result<String, FerrisWheelError> {
+        let mut output = String::new();
+        let failures = if changes.introduced_cycles.is_empty() {
+            0
+        } else {
+            1
+        };
+
+        writeln!(output, r#"<?xml version="1.0" encoding="UTF-8"?>"#)?;
+        writeln!(
+            output,
+            r#"<testsuites name="cycle-diff" tests="1" failures="{failures}">"#
+        )?;
+        writeln!(
+            output,
+            r#"  <testsuite name="diff-analysis" tests="1" failures="{failures}">"#
+        )?;
+        writeln!(
+            output,
+            r#"    <testcase name="no-new-cycles" classname="ferris-wheel">"#
+        )?;
+        if !changes.introduced_cycles.is_empty() {
+            writeln!(
+                output,
+                r#"      <failure message="{} introduces {} {}">"#,
+                self.to,
+                changes.introduced_cycles.len(),
+                pluralize("cycle", changes.introduced_cycles.len())
+            )?;
+            for cycle in &changes.introduced_cycles {
+                writeln!(output, "{}", cycle.join(" &lt;-&gt; "))?;
+            }
+            writeln!(output, r#"      </failure>"#)?;
+        }
+        writeln!(output, r#"    </testcase>"#)?;
+        writeln!(output, r#"  </testsuite>"#)?;
+        writeln!(output, r#"</testsuites>"#)?;
+
+        Ok(output)
+    }
+
+    pub fn generate_github_report(
+        &self,
+        changes: &StructuralChanges,
+    ) -> Result<String, FerrisWheelError> {
+        let mut output = String::new();
+
+        for cycle in &changes.introduced_cycles {
+            writeln!(
+                output,
+                "::error title=New Dependency Cycle::{}",
+                cycle.join(" ↔ ")
+            )?;
+        }
+        for cycle in &changes.fixed_cycles {
+            writeln!(
+                output,
+                "::notice title=Fixed Dependency Cycle::{}",
+                cycle.join(" ↔ ")
+            )?;
+        }
+        if changes.introduced_cycles.is_empty() && changes.fixed_cycles.is_empty() {
+            writeln!(
+                output,
+                "::notice title=Dependency Cycles::No cycles introduced or fixed since {}",
+                self.from
+            )?;
+        }
+
+        Ok(output)
+    }
+}
+
+fn dependency_noun(count: usize) -> &'static str {
+    if count == 1 {
+        "dependency"
+    } else {
+        "dependencies"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn changes() -> StructuralChanges {
+        StructuralChanges {
+            added_edges: vec![StructuralEdge {
+                from_workspace: "core".to_string(),
+                to_workspace: "apps".to_string(),
+                from_crate: "core-api".to_string(),
+                to_crate: "app-web".to_string(),
+                dependency_type: "normal".to_string(),
+            }],
+            introduced_cycles: vec![vec!["apps".to_string(), "core".to_string()]],
+            fixed_cycles: vec![vec!["core".to_string(), "legacy".to_string()]],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_side_is_a_directory_when_one_exists() {
+        let temp = tempfile::tempdir().unwrap();
+        let dir = temp.path().to_string_lossy().to_string();
+
+        assert_eq!(
+            DiffSide::parse(&dir),
+            DiffSide::Directory(temp.path().to_path_buf())
+        );
+        assert_eq!(
+            DiffSide::parse("origin/main"),
+            DiffSide::Revision("origin/main".to_string())
+        );
+        assert_eq!(DiffSide::WorkingTree.to_string(), "working tree");
+    }
+
+    #[test]
+    fn test_reports() {
+        let changes = changes();
+        let generator = DiffReportGenerator::new("main", "working tree");
+
+        let human = generator.generate_human_report(&changes).unwrap();
+        assert!(human.contains("🔀 main → working tree"));
+        assert!(human.contains("❌ 1 new cycle:\n  apps ↔ core\n"));
+        assert!(human.contains("🎉 1 fixed cycle:\n  core ↔ legacy\n"));
+        assert!(human.contains(
+            "➕ 1 added cross-workspace dependency:\n  core → apps (core-api → app-web, normal)\n"
+        ));
+        assert!(!human.contains("removed"));
+
+        let json: serde_json::Value =
+            serde_json::from_str(&generator.generate_json_report(&changes).unwrap()).unwrap();
+        assert_eq!(json["introduced_cycles"][0][1], "core");
+        assert_eq!(json["added_edges"][0]["to_crate"], "app-web");
+        assert!(json.get("added_workspaces").is_none());
+
+        let junit = generator.generate_junit_report(&changes).unwrap();
+        assert!(junit.contains(r#"failures="1""#));
+        assert!(junit.contains("apps &lt;-&gt; core"));
+
+        let github = generator.generate_github_report(&changes).unwrap();
+        assert!(github.contains("::error title=New Dependency Cycle::apps ↔ core"));
+        assert!(github.contains("::notice title=Fixed Dependency Cycle::core ↔ legacy"));
+    }
+
+    #[test]
+    fn test_no_changes() {
+        let generator = DiffReportGenerator::new("v1", "v2");
+        let changes = StructuralChanges::default();
+
+        let human = generator.generate_human_report(&changes).unwrap();
+        assert_eq!(human, "\n🔀 v1 → v2\n\n✅ No new cycles\n");
+        let junit = generator.generate_junit_report(&changes).unwrap();
+        assert!(junit.contains(r#"failures="0""#));
+    }
+}
@@ -0,0 +1,505 @@
+//! Fix command implementation
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::{self, Write};
+use std::path::{Path, PathBuf};
+
+use miette::{Result, WrapErr};
+use serde::Serialize;
+use toml_edit::DocumentMut;
+
+use crate::cli::{Commands, FixStrategy};
+use crate::common::{ConfigBuilder, FromCommand};
+use crate::config::{FixConfig, ProjectConfig};
+use crate::detector::{CycleEdge, WorkspaceCycle};
+use crate::error::FerrisWheelError;
+use crate::graph::DependencyType;
+use crate::manifest_edit::{self, DependencyEdit};
+use crate::reports::links::ManifestLinks;
+use crate::utils::string::pluralize;
+
+impl FromCommand for FixConfig {
+    fn from_command(
+        command: Commands,
+        _project: Option<&ProjectConfig>,
+    ) -> Result<Self, FerrisWheelError> {
+        match command {
+            Commands::Fix {
+                strategy,
+                write_patches,
+                common,
+                format,
+                output,
+            } => FixConfig::builder()
+                .with_paths(common.get_paths())
+                .with_format(format)
+                .with_output(output.output)
+                .with_strategy(strategy)
+                .with_write_patches(write_patches)
+                .with_exclude_dev(common.exclude_dev)
+                .with_exclude_build(common.exclude_build)
+                .with_exclude_target(common.exclude_target)
+                .with_target(common.target)
+                .with_max_workspaces(common.max_workspaces)
+                .with_exclude_paths(common.exclude_paths.clone())
+                .with_exclude_crates(common.exclude_crates.clone())
+                .with_follow_symlinks(common.follow_symlinks)
+                .with_no_cache(common.no_cache)
+                .with_analyzer(common.analyzer_config())
+                .with_workspace_names(common.workspace_names.clone())
+                .build(),
+            _ => Err(FerrisWheelError::ConfigurationError {
+                message: "Invalid command type for FixConfig".to_string(),
+            }),
+        }
+    }
+}
+
+crate::impl_try_from_command!(FixConfig);
+
+/// Execute the fix command for suggesting manifest edits that break cycles
+pub fn execute_fix_command(command: Commands, project: Option<&ProjectConfig>) -> Result<()> {
+    let config = FixConfig::from_command(command, project)
+        .wrap_err("Failed to parse fix command configuration")?;
+
+    use crate::executors::CommandExecutor;
+    use crate::executors::fix::FixExecutor;
+    FixExecutor::execute(config)
+}
+
+/// The edit suggested for one dependency
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum FixAction {
+    Remove,
+    MoveToDev,
+    FeatureGate,
+}
+
+impl FixAction {
+    /// The action `strategy` takes for a dependency of kind `kind`
+    ///
+    /// Dev dependencies can neither move nor be optional, so they are always
+    /// removed. `exclude_dev` tells whether the analysis ignores dev
+    /// dependencies, in which case moving a dependency there breaks the cycle
+    /// as analyzed.
+    pub fn for_dependency(strategy: FixStrategy, kind: &DependencyType, exclude_dev: bool) -> Self {
+        match (strategy, kind) {
+            (_, DependencyType::Dev) | (FixStrategy::Remove, _) => FixAction::Remove,
+            (FixStrategy::Dev, DependencyType::Normal) => FixAction::MoveToDev,
+            (FixStrategy::Dev, DependencyType::Build) => FixAction::Remove,
+            (FixStrategy::Auto, DependencyType::Normal) if exclude_dev => FixAction::MoveToDev,
+            (FixStrategy::Auto | FixStrategy::FeatureGate, _) => FixAction::FeatureGate,
+        }
+    }
+
+    fn edit(self) -> DependencyEdit {
+        match self {
+            FixAction::Remove => DependencyEdit::Remove,
+            FixAction::MoveToDev => DependencyEdit::MoveToDev,
+            FixAction::FeatureGate => DependencyEdit::FeatureGate,
+        }
+    }
+}
+
+impl fmt::Display for FixAction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FixAction::Remove => write!(f, "remove"),
+            FixAction::MoveToDev => write!(f, "move to [dev-dependencies]"),
+            FixAction::FeatureGate => write!(f, "make optional behind a feature"),
+        }
+    }
+}
+
+/// A suggested edit to the declaration behind one crate edge
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DependencyFix {
+    pub from_workspace: String,
+    pub to_workspace: String,
+    pub from_crate: String,
+    pub to_crate: String,
+    pub dependency_type: String,
+    pub action: FixAction,
+    /// Manifest of the depending crate
+    pub manifest: Option<PathBuf>,
+    /// 1-based line of the declaration; `None` when it was not found and the
+    /// manifest has to be edited by hand
+    pub line: Option<usize>,
+}
+
+/// The edits breaking one cycle
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct CycleFix {
+    pub workspaces: Vec<String>,
+    pub fixes: Vec<DependencyFix>,
+    /// Unified diff of every edit, relative to the base directory
+    pub patch: String,
+}
+
+/// Suggest the manifest edits breaking each cycle
+///
+/// Every suggested break point of a cycle is cut, so that no cycle remains
+/// among its workspaces. Manifests are read from disk but never written;
+/// patch paths are relative to `base` when they lie below it.
+pub fn plan_fixes(
+    cycles: &[WorkspaceCycle],
+    links: &ManifestLinks,
+    strategy: FixStrategy,
+    exclude_dev: bool,
+    base: &Path,
+) -> Result<Vec<CycleFix>, FerrisWheelError> {
+    cycles
+        .iter()
+        .map(|cycle| plan_cycle_fix(cycle, links, strategy, exclude_dev, base))
+        .collect()
+}
+
+fn plan_cycle_fix(
+    cycle: &WorkspaceCycle,
+    links: &ManifestLinks,
+    strategy: FixStrategy,
+    exclude_dev: bool,
+    base: &Path,
+) -> Result<CycleFix, FerrisWheelError> {
+    let mut fixes = Vec::new();
+    let mut kinds = Vec::new();
+    let mut by_manifest: BTreeMap<PathBuf, Vec<usize>> = BTreeMap::new();
+    let mut seen = BTreeSet::new();
+
+    let break_points = cycle.break_points();
+    for break_point in &break_points {
+        for edge in break_point.edges() {
+            let Some(kind) = edge_kind(edge) else {
+                continue;
+            };
+            if !seen.insert((
+                edge.from_workspace(),
+                edge.from_crate(),
+                edge.to_crate(),
+                edge.dependency_type(),
+            )) {
+                continue;
+            }
+
+            let manifest = links
+                .crate_manifest(edge.from_workspace(), edge.from_crate())
+                .map(Path::to_path_buf);
+            if let Some(manifest) = &manifest {
+                by_manifest
+                    .entry(manifest.clone())
+                    .or_default()
+                    .push(fixes.len());
+            }
+            fixes.push(DependencyFix {
+                from_workspace: edge.from_workspace().to_string(),
+                to_workspace: edge.to_workspace().to_string(),
+                from_crate: edge.from_crate().to_string(),
+                to_crate: edge.to_crate().to_string(),
+                dependency_type: edge.dependency_type().to_string(),
+                action: FixAction::for_dependency(strategy, &kind, exclude_dev),
+                manifest,
+                line: None,
+            });
+            kinds.push(kind);
+        }
+    }
+
+    let mut patch = String::new();
+    for (manifest, indices) in by_manifest {
+        let content = std::fs::read_to_string(&manifest).map_err(|source| {
+            FerrisWheelError::FileReadError {
+                path: manifest.clone(),
+                source,
+            }
+        })?;
+        let original: DocumentMut = content.parse().map_err(|e: toml_edit::TomlError| {
+            FerrisWheelError::ManifestEditError {
+                path: manifest.clone(),
+                message: e.to_string(),
+            }
+        })?;
+
+        let mut edited = original.clone();
+        for i in indices {
+            let (fix, kind) = (&mut fixes[i], &kinds[i]);
+
+            // Edit a fresh copy as well to find the line of this declaration
+            let mut single = original.clone();
+            if manifest_edit::apply_edit(&mut single, &fix.to_crate, kind, fix.action.edit()) {
+                fix.line = manifest_edit::first_changed_line(&content, &single.to_string());
+                manifest_edit::apply_edit(&mut edited, &fix.to_crate, kind, fix.action.edit());
+            }
+        }
+
+        let path = manifest.strip_prefix(base).unwrap_or(&manifest);
+        patch.push_str(&manifest_edit::unified_diff(
+            &path.to_string_lossy().replace('\\', "/"),
+            &content,
+            &edited.to_string(),
+        ));
+    }
+
+    Ok(CycleFix {
+        workspaces: cycle
+            .workspace_names()
+            .iter()
+            .map(ToString::to_string)
+            .collect(),
+        fixes,
+        patch,
+    })
+}
+
+fn edge_kind(edge: &CycleEdge) -> Option<DependencyType> {
+    match edge.dependency_type() {
+        "Normal" => Some(DependencyType::Normal),
+        "Dev" => Some(DependencyType::Dev),
+        "Build" => Some(DependencyType::Build),
+        _ => None,
+    }
+}
+
+/// File name of the patch breaking the `index`-th (0-based) cycle
+pub fn patch_file_name(index: usize, fix: &CycleFix) -> String {
+    let names: Vec<String> = fix
+        .workspaces
+        .iter()
+        .map(|name| {
+            name.chars()
+                .map(|c| {
+                    if c.is_alphanumeric() || c == '-' || c == '_' {
+                        c
+                    } else {
+                        '_'
+                    }
+                })
+                .collect()
+        })
+        .collect();
+    format!("{:02}-{}.patch", index + 1, names.join("-"))
+}
+
+/// Report generator for suggested cycle fixes
+pub struct FixReportGenerator;
+
+impl FixReportGenerator {
+    pub fn new() -> Self {
+        Self
+    }
+
+    pub fn generate_human_report(&self, fixes: &[CycleFix]) -> Result<String, FerrisWheelError> {
+        let mut output = String::new();
+
+        if fixes.is_empty() {
+            writeln!(output, "\n✅ No dependency cycles to fix")?;
+            return Ok(output);
+        }
+
+        let edits: usize = fixes.iter().map(|fix| fix.fixes.len()).sum();
+        writeln!(
+            output,
+            "\n🔧 {} {} to break {} {}:",
+            edits,
+            pluralize("edit", edits),
+            fixes.len(),
+            pluralize("cycle", fixes.len())
+        )?;
+
+        for (i, fix) in fixes.iter().enumerate() {
+            writeln!(
+                output,
+                "\n  Cycle {}: {}",
+                i + 1,
+                fix.workspaces.join(" ↔ ")
+            )?;
+            for dependency in &fix.fixes {
+                writeln!(
+                    output,
+                    "    ✂️  {} → {} ({}): {}",
+                    dependency.from_crate,
+                    dependency.to_crate,
+                    dependency.dependency_type,
+                    dependency.action
+                )?;
+                match (&dependency.manifest, dependency.line) {
+                    (Some(manifest), Some(line)) => {
+                        writeln!(output, "       {}:{line}", manifest.display())?
+                    }
+                    (Some(manifest), None) => writeln!(
+                        output,
+                        "       declaration not found, edit {} by hand",
+                        manifest.display()
+                    )?,
+                    (None, _) => writeln!(output, "       manifest not found, edit it by hand")?,
+                }
+            }
+            if !fix.patch.is_empty() {
+                writeln!(output)?;
+                for line in fix.patch.lines() {
+                    writeln!(output, "    {line}")?;
+                }
+            }
+        }
+
+        writeln!(
+            output,
+            "\n💡 Review each edit before applying it: code using a removed or moved dependency, \
+             or a feature-gated one, has to be updated too."
+        )?;
+
+        Ok(output)
+    }
+
+    pub fn generate_json_report(&self, fixes: &[CycleFix]) -> Result<String, FerrisWheelError> {
+        Ok(serde_json::to_string_pretty(fixes)?)
+    }
+
+    /// Every patch, concatenated for `git apply`
+    pub fn generate_patch_report(&self, fixes: &[CycleFix]) -> Result<String, FerrisWheelError> {
+        Ok(fixes.iter().map(|fix| fix.patch.as_str()).collect())
+    }
+}
+
+impl Default for FixReportGenerator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use std::fs;
+
+    use super::*;
+    use crate::analyzer::{CrateMember, WorkspaceInfo};
+
+    /// Workspaces `core` and `apps` with `core-lib` depending on `apps-lib`
+    /// (dev) and `apps-lib` on `core-lib` (normal)
+    fn setup() -> (tempfile::TempDir, ManifestLinks, WorkspaceCycle) {
+        let temp = tempfile::tempdir().unwrap();
+        let root = temp.path();
+        let mut workspaces = HashMap::new();
+        for (workspace, manifest) in [
+            (
+                "core",
+                "[package]\nname = \"core-lib\"\n\n[dev-dependencies]\napps-lib = { path = \
+                 \"../../apps/apps-lib\" }\n",
+            ),
+            (
+                "apps",
+                "[package]\nname = \"apps-lib\"\n\n[dependencies]\ncore-lib = { path = \
+                 \"../../core/core-lib\" }\n",
+            ),
+        ] {
+            let member = root.join(workspace).join(format!("{workspace}-lib"));
+            fs::create_dir_all(&member).unwrap();
+            fs::write(member.join("Cargo.toml"), manifest).unwrap();
+            workspaces.insert(
+                root.join(workspace),
+                WorkspaceInfo::builder()
+                    .with_name(workspace)
+                    .with_members(vec![
+                        CrateMember::builder()
+                            .with_name(format!("{workspace}-lib"))
+                            .with_path(member)
+                            .build()
+                            .unwrap(),
+                    ])
+                    .build()
+                    .unwrap(),
+            );
+        }
+
+        let cycle = WorkspaceCycle::builder()
+            .with_workspace_names(vec!["apps".to_string(), "core".to_string()])
+            .add_edge()
+            .from_workspace("core")
+            .to_workspace("apps")
+            .from_crate("core-lib")
+            .to_crate("apps-lib")
+            .dependency_type("Dev")
+            .add_edge()
+            .unwrap()
+            .from_workspace("apps")
+            .to_workspace("core")
+            .from_crate("apps-lib")
+            .to_crate("core-lib")
+            .dependency_type("Normal")
+            .build()
+            .unwrap();
+
+        (temp, ManifestLinks::from_workspaces(&workspaces), cycle)
+    }
+
+    #[test]
+    fn test_actions_by_strategy() {
+        use DependencyType::*;
+
+        let action = FixAction::for_dependency;
+        assert_eq!(action(FixStrategy::Auto, &Dev, false), FixAction::Remove);
+        assert_eq!(
+            action(FixStrategy::Auto, &Normal, false),
+            FixAction::FeatureGate
+        );
+        assert_eq!(
+            action(FixStrategy::Auto, &Normal, true),
+            FixAction::MoveToDev
+        );
+        assert_eq!(action(FixStrategy::Dev, &Build, false), FixAction::Remove);
+        assert_eq!(
+            action(FixStrategy::FeatureGate, &Build, false),
+            FixAction::FeatureGate
+        );
+        assert_eq!(
+            action(FixStrategy::Remove, &Normal, false),
+            FixAction::Remove
+        );
+    }
+
+    #[test]
+    fn test_plan_fixes_produces_a_patch() {
+        let (temp, links, cycle) = setup();
+
+        let fixes = plan_fixes(&[cycle], &links, FixStrategy::Remove, false, temp.path()).unwrap();
+
+        assert_eq!(fixes.len(), 1);
+        let fix = &fixes[0];
+        assert_eq!(fix.fixes.len(), 1);
+        assert_eq!(fix.fixes[0].from_crate, "core-lib");
+        assert_eq!(fix.fixes[0].action, FixAction::Remove);
+        assert_eq!(fix.fixes[0].line, Some(5));
+        assert_eq!(
+            fix.patch,
+            "--- a/core/core-lib/Cargo.toml\n+++ b/core/core-lib/Cargo.toml\n@@ -2,4 +2,3 @@\n \
+             name = \"core-lib\"\n \n [dev-dependencies]\n-apps-lib = { path = \
+             \"../../apps/apps-lib\" }\n"
+        );
+        assert_eq!(patch_file_name(0, fix), "01-apps-core.patch");
+    }
+
+    #[test]
+    fn test_reports() {
+        let (temp, links, cycle) = setup();
+        let fixes = plan_fixes(&[cycle], &links, FixStrategy::Auto, false, temp.path()).unwrap();
+        let generator = FixReportGenerator::new();
+
+        let human = generator.generate_human_report(&fixes).unwrap();
+        assert!(human.contains("1 edit to break 1 cycle:"));
+        assert!(human.contains("Cycle 1: apps ↔ core"));
+        assert!(human.contains("core-lib → apps-lib (Dev): remove"));
+        assert!(human.contains("    -apps-lib = "));
+
+        let json: serde_json::Value =
+            serde_json::from_str(&generator.generate_json_report(&fixes).unwrap()).unwrap();
+        assert_eq!(json[0]["fixes"][0]["action"], "remove");
+        assert_eq!(json[0]["fixes"][0]["line"], 5);
+
+        let patch = generator.generate_patch_report(&fixes).unwrap();
+        assert!(patch.starts_with("--- a/core/core-lib/Cargo.toml\n"));
+
+        let none = generator.generate_human_report(&[]).unwrap();
+        assert!(none.contains("No dependency cycles to fix"));
+    }
+}
@@ -1,14 +1,19 @@
 //! Spectacle command implementation
 
+use console::style;
 use miette::{Result, WrapErr};
 
 use crate::cli::Commands;
 use crate::common::{ConfigBuilder, FromCommand};
-use crate::config::GraphOptions;
+use crate::config::{GraphOptions, ProjectConfig};
 use crate::error::FerrisWheelError;
+use crate::graph::{MermaidOptions, Palette, WorkspaceGrouping};
 
 impl FromCommand for GraphOptions {
-    fn from_command(command: Commands) -> Result<Self, FerrisWheelError> {
+    fn from_command(
+        command: Commands,
+        project: Option<&ProjectConfig>,
+    ) -> Result<Self, FerrisWheelError> {
         match command {
             Commands::Spectacle {
                 common,
@@ -16,16 +21,84 @@ impl FromCommand for GraphOptions {
                 output,
                 highlight_cycles,
                 show_crates,
-            } => GraphOptions::builder()
-                .with_paths(common.get_paths())
-                .with_format(format)
-                .with_output(output)
-                .with_highlight_cycles(highlight_cycles)
-                .with_show_crates(show_crates)
-                .with_exclude_dev(common.exclude_dev)
-                .with_exclude_build(common.exclude_build)
-                .with_exclude_target(common.exclude_target)
-                .build(),
+                direction,
+                mermaid_theme,
+                edge_labels,
+                nest_workspaces,
+                group_by,
+                crate_subgraphs,
+                theme,
+                condensed,
+                cycles_only,
+                focus,
+                depth,
+                from_cache,
+            } => {
+                let paths = common.get_paths();
+
+                // Layer grouping uses the layers declared in the config file
+                let grouping = match group_by {
+                    Some(WorkspaceGrouping::Layer(_)) => {
+                        let layers = project.map(|p| p.layers.clone()).unwrap_or_default();
+                        if layers.is_empty() {
+                            return Err(FerrisWheelError::ConfigurationError {
+                                message: format!(
+                                    "--group-by layer needs [layers] in {}",
+                                    crate::constants::config::FILE_NAME
+                                ),
+                            });
+                        }
+                        Some(WorkspaceGrouping::Layer(layers))
+                    }
+                    other => other,
+                };
+
+                // --theme wins over the config file's [theme] section
+                let mut palette = match (&theme, project) {
+                    (Some(theme), _) => theme.palette()?,
+                    (None, Some(project)) => project.theme.palette()?,
+                    (None, None) => Palette::default(),
+                };
+                if let Some(mermaid_theme) = mermaid_theme {
+                    eprintln!(
+                        "{} --mermaid-theme is deprecated; use --theme, or `mermaid = \"{}\"` in \
+                         a theme file",
+                        style("⚠").yellow(),
+                        mermaid_theme.as_mermaid()
+                    );
+                    palette.mermaid_theme = Some(mermaid_theme);
+                }
+
+                GraphOptions::builder()
+                    .with_paths(paths)
+                    .with_format(format)
+                    .with_output(output.output)
+                    .with_highlight_cycles(highlight_cycles)
+                    .with_show_crates(show_crates)
+                    .with_exclude_dev(common.exclude_dev)
+                    .with_exclude_build(common.exclude_build)
+                    .with_exclude_target(common.exclude_target)
+                    .with_target(common.target)
+                    .with_max_workspaces(common.max_workspaces)
+                    .with_exclude_paths(common.exclude_paths.clone())
+                    .with_exclude_crates(common.exclude_crates.clone())
+                    .with_follow_symlinks(common.follow_symlinks)
+                    .with_no_cache(common.no_cache)
+                    .with_analyzer(common.analyzer_config())
+                    .with_workspace_names(common.workspace_names.clone())
+                    .with_mermaid_options(MermaidOptions { direction })
+                    .with_edge_labels(edge_labels)
+                    .with_severity_model(project.map(|p| p.severity).unwrap_or_default())
+                    .with_nest_workspaces(nest_workspaces)
+                    .with_grouping(grouping)
+                    .with_crate_subgraphs(crate_subgraphs)
+                    .with_palette(palette)
+                    .with_condensed(condensed)
+                    .with_cycles_only(cycles_only)
+                    .with_focus(focus, depth)
+                    .with_from_cache(from_cache)
+                    .build()
+            }
             _ => Err(FerrisWheelError::ConfigurationError {
                 message: "Invalid command type for GraphOptions".to_string(),
             }),
@@ -36,8 +109,8 @@ impl FromCommand for GraphOptions {
 crate::impl_try_from_command!(GraphOptions);
 
 /// Execute the spectacle command for generating visual dependency graphs
-pub fn execute_graph_command(command: Commands) -> Result<()> {
-    let config = GraphOptions::from_command(command)
+pub fn execute_graph_command(command: Commands, project: Option<&ProjectConfig>) -> Result<()> {
+    let config = GraphOptions::from_command(command, project)
         .wrap_err("Failed to parse spectacle command configuration")?;
 
     use crate::executors::CommandExecutor;
@@ -0,0 +1,72 @@
+//! Manpages command implementation
+
+use clap::CommandFactory;
+use miette::{Result, WrapErr};
+
+use crate::cli::{Cli, Commands};
+use crate::common::{ConfigBuilder, FromCommand};
+use crate::config::{ManpagesConfig, ProjectConfig};
+use crate::error::FerrisWheelError;
+
+impl FromCommand for ManpagesConfig {
+    fn from_command(
+        command: Commands,
+        _project: Option<&ProjectConfig>,
+    ) -> Result<Self, FerrisWheelError> {
+        match command {
+            Commands::Manpages { out_dir } => {
+                ManpagesConfig::builder().with_out_dir(out_dir).build()
+            }
+            _ => Err(FerrisWheelError::ConfigurationError {
+                message: "Invalid command type for ManpagesConfig".to_string(),
+            }),
+        }
+    }
+}
+
+crate::impl_try_from_command!(ManpagesConfig);
+
+/// Execute the manpages command for generating man pages
+pub fn execute_manpages_command(command: Commands, project: Option<&ProjectConfig>) -> Result<()> {
+    let config = ManpagesConfig::from_command(command, project)
+        .wrap_err("Failed to parse manpages command configuration")?;
+
+    use crate::executors::CommandExecutor;
+    use crate::executors::manpages::ManpagesExecutor;
+    ManpagesExecutor::execute(config)
+}
+
+/// The command tree the man pages are generated from
+///
+/// Pages are named after the binary (`cargo-ferris-wheel-inspect.1`), while
+/// their synopses show how the tool is invoked through cargo
+/// (`cargo ferris-wheel inspect`).
+pub fn man_command() -> clap::Command {
+    Cli::command()
+        .name("cargo-ferris-wheel")
+        .display_name("cargo-ferris-wheel")
+        .bin_name("cargo ferris-wheel")
+}
+
+#[cfg(test)]
+mod tests {
+    use tempfile::TempDir;
+
+    use super::*;
+
+    #[test]
+    fn test_pages_for_every_subcommand() {
+        let temp = TempDir::new().unwrap();
+        clap_mangen::generate_to(man_command(), temp.path()).unwrap();
+
+        let top = std::fs::read_to_string(temp.path().join("cargo-ferris-wheel.1")).unwrap();
+        assert!(top.starts_with(".ie \\n(.g .ds Aq"));
+        assert!(top.contains("cargo\\-ferris\\-wheel\\-inspect(1)"));
+
+        let inspect =
+            std::fs::read_to_string(temp.path().join("cargo-ferris-wheel-inspect.1")).unwrap();
+        assert!(inspect.contains("\\fBcargo ferris\\-wheel inspect\\fR"));
+        assert!(inspect.contains("\\-\\-error\\-on\\-cycles"));
+        assert!(temp.path().join("cargo-ferris-wheel-manpages.1").exists());
+    }
+}
@@ -6,24 +6,68 @@
 //! - lineup: See the full lineup of workspace dependencies
 //! - spectacle: Create a spectacular visualization of dependencies
 //! - ripples: Discover the ripple effects from changed files
+//! - path: Trace the shortest dependency chains between two workspaces
+//! - why: Explain a workspace dependency with a crate-level chain
+//! - between: List the crate dependencies between two workspaces
+//! - chain: List the dependency chains from one crate to another
+//! - carousel: Enforce the declared architecture layers
+//! - sweep: Find internal dependencies the sources never use
+//! - stats: Compute coupling, depth and cycle metrics per workspace
+//! - changelog: Summarize structural changes between two revisions
+//! - diff: Compare the cycles of two directories or revisions
+//! - fix: Suggest manifest edits that break cycles
+//! - tui: Explore workspaces and cycles interactively
+//! - completions: Print shell completion scripts
+//! - manpages: Generate man pages
+//! - schema: Print JSON Schemas of the reports and configuration file
 
 pub mod affected;
 pub mod analyze;
+pub mod between;
+pub mod carousel;
+pub mod chain;
+pub mod changelog;
 pub mod check;
+pub mod completions;
 pub mod deps;
+pub mod diff;
+pub mod fix;
 pub mod graph;
+pub mod manpages;
+pub mod path;
+pub mod schema;
+pub mod stats;
+pub mod sweep;
+pub mod tui;
+pub mod why;
 
 use miette::Result;
 
 use crate::cli::Commands;
+use crate::config::ProjectConfig;
 
-/// Execute a command based on CLI input
-pub fn execute_command(command: Commands) -> Result<()> {
+/// Execute a command based on CLI input, with the project configuration
+/// loaded for it, if any
+pub fn execute_command(command: Commands, project: Option<&ProjectConfig>) -> Result<()> {
     match &command {
-        Commands::Inspect { .. } => check::execute_check_command(command),
-        Commands::Spectacle { .. } => graph::execute_graph_command(command),
-        Commands::Spotlight { .. } => analyze::execute_analyze_command(command),
-        Commands::Lineup { .. } => deps::execute_deps_command(command),
-        Commands::Ripples { .. } => affected::execute_affected_command(command),
+        Commands::Inspect { .. } => check::execute_check_command(command, project),
+        Commands::Spectacle { .. } => graph::execute_graph_command(command, project),
+        Commands::Spotlight { .. } => analyze::execute_analyze_command(command, project),
+        Commands::Lineup { .. } => deps::execute_deps_command(command, project),
+        Commands::Ripples { .. } => affected::execute_affected_command(command, project),
+        Commands::Path { .. } => path::execute_path_command(command, project),
+        Commands::Why { .. } => why::execute_why_command(command, project),
+        Commands::Between { .. } => between::execute_between_command(command, project),
+        Commands::Chain { .. } => chain::execute_chain_command(command, project),
+        Commands::Carousel { .. } => carousel::execute_carousel_command(command, project),
+        Commands::Sweep { .. } => sweep::execute_sweep_command(command, project),
+        Commands::Stats { .. } => stats::execute_stats_command(command, project),
+        Commands::Changelog { .. } => changelog::execute_changelog_command(command, project),
+        Commands::Diff { .. } => diff::execute_diff_command(command, project),
+        Commands::Fix { .. } => fix::execute_fix_command(command, project),
+        Commands::Tui { .. } => tui::execute_tui_command(command, project),
+        Commands::Completions { .. } => completions::execute_completions_command(command, project),
+        Commands::Manpages { .. } => manpages::execute_manpages_command(command, project),
+        Commands::Schema { .. } => schema::execute_schema_command(command, project),
     }
 }
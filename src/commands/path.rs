@@ -0,0 +1,413 @@
+//! Path command implementation
+
+use std::fmt::Write;
+
+use miette::{Result, WrapErr};
+use petgraph::graph::{DiGraph, NodeIndex};
+use serde::Serialize;
+
+use crate::cli::Commands;
+use crate::common::{ConfigBuilder, FromCommand};
+use crate::config::{ProjectConfig, WorkspacePathConfig};
+use crate::error::FerrisWheelError;
+use crate::graph::{DependencyEdge, WorkspaceNode, shortest_paths};
+use crate::utils::string::pluralize;
+
+impl FromCommand for WorkspacePathConfig {
+    fn from_command(
+        command: Commands,
+        _project: Option<&ProjectConfig>,
+    ) -> Result<Self, FerrisWheelError> {
+        match command {
+            Commands::Path {
+                from,
+                to,
+                max_paths,
+                common,
+                format,
+            } => WorkspacePathConfig::builder()
+                .with_from(from)
+                .with_to(to)
+                .with_max_paths(max_paths)
+                .with_paths(common.get_paths())
+                .with_format(format.format)
+                .with_output(format.output.output)
+                .with_exclude_dev(common.exclude_dev)
+                .with_exclude_build(common.exclude_build)
+                .with_exclude_target(common.exclude_target)
+                .with_target(common.target)
+                .with_max_workspaces(common.max_workspaces)
+                .with_exclude_paths(common.exclude_paths.clone())
+                .with_exclude_crates(common.exclude_crates.clone())
+                .with_follow_symlinks(common.follow_symlinks)
+                .with_no_cache(common.no_cache)
+                .with_analyzer(common.analyzer_config())
+                .with_workspace_names(common.workspace_names.clone())
+                .build(),
+            _ => Err(FerrisWheelError::ConfigurationError {
+                message: "Invalid command type for WorkspacePathConfig".to_string(),
+            }),
+        }
+    }
+}
+
+crate::impl_try_from_command!(WorkspacePathConfig);
+
+/// Execute the path command for tracing dependency chains between workspaces
+pub fn execute_path_command(command: Commands, project: Option<&ProjectConfig>) -> Result<()> {
+    let config = WorkspacePathConfig::from_command(command, project)
+        .wrap_err("Failed to parse path command configuration")?;
+
+    use crate::executors::CommandExecutor;
+    use crate::executors::path::PathExecutor;
+    PathExecutor::execute(config)
+}
+
+/// A crate-level dependency used by one hop of a chain
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct HopEdge {
+    pub from_crate: String,
+    pub to_crate: String,
+    pub dependency_type: String,
+}
+
+/// One workspace-to-workspace step of a dependency chain
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ChainHop {
+    pub from_workspace: String,
+    pub to_workspace: String,
+    pub edges: Vec<HopEdge>,
+}
+
+/// A dependency chain between two workspaces
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DependencyChain {
+    pub workspaces: Vec<String>,
+    pub hops: Vec<ChainHop>,
+}
+
+/// Find the shortest dependency chains from workspace `from` to workspace
+/// `to`, returning at most `limit` chains
+pub fn find_dependency_chains(
+    graph: &DiGraph<WorkspaceNode, DependencyEdge>,
+    from: &str,
+    to: &str,
+    limit: usize,
+) -> Result<Vec<DependencyChain>, FerrisWheelError> {
+    if from == to {
+        return Err(FerrisWheelError::ConfigurationError {
+            message: format!("--from and --to must name different workspaces (got '{from}')"),
+        });
+    }
+
+    let sources = workspace_nodes(graph, from)?;
+    let targets = workspace_nodes(graph, to)?;
+
+    Ok(shortest_paths(graph, &sources, &targets, limit)
+        .into_iter()
+        .map(|path| chain_from_path(graph, &path))
+        .collect())
+}
+
+fn workspace_nodes(
+    graph: &DiGraph<WorkspaceNode, DependencyEdge>,
+    name: &str,
+) -> Result<Vec<NodeIndex>, FerrisWheelError> {
+    let nodes: Vec<NodeIndex> = graph
+        .node_indices()
+        .filter(|&idx| graph[idx].name() == name)
+        .collect();
+
+    if nodes.is_empty() {
+        return Err(FerrisWheelError::ConfigurationError {
+            message: format!("Unknown workspace '{name}'"),
+        });
+    }
+
+    Ok(nodes)
+}
+
+fn chain_from_path(
+    graph: &DiGraph<WorkspaceNode, DependencyEdge>,
+    path: &[NodeIndex],
+) -> DependencyChain {
+    let hops = path
+        .windows(2)
+        .map(|pair| {
+            let mut edges: Vec<HopEdge> = graph
+                .edges_connecting(pair[0], pair[1])
+                .map(|edge| HopEdge {
+                    from_crate: edge.weight().from_crate().to_string(),
+                    to_crate: edge.weight().to_crate().to_string(),
+                    dependency_type: edge.weight().dependency_type().as_str().to_string(),
+                })
+                .collect();
+            edges.sort_by(|a, b| {
+                a.from_crate
+                    .cmp(&b.from_crate)
+                    .then_with(|| a.to_crate.cmp(&b.to_crate))
+            });
+            edges.dedup();
+
+            ChainHop {
+                from_workspace: graph[pair[0]].name().to_string(),
+                to_workspace: graph[pair[1]].name().to_string(),
+                edges,
+            }
+        })
+        .collect();
+
+    DependencyChain {
+        workspaces: path
+            .iter()
+            .map(|&idx| graph[idx].name().to_string())
+            .collect(),
+        hops,
+    }
+}
+
+/// JSON output structure for the path command
+#[derive(Debug, Serialize)]
+struct PathJsonReport<'a> {
+    from: &'a str,
+    to: &'a str,
+    hops: Option<usize>,
+    paths: &'a [DependencyChain],
+}
+
+/// Report generator for dependency chains
+pub struct PathReportGenerator<'a> {
+    from: &'a str,
+    to: &'a str,
+}
+
+impl<'a> PathReportGenerator<'a> {
+    pub fn new(from: &'a str, to: &'a str) -> Self {
+        Self { from, to }
+    }
+
+    pub fn generate_human_report(
+        &self,
+        chains: &[DependencyChain],
+    ) -> Result<String, FerrisWheelError> {
+        let mut output = String::new();
+
+        let Some(first) = chains.first() else {
+            writeln!(
+                output,
+                "\n✅ No dependency path from {} to {}",
+                self.from, self.to
+            )?;
+            return Ok(output);
+        };
+
+        writeln!(
+            output,
+            "\n🧭 {} shortest {} from {} to {} ({} {}):",
+            chains.len(),
+            pluralize("path", chains.len()),
+            self.from,
+            self.to,
+            first.hops.len(),
+            pluralize("hop", first.hops.len())
+        )?;
+
+        for (i, chain) in chains.iter().enumerate() {
+            writeln!(
+                output,
+                "\n🔗 Path #{}: {}",
+                i + 1,
+                chain.workspaces.join(" → ")
+            )?;
+            for hop in &chain.hops {
+                writeln!(output, "  📦 {} → {}", hop.from_workspace, hop.to_workspace)?;
+                for edge in &hop.edges {
+                    writeln!(
+                        output,
+                        "    → {} → {} ({})",
+                        edge.from_crate, edge.to_crate, edge.dependency_type
+                    )?;
+                }
+            }
+        }
+
+        Ok(output)
+    }
+
+    pub fn generate_json_report(
+        &self,
+        chains: &[DependencyChain],
+    ) -> Result<String, FerrisWheelError> {
+        let report = PathJsonReport {
+            from: self.from,
+            to: self.to,
+            hops: chains.first().map(|chain| chain.hops.len()),
+            paths: chains,
+        };
+
+        Ok(serde_json::to_string_pretty(&report)?)
+    }
+
+    pub fn generate_junit_report(
+        &self,
+        chains: &[DependencyChain],
+    ) -> Result<String, FerrisWheelError> {
+        let mut output = String::new();
+
+        writeln!(output, r#"<?xml version="1.0" encoding="UTF-8"?>"#)?;
+        writeln!(
+            output,
+            r#"<testsuites name="workspace-paths" tests="1" failures="0">"#
+        )?;
+        writeln!(
+            output,
+            r#"  <testsuite name="path-analysis" tests="1" failures="0">"#
+        )?;
+        writeln!(
+            output,
+            r#"    <testcase name="trace-workspace-path" classname="ferris-wheel">"#
+        )?;
+
+        writeln!(
+            output,
+            "Dependency paths from {} to {}: {}",
+            self.from,
+            self.to,
+            chains.len()
+        )?;
+        for chain in chains {
+            writeln!(output, "  {}", chain.workspaces.join(" -&gt; "))?;
+        }
+
+        writeln!(output, r#"    </testcase>"#)?;
+        writeln!(output, r#"  </testsuite>"#)?;
+        writeln!(output, r#"</testsuites>"#)?;
+
+        Ok(output)
+    }
+
+    pub fn generate_github_report(
+        &self,
+        chains: &[DependencyChain],
+    ) -> Result<String, FerrisWheelError> {
+        let mut output = String::new();
+
+        if chains.is_empty() {
+            writeln!(
+                output,
+                "::notice title=Dependency Path::No dependency path from {} to {}",
+                self.from, self.to
+            )?;
+            return Ok(output);
+        }
+
+        for chain in chains {
+            writeln!(
+                output,
+                "::notice title=Dependency Path::{}",
+                chain.workspaces.join(" → ")
+            )?;
+        }
+
+        Ok(output)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::DependencyType;
+
+    fn create_test_graph() -> DiGraph<WorkspaceNode, DependencyEdge> {
+        let mut graph = DiGraph::new();
+        let nodes: Vec<NodeIndex> = ["tools", "utils", "services", "core"]
+            .iter()
+            .map(|name| {
+                graph.add_node(
+                    WorkspaceNode::builder()
+                        .with_name(name.to_string())
+                        .with_crates(vec![format!("{name}-lib")])
+                        .build()
+                        .unwrap(),
+                )
+            })
+            .collect();
+
+        // tools -> utils -> core, tools -> services -> core
+        for (from, to, from_crate, to_crate) in [
+            (0, 1, "tools-cli", "utils-fs"),
+            (1, 3, "utils-fs", "core-api"),
+            (0, 2, "tools-cli", "services-http"),
+            (2, 3, "services-http", "core-api"),
+            (2, 3, "services-db", "core-types"),
+        ] {
+            graph.add_edge(
+                nodes[from],
+                nodes[to],
+                DependencyEdge::builder()
+                    .with_from_crate(from_crate)
+                    .with_to_crate(to_crate)
+                    .with_dependency_type(DependencyType::Normal)
+                    .build()
+                    .unwrap(),
+            );
+        }
+
+        graph
+    }
+
+    #[test]
+    fn test_find_dependency_chains() {
+        let graph = create_test_graph();
+
+        let chains = find_dependency_chains(&graph, "tools", "core", 10).unwrap();
+
+        assert_eq!(chains.len(), 2);
+        assert_eq!(chains[0].workspaces, vec!["tools", "utils", "core"]);
+        assert_eq!(chains[1].workspaces, vec!["tools", "services", "core"]);
+        assert_eq!(chains[1].hops[1].edges.len(), 2);
+        assert_eq!(chains[1].hops[1].edges[0].from_crate, "services-db");
+
+        assert!(
+            find_dependency_chains(&graph, "core", "tools", 10)
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_unknown_or_identical_workspaces_are_errors() {
+        let graph = create_test_graph();
+
+        assert!(matches!(
+            find_dependency_chains(&graph, "tools", "missing", 10),
+            Err(FerrisWheelError::ConfigurationError { .. })
+        ));
+        assert!(matches!(
+            find_dependency_chains(&graph, "core", "core", 10),
+            Err(FerrisWheelError::ConfigurationError { .. })
+        ));
+    }
+
+    #[test]
+    fn test_reports() {
+        let graph = create_test_graph();
+        let chains = find_dependency_chains(&graph, "tools", "core", 1).unwrap();
+        let generator = PathReportGenerator::new("tools", "core");
+
+        let human = generator.generate_human_report(&chains).unwrap();
+        assert!(human.contains("1 shortest path from tools to core (2 hops)"));
+        assert!(human.contains("    → tools-cli → utils-fs (Normal)"));
+
+        let json: serde_json::Value =
+            serde_json::from_str(&generator.generate_json_report(&chains).unwrap()).unwrap();
+        assert_eq!(json["hops"], 2);
+        assert_eq!(
+            json["paths"][0]["hops"][0]["edges"][0]["to_crate"],
+            "utils-fs"
+        );
+
+        let empty = generator.generate_json_report(&[]).unwrap();
+        assert!(empty.contains("\"hops\": null"));
+    }
+}
@@ -0,0 +1,163 @@
+//! Schema command implementation
+
+use miette::{Result, WrapErr};
+use schemars::schema_for;
+
+use crate::cli::{Commands, SchemaDocument};
+use crate::commands::affected::AffectedJsonReport;
+use crate::common::{ConfigBuilder, FromCommand};
+use crate::config::{ProjectConfig, SchemaConfig};
+use crate::error::FerrisWheelError;
+use crate::reports::json::JsonReport;
+
+impl FromCommand for SchemaConfig {
+    fn from_command(
+        command: Commands,
+        _project: Option<&ProjectConfig>,
+    ) -> Result<Self, FerrisWheelError> {
+        match command {
+            Commands::Schema { document } => {
+                SchemaConfig::builder().with_document(document).build()
+            }
+            _ => Err(FerrisWheelError::ConfigurationError {
+                message: "Invalid command type for SchemaConfig".to_string(),
+            }),
+        }
+    }
+}
+
+crate::impl_try_from_command!(SchemaConfig);
+
+/// Execute the schema command for printing a JSON Schema
+pub fn execute_schema_command(command: Commands, project: Option<&ProjectConfig>) -> Result<()> {
+    let config = SchemaConfig::from_command(command, project)
+        .wrap_err("Failed to parse schema command configuration")?;
+
+    use crate::executors::CommandExecutor;
+    use crate::executors::schema::SchemaExecutor;
+    SchemaExecutor::execute(config)
+}
+
+/// The JSON Schema of `document`, pretty-printed
+pub fn generate_schema(document: SchemaDocument) -> Result<String, FerrisWheelError> {
+    let schema = match document {
+        SchemaDocument::Report => schema_for!(JsonReport),
+        SchemaDocument::Ripples => schema_for!(AffectedJsonReport),
+        SchemaDocument::Config => schema_for!(ProjectConfig),
+    };
+    serde_json::to_string_pretty(&schema).map_err(FerrisWheelError::Json)
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::Value;
+
+    use super::*;
+    use crate::detector::{CycleDetector, WorkspaceCycle};
+    use crate::reports::ReportGenerator;
+    use crate::reports::json::JsonReportGenerator;
+
+    fn schema(document: SchemaDocument) -> Value {
+        serde_json::from_str(&generate_schema(document).unwrap()).unwrap()
+    }
+
+    /// Property names of the object schema at `pointer`, following `$ref`s
+    fn properties<'a>(schema: &'a Value, pointer: &str) -> Vec<&'a str> {
+        let mut object = schema.pointer(pointer).unwrap();
+        if let Some(reference) = object.get("$ref").and_then(Value::as_str) {
+            object = schema.pointer(reference.trim_start_matches('#')).unwrap();
+        }
+        let mut names: Vec<_> = object["properties"]
+            .as_object()
+            .unwrap()
+            .keys()
+            .map(String::as_str)
+            .collect();
+        names.sort_unstable();
+        names
+    }
+
+    #[test]
+    fn test_report_schema_matches_report() {
+        let mut detector = CycleDetector::new();
+        detector.add_cycle(
+            WorkspaceCycle::builder()
+                .with_workspace_names(vec!["a".to_string(), "b".to_string()])
+                .add_edge()
+                .from_workspace("a")
+                .to_workspace("b")
+                .from_crate("a")
+                .to_crate("b")
+                .dependency_type("Normal")
+                .add_edge()
+                .unwrap()
+                .from_workspace("b")
+                .to_workspace("a")
+                .from_crate("b")
+                .to_crate("a")
+                .dependency_type("Dev")
+                .build()
+                .unwrap(),
+        );
+        let report: Value = serde_json::from_str(
+            &JsonReportGenerator::new()
+                .with_partial("timed out".to_string())
+                .with_layer_violations(Vec::new())
+                .generate_report(&detector)
+                .unwrap(),
+        )
+        .unwrap();
+
+        let keys = |value: &Value| -> Vec<String> {
+            let mut keys: Vec<_> = value.as_object().unwrap().keys().cloned().collect();
+            keys.sort_unstable();
+            keys
+        };
+
+        let schema = schema(SchemaDocument::Report);
+        let top = properties(&schema, "");
+        for key in keys(&report) {
+            assert!(
+                top.contains(&key.as_str()),
+                "report key {key} not in schema"
+            );
+        }
+        assert!(top.contains(&"grade"));
+        assert_eq!(
+            keys(&report["cycles"][0]),
+            properties(&schema, "/$defs/JsonCycle")
+        );
+        assert_eq!(
+            keys(&report["cycles"][0]["break_points"][0]),
+            properties(&schema, "/$defs/JsonBreakPoint")
+        );
+    }
+
+    #[test]
+    fn test_config_schema_covers_sections() {
+        let schema = schema(SchemaDocument::Config);
+
+        assert_eq!(
+            properties(&schema, ""),
+            [
+                "allow", "defaults", "effort", "grade", "layers", "links", "metadata", "names",
+                "policy", "profile", "rules", "severity", "theme"
+            ]
+        );
+        assert_eq!(schema["additionalProperties"], false);
+        assert!(properties(&schema, "/$defs/SeverityModel").contains(&"fail-at"));
+        assert!(
+            properties(&schema, "/$defs/ProfileConfig").contains(&"error-on-cycles"),
+            "profile keys are kebab-case"
+        );
+    }
+
+    #[test]
+    fn test_ripples_schema() {
+        let schema = schema(SchemaDocument::Ripples);
+
+        assert_eq!(schema["title"], "AffectedJsonReport");
+        assert!(properties(&schema, "").contains(&"affected_for_tests"));
+        assert!(properties(&schema, "/$defs/AffectedCrate").contains(&"chain"));
+    }
+}
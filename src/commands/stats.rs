@@ -0,0 +1,390 @@
+//! Stats command implementation
+
+use std::collections::BTreeSet;
+use std::fmt::Write;
+
+use miette::{Result, WrapErr};
+use petgraph::algo::tarjan_scc;
+use petgraph::graph::DiGraph;
+use petgraph::visit::EdgeRef;
+use serde::Serialize;
+
+use crate::cli::Commands;
+use crate::common::{ConfigBuilder, FromCommand};
+use crate::config::{ProjectConfig, StatsConfig};
+use crate::error::FerrisWheelError;
+use crate::graph::{DependencyEdge, WorkspaceNode};
+use crate::utils::string::pluralize;
+
+impl FromCommand for StatsConfig {
+    fn from_command(
+        command: Commands,
+        _project: Option<&ProjectConfig>,
+    ) -> Result<Self, FerrisWheelError> {
+        match command {
+            Commands::Stats {
+                common,
+                format,
+                output,
+            } => StatsConfig::builder()
+                .with_paths(common.get_paths())
+                .with_format(format)
+                .with_output(output.output)
+                .with_exclude_dev(common.exclude_dev)
+                .with_exclude_build(common.exclude_build)
+                .with_exclude_target(common.exclude_target)
+                .with_target(common.target)
+                .with_max_workspaces(common.max_workspaces)
+                .with_exclude_paths(common.exclude_paths.clone())
+                .with_exclude_crates(common.exclude_crates.clone())
+                .with_follow_symlinks(common.follow_symlinks)
+                .with_no_cache(common.no_cache)
+                .with_analyzer(common.analyzer_config())
+                .with_workspace_names(common.workspace_names.clone())
+                .build(),
+            _ => Err(FerrisWheelError::ConfigurationError {
+                message: "Invalid command type for StatsConfig".to_string(),
+            }),
+        }
+    }
+}
+
+crate::impl_try_from_command!(StatsConfig);
+
+/// Execute the stats command for computing per-workspace dependency metrics
+pub fn execute_stats_command(command: Commands, project: Option<&ProjectConfig>) -> Result<()> {
+    let config = StatsConfig::from_command(command, project)
+        .wrap_err("Failed to parse stats command configuration")?;
+
+    use crate::executors::CommandExecutor;
+    use crate::executors::stats::StatsExecutor;
+    StatsExecutor::execute(config)
+}
+
+/// Dependency metrics of one workspace
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct WorkspaceMetrics {
+    pub workspace: String,
+    pub crates: usize,
+    /// Workspaces depending on this one (afferent coupling, Ca)
+    pub fan_in: usize,
+    /// Workspaces this one depends on (efferent coupling, Ce)
+    pub fan_out: usize,
+    /// Ce / (Ca + Ce), from 0 (stable) to 1 (unstable); `None` for a
+    /// workspace without dependencies in either direction
+    pub instability: Option<f64>,
+    /// Longest chain of workspace dependencies below this one
+    ///
+    /// Workspaces of one strongly connected component share their depth.
+    pub depth: usize,
+    /// Size of the strongly connected component holding this workspace, 1
+    /// when it is not part of a cycle
+    pub scc_size: usize,
+}
+
+/// Dependency metrics of every workspace of a graph
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct StatsReport {
+    /// Sorted by workspace name
+    pub workspaces: Vec<WorkspaceMetrics>,
+    /// Dependent workspace pairs, counting each pair once
+    pub dependencies: usize,
+    pub max_depth: usize,
+    pub largest_scc: usize,
+    /// Strongly connected components with more than one workspace
+    pub cycles: usize,
+}
+
+impl StatsReport {
+    /// Compute the metrics of every workspace in a workspace graph
+    pub fn compute(graph: &DiGraph<WorkspaceNode, DependencyEdge>) -> Self {
+        let pairs: BTreeSet<_> = graph
+            .edge_references()
+            .filter(|edge| edge.source() != edge.target())
+            .map(|edge| (edge.source().index(), edge.target().index()))
+            .collect();
+
+        let mut fan_in = vec![0; graph.node_count()];
+        let mut fan_out = vec![0; graph.node_count()];
+        for &(from, to) in &pairs {
+            fan_out[from] += 1;
+            fan_in[to] += 1;
+        }
+
+        // Tarjan yields components in reverse topological order, so every
+        // component a node depends on has its depth computed already
+        let components = tarjan_scc(graph);
+        let mut component_of = vec![0; graph.node_count()];
+        for (component, nodes) in components.iter().enumerate() {
+            for node in nodes {
+                component_of[node.index()] = component;
+            }
+        }
+        let mut component_depth = vec![0; components.len()];
+        for (component, nodes) in components.iter().enumerate() {
+            component_depth[component] = nodes
+                .iter()
+                .flat_map(|&node| graph.neighbors(node))
+                .map(|dependency| component_of[dependency.index()])
+                .filter(|&other| other != component)
+                .map(|other| component_depth[other] + 1)
+                .max()
+                .unwrap_or(0);
+        }
+
+        let mut workspaces: Vec<WorkspaceMetrics> = graph
+            .node_indices()
+            .map(|node| {
+                let i = node.index();
+                let component = component_of[i];
+                WorkspaceMetrics {
+                    workspace: graph[node].name().to_string(),
+                    crates: graph[node].crates().len(),
+                    fan_in: fan_in[i],
+                    fan_out: fan_out[i],
+                    instability: (fan_in[i] + fan_out[i] > 0)
+                        .then(|| fan_out[i] as f64 / (fan_in[i] + fan_out[i]) as f64),
+                    depth: component_depth[component],
+                    scc_size: components[component].len(),
+                }
+            })
+            .collect();
+        workspaces.sort_by(|a, b| a.workspace.cmp(&b.workspace));
+
+        Self {
+            dependencies: pairs.len(),
+            max_depth: component_depth.iter().copied().max().unwrap_or(0),
+            largest_scc: components.iter().map(Vec::len).max().unwrap_or(0),
+            cycles: components.iter().filter(|nodes| nodes.len() > 1).count(),
+            workspaces,
+        }
+    }
+}
+
+/// Report generator for per-workspace dependency metrics
+pub struct StatsReportGenerator;
+
+impl StatsReportGenerator {
+    pub fn new() -> Self {
+        Self
+    }
+
+    pub fn generate_human_report(&self, report: &StatsReport) -> Result<String, FerrisWheelError> {
+        let mut output = String::new();
+
+        writeln!(
+            output,
+            "\n📊 Metrics for {} {} ({} {}):\n",
+            report.workspaces.len(),
+            pluralize("workspace", report.workspaces.len()),
+            report.dependencies,
+            dependency_noun(report.dependencies)
+        )?;
+
+        let width = report
+            .workspaces
+            .iter()
+            .map(|metrics| metrics.workspace.chars().count())
+            .chain(["Workspace".len()])
+            .max()
+            .unwrap_or_default();
+        writeln!(
+            output,
+            "  {:<width$}  Crates  Fan-in  Fan-out  Instability  Depth  SCC",
+            "Workspace"
+        )?;
+        for metrics in &report.workspaces {
+            writeln!(
+                output,
+                "  {:<width$}  {:>6}  {:>6}  {:>7}  {:>11}  {:>5}  {:>3}",
+                metrics.workspace,
+                metrics.crates,
+                metrics.fan_in,
+                metrics.fan_out,
+                metrics
+                    .instability
+                    .map_or_else(|| "-".to_string(), |value| format!("{value:.2}")),
+                metrics.depth,
+                metrics.scc_size
+            )?;
+        }
+
+        writeln!(
+            output,
+            "\n  Deepest chain: {} {}, largest SCC: {} {}, {} {}",
+            report.max_depth,
+            pluralize("level", report.max_depth),
+            report.largest_scc,
+            pluralize("workspace", report.largest_scc),
+            report.cycles,
+            pluralize("cycle", report.cycles)
+        )?;
+
+        Ok(output)
+    }
+
+    pub fn generate_json_report(&self, report: &StatsReport) -> Result<String, FerrisWheelError> {
+        Ok(serde_json::to_string_pretty(report)?)
+    }
+
+    pub fn generate_csv_report(&self, report: &StatsReport) -> Result<String, FerrisWheelError> {
+        let mut output = String::new();
+
+        writeln!(
+            output,
+            "workspace,crates,fan_in,fan_out,instability,depth,scc_size"
+        )?;
+        for metrics in &report.workspaces {
+            writeln!(
+                output,
+                "{},{},{},{},{},{},{}",
+                csv_field(&metrics.workspace),
+                metrics.crates,
+                metrics.fan_in,
+                metrics.fan_out,
+                metrics
+                    .instability
+                    .map(|value| format!("{value:.4}"))
+                    .unwrap_or_default(),
+                metrics.depth,
+                metrics.scc_size
+            )?;
+        }
+
+        Ok(output)
+    }
+}
+
+impl Default for StatsReportGenerator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Quote a CSV field when it holds a separator, quote or line break
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+fn dependency_noun(count: usize) -> &'static str {
+    if count == 1 {
+        "dependency"
+    } else {
+        "dependencies"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+    use crate::graph::DependencyType;
+
+    fn graph(
+        workspaces: &[&str],
+        edges: &[(&str, &str)],
+    ) -> DiGraph<WorkspaceNode, DependencyEdge> {
+        let mut graph = DiGraph::new();
+        let nodes: HashMap<&str, _> = workspaces
+            .iter()
+            .map(|&name| {
+                let node = graph.add_node(
+                    WorkspaceNode::builder()
+                        .with_name(name.to_string())
+                        .with_crates(vec![format!("{name}-lib")])
+                        .build()
+                        .unwrap(),
+                );
+                (name, node)
+            })
+            .collect();
+        for &(from, to) in edges {
+            graph.add_edge(
+                nodes[from],
+                nodes[to],
+                DependencyEdge::builder()
+                    .with_from_crate(&format!("{from}-lib"))
+                    .with_to_crate(&format!("{to}-lib"))
+                    .with_dependency_type(DependencyType::Normal)
+                    .build()
+                    .unwrap(),
+            );
+        }
+        graph
+    }
+
+    #[test]
+    fn test_metrics() {
+        // app -> services <-> billing -> core, app -> core (twice)
+        let graph = graph(
+            &["app", "services", "billing", "core", "scripts"],
+            &[
+                ("app", "services"),
+                ("services", "billing"),
+                ("billing", "services"),
+                ("billing", "core"),
+                ("app", "core"),
+                ("app", "core"),
+            ],
+        );
+
+        let report = StatsReport::compute(&graph);
+
+        let metrics = |name: &str| {
+            report
+                .workspaces
+                .iter()
+                .find(|metrics| metrics.workspace == name)
+                .unwrap()
+        };
+        assert_eq!(report.dependencies, 5);
+        assert_eq!(report.max_depth, 2);
+        assert_eq!(report.largest_scc, 2);
+        assert_eq!(report.cycles, 1);
+
+        let app = metrics("app");
+        assert_eq!((app.fan_in, app.fan_out), (0, 2));
+        assert_eq!(app.instability, Some(1.0));
+        assert_eq!(app.depth, 2);
+
+        let services = metrics("services");
+        assert_eq!((services.fan_in, services.fan_out), (2, 1));
+        assert_eq!(services.depth, 1);
+        assert_eq!(services.scc_size, 2);
+        assert_eq!(metrics("billing").depth, 1);
+
+        let core = metrics("core");
+        assert_eq!(core.instability, Some(0.0));
+        assert_eq!((core.depth, core.scc_size), (0, 1));
+
+        assert_eq!(metrics("scripts").instability, None);
+    }
+
+    #[test]
+    fn test_reports() {
+        let graph = graph(&["app", "core,legacy"], &[("app", "core,legacy")]);
+        let report = StatsReport::compute(&graph);
+        let generator = StatsReportGenerator::new();
+
+        let human = generator.generate_human_report(&report).unwrap();
+        assert!(human.contains("Metrics for 2 workspaces (1 dependency)"));
+        assert!(human.contains("Deepest chain: 1 level, largest SCC: 1 workspace, 0 cycles"));
+
+        let csv = generator.generate_csv_report(&report).unwrap();
+        assert_eq!(
+            csv,
+            "workspace,crates,fan_in,fan_out,instability,depth,scc_size\napp,1,0,1,1.0000,1,1\n\"\
+             core,legacy\",1,1,0,0.0000,0,1\n"
+        );
+
+        let json: serde_json::Value =
+            serde_json::from_str(&generator.generate_json_report(&report).unwrap()).unwrap();
+        assert_eq!(json["workspaces"][0]["instability"], 1.0);
+        assert_eq!(json["max_depth"], 1);
+    }
+}
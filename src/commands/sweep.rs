@@ -0,0 +1,517 @@
+//! Sweep command implementation
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+
+use miette::{Result, WrapErr};
+use rayon::prelude::*;
+use serde::Serialize;
+use walkdir::WalkDir;
+
+use crate::analyzer::{CrateMember, Dependency, WorkspaceInfo};
+use crate::cli::Commands;
+use crate::common::{ConfigBuilder, FromCommand};
+use crate::config::{ProjectConfig, SweepConfig};
+use crate::error::FerrisWheelError;
+use crate::toml_parser::find_dependency_line;
+use crate::utils::string::pluralize;
+
+impl FromCommand for SweepConfig {
+    fn from_command(
+        command: Commands,
+        _project: Option<&ProjectConfig>,
+    ) -> Result<Self, FerrisWheelError> {
+        match command {
+            Commands::Sweep { common, format } => SweepConfig::builder()
+                .with_paths(common.get_paths())
+                .with_format(format.format)
+                .with_output(format.output.output)
+                .with_exclude_dev(common.exclude_dev)
+                .with_exclude_build(common.exclude_build)
+                .with_exclude_target(common.exclude_target)
+                .with_target(common.target)
+                .with_max_workspaces(common.max_workspaces)
+                .with_exclude_paths(common.exclude_paths.clone())
+                .with_exclude_crates(common.exclude_crates.clone())
+                .with_follow_symlinks(common.follow_symlinks)
+                .with_no_cache(common.no_cache)
+                .with_analyzer(common.analyzer_config())
+                .with_workspace_names(common.workspace_names.clone())
+                .build(),
+            _ => Err(FerrisWheelError::ConfigurationError {
+                message: "Invalid command type for SweepConfig".to_string(),
+            }),
+        }
+    }
+}
+
+crate::impl_try_from_command!(SweepConfig);
+
+/// Execute the sweep command for finding unused internal dependencies
+pub fn execute_sweep_command(command: Commands, project: Option<&ProjectConfig>) -> Result<()> {
+    let config = SweepConfig::from_command(command, project)
+        .wrap_err("Failed to parse sweep command configuration")?;
+
+    use crate::executors::CommandExecutor;
+    use crate::executors::sweep::SweepExecutor;
+    SweepExecutor::execute(config)
+}
+
+/// An internal dependency that a crate's sources never reference
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct UnusedDependency {
+    pub workspace: String,
+    #[serde(rename = "crate")]
+    pub crate_name: String,
+    /// Name the dependency is declared under
+    pub dependency: String,
+    /// Dependency kinds it is declared as
+    pub dependency_types: Vec<String>,
+    pub manifest: PathBuf,
+    /// 1-based line declaring the dependency, if found
+    pub line: Option<usize>,
+}
+
+/// Dependency kinds to check
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SweepScope {
+    pub dev: bool,
+    pub build: bool,
+    pub target: bool,
+}
+
+impl Default for SweepScope {
+    fn default() -> Self {
+        Self {
+            dev: true,
+            build: true,
+            target: true,
+        }
+    }
+}
+
+/// Unused internal dependencies across the analyzed workspaces
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SweepReport {
+    pub crates_scanned: usize,
+    /// Internal dependencies checked, counting each crate and dependency once
+    pub internal_dependencies: usize,
+    /// Sorted by workspace, crate and dependency names
+    pub unused: Vec<UnusedDependency>,
+}
+
+impl SweepReport {
+    /// Check every internal dependency of every crate against the crate's
+    /// sources
+    ///
+    /// A dependency is internal when it names a crate of the analyzed
+    /// workspaces.
+    pub fn scan(
+        workspaces: &HashMap<PathBuf, WorkspaceInfo>,
+        crate_to_paths: &HashMap<String, Vec<PathBuf>>,
+        scope: SweepScope,
+    ) -> Self {
+        let crates: Vec<(&WorkspaceInfo, &CrateMember)> = workspaces
+            .values()
+            .flat_map(|workspace| {
+                workspace
+                    .members()
+                    .iter()
+                    .map(move |member| (workspace, member))
+            })
+            .collect();
+
+        let results: Vec<(usize, Vec<UnusedDependency>)> = crates
+            .par_iter()
+            .map(|(workspace, member)| {
+                let internal = internal_dependencies(member, crate_to_paths, scope);
+                if internal.is_empty() {
+                    return (0, Vec::new());
+                }
+
+                let referenced = referenced_names_in(member.path());
+                let manifest = member.path().join("Cargo.toml");
+                let content = std::fs::read_to_string(&manifest).unwrap_or_default();
+                let unused = internal
+                    .iter()
+                    .filter(|(dependency, _)| {
+                        !referenced.contains(&dependency.declared_name().replace('-', "_"))
+                    })
+                    .map(|(dependency, dependency_types)| UnusedDependency {
+                        workspace: workspace.name().to_string(),
+                        crate_name: member.name().to_string(),
+                        dependency: dependency.declared_name().to_string(),
+                        dependency_types: dependency_types.clone(),
+                        manifest: manifest.clone(),
+                        line: find_dependency_line(&content, dependency.name()),
+                    })
+                    .collect();
+
+                (internal.len(), unused)
+            })
+            .collect();
+
+        let mut unused: Vec<UnusedDependency> = results
+            .iter()
+            .flat_map(|(_, unused)| unused.iter().cloned())
+            .collect();
+        unused.sort_by(|a, b| {
+            (&a.workspace, &a.crate_name, &a.dependency).cmp(&(
+                &b.workspace,
+                &b.crate_name,
+                &b.dependency,
+            ))
+        });
+
+        Self {
+            crates_scanned: crates.len(),
+            internal_dependencies: results.iter().map(|(count, _)| count).sum(),
+            unused,
+        }
+    }
+
+    pub fn has_unused(&self) -> bool {
+        !self.unused.is_empty()
+    }
+}
+
+/// A crate's dependencies on crates of the analyzed workspaces, each with the
+/// kinds it is declared as
+fn internal_dependencies<'a>(
+    member: &'a CrateMember,
+    crate_to_paths: &HashMap<String, Vec<PathBuf>>,
+    scope: SweepScope,
+) -> Vec<(&'a Dependency, Vec<String>)> {
+    let mut kinds: Vec<(&[Dependency], &str)> = vec![(member.dependencies(), "Normal")];
+    if scope.dev {
+        kinds.push((member.dev_dependencies(), "Dev"));
+    }
+    if scope.build {
+        kinds.push((member.build_dependencies(), "Build"));
+    }
+    if scope.target {
+        for dependencies in member.target_dependencies().values() {
+            kinds.push((dependencies, "Target"));
+        }
+    }
+
+    let mut internal: BTreeMap<&str, (&Dependency, Vec<String>)> = BTreeMap::new();
+    for (dependencies, kind) in kinds {
+        for dependency in dependencies {
+            if !crate_to_paths.contains_key(dependency.name()) {
+                continue;
+            }
+            let (_, dependency_types) = internal
+                .entry(dependency.declared_name())
+                .or_insert_with(|| (dependency, Vec::new()));
+            if !dependency_types.iter().any(|existing| existing == kind) {
+                dependency_types.push(kind.to_string());
+            }
+        }
+    }
+
+    internal.into_values().collect()
+}
+
+/// Names referenced in the Rust sources of the crate in `crate_dir`
+///
+/// Directories holding another crate's manifest and `target` directories are
+/// skipped, so a root package doesn't pick up its members' sources.
+fn referenced_names_in(crate_dir: &Path) -> HashSet<String> {
+    let mut names = HashSet::new();
+    let sources = WalkDir::new(crate_dir)
+        .into_iter()
+        .filter_entry(|entry| {
+            entry.depth() == 0
+                || !entry.file_type().is_dir()
+                || (entry.file_name() != "target"
+                    && entry.file_name() != ".git"
+                    && !entry.path().join("Cargo.toml").exists())
+        })
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.file_type().is_file())
+        .filter(|entry| entry.path().extension().is_some_and(|ext| ext == "rs"));
+
+    for entry in sources {
+        if let Ok(source) = std::fs::read_to_string(entry.path()) {
+            referenced_names(&source, &mut names);
+        }
+    }
+
+    names
+}
+
+/// Collect the identifiers that start a path (`name::item`) or follow `use`
+/// or `extern crate` in Rust source
+fn referenced_names(source: &str, names: &mut HashSet<String>) {
+    let bytes = source.as_bytes();
+    let mut previous: Option<&str> = None;
+    let mut i = 0;
+
+    while i < bytes.len() {
+        let byte = bytes[i];
+        if byte.is_ascii_alphabetic() || byte == b'_' {
+            let start = i;
+            while i < bytes.len() && (bytes[i].is_ascii_alphanumeric() || bytes[i] == b'_') {
+                i += 1;
+            }
+            let ident = &source[start..i];
+            if source[i..].trim_start().starts_with("::")
+                || matches!(previous, Some("use" | "crate"))
+            {
+                names.insert(ident.to_string());
+            }
+            previous = Some(ident);
+        } else {
+            if !byte.is_ascii_whitespace() {
+                previous = None;
+            }
+            i += 1;
+        }
+    }
+}
+
+/// Report generator for unused internal dependencies
+pub struct SweepReportGenerator;
+
+impl SweepReportGenerator {
+    pub fn new() -> Self {
+        Self
+    }
+
+    pub fn generate_human_report(&self, report: &SweepReport) -> Result<String, FerrisWheelError> {
+        let mut output = String::new();
+
+        if !report.has_unused() {
+            writeln!(
+                output,
+                "\n✨ All {} internal {} in {} {} are used",
+                report.internal_dependencies,
+                dependency_noun(report.internal_dependencies),
+                report.crates_scanned,
+                pluralize("crate", report.crates_scanned)
+            )?;
+            return Ok(output);
+        }
+
+        writeln!(
+            output,
+            "\n🧹 {} unused internal {} in {} {}:\n",
+            report.unused.len(),
+            dependency_noun(report.unused.len()),
+            report.crates_scanned,
+            pluralize("crate", report.crates_scanned)
+        )?;
+        for unused in &report.unused {
+            writeln!(
+                output,
+                "  📦 {}/{} → {} ({})",
+                unused.workspace,
+                unused.crate_name,
+                unused.dependency,
+                unused.dependency_types.join(", ")
+            )?;
+            writeln!(output, "     {}", location(unused))?;
+        }
+
+        Ok(output)
+    }
+
+    pub fn generate_json_report(&self, report: &SweepReport) -> Result<String, FerrisWheelError> {
+        Ok(serde_json::to_string_pretty(report)?)
+    }
+
+    pub fn generate_junit_report(&self, report: &SweepReport) -> Result<String, FerrisWheelError> {
+        let mut output = String::new();
+        let failures = if report.has_unused() { 1 } else { 0 };
+
+        writeln!(output, r#"<?xml version="1.0" encoding="UTF-8"?>"#)?;
+        writeln!(
+            output,
+            r#"<testsuites name="unused-dependencies" tests="1" failures="{failures}">"#
+        )?;
+        writeln!(
+            output,
+            r#"  <testsuite name="sweep-analysis" tests="1" failures="{failures}">"#
+        )?;
+        writeln!(
+            output,
+            r#"    <testcase name="check-unused-dependencies" classname="ferris-wheel">"#
+        )?;
+        if report.has_unused() {
+            writeln!(
+                output,
+                r#"      <failure message="Unused internal dependencies found">"#
+            )?;
+            for unused in &report.unused {
+                writeln!(
+                    output,
+                    "{}/{} -&gt; {} ({})",
+                    unused.workspace,
+                    unused.crate_name,
+                    unused.dependency,
+                    location(unused)
+                )?;
+            }
+            writeln!(output, r#"      </failure>"#)?;
+        }
+        writeln!(output, r#"    </testcase>"#)?;
+        writeln!(output, r#"  </testsuite>"#)?;
+        writeln!(output, r#"</testsuites>"#)?;
+
+        Ok(output)
+    }
+
+    pub fn generate_github_report(&self, report: &SweepReport) -> Result<String, FerrisWheelError> {
+        let mut output = String::new();
+
+        for unused in &report.unused {
+            write!(output, "::error file={}", unused.manifest.display())?;
+            if let Some(line) = unused.line {
+                write!(output, ",line={line}")?;
+            }
+            writeln!(
+                output,
+                ",title=Unused Dependency::{} never uses {}",
+                unused.crate_name, unused.dependency
+            )?;
+        }
+        if !report.has_unused() {
+            writeln!(
+                output,
+                "::notice title=Unused Dependencies::All {} internal {} are used",
+                report.internal_dependencies,
+                dependency_noun(report.internal_dependencies)
+            )?;
+        }
+
+        Ok(output)
+    }
+}
+
+impl Default for SweepReportGenerator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn location(unused: &UnusedDependency) -> String {
+    match unused.line {
+        Some(line) => format!("{}:{line}", unused.manifest.display()),
+        None => unused.manifest.display().to_string(),
+    }
+}
+
+fn dependency_noun(count: usize) -> &'static str {
+    if count == 1 {
+        "dependency"
+    } else {
+        "dependencies"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::*;
+
+    #[test]
+    fn test_referenced_names() {
+        let mut names = HashSet::new();
+        referenced_names(
+            r#"
+use core_types::Id;
+use serde;
+extern crate legacy_macros;
+pub(crate) fn parse() -> io :: Result<()> {
+    let id = ids::next();
+    helpers.call();
+}
+"#,
+            &mut names,
+        );
+
+        for name in ["core_types", "serde", "legacy_macros", "io", "ids"] {
+            assert!(names.contains(name), "{name} not found in {names:?}");
+        }
+        assert!(!names.contains("helpers"));
+        assert!(!names.contains("parse"));
+    }
+
+    #[test]
+    fn test_unused_internal_dependencies_are_reported() {
+        let temp = tempfile::tempdir().unwrap();
+        let app = temp.path().join("app");
+        fs::create_dir_all(app.join("src")).unwrap();
+        fs::create_dir_all(app.join("tests")).unwrap();
+        fs::write(
+            app.join("Cargo.toml"),
+            r#"[package]
+name = "app"
+
+[dependencies]
+core-types = { path = "../core-types" }
+legacy = { path = "../legacy" }
+serde = "1"
+
+[dev-dependencies]
+test-utils = { path = "../test-utils" }
+"#,
+        )
+        .unwrap();
+        fs::write(
+            app.join("src/lib.rs"),
+            "pub fn id() -> core_types::Id { todo!() }\n",
+        )
+        .unwrap();
+        fs::write(app.join("tests/it.rs"), "use test_utils::fixture;\n").unwrap();
+
+        let dependency = |name: &str| Dependency::builder().with_name(name).build().unwrap();
+        let member = CrateMember::builder()
+            .with_name("app")
+            .with_path(app.clone())
+            .with_dependencies(vec![
+                dependency("core-types"),
+                dependency("legacy"),
+                dependency("serde"),
+            ])
+            .with_dev_dependencies(vec![dependency("test-utils")])
+            .build()
+            .unwrap();
+        let workspace = WorkspaceInfo::builder()
+            .with_name("app")
+            .with_members(vec![member])
+            .with_is_standalone(true)
+            .build()
+            .unwrap();
+        let workspaces = HashMap::from([(app.clone(), workspace)]);
+        let crate_to_paths: HashMap<String, Vec<PathBuf>> =
+            ["app", "core-types", "legacy", "test-utils"]
+                .into_iter()
+                .map(|name| (name.to_string(), vec![temp.path().join(name)]))
+                .collect();
+
+        let report = SweepReport::scan(&workspaces, &crate_to_paths, SweepScope::default());
+
+        assert_eq!(report.crates_scanned, 1);
+        assert_eq!(report.internal_dependencies, 3);
+        assert_eq!(
+            report.unused,
+            vec![UnusedDependency {
+                workspace: "app".to_string(),
+                crate_name: "app".to_string(),
+                dependency: "legacy".to_string(),
+                dependency_types: vec!["Normal".to_string()],
+                manifest: app.join("Cargo.toml"),
+                line: Some(6),
+            }]
+        );
+
+        let github = SweepReportGenerator::new()
+            .generate_github_report(&report)
+            .unwrap();
+        assert!(github.contains(",line=6,title=Unused Dependency::app never uses legacy"));
+    }
+}
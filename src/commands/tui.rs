@@ -0,0 +1,756 @@
+//! TUI command implementation
+
+use std::collections::BTreeSet;
+
+use miette::{Result, WrapErr};
+use petgraph::Direction;
+use petgraph::graph::{DiGraph, NodeIndex};
+use petgraph::visit::EdgeRef;
+use ratatui::Frame;
+use ratatui::crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::layout::{Constraint, Layout};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, List, ListItem, ListState, Paragraph, Wrap};
+
+use crate::cli::Commands;
+use crate::common::{ConfigBuilder, FromCommand};
+use crate::config::{ProjectConfig, TuiConfig};
+use crate::dependency_filter::DependencyFilter;
+use crate::detector::{CycleDetector, CycleDirection, WorkspaceCycle};
+use crate::error::FerrisWheelError;
+use crate::graph::{DependencyEdge, DependencyType, WorkspaceNode};
+use crate::utils::string::pluralize;
+
+impl FromCommand for TuiConfig {
+    fn from_command(
+        command: Commands,
+        _project: Option<&ProjectConfig>,
+    ) -> Result<Self, FerrisWheelError> {
+        match command {
+            Commands::Tui { common } => TuiConfig::builder()
+                .with_paths(common.get_paths())
+                .with_exclude_dev(common.exclude_dev)
+                .with_exclude_build(common.exclude_build)
+                .with_exclude_target(common.exclude_target)
+                .with_target(common.target)
+                .with_max_workspaces(common.max_workspaces)
+                .with_exclude_paths(common.exclude_paths.clone())
+                .with_exclude_crates(common.exclude_crates.clone())
+                .with_follow_symlinks(common.follow_symlinks)
+                .with_no_cache(common.no_cache)
+                .with_analyzer(common.analyzer_config())
+                .with_workspace_names(common.workspace_names.clone())
+                .build(),
+            _ => Err(FerrisWheelError::ConfigurationError {
+                message: "Invalid command type for TuiConfig".to_string(),
+            }),
+        }
+    }
+}
+
+crate::impl_try_from_command!(TuiConfig);
+
+/// Execute the tui command for exploring workspaces and cycles interactively
+pub fn execute_tui_command(command: Commands, project: Option<&ProjectConfig>) -> Result<()> {
+    let config = TuiConfig::from_command(command, project)
+        .wrap_err("Failed to parse tui command configuration")?;
+
+    use crate::executors::CommandExecutor;
+    use crate::executors::tui::TuiExecutor;
+    TuiExecutor::execute(config)
+}
+
+/// Which list the explorer shows
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum View {
+    Workspaces,
+    Cycles,
+}
+
+/// State of the interactive explorer
+///
+/// Holds the unfiltered workspace graph and derives the filtered graph and
+/// its cycles whenever a dependency kind is toggled, so key handling and
+/// rendering never touch the terminal and can be tested on their own.
+pub struct App {
+    graph: DiGraph<WorkspaceNode, DependencyEdge>,
+    filtered: DiGraph<WorkspaceNode, DependencyEdge>,
+    cycles: Vec<WorkspaceCycle>,
+    /// Workspaces sorted by name
+    workspaces: Vec<NodeIndex>,
+    exclude_dev: bool,
+    exclude_build: bool,
+    exclude_target: bool,
+    view: View,
+    /// Selected entry of the visible workspaces or cycles
+    selected: usize,
+    expanded: BTreeSet<NodeIndex>,
+    search: String,
+    searching: bool,
+    quit: bool,
+}
+
+impl App {
+    /// Create the explorer for an unfiltered workspace graph, starting with
+    /// the given dependency kinds filtered out
+    pub fn new(
+        graph: DiGraph<WorkspaceNode, DependencyEdge>,
+        exclude_dev: bool,
+        exclude_build: bool,
+        exclude_target: bool,
+    ) -> Result<Self> {
+        let mut workspaces: Vec<NodeIndex> = graph.node_indices().collect();
+        workspaces.sort_by(|&a, &b| graph[a].name().cmp(graph[b].name()));
+
+        let mut app = Self {
+            filtered: graph.clone(),
+            graph,
+            cycles: Vec::new(),
+            workspaces,
+            exclude_dev,
+            exclude_build,
+            exclude_target,
+            view: View::Workspaces,
+            selected: 0,
+            expanded: BTreeSet::new(),
+            search: String::new(),
+            searching: false,
+            quit: false,
+        };
+        app.refilter()?;
+        Ok(app)
+    }
+
+    pub fn view(&self) -> View {
+        self.view
+    }
+
+    pub fn cycles(&self) -> &[WorkspaceCycle] {
+        &self.cycles
+    }
+
+    pub fn should_quit(&self) -> bool {
+        self.quit
+    }
+
+    /// Name of the selected workspace, in the workspaces view
+    pub fn selected_workspace(&self) -> Option<&str> {
+        match self.view {
+            View::Workspaces => self
+                .visible_workspaces()
+                .get(self.selected)
+                .map(|&node| self.graph[node].name()),
+            View::Cycles => None,
+        }
+    }
+
+    /// The selected cycle, in the cycles view
+    pub fn selected_cycle(&self) -> Option<&WorkspaceCycle> {
+        match self.view {
+            View::Workspaces => None,
+            View::Cycles => self
+                .visible_cycles()
+                .get(self.selected)
+                .map(|&i| &self.cycles[i]),
+        }
+    }
+
+    /// React to a key press
+    pub fn handle_key(&mut self, key: KeyEvent) -> Result<()> {
+        if self.searching {
+            match key.code {
+                KeyCode::Enter => self.searching = false,
+                KeyCode::Esc => {
+                    self.searching = false;
+                    self.search.clear();
+                }
+                KeyCode::Backspace => {
+                    self.search.pop();
+                }
+                KeyCode::Char(c) => self.search.push(c),
+                _ => {}
+            }
+            self.selected = 0;
+            return Ok(());
+        }
+
+        match key.code {
+            KeyCode::Char('q') | KeyCode::Esc => self.quit = true,
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => self.quit = true,
+            KeyCode::Down | KeyCode::Char('j') if self.selected + 1 < self.visible_len() => {
+                self.selected += 1
+            }
+            KeyCode::Up | KeyCode::Char('k') => self.selected = self.selected.saturating_sub(1),
+            KeyCode::Home | KeyCode::Char('g') => self.selected = 0,
+            KeyCode::End | KeyCode::Char('G') => {
+                self.selected = self.visible_len().saturating_sub(1)
+            }
+            KeyCode::Enter | KeyCode::Char('l') | KeyCode::Right => self.toggle_expanded(),
+            KeyCode::Tab => self.switch_view(),
+            KeyCode::Char('c') => self.jump_to_cycle(),
+            KeyCode::Char('/') => {
+                self.searching = true;
+                self.search.clear();
+                self.selected = 0;
+            }
+            KeyCode::Char('d') => {
+                self.exclude_dev = !self.exclude_dev;
+                self.refilter()?;
+            }
+            KeyCode::Char('b') => {
+                self.exclude_build = !self.exclude_build;
+                self.refilter()?;
+            }
+            KeyCode::Char('t') => {
+                self.exclude_target = !self.exclude_target;
+                self.refilter()?;
+            }
+            _ => {}
+        }
+
+        Ok(())
+    }
+
+    /// Draw the explorer into `frame`
+    pub fn render(&self, frame: &mut Frame) {
+        let [header, body, footer] = Layout::vertical([
+            Constraint::Length(1),
+            Constraint::Min(0),
+            Constraint::Length(1),
+        ])
+        .areas(frame.area());
+        let [list_area, detail_area] =
+            Layout::horizontal([Constraint::Percentage(40), Constraint::Percentage(60)])
+                .areas(body);
+
+        frame.render_widget(Paragraph::new(self.header()), header);
+
+        let (title, items) = match self.view {
+            View::Workspaces => (
+                format!(" Workspaces ({}) ", self.visible_workspaces().len()),
+                self.workspace_items(),
+            ),
+            View::Cycles => (
+                format!(" Cycles ({}) ", self.visible_cycles().len()),
+                self.cycle_items(),
+            ),
+        };
+        let mut state = ListState::default().with_selected(self.selected_row());
+        frame.render_stateful_widget(
+            List::new(items)
+                .block(Block::default().borders(Borders::ALL).title(title))
+                .highlight_style(Style::default().add_modifier(Modifier::REVERSED))
+                .highlight_symbol("▶ "),
+            list_area,
+            &mut state,
+        );
+
+        let details = match self.view {
+            View::Workspaces => self.workspace_details(),
+            View::Cycles => self.cycle_details(),
+        };
+        frame.render_widget(
+            Paragraph::new(details)
+                .block(Block::default().borders(Borders::ALL).title(" Details "))
+                .wrap(Wrap { trim: false }),
+            detail_area,
+        );
+
+        let help = if self.searching {
+            format!("/{}▏  enter: done  esc: clear", self.search)
+        } else {
+            "j/k: move  enter: expand  tab: switch view  c: jump to cycle  d/b/t: toggle \
+             dev/build/target  /: search  q: quit"
+                .to_string()
+        };
+        frame.render_widget(
+            Paragraph::new(help).style(Style::default().fg(Color::DarkGray)),
+            footer,
+        );
+    }
+
+    fn header(&self) -> Line<'static> {
+        let toggle = |label: &'static str, excluded: bool| {
+            let style = if excluded {
+                Style::default().fg(Color::DarkGray)
+            } else {
+                Style::default().fg(Color::Green)
+            };
+            Span::styled(
+                format!(" [{}] {label}", if excluded { ' ' } else { 'x' }),
+                style,
+            )
+        };
+
+        let mut spans = vec![
+            Span::styled(
+                "🎡 ferris-wheel",
+                Style::default().add_modifier(Modifier::BOLD),
+            ),
+            Span::raw(format!(
+                "  {} {}, {} {} ",
+                self.workspaces.len(),
+                pluralize("workspace", self.workspaces.len()),
+                self.cycles.len(),
+                pluralize("cycle", self.cycles.len())
+            )),
+            toggle("dev", self.exclude_dev),
+            toggle("build", self.exclude_build),
+            toggle("target", self.exclude_target),
+        ];
+        if !self.search.is_empty() {
+            spans.push(Span::raw(format!("  filter: {}", self.search)));
+        }
+        Line::from(spans)
+    }
+
+    fn workspace_items(&self) -> Vec<ListItem<'static>> {
+        let mut items = Vec::new();
+        for node in self.visible_workspaces() {
+            let in_cycle = self.cycles_of(node).next().is_some();
+            let marker = if self.expanded.contains(&node) {
+                "▾"
+            } else {
+                "▸"
+            };
+            let style = if in_cycle {
+                Style::default().fg(Color::Red)
+            } else {
+                Style::default()
+            };
+            items.push(ListItem::new(Line::styled(
+                format!("{marker} {}", self.graph[node].name()),
+                style,
+            )));
+
+            if self.expanded.contains(&node) {
+                let dependencies = self.dependencies_of(node);
+                if dependencies.is_empty() {
+                    items.push(ListItem::new(Line::styled(
+                        "    (no dependencies)",
+                        Style::default().fg(Color::DarkGray),
+                    )));
+                }
+                for (workspace, _) in dependencies {
+                    items.push(ListItem::new(format!("    → {workspace}")));
+                }
+            }
+        }
+        items
+    }
+
+    fn cycle_items(&self) -> Vec<ListItem<'static>> {
+        self.visible_cycles()
+            .into_iter()
+            .map(|i| ListItem::new(self.cycles[i].workspace_names().join(" ↔ ")))
+            .collect()
+    }
+
+    fn workspace_details(&self) -> Vec<Line<'static>> {
+        let Some(&node) = self.visible_workspaces().get(self.selected) else {
+            return vec![Line::from("No workspace matches")];
+        };
+        let workspace = &self.graph[node];
+
+        let mut lines = vec![Line::styled(
+            workspace.name().to_string(),
+            Style::default().add_modifier(Modifier::BOLD),
+        )];
+        if let Some(path) = workspace.path() {
+            lines.push(Line::from(path.display().to_string()));
+        }
+
+        lines.push(Line::from(""));
+        lines.push(Line::from(format!(
+            "{} {}:",
+            workspace.crates().len(),
+            pluralize("crate", workspace.crates().len())
+        )));
+        for name in workspace.crates() {
+            lines.push(Line::from(format!("  📦 {name}")));
+        }
+
+        let dependencies = self.dependencies_of(node);
+        lines.push(Line::from(""));
+        lines.push(Line::from(format!("Depends on {}:", dependencies.len())));
+        for (workspace, edges) in dependencies {
+            lines.push(Line::from(format!("  → {workspace}")));
+            for edge in edges {
+                lines.push(Line::from(format!("      {}", describe(edge))));
+            }
+        }
+
+        let dependents: BTreeSet<&str> = self
+            .filtered
+            .neighbors_directed(node, Direction::Incoming)
+            .filter(|&other| other != node)
+            .map(|other| self.graph[other].name())
+            .collect();
+        lines.push(Line::from(""));
+        lines.push(Line::from(format!("Used by {}:", dependents.len())));
+        for workspace in dependents {
+            lines.push(Line::from(format!("  ← {workspace}")));
+        }
+
+        let cycles: Vec<&WorkspaceCycle> = self.cycles_of(node).collect();
+        if !cycles.is_empty() {
+            lines.push(Line::from(""));
+            lines.push(Line::styled(
+                format!(
+                    "In {} {} (press c to jump):",
+                    cycles.len(),
+                    pluralize("cycle", cycles.len())
+                ),
+                Style::default().fg(Color::Red),
+            ));
+            for cycle in cycles {
+                lines.push(Line::from(format!(
+                    "  {}",
+                    cycle.workspace_names().join(" ↔ ")
+                )));
+            }
+        }
+
+        lines
+    }
+
+    fn cycle_details(&self) -> Vec<Line<'static>> {
+        let Some(cycle) = self.selected_cycle() else {
+            return vec![Line::from(if self.cycles.is_empty() {
+                "✅ No cycles with the current filters"
+            } else {
+                "No cycle matches"
+            })];
+        };
+
+        let mut lines = vec![
+            Line::styled(
+                cycle.workspace_names().join(" ↔ "),
+                Style::default().add_modifier(Modifier::BOLD),
+            ),
+            Line::from(""),
+        ];
+        for ((from, to), edges) in sorted_directions(cycle) {
+            lines.push(Line::from(format!("{from} → {to}")));
+            for edge in edges {
+                lines.push(Line::from(format!(
+                    "  {} → {} ({})",
+                    edge.from_crate(),
+                    edge.to_crate(),
+                    edge.dependency_type()
+                )));
+            }
+        }
+        lines
+    }
+
+    /// Re-apply the dependency kind filters and detect the cycles again
+    fn refilter(&mut self) -> Result<()> {
+        let selected_workspace = self.selected_workspace().map(str::to_string);
+
+        self.filtered =
+            DependencyFilter::new(self.exclude_dev, self.exclude_build, self.exclude_target)
+                .apply(&self.graph);
+        let mut detector = CycleDetector::new();
+        detector
+            .detect_cycles(&self.filtered)
+            .wrap_err("Failed to detect cycles")?;
+        self.cycles = detector.cycles().to_vec();
+
+        // Keep the same workspace selected, and stay in range of the list of
+        // cycles, which may have shrunk
+        self.selected = match (self.view, selected_workspace) {
+            (View::Workspaces, Some(name)) => self
+                .visible_workspaces()
+                .iter()
+                .position(|&node| self.graph[node].name() == name)
+                .unwrap_or(0),
+            _ => self.selected.min(self.visible_len().saturating_sub(1)),
+        };
+        Ok(())
+    }
+
+    fn switch_view(&mut self) {
+        self.view = match self.view {
+            View::Workspaces => View::Cycles,
+            View::Cycles => View::Workspaces,
+        };
+        self.selected = 0;
+    }
+
+    fn toggle_expanded(&mut self) {
+        if let Some(&node) = self.visible_workspaces().get(self.selected)
+            && self.view == View::Workspaces
+            && !self.expanded.remove(&node)
+        {
+            self.expanded.insert(node);
+        }
+    }
+
+    /// Switch to the cycles view with the first cycle through the selected
+    /// workspace selected
+    fn jump_to_cycle(&mut self) {
+        let Some(name) = self.selected_workspace().map(str::to_string) else {
+            return;
+        };
+        self.search.clear();
+        if let Some(i) = self
+            .cycles
+            .iter()
+            .position(|cycle| cycle.workspace_names().iter().any(|other| **other == name))
+        {
+            self.view = View::Cycles;
+            self.selected = i;
+        }
+    }
+
+    fn visible_len(&self) -> usize {
+        match self.view {
+            View::Workspaces => self.visible_workspaces().len(),
+            View::Cycles => self.visible_cycles().len(),
+        }
+    }
+
+    fn visible_workspaces(&self) -> Vec<NodeIndex> {
+        self.workspaces
+            .iter()
+            .copied()
+            .filter(|&node| self.graph[node].name().contains(&self.search))
+            .collect()
+    }
+
+    /// Indices of the cycles through a workspace matching the search
+    fn visible_cycles(&self) -> Vec<usize> {
+        (0..self.cycles.len())
+            .filter(|&i| {
+                self.cycles[i]
+                    .workspace_names()
+                    .iter()
+                    .any(|name| name.contains(&self.search))
+            })
+            .collect()
+    }
+
+    /// Row of the selected entry in the rendered list, which also holds the
+    /// dependencies of expanded workspaces
+    fn selected_row(&self) -> Option<usize> {
+        if self.visible_len() == 0 {
+            return None;
+        }
+        if self.view == View::Cycles {
+            return Some(self.selected);
+        }
+
+        let row = self
+            .visible_workspaces()
+            .into_iter()
+            .take(self.selected)
+            .map(|node| {
+                if self.expanded.contains(&node) {
+                    1 + self.dependencies_of(node).len().max(1)
+                } else {
+                    1
+                }
+            })
+            .sum();
+        Some(row)
+    }
+
+    /// Dependencies on other workspaces, grouped by workspace name
+    fn dependencies_of(&self, node: NodeIndex) -> Vec<(String, Vec<&DependencyEdge>)> {
+        let mut dependencies: Vec<(String, Vec<&DependencyEdge>)> = Vec::new();
+        for edge in self.filtered.edges(node) {
+            if edge.target() == node {
+                continue;
+            }
+            let name = self.graph[edge.target()].name();
+            match dependencies.iter_mut().find(|(other, _)| other == name) {
+                Some((_, edges)) => edges.push(edge.weight()),
+                None => dependencies.push((name.to_string(), vec![edge.weight()])),
+            }
+        }
+        dependencies.sort_by(|a, b| a.0.cmp(&b.0));
+        for (_, edges) in &mut dependencies {
+            edges.sort_by(|a, b| {
+                (a.from_crate(), a.to_crate()).cmp(&(b.from_crate(), b.to_crate()))
+            });
+        }
+        dependencies
+    }
+
+    fn cycles_of(&self, node: NodeIndex) -> impl Iterator<Item = &WorkspaceCycle> {
+        let name = self.graph[node].name();
+        self.cycles
+            .iter()
+            .filter(move |cycle| cycle.workspace_names().iter().any(|other| &**other == name))
+    }
+}
+
+/// `from-crate → to-crate (kind)` for a dependency edge
+fn describe(edge: &DependencyEdge) -> String {
+    let kind = match edge.dependency_type() {
+        DependencyType::Normal => "normal",
+        DependencyType::Dev => "dev",
+        DependencyType::Build => "build",
+    };
+    match edge.target() {
+        Some(target) => format!(
+            "{} → {} ({kind}, {target})",
+            edge.from_crate(),
+            edge.to_crate()
+        ),
+        None => format!("{} → {} ({kind})", edge.from_crate(), edge.to_crate()),
+    }
+}
+
+fn sorted_directions(
+    cycle: &WorkspaceCycle,
+) -> Vec<(&CycleDirection, &Vec<crate::detector::CycleEdge>)> {
+    let mut directions: Vec<_> = cycle.edges_by_direction().iter().collect();
+    directions.sort_by(|a, b| a.0.cmp(b.0));
+    directions
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use ratatui::Terminal;
+    use ratatui::backend::TestBackend;
+
+    use super::*;
+
+    /// core ↔ apps through a dev dependency, apps → tools
+    fn create_graph() -> DiGraph<WorkspaceNode, DependencyEdge> {
+        let mut graph = DiGraph::new();
+        let nodes: HashMap<&str, NodeIndex> = ["apps", "core", "tools"]
+            .into_iter()
+            .map(|name| {
+                let node = graph.add_node(
+                    WorkspaceNode::builder()
+                        .with_name(name.to_string())
+                        .with_crates(vec![format!("{name}-lib")])
+                        .build()
+                        .unwrap(),
+                );
+                (name, node)
+            })
+            .collect();
+
+        for (from, to, dependency_type) in [
+            ("apps", "core", DependencyType::Normal),
+            ("core", "apps", DependencyType::Dev),
+            ("apps", "tools", DependencyType::Build),
+        ] {
+            graph.add_edge(
+                nodes[from],
+                nodes[to],
+                DependencyEdge::builder()
+                    .with_from_crate(&format!("{from}-lib"))
+                    .with_to_crate(&format!("{to}-lib"))
+                    .with_dependency_type(dependency_type)
+                    .build()
+                    .unwrap(),
+            );
+        }
+        graph
+    }
+
+    fn press(app: &mut App, keys: &str) {
+        for c in keys.chars() {
+            let code = match c {
+                '\t' => KeyCode::Tab,
+                '\n' => KeyCode::Enter,
+                c => KeyCode::Char(c),
+            };
+            app.handle_key(KeyEvent::from(code)).unwrap();
+        }
+    }
+
+    fn draw(app: &App) -> String {
+        let mut terminal = Terminal::new(TestBackend::new(100, 20)).unwrap();
+        terminal.draw(|frame| app.render(frame)).unwrap();
+        let buffer = terminal.backend().buffer();
+        (0..buffer.area.height)
+            .map(|y| {
+                (0..buffer.area.width)
+                    .map(|x| buffer[(x, y)].symbol())
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    #[test]
+    fn test_navigation_and_cycle_jump() {
+        let mut app = App::new(create_graph(), false, false, false).unwrap();
+        assert_eq!(app.cycles().len(), 1);
+        assert_eq!(app.selected_workspace(), Some("apps"));
+
+        press(&mut app, "jjj");
+        assert_eq!(app.selected_workspace(), Some("tools"));
+        press(&mut app, "c");
+        assert_eq!(app.view(), View::Workspaces);
+
+        press(&mut app, "kc");
+        assert_eq!(app.view(), View::Cycles);
+        assert_eq!(
+            app.selected_cycle().unwrap().workspace_names(),
+            ["apps".into(), "core".into()]
+        );
+
+        press(&mut app, "\t");
+        assert_eq!(app.selected_workspace(), Some("apps"));
+        press(&mut app, "q");
+        assert!(app.should_quit());
+    }
+
+    #[test]
+    fn test_toggling_dependency_kinds_updates_cycles() {
+        let mut app = App::new(create_graph(), true, false, false).unwrap();
+        assert!(app.cycles().is_empty());
+
+        press(&mut app, "jd");
+        assert_eq!(app.cycles().len(), 1);
+        assert_eq!(app.selected_workspace(), Some("core"));
+
+        press(&mut app, "\td");
+        assert!(app.cycles().is_empty());
+        assert!(app.selected_cycle().is_none());
+    }
+
+    #[test]
+    fn test_search_filters_the_list() {
+        let mut app = App::new(create_graph(), false, false, false).unwrap();
+
+        press(&mut app, "/to\n");
+        assert_eq!(app.selected_workspace(), Some("tools"));
+        press(&mut app, "j");
+        assert_eq!(app.selected_workspace(), Some("tools"));
+
+        press(&mut app, "/");
+        app.handle_key(KeyEvent::from(KeyCode::Esc)).unwrap();
+        assert_eq!(app.selected_workspace(), Some("apps"));
+        assert!(!app.should_quit());
+    }
+
+    #[test]
+    fn test_render() {
+        let mut app = App::new(create_graph(), false, false, false).unwrap();
+
+        press(&mut app, "\n");
+        let screen = draw(&app);
+        assert!(screen.contains("3 workspaces, 1 cycle"));
+        assert!(screen.contains("▾ apps"));
+        assert!(screen.contains("    → core"));
+        assert!(screen.contains("apps-lib → tools-lib (build)"));
+        assert!(screen.contains("In 1 cycle (press c to jump):"));
+
+        press(&mut app, "\t");
+        let screen = draw(&app);
+        assert!(screen.contains("Cycles (1)"));
+        assert!(screen.contains("core → apps"));
+        assert!(screen.contains("core-lib → apps-lib (Dev)"));
+    }
+}
@@ -0,0 +1,384 @@
+//! Why command implementation
+
+use std::fmt::Write;
+
+use miette::{Result, WrapErr};
+use petgraph::graph::{DiGraph, NodeIndex};
+use serde::Serialize;
+
+use crate::cli::Commands;
+use crate::common::{ConfigBuilder, FromCommand};
+use crate::config::{ProjectConfig, WhyConfig};
+use crate::error::FerrisWheelError;
+use crate::graph::{DependencyEdge, WorkspaceNode, shortest_paths};
+use crate::utils::string::pluralize;
+
+impl FromCommand for WhyConfig {
+    fn from_command(
+        command: Commands,
+        _project: Option<&ProjectConfig>,
+    ) -> Result<Self, FerrisWheelError> {
+        match command {
+            Commands::Why {
+                from,
+                to,
+                common,
+                format,
+            } => WhyConfig::builder()
+                .with_from(from)
+                .with_to(to)
+                .with_paths(common.get_paths())
+                .with_format(format.format)
+                .with_output(format.output.output)
+                .with_exclude_dev(common.exclude_dev)
+                .with_exclude_build(common.exclude_build)
+                .with_exclude_target(common.exclude_target)
+                .with_target(common.target)
+                .with_max_workspaces(common.max_workspaces)
+                .with_exclude_paths(common.exclude_paths.clone())
+                .with_exclude_crates(common.exclude_crates.clone())
+                .with_follow_symlinks(common.follow_symlinks)
+                .with_no_cache(common.no_cache)
+                .with_analyzer(common.analyzer_config())
+                .with_workspace_names(common.workspace_names.clone())
+                .build(),
+            _ => Err(FerrisWheelError::ConfigurationError {
+                message: "Invalid command type for WhyConfig".to_string(),
+            }),
+        }
+    }
+}
+
+crate::impl_try_from_command!(WhyConfig);
+
+/// Execute the why command for explaining a workspace dependency
+pub fn execute_why_command(command: Commands, project: Option<&ProjectConfig>) -> Result<()> {
+    let config = WhyConfig::from_command(command, project)
+        .wrap_err("Failed to parse why command configuration")?;
+
+    use crate::executors::CommandExecutor;
+    use crate::executors::why::WhyExecutor;
+    WhyExecutor::execute(config)
+}
+
+/// One crate-to-crate dependency in a chain
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct CrateLink {
+    pub from_workspace: String,
+    pub from_crate: String,
+    pub to_workspace: String,
+    pub to_crate: String,
+    pub dependency_types: Vec<String>,
+}
+
+/// Find the shortest crate-level dependency chain from any crate of
+/// workspace `from` to any crate of workspace `to`
+///
+/// `graph` must be a crate graph as built by
+/// [`DependencyGraphBuilder::build_crate_graph`](crate::graph::DependencyGraphBuilder::build_crate_graph).
+/// Returns `None` when `from` does not depend on `to`.
+pub fn find_crate_chain(
+    graph: &DiGraph<WorkspaceNode, DependencyEdge>,
+    from: &str,
+    to: &str,
+) -> Result<Option<Vec<CrateLink>>, FerrisWheelError> {
+    if from == to {
+        return Err(FerrisWheelError::ConfigurationError {
+            message: format!("FROM and TO must name different workspaces (got '{from}')"),
+        });
+    }
+
+    let sources = workspace_crates(graph, from)?;
+    let targets = workspace_crates(graph, to)?;
+
+    let Some(path) = shortest_paths(graph, &sources, &targets, 1)
+        .into_iter()
+        .next()
+    else {
+        return Ok(None);
+    };
+
+    Ok(Some(crate_links(graph, &path)))
+}
+
+/// The crate-to-crate dependencies along a path through a crate graph
+pub(crate) fn crate_links(
+    graph: &DiGraph<WorkspaceNode, DependencyEdge>,
+    path: &[NodeIndex],
+) -> Vec<CrateLink> {
+    path.windows(2)
+        .map(|pair| {
+            let (from_workspace, from_crate) = split_node(&graph[pair[0]]);
+            let (to_workspace, to_crate) = split_node(&graph[pair[1]]);
+
+            let mut dependency_types: Vec<String> = graph
+                .edges_connecting(pair[0], pair[1])
+                .map(|edge| edge.weight().dependency_type().as_str().to_string())
+                .collect();
+            dependency_types.sort();
+            dependency_types.dedup();
+
+            CrateLink {
+                from_workspace: from_workspace.to_string(),
+                from_crate: from_crate.to_string(),
+                to_workspace: to_workspace.to_string(),
+                to_crate: to_crate.to_string(),
+                dependency_types,
+            }
+        })
+        .collect()
+}
+
+pub(crate) fn workspace_crates(
+    graph: &DiGraph<WorkspaceNode, DependencyEdge>,
+    workspace: &str,
+) -> Result<Vec<NodeIndex>, FerrisWheelError> {
+    let nodes: Vec<NodeIndex> = graph
+        .node_indices()
+        .filter(|&idx| split_node(&graph[idx]).0 == workspace)
+        .collect();
+
+    if nodes.is_empty() {
+        return Err(FerrisWheelError::ConfigurationError {
+            message: format!("Unknown workspace '{workspace}'"),
+        });
+    }
+
+    Ok(nodes)
+}
+
+/// Split a `workspace/crate` crate graph node into its two parts
+pub(crate) fn split_node(node: &WorkspaceNode) -> (&str, &str) {
+    node.name().rsplit_once('/').unwrap_or(("", node.name()))
+}
+
+/// JSON output structure for the why command
+#[derive(Debug, Serialize)]
+struct WhyJsonReport<'a> {
+    from: &'a str,
+    to: &'a str,
+    depends: bool,
+    chain: &'a [CrateLink],
+}
+
+/// Report generator for crate-level dependency chains
+pub struct WhyReportGenerator<'a> {
+    from: &'a str,
+    to: &'a str,
+}
+
+impl<'a> WhyReportGenerator<'a> {
+    pub fn new(from: &'a str, to: &'a str) -> Self {
+        Self { from, to }
+    }
+
+    pub fn generate_human_report(
+        &self,
+        chain: Option<&[CrateLink]>,
+    ) -> Result<String, FerrisWheelError> {
+        let mut output = String::new();
+
+        let Some(links) = chain.filter(|links| !links.is_empty()) else {
+            writeln!(output, "\n✅ {} does not depend on {}", self.from, self.to)?;
+            return Ok(output);
+        };
+
+        writeln!(
+            output,
+            "\n🔍 {} depends on {} in {} crate {}:\n",
+            self.from,
+            self.to,
+            links.len(),
+            pluralize("hop", links.len())
+        )?;
+
+        writeln!(
+            output,
+            "  📦 {}/{}",
+            links[0].from_workspace, links[0].from_crate
+        )?;
+        for link in links {
+            writeln!(output, "    ↓ {}", link.dependency_types.join(", "))?;
+            writeln!(output, "  📦 {}/{}", link.to_workspace, link.to_crate)?;
+        }
+
+        Ok(output)
+    }
+
+    pub fn generate_json_report(
+        &self,
+        chain: Option<&[CrateLink]>,
+    ) -> Result<String, FerrisWheelError> {
+        let report = WhyJsonReport {
+            from: self.from,
+            to: self.to,
+            depends: chain.is_some(),
+            chain: chain.unwrap_or_default(),
+        };
+
+        Ok(serde_json::to_string_pretty(&report)?)
+    }
+
+    pub fn generate_junit_report(
+        &self,
+        chain: Option<&[CrateLink]>,
+    ) -> Result<String, FerrisWheelError> {
+        let mut output = String::new();
+
+        writeln!(output, r#"<?xml version="1.0" encoding="UTF-8"?>"#)?;
+        writeln!(
+            output,
+            r#"<testsuites name="workspace-why" tests="1" failures="0">"#
+        )?;
+        writeln!(
+            output,
+            r#"  <testsuite name="why-analysis" tests="1" failures="0">"#
+        )?;
+        writeln!(
+            output,
+            r#"    <testcase name="explain-workspace-dependency" classname="ferris-wheel">"#
+        )?;
+
+        match chain {
+            Some(links) => {
+                writeln!(output, "{} depends on {}:", self.from, self.to)?;
+                for link in links {
+                    writeln!(
+                        output,
+                        "  {}/{} -&gt; {}/{}",
+                        link.from_workspace, link.from_crate, link.to_workspace, link.to_crate
+                    )?;
+                }
+            }
+            None => writeln!(output, "{} does not depend on {}", self.from, self.to)?,
+        }
+
+        writeln!(output, r#"    </testcase>"#)?;
+        writeln!(output, r#"  </testsuite>"#)?;
+        writeln!(output, r#"</testsuites>"#)?;
+
+        Ok(output)
+    }
+
+    pub fn generate_github_report(
+        &self,
+        chain: Option<&[CrateLink]>,
+    ) -> Result<String, FerrisWheelError> {
+        let mut output = String::new();
+
+        match chain {
+            Some(links) => {
+                let mut crates: Vec<String> = links
+                    .first()
+                    .map(|link| format!("{}/{}", link.from_workspace, link.from_crate))
+                    .into_iter()
+                    .collect();
+                crates.extend(
+                    links
+                        .iter()
+                        .map(|link| format!("{}/{}", link.to_workspace, link.to_crate)),
+                );
+                writeln!(
+                    output,
+                    "::notice title=Dependency Chain::{}",
+                    crates.join(" → ")
+                )?;
+            }
+            None => writeln!(
+                output,
+                "::notice title=Dependency Chain::{} does not depend on {}",
+                self.from, self.to
+            )?,
+        }
+
+        Ok(output)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::DependencyType;
+
+    fn create_crate_graph() -> DiGraph<WorkspaceNode, DependencyEdge> {
+        let mut graph = DiGraph::new();
+        let nodes: Vec<NodeIndex> = [
+            ("tools", "tools-cli"),
+            ("tools", "tools-lib"),
+            ("utils", "utils-fs"),
+            ("core", "core-api"),
+            ("core", "core-types"),
+        ]
+        .iter()
+        .map(|(workspace, name)| {
+            graph.add_node(
+                WorkspaceNode::builder()
+                    .with_name(format!("{workspace}/{name}"))
+                    .with_crates(vec![name.to_string()])
+                    .build()
+                    .unwrap(),
+            )
+        })
+        .collect();
+
+        // tools-cli -> tools-lib -> utils-fs -> core-api -> core-types
+        for (from, to, dependency_type) in [
+            (0, 1, DependencyType::Normal),
+            (1, 2, DependencyType::Normal),
+            (1, 2, DependencyType::Dev),
+            (2, 3, DependencyType::Build),
+            (3, 4, DependencyType::Normal),
+        ] {
+            graph.add_edge(
+                nodes[from],
+                nodes[to],
+                DependencyEdge::builder()
+                    .with_from_crate(graph[nodes[from]].crates()[0].as_str())
+                    .with_to_crate(graph[nodes[to]].crates()[0].as_str())
+                    .with_dependency_type(dependency_type)
+                    .build()
+                    .unwrap(),
+            );
+        }
+
+        graph
+    }
+
+    #[test]
+    fn test_find_crate_chain() {
+        let graph = create_crate_graph();
+
+        let chain = find_crate_chain(&graph, "tools", "core").unwrap().unwrap();
+
+        assert_eq!(chain.len(), 2);
+        assert_eq!(chain[0].from_crate, "tools-lib");
+        assert_eq!(chain[0].to_crate, "utils-fs");
+        assert_eq!(chain[0].dependency_types, vec!["Dev", "Normal"]);
+        assert_eq!(chain[1].to_workspace, "core");
+        assert_eq!(chain[1].to_crate, "core-api");
+
+        assert_eq!(find_crate_chain(&graph, "core", "tools").unwrap(), None);
+        assert!(find_crate_chain(&graph, "tools", "missing").is_err());
+        assert!(find_crate_chain(&graph, "core", "core").is_err());
+    }
+
+    #[test]
+    fn test_reports() {
+        let graph = create_crate_graph();
+        let chain = find_crate_chain(&graph, "tools", "core").unwrap();
+        let generator = WhyReportGenerator::new("tools", "core");
+
+        let human = generator.generate_human_report(chain.as_deref()).unwrap();
+        assert!(human.contains("tools depends on core in 2 crate hops"));
+        assert!(human.contains("  📦 tools/tools-lib\n    ↓ Dev, Normal\n  📦 utils/utils-fs"));
+
+        let json: serde_json::Value =
+            serde_json::from_str(&generator.generate_json_report(chain.as_deref()).unwrap())
+                .unwrap();
+        assert_eq!(json["depends"], true);
+        assert_eq!(json["chain"][1]["dependency_types"][0], "Build");
+
+        let none = generator.generate_human_report(None).unwrap();
+        assert!(none.contains("tools does not depend on core"));
+    }
+}
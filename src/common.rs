@@ -1,27 +1,147 @@
 //! Common functionality shared across commands
 
 use std::path::PathBuf;
+use std::time::{Duration, Instant};
 
 use clap::Args;
 
+use crate::analyzer::{FeatureSelection, TargetPlatform, WorkspaceNames};
+use crate::config::AnalyzerConfig;
+use crate::reports::ReportVerbosity;
+
 /// Common arguments shared by multiple commands
 #[derive(Args, Debug, Clone)]
 pub struct CommonArgs {
     /// Paths to analyze (defaults to current directory)
-    #[arg(value_name = "PATH")]
+    #[arg(
+        value_name = "PATH",
+        value_delimiter = ',',
+        env = "CARGO_FERRIS_WHEEL_PATHS"
+    )]
     pub paths: Vec<PathBuf>,
 
     /// Exclude dev-dependencies from analysis
-    #[arg(long, env = "CARGO_FERRIS_WHEEL_EXCLUDE_DEV")]
+    #[arg(
+        long,
+        overrides_with = "no_exclude_dev",
+        env = "CARGO_FERRIS_WHEEL_EXCLUDE_DEV"
+    )]
     pub exclude_dev: bool,
 
+    /// Keep dev-dependencies from analysis even if `[defaults]` excludes them
+    #[arg(long, overrides_with = "exclude_dev")]
+    pub no_exclude_dev: bool,
+
     /// Exclude build-dependencies from analysis
-    #[arg(long, env = "CARGO_FERRIS_WHEEL_EXCLUDE_BUILD")]
+    #[arg(
+        long,
+        overrides_with = "no_exclude_build",
+        env = "CARGO_FERRIS_WHEEL_EXCLUDE_BUILD"
+    )]
     pub exclude_build: bool,
 
+    /// Keep build-dependencies from analysis even if `[defaults]` excludes them
+    #[arg(long, overrides_with = "exclude_build")]
+    pub no_exclude_build: bool,
+
     /// Exclude target-specific dependencies
-    #[arg(long, env = "CARGO_FERRIS_WHEEL_EXCLUDE_TARGET")]
+    #[arg(
+        long,
+        overrides_with = "no_exclude_target",
+        env = "CARGO_FERRIS_WHEEL_EXCLUDE_TARGET"
+    )]
     pub exclude_target: bool,
+
+    /// Keep target-specific dependencies even if `[defaults]` excludes them
+    #[arg(long, overrides_with = "exclude_target")]
+    pub no_exclude_target: bool,
+
+    /// Evaluate `[target.'cfg(...)']` dependencies for this target triple,
+    /// keeping only those that apply to it
+    #[arg(
+        long,
+        value_name = "TRIPLE",
+        conflicts_with = "exclude_target",
+        env = "CARGO_FERRIS_WHEEL_TARGET"
+    )]
+    pub target: Option<TargetPlatform>,
+
+    /// Abort with a diagnostic once discovery finds more than this many
+    /// workspaces, usually a sign it reached vendored sources or fixtures
+    #[arg(long, value_name = "N", env = "CARGO_FERRIS_WHEEL_MAX_WORKSPACES")]
+    pub max_workspaces: Option<usize>,
+
+    /// Skip directories matching this glob during discovery, relative to
+    /// each path (repeatable, e.g. `vendor` or `**/fixtures`)
+    #[arg(
+        long = "exclude-path",
+        value_name = "GLOB",
+        value_delimiter = ',',
+        env = "CARGO_FERRIS_WHEEL_EXCLUDE_PATH"
+    )]
+    pub exclude_paths: Vec<glob::Pattern>,
+
+    /// Leave crates whose name matches this glob out of the analysis, as if
+    /// they did not exist (repeatable, e.g. `*-fixtures` or `example-*`)
+    #[arg(
+        long = "exclude-crate",
+        value_name = "GLOB",
+        value_delimiter = ',',
+        env = "CARGO_FERRIS_WHEEL_EXCLUDE_CRATE"
+    )]
+    pub exclude_crates: Vec<glob::Pattern>,
+
+    /// Descend into symlinked directories during discovery; workspaces
+    /// reachable through several paths are still analyzed once
+    #[arg(long, env = "CARGO_FERRIS_WHEEL_FOLLOW_SYMLINKS")]
+    pub follow_symlinks: bool,
+
+    /// Analyze every workspace from scratch, without reading or updating
+    /// the analysis cache
+    #[arg(long, overrides_with = "cache", env = "CARGO_FERRIS_WHEEL_NO_CACHE")]
+    pub no_cache: bool,
+
+    /// Use the analysis cache even if `[defaults]` turns it off
+    #[arg(long, overrides_with = "no_cache")]
+    pub cache: bool,
+
+    /// How crate dependencies are read: by parsing manifests, or from
+    /// `cargo metadata` for Cargo's exact rename, inheritance and target
+    /// semantics
+    #[arg(long, value_enum, default_value_t, env = "CARGO_FERRIS_WHEEL_BACKEND")]
+    pub backend: crate::analyzer::AnalyzerBackend,
+
+    /// Only analyze optional dependencies enabled by these features
+    /// (comma-separated; use `crate/feature` to target one crate)
+    #[arg(
+        long,
+        value_delimiter = ',',
+        conflicts_with = "all_features",
+        env = "CARGO_FERRIS_WHEEL_FEATURES"
+    )]
+    pub features: Vec<String>,
+
+    /// Analyze every optional dependency (the default unless --features or
+    /// --no-default-features is given)
+    #[arg(long, env = "CARGO_FERRIS_WHEEL_ALL_FEATURES")]
+    pub all_features: bool,
+
+    /// Do not enable each crate's `default` feature
+    #[arg(
+        long,
+        conflicts_with = "all_features",
+        env = "CARGO_FERRIS_WHEEL_NO_DEFAULT_FEATURES"
+    )]
+    pub no_default_features: bool,
+
+    /// Profile from .ferris-wheel.toml to apply (auto-detected from the
+    /// current git branch if not specified)
+    #[arg(long, value_name = "NAME", env = "CARGO_FERRIS_WHEEL_PROFILE")]
+    pub profile: Option<String>,
+
+    /// Workspace display names from the project configuration
+    #[arg(skip)]
+    pub workspace_names: WorkspaceNames,
 }
 
 /// Common output format arguments
@@ -30,14 +150,79 @@ pub struct FormatArgs {
     /// Output format
     #[arg(short, long, value_enum, default_value = crate::constants::output::DEFAULT_FORMAT, env = "CARGO_FERRIS_WHEEL_FORMAT")]
     pub format: crate::cli::OutputFormat,
+
+    #[command(flatten)]
+    pub output: OutputArgs,
+}
+
+/// Output format arguments of a command that can write several reports from
+/// one analysis
+#[derive(Args, Debug, Clone)]
+pub struct ReportFormatArgs {
+    /// Output format; repeat it, or separate formats with commas, to write
+    /// one report per format into --output-dir
+    #[arg(
+        short,
+        long,
+        value_enum,
+        value_delimiter = ',',
+        default_value = crate::constants::output::DEFAULT_FORMAT,
+        env = "CARGO_FERRIS_WHEEL_FORMAT"
+    )]
+    pub format: Vec<crate::cli::OutputFormat>,
+
+    /// Directory to write one report per format into, instead of stdout
+    #[arg(long, conflicts_with = "output", env = "CARGO_FERRIS_WHEEL_OUTPUT_DIR")]
+    pub output_dir: Option<PathBuf>,
+
+    #[command(flatten)]
+    pub output: OutputArgs,
+}
+
+/// Common report destination arguments
+#[derive(Args, Debug, Clone)]
+pub struct OutputArgs {
+    /// Write the report to this file instead of stdout; the file is only
+    /// replaced once the report is complete
+    #[arg(short, long, env = "CARGO_FERRIS_WHEEL_OUTPUT")]
+    pub output: Option<PathBuf>,
 }
 
 /// Common cycle display arguments  
 #[derive(Args, Debug, Clone)]
 pub struct CycleDisplayArgs {
-    /// Maximum number of cycles to display (shows all by default)
-    #[arg(long, env = "CARGO_FERRIS_WHEEL_MAX_CYCLES")]
-    pub max_cycles: Option<usize>,
+    /// Show each cycle in the human report (-v), and also the crate
+    /// dependencies forming it (-vv); by default only cycle counts are shown
+    #[arg(short, long, action = clap::ArgAction::Count)]
+    pub verbose: u8,
+
+    /// Enumerate individual elementary cycles instead of reporting each
+    /// strongly connected component as a single cycle
+    #[arg(long, env = "CARGO_FERRIS_WHEEL_ELEMENTARY")]
+    pub elementary: bool,
+
+    /// Maximum number of elementary cycles to enumerate per strongly
+    /// connected component (with --elementary)
+    #[arg(
+        long,
+        default_value_t = crate::constants::detector::DEFAULT_MAX_ELEMENTARY_CYCLES,
+        requires = "elementary",
+        env = "CARGO_FERRIS_WHEEL_MAX_ELEMENTARY_CYCLES"
+    )]
+    pub max_elementary_cycles: usize,
+}
+
+impl CycleDisplayArgs {
+    /// How much of each cycle the human report shows
+    pub fn verbosity(&self) -> ReportVerbosity {
+        ReportVerbosity::from_occurrences(self.verbose)
+    }
+
+    /// Cap on elementary cycles per component, if elementary enumeration is
+    /// enabled
+    pub fn elementary_cycles(&self) -> Option<usize> {
+        self.elementary.then_some(self.max_elementary_cycles)
+    }
 }
 
 impl CommonArgs {
@@ -49,6 +234,76 @@ impl CommonArgs {
             self.paths.clone()
         }
     }
+
+    /// Dependency backend and features selected on the command line
+    pub fn analyzer_config(&self) -> AnalyzerConfig {
+        AnalyzerConfig {
+            backend: self.backend,
+            features: self.feature_selection(),
+        }
+    }
+
+    /// Features selected by --features, --all-features and
+    /// --no-default-features
+    pub fn feature_selection(&self) -> FeatureSelection {
+        if self.all_features || (self.features.is_empty() && !self.no_default_features) {
+            return FeatureSelection::All;
+        }
+
+        FeatureSelection::Selected {
+            features: self.features.clone(),
+            default_features: !self.no_default_features,
+        }
+    }
+}
+
+/// Point in time after which long-running analysis stops and reports what it
+/// has so far
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Deadline {
+    at: Option<Instant>,
+}
+
+impl Deadline {
+    /// A deadline that never expires
+    pub fn never() -> Self {
+        Self::default()
+    }
+
+    /// A deadline `timeout` from now
+    pub fn after(timeout: Duration) -> Self {
+        Self {
+            at: Instant::now().checked_add(timeout),
+        }
+    }
+
+    /// Whether the deadline has passed
+    pub fn is_expired(&self) -> bool {
+        self.at.is_some_and(|at| Instant::now() >= at)
+    }
+}
+
+/// Parse a duration such as `90`, `60s`, `5m`, `1h` or `500ms` (bare numbers
+/// are seconds)
+pub fn parse_duration(value: &str) -> Result<Duration, String> {
+    let value = value.trim();
+    let split = value
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(value.len());
+    let (amount, unit) = value.split_at(split);
+    let amount: u64 = amount
+        .parse()
+        .map_err(|_| format!("Invalid duration '{value}': expected e.g. 60s, 5m or 1h"))?;
+
+    match unit.trim() {
+        "" | "s" => Ok(Duration::from_secs(amount)),
+        "ms" => Ok(Duration::from_millis(amount)),
+        "m" => Ok(Duration::from_secs(amount.saturating_mul(60))),
+        "h" => Ok(Duration::from_secs(amount.saturating_mul(3600))),
+        unit => Err(format!(
+            "Invalid duration unit '{unit}' in '{value}': expected ms, s, m or h"
+        )),
+    }
 }
 
 /// Generic builder trait for configuration objects
@@ -62,11 +317,16 @@ pub trait ConfigBuilder: Sized {
 /// Trait for configurations that can be created from CLI commands
 /// This trait simplifies command-to-config conversions
 pub trait FromCommand: Sized {
-    /// The command variant that this config can be created from
-    fn from_command(command: crate::cli::Commands) -> Result<Self, crate::error::FerrisWheelError>;
+    /// The command variant that this config can be created from, with the
+    /// project configuration loaded for it, if any
+    fn from_command(
+        command: crate::cli::Commands,
+        project: Option<&crate::config::ProjectConfig>,
+    ) -> Result<Self, crate::error::FerrisWheelError>;
 }
 
-/// Macro to implement `TryFrom<Commands>` using [`FromCommand`] trait
+/// Macro to implement `TryFrom<Commands>` using [`FromCommand`] trait,
+/// without a project configuration
 #[macro_export]
 macro_rules! impl_try_from_command {
     ($config:ty) => {
@@ -74,7 +334,7 @@ macro_rules! impl_try_from_command {
             type Error = $crate::error::FerrisWheelError;
 
             fn try_from(command: $crate::cli::Commands) -> Result<Self, Self::Error> {
-                <$config as $crate::common::FromCommand>::from_command(command)
+                <$config as $crate::common::FromCommand>::from_command(command, None)
             }
         }
     };
@@ -89,8 +349,24 @@ mod tests {
         let args = CommonArgs {
             paths: vec![],
             exclude_dev: false,
+            no_exclude_dev: false,
             exclude_build: false,
+            no_exclude_build: false,
             exclude_target: false,
+            no_exclude_target: false,
+            target: None,
+            max_workspaces: None,
+            exclude_paths: vec![],
+            exclude_crates: vec![],
+            follow_symlinks: false,
+            no_cache: false,
+            cache: false,
+            backend: Default::default(),
+            features: vec![],
+            all_features: false,
+            no_default_features: false,
+            profile: None,
+            workspace_names: WorkspaceNames::default(),
         };
 
         let paths = args.get_paths();
@@ -106,11 +382,45 @@ mod tests {
         let args = CommonArgs {
             paths: test_paths.clone(),
             exclude_dev: false,
+            no_exclude_dev: false,
             exclude_build: false,
+            no_exclude_build: false,
             exclude_target: false,
+            no_exclude_target: false,
+            target: None,
+            max_workspaces: None,
+            exclude_paths: vec![],
+            exclude_crates: vec![],
+            follow_symlinks: false,
+            no_cache: false,
+            cache: false,
+            backend: Default::default(),
+            features: vec![],
+            all_features: false,
+            no_default_features: false,
+            profile: None,
+            workspace_names: WorkspaceNames::default(),
         };
 
         let paths = args.get_paths();
         assert_eq!(paths, test_paths);
     }
+
+    #[test]
+    fn test_parse_duration() {
+        assert_eq!(parse_duration("90"), Ok(Duration::from_secs(90)));
+        assert_eq!(parse_duration("60s"), Ok(Duration::from_secs(60)));
+        assert_eq!(parse_duration("5m"), Ok(Duration::from_secs(300)));
+        assert_eq!(parse_duration("1h"), Ok(Duration::from_secs(3600)));
+        assert_eq!(parse_duration("500ms"), Ok(Duration::from_millis(500)));
+        assert!(parse_duration("soon").is_err());
+        assert!(parse_duration("10d").is_err());
+    }
+
+    #[test]
+    fn test_deadline() {
+        assert!(!Deadline::never().is_expired());
+        assert!(Deadline::after(Duration::ZERO).is_expired());
+        assert!(!Deadline::after(Duration::from_secs(3600)).is_expired());
+    }
 }
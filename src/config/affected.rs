@@ -2,25 +2,55 @@
 
 use std::path::PathBuf;
 
-use crate::cli::OutputFormat;
+use crate::analyzer::{TargetPlatform, WorkspaceNames};
+use crate::cli::RipplesFormat;
+use crate::dependency_filter::DependencyFilter;
 use crate::error::FerrisWheelError;
 
+/// Where git's list of changed files starts
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ChangedSince {
+    /// Everything changed since this revision
+    Revision(String),
+    /// Everything changed since the merge base of this revision and `HEAD`
+    MergeBase(String),
+}
+
 #[derive(Debug, Clone)]
 pub struct AffectedConfig {
     /// List of changed files
     pub files: Vec<String>,
 
+    /// Ask git for the changed files, in place of `files`
+    pub changed_since: Option<ChangedSince>,
+
+    /// Read the changed files from this file, or from stdin when it is `-`,
+    /// in place of `files`
+    pub files_from: Option<PathBuf>,
+
     /// Include crate-level information
     pub show_crates: bool,
 
     /// Include only directly affected crates (no reverse dependencies)
     pub direct_only: bool,
 
+    /// Limit a change to `[workspace.dependencies]` in a workspace root
+    /// manifest to the members inheriting the changed entries
+    pub narrow_workspace_manifests: bool,
+
+    /// How many steps reverse dependencies propagate (None = no limit)
+    pub max_depth: Option<usize>,
+
+    /// Cargo subcommand the `cargo-commands` format runs
+    pub cargo_subcommand: String,
+
     /// Paths to analyze
     pub paths: Vec<PathBuf>,
 
     /// Output format
-    pub format: OutputFormat,
+    pub format: RipplesFormat,
+    /// File to write the report to instead of stdout
+    pub output: Option<PathBuf>,
 
     /// Exclude dev-dependencies from analysis
     pub exclude_dev: bool,
@@ -30,6 +60,23 @@ pub struct AffectedConfig {
 
     /// Exclude target-specific dependencies
     pub exclude_target: bool,
+    /// Only analyze target-specific dependencies that apply to this target
+    pub target: Option<TargetPlatform>,
+    /// Abort discovery once more than this many workspaces are found
+    pub max_workspaces: Option<usize>,
+    /// Directories skipped during discovery
+    pub exclude_paths: Vec<glob::Pattern>,
+    /// Crates left out of the analysis
+    pub exclude_crates: Vec<glob::Pattern>,
+    /// Follow symlinked directories during discovery
+    pub follow_symlinks: bool,
+    /// Skip the analysis cache
+    pub no_cache: bool,
+    /// Workspace display names
+    pub workspace_names: WorkspaceNames,
+    /// Filter deciding which dependencies carry ripples, built from the
+    /// exclusion flags and target unless one was given
+    pub filter: DependencyFilter,
 }
 
 impl AffectedConfig {
@@ -40,26 +87,54 @@ impl AffectedConfig {
 
 pub struct AffectedConfigBuilder {
     files: Vec<String>,
+    changed_since: Option<ChangedSince>,
+    files_from: Option<PathBuf>,
     show_crates: bool,
     direct_only: bool,
+    narrow_workspace_manifests: bool,
+    max_depth: Option<usize>,
+    cargo_subcommand: Option<String>,
     paths: Vec<PathBuf>,
-    format: OutputFormat,
+    format: RipplesFormat,
+    output: Option<PathBuf>,
     exclude_dev: bool,
     exclude_build: bool,
     exclude_target: bool,
+    target: Option<TargetPlatform>,
+    max_workspaces: Option<usize>,
+    exclude_paths: Option<Vec<glob::Pattern>>,
+    exclude_crates: Option<Vec<glob::Pattern>>,
+    follow_symlinks: bool,
+    no_cache: bool,
+    workspace_names: WorkspaceNames,
+    filter: Option<DependencyFilter>,
 }
 
 impl Default for AffectedConfigBuilder {
     fn default() -> Self {
         Self {
             files: Vec::new(),
+            changed_since: None,
+            files_from: None,
             show_crates: false,
             direct_only: false,
+            narrow_workspace_manifests: false,
+            max_depth: None,
+            cargo_subcommand: None,
             paths: Vec::new(),
-            format: OutputFormat::Human,
+            format: RipplesFormat::Human,
+            output: None,
             exclude_dev: false,
             exclude_build: false,
             exclude_target: false,
+            target: None,
+            max_workspaces: None,
+            exclude_paths: None,
+            exclude_crates: None,
+            follow_symlinks: false,
+            no_cache: false,
+            workspace_names: WorkspaceNames::default(),
+            filter: None,
         }
     }
 }
@@ -70,6 +145,16 @@ impl AffectedConfigBuilder {
         self
     }
 
+    pub fn with_changed_since(mut self, changed_since: Option<ChangedSince>) -> Self {
+        self.changed_since = changed_since;
+        self
+    }
+
+    pub fn with_files_from(mut self, files_from: Option<PathBuf>) -> Self {
+        self.files_from = files_from;
+        self
+    }
+
     pub fn with_show_crates(mut self, show: bool) -> Self {
         self.show_crates = show;
         self
@@ -80,16 +165,36 @@ impl AffectedConfigBuilder {
         self
     }
 
+    pub fn with_narrow_workspace_manifests(mut self, narrow: bool) -> Self {
+        self.narrow_workspace_manifests = narrow;
+        self
+    }
+
+    pub fn with_max_depth(mut self, max_depth: Option<usize>) -> Self {
+        self.max_depth = max_depth;
+        self
+    }
+
+    pub fn with_cargo_subcommand(mut self, cargo_subcommand: String) -> Self {
+        self.cargo_subcommand = Some(cargo_subcommand);
+        self
+    }
+
     pub fn with_paths(mut self, paths: Vec<PathBuf>) -> Self {
         self.paths = paths;
         self
     }
 
-    pub fn with_format(mut self, format: OutputFormat) -> Self {
+    pub fn with_format(mut self, format: RipplesFormat) -> Self {
         self.format = format;
         self
     }
 
+    pub fn with_output(mut self, output: Option<PathBuf>) -> Self {
+        self.output = output;
+        self
+    }
+
     pub fn with_exclude_dev(mut self, exclude: bool) -> Self {
         self.exclude_dev = exclude;
         self
@@ -105,22 +210,82 @@ impl AffectedConfigBuilder {
         self
     }
 
+    pub fn with_target(mut self, target: Option<TargetPlatform>) -> Self {
+        self.target = target;
+        self
+    }
+
+    pub fn with_max_workspaces(mut self, max_workspaces: Option<usize>) -> Self {
+        self.max_workspaces = max_workspaces;
+        self
+    }
+
+    pub fn with_exclude_paths(mut self, exclude_paths: Vec<glob::Pattern>) -> Self {
+        self.exclude_paths = Some(exclude_paths);
+        self
+    }
+
+    pub fn with_exclude_crates(mut self, exclude_crates: Vec<glob::Pattern>) -> Self {
+        self.exclude_crates = Some(exclude_crates);
+        self
+    }
+
+    pub fn with_follow_symlinks(mut self, follow_symlinks: bool) -> Self {
+        self.follow_symlinks = follow_symlinks;
+        self
+    }
+
+    pub fn with_no_cache(mut self, no_cache: bool) -> Self {
+        self.no_cache = no_cache;
+        self
+    }
+
+    pub fn with_workspace_names(mut self, workspace_names: WorkspaceNames) -> Self {
+        self.workspace_names = workspace_names;
+        self
+    }
+
+    /// Use `filter` instead of the exclusion flags and target
+    pub fn with_filter(mut self, filter: DependencyFilter) -> Self {
+        self.filter = Some(filter);
+        self
+    }
+
     pub fn build(self) -> Result<AffectedConfig, FerrisWheelError> {
-        if self.files.is_empty() {
+        if self.files.is_empty() && self.changed_since.is_none() && self.files_from.is_none() {
             return Err(FerrisWheelError::ConfigurationError {
                 message: "No files specified for affected analysis".to_string(),
             });
         }
 
+        let filter = self.filter.unwrap_or_else(|| {
+            DependencyFilter::new(self.exclude_dev, self.exclude_build, self.exclude_target)
+                .with_target(self.target)
+        });
+
         Ok(AffectedConfig {
             files: self.files,
+            changed_since: self.changed_since,
+            files_from: self.files_from,
             show_crates: self.show_crates,
             direct_only: self.direct_only,
+            narrow_workspace_manifests: self.narrow_workspace_manifests,
+            max_depth: self.max_depth,
+            cargo_subcommand: self.cargo_subcommand.unwrap_or_else(|| "test".to_string()),
             paths: self.paths,
             format: self.format,
+            output: self.output,
             exclude_dev: self.exclude_dev,
             exclude_build: self.exclude_build,
             exclude_target: self.exclude_target,
+            target: self.target,
+            max_workspaces: self.max_workspaces,
+            exclude_paths: self.exclude_paths.unwrap_or_default(),
+            exclude_crates: self.exclude_crates.unwrap_or_default(),
+            follow_symlinks: self.follow_symlinks,
+            no_cache: self.no_cache,
+            workspace_names: self.workspace_names,
+            filter,
         })
     }
 }
@@ -2,18 +2,51 @@
 
 use std::path::PathBuf;
 
+use super::AnalyzerConfig;
+use crate::analyzer::{TargetPlatform, WorkspaceNames};
 use crate::cli::OutputFormat;
+use crate::detector::{EffortModel, SeverityModel};
+use crate::reports::ReportVerbosity;
+use crate::reports::links::UrlTemplate;
 
 #[derive(Debug, Clone)]
 pub struct AnalyzeCrateConfig {
     pub crate_name: String,
     pub paths: Vec<PathBuf>,
     pub format: OutputFormat,
+    /// File to write the report to instead of stdout
+    pub output: Option<PathBuf>,
     pub exclude_dev: bool,
     pub exclude_build: bool,
     pub exclude_target: bool,
-    pub max_cycles: Option<usize>,
+    /// Only analyze target-specific dependencies that apply to this target
+    pub target: Option<TargetPlatform>,
+    /// Abort discovery once more than this many workspaces are found
+    pub max_workspaces: Option<usize>,
+    /// Directories skipped during discovery
+    pub exclude_paths: Vec<glob::Pattern>,
+    /// Crates left out of the analysis
+    pub exclude_crates: Vec<glob::Pattern>,
+    /// Follow symlinked directories during discovery
+    pub follow_symlinks: bool,
+    /// Skip the analysis cache
+    pub no_cache: bool,
+    /// Dependency backend and feature selection for the analyzer
+    pub analyzer: AnalyzerConfig,
+    /// Workspace display names
+    pub workspace_names: WorkspaceNames,
+    /// How much of each cycle the human report shows
+    pub verbosity: ReportVerbosity,
+    /// Enumerate elementary cycles, keeping at most this many per strongly
+    /// connected component (None = one cycle per component)
+    pub elementary_cycles: Option<usize>,
     pub intra_workspace: bool,
+    /// Code-browser URL template for linking cycle edges to manifests
+    pub url_template: Option<UrlTemplate>,
+    /// Model used to rate cycle severity
+    pub severity_model: SeverityModel,
+    /// Model used to estimate the effort to break each cycle
+    pub effort_model: EffortModel,
 }
 
 impl AnalyzeCrateConfig {
@@ -27,11 +60,24 @@ pub struct AnalyzeCrateConfigBuilder {
     crate_name: Option<String>,
     paths: Option<Vec<PathBuf>>,
     format: Option<OutputFormat>,
+    output: Option<PathBuf>,
     exclude_dev: Option<bool>,
     exclude_build: Option<bool>,
     exclude_target: Option<bool>,
-    max_cycles: Option<Option<usize>>,
+    target: Option<TargetPlatform>,
+    max_workspaces: Option<usize>,
+    exclude_paths: Option<Vec<glob::Pattern>>,
+    exclude_crates: Option<Vec<glob::Pattern>>,
+    follow_symlinks: Option<bool>,
+    no_cache: Option<bool>,
+    analyzer: Option<AnalyzerConfig>,
+    workspace_names: Option<WorkspaceNames>,
+    verbosity: Option<ReportVerbosity>,
+    elementary_cycles: Option<Option<usize>>,
     intra_workspace: Option<bool>,
+    url_template: Option<UrlTemplate>,
+    severity_model: Option<SeverityModel>,
+    effort_model: Option<EffortModel>,
 }
 
 impl AnalyzeCrateConfigBuilder {
@@ -40,11 +86,24 @@ impl AnalyzeCrateConfigBuilder {
             crate_name: None,
             paths: None,
             format: None,
+            output: None,
             exclude_dev: None,
             exclude_build: None,
             exclude_target: None,
-            max_cycles: None,
+            target: None,
+            max_workspaces: None,
+            exclude_paths: None,
+            exclude_crates: None,
+            follow_symlinks: None,
+            no_cache: None,
+            analyzer: None,
+            workspace_names: None,
+            verbosity: None,
+            elementary_cycles: None,
             intra_workspace: None,
+            url_template: None,
+            severity_model: None,
+            effort_model: None,
         }
     }
 
@@ -63,6 +122,11 @@ impl AnalyzeCrateConfigBuilder {
         self
     }
 
+    pub fn with_output(mut self, output: Option<PathBuf>) -> Self {
+        self.output = output;
+        self
+    }
+
     pub fn with_exclude_dev(mut self, exclude_dev: bool) -> Self {
         self.exclude_dev = Some(exclude_dev);
         self
@@ -78,8 +142,53 @@ impl AnalyzeCrateConfigBuilder {
         self
     }
 
-    pub fn with_max_cycles(mut self, max_cycles: Option<usize>) -> Self {
-        self.max_cycles = Some(max_cycles);
+    pub fn with_target(mut self, target: Option<TargetPlatform>) -> Self {
+        self.target = target;
+        self
+    }
+
+    pub fn with_max_workspaces(mut self, max_workspaces: Option<usize>) -> Self {
+        self.max_workspaces = max_workspaces;
+        self
+    }
+
+    pub fn with_exclude_paths(mut self, exclude_paths: Vec<glob::Pattern>) -> Self {
+        self.exclude_paths = Some(exclude_paths);
+        self
+    }
+
+    pub fn with_exclude_crates(mut self, exclude_crates: Vec<glob::Pattern>) -> Self {
+        self.exclude_crates = Some(exclude_crates);
+        self
+    }
+
+    pub fn with_follow_symlinks(mut self, follow_symlinks: bool) -> Self {
+        self.follow_symlinks = Some(follow_symlinks);
+        self
+    }
+
+    pub fn with_no_cache(mut self, no_cache: bool) -> Self {
+        self.no_cache = Some(no_cache);
+        self
+    }
+
+    pub fn with_analyzer(mut self, analyzer: AnalyzerConfig) -> Self {
+        self.analyzer = Some(analyzer);
+        self
+    }
+
+    pub fn with_workspace_names(mut self, workspace_names: WorkspaceNames) -> Self {
+        self.workspace_names = Some(workspace_names);
+        self
+    }
+
+    pub fn with_verbosity(mut self, verbosity: ReportVerbosity) -> Self {
+        self.verbosity = Some(verbosity);
+        self
+    }
+
+    pub fn with_elementary_cycles(mut self, elementary_cycles: Option<usize>) -> Self {
+        self.elementary_cycles = Some(elementary_cycles);
         self
     }
 
@@ -87,6 +196,21 @@ impl AnalyzeCrateConfigBuilder {
         self.intra_workspace = Some(intra_workspace);
         self
     }
+
+    pub fn with_url_template(mut self, url_template: Option<UrlTemplate>) -> Self {
+        self.url_template = url_template;
+        self
+    }
+
+    pub fn with_severity_model(mut self, severity_model: SeverityModel) -> Self {
+        self.severity_model = Some(severity_model);
+        self
+    }
+
+    pub fn with_effort_model(mut self, effort_model: EffortModel) -> Self {
+        self.effort_model = Some(effort_model);
+        self
+    }
 }
 
 impl crate::common::ConfigBuilder for AnalyzeCrateConfigBuilder {
@@ -109,6 +233,7 @@ impl crate::common::ConfigBuilder for AnalyzeCrateConfigBuilder {
                     message: "Missing required field: format".to_string(),
                 }
             })?,
+            output: self.output,
             exclude_dev: self.exclude_dev.ok_or_else(|| {
                 crate::error::FerrisWheelError::ConfigurationError {
                     message: "Missing required field: exclude_dev".to_string(),
@@ -124,9 +249,18 @@ impl crate::common::ConfigBuilder for AnalyzeCrateConfigBuilder {
                     message: "Missing required field: exclude_target".to_string(),
                 }
             })?,
-            max_cycles: self.max_cycles.ok_or_else(|| {
+            target: self.target,
+            max_workspaces: self.max_workspaces,
+            exclude_paths: self.exclude_paths.unwrap_or_default(),
+            exclude_crates: self.exclude_crates.unwrap_or_default(),
+            follow_symlinks: self.follow_symlinks.unwrap_or_default(),
+            no_cache: self.no_cache.unwrap_or_default(),
+            analyzer: self.analyzer.unwrap_or_default(),
+            workspace_names: self.workspace_names.unwrap_or_default(),
+            verbosity: self.verbosity.unwrap_or_default(),
+            elementary_cycles: self.elementary_cycles.ok_or_else(|| {
                 crate::error::FerrisWheelError::ConfigurationError {
-                    message: "Missing required field: max_cycles".to_string(),
+                    message: "Missing required field: elementary_cycles".to_string(),
                 }
             })?,
             intra_workspace: self.intra_workspace.ok_or_else(|| {
@@ -134,6 +268,9 @@ impl crate::common::ConfigBuilder for AnalyzeCrateConfigBuilder {
                     message: "Missing required field: intra_workspace".to_string(),
                 }
             })?,
+            url_template: self.url_template,
+            severity_model: self.severity_model.unwrap_or_default(),
+            effort_model: self.effort_model.unwrap_or_default(),
         })
     }
 }
@@ -0,0 +1,12 @@
+//! Analyzer configuration shared by every command that analyzes workspaces
+
+use crate::analyzer::{AnalyzerBackend, FeatureSelection};
+
+/// How the analyzer reads each crate's dependencies
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AnalyzerConfig {
+    /// Read dependencies by parsing manifests or from `cargo metadata`
+    pub backend: AnalyzerBackend,
+    /// Features whose optional dependencies are analyzed
+    pub features: FeatureSelection,
+}
@@ -0,0 +1,212 @@
+//! Carousel command configuration
+
+use std::path::PathBuf;
+
+use super::AnalyzerConfig;
+use crate::analyzer::{TargetPlatform, WorkspaceNames};
+use crate::cli::OutputFormat;
+use crate::detector::LayerModel;
+
+/// Configuration for the carousel command
+///
+/// Checks every workspace dependency against the declared architecture
+/// layers.
+#[derive(Debug, Clone)]
+pub struct CarouselConfig {
+    /// Layers to enforce, lowest first
+    pub layers: LayerModel,
+    /// Paths to search for Cargo workspaces
+    pub paths: Vec<PathBuf>,
+    /// Output format for the report
+    pub format: OutputFormat,
+    /// File to write the report to instead of stdout
+    pub output: Option<PathBuf>,
+    /// Exclude dev dependencies from the graph
+    pub exclude_dev: bool,
+    /// Exclude build dependencies from the graph
+    pub exclude_build: bool,
+    /// Exclude target-specific dependencies from the graph
+    pub exclude_target: bool,
+    /// Only analyze target-specific dependencies that apply to this target
+    pub target: Option<TargetPlatform>,
+    /// Abort discovery once more than this many workspaces are found
+    pub max_workspaces: Option<usize>,
+    /// Directories skipped during discovery
+    pub exclude_paths: Vec<glob::Pattern>,
+    /// Crates left out of the analysis
+    pub exclude_crates: Vec<glob::Pattern>,
+    /// Follow symlinked directories during discovery
+    pub follow_symlinks: bool,
+    /// Skip the analysis cache
+    pub no_cache: bool,
+    /// Dependency backend and feature selection for the analyzer
+    pub analyzer: AnalyzerConfig,
+    /// Workspace display names
+    pub workspace_names: WorkspaceNames,
+}
+
+impl CarouselConfig {
+    pub fn builder() -> CarouselConfigBuilder {
+        CarouselConfigBuilder::new()
+    }
+}
+
+#[derive(Default)]
+pub struct CarouselConfigBuilder {
+    layers: Option<LayerModel>,
+    paths: Option<Vec<PathBuf>>,
+    format: Option<OutputFormat>,
+    output: Option<PathBuf>,
+    exclude_dev: Option<bool>,
+    exclude_build: Option<bool>,
+    exclude_target: Option<bool>,
+    target: Option<TargetPlatform>,
+    max_workspaces: Option<usize>,
+    exclude_paths: Option<Vec<glob::Pattern>>,
+    exclude_crates: Option<Vec<glob::Pattern>>,
+    follow_symlinks: Option<bool>,
+    no_cache: Option<bool>,
+    analyzer: Option<AnalyzerConfig>,
+    workspace_names: Option<WorkspaceNames>,
+}
+
+impl CarouselConfigBuilder {
+    pub fn new() -> Self {
+        Self {
+            layers: None,
+            paths: None,
+            format: None,
+            output: None,
+            exclude_dev: None,
+            exclude_build: None,
+            exclude_target: None,
+            target: None,
+            max_workspaces: None,
+            exclude_paths: None,
+            exclude_crates: None,
+            follow_symlinks: None,
+            no_cache: None,
+            analyzer: None,
+            workspace_names: None,
+        }
+    }
+
+    pub fn with_layers(mut self, layers: LayerModel) -> Self {
+        self.layers = Some(layers);
+        self
+    }
+
+    pub fn with_paths(mut self, paths: Vec<PathBuf>) -> Self {
+        self.paths = Some(paths);
+        self
+    }
+
+    pub fn with_format(mut self, format: OutputFormat) -> Self {
+        self.format = Some(format);
+        self
+    }
+
+    pub fn with_output(mut self, output: Option<PathBuf>) -> Self {
+        self.output = output;
+        self
+    }
+
+    pub fn with_exclude_dev(mut self, exclude_dev: bool) -> Self {
+        self.exclude_dev = Some(exclude_dev);
+        self
+    }
+
+    pub fn with_exclude_build(mut self, exclude_build: bool) -> Self {
+        self.exclude_build = Some(exclude_build);
+        self
+    }
+
+    pub fn with_exclude_target(mut self, exclude_target: bool) -> Self {
+        self.exclude_target = Some(exclude_target);
+        self
+    }
+
+    pub fn with_target(mut self, target: Option<TargetPlatform>) -> Self {
+        self.target = target;
+        self
+    }
+
+    pub fn with_max_workspaces(mut self, max_workspaces: Option<usize>) -> Self {
+        self.max_workspaces = max_workspaces;
+        self
+    }
+
+    pub fn with_exclude_paths(mut self, exclude_paths: Vec<glob::Pattern>) -> Self {
+        self.exclude_paths = Some(exclude_paths);
+        self
+    }
+
+    pub fn with_exclude_crates(mut self, exclude_crates: Vec<glob::Pattern>) -> Self {
+        self.exclude_crates = Some(exclude_crates);
+        self
+    }
+
+    pub fn with_follow_symlinks(mut self, follow_symlinks: bool) -> Self {
+        self.follow_symlinks = Some(follow_symlinks);
+        self
+    }
+
+    pub fn with_no_cache(mut self, no_cache: bool) -> Self {
+        self.no_cache = Some(no_cache);
+        self
+    }
+
+    pub fn with_analyzer(mut self, analyzer: AnalyzerConfig) -> Self {
+        self.analyzer = Some(analyzer);
+        self
+    }
+
+    pub fn with_workspace_names(mut self, workspace_names: WorkspaceNames) -> Self {
+        self.workspace_names = Some(workspace_names);
+        self
+    }
+}
+
+impl crate::common::ConfigBuilder for CarouselConfigBuilder {
+    type Config = CarouselConfig;
+
+    fn build(self) -> Result<Self::Config, crate::error::FerrisWheelError> {
+        Ok(CarouselConfig {
+            layers: self.layers.unwrap_or_default(),
+            paths: self.paths.ok_or_else(|| {
+                crate::error::FerrisWheelError::ConfigurationError {
+                    message: "Missing required field: paths".to_string(),
+                }
+            })?,
+            format: self.format.ok_or_else(|| {
+                crate::error::FerrisWheelError::ConfigurationError {
+                    message: "Missing required field: format".to_string(),
+                }
+            })?,
+            output: self.output,
+            exclude_dev: self.exclude_dev.ok_or_else(|| {
+                crate::error::FerrisWheelError::ConfigurationError {
+                    message: "Missing required field: exclude_dev".to_string(),
+                }
+            })?,
+            exclude_build: self.exclude_build.ok_or_else(|| {
+                crate::error::FerrisWheelError::ConfigurationError {
+                    message: "Missing required field: exclude_build".to_string(),
+                }
+            })?,
+            exclude_target: self.exclude_target.ok_or_else(|| {
+                crate::error::FerrisWheelError::ConfigurationError {
+                    message: "Missing required field: exclude_target".to_string(),
+                }
+            })?,
+            target: self.target,
+            max_workspaces: self.max_workspaces,
+            exclude_paths: self.exclude_paths.unwrap_or_default(),
+            exclude_crates: self.exclude_crates.unwrap_or_default(),
+            follow_symlinks: self.follow_symlinks.unwrap_or_default(),
+            no_cache: self.no_cache.unwrap_or_default(),
+            analyzer: self.analyzer.unwrap_or_default(),
+            workspace_names: self.workspace_names.unwrap_or_default(),
+        })
+    }
+}
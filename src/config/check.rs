@@ -1,8 +1,18 @@
 //! Check command configuration
 
 use std::path::PathBuf;
+use std::time::Duration;
 
-use crate::cli::OutputFormat;
+use super::AnalyzerConfig;
+use crate::analyzer::{TargetPlatform, WorkspaceNames};
+use crate::cli::{CycleLevel, OutputFormat};
+use crate::detector::{
+    CycleAllowlist, DependencyPolicy, EffortModel, FailureBudget, GradeWeights, LayerModel,
+    SeverityModel,
+};
+use crate::graph::HypotheticalEdge;
+use crate::reports::ReportVerbosity;
+use crate::reports::links::UrlTemplate;
 
 /// Configuration for the check command
 ///
@@ -12,20 +22,89 @@ use crate::cli::OutputFormat;
 pub struct CheckCyclesConfig {
     /// Paths to search for Cargo workspaces
     pub paths: Vec<PathBuf>,
-    /// Output format for the report
-    pub format: OutputFormat,
+    /// Output formats, one report each
+    pub formats: Vec<OutputFormat>,
+    /// Directory to write one report per format into
+    pub output_dir: Option<PathBuf>,
+    /// File to write the report to instead of stdout
+    pub output: Option<PathBuf>,
     /// Whether to exit with error code if cycles are found
     pub error_on_cycles: bool,
+    /// Limits on the failing cycles; when set, they decide the exit code
+    pub budget: FailureBudget,
+    /// Fail on every dev-dependency that crosses a workspace boundary
+    pub deny_cross_workspace_dev_deps: bool,
     /// Exclude dev dependencies from cycle detection
     pub exclude_dev: bool,
     /// Exclude build dependencies from cycle detection
     pub exclude_build: bool,
     /// Exclude target-specific dependencies from cycle detection
     pub exclude_target: bool,
-    /// Maximum number of cycles to report (None = all)
-    pub max_cycles: Option<usize>,
+    /// Only analyze target-specific dependencies that apply to this target
+    pub target: Option<TargetPlatform>,
+    /// Abort discovery once more than this many workspaces are found
+    pub max_workspaces: Option<usize>,
+    /// Directories skipped during discovery
+    pub exclude_paths: Vec<glob::Pattern>,
+    /// Crates left out of the analysis
+    pub exclude_crates: Vec<glob::Pattern>,
+    /// Follow symlinked directories during discovery
+    pub follow_symlinks: bool,
+    /// Skip the analysis cache
+    pub no_cache: bool,
+    /// Dependency backend and feature selection for the analyzer
+    pub analyzer: AnalyzerConfig,
+    /// Workspace display names
+    pub workspace_names: WorkspaceNames,
+    /// How much of each cycle the human report shows
+    pub verbosity: ReportVerbosity,
+    /// Enumerate elementary cycles, keeping at most this many per strongly
+    /// connected component (None = one cycle per component)
+    pub elementary_cycles: Option<usize>,
     /// Only check for cycles within each workspace (not across workspaces)
     pub intra_workspace: bool,
+    /// Detect cycles between workspaces or between individual crates
+    pub level: CycleLevel,
+    /// Code-browser URL template for linking cycle edges to manifests
+    pub url_template: Option<UrlTemplate>,
+    /// Name of the project profile whose rules were applied
+    pub profile: Option<String>,
+    /// Weights for the architecture grade
+    pub grade_weights: GradeWeights,
+    /// Model used to rate cycle severity
+    pub severity_model: SeverityModel,
+    /// Model used to estimate the effort to break each cycle
+    pub effort_model: EffortModel,
+    /// Accepted cycles that never fail the run
+    pub allowlist: CycleAllowlist,
+    /// Rule for cycles through each dependency type
+    pub policy: DependencyPolicy,
+    /// Declared architecture layers to check dependencies against
+    pub layer_model: LayerModel,
+    /// Trend ledger to compare against and record this run in
+    pub ledger: Option<PathBuf>,
+    /// Baseline of known cycles; only cycles missing from it fail the run
+    pub baseline: Option<PathBuf>,
+    /// File to record the cycles found in as a baseline
+    pub write_baseline: Option<PathBuf>,
+    /// Socket, named pipe or file to stream analysis events to
+    pub events: Option<PathBuf>,
+    /// Dependencies to add to the graph before detecting cycles
+    pub what_if: Vec<HypotheticalEdge>,
+    /// Stop analysis after this long and report partial results
+    pub timeout: Option<Duration>,
+    /// Report cycles for each dependency-kind filter instead of one graph
+    pub kind_matrix: bool,
+    /// Re-check whenever a manifest changes instead of exiting
+    pub watch: bool,
+    /// Stop cycle detection at the first failing cycle and fail the run
+    pub fail_fast: bool,
+    /// File to save the workspace graph to
+    pub save_graph: Option<PathBuf>,
+    /// Template to render the report with instead of a built-in format
+    pub template: Option<PathBuf>,
+    /// Report suspicious manifest constructs found during discovery
+    pub manifest_warnings: bool,
 }
 
 impl CheckCyclesConfig {
@@ -37,26 +116,94 @@ impl CheckCyclesConfig {
 #[derive(Default)]
 pub struct CheckCyclesConfigBuilder {
     paths: Option<Vec<PathBuf>>,
-    format: Option<OutputFormat>,
+    formats: Option<Vec<OutputFormat>>,
+    output_dir: Option<PathBuf>,
+    output: Option<PathBuf>,
     error_on_cycles: Option<bool>,
+    budget: Option<FailureBudget>,
+    deny_cross_workspace_dev_deps: Option<bool>,
     exclude_dev: Option<bool>,
     exclude_build: Option<bool>,
     exclude_target: Option<bool>,
-    max_cycles: Option<Option<usize>>,
+    target: Option<TargetPlatform>,
+    max_workspaces: Option<usize>,
+    exclude_paths: Option<Vec<glob::Pattern>>,
+    exclude_crates: Option<Vec<glob::Pattern>>,
+    follow_symlinks: Option<bool>,
+    no_cache: Option<bool>,
+    analyzer: Option<AnalyzerConfig>,
+    workspace_names: Option<WorkspaceNames>,
+    verbosity: Option<ReportVerbosity>,
+    elementary_cycles: Option<Option<usize>>,
     intra_workspace: Option<bool>,
+    level: Option<CycleLevel>,
+    url_template: Option<UrlTemplate>,
+    profile: Option<String>,
+    grade_weights: Option<GradeWeights>,
+    severity_model: Option<SeverityModel>,
+    effort_model: Option<EffortModel>,
+    allowlist: Option<CycleAllowlist>,
+    policy: Option<DependencyPolicy>,
+    layer_model: Option<LayerModel>,
+    ledger: Option<PathBuf>,
+    baseline: Option<PathBuf>,
+    write_baseline: Option<PathBuf>,
+    events: Option<PathBuf>,
+    what_if: Option<Vec<HypotheticalEdge>>,
+    timeout: Option<Duration>,
+    kind_matrix: Option<bool>,
+    watch: Option<bool>,
+    fail_fast: Option<bool>,
+    save_graph: Option<PathBuf>,
+    template: Option<PathBuf>,
+    manifest_warnings: Option<bool>,
 }
 
 impl CheckCyclesConfigBuilder {
     pub fn new() -> Self {
         Self {
             paths: None,
-            format: None,
+            formats: None,
+            output_dir: None,
+            output: None,
             error_on_cycles: None,
+            budget: None,
+            deny_cross_workspace_dev_deps: None,
             exclude_dev: None,
             exclude_build: None,
             exclude_target: None,
-            max_cycles: None,
+            target: None,
+            max_workspaces: None,
+            exclude_paths: None,
+            exclude_crates: None,
+            follow_symlinks: None,
+            no_cache: None,
+            analyzer: None,
+            workspace_names: None,
+            verbosity: None,
+            elementary_cycles: None,
             intra_workspace: None,
+            level: None,
+            url_template: None,
+            profile: None,
+            grade_weights: None,
+            severity_model: None,
+            effort_model: None,
+            allowlist: None,
+            policy: None,
+            layer_model: None,
+            ledger: None,
+            baseline: None,
+            write_baseline: None,
+            events: None,
+            what_if: None,
+            timeout: None,
+            kind_matrix: None,
+            watch: None,
+            fail_fast: None,
+            save_graph: None,
+            template: None,
+            manifest_warnings: None,
         }
     }
 
@@ -65,8 +212,22 @@ impl CheckCyclesConfigBuilder {
         self
     }
 
-    pub fn with_format(mut self, format: OutputFormat) -> Self {
-        self.format = Some(format);
+    pub fn with_format(self, format: OutputFormat) -> Self {
+        self.with_formats(vec![format])
+    }
+
+    pub fn with_formats(mut self, formats: Vec<OutputFormat>) -> Self {
+        self.formats = Some(formats);
+        self
+    }
+
+    pub fn with_output_dir(mut self, output_dir: Option<PathBuf>) -> Self {
+        self.output_dir = output_dir;
+        self
+    }
+
+    pub fn with_output(mut self, output: Option<PathBuf>) -> Self {
+        self.output = output;
         self
     }
 
@@ -75,6 +236,16 @@ impl CheckCyclesConfigBuilder {
         self
     }
 
+    pub fn with_budget(mut self, budget: FailureBudget) -> Self {
+        self.budget = Some(budget);
+        self
+    }
+
+    pub fn with_deny_cross_workspace_dev_deps(mut self, deny: bool) -> Self {
+        self.deny_cross_workspace_dev_deps = Some(deny);
+        self
+    }
+
     pub fn with_exclude_dev(mut self, exclude_dev: bool) -> Self {
         self.exclude_dev = Some(exclude_dev);
         self
@@ -90,8 +261,53 @@ impl CheckCyclesConfigBuilder {
         self
     }
 
-    pub fn with_max_cycles(mut self, max_cycles: Option<usize>) -> Self {
-        self.max_cycles = Some(max_cycles);
+    pub fn with_target(mut self, target: Option<TargetPlatform>) -> Self {
+        self.target = target;
+        self
+    }
+
+    pub fn with_max_workspaces(mut self, max_workspaces: Option<usize>) -> Self {
+        self.max_workspaces = max_workspaces;
+        self
+    }
+
+    pub fn with_exclude_paths(mut self, exclude_paths: Vec<glob::Pattern>) -> Self {
+        self.exclude_paths = Some(exclude_paths);
+        self
+    }
+
+    pub fn with_exclude_crates(mut self, exclude_crates: Vec<glob::Pattern>) -> Self {
+        self.exclude_crates = Some(exclude_crates);
+        self
+    }
+
+    pub fn with_follow_symlinks(mut self, follow_symlinks: bool) -> Self {
+        self.follow_symlinks = Some(follow_symlinks);
+        self
+    }
+
+    pub fn with_no_cache(mut self, no_cache: bool) -> Self {
+        self.no_cache = Some(no_cache);
+        self
+    }
+
+    pub fn with_analyzer(mut self, analyzer: AnalyzerConfig) -> Self {
+        self.analyzer = Some(analyzer);
+        self
+    }
+
+    pub fn with_workspace_names(mut self, workspace_names: WorkspaceNames) -> Self {
+        self.workspace_names = Some(workspace_names);
+        self
+    }
+
+    pub fn with_verbosity(mut self, verbosity: ReportVerbosity) -> Self {
+        self.verbosity = Some(verbosity);
+        self
+    }
+
+    pub fn with_elementary_cycles(mut self, elementary_cycles: Option<usize>) -> Self {
+        self.elementary_cycles = Some(elementary_cycles);
         self
     }
 
@@ -99,28 +315,158 @@ impl CheckCyclesConfigBuilder {
         self.intra_workspace = Some(intra_workspace);
         self
     }
+
+    pub fn with_level(mut self, level: CycleLevel) -> Self {
+        self.level = Some(level);
+        self
+    }
+
+    pub fn with_url_template(mut self, url_template: Option<UrlTemplate>) -> Self {
+        self.url_template = url_template;
+        self
+    }
+
+    pub fn with_profile(mut self, profile: Option<String>) -> Self {
+        self.profile = profile;
+        self
+    }
+
+    pub fn with_grade_weights(mut self, grade_weights: GradeWeights) -> Self {
+        self.grade_weights = Some(grade_weights);
+        self
+    }
+
+    pub fn with_severity_model(mut self, severity_model: SeverityModel) -> Self {
+        self.severity_model = Some(severity_model);
+        self
+    }
+
+    pub fn with_effort_model(mut self, effort_model: EffortModel) -> Self {
+        self.effort_model = Some(effort_model);
+        self
+    }
+
+    pub fn with_allowlist(mut self, allowlist: CycleAllowlist) -> Self {
+        self.allowlist = Some(allowlist);
+        self
+    }
+
+    pub fn with_policy(mut self, policy: DependencyPolicy) -> Self {
+        self.policy = Some(policy);
+        self
+    }
+
+    pub fn with_layer_model(mut self, layer_model: LayerModel) -> Self {
+        self.layer_model = Some(layer_model);
+        self
+    }
+
+    pub fn with_ledger(mut self, ledger: Option<PathBuf>) -> Self {
+        self.ledger = ledger;
+        self
+    }
+
+    pub fn with_baseline(mut self, baseline: Option<PathBuf>) -> Self {
+        self.baseline = baseline;
+        self
+    }
+
+    pub fn with_write_baseline(mut self, write_baseline: Option<PathBuf>) -> Self {
+        self.write_baseline = write_baseline;
+        self
+    }
+
+    pub fn with_events(mut self, events: Option<PathBuf>) -> Self {
+        self.events = events;
+        self
+    }
+
+    pub fn with_what_if(mut self, what_if: Vec<HypotheticalEdge>) -> Self {
+        self.what_if = Some(what_if);
+        self
+    }
+
+    pub fn with_timeout(mut self, timeout: Option<Duration>) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    pub fn with_kind_matrix(mut self, kind_matrix: bool) -> Self {
+        self.kind_matrix = Some(kind_matrix);
+        self
+    }
+
+    pub fn with_watch(mut self, watch: bool) -> Self {
+        self.watch = Some(watch);
+        self
+    }
+
+    pub fn with_fail_fast(mut self, fail_fast: bool) -> Self {
+        self.fail_fast = Some(fail_fast);
+        self
+    }
+
+    pub fn with_save_graph(mut self, save_graph: Option<PathBuf>) -> Self {
+        self.save_graph = save_graph;
+        self
+    }
+
+    pub fn with_template(mut self, template: Option<PathBuf>) -> Self {
+        self.template = template;
+        self
+    }
+
+    pub fn with_manifest_warnings(mut self, manifest_warnings: bool) -> Self {
+        self.manifest_warnings = Some(manifest_warnings);
+        self
+    }
 }
 
 impl crate::common::ConfigBuilder for CheckCyclesConfigBuilder {
     type Config = CheckCyclesConfig;
 
     fn build(self) -> Result<Self::Config, crate::error::FerrisWheelError> {
+        if self.save_graph.is_some() && self.level == Some(CycleLevel::Crate) {
+            return Err(crate::error::FerrisWheelError::ConfigurationError {
+                message: "--save-graph saves workspace graphs and cannot be used with --level \
+                          crate"
+                    .to_string(),
+            });
+        }
+
+        let formats =
+            self.formats
+                .ok_or_else(|| crate::error::FerrisWheelError::ConfigurationError {
+                    message: "Missing required field: formats".to_string(),
+                })?;
+        if formats.is_empty() {
+            return Err(crate::error::FerrisWheelError::ConfigurationError {
+                message: "At least one output format is required".to_string(),
+            });
+        }
+        if formats.len() > 1 && self.output_dir.is_none() {
+            return Err(crate::error::FerrisWheelError::ConfigurationError {
+                message: "Several output formats need --output-dir to write their reports to"
+                    .to_string(),
+            });
+        }
+
         Ok(CheckCyclesConfig {
             paths: self.paths.ok_or_else(|| {
                 crate::error::FerrisWheelError::ConfigurationError {
                     message: "Missing required field: paths".to_string(),
                 }
             })?,
-            format: self.format.ok_or_else(|| {
-                crate::error::FerrisWheelError::ConfigurationError {
-                    message: "Missing required field: format".to_string(),
-                }
-            })?,
+            formats,
+            output_dir: self.output_dir,
+            output: self.output,
             error_on_cycles: self.error_on_cycles.ok_or_else(|| {
                 crate::error::FerrisWheelError::ConfigurationError {
                     message: "Missing required field: error_on_cycles".to_string(),
                 }
             })?,
+            budget: self.budget.unwrap_or_default(),
+            deny_cross_workspace_dev_deps: self.deny_cross_workspace_dev_deps.unwrap_or_default(),
             exclude_dev: self.exclude_dev.ok_or_else(|| {
                 crate::error::FerrisWheelError::ConfigurationError {
                     message: "Missing required field: exclude_dev".to_string(),
@@ -136,9 +482,18 @@ impl crate::common::ConfigBuilder for CheckCyclesConfigBuilder {
                     message: "Missing required field: exclude_target".to_string(),
                 }
             })?,
-            max_cycles: self.max_cycles.ok_or_else(|| {
+            target: self.target,
+            max_workspaces: self.max_workspaces,
+            exclude_paths: self.exclude_paths.unwrap_or_default(),
+            exclude_crates: self.exclude_crates.unwrap_or_default(),
+            follow_symlinks: self.follow_symlinks.unwrap_or_default(),
+            no_cache: self.no_cache.unwrap_or_default(),
+            analyzer: self.analyzer.unwrap_or_default(),
+            workspace_names: self.workspace_names.unwrap_or_default(),
+            verbosity: self.verbosity.unwrap_or_default(),
+            elementary_cycles: self.elementary_cycles.ok_or_else(|| {
                 crate::error::FerrisWheelError::ConfigurationError {
-                    message: "Missing required field: max_cycles".to_string(),
+                    message: "Missing required field: elementary_cycles".to_string(),
                 }
             })?,
             intra_workspace: self.intra_workspace.ok_or_else(|| {
@@ -146,6 +501,31 @@ impl crate::common::ConfigBuilder for CheckCyclesConfigBuilder {
                     message: "Missing required field: intra_workspace".to_string(),
                 }
             })?,
+            level: self.level.ok_or_else(|| {
+                crate::error::FerrisWheelError::ConfigurationError {
+                    message: "Missing required field: level".to_string(),
+                }
+            })?,
+            url_template: self.url_template,
+            profile: self.profile,
+            grade_weights: self.grade_weights.unwrap_or_default(),
+            severity_model: self.severity_model.unwrap_or_default(),
+            effort_model: self.effort_model.unwrap_or_default(),
+            allowlist: self.allowlist.unwrap_or_default(),
+            policy: self.policy.unwrap_or_default(),
+            layer_model: self.layer_model.unwrap_or_default(),
+            ledger: self.ledger,
+            baseline: self.baseline,
+            write_baseline: self.write_baseline,
+            events: self.events,
+            what_if: self.what_if.unwrap_or_default(),
+            timeout: self.timeout,
+            kind_matrix: self.kind_matrix.unwrap_or(false),
+            watch: self.watch.unwrap_or(false),
+            fail_fast: self.fail_fast.unwrap_or(false),
+            save_graph: self.save_graph,
+            template: self.template,
+            manifest_warnings: self.manifest_warnings.unwrap_or(false),
         })
     }
 }
@@ -0,0 +1,46 @@
+//! Completions command configuration
+
+use clap_complete::Shell;
+
+/// Configuration for the completions command
+#[derive(Debug, Clone)]
+pub struct CompletionsConfig {
+    /// Shell to print a completion script for
+    pub shell: Shell,
+}
+
+impl CompletionsConfig {
+    pub fn builder() -> CompletionsConfigBuilder {
+        CompletionsConfigBuilder::new()
+    }
+}
+
+#[derive(Default)]
+pub struct CompletionsConfigBuilder {
+    shell: Option<Shell>,
+}
+
+impl CompletionsConfigBuilder {
+    pub fn new() -> Self {
+        Self { shell: None }
+    }
+
+    pub fn with_shell(mut self, shell: Shell) -> Self {
+        self.shell = Some(shell);
+        self
+    }
+}
+
+impl crate::common::ConfigBuilder for CompletionsConfigBuilder {
+    type Config = CompletionsConfig;
+
+    fn build(self) -> Result<Self::Config, crate::error::FerrisWheelError> {
+        Ok(CompletionsConfig {
+            shell: self.shell.ok_or_else(|| {
+                crate::error::FerrisWheelError::ConfigurationError {
+                    message: "Missing required field: shell".to_string(),
+                }
+            })?,
+        })
+    }
+}
@@ -0,0 +1,533 @@
+//! The `[defaults]` section of the project configuration
+//!
+//! Defaults fill in the paths, discovery exclusions, output format and
+//! dependency filters of every command, so CI and developers share one set
+//! of settings. The active profile's `[profile.<name>.defaults]` override
+//! them. Anything given on the command line or through an environment
+//! variable takes precedence over the file.
+
+use std::path::{Path, PathBuf};
+
+use clap::parser::ValueSource;
+use clap::{ArgMatches, ValueEnum};
+use schemars::JsonSchema;
+use serde::Deserialize;
+
+use super::ProjectConfig;
+use crate::analyzer::WorkspaceNames;
+use crate::cli::{Commands, FixFormat, OutputFormat, RipplesFormat, StatsFormat};
+use crate::error::FerrisWheelError;
+
+/// Settings every command starts from (`[defaults]`)
+#[derive(Debug, Clone, Default, Deserialize, JsonSchema)]
+#[serde(default, deny_unknown_fields, rename_all = "kebab-case")]
+pub struct DefaultsConfig {
+    /// Paths analyzed when none are given, relative to the configuration
+    /// file's directory
+    pub paths: Vec<PathBuf>,
+    /// Directories skipped during discovery, like `--exclude-path`
+    pub exclude_paths: Vec<String>,
+    /// Crates left out of the analysis, like `--exclude-crate`
+    pub exclude_crates: Vec<String>,
+    /// Output format; commands without this format keep their own default
+    pub format: Option<String>,
+    /// Exclude dev-dependencies from analysis
+    pub exclude_dev: Option<bool>,
+    /// Exclude build-dependencies from analysis
+    pub exclude_build: Option<bool>,
+    /// Exclude target-specific dependencies
+    pub exclude_target: Option<bool>,
+    /// Exit with an error code if `inspect` finds cycles
+    pub error_on_cycles: Option<bool>,
+    /// Read and update the analysis cache
+    pub cache: Option<bool>,
+}
+
+/// The settings of a parsed command that `[defaults]` can fill in
+struct CommandSettings<'a> {
+    paths: Option<&'a mut Vec<PathBuf>>,
+    exclude_dev: &'a mut bool,
+    exclude_build: &'a mut bool,
+    exclude_target: &'a mut bool,
+    error_on_cycles: Option<&'a mut bool>,
+    no_cache: &'a mut bool,
+    exclude_paths: &'a mut Vec<glob::Pattern>,
+    exclude_crates: &'a mut Vec<glob::Pattern>,
+    format: Option<Format<'a>>,
+    workspace_names: &'a mut WorkspaceNames,
+    profile: Option<String>,
+}
+
+enum Format<'a> {
+    Output(&'a mut OutputFormat),
+    Outputs(&'a mut Vec<OutputFormat>),
+    Stats(&'a mut StatsFormat),
+    Fix(&'a mut FixFormat),
+    Ripples(&'a mut RipplesFormat),
+}
+
+impl<'a> CommandSettings<'a> {
+    fn of(command: &'a mut Commands) -> Option<Self> {
+        use Commands::*;
+
+        let (common, format, error_on_cycles) = match command {
+            Inspect {
+                common,
+                format,
+                error_on_cycles,
+                ..
+            } => (
+                common,
+                Some(Format::Outputs(&mut format.format)),
+                Some(error_on_cycles),
+            ),
+            Spotlight { common, format, .. }
+            | Lineup { common, format, .. }
+            | Path { common, format, .. }
+            | Why { common, format, .. }
+            | Between { common, format, .. }
+            | Chain { common, format, .. }
+            | Carousel { common, format, .. }
+            | Sweep { common, format, .. }
+            | Diff { common, format, .. } => {
+                (common, Some(Format::Output(&mut format.format)), None)
+            }
+            Stats { common, format, .. } => (common, Some(Format::Stats(format)), None),
+            Fix { common, format, .. } => (common, Some(Format::Fix(format)), None),
+            // The graph formats of spectacle are not output formats
+            Spectacle { common, .. } | Changelog { common, .. } | Tui { common, .. } => {
+                (common, None, None)
+            }
+            Ripples {
+                exclude_dev,
+                exclude_build,
+                exclude_target,
+                exclude_paths,
+                exclude_crates,
+                no_cache,
+                format,
+                workspace_names,
+                profile,
+                ..
+            } => {
+                return Some(Self {
+                    paths: None,
+                    exclude_dev,
+                    exclude_build,
+                    exclude_target,
+                    error_on_cycles: None,
+                    no_cache,
+                    exclude_paths,
+                    exclude_crates,
+                    format: Some(Format::Ripples(format)),
+                    workspace_names,
+                    profile: profile.clone(),
+                });
+            }
+            Completions { .. } | Manpages { .. } | Schema { .. } => return None,
+        };
+
+        Some(Self {
+            paths: Some(&mut common.paths),
+            exclude_dev: &mut common.exclude_dev,
+            exclude_build: &mut common.exclude_build,
+            exclude_target: &mut common.exclude_target,
+            error_on_cycles,
+            no_cache: &mut common.no_cache,
+            exclude_paths: &mut common.exclude_paths,
+            exclude_crates: &mut common.exclude_crates,
+            format,
+            workspace_names: &mut common.workspace_names,
+            profile: common.profile.clone(),
+        })
+    }
+}
+
+/// Fill in the settings of `command` that `matches` shows were left unset
+/// from the `[defaults]` of the project configuration, if there is one, as
+/// overridden by the active profile, and hand it the project's workspace
+/// display names
+///
+/// Returns the project configuration, so the command can use it without
+/// loading it again.
+pub fn apply_project_defaults(
+    command: &mut Commands,
+    matches: &ArgMatches,
+) -> Result<Option<ProjectConfig>, FerrisWheelError> {
+    let Some(settings) = CommandSettings::of(command) else {
+        return Ok(None);
+    };
+    let start = settings.paths.as_deref().cloned().unwrap_or_default();
+    let Some(project) = ProjectConfig::discover_for(&start)? else {
+        return match &settings.profile {
+            Some(profile) => Err(FerrisWheelError::ConfigurationError {
+                message: format!(
+                    "Profile '{profile}' requested but no {} was found",
+                    crate::constants::config::FILE_NAME
+                ),
+            }),
+            None => Ok(None),
+        };
+    };
+    *settings.workspace_names = project.names.clone();
+
+    // Without profiles, there is no need to ask git for the branch
+    let profile = if settings.profile.is_some() || !project.profile.is_empty() {
+        project.select_profile(settings.profile.as_deref())?
+    } else {
+        None
+    };
+    let defaults = match profile {
+        Some((_, profile)) => project.defaults.overridden_by(&profile.defaults),
+        None => project.defaults.clone(),
+    };
+
+    defaults.apply(settings, project.root().unwrap_or(Path::new(".")), matches)?;
+    Ok(Some(project))
+}
+
+impl DefaultsConfig {
+    /// These defaults with every setting `other` sets replaced by its value
+    pub fn overridden_by(&self, other: &DefaultsConfig) -> DefaultsConfig {
+        DefaultsConfig {
+            paths: overridden_list(&self.paths, &other.paths),
+            exclude_paths: overridden_list(&self.exclude_paths, &other.exclude_paths),
+            exclude_crates: overridden_list(&self.exclude_crates, &other.exclude_crates),
+            format: other.format.clone().or_else(|| self.format.clone()),
+            exclude_dev: other.exclude_dev.or(self.exclude_dev),
+            exclude_build: other.exclude_build.or(self.exclude_build),
+            exclude_target: other.exclude_target.or(self.exclude_target),
+            error_on_cycles: other.error_on_cycles.or(self.error_on_cycles),
+            cache: other.cache.or(self.cache),
+        }
+    }
+
+    fn apply(
+        &self,
+        settings: CommandSettings<'_>,
+        root: &Path,
+        matches: &ArgMatches,
+    ) -> Result<(), FerrisWheelError> {
+        let unset = |id: &str| {
+            matches
+                .value_source(id)
+                .is_none_or(|source| source == ValueSource::DefaultValue)
+        };
+
+        if let Some(paths) = settings.paths
+            && paths.is_empty()
+        {
+            *paths = self.paths.iter().map(|path| root.join(path)).collect();
+        }
+        if !self.exclude_paths.is_empty() && unset("exclude_paths") {
+            *settings.exclude_paths = patterns(&self.exclude_paths, "exclude path")?;
+        }
+        if !self.exclude_crates.is_empty() && unset("exclude_crates") {
+            *settings.exclude_crates = patterns(&self.exclude_crates, "exclude crate")?;
+        }
+
+        // A flag counts as given when either it or its negation is
+        for (id, negation, flag, value) in [
+            (
+                "exclude_dev",
+                "no_exclude_dev",
+                Some(settings.exclude_dev),
+                self.exclude_dev,
+            ),
+            (
+                "exclude_build",
+                "no_exclude_build",
+                Some(settings.exclude_build),
+                self.exclude_build,
+            ),
+            (
+                "exclude_target",
+                "no_exclude_target",
+                Some(settings.exclude_target),
+                self.exclude_target,
+            ),
+            (
+                "error_on_cycles",
+                "no_error_on_cycles",
+                settings.error_on_cycles,
+                self.error_on_cycles,
+            ),
+            (
+                "no_cache",
+                "cache",
+                Some(settings.no_cache),
+                self.cache.map(|cache| !cache),
+            ),
+        ] {
+            if let (Some(flag), Some(value)) = (flag, value)
+                && unset(id)
+                && unset(negation)
+            {
+                *flag = value;
+            }
+        }
+
+        if let Some(value) = &self.format {
+            check_format(value)?;
+        }
+        if let (Some(format), Some(value)) = (settings.format, &self.format)
+            && unset("format")
+        {
+            match format {
+                Format::Output(format) => set_format(format, value),
+                Format::Outputs(formats) => {
+                    if let Ok(parsed) = OutputFormat::from_str(value, true) {
+                        *formats = vec![parsed];
+                    }
+                }
+                Format::Stats(format) => set_format(format, value),
+                Format::Fix(format) => set_format(format, value),
+                Format::Ripples(format) => set_format(format, value),
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// `theirs` unless it is empty, then `ours`
+fn overridden_list<T: Clone>(ours: &[T], theirs: &[T]) -> Vec<T> {
+    if theirs.is_empty() { ours } else { theirs }.to_vec()
+}
+
+/// Parse the globs of a `[defaults]` list of `what`s
+fn patterns(globs: &[String], what: &str) -> Result<Vec<glob::Pattern>, FerrisWheelError> {
+    globs
+        .iter()
+        .map(|pattern| {
+            glob::Pattern::new(pattern).map_err(|e| FerrisWheelError::ConfigurationError {
+                message: format!("Invalid {what} '{pattern}' in [defaults]: {e}"),
+            })
+        })
+        .collect()
+}
+
+/// Fail unless `value` is the format of at least one command
+fn check_format(value: &str) -> Result<(), FerrisWheelError> {
+    fn names<T: ValueEnum>() -> Vec<String> {
+        T::value_variants()
+            .iter()
+            .filter_map(|variant| variant.to_possible_value())
+            .map(|value| value.get_name().to_string())
+            .collect()
+    }
+
+    let mut accepted = [
+        names::<OutputFormat>(),
+        names::<StatsFormat>(),
+        names::<FixFormat>(),
+        names::<RipplesFormat>(),
+    ]
+    .concat();
+    if accepted.iter().any(|name| name.eq_ignore_ascii_case(value)) {
+        return Ok(());
+    }
+
+    accepted.sort();
+    accepted.dedup();
+    Err(FerrisWheelError::ConfigurationError {
+        message: format!(
+            "Invalid format '{value}' in [defaults]: expected one of {}",
+            accepted.join(", ")
+        ),
+    })
+}
+
+/// Set `format` to `value`, unless the command has no such format
+fn set_format<T: ValueEnum>(format: &mut T, value: &str) {
+    if let Ok(parsed) = T::from_str(value, true) {
+        *format = parsed;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use clap::{CommandFactory, FromArgMatches};
+
+    use super::*;
+    use crate::cli::{CargoArgs, CargoCommand};
+
+    fn parse(args: &[&str], defaults: &DefaultsConfig) -> Commands {
+        try_parse(args, defaults).unwrap()
+    }
+
+    fn try_parse(args: &[&str], defaults: &DefaultsConfig) -> Result<Commands, FerrisWheelError> {
+        let matches = CargoArgs::command()
+            .try_get_matches_from(["cargo", "ferris-wheel"].iter().chain(args))
+            .unwrap();
+        let CargoCommand::FerrisWheel(mut cli) =
+            CargoArgs::from_arg_matches(&matches).unwrap().command;
+        let (_, ferris_wheel) = matches.subcommand().unwrap();
+        let (_, command) = ferris_wheel.subcommand().unwrap();
+
+        let settings = CommandSettings::of(&mut cli.command).unwrap();
+        defaults.apply(settings, Path::new("/repo"), command)?;
+        Ok(cli.command)
+    }
+
+    #[test]
+    fn test_profile_defaults_override_project_defaults() {
+        let defaults: DefaultsConfig = toml::from_str(
+            r#"
+paths = ["core"]
+exclude-paths = ["vendor"]
+exclude-dev = true
+"#,
+        )
+        .unwrap();
+        let ci: DefaultsConfig = toml::from_str(
+            r#"
+format = "github"
+exclude-dev = false
+"#,
+        )
+        .unwrap();
+
+        let merged = defaults.overridden_by(&ci);
+        assert_eq!(merged.paths, vec![PathBuf::from("core")]);
+        assert_eq!(merged.exclude_paths, vec!["vendor".to_string()]);
+        assert_eq!(merged.format.as_deref(), Some("github"));
+        assert_eq!(merged.exclude_dev, Some(false));
+        assert_eq!(merged.exclude_build, None);
+    }
+
+    #[test]
+    fn test_defaults_fill_in_unset_settings_only() {
+        let defaults: DefaultsConfig = toml::from_str(
+            r#"
+paths = ["core", "apps"]
+exclude-paths = ["vendor"]
+exclude-crates = ["*-fixtures"]
+format = "json"
+exclude-dev = true
+"#,
+        )
+        .unwrap();
+
+        let Commands::Inspect { common, format, .. } = parse(&["inspect"], &defaults) else {
+            unreachable!()
+        };
+        assert_eq!(
+            common.paths,
+            vec![PathBuf::from("/repo/core"), PathBuf::from("/repo/apps")]
+        );
+        assert_eq!(common.exclude_paths[0].as_str(), "vendor");
+        assert_eq!(common.exclude_crates[0].as_str(), "*-fixtures");
+        assert_eq!(format.format, [OutputFormat::Json]);
+        assert!(common.exclude_dev);
+        assert!(!common.exclude_build);
+
+        let Commands::Inspect { common, format, .. } = parse(
+            &[
+                "inspect",
+                "lib",
+                "--format",
+                "human",
+                "--exclude-path",
+                "fixtures",
+            ],
+            &defaults,
+        ) else {
+            unreachable!()
+        };
+        assert_eq!(common.paths, vec![PathBuf::from("lib")]);
+        assert_eq!(common.exclude_paths[0].as_str(), "fixtures");
+        assert_eq!(format.format, [OutputFormat::Human]);
+
+        // Formats a command does not have leave its default alone
+        let csv = DefaultsConfig {
+            format: Some("csv".to_string()),
+            ..DefaultsConfig::default()
+        };
+        let Commands::Stats { format, .. } = parse(&["stats"], &csv) else {
+            unreachable!()
+        };
+        assert_eq!(format, StatsFormat::Csv);
+        let Commands::Inspect { format, .. } = parse(&["inspect"], &csv) else {
+            unreachable!()
+        };
+        assert_eq!(format.format, [OutputFormat::Human]);
+    }
+
+    #[test]
+    fn test_unknown_default_format_is_an_error() {
+        let defaults = DefaultsConfig {
+            format: Some("jsno".to_string()),
+            ..DefaultsConfig::default()
+        };
+        let Err(err) = try_parse(&["inspect"], &defaults) else {
+            panic!("an unknown format should be rejected")
+        };
+
+        let message = err.to_string();
+        assert!(message.contains("'jsno'"), "{message}");
+        assert!(
+            message.contains("csv") && message.contains("json"),
+            "{message}"
+        );
+    }
+
+    #[test]
+    fn test_command_line_turns_off_true_defaults() {
+        let defaults: DefaultsConfig = toml::from_str(
+            r#"
+exclude-dev = true
+exclude-target = true
+error-on-cycles = true
+"#,
+        )
+        .unwrap();
+
+        let Commands::Inspect {
+            common,
+            error_on_cycles,
+            ..
+        } = parse(&["inspect"], &defaults)
+        else {
+            unreachable!()
+        };
+        assert!(common.exclude_dev && common.exclude_target && error_on_cycles);
+
+        let Commands::Inspect {
+            common,
+            error_on_cycles,
+            ..
+        } = parse(
+            &["inspect", "--no-exclude-dev", "--no-error-on-cycles"],
+            &defaults,
+        )
+        else {
+            unreachable!()
+        };
+        assert!(!common.exclude_dev);
+        assert!(common.exclude_target);
+        assert!(!error_on_cycles);
+
+        // The last of a flag and its `--no-` form wins
+        let Commands::Stats { common, .. } = parse(
+            &["stats", "--no-exclude-target", "--exclude-target"],
+            &defaults,
+        ) else {
+            unreachable!()
+        };
+        assert!(common.exclude_target);
+
+        let no_cache = DefaultsConfig {
+            cache: Some(false),
+            ..DefaultsConfig::default()
+        };
+        let Commands::Stats { common, .. } = parse(&["stats"], &no_cache) else {
+            unreachable!()
+        };
+        assert!(common.no_cache);
+        let Commands::Stats { common, .. } = parse(&["stats", "--cache"], &no_cache) else {
+            unreachable!()
+        };
+        assert!(!common.no_cache);
+    }
+}
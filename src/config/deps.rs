@@ -1,8 +1,12 @@
 //! Deps command configuration
 
 use std::path::PathBuf;
+use std::sync::Arc;
 
+use super::AnalyzerConfig;
+use crate::analyzer::{MetadataProvider, TargetPlatform, WorkspaceNames};
 use crate::cli::OutputFormat;
+use crate::dependency_filter::DependencyFilter;
 
 #[derive(Debug, Clone)]
 pub struct WorkspaceDepsConfig {
@@ -11,9 +15,31 @@ pub struct WorkspaceDepsConfig {
     pub transitive: bool,
     pub paths: Vec<PathBuf>,
     pub format: OutputFormat,
+    /// File to write the report to instead of stdout
+    pub output: Option<PathBuf>,
     pub exclude_dev: bool,
     pub exclude_build: bool,
     pub exclude_target: bool,
+    /// Only analyze target-specific dependencies that apply to this target
+    pub target: Option<TargetPlatform>,
+    /// Abort discovery once more than this many workspaces are found
+    pub max_workspaces: Option<usize>,
+    /// Directories skipped during discovery
+    pub exclude_paths: Vec<glob::Pattern>,
+    /// Crates left out of the analysis
+    pub exclude_crates: Vec<glob::Pattern>,
+    /// Follow symlinked directories during discovery
+    pub follow_symlinks: bool,
+    /// Skip the analysis cache
+    pub no_cache: bool,
+    /// Filter deciding which dependencies are lined up, built from the
+    /// exclusion flags and target unless one was given
+    pub filter: DependencyFilter,
+    /// Dependency backend and feature selection for the analyzer
+    pub analyzer: AnalyzerConfig,
+    /// Workspace display names
+    pub workspace_names: WorkspaceNames,
+    pub metadata_providers: Vec<Arc<dyn MetadataProvider>>,
 }
 
 impl WorkspaceDepsConfig {
@@ -29,9 +55,20 @@ pub struct WorkspaceDepsConfigBuilder {
     transitive: Option<bool>,
     paths: Option<Vec<PathBuf>>,
     format: Option<OutputFormat>,
+    output: Option<PathBuf>,
     exclude_dev: Option<bool>,
     exclude_build: Option<bool>,
     exclude_target: Option<bool>,
+    target: Option<TargetPlatform>,
+    max_workspaces: Option<usize>,
+    exclude_paths: Option<Vec<glob::Pattern>>,
+    exclude_crates: Option<Vec<glob::Pattern>>,
+    follow_symlinks: Option<bool>,
+    no_cache: Option<bool>,
+    filter: Option<DependencyFilter>,
+    analyzer: Option<AnalyzerConfig>,
+    workspace_names: Option<WorkspaceNames>,
+    metadata_providers: Option<Vec<Arc<dyn MetadataProvider>>>,
 }
 
 impl WorkspaceDepsConfigBuilder {
@@ -42,9 +79,20 @@ impl WorkspaceDepsConfigBuilder {
             transitive: None,
             paths: None,
             format: None,
+            output: None,
             exclude_dev: None,
             exclude_build: None,
             exclude_target: None,
+            target: None,
+            max_workspaces: None,
+            exclude_paths: None,
+            exclude_crates: None,
+            follow_symlinks: None,
+            no_cache: None,
+            filter: None,
+            analyzer: None,
+            workspace_names: None,
+            metadata_providers: None,
         }
     }
 
@@ -73,6 +121,11 @@ impl WorkspaceDepsConfigBuilder {
         self
     }
 
+    pub fn with_output(mut self, output: Option<PathBuf>) -> Self {
+        self.output = output;
+        self
+    }
+
     pub fn with_exclude_dev(mut self, exclude_dev: bool) -> Self {
         self.exclude_dev = Some(exclude_dev);
         self
@@ -87,12 +140,83 @@ impl WorkspaceDepsConfigBuilder {
         self.exclude_target = Some(exclude_target);
         self
     }
+
+    pub fn with_target(mut self, target: Option<TargetPlatform>) -> Self {
+        self.target = target;
+        self
+    }
+
+    pub fn with_max_workspaces(mut self, max_workspaces: Option<usize>) -> Self {
+        self.max_workspaces = max_workspaces;
+        self
+    }
+
+    pub fn with_exclude_paths(mut self, exclude_paths: Vec<glob::Pattern>) -> Self {
+        self.exclude_paths = Some(exclude_paths);
+        self
+    }
+
+    pub fn with_exclude_crates(mut self, exclude_crates: Vec<glob::Pattern>) -> Self {
+        self.exclude_crates = Some(exclude_crates);
+        self
+    }
+
+    pub fn with_follow_symlinks(mut self, follow_symlinks: bool) -> Self {
+        self.follow_symlinks = Some(follow_symlinks);
+        self
+    }
+
+    pub fn with_no_cache(mut self, no_cache: bool) -> Self {
+        self.no_cache = Some(no_cache);
+        self
+    }
+
+    /// Use `filter` instead of the exclusion flags and target
+    pub fn with_filter(mut self, filter: DependencyFilter) -> Self {
+        self.filter = Some(filter);
+        self
+    }
+
+    pub fn with_analyzer(mut self, analyzer: AnalyzerConfig) -> Self {
+        self.analyzer = Some(analyzer);
+        self
+    }
+
+    pub fn with_workspace_names(mut self, workspace_names: WorkspaceNames) -> Self {
+        self.workspace_names = Some(workspace_names);
+        self
+    }
+
+    pub fn with_metadata_providers(mut self, providers: Vec<Arc<dyn MetadataProvider>>) -> Self {
+        self.metadata_providers = Some(providers);
+        self
+    }
 }
 
 impl crate::common::ConfigBuilder for WorkspaceDepsConfigBuilder {
     type Config = WorkspaceDepsConfig;
 
     fn build(self) -> Result<Self::Config, crate::error::FerrisWheelError> {
+        let exclude_dev =
+            self.exclude_dev
+                .ok_or_else(|| crate::error::FerrisWheelError::ConfigurationError {
+                    message: "Missing required field: exclude_dev".to_string(),
+                })?;
+        let exclude_build = self.exclude_build.ok_or_else(|| {
+            crate::error::FerrisWheelError::ConfigurationError {
+                message: "Missing required field: exclude_build".to_string(),
+            }
+        })?;
+        let exclude_target = self.exclude_target.ok_or_else(|| {
+            crate::error::FerrisWheelError::ConfigurationError {
+                message: "Missing required field: exclude_target".to_string(),
+            }
+        })?;
+        let filter = self.filter.unwrap_or_else(|| {
+            DependencyFilter::new(exclude_dev, exclude_build, exclude_target)
+                .with_target(self.target)
+        });
+
         Ok(WorkspaceDepsConfig {
             workspace: self.workspace.ok_or_else(|| {
                 crate::error::FerrisWheelError::ConfigurationError {
@@ -119,21 +243,20 @@ impl crate::common::ConfigBuilder for WorkspaceDepsConfigBuilder {
                     message: "Missing required field: format".to_string(),
                 }
             })?,
-            exclude_dev: self.exclude_dev.ok_or_else(|| {
-                crate::error::FerrisWheelError::ConfigurationError {
-                    message: "Missing required field: exclude_dev".to_string(),
-                }
-            })?,
-            exclude_build: self.exclude_build.ok_or_else(|| {
-                crate::error::FerrisWheelError::ConfigurationError {
-                    message: "Missing required field: exclude_build".to_string(),
-                }
-            })?,
-            exclude_target: self.exclude_target.ok_or_else(|| {
-                crate::error::FerrisWheelError::ConfigurationError {
-                    message: "Missing required field: exclude_target".to_string(),
-                }
-            })?,
+            output: self.output,
+            exclude_dev,
+            exclude_build,
+            exclude_target,
+            target: self.target,
+            max_workspaces: self.max_workspaces,
+            exclude_paths: self.exclude_paths.unwrap_or_default(),
+            exclude_crates: self.exclude_crates.unwrap_or_default(),
+            follow_symlinks: self.follow_symlinks.unwrap_or_default(),
+            no_cache: self.no_cache.unwrap_or_default(),
+            filter,
+            analyzer: self.analyzer.unwrap_or_default(),
+            workspace_names: self.workspace_names.unwrap_or_default(),
+            metadata_providers: self.metadata_providers.unwrap_or_default(),
         })
     }
 }
@@ -2,7 +2,11 @@
 
 use std::path::PathBuf;
 
-use crate::cli::GraphFormat;
+use super::AnalyzerConfig;
+use crate::analyzer::{TargetPlatform, WorkspaceNames};
+use crate::cli::{EdgeLabels, GraphFormat};
+use crate::detector::SeverityModel;
+use crate::graph::{MermaidOptions, Palette, WorkspaceGrouping};
 
 #[derive(Debug, Clone)]
 pub struct GraphOptions {
@@ -14,6 +18,40 @@ pub struct GraphOptions {
     pub exclude_dev: bool,
     pub exclude_build: bool,
     pub exclude_target: bool,
+    /// Only analyze target-specific dependencies that apply to this target
+    pub target: Option<TargetPlatform>,
+    /// Abort discovery once more than this many workspaces are found
+    pub max_workspaces: Option<usize>,
+    /// Directories skipped during discovery
+    pub exclude_paths: Vec<glob::Pattern>,
+    /// Crates left out of the analysis
+    pub exclude_crates: Vec<glob::Pattern>,
+    /// Follow symlinked directories during discovery
+    pub follow_symlinks: bool,
+    /// Skip the analysis cache
+    pub no_cache: bool,
+    /// Dependency backend and feature selection for the analyzer
+    pub analyzer: AnalyzerConfig,
+    /// Workspace display names
+    pub workspace_names: WorkspaceNames,
+    pub mermaid: MermaidOptions,
+    /// Edge label verbosity (follows `show_crates` if unset)
+    pub edge_labels: Option<EdgeLabels>,
+    pub severity_model: SeverityModel,
+    pub nest_workspaces: bool,
+    /// How to group workspaces into subgraphs and clusters
+    pub grouping: Option<WorkspaceGrouping>,
+    /// Draw Mermaid workspaces as subgraphs of their crates
+    pub crate_subgraphs: bool,
+    /// Colors for Mermaid, DOT and D2 graphs
+    pub palette: Palette,
+    pub condensed: bool,
+    /// Drop workspaces and dependencies outside every cycle
+    pub cycles_only: bool,
+    /// Workspace whose neighborhood is drawn, and how many hops around it
+    pub focus: Option<(String, usize)>,
+    /// File holding a saved workspace graph to render
+    pub from_cache: Option<PathBuf>,
 }
 
 impl GraphOptions {
@@ -32,6 +70,25 @@ pub struct GraphOptionsBuilder {
     exclude_dev: Option<bool>,
     exclude_build: Option<bool>,
     exclude_target: Option<bool>,
+    target: Option<TargetPlatform>,
+    max_workspaces: Option<usize>,
+    exclude_paths: Option<Vec<glob::Pattern>>,
+    exclude_crates: Option<Vec<glob::Pattern>>,
+    follow_symlinks: Option<bool>,
+    no_cache: Option<bool>,
+    analyzer: Option<AnalyzerConfig>,
+    workspace_names: Option<WorkspaceNames>,
+    mermaid: Option<MermaidOptions>,
+    edge_labels: Option<EdgeLabels>,
+    severity_model: Option<SeverityModel>,
+    nest_workspaces: Option<bool>,
+    grouping: Option<WorkspaceGrouping>,
+    crate_subgraphs: Option<bool>,
+    palette: Option<Palette>,
+    condensed: Option<bool>,
+    cycles_only: Option<bool>,
+    focus: Option<(String, usize)>,
+    from_cache: Option<PathBuf>,
 }
 
 impl GraphOptionsBuilder {
@@ -45,6 +102,25 @@ impl GraphOptionsBuilder {
             exclude_dev: None,
             exclude_build: None,
             exclude_target: None,
+            target: None,
+            max_workspaces: None,
+            exclude_paths: None,
+            exclude_crates: None,
+            follow_symlinks: None,
+            no_cache: None,
+            analyzer: None,
+            workspace_names: None,
+            mermaid: None,
+            edge_labels: None,
+            severity_model: None,
+            nest_workspaces: None,
+            grouping: None,
+            crate_subgraphs: None,
+            palette: None,
+            condensed: None,
+            cycles_only: None,
+            focus: None,
+            from_cache: None,
         }
     }
 
@@ -87,6 +163,101 @@ impl GraphOptionsBuilder {
         self.exclude_target = Some(exclude_target);
         self
     }
+
+    pub fn with_target(mut self, target: Option<TargetPlatform>) -> Self {
+        self.target = target;
+        self
+    }
+
+    pub fn with_max_workspaces(mut self, max_workspaces: Option<usize>) -> Self {
+        self.max_workspaces = max_workspaces;
+        self
+    }
+
+    pub fn with_exclude_paths(mut self, exclude_paths: Vec<glob::Pattern>) -> Self {
+        self.exclude_paths = Some(exclude_paths);
+        self
+    }
+
+    pub fn with_exclude_crates(mut self, exclude_crates: Vec<glob::Pattern>) -> Self {
+        self.exclude_crates = Some(exclude_crates);
+        self
+    }
+
+    pub fn with_follow_symlinks(mut self, follow_symlinks: bool) -> Self {
+        self.follow_symlinks = Some(follow_symlinks);
+        self
+    }
+
+    pub fn with_no_cache(mut self, no_cache: bool) -> Self {
+        self.no_cache = Some(no_cache);
+        self
+    }
+
+    pub fn with_analyzer(mut self, analyzer: AnalyzerConfig) -> Self {
+        self.analyzer = Some(analyzer);
+        self
+    }
+
+    pub fn with_workspace_names(mut self, workspace_names: WorkspaceNames) -> Self {
+        self.workspace_names = Some(workspace_names);
+        self
+    }
+
+    pub fn with_mermaid_options(mut self, mermaid: MermaidOptions) -> Self {
+        self.mermaid = Some(mermaid);
+        self
+    }
+
+    pub fn with_edge_labels(mut self, edge_labels: Option<EdgeLabels>) -> Self {
+        self.edge_labels = edge_labels;
+        self
+    }
+
+    pub fn with_severity_model(mut self, severity_model: SeverityModel) -> Self {
+        self.severity_model = Some(severity_model);
+        self
+    }
+
+    pub fn with_nest_workspaces(mut self, nest_workspaces: bool) -> Self {
+        self.nest_workspaces = Some(nest_workspaces);
+        self
+    }
+
+    pub fn with_grouping(mut self, grouping: Option<WorkspaceGrouping>) -> Self {
+        self.grouping = grouping;
+        self
+    }
+
+    pub fn with_crate_subgraphs(mut self, crate_subgraphs: bool) -> Self {
+        self.crate_subgraphs = Some(crate_subgraphs);
+        self
+    }
+
+    pub fn with_palette(mut self, palette: Palette) -> Self {
+        self.palette = Some(palette);
+        self
+    }
+
+    pub fn with_condensed(mut self, condensed: bool) -> Self {
+        self.condensed = Some(condensed);
+        self
+    }
+
+    pub fn with_cycles_only(mut self, cycles_only: bool) -> Self {
+        self.cycles_only = Some(cycles_only);
+        self
+    }
+
+    pub fn with_focus(mut self, workspace: Option<String>, depth: usize) -> Self {
+        self.focus = workspace.map(|workspace| (workspace, depth));
+        self
+    }
+
+    pub fn with_from_cache(mut self, from_cache: Option<PathBuf>) -> Self {
+        self.from_cache = from_cache;
+        self
+    }
 }
 
 impl crate::common::ConfigBuilder for GraphOptionsBuilder {
@@ -134,6 +305,29 @@ impl crate::common::ConfigBuilder for GraphOptionsBuilder {
                     message: "Missing required field: exclude_target".to_string(),
                 }
             })?,
+            target: self.target,
+            max_workspaces: self.max_workspaces,
+            exclude_paths: self.exclude_paths.unwrap_or_default(),
+            exclude_crates: self.exclude_crates.unwrap_or_default(),
+            follow_symlinks: self.follow_symlinks.unwrap_or_default(),
+            no_cache: self.no_cache.unwrap_or_default(),
+            analyzer: self.analyzer.unwrap_or_default(),
+            workspace_names: self.workspace_names.unwrap_or_default(),
+            mermaid: self.mermaid.ok_or_else(|| {
+                crate::error::FerrisWheelError::ConfigurationError {
+                    message: "Missing required field: mermaid".to_string(),
+                }
+            })?,
+            edge_labels: self.edge_labels,
+            severity_model: self.severity_model.unwrap_or_default(),
+            nest_workspaces: self.nest_workspaces.unwrap_or(false),
+            grouping: self.grouping,
+            crate_subgraphs: self.crate_subgraphs.unwrap_or(false),
+            palette: self.palette.unwrap_or_default(),
+            condensed: self.condensed.unwrap_or(false),
+            cycles_only: self.cycles_only.unwrap_or(false),
+            focus: self.focus,
+            from_cache: self.from_cache,
         })
     }
 }
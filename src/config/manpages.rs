@@ -0,0 +1,43 @@
+//! Manpages command configuration
+
+use std::path::PathBuf;
+
+/// Configuration for the manpages command
+#[derive(Debug, Clone)]
+pub struct ManpagesConfig {
+    /// Directory to write every page into; the top-level page is printed to
+    /// stdout when unset
+    pub out_dir: Option<PathBuf>,
+}
+
+impl ManpagesConfig {
+    pub fn builder() -> ManpagesConfigBuilder {
+        ManpagesConfigBuilder::new()
+    }
+}
+
+#[derive(Default)]
+pub struct ManpagesConfigBuilder {
+    out_dir: Option<PathBuf>,
+}
+
+impl ManpagesConfigBuilder {
+    pub fn new() -> Self {
+        Self { out_dir: None }
+    }
+
+    pub fn with_out_dir(mut self, out_dir: Option<PathBuf>) -> Self {
+        self.out_dir = out_dir;
+        self
+    }
+}
+
+impl crate::common::ConfigBuilder for ManpagesConfigBuilder {
+    type Config = ManpagesConfig;
+
+    fn build(self) -> Result<Self::Config, crate::error::FerrisWheelError> {
+        Ok(ManpagesConfig {
+            out_dir: self.out_dir,
+        })
+    }
+}
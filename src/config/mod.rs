@@ -6,6 +6,9 @@
 //!
 //! ## Command Configurations
 //!
+//! - **AnalyzerConfig**: Dependency backend and feature selection, shared by
+//!   every command that analyzes workspaces
+//!
 //! - **CheckCyclesConfig**: Configuration for the `check` command to detect
 //!   cycles
 //! - **AnalyzeCrateConfig**: Configuration for the `analyze` command to examine
@@ -14,6 +17,38 @@
 //!   optimization
 //! - **GraphOptions**: Configuration for the `graph` command to visualize
 //!   dependencies
+//! - **ChangelogConfig**: Configuration for the `changelog` command comparing
+//!   two revisions
+//! - **DiffConfig**: Configuration for the `diff` command comparing the cycles
+//!   and dependencies of two directories or revisions
+//! - **WorkspacePathConfig**: Configuration for the `path` command tracing
+//!   dependency chains between workspaces
+//! - **WhyConfig**: Configuration for the `why` command explaining a workspace
+//!   dependency at crate granularity
+//! - **BetweenConfig**: Configuration for the `between` command listing the
+//!   crate dependencies between two workspaces
+//! - **ChainConfig**: Configuration for the `chain` command listing the
+//!   dependency chains between two crates
+//! - **CarouselConfig**: Configuration for the `carousel` command enforcing
+//!   declared architecture layers
+//! - **SweepConfig**: Configuration for the `sweep` command finding unused
+//!   internal dependencies
+//! - **FixConfig**: Configuration for the `fix` command suggesting manifest
+//!   edits that break cycles
+//! - **StatsConfig**: Configuration for the `stats` command computing
+//!   per-workspace dependency metrics
+//! - **CompletionsConfig**: Configuration for the `completions` command
+//!   printing shell completion scripts
+//! - **ManpagesConfig**: Configuration for the `manpages` command generating
+//!   man pages
+//! - **SchemaConfig**: Configuration for the `schema` command printing JSON
+//!   Schemas
+//! - **TuiConfig**: Configuration for the `tui` command exploring workspaces
+//!   and cycles interactively
+//! - **ProjectConfig**: Repository-level settings read from
+//!   `.ferris-wheel.toml`
+//! - **DefaultsConfig**: The `[defaults]` section of the project configuration,
+//!   filling in settings left unset on the command line
 //!
 //! ## Example
 //!
@@ -40,12 +75,46 @@
 
 pub mod affected;
 pub mod analyze;
+pub mod analyzer;
+pub mod between;
+pub mod carousel;
+pub mod chain;
+pub mod changelog;
 pub mod check;
+pub mod completions;
+pub mod defaults;
 pub mod deps;
+pub mod diff;
+pub mod fix;
 pub mod graph;
+pub mod manpages;
+pub mod path;
+pub mod project;
+pub mod schema;
+pub mod stats;
+pub mod sweep;
+pub mod tui;
+pub mod why;
 
-pub use affected::AffectedConfig;
+pub use affected::{AffectedConfig, ChangedSince};
 pub use analyze::AnalyzeCrateConfig;
+pub use analyzer::AnalyzerConfig;
+pub use between::BetweenConfig;
+pub use carousel::CarouselConfig;
+pub use chain::ChainConfig;
+pub use changelog::ChangelogConfig;
 pub use check::CheckCyclesConfig;
+pub use completions::CompletionsConfig;
+pub use defaults::{DefaultsConfig, apply_project_defaults};
 pub use deps::WorkspaceDepsConfig;
+pub use diff::{DiffConfig, DiffSide};
+pub use fix::FixConfig;
 pub use graph::GraphOptions;
+pub use manpages::ManpagesConfig;
+pub use path::WorkspacePathConfig;
+pub use project::ProjectConfig;
+pub use schema::SchemaConfig;
+pub use stats::StatsConfig;
+pub use sweep::SweepConfig;
+pub use tui::TuiConfig;
+pub use why::WhyConfig;
@@ -0,0 +1,438 @@
+//! Repository-level project configuration (`.ferris-wheel.toml`)
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use miette::{NamedSource, SourceSpan};
+use schemars::JsonSchema;
+use serde::Deserialize;
+
+use super::DefaultsConfig;
+use crate::analyzer::{
+    CommandMetadataProvider, ManifestMetadataProvider, MetadataProvider, WorkspaceNames,
+};
+use crate::constants::config::FILE_NAME;
+use crate::detector::{
+    AllowedCycle, CycleAllowlist, CycleSeverity, DependencyPolicy, EffortModel, GradeWeights,
+    LayerModel, SeverityModel,
+};
+use crate::error::FerrisWheelError;
+use crate::git;
+use crate::graph::ThemeConfig;
+use crate::reports::links::UrlTemplate;
+
+/// Settings read from the project's `.ferris-wheel.toml`
+///
+/// The file is discovered by walking up from the analyzed path, and its
+/// directory is treated as the repository root.
+#[derive(Debug, Clone, Default, Deserialize, JsonSchema)]
+#[serde(default, deny_unknown_fields)]
+pub struct ProjectConfig {
+    /// Settings every command starts from (`[defaults]`)
+    pub defaults: DefaultsConfig,
+    /// Code-browser link settings
+    pub links: LinksConfig,
+    /// Named rule profiles (`[profile.<name>]`)
+    pub profile: BTreeMap<String, ProfileConfig>,
+    /// Weights for the architecture grade (`[grade]`)
+    pub grade: GradeWeights,
+    /// Cycle severity scoring model (`[severity]`)
+    pub severity: SeverityModel,
+    /// Refactor effort model (`[effort]`)
+    pub effort: EffortModel,
+    /// Declared architecture layers (`[layers]`)
+    pub layers: LayerModel,
+    /// Crate metadata providers (`[metadata]`)
+    pub metadata: MetadataConfig,
+    /// Dependency rules enforced by `inspect` (`[rules]`)
+    pub rules: RulesConfig,
+    /// Accepted cycles (`[[allow]]`)
+    pub allow: Vec<AllowedCycle>,
+    /// Rule for cycles through each dependency type (`[policy]`)
+    pub policy: DependencyPolicy,
+    /// Workspace display names (`[names]`)
+    pub names: WorkspaceNames,
+    /// Graph color palette (`[theme]`)
+    pub theme: ThemeConfig,
+    #[serde(skip)]
+    root: Option<PathBuf>,
+}
+
+/// The `[links]` section of the project configuration
+#[derive(Debug, Clone, Default, Deserialize, JsonSchema)]
+#[serde(default, deny_unknown_fields)]
+pub struct LinksConfig {
+    /// URL template with `{rev}`, `{path}` and `{line}` placeholders, e.g.
+    /// `https://github.com/org/repo/blob/{rev}/{path}#L{line}`
+    pub url: Option<String>,
+    /// Revision substituted for `{rev}` (defaults to `$GITHUB_SHA`, then the
+    /// current git commit)
+    pub rev: Option<String>,
+}
+
+/// The `[rules]` section: dependency rules checked on every `inspect` run
+#[derive(Debug, Clone, Default, Deserialize, JsonSchema)]
+#[serde(default, deny_unknown_fields, rename_all = "kebab-case")]
+pub struct RulesConfig {
+    /// Fail on every dev-dependency that crosses a workspace boundary
+    #[serde(alias = "deny_cross_workspace_dev_deps")]
+    pub deny_cross_workspace_dev_deps: bool,
+}
+
+/// The `[metadata]` section: where crate metadata comes from
+#[derive(Debug, Clone, Default, Deserialize, JsonSchema)]
+#[serde(default, deny_unknown_fields)]
+pub struct MetadataConfig {
+    /// Read `[package.metadata.ferris-wheel]` from each crate manifest
+    pub manifest: bool,
+    /// Program and leading arguments run once per crate, printing
+    /// `key=value` lines; it runs from the configuration file's directory
+    pub command: Vec<String>,
+}
+
+/// A `[profile.<name>]` section overriding rules for one context
+///
+/// Rule values set in the active profile take precedence over the command
+/// line, so a single CI invocation can be relaxed on experimental branches
+/// while staying strict on `main`. Unset values fall back to the command
+/// line. The profile's `defaults` override the project's `[defaults]` and,
+/// like them, yield to the command line.
+#[derive(Debug, Clone, Default, Deserialize, JsonSchema)]
+#[serde(default, deny_unknown_fields, rename_all = "kebab-case")]
+pub struct ProfileConfig {
+    /// Branch name globs that select this profile automatically, e.g.
+    /// `experimental/*`
+    pub branches: Vec<String>,
+    /// Exit with an error code if cycles are found
+    pub error_on_cycles: Option<bool>,
+    /// Most failing cycles tolerated before exiting with an error code
+    pub max_cycles: Option<usize>,
+    /// Highest severity a failing cycle may have before exiting with an
+    /// error code
+    pub max_severity: Option<CycleSeverity>,
+    /// Exclude dev-dependencies from analysis
+    pub exclude_dev: Option<bool>,
+    /// Exclude build-dependencies from analysis
+    pub exclude_build: Option<bool>,
+    /// Exclude target-specific dependencies
+    pub exclude_target: Option<bool>,
+    /// Fail on every dev-dependency that crosses a workspace boundary
+    #[serde(alias = "deny_cross_workspace_dev_deps")]
+    pub deny_cross_workspace_dev_deps: Option<bool>,
+    /// Settings overriding `[defaults]` (`[profile.<name>.defaults]`)
+    pub defaults: DefaultsConfig,
+}
+
+impl ProjectConfig {
+    /// Find and load the nearest `.ferris-wheel.toml` at or above `start`
+    pub fn discover(start: &Path) -> Result<Option<Self>, FerrisWheelError> {
+        let start = start.canonicalize().unwrap_or_else(|_| start.to_path_buf());
+
+        for dir in start.ancestors() {
+            let candidate = dir.join(FILE_NAME);
+            if candidate.is_file() {
+                return Self::load(&candidate).map(Some);
+            }
+        }
+
+        Ok(None)
+    }
+
+    /// Discover the project configuration for the paths a command analyzes
+    ///
+    /// The search starts from the first path, falling back to the current
+    /// directory.
+    pub fn discover_for(paths: &[PathBuf]) -> Result<Option<Self>, FerrisWheelError> {
+        match paths.first() {
+            Some(path) => Self::discover(path),
+            None => Self::discover(&std::env::current_dir()?),
+        }
+    }
+
+    /// Load a project configuration from an explicit file path
+    pub fn load(path: &Path) -> Result<Self, FerrisWheelError> {
+        let content =
+            std::fs::read_to_string(path).map_err(|e| FerrisWheelError::FileReadError {
+                path: path.to_path_buf(),
+                source: e,
+            })?;
+
+        let mut config: Self = toml::from_str(&content).map_err(|e| {
+            let span = e
+                .span()
+                .map(|span| SourceSpan::new(span.start.into(), span.end - span.start));
+
+            FerrisWheelError::TomlParseError(Box::new(crate::error::TomlParseError {
+                file: path.display().to_string(),
+                source_code: NamedSource::new(path.display().to_string(), content.clone()),
+                span,
+                source: e,
+            }))
+        })?;
+        config.root = path.parent().map(Path::to_path_buf);
+
+        Ok(config)
+    }
+
+    /// Directory containing the configuration file
+    pub fn root(&self) -> Option<&Path> {
+        self.root.as_deref()
+    }
+
+    /// Resolve the profile to apply
+    ///
+    /// An explicitly requested profile must exist. Otherwise the current
+    /// branch is matched against each profile's `branches` globs.
+    pub fn select_profile(
+        &self,
+        requested: Option<&str>,
+    ) -> Result<Option<(&str, &ProfileConfig)>, FerrisWheelError> {
+        if requested.is_some() {
+            return self.select_profile_for_branch(requested, None);
+        }
+
+        let branch = self.root.as_deref().and_then(git::current_branch);
+        self.select_profile_for_branch(None, branch.as_deref())
+    }
+
+    fn select_profile_for_branch(
+        &self,
+        requested: Option<&str>,
+        branch: Option<&str>,
+    ) -> Result<Option<(&str, &ProfileConfig)>, FerrisWheelError> {
+        if let Some(name) = requested {
+            return match self.profile.get_key_value(name) {
+                Some((name, profile)) => Ok(Some((name.as_str(), profile))),
+                None => Err(FerrisWheelError::ConfigurationError {
+                    message: format!("Unknown profile '{name}'"),
+                }),
+            };
+        }
+
+        let Some(branch) = branch else {
+            return Ok(None);
+        };
+
+        let mut matching = Vec::new();
+        for (name, profile) in &self.profile {
+            for pattern in &profile.branches {
+                let pattern = glob::Pattern::new(pattern).map_err(|e| {
+                    FerrisWheelError::ConfigurationError {
+                        message: format!(
+                            "Invalid branch pattern '{pattern}' in profile '{name}': {e}"
+                        ),
+                    }
+                })?;
+                if pattern.matches(branch) {
+                    matching.push((name.as_str(), profile));
+                    break;
+                }
+            }
+        }
+
+        match matching.len() {
+            0 => Ok(None),
+            1 => Ok(matching.pop()),
+            _ => Err(FerrisWheelError::ConfigurationError {
+                message: format!(
+                    "Branch '{branch}' matches several profiles ({}); pass --profile to choose one",
+                    matching
+                        .iter()
+                        .map(|(name, _)| *name)
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                ),
+            }),
+        }
+    }
+
+    /// Metadata providers configured in the `[metadata]` section
+    ///
+    /// The command provider runs after the manifest provider, so its values
+    /// win on conflicting keys.
+    pub fn metadata_providers(&self) -> Vec<Arc<dyn MetadataProvider>> {
+        let mut providers: Vec<Arc<dyn MetadataProvider>> = Vec::new();
+        if self.metadata.manifest {
+            providers.push(Arc::new(ManifestMetadataProvider));
+        }
+        if !self.metadata.command.is_empty() {
+            providers.push(Arc::new(CommandMetadataProvider::new(
+                self.metadata.command.clone(),
+                self.root.clone().unwrap_or_else(|| PathBuf::from(".")),
+            )));
+        }
+        providers
+    }
+
+    /// The accepted cycles of the `[[allow]]` entries, as of today
+    pub fn allowlist(&self) -> Result<CycleAllowlist, FerrisWheelError> {
+        CycleAllowlist::new(self.allow.clone())
+    }
+
+    /// The `[policy]` rules, checked for rules that cannot apply
+    pub fn dependency_policy(&self) -> Result<DependencyPolicy, FerrisWheelError> {
+        self.policy.validated()
+    }
+
+    /// Code-browser URL template, if one is configured
+    pub fn url_template(&self) -> Option<UrlTemplate> {
+        let template = self.links.url.as_ref()?;
+        let root = self.root.clone()?;
+        let rev = self
+            .links
+            .rev
+            .clone()
+            .or_else(|| std::env::var("GITHUB_SHA").ok())
+            .or_else(|| current_git_rev(&root))
+            .unwrap_or_else(|| "HEAD".to_string());
+
+        Some(UrlTemplate::new(template.clone(), root, rev))
+    }
+}
+
+fn current_git_rev(root: &Path) -> Option<String> {
+    git::run_git(root, &["rev-parse", "HEAD"])
+        .ok()
+        .filter(|rev| !rev.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use tempfile::TempDir;
+
+    use super::*;
+
+    #[test]
+    fn test_discover_walks_up_to_config() {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path().canonicalize().unwrap();
+        std::fs::write(
+            root.join(FILE_NAME),
+            r#"
+[links]
+url = "https://github.com/org/repo/blob/{rev}/{path}#L{line}"
+rev = "main"
+"#,
+        )
+        .unwrap();
+        let nested = root.join("a/b");
+        std::fs::create_dir_all(&nested).unwrap();
+
+        let config = ProjectConfig::discover(&nested).unwrap().unwrap();
+        assert_eq!(config.root(), Some(root.as_path()));
+
+        let template = config.url_template().unwrap();
+        assert_eq!(
+            template.render(&root.join("a/Cargo.toml"), Some(3)),
+            Some("https://github.com/org/repo/blob/main/a/Cargo.toml#L3".to_string())
+        );
+    }
+
+    #[test]
+    fn test_missing_links_section_has_no_template() {
+        let temp = TempDir::new().unwrap();
+        std::fs::write(temp.path().join(FILE_NAME), "").unwrap();
+
+        let config = ProjectConfig::discover(temp.path()).unwrap().unwrap();
+        assert!(config.url_template().is_none());
+    }
+
+    const PROFILES: &str = r#"
+[profile.experimental]
+branches = ["experimental/*"]
+error-on-cycles = false
+exclude-dev = true
+
+[profile.strict]
+branches = ["main", "release/*"]
+error-on-cycles = true
+"#;
+
+    #[test]
+    fn test_profile_selected_by_branch() {
+        let config: ProjectConfig = toml::from_str(PROFILES).unwrap();
+
+        let (name, profile) = config
+            .select_profile_for_branch(None, Some("experimental/new-layout"))
+            .unwrap()
+            .unwrap();
+        assert_eq!(name, "experimental");
+        assert_eq!(profile.error_on_cycles, Some(false));
+        assert_eq!(profile.exclude_dev, Some(true));
+
+        let (name, _) = config
+            .select_profile_for_branch(None, Some("release/1.0"))
+            .unwrap()
+            .unwrap();
+        assert_eq!(name, "strict");
+
+        assert!(
+            config
+                .select_profile_for_branch(None, Some("feature/x"))
+                .unwrap()
+                .is_none()
+        );
+        assert!(
+            config
+                .select_profile_for_branch(None, None)
+                .unwrap()
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn test_requested_profile_wins_over_branch() {
+        let config: ProjectConfig = toml::from_str(PROFILES).unwrap();
+
+        let (name, _) = config
+            .select_profile_for_branch(Some("strict"), Some("experimental/x"))
+            .unwrap()
+            .unwrap();
+        assert_eq!(name, "strict");
+
+        assert!(matches!(
+            config.select_profile_for_branch(Some("missing"), None),
+            Err(FerrisWheelError::ConfigurationError { .. })
+        ));
+    }
+
+    #[test]
+    fn test_ambiguous_branch_is_an_error() {
+        let config: ProjectConfig = toml::from_str(
+            r#"
+[profile.a]
+branches = ["main"]
+
+[profile.b]
+branches = ["ma*"]
+"#,
+        )
+        .unwrap();
+
+        assert!(matches!(
+            config.select_profile_for_branch(None, Some("main")),
+            Err(FerrisWheelError::ConfigurationError { .. })
+        ));
+    }
+
+    #[test]
+    fn test_grade_weights_override_defaults() {
+        let config: ProjectConfig = toml::from_str("[grade]\ncycle-size = 1.5\n").unwrap();
+
+        assert_eq!(config.grade.cycle_size, 1.5);
+        assert_eq!(config.grade.cycle, GradeWeights::default().cycle);
+    }
+
+    #[test]
+    fn test_unknown_keys_are_rejected() {
+        let temp = TempDir::new().unwrap();
+        let path = temp.path().join(FILE_NAME);
+        std::fs::write(&path, "[links]\nurl_template = \"x\"\n").unwrap();
+
+        assert!(matches!(
+            ProjectConfig::load(&path),
+            Err(FerrisWheelError::TomlParseError(_))
+        ));
+    }
+}
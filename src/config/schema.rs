@@ -0,0 +1,46 @@
+//! Schema command configuration
+
+use crate::cli::SchemaDocument;
+
+/// Configuration for the schema command
+#[derive(Debug, Clone)]
+pub struct SchemaConfig {
+    /// Document to print the JSON Schema of
+    pub document: SchemaDocument,
+}
+
+impl SchemaConfig {
+    pub fn builder() -> SchemaConfigBuilder {
+        SchemaConfigBuilder::new()
+    }
+}
+
+#[derive(Default)]
+pub struct SchemaConfigBuilder {
+    document: Option<SchemaDocument>,
+}
+
+impl SchemaConfigBuilder {
+    pub fn new() -> Self {
+        Self { document: None }
+    }
+
+    pub fn with_document(mut self, document: SchemaDocument) -> Self {
+        self.document = Some(document);
+        self
+    }
+}
+
+impl crate::common::ConfigBuilder for SchemaConfigBuilder {
+    type Config = SchemaConfig;
+
+    fn build(self) -> Result<Self::Config, crate::error::FerrisWheelError> {
+        Ok(SchemaConfig {
+            document: self.document.ok_or_else(|| {
+                crate::error::FerrisWheelError::ConfigurationError {
+                    message: "Missing required field: document".to_string(),
+                }
+            })?,
+        })
+    }
+}
@@ -32,6 +32,43 @@ pub mod output {
     pub const DEFAULT_FORMAT: &str = "human";
 }
 
+/// Cycle detection configuration
+pub mod detector {
+    /// Default cap on elementary cycles enumerated per strongly connected
+    /// component
+    pub const DEFAULT_MAX_ELEMENTARY_CYCLES: usize = 100;
+}
+
+/// Ripples command configuration
+pub mod ripples {
+    /// Maximum length of one line of `--format cargo-args` output, kept well
+    /// below the 32 KiB command line limit on Windows
+    pub const MAX_CARGO_ARGS_LINE_LENGTH: usize = 30_000;
+}
+
+/// Process exit codes
+pub mod exit {
+    /// Analysis stopped at `--timeout` and reported partial results (the
+    /// same code as coreutils `timeout`)
+    pub const TIMEOUT: i32 = 124;
+}
+
+/// Environment variable configuration
+pub mod env {
+    /// Prefix of the environment variables backing command-line flags
+    pub const PREFIX: &str = "CARGO_FERRIS_WHEEL_";
+
+    /// Shorter prefix accepted for the same variables, e.g.
+    /// `FERRIS_WHEEL_FORMAT`
+    pub const SHORT_PREFIX: &str = "FERRIS_WHEEL_";
+}
+
+/// Project configuration file settings
+pub mod config {
+    /// Name of the repository-level configuration file
+    pub const FILE_NAME: &str = ".ferris-wheel.toml";
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -46,4 +83,14 @@ mod tests {
     fn test_output_constants() {
         assert_eq!(output::DEFAULT_FORMAT, "human");
     }
+
+    #[test]
+    fn test_detector_constants() {
+        assert_eq!(detector::DEFAULT_MAX_ELEMENTARY_CYCLES, 100);
+    }
+
+    #[test]
+    fn test_config_constants() {
+        assert_eq!(config::FILE_NAME, ".ferris-wheel.toml");
+    }
 }
@@ -1,33 +1,149 @@
 //! Dependency filtering functionality
+//!
+//! A [`DependencyFilter`] decides which dependencies become edges of a
+//! dependency graph. Filters are built by chaining combinators, and the same
+//! filter can be handed to
+//! [`DependencyGraphBuilder`](crate::graph::DependencyGraphBuilder),
+//! to the ripples analysis and to the lineup, or applied to a graph that was
+//! already built:
+//!
+//! ```
+//! use cargo_ferris_wheel::dependency_filter::DependencyFilter;
+//! use cargo_ferris_wheel::graph::DependencyType;
+//!
+//! let filter = DependencyFilter::all()
+//!     .exclude_kind(DependencyType::Dev)
+//!     .with_target("x86_64-unknown-linux-gnu".parse().ok())
+//!     .exclude_workspace_pair("app", "legacy")
+//!     .with_predicate(|edge| !edge.to_crate().ends_with("-test-utils"));
+//!
+//! assert!(!filter.include_dev());
+//! assert!(filter.includes_workspace_pair("app", "core"));
+//! assert!(!filter.includes_workspace_pair("app", "legacy"));
+//! ```
 
-use crate::analyzer::Dependency;
+use std::collections::BTreeSet;
+use std::fmt;
+use std::sync::Arc;
 
-/// Encapsulates dependency filtering logic based on dependency types
-#[derive(Debug, Clone, Copy, Default)]
+use petgraph::graph::DiGraph;
+
+use crate::analyzer::{Dependency, TargetPlatform};
+use crate::graph::{DependencyEdge, DependencyType, WorkspaceNode};
+
+type EdgePredicate = Arc<dyn Fn(&DependencyEdge) -> bool + Send + Sync>;
+
+/// Composable filter over dependency kinds, target platforms, workspace
+/// pairs and arbitrary edge predicates
+///
+/// The default filter includes every dependency.
+#[derive(Clone, Default)]
 pub struct DependencyFilter {
-    exclude_dev: bool,
-    exclude_build: bool,
+    excluded_kinds: BTreeSet<DependencyType>,
     exclude_target: bool,
+    target: Option<TargetPlatform>,
+    excluded_pairs: Vec<(String, String)>,
+    included_pairs: Vec<(String, String)>,
+    predicates: Vec<EdgePredicate>,
 }
 
 impl DependencyFilter {
     /// Create a new dependency filter
     pub fn new(exclude_dev: bool, exclude_build: bool, exclude_target: bool) -> Self {
-        Self {
-            exclude_dev,
-            exclude_build,
-            exclude_target,
+        let mut filter = Self::all();
+        if exclude_dev {
+            filter = filter.exclude_kind(DependencyType::Dev);
+        }
+        if exclude_build {
+            filter = filter.exclude_kind(DependencyType::Build);
         }
+        if exclude_target {
+            filter = filter.exclude_target_specific();
+        }
+        filter
+    }
+
+    /// A filter including every dependency
+    pub fn all() -> Self {
+        Self::default()
+    }
+
+    /// Drop dependencies of the given kind
+    pub fn exclude_kind(mut self, kind: DependencyType) -> Self {
+        self.excluded_kinds.insert(kind);
+        self
+    }
+
+    /// Include dependencies of the given kind again after excluding them
+    pub fn include_kind(mut self, kind: DependencyType) -> Self {
+        self.excluded_kinds.remove(&kind);
+        self
+    }
+
+    /// Drop every target-specific dependency
+    pub fn exclude_target_specific(mut self) -> Self {
+        self.exclude_target = true;
+        self
+    }
+
+    /// Only include target-specific dependencies that apply to `target`
+    ///
+    /// With no target, every target-specific dependency is included unless
+    /// target-specific dependencies are excluded altogether.
+    pub fn with_target(mut self, target: Option<TargetPlatform>) -> Self {
+        self.target = target;
+        self
+    }
+
+    /// Drop dependencies from workspace `from` on workspace `to`
+    pub fn exclude_workspace_pair(
+        mut self,
+        from: impl Into<String>,
+        to: impl Into<String>,
+    ) -> Self {
+        self.excluded_pairs.push((from.into(), to.into()));
+        self
+    }
+
+    /// Keep dependencies from workspace `from` on workspace `to`
+    ///
+    /// Once any pair is included, dependencies between workspaces that were
+    /// not included are dropped. Dependencies within one workspace are never
+    /// affected by workspace pairs.
+    pub fn include_workspace_pair(
+        mut self,
+        from: impl Into<String>,
+        to: impl Into<String>,
+    ) -> Self {
+        self.included_pairs.push((from.into(), to.into()));
+        self
+    }
+
+    /// Only include dependencies accepted by `predicate`
+    ///
+    /// Predicates see the edge a dependency would become and are combined
+    /// with every other rule of the filter, including other predicates.
+    pub fn with_predicate(
+        mut self,
+        predicate: impl Fn(&DependencyEdge) -> bool + Send + Sync + 'static,
+    ) -> Self {
+        self.predicates.push(Arc::new(predicate));
+        self
+    }
+
+    /// Check if dependencies of `kind` should be included
+    pub fn includes_kind(&self, kind: &DependencyType) -> bool {
+        !self.excluded_kinds.contains(kind)
     }
 
     /// Check if dev dependencies should be included
     pub fn include_dev(&self) -> bool {
-        !self.exclude_dev
+        self.includes_kind(&DependencyType::Dev)
     }
 
     /// Check if build dependencies should be included
     pub fn include_build(&self) -> bool {
-        !self.exclude_build
+        self.includes_kind(&DependencyType::Build)
     }
 
     /// Check if target-specific dependencies should be included
@@ -35,22 +151,186 @@ impl DependencyFilter {
         !self.exclude_target
     }
 
+    /// Check if a dependency declared for `target` should be included
+    pub fn includes_target(&self, target: Option<&str>) -> bool {
+        match target {
+            None => true,
+            Some(_) if self.exclude_target => false,
+            Some(spec) => self.target.is_none_or(|platform| platform.matches(spec)),
+        }
+    }
+
+    /// Check if dependencies from workspace `from` on workspace `to` should be
+    /// included
+    pub fn includes_workspace_pair(&self, from: &str, to: &str) -> bool {
+        if from == to {
+            return true;
+        }
+        let is_pair = |(pair_from, pair_to): &(String, String)| pair_from == from && pair_to == to;
+        !self.excluded_pairs.iter().any(is_pair)
+            && (self.included_pairs.is_empty() || self.included_pairs.iter().any(is_pair))
+    }
+
     /// Check if a dependency should be included based on its target field
     ///
     /// This method only filters based on the dependency's target field.
     /// Filtering by dependency type (dev, build) happens at a higher level
     /// where dependencies are already categorized into separate collections.
     pub fn should_include_dependency(&self, dep: &Dependency) -> bool {
-        // If it has a target and we're excluding targets, skip it
-        if dep.target().is_some() && self.exclude_target {
-            return false;
-        }
-        true
+        self.includes_target(dep.target())
+    }
+
+    /// Check if an edge passes this filter, ignoring workspace pairs
+    pub fn includes_edge(&self, edge: &DependencyEdge) -> bool {
+        self.includes_kind(edge.dependency_type())
+            && self.includes_target(edge.target())
+            && self.predicates.iter().all(|predicate| predicate(edge))
+    }
+
+    /// Check if an edge from workspace `from` to workspace `to` passes this
+    /// filter
+    pub fn includes(&self, from: &str, to: &str, edge: &DependencyEdge) -> bool {
+        self.includes_workspace_pair(from, to) && self.includes_edge(edge)
+    }
+
+    /// Copy of `graph` without the edges this filter excludes
+    ///
+    /// Lets one unfiltered graph serve several filters without re-analyzing
+    /// the workspaces. Nodes of crate graphs, named `workspace/crate`, are
+    /// matched against workspace pairs by their workspace part.
+    pub fn apply(
+        &self,
+        graph: &DiGraph<WorkspaceNode, DependencyEdge>,
+    ) -> DiGraph<WorkspaceNode, DependencyEdge> {
+        graph.filter_map(
+            |_, node| Some(node.clone()),
+            |index, edge| {
+                let (from, to) = graph.edge_endpoints(index)?;
+                self.includes(
+                    graph[from].workspace_name(),
+                    graph[to].workspace_name(),
+                    edge,
+                )
+                .then(|| edge.clone())
+            },
+        )
+    }
+}
+
+impl fmt::Debug for DependencyFilter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DependencyFilter")
+            .field("excluded_kinds", &self.excluded_kinds)
+            .field("exclude_target", &self.exclude_target)
+            .field("target", &self.target)
+            .field("excluded_pairs", &self.excluded_pairs)
+            .field("included_pairs", &self.included_pairs)
+            .field("predicates", &self.predicates.len())
+            .finish()
     }
 }
 
 impl From<&crate::common::CommonArgs> for DependencyFilter {
     fn from(args: &crate::common::CommonArgs) -> Self {
         Self::new(args.exclude_dev, args.exclude_build, args.exclude_target)
+            .with_target(args.target)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::ConfigBuilder;
+
+    fn edge(target: Option<&str>) -> DependencyEdge {
+        DependencyEdge::builder()
+            .with_from_crate("app")
+            .with_to_crate("platform")
+            .with_dependency_type(DependencyType::Normal)
+            .with_target(target)
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_target_platform_evaluates_target_specific_edges() {
+        let unix = edge(Some("cfg(unix)"));
+        let windows = edge(Some("cfg(windows)"));
+        let untargeted = edge(None);
+
+        let linux = DependencyFilter::new(false, false, false)
+            .with_target("x86_64-unknown-linux-gnu".parse().ok());
+        assert!(linux.includes_edge(&unix));
+        assert!(!linux.includes_edge(&windows));
+        assert!(linux.includes_edge(&untargeted));
+
+        let every_target = DependencyFilter::new(false, false, false);
+        assert!(every_target.includes_edge(&unix));
+        assert!(every_target.includes_edge(&windows));
+
+        let no_targets = DependencyFilter::new(false, false, true);
+        assert!(!no_targets.includes_edge(&unix));
+        assert!(no_targets.includes_edge(&untargeted));
+    }
+
+    #[test]
+    fn test_combinators_compose() {
+        let dev = DependencyEdge::builder()
+            .with_from_crate("app")
+            .with_to_crate("fixtures")
+            .with_dependency_type(DependencyType::Dev)
+            .build()
+            .unwrap();
+        let helpers = DependencyEdge::builder()
+            .with_from_crate("app")
+            .with_to_crate("test-helpers")
+            .with_dependency_type(DependencyType::Normal)
+            .build()
+            .unwrap();
+        let normal = edge(None);
+
+        let filter = DependencyFilter::all()
+            .exclude_kind(DependencyType::Dev)
+            .exclude_workspace_pair("app", "legacy")
+            .with_predicate(|edge| !edge.to_crate().ends_with("-helpers"));
+
+        assert!(!filter.includes_edge(&dev));
+        assert!(!filter.includes_edge(&helpers));
+        assert!(filter.includes("app", "core", &normal));
+        assert!(!filter.includes("app", "legacy", &normal));
+        assert!(filter.include_kind(DependencyType::Dev).includes_edge(&dev));
+
+        let only_core = DependencyFilter::all().include_workspace_pair("app", "core");
+        assert!(only_core.includes_workspace_pair("app", "core"));
+        assert!(!only_core.includes_workspace_pair("app", "legacy"));
+        assert!(only_core.includes_workspace_pair("legacy", "legacy"));
+    }
+
+    #[test]
+    fn test_apply_matches_crate_nodes_by_workspace() {
+        let node = |name: &str, krate: &str| {
+            WorkspaceNode::builder()
+                .with_name(name.to_string())
+                .with_crates(vec![krate.to_string()])
+                .build()
+                .unwrap()
+        };
+        let mut graph = DiGraph::new();
+        let app = graph.add_node(node("app/app-lib", "app-lib"));
+        let legacy = graph.add_node(node("legacy/old-lib", "old-lib"));
+        let core = graph.add_node(node("core", "core-lib"));
+        graph.add_edge(app, legacy, edge(None));
+        graph.add_edge(app, core, edge(None));
+
+        let filtered = DependencyFilter::all()
+            .exclude_workspace_pair("app", "legacy")
+            .exclude_workspace_pair("app", "core")
+            .apply(&graph);
+        assert_eq!(filtered.edge_count(), 0);
+
+        let filtered = DependencyFilter::all()
+            .exclude_workspace_pair("app", "legacy")
+            .apply(&graph);
+        assert_eq!(filtered.edge_count(), 1);
     }
 }
@@ -0,0 +1,235 @@
+//! Accepted cycles
+//!
+//! The `[[allow]]` entries of `.ferris-wheel.toml` declare cycles that are
+//! known and accepted. An allowed cycle is still reported, marked as allowed,
+//! but never fails the run. Each entry names the cycle by its workspaces or
+//! by its ID and can carry an `expires` date, after which it stops applying
+//! so a suppression can't outlive the plan to fix the cycle.
+
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use schemars::JsonSchema;
+use serde::Deserialize;
+use toml::value::{Date, Datetime};
+
+use super::WorkspaceCycle;
+use crate::error::FerrisWheelError;
+
+/// One accepted cycle (`[[allow]]`)
+#[derive(Debug, Clone, Default, Deserialize, JsonSchema)]
+#[serde(default, deny_unknown_fields)]
+pub struct AllowedCycle {
+    /// Workspaces of the cycle, in any order
+    pub workspaces: Vec<String>,
+    /// Stable cycle ID, see [`WorkspaceCycle::id`]
+    pub id: Option<String>,
+    /// Why the cycle is accepted, for readers of the file
+    pub reason: Option<String>,
+    /// Last day the entry applies, as a TOML date like `2026-12-31`
+    #[schemars(with = "Option<String>")]
+    pub expires: Option<Datetime>,
+}
+
+impl AllowedCycle {
+    /// Whether the entry names `cycle`
+    pub fn matches(&self, cycle: &WorkspaceCycle) -> bool {
+        if let Some(id) = &self.id {
+            return *id == cycle.id();
+        }
+        let mut allowed: Vec<&str> = self.workspaces.iter().map(String::as_str).collect();
+        allowed.sort_unstable();
+        allowed.dedup();
+        let mut names: Vec<&str> = cycle.workspace_names().iter().map(AsRef::as_ref).collect();
+        names.sort_unstable();
+        names.dedup();
+        allowed == names
+    }
+
+    /// Whether the entry's expiry date is before `today`
+    pub fn is_expired(&self, today: Date) -> bool {
+        self.expiry_date().is_some_and(|expires| expires < today)
+    }
+
+    /// The day the entry expires after, if it has one
+    pub fn expiry_date(&self) -> Option<Date> {
+        self.expires.and_then(|expires| expires.date)
+    }
+
+    /// A short description of the entry for messages
+    pub fn describe(&self) -> String {
+        match &self.id {
+            Some(id) => format!("cycle {id}"),
+            None => format!("cycle {}", self.workspaces.join(" ↔ ")),
+        }
+    }
+}
+
+/// The accepted cycles of a project, as of a given day
+#[derive(Debug, Clone, Default)]
+pub struct CycleAllowlist {
+    entries: Vec<AllowedCycle>,
+    today: Option<Date>,
+}
+
+impl CycleAllowlist {
+    /// Check the `entries` of a project, evaluating expiry dates against
+    /// today's date (UTC)
+    pub fn new(entries: Vec<AllowedCycle>) -> Result<Self, FerrisWheelError> {
+        Self::on(entries, today())
+    }
+
+    /// Check the `entries` of a project, evaluating expiry dates against
+    /// `today`
+    pub fn on(entries: Vec<AllowedCycle>, today: Date) -> Result<Self, FerrisWheelError> {
+        for entry in &entries {
+            let invalid = |problem: &str| FerrisWheelError::ConfigurationError {
+                message: format!(
+                    "Invalid [[allow]] entry for {}: {problem}",
+                    entry.describe()
+                ),
+            };
+            match (&entry.id, entry.workspaces.len()) {
+                (Some(_), 0) => {}
+                (Some(_), _) => return Err(invalid("set either `id` or `workspaces`, not both")),
+                (None, 0) => return Err(invalid("set `id` or `workspaces`")),
+                (None, 1) => return Err(invalid("a cycle spans at least two workspaces")),
+                (None, _) => {}
+            }
+            if entry.expires.is_some() && entry.expiry_date().is_none() {
+                return Err(invalid("`expires` must be a date such as 2026-12-31"));
+            }
+        }
+
+        Ok(Self {
+            entries,
+            today: Some(today),
+        })
+    }
+
+    /// Whether an entry that has not expired allows `cycle`
+    pub fn allows(&self, cycle: &WorkspaceCycle) -> bool {
+        self.active().any(|entry| entry.matches(cycle))
+    }
+
+    /// Entries whose expiry date has passed
+    pub fn expired(&self) -> impl Iterator<Item = &AllowedCycle> {
+        self.entries
+            .iter()
+            .filter(|entry| self.today.is_some_and(|today| entry.is_expired(today)))
+    }
+
+    fn active(&self) -> impl Iterator<Item = &AllowedCycle> {
+        self.entries
+            .iter()
+            .filter(|entry| self.today.is_none_or(|today| !entry.is_expired(today)))
+    }
+}
+
+/// Today's date in UTC
+fn today() -> Date {
+    let days = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_secs() / 86_400)
+        .unwrap_or_default();
+    civil_from_days(days)
+}
+
+/// The calendar date `days` days after 1970-01-01, after Howard Hinnant's
+/// `civil_from_days`
+fn civil_from_days(days: u64) -> Date {
+    let z = days + 719_468;
+    let era = z / 146_097;
+    let day_of_era = z % 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = year_of_era + era * 400 + u64::from(month <= 2);
+
+    Date {
+        year: year as u16,
+        month: month as u8,
+        day: day as u8,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cycle(from: &str, to: &str) -> WorkspaceCycle {
+        WorkspaceCycle::builder()
+            .with_workspace_names(vec![from.to_string(), to.to_string()])
+            .add_edge()
+            .from_workspace(from)
+            .to_workspace(to)
+            .from_crate(&format!("{from}-core"))
+            .to_crate(&format!("{to}-core"))
+            .dependency_type("Normal")
+            .add_edge()
+            .unwrap()
+            .from_workspace(to)
+            .to_workspace(from)
+            .from_crate(&format!("{to}-core"))
+            .to_crate(&format!("{from}-core"))
+            .dependency_type("Normal")
+            .build()
+            .unwrap()
+    }
+
+    fn date(year: u16, month: u8, day: u8) -> Date {
+        Date { year, month, day }
+    }
+
+    #[test]
+    fn test_allowlist_matches_workspaces_or_id_until_expiry() {
+        #[derive(Deserialize)]
+        struct File {
+            allow: Vec<AllowedCycle>,
+        }
+        let File { allow: entries } = toml::from_str(&format!(
+            r#"
+[[allow]]
+workspaces = ["core", "apps"]
+reason = "Being split up"
+expires = 2026-06-30
+
+[[allow]]
+id = "{}"
+"#,
+            cycle("api", "db").id()
+        ))
+        .unwrap();
+
+        let allowlist = CycleAllowlist::on(entries.clone(), date(2026, 6, 30)).unwrap();
+        assert!(allowlist.allows(&cycle("apps", "core")));
+        assert!(allowlist.allows(&cycle("db", "api")));
+        assert!(!allowlist.allows(&cycle("apps", "db")));
+        assert_eq!(allowlist.expired().count(), 0);
+
+        let allowlist = CycleAllowlist::on(entries, date(2026, 7, 1)).unwrap();
+        assert!(!allowlist.allows(&cycle("apps", "core")));
+        assert!(allowlist.allows(&cycle("db", "api")));
+        assert_eq!(allowlist.expired().count(), 1);
+    }
+
+    #[test]
+    fn test_allowlist_rejects_ambiguous_entries() {
+        let both = AllowedCycle {
+            workspaces: vec!["a".to_string(), "b".to_string()],
+            id: Some("0123456789abcdef".to_string()),
+            ..AllowedCycle::default()
+        };
+        assert!(CycleAllowlist::on(vec![both], date(2026, 1, 1)).is_err());
+        assert!(CycleAllowlist::on(vec![AllowedCycle::default()], date(2026, 1, 1)).is_err());
+    }
+
+    #[test]
+    fn test_civil_from_days() {
+        assert_eq!(civil_from_days(0), date(1970, 1, 1));
+        assert_eq!(civil_from_days(19_782), date(2024, 2, 29));
+        assert_eq!(civil_from_days(20_742), date(2026, 10, 16));
+    }
+}
@@ -0,0 +1,137 @@
+//! Workspace boundary rules
+//!
+//! Unlike cycles, these rules judge single dependencies. With
+//! `deny-cross-workspace-dev-deps` enabled in the `[rules]` section of
+//! `.ferris-wheel.toml`, a crate's dev-dependencies must stay inside its own
+//! workspace: every dev-dependency on a crate in another workspace is
+//! reported, whether or not it closes a cycle.
+//!
+//! ```toml
+//! [rules]
+//! deny-cross-workspace-dev-deps = true
+//! ```
+
+use petgraph::graph::DiGraph;
+use petgraph::visit::EdgeRef;
+
+use super::CycleEdge;
+use crate::error::FerrisWheelError;
+use crate::graph::{DependencyEdge, DependencyType, WorkspaceNode};
+
+/// Every dev-dependency crossing a workspace boundary in a workspace graph
+///
+/// Edges are sorted by workspace and then crate names.
+pub fn cross_workspace_dev_dependencies(
+    graph: &DiGraph<WorkspaceNode, DependencyEdge>,
+) -> Result<Vec<CycleEdge>, FerrisWheelError> {
+    let mut edges = graph
+        .edge_references()
+        .filter(|edge| edge.source() != edge.target())
+        .filter(|edge| *edge.weight().dependency_type() == DependencyType::Dev)
+        .map(|edge| {
+            CycleEdge::builder()
+                .from_workspace(graph[edge.source()].name())
+                .to_workspace(graph[edge.target()].name())
+                .from_crate(edge.weight().from_crate())
+                .to_crate(edge.weight().to_crate())
+                .dependency_type(edge.weight().dependency_type().as_str())
+                .build()
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    edges.sort_by(|a, b| {
+        (
+            a.from_workspace(),
+            a.to_workspace(),
+            a.from_crate(),
+            a.to_crate(),
+        )
+            .cmp(&(
+                b.from_workspace(),
+                b.to_workspace(),
+                b.from_crate(),
+                b.to_crate(),
+            ))
+    });
+    edges.dedup_by(|a, b| {
+        (
+            a.from_workspace(),
+            a.to_workspace(),
+            a.from_crate(),
+            a.to_crate(),
+        ) == (
+            b.from_workspace(),
+            b.to_workspace(),
+            b.from_crate(),
+            b.to_crate(),
+        )
+    });
+
+    Ok(edges)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::ConfigBuilder;
+
+    fn node(name: &str) -> WorkspaceNode {
+        WorkspaceNode::builder()
+            .with_name(name.to_string())
+            .with_crates(vec![format!("{name}-lib")])
+            .build()
+            .unwrap()
+    }
+
+    fn edge(from: &str, to: &str, dependency_type: DependencyType) -> DependencyEdge {
+        DependencyEdge::builder()
+            .with_from_crate(from)
+            .with_to_crate(to)
+            .with_dependency_type(dependency_type)
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_only_cross_workspace_dev_dependencies_are_reported() {
+        let mut graph = DiGraph::new();
+        let app = graph.add_node(node("app"));
+        let core = graph.add_node(node("core"));
+        let testing = graph.add_node(node("testing"));
+
+        graph.add_edge(
+            app,
+            core,
+            edge("app-lib", "core-lib", DependencyType::Normal),
+        );
+        graph.add_edge(
+            core,
+            testing,
+            edge("core-lib", "testing-lib", DependencyType::Dev),
+        );
+        graph.add_edge(
+            app,
+            testing,
+            edge("app-lib", "testing-lib", DependencyType::Dev),
+        );
+        graph.add_edge(app, app, edge("app-lib", "app-macros", DependencyType::Dev));
+        graph.add_edge(
+            core,
+            app,
+            edge("core-lib", "app-lib", DependencyType::Build),
+        );
+
+        let edges = cross_workspace_dev_dependencies(&graph).unwrap();
+        let pairs: Vec<_> = edges
+            .iter()
+            .map(|e| (e.from_crate(), e.to_crate(), e.dependency_type()))
+            .collect();
+        assert_eq!(
+            pairs,
+            vec![
+                ("app-lib", "testing-lib", "Dev"),
+                ("core-lib", "testing-lib", "Dev")
+            ]
+        );
+    }
+}
@@ -0,0 +1,111 @@
+//! Failure budget for gradual cleanup
+//!
+//! A repository with many cycles can't turn on `--error-on-cycles` overnight.
+//! A budget lets the run pass while the failing cycles stay within limits:
+//! `--max-cycles` caps how many there may be and `--max-severity` the worst
+//! severity any of them may have. Lowering the limits as cycles get fixed
+//! keeps the repository from sliding back.
+
+use super::{CycleDetector, CycleSeverity};
+use crate::utils::string::pluralize;
+
+/// Limits on the failing cycles a run tolerates
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct FailureBudget {
+    /// Most failing cycles allowed
+    pub max_cycles: Option<usize>,
+    /// Highest severity a failing cycle may have
+    pub max_severity: Option<CycleSeverity>,
+}
+
+impl FailureBudget {
+    /// Whether any limit is set, in which case the budget decides the exit
+    /// code
+    pub fn is_set(&self) -> bool {
+        self.max_cycles.is_some() || self.max_severity.is_some()
+    }
+
+    /// How the failing cycles of `detector` exceed the budget, one message per
+    /// limit; empty when they fit
+    pub fn overruns(&self, detector: &CycleDetector) -> Vec<String> {
+        let failing: Vec<_> = detector
+            .cycles()
+            .iter()
+            .filter(|cycle| detector.is_failing(cycle))
+            .collect();
+        let mut overruns = Vec::new();
+
+        if let Some(max_cycles) = self.max_cycles
+            && failing.len() > max_cycles
+        {
+            overruns.push(format!(
+                "{} failing {}, the budget allows {max_cycles}",
+                failing.len(),
+                pluralize("cycle", failing.len())
+            ));
+        }
+
+        if let Some(max_severity) = self.max_severity {
+            let over = failing
+                .iter()
+                .filter(|cycle| detector.severity(cycle) > max_severity)
+                .count();
+            if over > 0 {
+                overruns.push(format!(
+                    "{over} failing {} above {} severity, the most the budget allows",
+                    pluralize("cycle", over),
+                    max_severity.to_string().to_lowercase()
+                ));
+            }
+        }
+
+        overruns
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::detector::WorkspaceCycle;
+
+    #[test]
+    fn test_overruns_per_limit() {
+        let mut detector = CycleDetector::new();
+        // One normal and one dev edge between two workspaces: medium severity
+        detector.add_cycle(
+            WorkspaceCycle::builder()
+                .with_workspace_names(vec!["a".to_string(), "b".to_string()])
+                .add_edge()
+                .from_workspace("a")
+                .to_workspace("b")
+                .from_crate("a")
+                .to_crate("b")
+                .dependency_type("Normal")
+                .add_edge()
+                .unwrap()
+                .from_workspace("b")
+                .to_workspace("a")
+                .from_crate("b")
+                .to_crate("a")
+                .dependency_type("Dev")
+                .build()
+                .unwrap(),
+        );
+
+        let within = FailureBudget {
+            max_cycles: Some(1),
+            max_severity: Some(CycleSeverity::Medium),
+        };
+        assert!(within.is_set());
+        assert!(within.overruns(&detector).is_empty());
+
+        let strict = FailureBudget {
+            max_cycles: Some(0),
+            max_severity: Some(CycleSeverity::Low),
+        };
+        assert_eq!(strict.overruns(&detector).len(), 2);
+
+        assert!(!FailureBudget::default().is_set());
+        assert!(FailureBudget::default().overruns(&detector).is_empty());
+    }
+}
@@ -1,28 +1,59 @@
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::sync::{Arc, mpsc};
 
 use miette::{Result, WrapErr};
 use petgraph::algo::tarjan_scc;
 use petgraph::graph::{DiGraph, NodeIndex};
 use petgraph::visit::EdgeRef;
+use rayon::prelude::*;
 
-use crate::graph::{DependencyEdge, WorkspaceNode};
-
-// Removed CycleSearchContext and related builder - no longer needed
-// Now we collect all edges between workspaces in an SCC directly
+use super::{
+    CrateLines, CycleAllowlist, CycleSeverity, DependencyPolicy, EffortEstimate, EffortModel,
+    SeverityModel,
+};
+use crate::common::Deadline;
+use crate::graph::{DependencyEdge, DependencyType, WorkspaceNode};
+use crate::timings::{Phase, Timer};
 
 /// Detector for finding dependency cycles in workspace graphs
 ///
 /// Uses Tarjan's Strongly Connected Components algorithm to efficiently
-/// find all cycles in the dependency graph.
+/// find all cycles in the dependency graph. By default each strongly
+/// connected component is reported as one cycle; with
+/// [`CycleDetector::with_elementary_cycles`] the distinct elementary cycles
+/// within each component are enumerated instead.
 pub struct CycleDetector {
     cycles: Vec<WorkspaceCycle>,
+    elementary_limit: Option<usize>,
+    truncated: bool,
+    severity_model: SeverityModel,
+    effort_model: EffortModel,
+    crate_lines: Option<CrateLines>,
+    deadline: Deadline,
+    timed_out: bool,
+    fail_fast: bool,
+    stopped_early: bool,
+    allowlist: CycleAllowlist,
+    policy: DependencyPolicy,
+}
+
+/// Cycles found in one strongly connected component
+#[derive(Default)]
+struct ComponentCycles {
+    cycles: Vec<WorkspaceCycle>,
+    truncated: bool,
+    timed_out: bool,
 }
 
+/// The workspaces an edge leaves and enters, shared with the graph the cycle
+/// was found in
+pub type CycleDirection = (Arc<str>, Arc<str>);
+
 #[derive(Debug, Clone)]
 pub struct WorkspaceCycle {
-    workspace_names: Vec<String>,
+    workspace_names: Vec<Arc<str>>,
     edges: Vec<CycleEdge>,
-    edges_by_direction: HashMap<(String, String), Vec<CycleEdge>>,
+    edges_by_direction: HashMap<CycleDirection, Vec<CycleEdge>>,
 }
 
 impl WorkspaceCycle {
@@ -34,19 +65,84 @@ impl WorkspaceCycle {
         &self.edges
     }
 
-    pub fn edges_by_direction(&self) -> &HashMap<(String, String), Vec<CycleEdge>> {
+    pub fn edges_by_direction(&self) -> &HashMap<CycleDirection, Vec<CycleEdge>> {
         &self.edges_by_direction
     }
 
-    pub fn workspace_names(&self) -> &[String] {
+    pub fn workspace_names(&self) -> &[Arc<str>] {
         &self.workspace_names
     }
+
+    /// Stable identifier for this cycle
+    ///
+    /// The ID is a 64-bit FNV-1a hash of the sorted workspace names and the
+    /// sorted, deduplicated crate edges, rendered as 16 hex digits. It does
+    /// not depend on discovery order, so baselines and trend tooling can
+    /// recognize the same cycle across runs. Adding or removing a crate edge
+    /// gives the cycle a new ID.
+    pub fn id(&self) -> String {
+        let mut names = self.workspace_names.clone();
+        names.sort();
+        names.dedup();
+
+        let mut edges: Vec<[&str; 5]> = self
+            .edges
+            .iter()
+            .map(|edge| {
+                [
+                    edge.from_workspace(),
+                    edge.to_workspace(),
+                    edge.from_crate(),
+                    edge.to_crate(),
+                    edge.dependency_type(),
+                ]
+            })
+            .collect();
+        edges.sort();
+        edges.dedup();
+
+        // Unit and record separators keep field boundaries unambiguous
+        let mut hash = FNV_OFFSET_BASIS;
+        let mut feed = |bytes: &[u8]| {
+            for &byte in bytes {
+                hash ^= u64::from(byte);
+                hash = hash.wrapping_mul(FNV_PRIME);
+            }
+        };
+        for name in &names {
+            feed(name.as_bytes());
+            feed(&[0x1f]);
+        }
+        feed(&[0x1e]);
+        for edge in &edges {
+            for field in edge {
+                feed(field.as_bytes());
+                feed(&[0x1f]);
+            }
+            feed(&[0x1e]);
+        }
+
+        format!("{hash:016x}")
+    }
 }
 
+impl From<Vec<CycleEdge>> for WorkspaceCycle {
+    /// Collect `edges` into a cycle, taking its workspaces from the edges
+    fn from(edges: Vec<CycleEdge>) -> Self {
+        edges
+            .into_iter()
+            .fold(WorkspaceCycle::builder(), WorkspaceCycleBuilder::with_edge)
+            .build()
+    }
+}
+
+const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
 pub struct WorkspaceCycleBuilder {
-    workspace_names: HashSet<String>,
+    workspace_names: HashSet<Arc<str>>,
     edges: Vec<CycleEdge>,
-    edges_by_direction: HashMap<(String, String), Vec<CycleEdge>>,
+    edges_by_direction: HashMap<CycleDirection, Vec<CycleEdge>>,
 }
 
 impl Default for WorkspaceCycleBuilder {
@@ -68,18 +164,33 @@ impl WorkspaceCycleBuilder {
         CycleEdgeBuilder::new(self)
     }
 
-    pub fn with_workspace_names(mut self, names: Vec<String>) -> Self {
-        self.workspace_names = names.into_iter().collect();
+    pub fn with_workspace_names(
+        mut self,
+        names: impl IntoIterator<Item = impl Into<Arc<str>>>,
+    ) -> Self {
+        self.workspace_names = names.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Add an edge built with [`CycleEdge::builder`]
+    pub fn with_edge(mut self, edge: CycleEdge) -> Self {
+        self.add_edge_internal(edge);
         self
     }
 
     fn add_edge_internal(&mut self, edge: CycleEdge) {
         // Add to workspace names
-        self.workspace_names.insert(edge.from_workspace.clone());
-        self.workspace_names.insert(edge.to_workspace.clone());
+        for name in [&edge.from_workspace, &edge.to_workspace] {
+            if !self.workspace_names.contains(name) {
+                self.workspace_names.insert(Arc::clone(name));
+            }
+        }
 
         // Add to edges_by_direction
-        let direction = (edge.from_workspace.clone(), edge.to_workspace.clone());
+        let direction = (
+            Arc::clone(&edge.from_workspace),
+            Arc::clone(&edge.to_workspace),
+        );
         self.edges_by_direction
             .entry(direction)
             .or_default()
@@ -90,7 +201,7 @@ impl WorkspaceCycleBuilder {
     }
 
     pub fn build(self) -> WorkspaceCycle {
-        let mut workspace_names: Vec<String> = self.workspace_names.into_iter().collect();
+        let mut workspace_names: Vec<Arc<str>> = self.workspace_names.into_iter().collect();
         workspace_names.sort();
 
         WorkspaceCycle {
@@ -103,11 +214,13 @@ impl WorkspaceCycleBuilder {
 
 pub struct CycleEdgeBuilder<T> {
     parent: T,
-    from_workspace: Option<String>,
-    to_workspace: Option<String>,
-    from_crate: Option<String>,
-    to_crate: Option<String>,
-    dependency_type: Option<String>,
+    from_workspace: Option<Arc<str>>,
+    to_workspace: Option<Arc<str>>,
+    from_crate: Option<Arc<str>>,
+    to_crate: Option<Arc<str>>,
+    dependency_type: Option<DependencyType>,
+    /// A name given to `dependency_type` that names no dependency type
+    unknown_dependency_type: Option<String>,
 }
 
 impl<T> CycleEdgeBuilder<T> {
@@ -119,31 +232,41 @@ impl<T> CycleEdgeBuilder<T> {
             from_crate: None,
             to_crate: None,
             dependency_type: None,
+            unknown_dependency_type: None,
         }
     }
 
     pub fn from_workspace(mut self, ws: &str) -> Self {
-        self.from_workspace = Some(ws.to_string());
+        self.from_workspace = Some(Arc::from(ws));
         self
     }
 
     pub fn to_workspace(mut self, ws: &str) -> Self {
-        self.to_workspace = Some(ws.to_string());
+        self.to_workspace = Some(Arc::from(ws));
         self
     }
 
     pub fn from_crate(mut self, cr: &str) -> Self {
-        self.from_crate = Some(cr.to_string());
+        self.from_crate = Some(Arc::from(cr));
         self
     }
 
     pub fn to_crate(mut self, cr: &str) -> Self {
-        self.to_crate = Some(cr.to_string());
+        self.to_crate = Some(Arc::from(cr));
         self
     }
 
+    /// Set the dependency type by name (`Normal`, `Dev` or `Build`, in any
+    /// case); building fails on any other name
     pub fn dependency_type(mut self, dt: &str) -> Self {
-        self.dependency_type = Some(dt.to_string());
+        self.dependency_type = DependencyType::from_name(dt);
+        self.unknown_dependency_type = self.dependency_type.is_none().then(|| dt.to_string());
+        self
+    }
+
+    pub fn dependency_kind(mut self, kind: DependencyType) -> Self {
+        self.dependency_type = Some(kind);
+        self.unknown_dependency_type = None;
         self
     }
 }
@@ -161,49 +284,83 @@ impl CycleEdgeBuilder<WorkspaceCycleBuilder> {
         Ok(parent.build())
     }
 
-    fn build_and_add(mut self) -> Result<WorkspaceCycleBuilder, crate::error::FerrisWheelError> {
+    fn build_and_add(self) -> Result<WorkspaceCycleBuilder, crate::error::FerrisWheelError> {
+        let (mut parent, edge) = self.into_parts()?;
+        parent.add_edge_internal(edge);
+        Ok(parent)
+    }
+}
+
+impl CycleEdgeBuilder<()> {
+    pub fn build(self) -> Result<CycleEdge, crate::error::FerrisWheelError> {
+        let ((), edge) = self.into_parts()?;
+        Ok(edge)
+    }
+}
+
+impl<T> CycleEdgeBuilder<T> {
+    /// Split the builder into its parent and the built edge, reporting an
+    /// unknown dependency type, or else every missing field at once
+    fn into_parts(self) -> Result<(T, CycleEdge), crate::error::FerrisWheelError> {
+        if let Some(name) = self.unknown_dependency_type {
+            return Err(crate::error::FerrisWheelError::InvalidDependencyType { name });
+        }
+
+        let missing: Vec<&'static str> = [
+            ("from_workspace", self.from_workspace.is_none()),
+            ("to_workspace", self.to_workspace.is_none()),
+            ("from_crate", self.from_crate.is_none()),
+            ("to_crate", self.to_crate.is_none()),
+            ("dependency_type", self.dependency_type.is_none()),
+        ]
+        .into_iter()
+        .filter_map(|(field, is_missing)| is_missing.then_some(field))
+        .collect();
+
+        let (
+            Some(from_workspace),
+            Some(to_workspace),
+            Some(from_crate),
+            Some(to_crate),
+            Some(dependency_type),
+        ) = (
+            self.from_workspace,
+            self.to_workspace,
+            self.from_crate,
+            self.to_crate,
+            self.dependency_type,
+        )
+        else {
+            return Err(crate::error::FerrisWheelError::InvalidCycleEdge { missing });
+        };
+
         let edge = CycleEdge {
-            from_workspace: self.from_workspace.ok_or_else(|| {
-                crate::error::FerrisWheelError::ConfigurationError {
-                    message: "Missing required field: from_workspace in CycleEdge".to_string(),
-                }
-            })?,
-            to_workspace: self.to_workspace.ok_or_else(|| {
-                crate::error::FerrisWheelError::ConfigurationError {
-                    message: "Missing required field: to_workspace in CycleEdge".to_string(),
-                }
-            })?,
-            from_crate: self.from_crate.ok_or_else(|| {
-                crate::error::FerrisWheelError::ConfigurationError {
-                    message: "Missing required field: from_crate in CycleEdge".to_string(),
-                }
-            })?,
-            to_crate: self.to_crate.ok_or_else(|| {
-                crate::error::FerrisWheelError::ConfigurationError {
-                    message: "Missing required field: to_crate in CycleEdge".to_string(),
-                }
-            })?,
-            dependency_type: self.dependency_type.ok_or_else(|| {
-                crate::error::FerrisWheelError::ConfigurationError {
-                    message: "Missing required field: dependency_type in CycleEdge".to_string(),
-                }
-            })?,
+            from_workspace,
+            to_workspace,
+            from_crate,
+            to_crate,
+            dependency_type,
         };
-        self.parent.add_edge_internal(edge);
-        Ok(self.parent)
+        Ok((self.parent, edge))
     }
 }
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct CycleEdge {
-    from_workspace: String,
-    to_workspace: String,
-    from_crate: String,
-    to_crate: String,
-    dependency_type: String,
+    from_workspace: Arc<str>,
+    to_workspace: Arc<str>,
+    from_crate: Arc<str>,
+    to_crate: Arc<str>,
+    dependency_type: DependencyType,
 }
 
 impl CycleEdge {
+    /// Start building an edge outside of a [`WorkspaceCycleBuilder`], e.g.
+    /// from a detector implemented outside this crate
+    pub fn builder() -> CycleEdgeBuilder<()> {
+        CycleEdgeBuilder::new(())
+    }
+
     pub fn from_workspace(&self) -> &str {
         &self.from_workspace
     }
@@ -220,8 +377,25 @@ impl CycleEdge {
         &self.to_crate
     }
 
-    pub fn dependency_type(&self) -> &str {
-        &self.dependency_type
+    pub fn dependency_type(&self) -> &'static str {
+        self.dependency_type.as_str()
+    }
+
+    pub fn dependency_kind(&self) -> DependencyType {
+        self.dependency_type
+    }
+
+    /// The graph edge `edge` from `from` to `to`, sharing the graph's copies
+    /// of the names
+    fn from_graph(from: &WorkspaceNode, to: &WorkspaceNode, edge: &DependencyEdge) -> Self {
+        let (from_crate, to_crate) = edge.interned_crates();
+        Self {
+            from_workspace: Arc::clone(from.interned_name()),
+            to_workspace: Arc::clone(to.interned_name()),
+            from_crate: Arc::clone(from_crate),
+            to_crate: Arc::clone(to_crate),
+            dependency_type: *edge.dependency_type(),
+        }
     }
 }
 
@@ -231,42 +405,274 @@ impl Default for CycleDetector {
     }
 }
 
+impl From<Vec<WorkspaceCycle>> for CycleDetector {
+    /// Wrap cycles found by another detection algorithm, so they can be
+    /// passed to the report generators and renderers
+    fn from(cycles: Vec<WorkspaceCycle>) -> Self {
+        Self {
+            cycles,
+            ..Self::new()
+        }
+    }
+}
+
 impl CycleDetector {
     /// Create a new cycle detector
     pub fn new() -> Self {
-        Self { cycles: Vec::new() }
+        Self {
+            cycles: Vec::new(),
+            elementary_limit: None,
+            truncated: false,
+            severity_model: SeverityModel::default(),
+            effort_model: EffortModel::default(),
+            crate_lines: None,
+            deadline: Deadline::never(),
+            timed_out: false,
+            fail_fast: false,
+            stopped_early: false,
+            allowlist: CycleAllowlist::default(),
+            policy: DependencyPolicy::default(),
+        }
+    }
+
+    /// Rate cycles with a custom severity model
+    pub fn with_severity_model(mut self, severity_model: SeverityModel) -> Self {
+        self.severity_model = severity_model;
+        self
+    }
+
+    /// The model used to rate cycle severity
+    pub fn severity_model(&self) -> &SeverityModel {
+        &self.severity_model
+    }
+
+    /// Severity of a cycle under this detector's model
+    pub fn severity(&self, cycle: &WorkspaceCycle) -> CycleSeverity {
+        self.severity_model.classify(cycle)
+    }
+
+    /// Estimate refactor effort with a custom model
+    pub fn with_effort_model(mut self, effort_model: EffortModel) -> Self {
+        self.effort_model = effort_model;
+        self
+    }
+
+    /// The model used to estimate refactor effort
+    pub fn effort_model(&self) -> &EffortModel {
+        &self.effort_model
+    }
+
+    /// Weigh effort estimates by the lines of code in `crate_lines`
+    ///
+    /// Only used when the effort model sets `lines-per-point`.
+    pub fn with_crate_lines(mut self, crate_lines: CrateLines) -> Self {
+        self.crate_lines = Some(crate_lines);
+        self
+    }
+
+    /// Estimated effort to break a cycle under this detector's model
+    pub fn effort(&self, cycle: &WorkspaceCycle) -> EffortEstimate {
+        self.effort_model.estimate(cycle, self.crate_lines.as_ref())
+    }
+
+    /// Accept the cycles of `allowlist`: they are still reported but never
+    /// fail the run
+    pub fn with_allowlist(mut self, allowlist: CycleAllowlist) -> Self {
+        self.allowlist = allowlist;
+        self
+    }
+
+    /// The accepted cycles
+    pub fn allowlist(&self) -> &CycleAllowlist {
+        &self.allowlist
+    }
+
+    /// Whether `cycle` is accepted by the allowlist
+    pub fn is_allowed(&self, cycle: &WorkspaceCycle) -> bool {
+        self.allowlist.allows(cycle)
+    }
+
+    /// Let the dependency types of `policy` close cycles that only warn
+    pub fn with_policy(mut self, policy: DependencyPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    /// The rule for each dependency type
+    pub fn policy(&self) -> &DependencyPolicy {
+        &self.policy
+    }
+
+    /// Whether `cycle` only exists because of dependency types the policy
+    /// lets warn
+    pub fn is_warn_only(&self, cycle: &WorkspaceCycle) -> bool {
+        self.policy.only_warns(cycle)
+    }
+
+    /// Whether `cycle` is severe enough to fail the run, not allowed and not
+    /// warn-only
+    pub fn is_failing(&self, cycle: &WorkspaceCycle) -> bool {
+        self.severity_model.is_failure(cycle)
+            && !self.is_allowed(cycle)
+            && !self.is_warn_only(cycle)
+    }
+
+    /// Whether any cycle would fail the run, see [`Self::is_failing`]
+    pub fn has_failing_cycles(&self) -> bool {
+        self.cycles.iter().any(|cycle| self.is_failing(cycle))
+    }
+
+    /// Enumerate elementary cycles with Johnson's algorithm, keeping at most
+    /// `limit` cycles per strongly connected component
+    pub fn with_elementary_cycles(mut self, limit: usize) -> Self {
+        self.elementary_limit = Some(limit);
+        self
+    }
+
+    /// Stop looking for further cycles once `deadline` has passed
+    ///
+    /// Cycles found before the deadline are kept and
+    /// [`CycleDetector::is_timed_out`] reports that the result is partial.
+    pub fn with_deadline(mut self, deadline: Deadline) -> Self {
+        self.deadline = deadline;
+        self
+    }
+
+    /// Stop detection at the first cycle the severity model fails on
+    ///
+    /// Components after it are never turned into cycles, which saves
+    /// collecting their edges on large graphs.
+    /// [`CycleDetector::is_stopped_early`] reports whether detection
+    /// stopped this way.
+    pub fn with_fail_fast(mut self, fail_fast: bool) -> Self {
+        self.fail_fast = fail_fast;
+        self
     }
 
     /// Detect all cycles in the dependency graph
     ///
     /// Uses Tarjan's algorithm to find strongly connected components,
-    /// then identifies actual cycles within each component.
+    /// then identifies actual cycles within each component. Components are
+    /// independent, so they are processed in parallel; cycles are reported
+    /// in component order either way.
     pub fn detect_cycles(&mut self, graph: &DiGraph<WorkspaceNode, DependencyEdge>) -> Result<()> {
-        // Use Tarjan's algorithm to find strongly connected components
-        let sccs = tarjan_scc(graph);
-
-        // Filter SCCs with more than one node (these contain cycles)
-        for scc in sccs {
-            if scc.len() > 1 {
-                // Find all elementary cycles within this SCC
-                self.find_all_cycles_in_scc(graph, scc)
-                    .wrap_err("Failed to find cycles in SCC")?;
+        self.detect_cycles_with(graph, |_, _| {})
+    }
+
+    /// Detect all cycles, calling `on_cycle` with each one as soon as its
+    /// component has been searched
+    ///
+    /// Components finish in whatever order the parallel search gets to
+    /// them, so `on_cycle` may see cycles out of component order;
+    /// [`CycleDetector::cycles`] keeps component order.
+    pub fn detect_cycles_with(
+        &mut self,
+        graph: &DiGraph<WorkspaceNode, DependencyEdge>,
+        mut on_cycle: impl FnMut(&CycleDetector, &WorkspaceCycle),
+    ) -> Result<()> {
+        let _timer = Timer::start(Phase::Detection);
+
+        // Use Tarjan's algorithm to find strongly connected components, and
+        // keep those with more than one node (these contain cycles)
+        let sccs: Vec<Vec<NodeIndex>> = tarjan_scc(graph)
+            .into_iter()
+            .filter(|scc| scc.len() > 1)
+            .collect();
+
+        if self.fail_fast {
+            // One component at a time, so later components are skipped once
+            // a failing cycle turns up
+            for scc in sccs {
+                if self.deadline.is_expired() {
+                    self.timed_out = true;
+                    break;
+                }
+                let found = self.cycles_in_scc(graph, scc)?;
+                for cycle in &found.cycles {
+                    on_cycle(self, cycle);
+                }
+                let failed = found.cycles.iter().any(|cycle| self.is_failing(cycle));
+                self.record(found);
+                if failed {
+                    self.stopped_early = true;
+                    break;
+                }
             }
+            return Ok(());
+        }
+
+        // Workers send each component's cycles back as they finish, so they
+        // can be handed to `on_cycle` while the rest are still searched
+        let detector = &*self;
+        let component_count = sccs.len();
+        let (sender, receiver) = mpsc::channel();
+        let mut found: Vec<Option<Result<ComponentCycles>>> =
+            (0..component_count).map(|_| None).collect();
+        std::thread::scope(|scope| {
+            scope.spawn(move || {
+                sccs.into_par_iter()
+                    .enumerate()
+                    .for_each_with(sender, |sender, (index, scc)| {
+                        let cycles = if detector.deadline.is_expired() {
+                            Ok(ComponentCycles {
+                                timed_out: true,
+                                ..ComponentCycles::default()
+                            })
+                        } else {
+                            detector.cycles_in_scc(graph, scc)
+                        };
+                        // The receiver outlives every worker
+                        let _ = sender.send((index, cycles));
+                    });
+            });
+            for (index, cycles) in receiver {
+                if let Ok(component) = &cycles {
+                    for cycle in &component.cycles {
+                        on_cycle(detector, cycle);
+                    }
+                }
+                found[index] = Some(cycles);
+            }
+        });
+        for found in found.into_iter().flatten() {
+            self.record(found?);
         }
 
         Ok(())
     }
 
+    fn cycles_in_scc(
+        &self,
+        graph: &DiGraph<WorkspaceNode, DependencyEdge>,
+        scc: Vec<NodeIndex>,
+    ) -> Result<ComponentCycles> {
+        match self.elementary_limit {
+            Some(limit) => self
+                .find_elementary_cycles_in_scc(graph, scc, limit)
+                .wrap_err("Failed to enumerate elementary cycles in SCC"),
+            None => self
+                .find_all_cycles_in_scc(graph, scc)
+                .wrap_err("Failed to find cycles in SCC"),
+        }
+    }
+
+    fn record(&mut self, found: ComponentCycles) {
+        self.cycles.extend(found.cycles);
+        self.truncated |= found.truncated;
+        self.timed_out |= found.timed_out;
+    }
+
     fn find_all_cycles_in_scc(
-        &mut self,
+        &self,
         graph: &DiGraph<WorkspaceNode, DependencyEdge>,
         scc: Vec<NodeIndex>,
-    ) -> Result<()> {
+    ) -> Result<ComponentCycles> {
         // For workspace cycles, we just need to know which workspaces form a cycle
         // and collect ALL edges between them
 
+        let mut found = ComponentCycles::default();
         if scc.len() < 2 {
-            return Ok(());
+            return Ok(found);
         }
 
         // Get workspace names for the SCC
@@ -282,30 +688,20 @@ impl CycleDetector {
         let scc_set: HashSet<NodeIndex> = scc.iter().cloned().collect();
 
         let mut edge_count = 0;
-        let mut edges_by_direction_check: HashMap<(String, String), bool> = HashMap::new();
+        let mut directions: HashSet<(NodeIndex, NodeIndex)> = HashSet::new();
 
         for &from_idx in &scc {
-            let from_node = &graph[from_idx];
-
             for edge in graph.edges(from_idx) {
                 let to_idx = edge.target();
                 if scc_set.contains(&to_idx) && from_idx != to_idx {
-                    let edge_data = edge.weight();
-                    let to_node = &graph[to_idx];
-
                     // Track edge directions for 2-node cycle check
-                    let direction = (from_node.name().to_string(), to_node.name().to_string());
-                    edges_by_direction_check.insert(direction, true);
-
-                    // Add edge using the internal method
-                    let cycle_edge = CycleEdge {
-                        from_workspace: from_node.name().to_string(),
-                        to_workspace: to_node.name().to_string(),
-                        from_crate: edge_data.from_crate().to_string(),
-                        to_crate: edge_data.to_crate().to_string(),
-                        dependency_type: format!("{:?}", edge_data.dependency_type()),
-                    };
-                    builder.add_edge_internal(cycle_edge);
+                    directions.insert((from_idx, to_idx));
+
+                    builder.add_edge_internal(CycleEdge::from_graph(
+                        &graph[from_idx],
+                        &graph[to_idx],
+                        edge.weight(),
+                    ));
                     edge_count += 1;
                 }
             }
@@ -315,27 +711,98 @@ impl CycleDetector {
         if edge_count > 0 {
             // For 2-node cycles, verify bidirectional dependencies exist
             if scc.len() == 2 {
-                let ws1 = &workspace_names[0];
-                let ws2 = &workspace_names[1];
-
-                let has_forward =
-                    edges_by_direction_check.contains_key(&(ws1.clone(), ws2.clone()));
-                let has_backward =
-                    edges_by_direction_check.contains_key(&(ws2.clone(), ws1.clone()));
+                let has_forward = directions.contains(&(scc[0], scc[1]));
+                let has_backward = directions.contains(&(scc[1], scc[0]));
 
                 if has_forward && has_backward {
-                    self.cycles.push(builder.build());
+                    found.cycles.push(builder.build());
                 }
             } else {
                 // For larger SCCs, all nodes are mutually reachable
-                self.cycles.push(builder.build());
+                found.cycles.push(builder.build());
             }
         }
 
-        Ok(())
+        Ok(found)
     }
 
-    // Removed deduplicate_cycles - no longer needed with new approach
+    fn find_elementary_cycles_in_scc(
+        &self,
+        graph: &DiGraph<WorkspaceNode, DependencyEdge>,
+        mut scc: Vec<NodeIndex>,
+        limit: usize,
+    ) -> Result<ComponentCycles> {
+        // Sort so that enumeration order (and each cycle's starting
+        // workspace) is stable across runs
+        scc.sort();
+        let position: HashMap<NodeIndex, usize> = scc
+            .iter()
+            .enumerate()
+            .map(|(pos, &idx)| (idx, pos))
+            .collect();
+
+        // Collapse parallel edges: a cycle is a sequence of workspaces
+        let adjacency: Vec<Vec<usize>> = scc
+            .iter()
+            .enumerate()
+            .map(|(pos, &idx)| {
+                let mut targets: Vec<usize> = graph
+                    .edges(idx)
+                    .filter_map(|edge| position.get(&edge.target()).copied())
+                    .filter(|&target| target != pos)
+                    .collect();
+                targets.sort_unstable();
+                targets.dedup();
+                targets
+            })
+            .collect();
+
+        // Search for one more than the limit so we know whether we stopped
+        // early
+        let mut search = ElementaryCycleSearch::new(&adjacency, limit.saturating_add(1));
+        let mut component = ComponentCycles::default();
+        for start in 0..scc.len() {
+            if search.is_full() {
+                break;
+            }
+            if self.deadline.is_expired() {
+                component.timed_out = true;
+                break;
+            }
+            search.circuits_from(start);
+        }
+
+        let mut found = search.cycles;
+        if found.len() > limit {
+            found.truncate(limit);
+            component.truncated = true;
+        }
+
+        for positions in found {
+            let workspace_names: Vec<String> = positions
+                .iter()
+                .map(|&pos| graph[scc[pos]].name().to_string())
+                .collect();
+            let mut builder = WorkspaceCycle::builder().with_workspace_names(workspace_names);
+
+            for (i, &from_pos) in positions.iter().enumerate() {
+                let from_idx = scc[from_pos];
+                let to_idx = scc[positions[(i + 1) % positions.len()]];
+
+                for edge in graph.edges_connecting(from_idx, to_idx) {
+                    builder.add_edge_internal(CycleEdge::from_graph(
+                        &graph[from_idx],
+                        &graph[to_idx],
+                        edge.weight(),
+                    ));
+                }
+            }
+
+            component.cycles.push(builder.build());
+        }
+
+        Ok(component)
+    }
 
     /// Get all detected cycles
     pub fn cycles(&self) -> &[WorkspaceCycle] {
@@ -352,6 +819,62 @@ impl CycleDetector {
         self.cycles.len()
     }
 
+    /// Whether elementary cycle enumeration stopped at the configured limit
+    /// for at least one component
+    pub fn is_truncated(&self) -> bool {
+        self.truncated
+    }
+
+    /// Whether detection stopped early because the deadline passed, so some
+    /// cycles may be missing
+    pub fn is_timed_out(&self) -> bool {
+        self.timed_out
+    }
+
+    /// Whether fail-fast detection stopped at a failing cycle, so later
+    /// cycles were not looked for
+    pub fn is_stopped_early(&self) -> bool {
+        self.stopped_early
+    }
+
+    /// Cycles containing a dependency from `from_crate` to `to_crate`
+    pub fn cycles_through(&self, from_crate: &str, to_crate: &str) -> Vec<&WorkspaceCycle> {
+        self.cycles
+            .iter()
+            .filter(|cycle| {
+                cycle
+                    .edges()
+                    .iter()
+                    .any(|edge| edge.from_crate() == from_crate && edge.to_crate() == to_crate)
+            })
+            .collect()
+    }
+
+    /// Cycles through the dependency from `from_crate` to `to_crate` that the
+    /// dependency creates
+    ///
+    /// `before` holds the cyclic components of the graph without the
+    /// dependency. A cycle whose workspaces were all already in one of them
+    /// existed before, so it is not counted.
+    pub fn cycles_introduced_by(
+        &self,
+        from_crate: &str,
+        to_crate: &str,
+        before: &[BTreeSet<String>],
+    ) -> Vec<&WorkspaceCycle> {
+        self.cycles_through(from_crate, to_crate)
+            .into_iter()
+            .filter(|cycle| {
+                !before.iter().any(|component| {
+                    cycle
+                        .workspace_names()
+                        .iter()
+                        .all(|name| component.contains(&**name))
+                })
+            })
+            .collect()
+    }
+
     /// Add a cycle to the detector (used for filtered results)
     pub fn add_cycle(&mut self, cycle: WorkspaceCycle) {
         // The builder already ensures edges_by_direction is populated
@@ -359,8 +882,106 @@ impl CycleDetector {
     }
 }
 
+/// State for Johnson's elementary circuit search over a dense adjacency list
+///
+/// Circuits are found from each start vertex in turn, only visiting vertices
+/// at or after the start, so every cycle is reported exactly once beginning
+/// at its lowest vertex.
+struct ElementaryCycleSearch<'a> {
+    adjacency: &'a [Vec<usize>],
+    limit: usize,
+    blocked: Vec<bool>,
+    blocked_by: Vec<HashSet<usize>>,
+    path: Vec<usize>,
+    cycles: Vec<Vec<usize>>,
+}
+
+impl<'a> ElementaryCycleSearch<'a> {
+    fn new(adjacency: &'a [Vec<usize>], limit: usize) -> Self {
+        Self {
+            adjacency,
+            limit,
+            blocked: vec![false; adjacency.len()],
+            blocked_by: vec![HashSet::new(); adjacency.len()],
+            path: Vec::new(),
+            cycles: Vec::new(),
+        }
+    }
+
+    fn is_full(&self) -> bool {
+        self.cycles.len() >= self.limit
+    }
+
+    fn circuits_from(&mut self, start: usize) {
+        for vertex in start..self.adjacency.len() {
+            self.blocked[vertex] = false;
+            self.blocked_by[vertex].clear();
+        }
+        self.circuit(start, start);
+    }
+
+    fn circuit(&mut self, vertex: usize, start: usize) -> bool {
+        let adjacency = self.adjacency;
+        let mut closed = false;
+
+        self.path.push(vertex);
+        self.blocked[vertex] = true;
+
+        for &next in adjacency[vertex].iter().filter(|&&next| next >= start) {
+            if self.is_full() {
+                break;
+            }
+            if next == start {
+                self.cycles.push(self.path.clone());
+                closed = true;
+            } else if !self.blocked[next] && self.circuit(next, start) {
+                closed = true;
+            }
+        }
+
+        if closed {
+            self.unblock(vertex);
+        } else {
+            for &next in adjacency[vertex].iter().filter(|&&next| next >= start) {
+                self.blocked_by[next].insert(vertex);
+            }
+        }
+
+        self.path.pop();
+        closed
+    }
+
+    fn unblock(&mut self, vertex: usize) {
+        let mut pending = vec![vertex];
+        while let Some(vertex) = pending.pop() {
+            self.blocked[vertex] = false;
+            for waiting in std::mem::take(&mut self.blocked_by[vertex]) {
+                if self.blocked[waiting] {
+                    pending.push(waiting);
+                }
+            }
+        }
+    }
+}
+
+/// Node names of each strongly connected component of `graph` that
+/// contains a cycle
+pub fn cyclic_components(graph: &DiGraph<WorkspaceNode, DependencyEdge>) -> Vec<BTreeSet<String>> {
+    tarjan_scc(graph)
+        .into_iter()
+        .filter(|scc| scc.len() > 1)
+        .map(|scc| {
+            scc.into_iter()
+                .map(|node| graph[node].name().to_string())
+                .collect()
+        })
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
+    use std::time::Duration;
+
     use petgraph::graph::DiGraph;
 
     use super::*;
@@ -478,13 +1099,25 @@ mod tests {
         assert!(
             cycle
                 .edges_by_direction()
-                .contains_key(&("workspace-a".to_string(), "workspace-b".to_string()))
+                .contains_key(&("workspace-a".into(), "workspace-b".into()))
         );
         assert!(
             cycle
                 .edges_by_direction()
-                .contains_key(&("workspace-b".to_string(), "workspace-a".to_string()))
+                .contains_key(&("workspace-b".into(), "workspace-a".into()))
         );
+
+        // Cycle edges share the graph's copies of the names
+        let edge = &cycle.edges()[0];
+        let graph_edge = graph
+            .edge_weights()
+            .find(|e| e.from_crate() == edge.from_crate())
+            .unwrap();
+        assert!(Arc::ptr_eq(
+            &edge.from_crate,
+            graph_edge.interned_crates().0
+        ));
+        assert_eq!(edge.dependency_kind(), DependencyType::Normal);
     }
 
     #[test]
@@ -653,13 +1286,13 @@ mod tests {
 
         let a_to_b_edges = cycle
             .edges_by_direction()
-            .get(&("workspace-a".to_string(), "workspace-b".to_string()))
+            .get(&("workspace-a".into(), "workspace-b".into()))
             .unwrap();
         assert_eq!(a_to_b_edges.len(), 3, "Should have 3 edges from A to B");
 
         let b_to_a_edges = cycle
             .edges_by_direction()
-            .get(&("workspace-b".to_string(), "workspace-a".to_string()))
+            .get(&("workspace-b".into(), "workspace-a".into()))
             .unwrap();
         assert_eq!(b_to_a_edges.len(), 2, "Should have 2 edges from B to A");
 
@@ -667,11 +1300,11 @@ mod tests {
         let edge_types: Vec<String> = cycle
             .edges()
             .iter()
-            .map(|e| e.dependency_type.clone())
+            .map(|e| e.dependency_type().to_string())
             .collect();
-        assert!(edge_types.contains(&"Normal".to_string()));
-        assert!(edge_types.contains(&"Dev".to_string()));
-        assert!(edge_types.contains(&"Build".to_string()));
+        assert!(edge_types.contains(&"Normal".into()));
+        assert!(edge_types.contains(&"Dev".into()));
+        assert!(edge_types.contains(&"Build".into()));
     }
 
     #[test]
@@ -830,8 +1463,11 @@ mod tests {
         assert_eq!(cycle.edges().len(), 2);
 
         // Verify the dependency types are preserved
-        let has_dev_dep = cycle.edges().iter().any(|e| e.dependency_type == "Dev");
-        let has_normal_dep = cycle.edges().iter().any(|e| e.dependency_type == "Normal");
+        let has_dev_dep = cycle.edges().iter().any(|e| e.dependency_type() == "Dev");
+        let has_normal_dep = cycle
+            .edges()
+            .iter()
+            .any(|e| e.dependency_type() == "Normal");
         assert!(has_dev_dep);
         assert!(has_normal_dep);
     }
@@ -1009,22 +1645,22 @@ mod tests {
         assert!(
             cycle
                 .edges_by_direction()
-                .contains_key(&("nodes".to_string(), "sdk".to_string()))
+                .contains_key(&("nodes".into(), "sdk".into()))
         );
         assert!(
             cycle
                 .edges_by_direction()
-                .contains_key(&("sdk".to_string(), "core".to_string()))
+                .contains_key(&("sdk".into(), "core".into()))
         );
         assert!(
             cycle
                 .edges_by_direction()
-                .contains_key(&("core".to_string(), "nodes".to_string()))
+                .contains_key(&("core".into(), "nodes".into()))
         );
         assert!(
             cycle
                 .edges_by_direction()
-                .contains_key(&("nodes".to_string(), "core".to_string()))
+                .contains_key(&("nodes".into(), "core".into()))
         );
     }
 
@@ -1094,25 +1730,27 @@ mod tests {
         assert_eq!(cycle.edges().len(), 2, "Cycle should have 2 edges");
 
         // Verify the cycle contains both workspaces
-        assert!(cycle.workspace_names().contains(&"nodes".to_string()));
-        assert!(cycle.workspace_names().contains(&"core".to_string()));
+        assert!(cycle.workspace_names().contains(&"nodes".into()));
+        assert!(cycle.workspace_names().contains(&"core".into()));
 
         // Verify both edges are present
         let edge_pairs: Vec<(String, String)> = cycle
             .edges()
             .iter()
-            .map(|e| (e.from_workspace.clone(), e.to_workspace.clone()))
+            .map(|e| (e.from_workspace.to_string(), e.to_workspace.to_string()))
             .collect();
 
-        assert!(edge_pairs.contains(&("nodes".to_string(), "core".to_string())));
-        assert!(edge_pairs.contains(&("core".to_string(), "nodes".to_string())));
+        assert!(edge_pairs.contains(&("nodes".into(), "core".into())));
+        assert!(edge_pairs.contains(&("core".into(), "nodes".into())));
 
         // Print the cycle for debugging
         eprintln!("\nDetected cycle:");
         for edge in cycle.edges() {
             eprintln!(
                 "  {} -> {} ({})",
-                edge.from_workspace, edge.to_workspace, edge.dependency_type
+                edge.from_workspace,
+                edge.to_workspace,
+                edge.dependency_type()
             );
         }
     }
@@ -1322,12 +1960,12 @@ mod tests {
         let dep_types: Vec<String> = cycle
             .edges()
             .iter()
-            .map(|e| e.dependency_type.clone())
+            .map(|e| e.dependency_type().to_string())
             .collect();
 
-        assert!(dep_types.contains(&"Normal".to_string()));
-        assert!(dep_types.contains(&"Dev".to_string()));
-        assert!(dep_types.contains(&"Build".to_string()));
+        assert!(dep_types.contains(&"Normal".into()));
+        assert!(dep_types.contains(&"Dev".into()));
+        assert!(dep_types.contains(&"Build".into()));
     }
 
     #[test]
@@ -1477,13 +2115,13 @@ mod tests {
         // Check edge grouping
         let a_to_b = cycle
             .edges_by_direction()
-            .get(&("workspace-a".to_string(), "workspace-b".to_string()))
+            .get(&("workspace-a".into(), "workspace-b".into()))
             .unwrap();
         assert_eq!(a_to_b.len(), 3, "Should have 3 edges from A to B");
 
         let b_to_a = cycle
             .edges_by_direction()
-            .get(&("workspace-b".to_string(), "workspace-a".to_string()))
+            .get(&("workspace-b".into(), "workspace-a".into()))
             .unwrap();
         assert_eq!(b_to_a.len(), 3, "Should have 3 edges from B to A");
     }
@@ -1582,10 +2220,10 @@ mod tests {
 
         // Verify all workspaces are in the cycle
         let workspace_names = cycle.workspace_names();
-        assert!(workspace_names.contains(&"workspace-a".to_string()));
-        assert!(workspace_names.contains(&"workspace-b".to_string()));
-        assert!(workspace_names.contains(&"workspace-c".to_string()));
-        assert!(workspace_names.contains(&"workspace-d".to_string()));
+        assert!(workspace_names.contains(&"workspace-a".into()));
+        assert!(workspace_names.contains(&"workspace-b".into()));
+        assert!(workspace_names.contains(&"workspace-c".into()));
+        assert!(workspace_names.contains(&"workspace-d".into()));
     }
 
     #[test]
@@ -1735,7 +2373,7 @@ mod tests {
         // Check that B→C has 2 edges
         let b_to_c = cycle
             .edges_by_direction()
-            .get(&("workspace-b".to_string(), "workspace-c".to_string()))
+            .get(&("workspace-b".into(), "workspace-c".into()))
             .unwrap();
         assert_eq!(b_to_c.len(), 2, "Should have 2 edges from B to C");
     }
@@ -1822,4 +2460,404 @@ mod tests {
             "Should have 12 unique directions"
         );
     }
+
+    fn elementary_test_graph() -> DiGraph<WorkspaceNode, DependencyEdge> {
+        let mut graph = DiGraph::new();
+        let nodes: Vec<NodeIndex> = ["a", "b", "c", "d"]
+            .iter()
+            .map(|name| {
+                graph.add_node(
+                    WorkspaceNode::builder()
+                        .with_name(format!("workspace-{name}"))
+                        .with_crates(vec![format!("crate-{name}")])
+                        .build()
+                        .unwrap(),
+                )
+            })
+            .collect();
+
+        // A <-> B, A -> B -> C -> A and B -> C -> D -> B, with a parallel
+        // A -> B edge
+        for (from, to, from_crate) in [
+            (0, 1, "crate-a"),
+            (0, 1, "crate-a-extra"),
+            (1, 0, "crate-b"),
+            (1, 2, "crate-b"),
+            (2, 0, "crate-c"),
+            (2, 3, "crate-c"),
+            (3, 1, "crate-d"),
+        ] {
+            let to_crate = format!("crate-{}", ["a", "b", "c", "d"][to]);
+            graph.add_edge(
+                nodes[from],
+                nodes[to],
+                DependencyEdge::builder()
+                    .with_from_crate(from_crate)
+                    .with_to_crate(&to_crate)
+                    .with_dependency_type(DependencyType::Normal)
+                    .build()
+                    .unwrap(),
+            );
+        }
+
+        graph
+    }
+
+    #[test]
+    fn test_elementary_cycles_are_enumerated() {
+        let graph = elementary_test_graph();
+
+        let mut detector = CycleDetector::new().with_elementary_cycles(100);
+        detector.detect_cycles(&graph).unwrap();
+
+        let mut cycles: Vec<Vec<&str>> = detector
+            .cycles()
+            .iter()
+            .map(|cycle| cycle.workspace_names().iter().map(AsRef::as_ref).collect())
+            .collect();
+        cycles.sort();
+        assert_eq!(
+            cycles,
+            vec![
+                vec!["workspace-a", "workspace-b"],
+                vec!["workspace-a", "workspace-b", "workspace-c"],
+                vec!["workspace-b", "workspace-c", "workspace-d"],
+            ]
+        );
+        assert!(!detector.is_truncated());
+
+        // Each cycle only carries the edges along its own path, including
+        // parallel edges
+        let two_node = detector
+            .cycles()
+            .iter()
+            .find(|cycle| cycle.workspace_names().len() == 2)
+            .unwrap();
+        assert_eq!(two_node.edges().len(), 3);
+        assert!(
+            two_node
+                .edges()
+                .iter()
+                .all(|edge| edge.to_workspace() != "workspace-c")
+        );
+    }
+
+    #[test]
+    fn test_elementary_cycles_respect_limit() {
+        let graph = elementary_test_graph();
+
+        let mut detector = CycleDetector::new().with_elementary_cycles(2);
+        detector.detect_cycles(&graph).unwrap();
+        assert_eq!(detector.cycle_count(), 2);
+        assert!(detector.is_truncated());
+
+        let mut detector = CycleDetector::new().with_elementary_cycles(3);
+        detector.detect_cycles(&graph).unwrap();
+        assert_eq!(detector.cycle_count(), 3);
+        assert!(!detector.is_truncated());
+    }
+
+    #[test]
+    fn test_expired_deadline_stops_detection() {
+        let graph = elementary_test_graph();
+
+        let mut detector = CycleDetector::new().with_deadline(Deadline::after(Duration::ZERO));
+        detector.detect_cycles(&graph).unwrap();
+        assert!(detector.is_timed_out());
+        assert_eq!(detector.cycle_count(), 0);
+
+        let mut detector = CycleDetector::new();
+        detector.detect_cycles(&graph).unwrap();
+        assert!(!detector.is_timed_out());
+        assert!(detector.has_cycles());
+    }
+
+    #[test]
+    fn test_fail_fast_stops_at_first_failing_cycle() {
+        // Two separate cycles: A <-> B and C <-> D
+        let mut graph = DiGraph::new();
+        let nodes: Vec<NodeIndex> = ["a", "b", "c", "d"]
+            .iter()
+            .map(|name| {
+                graph.add_node(
+                    WorkspaceNode::builder()
+                        .with_name(format!("workspace-{name}"))
+                        .with_crates(vec![format!("crate-{name}")])
+                        .build()
+                        .unwrap(),
+                )
+            })
+            .collect();
+        for (from, to) in [(0, 1), (1, 0), (2, 3), (3, 2)] {
+            graph.add_edge(
+                nodes[from],
+                nodes[to],
+                DependencyEdge::builder()
+                    .with_from_crate(&format!("crate-{}", ["a", "b", "c", "d"][from]))
+                    .with_to_crate(&format!("crate-{}", ["a", "b", "c", "d"][to]))
+                    .with_dependency_type(DependencyType::Normal)
+                    .build()
+                    .unwrap(),
+            );
+        }
+
+        let mut detector = CycleDetector::new().with_fail_fast(true);
+        detector.detect_cycles(&graph).unwrap();
+        assert_eq!(detector.cycle_count(), 1);
+        assert!(detector.is_stopped_early());
+        assert!(detector.has_failing_cycles());
+
+        // Cycles that would not fail the run do not stop detection
+        let lenient = SeverityModel {
+            high_share: 1.0,
+            fail_at: CycleSeverity::High,
+            ..SeverityModel::default()
+        };
+        let mut detector = CycleDetector::new()
+            .with_severity_model(lenient)
+            .with_fail_fast(true);
+        detector.detect_cycles(&graph).unwrap();
+        assert_eq!(detector.cycle_count(), 2);
+        assert!(!detector.is_stopped_early());
+    }
+
+    #[test]
+    fn test_parallel_detection_keeps_component_order() {
+        // 50 separate two-workspace cycles
+        let mut graph = DiGraph::new();
+        for pair in 0..50 {
+            let [a, b] = ["a", "b"].map(|side| {
+                graph.add_node(
+                    WorkspaceNode::builder()
+                        .with_name(format!("workspace-{pair}-{side}"))
+                        .with_crates(vec![format!("crate-{pair}-{side}")])
+                        .build()
+                        .unwrap(),
+                )
+            });
+            for (from, to) in [(a, b), (b, a)] {
+                graph.add_edge(
+                    from,
+                    to,
+                    DependencyEdge::builder()
+                        .with_from_crate(&graph[from].crates()[0].clone())
+                        .with_to_crate(&graph[to].crates()[0].clone())
+                        .with_dependency_type(DependencyType::Normal)
+                        .build()
+                        .unwrap(),
+                );
+            }
+        }
+
+        let mut parallel = CycleDetector::new();
+        parallel.detect_cycles(&graph).unwrap();
+
+        // Fail-fast detection that never stops runs one component at a time
+        let never_fails = SeverityModel {
+            high_share: 1.0,
+            fail_at: CycleSeverity::High,
+            ..SeverityModel::default()
+        };
+        let mut sequential = CycleDetector::new()
+            .with_severity_model(never_fails)
+            .with_fail_fast(true);
+        sequential.detect_cycles(&graph).unwrap();
+
+        assert_eq!(parallel.cycle_count(), 50);
+        let ids = |detector: &CycleDetector| -> Vec<String> {
+            detector.cycles().iter().map(WorkspaceCycle::id).collect()
+        };
+        assert_eq!(ids(&parallel), ids(&sequential));
+    }
+
+    #[test]
+    fn test_cycles_introduced_by_skip_existing_cycles() {
+        let dependency = |from_crate: &str, to_crate: &str| {
+            DependencyEdge::builder()
+                .with_from_crate(from_crate)
+                .with_to_crate(to_crate)
+                .with_dependency_type(DependencyType::Normal)
+                .build()
+                .unwrap()
+        };
+        let mut graph = elementary_test_graph();
+        let a = graph
+            .node_indices()
+            .find(|&node| graph[node].name() == "workspace-a")
+            .unwrap();
+        let c = graph
+            .node_indices()
+            .find(|&node| graph[node].name() == "workspace-c")
+            .unwrap();
+        let e = graph.add_node(
+            WorkspaceNode::builder()
+                .with_name("workspace-e".to_string())
+                .with_crates(vec!["crate-e".to_string()])
+                .build()
+                .unwrap(),
+        );
+        graph.add_edge(e, a, dependency("crate-e", "crate-a"));
+        let before = cyclic_components(&graph);
+        assert_eq!(before.len(), 1);
+
+        // A -> C runs inside the existing a-b-c-d cycle
+        graph.add_edge(a, c, dependency("crate-a", "crate-c"));
+        let mut detector = CycleDetector::new();
+        detector.detect_cycles(&graph).unwrap();
+        assert_eq!(detector.cycles_through("crate-a", "crate-c").len(), 1);
+        assert!(
+            detector
+                .cycles_introduced_by("crate-a", "crate-c", &before)
+                .is_empty()
+        );
+
+        // A -> E pulls workspace-e into the cycle
+        graph.add_edge(a, e, dependency("crate-a", "crate-e"));
+        let mut detector = CycleDetector::new();
+        detector.detect_cycles(&graph).unwrap();
+        let introduced = detector.cycles_introduced_by("crate-a", "crate-e", &before);
+        assert_eq!(introduced.len(), 1);
+        assert!(
+            introduced[0]
+                .workspace_names()
+                .contains(&"workspace-e".into())
+        );
+    }
+
+    #[test]
+    fn test_cycles_through_edge() {
+        let graph = elementary_test_graph();
+
+        let mut detector = CycleDetector::new().with_elementary_cycles(100);
+        detector.detect_cycles(&graph).unwrap();
+
+        let through = detector.cycles_through("crate-d", "crate-b");
+        assert_eq!(through.len(), 1);
+        assert_eq!(
+            through[0].workspace_names(),
+            [
+                "workspace-b".into(),
+                "workspace-c".into(),
+                "workspace-d".into()
+            ]
+        );
+        assert_eq!(detector.cycles_through("crate-b", "crate-c").len(), 2);
+        assert!(detector.cycles_through("crate-a", "crate-d").is_empty());
+    }
+
+    #[test]
+    fn test_cycle_id_is_stable() {
+        let cycle = |edges: &[(&str, &str, &str, &str)]| {
+            let mut builder = WorkspaceCycle::builder().add_edge();
+            for (i, &(from_ws, to_ws, from_crate, to_crate)) in edges.iter().enumerate() {
+                builder = builder
+                    .from_workspace(from_ws)
+                    .to_workspace(to_ws)
+                    .from_crate(from_crate)
+                    .to_crate(to_crate)
+                    .dependency_type("Normal");
+                if i + 1 < edges.len() {
+                    builder = builder.add_edge().unwrap();
+                }
+            }
+            builder.build().unwrap()
+        };
+
+        let forward = cycle(&[("a", "b", "a1", "b1"), ("b", "a", "b1", "a1")]);
+        let reversed = cycle(&[("b", "a", "b1", "a1"), ("a", "b", "a1", "b1")]);
+        let duplicated = cycle(&[
+            ("a", "b", "a1", "b1"),
+            ("b", "a", "b1", "a1"),
+            ("a", "b", "a1", "b1"),
+        ]);
+        let extra_edge = cycle(&[
+            ("a", "b", "a1", "b1"),
+            ("b", "a", "b1", "a1"),
+            ("a", "b", "a2", "b1"),
+        ]);
+
+        assert_eq!(forward.id().len(), 16);
+        assert_eq!(forward.id(), reversed.id());
+        assert_eq!(forward.id(), duplicated.id());
+        assert_ne!(forward.id(), extra_edge.id());
+        // Pinned so an accidental change to the hashing is caught
+        assert_eq!(forward.id(), "817f8dc6532f1dd2");
+    }
+
+    #[test]
+    fn test_cycle_from_external_edges() {
+        let edge = |from_ws: &str, to_ws: &str, from_crate: &str, to_crate: &str| {
+            CycleEdge::builder()
+                .from_workspace(from_ws)
+                .to_workspace(to_ws)
+                .from_crate(from_crate)
+                .to_crate(to_crate)
+                .dependency_type("Normal")
+                .build()
+                .unwrap()
+        };
+
+        let cycle =
+            WorkspaceCycle::from(vec![edge("b", "a", "b1", "a1"), edge("a", "b", "a1", "b1")]);
+        assert_eq!(cycle.workspace_names(), ["a".into(), "b".into()]);
+        assert_eq!(cycle.edges().len(), 2);
+        assert_eq!(
+            cycle.edges_by_direction()[&("a".into(), "b".into())].len(),
+            1
+        );
+        assert_eq!(cycle.id(), "817f8dc6532f1dd2");
+
+        let detector = CycleDetector::from(vec![cycle]);
+        assert_eq!(detector.cycle_count(), 1);
+
+        match CycleEdge::builder()
+            .from_workspace("a")
+            .to_workspace("b")
+            .build()
+        {
+            Err(crate::error::FerrisWheelError::InvalidCycleEdge { missing }) => {
+                assert_eq!(missing, ["from_crate", "to_crate", "dependency_type"]);
+            }
+            other => panic!("Expected InvalidCycleEdge, got {other:?}"),
+        }
+
+        // Errors from a cycle builder name the missing fields too
+        let error = WorkspaceCycle::builder()
+            .add_edge()
+            .from_workspace("a")
+            .build()
+            .unwrap_err();
+        assert!(matches!(
+            error,
+            crate::error::FerrisWheelError::InvalidCycleEdge { ref missing } if missing.len() == 4
+        ));
+    }
+
+    #[test]
+    fn test_unknown_dependency_type_is_an_error() {
+        let builder = || {
+            CycleEdge::builder()
+                .from_workspace("a")
+                .to_workspace("b")
+                .from_crate("a1")
+                .to_crate("b1")
+        };
+
+        match builder().dependency_type("optional").build() {
+            Err(crate::error::FerrisWheelError::InvalidDependencyType { name }) => {
+                assert_eq!(name, "optional");
+            }
+            other => panic!("Expected InvalidDependencyType, got {other:?}"),
+        }
+
+        // Names match in any case, and a later valid type replaces a bad one
+        assert!(builder().dependency_type("dev").build().is_ok());
+        assert!(
+            builder()
+                .dependency_type("optional")
+                .dependency_kind(DependencyType::Build)
+                .build()
+                .is_ok()
+        );
+    }
 }
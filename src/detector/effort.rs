@@ -0,0 +1,415 @@
+//! Refactor effort estimates
+//!
+//! Severity says how bad a cycle is; effort says how much work it takes to
+//! break it, so an untangling roadmap can weigh the two. A single
+//! [`EffortModel`], configured through the `[effort]` section of
+//! `.ferris-wheel.toml`, sizes every cycle as small, medium or large.
+//!
+//! A cycle is broken most cheaply by cutting every dependency along one of
+//! its hops between workspaces. The score adds up:
+//!
+//! - the weight of each edge in the cheapest hop, by dependency type, since a
+//!   dev-dependency used by tests is easier to drop than a normal one
+//! - `crate-points` for each crate taking part in the cycle
+//! - with `lines-per-point` set, one point per that many lines of Rust in the
+//!   crates that must drop a dependency
+//!
+//! Scores reaching `medium` or `large` are sized accordingly.
+
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use walkdir::WalkDir;
+
+use super::{CycleDirection, CycleEdge, WorkspaceCycle};
+use crate::analyzer::WorkspaceInfo;
+
+/// Rough size of the work needed to break a cycle
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, JsonSchema)]
+pub enum EffortSize {
+    #[serde(rename = "S")]
+    Small,
+    #[serde(rename = "M")]
+    Medium,
+    #[serde(rename = "L")]
+    Large,
+}
+
+impl fmt::Display for EffortSize {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            EffortSize::Small => "S",
+            EffortSize::Medium => "M",
+            EffortSize::Large => "L",
+        })
+    }
+}
+
+/// Points per cut edge of each dependency type
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize, JsonSchema)]
+#[serde(default, deny_unknown_fields)]
+pub struct EffortWeights {
+    pub normal: f64,
+    pub build: f64,
+    pub dev: f64,
+}
+
+impl Default for EffortWeights {
+    fn default() -> Self {
+        Self {
+            normal: 2.0,
+            build: 1.5,
+            dev: 0.5,
+        }
+    }
+}
+
+/// Configurable model estimating the effort to break each cycle
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize, JsonSchema)]
+#[serde(default, deny_unknown_fields, rename_all = "kebab-case")]
+pub struct EffortModel {
+    /// Points per edge in the cheapest hop, by dependency type
+    pub weights: EffortWeights,
+    /// Points per crate taking part in the cycle
+    pub crate_points: f64,
+    /// Lines of Rust worth one point; lines are not counted when unset
+    pub lines_per_point: Option<f64>,
+    /// Scores of at least this much are medium effort
+    pub medium: f64,
+    /// Scores of at least this much are large effort
+    pub large: f64,
+}
+
+impl Default for EffortModel {
+    fn default() -> Self {
+        Self {
+            weights: EffortWeights::default(),
+            crate_points: 0.5,
+            lines_per_point: None,
+            medium: 4.0,
+            large: 10.0,
+        }
+    }
+}
+
+/// Estimated effort to break one cycle, with the reasons behind it
+#[derive(Debug, Clone, PartialEq, Serialize, JsonSchema)]
+pub struct EffortEstimate {
+    size: EffortSize,
+    score: f64,
+    rationale: Vec<String>,
+}
+
+impl EffortEstimate {
+    pub fn size(&self) -> EffortSize {
+        self.size
+    }
+
+    pub fn score(&self) -> f64 {
+        self.score
+    }
+
+    /// One sentence per factor that went into the score
+    pub fn rationale(&self) -> &[String] {
+        &self.rationale
+    }
+}
+
+impl EffortModel {
+    /// Estimate the effort to break `cycle`
+    ///
+    /// `lines` is only consulted when `lines-per-point` is set.
+    pub fn estimate(&self, cycle: &WorkspaceCycle, lines: Option<&CrateLines>) -> EffortEstimate {
+        let mut score = 0.0;
+        let mut rationale = Vec::new();
+
+        let cut = self.cheapest_cut(cycle);
+        if let Some(((from, to), edges, cost)) = &cut {
+            let mut kinds: BTreeMap<String, usize> = BTreeMap::new();
+            for edge in edges.iter() {
+                *kinds
+                    .entry(edge.dependency_type().to_ascii_lowercase())
+                    .or_default() += 1;
+            }
+            let kinds: Vec<String> = kinds
+                .iter()
+                .map(|(kind, count)| format!("{count} {kind}"))
+                .collect();
+            score += cost;
+            rationale.push(format!(
+                "Cheapest cut drops {} from {from} to {to} ({})",
+                plural(edges.len(), "dependency", "dependencies"),
+                kinds.join(", ")
+            ));
+        }
+
+        let crates: HashSet<(&str, &str)> = cycle
+            .edges()
+            .iter()
+            .flat_map(|edge| {
+                [
+                    (edge.from_workspace(), edge.from_crate()),
+                    (edge.to_workspace(), edge.to_crate()),
+                ]
+            })
+            .collect();
+        score += self.crate_points * crates.len() as f64;
+        rationale.push(format!(
+            "{} across {} take part in the cycle",
+            plural(crates.len(), "crate", "crates"),
+            plural(cycle.workspace_names().len(), "workspace", "workspaces")
+        ));
+
+        if let (Some(lines_per_point), Some(lines), Some((_, edges, _))) =
+            (self.lines_per_point, lines, &cut)
+        {
+            let changed: BTreeSet<(&str, &str)> = edges
+                .iter()
+                .map(|edge| (edge.from_workspace(), edge.from_crate()))
+                .collect();
+            let total: usize = changed
+                .iter()
+                .filter_map(|(workspace, krate)| lines.get(workspace, krate))
+                .sum();
+            if lines_per_point > 0.0 {
+                score += total as f64 / lines_per_point;
+            }
+            rationale.push(format!(
+                "{} of Rust in the {} that must drop a dependency",
+                plural(total, "line", "lines"),
+                if changed.len() == 1 {
+                    "crate"
+                } else {
+                    "crates"
+                }
+            ));
+        }
+
+        let size = if score >= self.large {
+            EffortSize::Large
+        } else if score >= self.medium {
+            EffortSize::Medium
+        } else {
+            EffortSize::Small
+        };
+
+        EffortEstimate {
+            size,
+            score,
+            rationale,
+        }
+    }
+
+    fn weight(&self, edge: &CycleEdge) -> f64 {
+        match edge.dependency_type().to_ascii_lowercase().as_str() {
+            "build" => self.weights.build,
+            "dev" => self.weights.dev,
+            _ => self.weights.normal,
+        }
+    }
+
+    /// The hop between two workspaces whose edges are cheapest to remove,
+    /// preferring fewer edges and then the alphabetically first hop on ties
+    fn cheapest_cut<'a>(
+        &self,
+        cycle: &'a WorkspaceCycle,
+    ) -> Option<(&'a CycleDirection, &'a Vec<CycleEdge>, f64)> {
+        cycle
+            .edges_by_direction()
+            .iter()
+            .map(|(hop, edges)| {
+                (
+                    hop,
+                    edges,
+                    edges.iter().map(|e| self.weight(e)).sum::<f64>(),
+                )
+            })
+            .min_by(|a, b| {
+                a.2.total_cmp(&b.2)
+                    .then(a.1.len().cmp(&b.1.len()))
+                    .then(a.0.cmp(b.0))
+            })
+    }
+}
+
+pub(super) fn plural(count: usize, one: &str, many: &str) -> String {
+    format!("{count} {}", if count == 1 { one } else { many })
+}
+
+/// Lines of Rust source per crate, keyed by workspace and crate name
+#[derive(Debug, Clone, Default)]
+pub struct CrateLines {
+    lines: HashMap<(String, String), usize>,
+}
+
+impl CrateLines {
+    /// Count the lines of every crate that would drop a dependency to break
+    /// one of `cycles`
+    pub fn count(workspaces: &HashMap<PathBuf, WorkspaceInfo>, cycles: &[WorkspaceCycle]) -> Self {
+        let wanted: HashSet<(&str, &str)> = cycles
+            .iter()
+            .flat_map(|cycle| cycle.edges())
+            .map(|edge| (edge.from_workspace(), edge.from_crate()))
+            .collect();
+
+        let mut lines = HashMap::new();
+        for workspace in workspaces.values() {
+            for member in workspace.members() {
+                if wanted.contains(&(workspace.name(), member.name())) {
+                    lines.insert(
+                        (workspace.name().to_string(), member.name().to_string()),
+                        rust_lines(member.path()),
+                    );
+                }
+            }
+        }
+
+        Self { lines }
+    }
+
+    pub fn get(&self, workspace: &str, crate_name: &str) -> Option<usize> {
+        self.lines
+            .get(&(workspace.to_string(), crate_name.to_string()))
+            .copied()
+    }
+}
+
+impl FromIterator<((String, String), usize)> for CrateLines {
+    fn from_iter<I: IntoIterator<Item = ((String, String), usize)>>(iter: I) -> Self {
+        Self {
+            lines: iter.into_iter().collect(),
+        }
+    }
+}
+
+/// Lines in the `.rs` files below `dir`, outside build output
+fn rust_lines(dir: &Path) -> usize {
+    WalkDir::new(dir)
+        .into_iter()
+        .filter_entry(|e| e.file_name() != "target")
+        .filter_map(|e| e.ok())
+        .filter(|e| e.path().extension().is_some_and(|ext| ext == "rs"))
+        .filter_map(|e| std::fs::read_to_string(e.path()).ok())
+        .map(|source| source.lines().count())
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cycle(forward: &[&str], back: &[&str]) -> WorkspaceCycle {
+        let (last, back) = back.split_last().unwrap();
+        let mut builder = WorkspaceCycle::builder()
+            .with_workspace_names(vec!["app".to_string(), "core".to_string()])
+            .add_edge();
+        for (i, dependency_type) in forward.iter().enumerate() {
+            builder = builder
+                .from_workspace("app")
+                .to_workspace("core")
+                .from_crate(&format!("app-{i}"))
+                .to_crate("core-lib")
+                .dependency_type(dependency_type)
+                .add_edge()
+                .unwrap();
+        }
+        for (i, dependency_type) in back.iter().enumerate() {
+            builder = builder
+                .from_workspace("core")
+                .to_workspace("app")
+                .from_crate("core-lib")
+                .to_crate(&format!("app-{}", i + 1))
+                .dependency_type(dependency_type)
+                .add_edge()
+                .unwrap();
+        }
+        builder
+            .from_workspace("core")
+            .to_workspace("app")
+            .from_crate("core-lib")
+            .to_crate("app-0")
+            .dependency_type(last)
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_cheapest_hop_drives_the_estimate() {
+        let model = EffortModel::default();
+
+        let small = model.estimate(&cycle(&["Normal", "Normal"], &["Dev"]), None);
+        assert_eq!(small.size(), EffortSize::Small);
+        assert_eq!(small.score(), 0.5 + 3.0 * 0.5);
+        assert_eq!(
+            small.rationale()[0],
+            "Cheapest cut drops 1 dependency from core to app (1 dev)"
+        );
+        assert_eq!(
+            small.rationale()[1],
+            "3 crates across 2 workspaces take part in the cycle"
+        );
+
+        let medium = model.estimate(&cycle(&["Normal", "Normal"], &["Normal", "Build"]), None);
+        assert_eq!(medium.size(), EffortSize::Medium);
+        assert_eq!(
+            medium.rationale()[0],
+            "Cheapest cut drops 2 dependencies from core to app (1 build, 1 normal)"
+        );
+
+        let large = model.estimate(&cycle(&["Normal"; 5], &["Normal"; 5]), None);
+        assert_eq!(large.size(), EffortSize::Large);
+    }
+
+    #[test]
+    fn test_lines_of_code_add_to_the_score() {
+        let model = EffortModel {
+            lines_per_point: Some(1000.0),
+            ..EffortModel::default()
+        };
+        let lines: CrateLines = [(("core".to_string(), "core-lib".to_string()), 8000)]
+            .into_iter()
+            .collect();
+
+        let estimate = model.estimate(&cycle(&["Normal", "Normal"], &["Dev"]), Some(&lines));
+        assert_eq!(estimate.size(), EffortSize::Large);
+        assert_eq!(
+            estimate.rationale()[2],
+            "8000 lines of Rust in the crate that must drop a dependency"
+        );
+    }
+
+    #[test]
+    fn test_model_from_toml() {
+        let model: EffortModel = toml::from_str(
+            r#"
+lines-per-point = 500
+large = 12
+
+[weights]
+dev = 1
+"#,
+        )
+        .unwrap();
+
+        assert_eq!(model.lines_per_point, Some(500.0));
+        assert_eq!(model.large, 12.0);
+        assert_eq!(model.medium, 4.0);
+        assert_eq!(model.weights.dev, 1.0);
+        assert_eq!(model.weights.normal, 2.0);
+    }
+
+    #[test]
+    fn test_counting_lines() {
+        let temp = tempfile::TempDir::new().unwrap();
+        std::fs::create_dir_all(temp.path().join("src")).unwrap();
+        std::fs::create_dir_all(temp.path().join("target")).unwrap();
+        std::fs::write(temp.path().join("src/lib.rs"), "fn a() {}\nfn b() {}\n").unwrap();
+        std::fs::write(temp.path().join("target/gen.rs"), "fn c() {}\n").unwrap();
+        std::fs::write(temp.path().join("README.md"), "# docs\n").unwrap();
+
+        assert_eq!(rust_lines(temp.path()), 2);
+    }
+}
@@ -0,0 +1,591 @@
+//! Feedback arc set suggestions
+//!
+//! Finding a minimum feedback arc set is NP-hard, so we use the greedy
+//! Eades–Lin–Smyth ordering heuristic weighted by the number of crate edges in
+//! each direction, then drop any suggested arc that can be kept without
+//! re-creating a cycle. The result is minimal (no suggestion is redundant)
+//! though not guaranteed to be minimum.
+//!
+//! Suggestions are ranked by edge betweenness: how many routes between the
+//! cycle's workspaces run through a direction. Cutting the direction most
+//! routes depend on does the most to untangle the cycle, so it comes first.
+
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::sync::Arc;
+
+use petgraph::algo::tarjan_scc;
+use petgraph::graphmap::DiGraphMap;
+
+use super::effort::plural;
+use super::{CycleEdge, WorkspaceCycle};
+
+/// A workspace-to-workspace dependency direction suggested for removal
+#[derive(Debug, Clone, PartialEq)]
+pub struct BreakPoint {
+    from_workspace: String,
+    to_workspace: String,
+    edges: Vec<CycleEdge>,
+    betweenness: f64,
+}
+
+impl BreakPoint {
+    pub fn from_workspace(&self) -> &str {
+        &self.from_workspace
+    }
+
+    pub fn to_workspace(&self) -> &str {
+        &self.to_workspace
+    }
+
+    /// The crate-level edges that have to go to remove this direction
+    pub fn edges(&self) -> &[CycleEdge] {
+        &self.edges
+    }
+
+    /// Number of shortest routes between the cycle's workspaces that use this
+    /// direction, with ties between equally short routes split evenly
+    pub fn betweenness(&self) -> f64 {
+        self.betweenness
+    }
+
+    /// Why this direction is worth cutting, in one sentence
+    pub fn rationale(&self) -> String {
+        let mut kinds: BTreeMap<String, usize> = BTreeMap::new();
+        for edge in &self.edges {
+            *kinds
+                .entry(edge.dependency_type().to_ascii_lowercase())
+                .or_default() += 1;
+        }
+        let kinds: Vec<String> = kinds
+            .iter()
+            .map(|(kind, count)| format!("{count} {kind}"))
+            .collect();
+        let mut rationale = format!(
+            "Drops {} from {} to {} ({}); {:.1} of the shortest routes between the cycle's \
+             workspaces run through it",
+            plural(self.edges.len(), "dependency", "dependencies"),
+            self.from_workspace,
+            self.to_workspace,
+            kinds.join(", "),
+            self.betweenness
+        );
+        if !self.edges.is_empty()
+            && self
+                .edges
+                .iter()
+                .all(|edge| !edge.dependency_type().eq_ignore_ascii_case("normal"))
+        {
+            rationale.push_str("; only dev/build dependencies, so no library code has to change");
+        }
+        rationale
+    }
+}
+
+impl WorkspaceCycle {
+    /// Suggest the smallest set of dependency directions whose removal breaks
+    /// every cycle among this cycle's workspaces
+    ///
+    /// Break points are ordered by priority: directions with the highest edge
+    /// betweenness come first, then those backed by the fewest crate edges.
+    pub fn break_points(&self) -> Vec<BreakPoint> {
+        let weights: BTreeMap<(&str, &str), usize> = self
+            .edges_by_direction()
+            .iter()
+            .filter(|((from, to), _)| from != to)
+            .map(|((from, to), edges)| ((&**from, &**to), edges.len()))
+            .collect();
+
+        let order = eades_ordering(&weights);
+        let position: HashMap<&str, usize> =
+            order.iter().enumerate().map(|(i, &ws)| (ws, i)).collect();
+
+        let (mut backward, forward): (Vec<_>, Vec<_>) = weights
+            .iter()
+            .map(|(&arc, &weight)| (arc, weight))
+            .partition(|((from, to), _)| position[from] > position[to]);
+
+        // Try the most expensive arcs first so that the cheap ones are the
+        // ones left to remove
+        backward.sort_by(|(a_arc, a_weight), (b_arc, b_weight)| {
+            b_weight.cmp(a_weight).then_with(|| a_arc.cmp(b_arc))
+        });
+
+        let mut kept: BTreeSet<(&str, &str)> = forward.into_iter().map(|(arc, _)| arc).collect();
+        let mut removed = Vec::new();
+        for (arc, weight) in backward {
+            if reaches(&kept, arc.1, arc.0) {
+                removed.push((arc, weight));
+            } else {
+                kept.insert(arc);
+            }
+        }
+
+        let betweenness = edge_betweenness(&weights.keys().copied().collect());
+        removed.sort_by(|(a_arc, a_weight), (b_arc, b_weight)| {
+            betweenness[b_arc]
+                .total_cmp(&betweenness[a_arc])
+                .then_with(|| a_weight.cmp(b_weight))
+                .then_with(|| a_arc.cmp(b_arc))
+        });
+
+        removed
+            .into_iter()
+            .map(|((from, to), _)| BreakPoint {
+                from_workspace: from.to_string(),
+                to_workspace: to.to_string(),
+                edges: self
+                    .edges_by_direction()
+                    .get(&(Arc::from(from), Arc::from(to)))
+                    .cloned()
+                    .unwrap_or_default(),
+                betweenness: betweenness[&(from, to)],
+            })
+            .collect()
+    }
+}
+
+/// Bucket queue behind [`eades_ordering`]: sinks, sources, and the other
+/// workspaces bucketed by weighted out-degree minus in-degree
+///
+/// Workspaces are kept in name order within each bucket so ties are broken
+/// the same way on every run.
+struct EadesQueue {
+    out_degree: Vec<i64>,
+    in_degree: Vec<i64>,
+    /// Bucket each workspace waits in, `None` once placed
+    slots: Vec<Option<Slot>>,
+    sinks: BTreeSet<usize>,
+    sources: BTreeSet<usize>,
+    deltas: BTreeMap<i64, BTreeSet<usize>>,
+}
+
+#[derive(Clone, Copy)]
+enum Slot {
+    Sink,
+    Source,
+    Delta(i64),
+}
+
+impl EadesQueue {
+    fn new(out_degree: Vec<i64>, in_degree: Vec<i64>) -> Self {
+        let mut queue = Self {
+            slots: vec![None; out_degree.len()],
+            out_degree,
+            in_degree,
+            sinks: BTreeSet::new(),
+            sources: BTreeSet::new(),
+            deltas: BTreeMap::new(),
+        };
+        for ws in 0..queue.slots.len() {
+            queue.enqueue(ws);
+        }
+        queue
+    }
+
+    fn enqueue(&mut self, ws: usize) {
+        let slot = if self.out_degree[ws] == 0 {
+            self.sinks.insert(ws);
+            Slot::Sink
+        } else if self.in_degree[ws] == 0 {
+            self.sources.insert(ws);
+            Slot::Source
+        } else {
+            let delta = self.out_degree[ws] - self.in_degree[ws];
+            self.deltas.entry(delta).or_default().insert(ws);
+            Slot::Delta(delta)
+        };
+        self.slots[ws] = Some(slot);
+    }
+
+    fn dequeue(&mut self, ws: usize) {
+        match self.slots[ws].take() {
+            Some(Slot::Sink) => {
+                self.sinks.remove(&ws);
+            }
+            Some(Slot::Source) => {
+                self.sources.remove(&ws);
+            }
+            Some(Slot::Delta(delta)) => {
+                if let Some(bucket) = self.deltas.get_mut(&delta) {
+                    bucket.remove(&ws);
+                    if bucket.is_empty() {
+                        self.deltas.remove(&delta);
+                    }
+                }
+            }
+            None => {}
+        }
+    }
+
+    /// Next workspace to place, and whether it goes to the tail of the
+    /// ordering: a sink, else a source, else the largest degree difference
+    fn pop(&mut self) -> Option<(usize, bool)> {
+        let (ws, to_tail) = if let Some(&sink) = self.sinks.first() {
+            (sink, true)
+        } else if let Some(&source) = self.sources.first() {
+            (source, false)
+        } else {
+            (*self.deltas.values().next_back()?.first()?, false)
+        };
+        self.dequeue(ws);
+        Some((ws, to_tail))
+    }
+
+    /// Drop `weight` from a workspace's in- or out-degree, moving it to its
+    /// new bucket
+    fn lower_degree(&mut self, ws: usize, weight: i64, outgoing: bool) {
+        if self.slots[ws].is_none() {
+            return;
+        }
+        self.dequeue(ws);
+        if outgoing {
+            self.out_degree[ws] -= weight;
+        } else {
+            self.in_degree[ws] -= weight;
+        }
+        self.enqueue(ws);
+    }
+}
+
+/// Order vertices so that as few (weighted) arcs as possible point backwards
+///
+/// Degrees are updated as workspaces are placed rather than recomputed, so
+/// each arc is looked at once.
+fn eades_ordering<'a>(weights: &BTreeMap<(&'a str, &'a str), usize>) -> Vec<&'a str> {
+    let nodes: Vec<&str> = weights
+        .keys()
+        .flat_map(|&(from, to)| [from, to])
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .collect();
+    let index: HashMap<&str, usize> = nodes.iter().enumerate().map(|(i, &ws)| (ws, i)).collect();
+
+    let mut outgoing: Vec<Vec<(usize, i64)>> = vec![Vec::new(); nodes.len()];
+    let mut incoming: Vec<Vec<(usize, i64)>> = vec![Vec::new(); nodes.len()];
+    let mut out_degree = vec![0; nodes.len()];
+    let mut in_degree = vec![0; nodes.len()];
+    for (&(from, to), &weight) in weights {
+        let (from, to, weight) = (index[from], index[to], weight as i64);
+        outgoing[from].push((to, weight));
+        incoming[to].push((from, weight));
+        out_degree[from] += weight;
+        in_degree[to] += weight;
+    }
+
+    let mut queue = EadesQueue::new(out_degree, in_degree);
+    let mut head = Vec::new();
+    let mut tail = Vec::new();
+    while let Some((ws, to_tail)) = queue.pop() {
+        if to_tail {
+            tail.push(nodes[ws]);
+        } else {
+            head.push(nodes[ws]);
+        }
+        for &(to, weight) in &outgoing[ws] {
+            queue.lower_degree(to, weight, false);
+        }
+        for &(from, weight) in &incoming[ws] {
+            queue.lower_degree(from, weight, true);
+        }
+    }
+
+    tail.reverse();
+    head.extend(tail);
+    head
+}
+
+/// Edge betweenness of every arc, using Brandes' algorithm on unweighted
+/// arcs
+///
+/// Only routes inside a strongly connected component are counted, so arcs
+/// between components score zero.
+fn edge_betweenness<'a>(arcs: &BTreeSet<(&'a str, &'a str)>) -> HashMap<(&'a str, &'a str), f64> {
+    let mut betweenness: HashMap<(&str, &str), f64> = arcs.iter().map(|&arc| (arc, 0.0)).collect();
+
+    let graph: DiGraphMap<&str, ()> = arcs.iter().copied().collect();
+    let component: HashMap<&str, usize> = tarjan_scc(&graph)
+        .into_iter()
+        .enumerate()
+        .flat_map(|(id, members)| members.into_iter().map(move |ws| (ws, id)))
+        .collect();
+    let mut successors: BTreeMap<&str, Vec<&str>> = BTreeMap::new();
+    for &(from, to) in arcs {
+        if component[from] == component[to] {
+            successors.entry(from).or_default().push(to);
+        }
+    }
+
+    for &source in successors.keys() {
+        // Breadth-first search counting shortest routes to every workspace
+        let mut routes: HashMap<&str, f64> = HashMap::from([(source, 1.0)]);
+        let mut distance: HashMap<&str, usize> = HashMap::from([(source, 0)]);
+        let mut predecessors: HashMap<&str, Vec<&str>> = HashMap::new();
+        let mut visited = vec![source];
+        let mut next = 0;
+
+        while let Some(&ws) = visited.get(next) {
+            next += 1;
+            for &to in successors.get(ws).into_iter().flatten() {
+                if !distance.contains_key(to) {
+                    distance.insert(to, distance[ws] + 1);
+                    visited.push(to);
+                }
+                if distance[to] == distance[ws] + 1 {
+                    *routes.entry(to).or_default() += routes[ws];
+                    predecessors.entry(to).or_default().push(ws);
+                }
+            }
+        }
+
+        // Accumulate each arc's share of the routes, farthest workspaces first
+        let mut dependency: HashMap<&str, f64> = HashMap::new();
+        for &ws in visited.iter().rev() {
+            let through = 1.0 + dependency.get(ws).copied().unwrap_or_default();
+            for &prev in predecessors.get(ws).into_iter().flatten() {
+                let share = routes[prev] / routes[ws] * through;
+                *betweenness.entry((prev, ws)).or_default() += share;
+                *dependency.entry(prev).or_default() += share;
+            }
+        }
+    }
+
+    betweenness
+}
+
+/// Whether `to` is reachable from `from` using `arcs`
+fn reaches(arcs: &BTreeSet<(&str, &str)>, from: &str, to: &str) -> bool {
+    let mut stack = vec![from];
+    let mut seen = BTreeSet::new();
+
+    while let Some(ws) = stack.pop() {
+        if ws == to {
+            return true;
+        }
+        if seen.insert(ws) {
+            stack.extend(
+                arcs.iter()
+                    .filter(|(arc_from, _)| *arc_from == ws)
+                    .map(|&(_, arc_to)| arc_to),
+            );
+        }
+    }
+
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cycle(edges: &[(&str, &str, &str, &str)]) -> WorkspaceCycle {
+        let mut names: Vec<String> = edges
+            .iter()
+            .flat_map(|(from, to, _, _)| [from.to_string(), to.to_string()])
+            .collect();
+        names.sort();
+        names.dedup();
+
+        let (last, rest) = edges.split_last().expect("at least one edge");
+        let mut builder = WorkspaceCycle::builder()
+            .with_workspace_names(names)
+            .add_edge();
+        for &(from_ws, to_ws, from_crate, to_crate) in rest {
+            builder = builder
+                .from_workspace(from_ws)
+                .to_workspace(to_ws)
+                .from_crate(from_crate)
+                .to_crate(to_crate)
+                .dependency_type("Normal")
+                .add_edge()
+                .unwrap();
+        }
+
+        let &(from_ws, to_ws, from_crate, to_crate) = last;
+        builder
+            .from_workspace(from_ws)
+            .to_workspace(to_ws)
+            .from_crate(from_crate)
+            .to_crate(to_crate)
+            .dependency_type("Normal")
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_two_workspace_cycle_breaks_cheaper_direction() {
+        let cycle = cycle(&[
+            ("a", "b", "a1", "b1"),
+            ("a", "b", "a2", "b1"),
+            ("b", "a", "b1", "a3"),
+        ]);
+
+        let breaks = cycle.break_points();
+
+        assert_eq!(breaks.len(), 1);
+        assert_eq!(breaks[0].from_workspace(), "b");
+        assert_eq!(breaks[0].to_workspace(), "a");
+        assert_eq!(breaks[0].edges().len(), 1);
+    }
+
+    #[test]
+    fn test_shared_arc_breaks_overlapping_cycles() {
+        // a -> b -> c -> a and a -> b -> d -> a share a -> b
+        let cycle = cycle(&[
+            ("a", "b", "a1", "b1"),
+            ("b", "c", "b1", "c1"),
+            ("b", "c", "b2", "c1"),
+            ("c", "a", "c1", "a1"),
+            ("c", "a", "c2", "a1"),
+            ("b", "d", "b1", "d1"),
+            ("b", "d", "b2", "d1"),
+            ("d", "a", "d1", "a1"),
+            ("d", "a", "d2", "a1"),
+        ]);
+
+        let breaks = cycle.break_points();
+
+        assert_eq!(breaks.len(), 1);
+        assert_eq!(breaks[0].from_workspace(), "a");
+        assert_eq!(breaks[0].to_workspace(), "b");
+    }
+
+    #[test]
+    fn test_break_points_ranked_by_betweenness() {
+        // a <-> b <-> c <-> d, where c -> b carries the most routes
+        let cycle = cycle(&[
+            ("a", "b", "a1", "b1"),
+            ("b", "a", "b1", "a1"),
+            ("b", "a", "b2", "a1"),
+            ("b", "a", "b3", "a1"),
+            ("b", "c", "b1", "c1"),
+            ("b", "c", "b2", "c1"),
+            ("c", "b", "c1", "b1"),
+            ("c", "b", "c2", "b1"),
+            ("c", "b", "c3", "b1"),
+            ("c", "d", "c1", "d1"),
+            ("d", "c", "d1", "c1"),
+            ("d", "c", "d2", "c1"),
+            ("d", "c", "d3", "c1"),
+        ]);
+
+        let breaks = cycle.break_points();
+        let ranked: Vec<(&str, &str, f64)> = breaks
+            .iter()
+            .map(|b| (b.from_workspace(), b.to_workspace(), b.betweenness()))
+            .collect();
+
+        assert_eq!(
+            ranked,
+            vec![("c", "b", 4.0), ("a", "b", 3.0), ("c", "d", 3.0)]
+        );
+        assert_eq!(breaks[0].edges().len(), 3);
+    }
+
+    #[test]
+    fn test_edge_betweenness_splits_equal_routes() {
+        // Two equally short routes from a to d, closed into a cycle by d -> a,
+        // and an arc from the cycle out to e
+        let arcs: BTreeSet<(&str, &str)> = [
+            ("a", "b"),
+            ("a", "c"),
+            ("b", "d"),
+            ("c", "d"),
+            ("d", "a"),
+            ("d", "e"),
+        ]
+        .into_iter()
+        .collect();
+
+        let betweenness = edge_betweenness(&arcs);
+
+        assert_eq!(betweenness[&("a", "b")], 3.5);
+        assert_eq!(betweenness[&("b", "d")], 3.5);
+        assert_eq!(betweenness[&("d", "a")], 7.0);
+        assert_eq!(betweenness[&("d", "e")], 0.0);
+    }
+
+    #[test]
+    fn test_eades_ordering_puts_sources_first_and_sinks_last() {
+        let weights: BTreeMap<(&str, &str), usize> = [
+            (("src", "a"), 1),
+            (("a", "b"), 3),
+            (("b", "a"), 1),
+            (("b", "sink"), 1),
+        ]
+        .into_iter()
+        .collect();
+
+        assert_eq!(eades_ordering(&weights), ["src", "a", "b", "sink"]);
+    }
+
+    #[test]
+    fn test_break_point_rationale() {
+        let normal = cycle(&[("a", "b", "a1", "b1"), ("b", "a", "b1", "a1")]).break_points();
+        assert_eq!(
+            normal[0].rationale(),
+            format!(
+                "Drops 1 dependency from {} to {} (1 normal); 1.0 of the shortest routes between \
+                 the cycle's workspaces run through it",
+                normal[0].from_workspace(),
+                normal[0].to_workspace()
+            )
+        );
+
+        // Two normal edges one way and a single dev edge back: cut the dev one
+        let dev = WorkspaceCycle::builder()
+            .with_workspace_names(vec!["a".to_string(), "b".to_string()])
+            .add_edge()
+            .from_workspace("a")
+            .to_workspace("b")
+            .from_crate("a1")
+            .to_crate("b1")
+            .dependency_type("Normal")
+            .add_edge()
+            .unwrap()
+            .from_workspace("a")
+            .to_workspace("b")
+            .from_crate("a2")
+            .to_crate("b1")
+            .dependency_type("Normal")
+            .add_edge()
+            .unwrap()
+            .from_workspace("b")
+            .to_workspace("a")
+            .from_crate("b1")
+            .to_crate("a1")
+            .dependency_type("Dev")
+            .build()
+            .unwrap();
+        let rationale = dev.break_points()[0].rationale();
+        assert!(rationale.starts_with("Drops 1 dependency from b to a (1 dev); "));
+        assert!(
+            rationale.ends_with("; only dev/build dependencies, so no library code has to change")
+        );
+    }
+
+    #[test]
+    fn test_break_points_leave_no_cycle() {
+        let cycle = cycle(&[
+            ("a", "b", "a1", "b1"),
+            ("b", "a", "b1", "a1"),
+            ("b", "c", "b1", "c1"),
+            ("c", "b", "c1", "b1"),
+            ("c", "a", "c1", "a1"),
+        ]);
+
+        let breaks = cycle.break_points();
+        let removed: BTreeSet<(&str, &str)> = breaks
+            .iter()
+            .map(|b| (b.from_workspace(), b.to_workspace()))
+            .collect();
+        let kept: BTreeSet<(&str, &str)> =
+            [("a", "b"), ("b", "a"), ("b", "c"), ("c", "b"), ("c", "a")]
+                .into_iter()
+                .filter(|arc| !removed.contains(arc))
+                .collect();
+
+        for &(from, to) in &kept {
+            assert!(!reaches(&kept, to, from), "{from} -> {to} still cycles");
+        }
+    }
+}
@@ -0,0 +1,226 @@
+//! Aggregate architecture grade
+//!
+//! Condenses cycle and coupling metrics into a single 0–100 score and an
+//! A–F letter grade that can be tracked over time.
+//!
+//! ## Formula
+//!
+//! ```text
+//! penalty = cycle_weight      * cycle_count
+//!         + cycle_size_weight * workspaces_in_cycles
+//!         + coupling_weight   * average_fan_out
+//! score   = max(0, 100 - penalty)
+//! ```
+//!
+//! where `workspaces_in_cycles` counts the distinct workspaces taking part in
+//! any cycle and `average_fan_out` is the number of distinct workspace
+//! dependencies per workspace. Scores of 90 and above grade A, 80 B, 70 C,
+//! 60 D and anything lower F.
+
+use std::collections::HashSet;
+use std::fmt;
+
+use petgraph::graph::DiGraph;
+use petgraph::visit::EdgeRef;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use super::CycleDetector;
+use crate::graph::{DependencyEdge, WorkspaceNode};
+
+/// Weights applied by the grading formula
+///
+/// Read from the `[grade]` section of `.ferris-wheel.toml`.
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize, JsonSchema)]
+#[serde(default, deny_unknown_fields, rename_all = "kebab-case")]
+pub struct GradeWeights {
+    /// Penalty per detected cycle
+    pub cycle: f64,
+    /// Penalty per workspace taking part in a cycle
+    pub cycle_size: f64,
+    /// Penalty per unit of average workspace fan-out
+    pub coupling: f64,
+}
+
+impl Default for GradeWeights {
+    fn default() -> Self {
+        Self {
+            cycle: 10.0,
+            cycle_size: 5.0,
+            coupling: 2.0,
+        }
+    }
+}
+
+/// Letter grade derived from the score
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize, JsonSchema,
+)]
+pub enum Grade {
+    A,
+    B,
+    C,
+    D,
+    F,
+}
+
+impl Grade {
+    fn from_score(score: f64) -> Self {
+        match score {
+            s if s >= 90.0 => Grade::A,
+            s if s >= 80.0 => Grade::B,
+            s if s >= 70.0 => Grade::C,
+            s if s >= 60.0 => Grade::D,
+            _ => Grade::F,
+        }
+    }
+}
+
+impl fmt::Display for Grade {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{self:?}")
+    }
+}
+
+/// The architecture grade of one dependency graph, with the metrics it was
+/// computed from
+#[derive(Debug, Clone, PartialEq, Serialize, JsonSchema)]
+pub struct ArchitectureGrade {
+    pub grade: Grade,
+    pub score: f64,
+    pub cycle_count: usize,
+    pub largest_cycle: usize,
+    pub workspaces_in_cycles: usize,
+    pub average_fan_out: f64,
+}
+
+impl ArchitectureGrade {
+    /// Grade a dependency graph using the cycles found by `detector`
+    pub fn compute(
+        graph: &DiGraph<WorkspaceNode, DependencyEdge>,
+        detector: &CycleDetector,
+        weights: &GradeWeights,
+    ) -> Self {
+        let dependency_pairs: HashSet<_> = graph
+            .edge_references()
+            .filter(|edge| edge.source() != edge.target())
+            .map(|edge| (edge.source(), edge.target()))
+            .collect();
+        let average_fan_out = if graph.node_count() == 0 {
+            0.0
+        } else {
+            dependency_pairs.len() as f64 / graph.node_count() as f64
+        };
+
+        let cycle_count = detector.cycle_count();
+        let largest_cycle = detector
+            .cycles()
+            .iter()
+            .map(|cycle| cycle.workspace_names().len())
+            .max()
+            .unwrap_or(0);
+        let workspaces_in_cycles = detector
+            .cycles()
+            .iter()
+            .flat_map(|cycle| cycle.workspace_names())
+            .collect::<HashSet<_>>()
+            .len();
+
+        let penalty = weights.cycle * cycle_count as f64
+            + weights.cycle_size * workspaces_in_cycles as f64
+            + weights.coupling * average_fan_out;
+        let score = (100.0 - penalty).clamp(0.0, 100.0);
+
+        Self {
+            grade: Grade::from_score(score),
+            score,
+            cycle_count,
+            largest_cycle,
+            workspaces_in_cycles,
+            average_fan_out,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::ConfigBuilder;
+    use crate::graph::DependencyType;
+
+    fn graph_with_edges(edges: &[(usize, usize)]) -> DiGraph<WorkspaceNode, DependencyEdge> {
+        let mut graph = DiGraph::new();
+        let nodes: Vec<_> = (0..4)
+            .map(|i| {
+                graph.add_node(
+                    WorkspaceNode::builder()
+                        .with_name(format!("ws-{i}"))
+                        .with_crates(vec![format!("crate-{i}")])
+                        .build()
+                        .unwrap(),
+                )
+            })
+            .collect();
+
+        for &(from, to) in edges {
+            graph.add_edge(
+                nodes[from],
+                nodes[to],
+                DependencyEdge::builder()
+                    .with_from_crate(&format!("crate-{from}"))
+                    .with_to_crate(&format!("crate-{to}"))
+                    .with_dependency_type(DependencyType::Normal)
+                    .build()
+                    .unwrap(),
+            );
+        }
+
+        graph
+    }
+
+    #[test]
+    fn test_acyclic_graph_grades_a() {
+        let graph = graph_with_edges(&[(0, 1), (1, 2), (2, 3)]);
+        let mut detector = CycleDetector::new();
+        detector.detect_cycles(&graph).unwrap();
+
+        let grade = ArchitectureGrade::compute(&graph, &detector, &GradeWeights::default());
+
+        assert_eq!(grade.grade, Grade::A);
+        assert_eq!(grade.cycle_count, 0);
+        assert_eq!(grade.average_fan_out, 0.75);
+        assert_eq!(grade.score, 98.5);
+    }
+
+    #[test]
+    fn test_cycles_lower_the_grade() {
+        let graph = graph_with_edges(&[(0, 1), (1, 0), (2, 3), (3, 2)]);
+        let mut detector = CycleDetector::new();
+        detector.detect_cycles(&graph).unwrap();
+
+        let grade = ArchitectureGrade::compute(&graph, &detector, &GradeWeights::default());
+
+        // 100 - (10 * 2 + 5 * 4 + 2 * 1.0)
+        assert_eq!(grade.score, 58.0);
+        assert_eq!(grade.grade, Grade::F);
+        assert_eq!(grade.largest_cycle, 2);
+        assert_eq!(grade.workspaces_in_cycles, 4);
+    }
+
+    #[test]
+    fn test_weights_are_configurable() {
+        let graph = graph_with_edges(&[(0, 1), (1, 0)]);
+        let mut detector = CycleDetector::new();
+        detector.detect_cycles(&graph).unwrap();
+
+        let weights = GradeWeights {
+            cycle: 5.0,
+            cycle_size: 0.0,
+            coupling: 0.0,
+        };
+        let grade = ArchitectureGrade::compute(&graph, &detector, &weights);
+
+        assert_eq!(grade.score, 95.0);
+        assert_eq!(grade.grade, Grade::A);
+    }
+}
@@ -0,0 +1,405 @@
+//! Declared architecture layers
+//!
+//! The `[layers]` section of `.ferris-wheel.toml` lists layers from the
+//! bottom of the stack to the top and assigns workspaces to them by name glob.
+//! A workspace may depend on workspaces in its own layer or any layer below
+//! it; a dependency pointing to a higher layer is an inversion.
+//!
+//! ```toml
+//! [layers]
+//! order = ["core", "services", "apps"]
+//!
+//! [layers.workspaces]
+//! core = ["core", "utils-*"]
+//! services = ["services-*"]
+//! apps = ["app-*"]
+//! ```
+//!
+//! The order may also be written as a chain, and a layer without an entry in
+//! `workspaces` holds the workspaces matching its own name:
+//!
+//! ```toml
+//! [layers]
+//! order = "core < services-* < app-*"
+//! ```
+//!
+//! An allowed-dependency matrix replaces the ordering rule for the layers it
+//! lists: each may depend on its own layer and exactly the layers given,
+//! whether they are lower or higher. Layers it does not list keep the
+//! ordering rule.
+//!
+//! ```toml
+//! [layers.allow]
+//! apps = ["services"]          # apps may not use core directly
+//! services = ["core", "apps"]  # a sanctioned upward dependency
+//! ```
+//!
+//! Workspaces that match no layer are not checked.
+
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+use glob::Pattern;
+use petgraph::graph::DiGraph;
+use petgraph::visit::EdgeRef;
+use schemars::{JsonSchema, Schema, SchemaGenerator, json_schema};
+use serde::{Deserialize, Deserializer, Serialize};
+
+use crate::error::FerrisWheelError;
+use crate::graph::{DependencyEdge, WorkspaceNode};
+
+/// Layer ordering and workspace assignments
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, JsonSchema)]
+#[serde(default, deny_unknown_fields, rename_all = "kebab-case")]
+pub struct LayerModel {
+    /// Layer names, lowest first
+    #[serde(deserialize_with = "deserialize_order")]
+    #[schemars(schema_with = "order_schema")]
+    pub order: Vec<String>,
+    /// Workspace name globs belonging to each layer
+    pub workspaces: BTreeMap<String, Vec<String>>,
+    /// Layers each listed layer may depend on besides its own
+    pub allow: BTreeMap<String, Vec<String>>,
+}
+
+/// A workspace dependency the layers do not allow
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct LayerViolation {
+    pub from_workspace: String,
+    pub to_workspace: String,
+    pub from_layer: String,
+    pub to_layer: String,
+    /// Number of crate-level dependencies in this direction
+    pub edges: usize,
+}
+
+impl LayerModel {
+    /// Layers from a chain such as `core < services < apps`, each holding the
+    /// workspaces matching its name
+    pub fn from_chain(chain: &str) -> Self {
+        Self {
+            order: parse_chain(chain),
+            ..Self::default()
+        }
+    }
+
+    /// Whether no layers are declared
+    pub fn is_empty(&self) -> bool {
+        self.order.is_empty()
+    }
+
+    /// Find every workspace dependency the layers do not allow: one pointing
+    /// to a higher layer, or for layers with an `allow` entry, to a layer
+    /// missing from it
+    ///
+    /// Violations are sorted by workspace names.
+    pub fn violations(
+        &self,
+        graph: &DiGraph<WorkspaceNode, DependencyEdge>,
+    ) -> Result<Vec<LayerViolation>, FerrisWheelError> {
+        let layers = self.compile()?;
+        let allowed = self.allowed_ranks()?;
+        let layer_of = |name: &str| {
+            layers
+                .iter()
+                .find(|(_, patterns)| patterns.iter().any(|p| p.matches(name)))
+                .map(|(rank, _)| *rank)
+        };
+
+        let mut violations: HashMap<(String, String), LayerViolation> = HashMap::new();
+        for edge in graph.edge_references() {
+            let from = graph[edge.source()].name();
+            let to = graph[edge.target()].name();
+            let (Some(from_rank), Some(to_rank)) = (layer_of(from), layer_of(to)) else {
+                continue;
+            };
+            let is_allowed = match &allowed[from_rank] {
+                Some(ranks) => to_rank == from_rank || ranks.contains(&to_rank),
+                None => to_rank <= from_rank,
+            };
+            if is_allowed {
+                continue;
+            }
+
+            violations
+                .entry((from.to_string(), to.to_string()))
+                .or_insert_with(|| LayerViolation {
+                    from_workspace: from.to_string(),
+                    to_workspace: to.to_string(),
+                    from_layer: self.order[from_rank].clone(),
+                    to_layer: self.order[to_rank].clone(),
+                    edges: 0,
+                })
+                .edges += 1;
+        }
+
+        let mut violations: Vec<LayerViolation> = violations.into_values().collect();
+        violations.sort_by(|a, b| {
+            a.from_workspace
+                .cmp(&b.from_workspace)
+                .then_with(|| a.to_workspace.cmp(&b.to_workspace))
+        });
+
+        Ok(violations)
+    }
+
+    /// Workspaces in `graph` that match no layer, sorted by name
+    pub fn unassigned(
+        &self,
+        graph: &DiGraph<WorkspaceNode, DependencyEdge>,
+    ) -> Result<Vec<String>, FerrisWheelError> {
+        let layers = self.compile()?;
+        let mut unassigned: Vec<String> = graph
+            .node_weights()
+            .map(|node| node.name())
+            .filter(|name| {
+                !layers
+                    .iter()
+                    .any(|(_, patterns)| patterns.iter().any(|p| p.matches(name)))
+            })
+            .map(str::to_string)
+            .collect();
+        unassigned.sort();
+        unassigned.dedup();
+
+        Ok(unassigned)
+    }
+
+    /// The layer of each workspace in `graph` that matches one, by
+    /// workspace name
+    pub fn assignments(
+        &self,
+        graph: &DiGraph<WorkspaceNode, DependencyEdge>,
+    ) -> Result<BTreeMap<String, String>, FerrisWheelError> {
+        let layers = self.compile()?;
+        Ok(graph
+            .node_weights()
+            .filter_map(|node| {
+                layers
+                    .iter()
+                    .find(|(_, patterns)| patterns.iter().any(|p| p.matches(node.name())))
+                    .map(|(rank, _)| (node.name().to_string(), self.order[*rank].clone()))
+            })
+            .collect())
+    }
+
+    /// The ranks each layer's `allow` entry permits, indexed by rank; `None`
+    /// for layers following the ordering rule
+    fn allowed_ranks(&self) -> Result<Vec<Option<BTreeSet<usize>>>, FerrisWheelError> {
+        let rank_of = |layer: &String| {
+            self.order
+                .iter()
+                .position(|known| known == layer)
+                .ok_or_else(|| FerrisWheelError::ConfigurationError {
+                    message: format!(
+                        "Layer '{layer}' is used in [layers.allow] but missing from order"
+                    ),
+                })
+        };
+
+        let mut allowed = vec![None; self.order.len()];
+        for (layer, targets) in &self.allow {
+            allowed[rank_of(layer)?] = Some(targets.iter().map(rank_of).collect::<Result<_, _>>()?);
+        }
+        Ok(allowed)
+    }
+
+    /// Resolve each layer's globs, keyed by its position in `order`
+    fn compile(&self) -> Result<Vec<(usize, Vec<Pattern>)>, FerrisWheelError> {
+        if let Some(unknown) = self
+            .workspaces
+            .keys()
+            .find(|layer| !self.order.contains(layer))
+        {
+            return Err(FerrisWheelError::ConfigurationError {
+                message: format!("Layer '{unknown}' is assigned workspaces but missing from order"),
+            });
+        }
+
+        self.order
+            .iter()
+            .enumerate()
+            .map(|(rank, layer)| {
+                let globs = match self.workspaces.get(layer) {
+                    Some(globs) => globs.clone(),
+                    None => vec![layer.clone()],
+                };
+                let patterns = globs
+                    .iter()
+                    .map(|glob| {
+                        Pattern::new(glob).map_err(|e| FerrisWheelError::ConfigurationError {
+                            message: format!("Invalid workspace pattern '{glob}': {e}"),
+                        })
+                    })
+                    .collect::<Result<_, _>>()?;
+                Ok((rank, patterns))
+            })
+            .collect()
+    }
+}
+
+/// Accept `order` either as a list or as a `core < services < apps` chain
+fn deserialize_order<'de, D>(deserializer: D) -> Result<Vec<String>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Order {
+        List(Vec<String>),
+        Chain(String),
+    }
+
+    Ok(match Order::deserialize(deserializer)? {
+        Order::List(order) => order,
+        Order::Chain(chain) => parse_chain(&chain),
+    })
+}
+
+/// Schema of `order`: a list of layer names or a `<` chain
+fn order_schema(_: &mut SchemaGenerator) -> Schema {
+    json_schema!({
+        "anyOf": [
+            { "type": "array", "items": { "type": "string" } },
+            { "type": "string", "description": "Layers joined by `<`, lowest first" }
+        ]
+    })
+}
+
+fn parse_chain(chain: &str) -> Vec<String> {
+    chain
+        .split('<')
+        .map(str::trim)
+        .filter(|layer| !layer.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::ConfigBuilder;
+    use crate::graph::DependencyType;
+
+    fn model() -> LayerModel {
+        toml::from_str(
+            r#"
+order = ["core", "services", "apps"]
+
+[workspaces]
+core = ["core", "utils-*"]
+services = ["services-*"]
+apps = ["app-*"]
+"#,
+        )
+        .unwrap()
+    }
+
+    fn graph(edges: &[(&str, &str)]) -> DiGraph<WorkspaceNode, DependencyEdge> {
+        let mut graph = DiGraph::new();
+        let mut nodes = HashMap::new();
+        for &(from, to) in edges {
+            for name in [from, to] {
+                nodes.entry(name).or_insert_with(|| {
+                    graph.add_node(
+                        WorkspaceNode::builder()
+                            .with_name(name.to_string())
+                            .with_crates(vec![format!("{name}-lib")])
+                            .build()
+                            .unwrap(),
+                    )
+                });
+            }
+            graph.add_edge(
+                nodes[from],
+                nodes[to],
+                DependencyEdge::builder()
+                    .with_from_crate(&format!("{from}-lib"))
+                    .with_to_crate(&format!("{to}-lib"))
+                    .with_dependency_type(DependencyType::Normal)
+                    .build()
+                    .unwrap(),
+            );
+        }
+        graph
+    }
+
+    #[test]
+    fn test_upward_dependencies_are_violations() {
+        let graph = graph(&[
+            ("app-web", "services-auth"),
+            ("services-auth", "core"),
+            ("utils-fs", "core"),
+            ("core", "services-auth"),
+            ("core", "services-auth"),
+            ("utils-fs", "app-web"),
+            ("unassigned", "app-web"),
+        ]);
+
+        let violations = model().violations(&graph).unwrap();
+
+        assert_eq!(violations.len(), 2);
+        assert_eq!(violations[0].from_workspace, "core");
+        assert_eq!(violations[0].to_layer, "services");
+        assert_eq!(violations[0].edges, 2);
+        assert_eq!(violations[1].from_workspace, "utils-fs");
+        assert_eq!(violations[1].to_workspace, "app-web");
+    }
+
+    #[test]
+    fn test_allow_matrix_replaces_ordering_for_listed_layers() {
+        let mut model = model();
+        model.allow = toml::from_str(
+            r#"
+apps = ["services"]
+services = ["core", "apps"]
+"#,
+        )
+        .unwrap();
+
+        let graph = graph(&[
+            ("app-web", "app-admin"),
+            ("app-web", "services-auth"),
+            ("app-web", "core"),
+            ("services-auth", "app-web"),
+            ("core", "services-auth"),
+        ]);
+        let violations = model.violations(&graph).unwrap();
+
+        assert_eq!(violations.len(), 2);
+        assert_eq!(violations[0].from_workspace, "app-web");
+        assert_eq!(violations[0].to_layer, "core");
+        assert_eq!(violations[1].from_workspace, "core");
+        assert_eq!(violations[1].to_layer, "services");
+
+        model.allow.insert("plugins".to_string(), vec![]);
+        assert!(model.violations(&graph).is_err());
+    }
+
+    #[test]
+    fn test_layers_missing_from_order_are_rejected() {
+        let mut model = model();
+        model
+            .workspaces
+            .insert("plugins".to_string(), vec!["plugin-*".to_string()]);
+
+        assert!(model.violations(&graph(&[])).is_err());
+    }
+
+    #[test]
+    fn test_chained_order_matches_layer_names() {
+        let model: LayerModel = toml::from_str(r#"order = "core < services-* < app-*""#).unwrap();
+        assert_eq!(model, LayerModel::from_chain("core < services-* < app-*"));
+        assert_eq!(model.order, vec!["core", "services-*", "app-*"]);
+
+        let graph = graph(&[
+            ("app-web", "services-auth"),
+            ("core", "services-auth"),
+            ("unassigned", "core"),
+        ]);
+        let violations = model.violations(&graph).unwrap();
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].from_layer, "core");
+        assert_eq!(violations[0].to_layer, "services-*");
+        assert_eq!(model.unassigned(&graph).unwrap(), vec!["unassigned"]);
+    }
+}
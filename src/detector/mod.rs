@@ -10,6 +10,11 @@
 //! complexity where V is the number of vertices (workspaces) and E is the
 //! number of edges (dependencies).
 //!
+//! Each strongly connected component is reported as a single cycle by
+//! default. Because one large component can be hard to act on, the detector
+//! can instead enumerate the distinct elementary cycles inside each component
+//! using Johnson's algorithm, up to a configurable limit.
+//!
 //! ## Key Components
 //!
 //! - **CycleDetector**: Main detector that finds cycles using Tarjan's
@@ -17,6 +22,23 @@
 //! - **WorkspaceCycle**: Represents a detected cycle with participating
 //!   workspaces
 //! - **CycleEdge**: Represents a dependency edge within a cycle
+//! - **BreakPoint**: A dependency direction suggested for removal, from a
+//!   feedback arc set heuristic over each cycle
+//! - **SeverityModel**: Configurable scoring that rates each cycle as low,
+//!   medium or high severity
+//! - **CycleAllowlist**: Accepted cycles that are reported but never fail the
+//!   run, until their expiry date
+//! - **DependencyPolicy**: Whether each dependency type may close a cycle, only
+//!   warn, or is ignored
+//! - **FailureBudget**: Limits on the failing cycles a run tolerates, for
+//!   gradual cleanup
+//! - **EffortModel**: Sizes the work needed to break each cycle as S, M or L
+//! - **ArchitectureGrade**: Condenses cycle and coupling metrics into an A–F
+//!   grade
+//! - **LayerModel**: Declared architecture layers, used to find dependencies
+//!   that point to a higher layer
+//! - **cross_workspace_dev_dependencies**: Dev-dependencies crossing workspace
+//!   boundaries, for the `deny-cross-workspace-dev-deps` rule
 //!
 //! ## Example
 //!
@@ -76,6 +98,24 @@
 //! # }
 //! ```
 
+mod allowlist;
+mod boundaries;
+mod budget;
 mod detector_impl;
+mod effort;
+mod feedback;
+mod grade;
+mod layers;
+mod policy;
+mod severity;
 
+pub use allowlist::*;
+pub use boundaries::*;
+pub use budget::*;
 pub use detector_impl::*;
+pub use effort::*;
+pub use feedback::*;
+pub use grade::*;
+pub use layers::*;
+pub use policy::*;
+pub use severity::*;
@@ -0,0 +1,161 @@
+//! Per-dependency-type cycle policy
+//!
+//! The `[policy]` section of `.ferris-wheel.toml` decides, for each
+//! dependency type, whether its edges may close a cycle:
+//!
+//! - `deny` (the default): cycles through these edges are reported and fail the
+//!   run as the severity model decides
+//! - `warn`: cycles are still reported, but a cycle that only exists because of
+//!   these edges never fails the run
+//! - `ignore`: the edges are left out of cycle detection altogether, like
+//!   `--exclude-dev` and `--exclude-build`; normal dependencies can't be
+//!   ignored
+
+use petgraph::algo::is_cyclic_directed;
+use petgraph::graphmap::DiGraphMap;
+use schemars::JsonSchema;
+use serde::Deserialize;
+
+use super::WorkspaceCycle;
+use crate::error::FerrisWheelError;
+use crate::graph::DependencyType;
+
+/// What edges of one dependency type may do in a cycle
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum CycleRule {
+    /// Cycles through these edges can fail the run
+    #[default]
+    Deny,
+    /// Cycles that need these edges are reported but never fail the run
+    Warn,
+    /// These edges are left out of cycle detection
+    Ignore,
+}
+
+/// Rule for each dependency type (`[policy]`)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, JsonSchema)]
+#[serde(default, deny_unknown_fields)]
+pub struct DependencyPolicy {
+    pub normal: CycleRule,
+    pub build: CycleRule,
+    pub dev: CycleRule,
+}
+
+impl DependencyPolicy {
+    /// Rule for dependencies of `kind`
+    pub fn rule(&self, kind: DependencyType) -> CycleRule {
+        match kind {
+            DependencyType::Normal => self.normal,
+            DependencyType::Build => self.build,
+            DependencyType::Dev => self.dev,
+        }
+    }
+
+    /// The policy, unless it ignores normal dependencies, which cycle
+    /// detection can't leave out
+    pub fn validated(self) -> Result<Self, FerrisWheelError> {
+        if self.normal == CycleRule::Ignore {
+            return Err(FerrisWheelError::ConfigurationError {
+                message: "Invalid [policy]: normal dependencies can be `deny` or `warn`, not \
+                          `ignore`"
+                    .to_string(),
+            });
+        }
+        Ok(self)
+    }
+
+    /// Whether dependencies of `kind` are left out of cycle detection
+    pub fn ignores(&self, kind: DependencyType) -> bool {
+        self.rule(kind) == CycleRule::Ignore
+    }
+
+    /// Whether `cycle` only exists because of edges whose type is `warn`
+    ///
+    /// This is the case when the cycle's workspaces no longer form a cycle
+    /// once those edges are removed.
+    pub fn only_warns(&self, cycle: &WorkspaceCycle) -> bool {
+        let warns = |dependency_type: &str| {
+            DependencyType::from_name(dependency_type)
+                .is_some_and(|kind| self.rule(kind) == CycleRule::Warn)
+        };
+        if !cycle
+            .edges()
+            .iter()
+            .any(|edge| warns(edge.dependency_type()))
+        {
+            return false;
+        }
+
+        let remaining: DiGraphMap<&str, ()> = cycle
+            .edges()
+            .iter()
+            .filter(|edge| !warns(edge.dependency_type()))
+            .map(|edge| (edge.from_workspace(), edge.to_workspace()))
+            .collect();
+        !is_cyclic_directed(&remaining)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cycle(edges: &[(&str, &str, &str)]) -> WorkspaceCycle {
+        let (last, rest) = edges.split_last().unwrap();
+        let mut builder = WorkspaceCycle::builder()
+            .with_workspace_names(vec!["a".to_string(), "b".to_string(), "c".to_string()])
+            .add_edge();
+        for (from, to, dependency_type) in rest {
+            builder = builder
+                .from_workspace(from)
+                .to_workspace(to)
+                .from_crate(from)
+                .to_crate(to)
+                .dependency_type(dependency_type)
+                .add_edge()
+                .unwrap();
+        }
+        let (from, to, dependency_type) = last;
+        builder
+            .from_workspace(from)
+            .to_workspace(to)
+            .from_crate(from)
+            .to_crate(to)
+            .dependency_type(dependency_type)
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_only_warns_when_warn_edges_close_the_cycle() {
+        let policy: DependencyPolicy = toml::from_str("dev = \"warn\"").unwrap();
+        assert_eq!(policy.normal, CycleRule::Deny);
+
+        // The dev edge closes the only loop
+        assert!(policy.only_warns(&cycle(&[
+            ("a", "b", "Normal"),
+            ("b", "c", "Normal"),
+            ("c", "a", "Dev"),
+        ])));
+        // a ↔ b stays a cycle without the dev edge
+        assert!(!policy.only_warns(&cycle(&[
+            ("a", "b", "Normal"),
+            ("b", "a", "Normal"),
+            ("b", "c", "Normal"),
+            ("c", "a", "Dev"),
+        ])));
+        // No dev edge at all
+        assert!(!policy.only_warns(&cycle(&[("a", "b", "Normal"), ("b", "a", "Build")])));
+        assert!(
+            toml::from_str::<DependencyPolicy>("normal = \"ignore\"")
+                .unwrap()
+                .validated()
+                .is_err()
+        );
+        assert!(
+            !DependencyPolicy::default()
+                .only_warns(&cycle(&[("a", "b", "Normal"), ("b", "a", "Dev"),]))
+        );
+    }
+}
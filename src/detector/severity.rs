@@ -0,0 +1,252 @@
+//! Cycle severity scoring
+//!
+//! A single [`SeverityModel`] classifies every cycle so that reports, exit
+//! codes and rendered graphs agree on how serious a cycle is. The model is
+//! configured through the `[severity]` section of `.ferris-wheel.toml`.
+//!
+//! A cycle is rated by two measures:
+//!
+//! - the number of workspaces it spans, compared against `medium-workspaces`
+//!   and `high-workspaces`
+//! - the weighted share of its edges, where each edge contributes the weight of
+//!   its dependency type and the sum is divided by the edge count, compared
+//!   against `medium-share` and `high-share`
+//!
+//! Whichever measure rates higher wins. With the default weights only normal
+//! dependencies count, so a cycle is high severity when it spans five or more
+//! workspaces or normal dependencies outnumber dev and build dependencies.
+
+use std::fmt;
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use super::WorkspaceCycle;
+
+/// How serious a dependency cycle is
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    Serialize,
+    Deserialize,
+    JsonSchema,
+    clap::ValueEnum,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum CycleSeverity {
+    /// Few workspaces, mostly dev/build dependencies
+    Low,
+    /// Several workspaces or a mix of dependency types
+    Medium,
+    /// Many workspaces or mostly normal dependencies
+    High,
+}
+
+impl fmt::Display for CycleSeverity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{self:?}")
+    }
+}
+
+/// Weight of each dependency type when scoring a cycle's edges
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize, JsonSchema)]
+#[serde(default, deny_unknown_fields)]
+pub struct DependencyWeights {
+    pub normal: f64,
+    pub build: f64,
+    pub dev: f64,
+}
+
+impl Default for DependencyWeights {
+    fn default() -> Self {
+        Self {
+            normal: 1.0,
+            build: 0.0,
+            dev: 0.0,
+        }
+    }
+}
+
+/// Configurable model assigning a [`CycleSeverity`] to each cycle
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize, JsonSchema)]
+#[serde(default, deny_unknown_fields, rename_all = "kebab-case")]
+pub struct SeverityModel {
+    /// Weight of each dependency type
+    pub weights: DependencyWeights,
+    /// Cycles spanning at least this many workspaces are medium severity
+    pub medium_workspaces: usize,
+    /// Cycles spanning at least this many workspaces are high severity
+    pub high_workspaces: usize,
+    /// Cycles whose weighted edge share exceeds this are medium severity
+    pub medium_share: f64,
+    /// Cycles whose weighted edge share exceeds this are high severity
+    pub high_share: f64,
+    /// Lowest severity that fails `--error-on-cycles`
+    pub fail_at: CycleSeverity,
+}
+
+impl Default for SeverityModel {
+    fn default() -> Self {
+        Self {
+            weights: DependencyWeights::default(),
+            medium_workspaces: 3,
+            high_workspaces: 5,
+            medium_share: 0.0,
+            high_share: 0.5,
+            fail_at: CycleSeverity::Low,
+        }
+    }
+}
+
+impl SeverityModel {
+    /// Classify a cycle
+    pub fn classify(&self, cycle: &WorkspaceCycle) -> CycleSeverity {
+        let workspace_count = cycle.workspace_names().len();
+        let share = self.weighted_share(cycle);
+
+        if workspace_count >= self.high_workspaces || share > self.high_share {
+            CycleSeverity::High
+        } else if workspace_count >= self.medium_workspaces || share > self.medium_share {
+            CycleSeverity::Medium
+        } else {
+            CycleSeverity::Low
+        }
+    }
+
+    /// Whether a cycle is severe enough to fail the run
+    pub fn is_failure(&self, cycle: &WorkspaceCycle) -> bool {
+        self.classify(cycle) >= self.fail_at
+    }
+
+    fn weighted_share(&self, cycle: &WorkspaceCycle) -> f64 {
+        let edges = cycle.edges();
+        if edges.is_empty() {
+            return 0.0;
+        }
+
+        let total: f64 = edges
+            .iter()
+            .map(
+                |edge| match edge.dependency_type().to_ascii_lowercase().as_str() {
+                    "normal" => self.weights.normal,
+                    "build" => self.weights.build,
+                    "dev" => self.weights.dev,
+                    _ => 0.0,
+                },
+            )
+            .sum();
+
+        total / edges.len() as f64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cycle(workspaces: usize, dependency_types: &[&str]) -> WorkspaceCycle {
+        let names: Vec<String> = (0..workspaces).map(|i| format!("ws-{i}")).collect();
+        let (last, rest) = dependency_types.split_last().unwrap();
+
+        let mut builder = WorkspaceCycle::builder()
+            .with_workspace_names(names)
+            .add_edge();
+        for (i, dependency_type) in rest.iter().enumerate() {
+            builder = builder
+                .from_workspace("ws-0")
+                .to_workspace("ws-1")
+                .from_crate(&format!("crate-{i}"))
+                .to_crate("target")
+                .dependency_type(dependency_type)
+                .add_edge()
+                .unwrap();
+        }
+        builder
+            .from_workspace("ws-1")
+            .to_workspace("ws-0")
+            .from_crate("back")
+            .to_crate("crate-0")
+            .dependency_type(last)
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_default_model() {
+        let model = SeverityModel::default();
+
+        assert_eq!(
+            model.classify(&cycle(2, &["Dev", "Dev"])),
+            CycleSeverity::Low
+        );
+        assert_eq!(
+            model.classify(&cycle(2, &["Normal", "Dev"])),
+            CycleSeverity::Medium
+        );
+        assert_eq!(
+            model.classify(&cycle(3, &["Dev", "Build"])),
+            CycleSeverity::Medium
+        );
+        assert_eq!(
+            model.classify(&cycle(2, &["Normal", "Normal", "Dev"])),
+            CycleSeverity::High
+        );
+        assert_eq!(
+            model.classify(&cycle(5, &["Dev", "Dev"])),
+            CycleSeverity::High
+        );
+    }
+
+    #[test]
+    fn test_custom_weights_and_thresholds() {
+        let model = SeverityModel {
+            weights: DependencyWeights {
+                normal: 1.0,
+                build: 1.0,
+                dev: 0.5,
+            },
+            medium_workspaces: 10,
+            high_workspaces: 20,
+            medium_share: 0.25,
+            high_share: 0.9,
+            fail_at: CycleSeverity::High,
+        };
+
+        assert_eq!(
+            model.classify(&cycle(2, &["Dev", "Dev"])),
+            CycleSeverity::Medium
+        );
+        assert_eq!(
+            model.classify(&cycle(2, &["Build", "Build"])),
+            CycleSeverity::High
+        );
+        assert!(!model.is_failure(&cycle(2, &["Dev", "Dev"])));
+        assert!(model.is_failure(&cycle(2, &["Build", "Normal"])));
+    }
+
+    #[test]
+    fn test_model_from_toml() {
+        let model: SeverityModel = toml::from_str(
+            r#"
+high-workspaces = 4
+fail-at = "medium"
+
+[weights]
+dev = 0.25
+"#,
+        )
+        .unwrap();
+
+        assert_eq!(model.high_workspaces, 4);
+        assert_eq!(model.medium_workspaces, 3);
+        assert_eq!(model.fail_at, CycleSeverity::Medium);
+        assert_eq!(model.weights.dev, 0.25);
+        assert_eq!(model.weights.normal, 1.0);
+    }
+}
@@ -70,6 +70,96 @@ pub enum FerrisWheelError {
         help("This may be an internal error with graph processing")
     )]
     GraphError { message: String },
+
+    #[error("Git error: {message}")]
+    #[diagnostic(
+        code(ferris_wheel::git_error),
+        help("Make sure you are inside a git repository and the revision exists")
+    )]
+    GitError { message: String },
+
+    #[error("Metadata provider '{provider}' failed: {message}")]
+    #[diagnostic(
+        code(ferris_wheel::metadata_error),
+        help("Check the [metadata] section of .ferris-wheel.toml")
+    )]
+    MetadataError { provider: String, message: String },
+
+    #[error("cargo metadata failed for '{path}': {message}")]
+    #[diagnostic(
+        code(ferris_wheel::cargo_metadata_error),
+        help("Run `cargo metadata --no-deps` in the workspace to see Cargo's own error")
+    )]
+    CargoMetadataError { path: PathBuf, message: String },
+
+    #[error("Invalid cycle edge: missing {}", missing.join(", "))]
+    #[diagnostic(
+        code(ferris_wheel::invalid_cycle_edge),
+        help("Set every missing field on the CycleEdgeBuilder before building")
+    )]
+    InvalidCycleEdge { missing: Vec<&'static str> },
+
+    #[error(
+        "Invalid dependency type '{name}': expected one of {}",
+        crate::graph::DependencyType::ALL.map(|kind| kind.as_str()).join(", ")
+    )]
+    #[diagnostic(
+        code(ferris_wheel::invalid_dependency_type),
+        help("Dependency type names are matched in any case")
+    )]
+    InvalidDependencyType { name: String },
+
+    #[error("No layout engine found to render {format} images")]
+    #[diagnostic(
+        code(ferris_wheel::layout_engine_missing),
+        help(
+            "Install Graphviz (`dot`) or D2 (`d2`) and make sure it is on PATH, or use --format \
+             dot/d2 and render the output yourself"
+        )
+    )]
+    LayoutEngineMissing { format: &'static str },
+
+    #[error("Layout engine '{engine}' failed: {message}")]
+    #[diagnostic(
+        code(ferris_wheel::layout_engine_failed),
+        help("Try rendering the --format dot or d2 output with the engine directly")
+    )]
+    LayoutEngineFailed {
+        engine: &'static str,
+        message: String,
+    },
+
+    #[error("Discovered more than {limit} workspaces under '{path}'")]
+    #[diagnostic(
+        code(ferris_wheel::too_many_workspaces),
+        help(
+            "Most were found under {hotspots}. Discovery has probably wandered into vendored \
+             sources or test fixtures: narrow the paths to analyze, or raise --max-workspaces if \
+             this many is expected"
+        )
+    )]
+    TooManyWorkspaces {
+        limit: usize,
+        path: PathBuf,
+        hotspots: String,
+    },
+
+    #[error("Failed to watch for manifest changes: {message}")]
+    #[diagnostic(
+        code(ferris_wheel::watch_error),
+        help(
+            "Check that the watched paths exist. On Linux, very large trees may need a higher \
+             fs.inotify.max_user_watches"
+        )
+    )]
+    WatchError { message: String },
+
+    #[error("Failed to edit manifest '{path}': {message}")]
+    #[diagnostic(
+        code(ferris_wheel::manifest_edit_error),
+        help("Check that the manifest is valid TOML")
+    )]
+    ManifestEditError { path: PathBuf, message: String },
 }
 
 #[cfg(test)]
@@ -131,6 +221,39 @@ mod tests {
         assert_eq!(error_str, "Graph error: Cycle detected in graph");
     }
 
+    #[test]
+    fn test_git_error() {
+        let error = FerrisWheelError::GitError {
+            message: "unknown revision 'v9'".to_string(),
+        };
+
+        assert_eq!(error.to_string(), "Git error: unknown revision 'v9'");
+    }
+
+    #[test]
+    fn test_invalid_cycle_edge() {
+        let error = FerrisWheelError::InvalidCycleEdge {
+            missing: vec!["from_crate", "dependency_type"],
+        };
+
+        assert_eq!(
+            error.to_string(),
+            "Invalid cycle edge: missing from_crate, dependency_type"
+        );
+    }
+
+    #[test]
+    fn test_invalid_dependency_type() {
+        let error = FerrisWheelError::InvalidDependencyType {
+            name: "optional".to_string(),
+        };
+
+        assert_eq!(
+            error.to_string(),
+            "Invalid dependency type 'optional': expected one of Normal, Dev, Build"
+        );
+    }
+
     #[test]
     fn test_error_codes() {
         // Test that all error variants have proper diagnostic codes
@@ -1,24 +1,43 @@
 //! Executor for the affected command
 
+use std::collections::HashSet;
 use std::fmt::Write;
 
-use miette::{Result, WrapErr};
+use miette::{IntoDiagnostic, Result, WrapErr};
 
 use crate::analyzer::WorkspaceAnalyzer;
-use crate::cli::OutputFormat;
-use crate::commands::affected::{AffectedAnalysis, AffectedJsonReport};
+use crate::cli::RipplesFormat;
+use crate::commands::affected::{
+    AffectedAnalysis, AffectedJsonReport, CrateId, base_revision, cargo_commands,
+    cargo_package_args, changed_files_from_git, github_matrix, impact_tree, package_specs,
+    read_file_list,
+};
 use crate::config::AffectedConfig;
 use crate::error::FerrisWheelError;
 use crate::executors::CommandExecutor;
 use crate::graph::DependencyGraphBuilder;
 use crate::progress::ProgressReporter;
+use crate::reports::output::{ReportOutput, report_written};
+use crate::utils::string::pluralize;
 
 pub struct AffectedExecutor;
 
 impl CommandExecutor for AffectedExecutor {
     type Config = AffectedConfig;
 
-    fn execute(config: Self::Config) -> Result<()> {
+    fn execute(mut config: Self::Config) -> Result<()> {
+        if let Some(since) = &config.changed_since {
+            let cwd = std::env::current_dir().into_diagnostic()?;
+            config.files = changed_files_from_git(&cwd, since)
+                .into_diagnostic()
+                .wrap_err("Failed to list changed files with git")?;
+        }
+        if let Some(path) = &config.files_from {
+            config.files = read_file_list(path)
+                .into_diagnostic()
+                .wrap_err("Failed to read the list of changed files")?;
+        }
+
         // Create progress reporter if we're in an interactive terminal
         let mut progress = if console::Term::stderr().is_term() {
             Some(ProgressReporter::new())
@@ -27,17 +46,19 @@ impl CommandExecutor for AffectedExecutor {
         };
 
         // Discover workspaces
-        let mut analyzer = WorkspaceAnalyzer::new();
+        let mut analyzer = WorkspaceAnalyzer::new()
+            .with_max_workspaces(config.max_workspaces)
+            .with_excluded_paths(config.exclude_paths.clone())
+            .with_excluded_crates(config.exclude_crates.clone())
+            .with_follow_symlinks(config.follow_symlinks)
+            .with_workspace_names(config.workspace_names.clone())
+            .with_cache(!config.no_cache);
         analyzer
             .discover_workspaces(&config.paths, progress.as_mut())
             .wrap_err("Failed to discover workspaces")?;
 
         // Build dependency graph for analysis
-        let mut graph_builder = DependencyGraphBuilder::new(
-            config.exclude_dev,
-            config.exclude_build,
-            config.exclude_target,
-        );
+        let mut graph_builder = DependencyGraphBuilder::filtered(config.filter.clone());
 
         graph_builder
             .build_cross_workspace_graph(
@@ -50,32 +71,65 @@ impl CommandExecutor for AffectedExecutor {
             .wrap_err("Failed to build cross-workspace dependency graph")?;
 
         // Create affected analysis
-        let filter = crate::dependency_filter::DependencyFilter::new(
-            config.exclude_dev,
-            config.exclude_build,
-            config.exclude_target,
-        );
-        let affected_analysis = AffectedAnalysis::new(
+        let mut affected_analysis = AffectedAnalysis::new(
             analyzer.workspaces(),
             analyzer.crate_path_to_workspace(),
-            filter,
+            config.filter.clone(),
         )?;
+        if config.narrow_workspace_manifests {
+            let base = match &config.changed_since {
+                Some(since) => {
+                    let cwd = std::env::current_dir().into_diagnostic()?;
+                    base_revision(&cwd, since)
+                        .into_diagnostic()
+                        .wrap_err("Failed to resolve the base revision")?
+                }
+                None => "HEAD".to_string(),
+            };
+            affected_analysis = affected_analysis.with_manifest_base(Some(base));
+        }
 
         // Analyze affected files
-        let result = affected_analysis.analyze_affected_files(&config.files);
+        let result = affected_analysis.analyze_affected_files(&config.files, config.max_depth);
 
         // Generate report based on format
         let report = match config.format {
-            OutputFormat::Json => generate_json_report(&result, &affected_analysis, &config)?,
-            OutputFormat::Human => generate_human_report(&result, &affected_analysis, &config)?,
-            OutputFormat::GitHub => generate_github_report(&result, &config)?,
-            OutputFormat::Junit => generate_junit_report(&result, &config)?,
+            RipplesFormat::Json => generate_json_report(&result, &affected_analysis, &config)?,
+            RipplesFormat::Human => generate_human_report(&result, &affected_analysis, &config)?,
+            RipplesFormat::GitHub => generate_github_report(&result, &config)?,
+            RipplesFormat::Junit => generate_junit_report(&result, &config)?,
+            RipplesFormat::CargoArgs => generate_cargo_args_report(&result, &config),
+            RipplesFormat::CargoCommands => {
+                generate_cargo_commands_report(&result, &affected_analysis, &config)?
+            }
+            RipplesFormat::GitHubMatrix => {
+                generate_github_matrix_report(&result, &affected_analysis, &config)?
+            }
+            RipplesFormat::Tree => impact_tree(&result, &affected_analysis, config.direct_only),
+            RipplesFormat::PackageSpecs => {
+                package_specs(selected_crates(&result, &config)).join("\n")
+            }
         };
 
-        println!("{report}");
+        // An empty cargo-args, cargo-commands or package-specs report must not
+        // become a blank line, which a shell loop would turn into a cargo
+        // invocation covering everything; an output file is still replaced
+        if !report.is_empty() || config.output.is_some() {
+            let rendered = if report.is_empty() {
+                report
+            } else {
+                format!("{report}\n")
+            };
+            ReportOutput::write_rendered(config.output.as_deref(), &rendered)
+                .map(report_written)
+                .into_diagnostic()
+                .wrap_err("Failed to write report")?;
+        }
 
         // Report unmatched files
-        if !result.unmatched_files.is_empty() && config.format == OutputFormat::Human {
+        if !result.unmatched_files.is_empty()
+            && matches!(config.format, RipplesFormat::Human | RipplesFormat::Tree)
+        {
             eprintln!("\n⚠️  Warning: Could not map the following files to any crate:");
             for file in &result.unmatched_files {
                 eprintln!("  - {file}");
@@ -114,6 +168,8 @@ fn generate_json_report(
                 .into_iter()
                 .filter(|ws| result.directly_affected_workspaces.contains(&ws.name))
                 .collect(),
+            affected_for_build: direct_crates.clone(),
+            affected_for_tests: direct_crates.clone(),
             directly_affected_crates: direct_crates,
             directly_affected_workspaces: full_report.directly_affected_workspaces,
         }
@@ -183,28 +239,54 @@ fn generate_human_report(
 
     // All affected (including reverse dependencies)
     if !config.direct_only {
-        writeln!(
-            output,
-            "\n🔄 All affected (including reverse dependencies):"
-        )?;
+        match config.max_depth {
+            Some(max_depth) => writeln!(
+                output,
+                "\n🔄 All affected (including reverse dependencies up to {max_depth} {} away):",
+                pluralize("step", max_depth)
+            )?,
+            None => writeln!(
+                output,
+                "\n🔄 All affected (including reverse dependencies):"
+            )?,
+        }
         if config.show_crates {
             writeln!(output, "  Crates: {}", result.all_affected_crates.len())?;
+            writeln!(
+                output,
+                "  Builds affected: {}, tests affected: {}",
+                result.affected_for_build.len(),
+                result.affected_for_tests.len()
+            )?;
             let mut sorted_all_crates: Vec<_> = result
                 .all_affected_crates
                 .iter()
                 .filter(|crate_id| !result.directly_affected_crates.contains(*crate_id))
                 .map(|crate_id| {
+                    let chain: Vec<&str> = result
+                        .chain(crate_id)
+                        .into_iter()
+                        .map(|id| id.name())
+                        .collect();
+                    let scope = if result.affected_for_build.contains(crate_id) {
+                        ""
+                    } else if result.affected_for_tests.contains(crate_id) {
+                        " [tests only]"
+                    } else {
+                        " [through dev-dependencies of dependencies]"
+                    };
                     (
                         analysis
                             .workspace_name(crate_id)
                             .unwrap_or_else(|| "unknown".to_string()),
                         crate_id.name().to_string(),
+                        format!("{}{scope}", chain[..chain.len() - 1].join(" → ")),
                     )
                 })
                 .collect();
             sorted_all_crates.sort_by(|a, b| a.1.cmp(&b.1).then_with(|| a.0.cmp(&b.0)));
-            for (workspace, crate_name) in sorted_all_crates {
-                writeln!(output, "    - {crate_name} ({workspace})")?;
+            for (workspace, crate_name, via) in sorted_all_crates {
+                writeln!(output, "    - {crate_name} ({workspace}) via {via}")?;
             }
         }
         writeln!(
@@ -232,6 +314,60 @@ fn generate_human_report(
     Ok(output)
 }
 
+/// The crates the machine-readable formats list
+fn selected_crates<'a>(
+    result: &'a crate::commands::affected::AffectedResult,
+    config: &AffectedConfig,
+) -> &'a HashSet<CrateId> {
+    if config.direct_only {
+        &result.directly_affected_crates
+    } else {
+        &result.all_affected_crates
+    }
+}
+
+fn generate_cargo_args_report(
+    result: &crate::commands::affected::AffectedResult,
+    config: &AffectedConfig,
+) -> String {
+    cargo_package_args(
+        selected_crates(result, config)
+            .iter()
+            .map(|crate_id| crate_id.name()),
+        crate::constants::ripples::MAX_CARGO_ARGS_LINE_LENGTH,
+    )
+    .join("\n")
+}
+
+fn generate_cargo_commands_report(
+    result: &crate::commands::affected::AffectedResult,
+    analysis: &AffectedAnalysis,
+    config: &AffectedConfig,
+) -> Result<String> {
+    let base = std::env::current_dir().into_diagnostic()?;
+    let base = base.canonicalize().unwrap_or(base);
+    Ok(cargo_commands(
+        analysis,
+        selected_crates(result, config),
+        &config.cargo_subcommand,
+        &base,
+        crate::constants::ripples::MAX_CARGO_ARGS_LINE_LENGTH,
+    )
+    .join("\n"))
+}
+
+fn generate_github_matrix_report(
+    result: &crate::commands::affected::AffectedResult,
+    analysis: &AffectedAnalysis,
+    config: &AffectedConfig,
+) -> Result<String> {
+    let base = std::env::current_dir().into_diagnostic()?;
+    let base = base.canonicalize().unwrap_or(base);
+    let matrix = github_matrix(analysis, selected_crates(result, config), &base);
+    // Kept on one line so it can be written to $GITHUB_OUTPUT as is
+    serde_json::to_string(&matrix).into_diagnostic()
+}
+
 fn generate_github_report(
     result: &crate::commands::affected::AffectedResult,
     config: &AffectedConfig,
@@ -1,18 +1,24 @@
 //! Analyze command executor
 
+use std::io::Write;
+
 use console::style;
 use miette::{Result, WrapErr};
 
 use crate::analyzer::WorkspaceAnalyzer;
 use crate::cli::OutputFormat;
 use crate::config::AnalyzeCrateConfig;
-use crate::detector::CycleDetector;
+use crate::detector::{CrateLines, CycleDetector};
+use crate::error::FerrisWheelError;
 use crate::executors::CommandExecutor;
 use crate::graph::DependencyGraphBuilder;
 use crate::progress::ProgressReporter;
+use crate::reports::json::{RunFilters, RunMetadata};
+use crate::reports::links::{self, ManifestLinks};
+use crate::reports::output::{ReportOutput, report_written};
 use crate::reports::{
     GitHubReportGenerator, HumanReportGenerator, JsonReportGenerator, JunitReportGenerator,
-    ReportGenerator,
+    ReportGenerator, github,
 };
 
 pub struct AnalyzeExecutor;
@@ -35,7 +41,15 @@ impl CommandExecutor for AnalyzeExecutor {
         };
 
         // Discover and analyze workspaces
-        let mut analyzer = WorkspaceAnalyzer::new();
+        let mut analyzer = WorkspaceAnalyzer::new()
+            .with_backend(config.analyzer.backend)
+            .with_features(config.analyzer.features.clone())
+            .with_workspace_names(config.workspace_names.clone())
+            .with_max_workspaces(config.max_workspaces)
+            .with_excluded_paths(config.exclude_paths.clone())
+            .with_excluded_crates(config.exclude_crates.clone())
+            .with_follow_symlinks(config.follow_symlinks)
+            .with_cache(!config.no_cache);
         analyzer
             .discover_workspaces(&config.paths, progress.as_mut())
             .wrap_err("Failed to discover and analyze workspaces")?;
@@ -51,7 +65,8 @@ impl CommandExecutor for AnalyzeExecutor {
             config.exclude_dev,
             config.exclude_build,
             config.exclude_target,
-        );
+        )
+        .with_target(config.target);
 
         if config.intra_workspace {
             graph_builder
@@ -74,7 +89,11 @@ impl CommandExecutor for AnalyzeExecutor {
             p.start_cycle_detection();
         }
 
-        let mut detector = CycleDetector::new();
+        let mut detector = match config.elementary_cycles {
+            Some(limit) => CycleDetector::new().with_elementary_cycles(limit),
+            None => CycleDetector::new(),
+        }
+        .with_severity_model(config.severity_model);
         detector
             .detect_cycles(graph_builder.graph())
             .wrap_err("Failed to detect dependency cycles")?;
@@ -83,6 +102,15 @@ impl CommandExecutor for AnalyzeExecutor {
             p.finish_cycle_detection(detector.cycle_count());
         }
 
+        if detector.is_truncated() {
+            eprintln!(
+                "{} Stopped after {} elementary cycles in at least one component; raise \
+                 --max-elementary-cycles to see more",
+                style("⚠").yellow(),
+                config.elementary_cycles.unwrap_or_default()
+            );
+        }
+
         // Filter cycles that involve the specified crate
         let relevant_cycles: Vec<_> = detector
             .cycles()
@@ -114,37 +142,86 @@ impl CommandExecutor for AnalyzeExecutor {
 
         // Generate report based on format
         // For now, we'll create a custom detector with only the relevant cycles
-        let mut filtered_detector = CycleDetector::new();
+        let mut filtered_detector = CycleDetector::new()
+            .with_severity_model(config.severity_model)
+            .with_effort_model(config.effort_model);
         for cycle in relevant_cycles {
             filtered_detector.add_cycle(cycle);
         }
+        if config.effort_model.lines_per_point.is_some() {
+            let lines = CrateLines::count(analyzer.workspaces(), filtered_detector.cycles());
+            filtered_detector = filtered_detector.with_crate_lines(lines);
+        }
 
+        let mut out = ReportOutput::open(config.output.as_deref())
+            .wrap_err("Failed to open the report output")?;
         let report_result = match config.format {
             OutputFormat::Human => {
-                let generator = HumanReportGenerator::new(config.max_cycles);
-                generator.generate_report(&filtered_detector)
+                let mut generator = HumanReportGenerator::new().with_verbosity(config.verbosity);
+                if out.is_stdout() && links::stdout_supports_hyperlinks() {
+                    generator = generator.with_links(links::manifest_links(
+                        &analyzer,
+                        config.url_template.as_ref(),
+                    ));
+                }
+                generator.write_report(&filtered_detector, &mut out)
             }
             OutputFormat::Json => {
-                let generator = JsonReportGenerator::new();
-                generator.generate_report(&filtered_detector)
+                let generator = JsonReportGenerator::new().with_run(run_metadata(&config));
+                generator.write_report(&filtered_detector, &mut out)
             }
             OutputFormat::Junit => {
-                let generator = JunitReportGenerator::new();
-                generator.generate_report(&filtered_detector)
+                let generator = JunitReportGenerator::new()
+                    .with_manifest_locations(ManifestLinks::from_workspaces(analyzer.workspaces()));
+                generator.write_report(&filtered_detector, &mut out)
             }
             OutputFormat::GitHub => {
-                let generator = GitHubReportGenerator::new();
-                generator.generate_report(&filtered_detector)
+                let mut generator = GitHubReportGenerator::new().with_file_annotations(
+                    ManifestLinks::from_workspaces(analyzer.workspaces()),
+                    github::annotation_root(),
+                );
+                if config.url_template.is_some() {
+                    generator = generator.with_links(links::manifest_links(
+                        &analyzer,
+                        config.url_template.as_ref(),
+                    ));
+                }
+                generator.write_report(&filtered_detector, &mut out)
             }
         };
 
-        match report_result {
-            Ok(report) => println!("{report}"),
-            Err(e) => {
-                return Err(e).wrap_err("Failed to generate report for crate analysis");
-            }
-        }
+        report_result
+            .and_then(|()| writeln!(out).map_err(FerrisWheelError::from))
+            .and_then(|()| out.finish())
+            .map(report_written)
+            .wrap_err("Failed to generate report for crate analysis")?;
 
         Ok(())
     }
 }
+
+/// How this run was invoked, for the JSON report
+fn run_metadata(config: &AnalyzeCrateConfig) -> RunMetadata {
+    RunMetadata::new(
+        config.paths.clone(),
+        RunFilters {
+            exclude_dev: config.exclude_dev,
+            exclude_build: config.exclude_build,
+            exclude_target: config.exclude_target,
+            target: config.target.as_ref().map(ToString::to_string),
+            exclude_paths: config
+                .exclude_paths
+                .iter()
+                .map(ToString::to_string)
+                .collect(),
+            exclude_crates: config
+                .exclude_crates
+                .iter()
+                .map(ToString::to_string)
+                .collect(),
+            crate_name: Some(config.crate_name.clone()),
+            ..RunFilters::default()
+        }
+        .with_features(&config.analyzer.features),
+    )
+}
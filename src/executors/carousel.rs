@@ -0,0 +1,96 @@
+//! Carousel command executor
+
+use console::style;
+use miette::{IntoDiagnostic, Result, WrapErr};
+
+use crate::analyzer::WorkspaceAnalyzer;
+use crate::cli::OutputFormat;
+use crate::commands::carousel::{CarouselReportGenerator, LayerReport};
+use crate::config::CarouselConfig;
+use crate::executors::CommandExecutor;
+use crate::graph::DependencyGraphBuilder;
+use crate::progress::ProgressReporter;
+use crate::reports::output::{ReportOutput, report_written};
+
+pub struct CarouselExecutor;
+
+impl CommandExecutor for CarouselExecutor {
+    type Config = CarouselConfig;
+
+    fn execute(config: Self::Config) -> Result<()> {
+        eprintln!(
+            "{} Checking workspace dependencies against {} declared layers...\n",
+            style("🎠").cyan(),
+            config.layers.order.len()
+        );
+
+        // Create progress reporter if we're in an interactive terminal
+        let mut progress = if console::Term::stderr().is_term() {
+            Some(ProgressReporter::new())
+        } else {
+            None
+        };
+
+        // Discover and analyze workspaces
+        let mut analyzer = WorkspaceAnalyzer::new()
+            .with_backend(config.analyzer.backend)
+            .with_features(config.analyzer.features.clone())
+            .with_workspace_names(config.workspace_names.clone())
+            .with_max_workspaces(config.max_workspaces)
+            .with_excluded_paths(config.exclude_paths.clone())
+            .with_excluded_crates(config.exclude_crates.clone())
+            .with_follow_symlinks(config.follow_symlinks)
+            .with_cache(!config.no_cache);
+        analyzer
+            .discover_workspaces(&config.paths, progress.as_mut())
+            .wrap_err("Failed to discover and analyze workspaces")?;
+
+        let mut graph_builder = DependencyGraphBuilder::new(
+            config.exclude_dev,
+            config.exclude_build,
+            config.exclude_target,
+        )
+        .with_target(config.target);
+        graph_builder
+            .build_cross_workspace_graph(
+                analyzer.workspaces(),
+                analyzer.crate_to_workspace(),
+                analyzer.crate_path_to_workspace(),
+                analyzer.crate_to_paths(),
+                progress.as_ref(),
+            )
+            .wrap_err("Failed to build dependency graph")?;
+
+        let report = LayerReport::check(&config.layers, graph_builder.graph())
+            .into_diagnostic()
+            .wrap_err("Failed to check dependencies against declared layers")?;
+
+        let generator = CarouselReportGenerator::new();
+        let report_result = match config.format {
+            OutputFormat::Human => generator.generate_human_report(&report),
+            OutputFormat::Json => generator.generate_json_report(&report),
+            OutputFormat::Junit => generator.generate_junit_report(&report),
+            OutputFormat::GitHub => generator.generate_github_report(&report),
+        };
+
+        match report_result {
+            Ok(output) => {
+                ReportOutput::write_rendered(config.output.as_deref(), &output)
+                    .map(report_written)
+                    .into_diagnostic()
+                    .wrap_err("Failed to write carousel report")?;
+            }
+            Err(e) => {
+                return Err(e)
+                    .into_diagnostic()
+                    .wrap_err("Failed to generate carousel report");
+            }
+        }
+
+        if report.has_violations() {
+            crate::timings::exit(1);
+        }
+
+        Ok(())
+    }
+}
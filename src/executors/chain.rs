@@ -0,0 +1,98 @@
+//! Chain command executor
+
+use console::style;
+use miette::{IntoDiagnostic, Result, WrapErr};
+
+use crate::analyzer::WorkspaceAnalyzer;
+use crate::cli::OutputFormat;
+use crate::commands::chain::{ChainReportGenerator, find_crate_chains};
+use crate::config::ChainConfig;
+use crate::executors::CommandExecutor;
+use crate::graph::DependencyGraphBuilder;
+use crate::progress::ProgressReporter;
+use crate::reports::output::{ReportOutput, report_written};
+
+pub struct ChainExecutor;
+
+impl CommandExecutor for ChainExecutor {
+    type Config = ChainConfig;
+
+    fn execute(config: Self::Config) -> Result<()> {
+        eprintln!(
+            "{} Listing dependency chains from {} to {}...\n",
+            style("⛓️").cyan(),
+            style(&config.from).bold(),
+            style(&config.to).bold()
+        );
+
+        // Create progress reporter if we're in an interactive terminal
+        let mut progress = if console::Term::stderr().is_term() {
+            Some(ProgressReporter::new())
+        } else {
+            None
+        };
+
+        // Discover and analyze workspaces
+        let mut analyzer = WorkspaceAnalyzer::new()
+            .with_backend(config.analyzer.backend)
+            .with_features(config.analyzer.features.clone())
+            .with_workspace_names(config.workspace_names.clone())
+            .with_max_workspaces(config.max_workspaces)
+            .with_excluded_paths(config.exclude_paths.clone())
+            .with_excluded_crates(config.exclude_crates.clone())
+            .with_follow_symlinks(config.follow_symlinks)
+            .with_cache(!config.no_cache);
+        analyzer
+            .discover_workspaces(&config.paths, progress.as_mut())
+            .wrap_err("Failed to discover and analyze workspaces")?;
+
+        let mut graph_builder = DependencyGraphBuilder::new(
+            config.exclude_dev,
+            config.exclude_build,
+            config.exclude_target,
+        )
+        .with_target(config.target);
+        graph_builder
+            .build_crate_graph(
+                analyzer.workspaces(),
+                analyzer.crate_to_workspace(),
+                analyzer.crate_path_to_workspace(),
+                analyzer.crate_to_paths(),
+                progress.as_ref(),
+            )
+            .wrap_err("Failed to build crate-level dependency graph")?;
+
+        let chains = find_crate_chains(
+            graph_builder.graph(),
+            &config.from,
+            &config.to,
+            config.max_paths,
+        )
+        .into_diagnostic()
+        .wrap_err("Failed to list crate dependency chains")?;
+
+        let generator = ChainReportGenerator::new(&config.from, &config.to);
+        let report_result = match config.format {
+            OutputFormat::Human => generator.generate_human_report(&chains),
+            OutputFormat::Json => generator.generate_json_report(&chains),
+            OutputFormat::Junit => generator.generate_junit_report(&chains),
+            OutputFormat::GitHub => generator.generate_github_report(&chains),
+        };
+
+        match report_result {
+            Ok(report) => {
+                ReportOutput::write_rendered(config.output.as_deref(), &report)
+                    .map(report_written)
+                    .into_diagnostic()
+                    .wrap_err("Failed to write chain report")?;
+            }
+            Err(e) => {
+                return Err(e)
+                    .into_diagnostic()
+                    .wrap_err("Failed to generate chain report");
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,117 @@
+//! Changelog command executor
+
+use std::path::PathBuf;
+
+use console::style;
+use miette::{IntoDiagnostic, Result, WrapErr};
+
+use crate::analyzer::WorkspaceAnalyzer;
+use crate::commands::changelog::StructureSnapshot;
+use crate::config::ChangelogConfig;
+use crate::detector::CycleDetector;
+use crate::executors::CommandExecutor;
+use crate::git::{self, Worktree};
+use crate::graph::DependencyGraphBuilder;
+use crate::reports::output::{ReportOutput, report_written};
+
+pub struct ChangelogExecutor;
+
+impl CommandExecutor for ChangelogExecutor {
+    type Config = ChangelogConfig;
+
+    fn execute(config: Self::Config) -> Result<()> {
+        let to_label = config.to.as_deref().unwrap_or("working tree");
+        eprintln!(
+            "{} Comparing workspace structure between {} and {}...\n",
+            style("📝").cyan(),
+            style(&config.from).bold(),
+            style(to_label).bold()
+        );
+
+        let first_path = config
+            .paths
+            .first()
+            .cloned()
+            .unwrap_or_else(|| PathBuf::from("."));
+        let repo_root = git::repo_root(&first_path)
+            .into_diagnostic()
+            .wrap_err("Failed to locate the git repository")?;
+
+        let old = snapshot_revision(&config, &repo_root, &config.from)?;
+        let new = match &config.to {
+            Some(rev) => snapshot_revision(&config, &repo_root, rev)?,
+            None => snapshot_paths(&config, &config.paths)?,
+        };
+
+        let markdown = old
+            .diff(&new)
+            .to_markdown(&config.from, to_label)
+            .into_diagnostic()
+            .wrap_err("Failed to generate changelog")?;
+        ReportOutput::write_rendered(config.output.as_deref(), &markdown)
+            .map(report_written)
+            .into_diagnostic()
+            .wrap_err("Failed to write changelog")?;
+
+        Ok(())
+    }
+}
+
+fn snapshot_revision(
+    config: &ChangelogConfig,
+    repo_root: &std::path::Path,
+    rev: &str,
+) -> Result<StructureSnapshot> {
+    let worktree = Worktree::checkout(repo_root, rev)
+        .into_diagnostic()
+        .wrap_err_with(|| format!("Failed to check out revision '{rev}'"))?;
+
+    let paths: Vec<PathBuf> = config
+        .paths
+        .iter()
+        .map(|path| worktree.map_path(path))
+        .collect();
+
+    snapshot_paths(config, &paths).wrap_err_with(|| format!("Failed to analyze revision '{rev}'"))
+}
+
+fn snapshot_paths(config: &ChangelogConfig, paths: &[PathBuf]) -> Result<StructureSnapshot> {
+    let mut analyzer = WorkspaceAnalyzer::new()
+        .with_backend(config.analyzer.backend)
+        .with_features(config.analyzer.features.clone())
+        .with_workspace_names(config.workspace_names.clone())
+        .with_max_workspaces(config.max_workspaces)
+        .with_excluded_paths(config.exclude_paths.clone())
+        .with_excluded_crates(config.exclude_crates.clone())
+        .with_follow_symlinks(config.follow_symlinks)
+        .with_cache(!config.no_cache);
+    analyzer
+        .discover_workspaces(paths, None)
+        .wrap_err("Failed to discover and analyze workspaces")?;
+
+    let mut graph_builder = DependencyGraphBuilder::new(
+        config.exclude_dev,
+        config.exclude_build,
+        config.exclude_target,
+    )
+    .with_target(config.target);
+    graph_builder
+        .build_cross_workspace_graph(
+            analyzer.workspaces(),
+            analyzer.crate_to_workspace(),
+            analyzer.crate_path_to_workspace(),
+            analyzer.crate_to_paths(),
+            None,
+        )
+        .wrap_err("Failed to build cross-workspace dependency graph")?;
+
+    let mut detector = CycleDetector::new();
+    detector
+        .detect_cycles(graph_builder.graph())
+        .wrap_err("Failed to detect dependency cycles")?;
+
+    Ok(StructureSnapshot::from_graph(
+        graph_builder.graph(),
+        detector.cycles(),
+    ))
+}
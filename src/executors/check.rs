@@ -1,19 +1,38 @@
 //! Check command executor
 
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+
 use console::style;
 use miette::{IntoDiagnostic, Result, WrapErr};
+use petgraph::graph::DiGraph;
 
 use crate::analyzer::WorkspaceAnalyzer;
-use crate::cli::OutputFormat;
+use crate::cli::{CycleLevel, OutputFormat};
+use crate::commands::changelog::StructureSnapshot;
+use crate::common::Deadline;
 use crate::config::CheckCyclesConfig;
-use crate::detector::CycleDetector;
+use crate::dependency_filter::DependencyFilter;
+use crate::detector::{
+    ArchitectureGrade, CrateLines, CycleDetector, CycleEdge, FailureBudget, LayerViolation,
+    WorkspaceCycle, cross_workspace_dev_dependencies, cyclic_components,
+};
 use crate::executors::CommandExecutor;
-use crate::graph::DependencyGraphBuilder;
+use crate::graph::{DependencyEdge, DependencyGraphBuilder, GraphCache, WorkspaceNode};
 use crate::progress::ProgressReporter;
+use crate::reports::baseline::Baseline;
+use crate::reports::events::{AnalysisEvent, EventSink};
+use crate::reports::json::{RunFilters, RunMetadata};
+use crate::reports::kind_matrix::{KIND_FILTERS, KindFilterResult, KindMatrixReportGenerator};
+use crate::reports::ledger::{self, LayerTrend, LedgerEntry, TrendLedger};
+use crate::reports::links::{self, ManifestLinks};
+use crate::reports::output::{ReportOutput, report_file_name, report_written};
 use crate::reports::{
     GitHubReportGenerator, HumanReportGenerator, JsonReportGenerator, JunitReportGenerator,
-    ReportGenerator,
+    ReportGenerator, TemplateReportGenerator, github,
 };
+use crate::utils::string::pluralize;
+use crate::watch::ManifestWatcher;
 
 pub struct CheckExecutor;
 
@@ -26,6 +45,11 @@ impl CommandExecutor for CheckExecutor {
                 "{} Checking for intra-workspace dependency cycles...\n",
                 style("🎡").cyan()
             );
+        } else if config.level == CycleLevel::Crate {
+            eprintln!(
+                "{} Checking for crate-level dependency cycles...\n",
+                style("🎡").cyan()
+            );
         } else {
             eprintln!(
                 "{} Checking for inter-workspace dependency cycles...\n",
@@ -33,124 +57,953 @@ impl CommandExecutor for CheckExecutor {
             );
         }
 
-        // Create progress reporter if we're in an interactive terminal
-        let mut progress = if console::Term::stderr().is_term() {
-            Some(ProgressReporter::new())
-        } else {
-            None
+        if let Some(profile) = &config.profile {
+            eprintln!(
+                "{} Applying rules from profile {}\n",
+                style("📋").cyan(),
+                style(profile).bold()
+            );
+        }
+
+        // Load the template up front, so a broken one fails before the analysis
+        let template = config
+            .template
+            .as_deref()
+            .map(TemplateReportGenerator::from_file)
+            .transpose()?;
+
+        let mut events = match &config.events {
+            Some(path) => Some(
+                EventSink::open(path)
+                    .into_diagnostic()
+                    .wrap_err_with(|| format!("Failed to open event stream {}", path.display()))?,
+            ),
+            None => None,
         };
 
-        // Discover and analyze workspaces
-        let mut analyzer = WorkspaceAnalyzer::new();
-        analyzer
-            .discover_workspaces(&config.paths, progress.as_mut())
-            .wrap_err("Failed to discover and analyze workspaces")?;
+        if config.watch {
+            return run_watch(&config, template.as_ref(), &mut events);
+        }
 
-        if analyzer.workspaces().is_empty() {
-            eprintln!("{} No workspaces found to analyze", style("ℹ").blue());
-            return Ok(());
+        let run = check(&config, template.as_ref(), &mut events)?;
+        if let Some(code) = run.exit_code {
+            crate::timings::exit(code);
         }
 
-        // Build dependency graph
-        eprintln!("\n{} Building dependency graph...", style("🔨").blue());
-        eprintln!(
-            "  {} Exclude dev dependencies: {}",
-            style("→").dim(),
-            if config.exclude_dev {
-                style("yes").red()
-            } else {
-                style("no").green()
-            }
-        );
+        Ok(())
+    }
+}
+
+/// Outcome of one check: the structure it saw and the exit code it fails
+/// with, if any
+#[derive(Default)]
+struct CheckRun {
+    /// Workspaces, dependencies and cycles, captured only in watch mode
+    snapshot: StructureSnapshot,
+    exit_code: Option<i32>,
+}
+
+/// Analyze the paths, detect cycles and write the reports, events and
+/// baseline checks the configuration asks for
+fn check(
+    config: &CheckCyclesConfig,
+    template: Option<&TemplateReportGenerator>,
+    events: &mut Option<EventSink>,
+) -> Result<CheckRun> {
+    // Create progress reporter if we're in an interactive terminal
+    let mut progress = if console::Term::stderr().is_term() {
+        Some(ProgressReporter::new())
+    } else {
+        None
+    };
+
+    let deadline = config.timeout.map(Deadline::after).unwrap_or_default();
+    let started = Instant::now();
+
+    emit(
+        events,
+        AnalysisEvent::AnalysisStarted {
+            paths: config.paths.clone(),
+        },
+    );
+
+    // Discover and analyze workspaces
+    let mut analyzer = WorkspaceAnalyzer::new()
+        .with_backend(config.analyzer.backend)
+        .with_features(config.analyzer.features.clone())
+        .with_workspace_names(config.workspace_names.clone())
+        .with_max_workspaces(config.max_workspaces)
+        .with_excluded_paths(config.exclude_paths.clone())
+        .with_excluded_crates(config.exclude_crates.clone())
+        .with_follow_symlinks(config.follow_symlinks)
+        .with_manifest_warnings(config.manifest_warnings)
+        .with_cache(!config.no_cache)
+        .with_deadline(deadline);
+    analyzer
+        .discover_workspaces(&config.paths, progress.as_mut())
+        .wrap_err("Failed to discover and analyze workspaces")?;
+    emit(
+        events,
+        AnalysisEvent::WorkspacesDiscovered {
+            count: analyzer.workspaces().len(),
+        },
+    );
+
+    if analyzer.workspaces().is_empty() && analyzer.skipped_workspaces() == 0 {
+        eprintln!("{} No workspaces found to analyze", style("ℹ").blue());
+        return Ok(CheckRun::default());
+    }
+
+    // Build dependency graph
+    eprintln!("\n{} Building dependency graph...", style("🔨").blue());
+    eprintln!(
+        "  {} Exclude dev dependencies: {}",
+        style("→").dim(),
+        if config.exclude_dev {
+            style("yes").red()
+        } else {
+            style("no").green()
+        }
+    );
+    eprintln!(
+        "  {} Exclude build dependencies: {}",
+        style("→").dim(),
+        if config.exclude_build {
+            style("yes").red()
+        } else {
+            style("no").green()
+        }
+    );
+    eprintln!(
+        "  {} Exclude target dependencies: {}",
+        style("→").dim(),
+        if config.exclude_target {
+            style("yes").red()
+        } else {
+            style("no").green()
+        }
+    );
+    if let Some(target) = config.target {
         eprintln!(
-            "  {} Exclude build dependencies: {}",
+            "  {} Target platform: {}",
             style("→").dim(),
-            if config.exclude_build {
-                style("yes").red()
-            } else {
-                style("no").green()
-            }
+            style(target).cyan()
         );
+    }
+
+    // The kind matrix filters dev and build dependencies itself, and the
+    // dev-dependency rule needs dev edges even when cycles ignore them
+    let dev_rule_needs_dev_edges = config.deny_cross_workspace_dev_deps && config.exclude_dev;
+    let mut graph_builder = DependencyGraphBuilder::new(
+        config.exclude_dev && !config.kind_matrix && !dev_rule_needs_dev_edges,
+        config.exclude_build && !config.kind_matrix,
+        config.exclude_target,
+    )
+    .with_target(config.target);
+
+    if config.intra_workspace {
+        graph_builder
+            .build_intra_workspace_graph(analyzer.workspaces(), progress.as_ref())
+            .wrap_err("Failed to build intra-workspace dependency graph")?;
+    } else if config.level == CycleLevel::Crate {
+        graph_builder
+            .build_crate_graph(
+                analyzer.workspaces(),
+                analyzer.crate_to_workspace(),
+                analyzer.crate_path_to_workspace(),
+                analyzer.crate_to_paths(),
+                progress.as_ref(),
+            )
+            .wrap_err("Failed to build crate-level dependency graph")?;
+    } else {
+        graph_builder
+            .build_cross_workspace_graph(
+                analyzer.workspaces(),
+                analyzer.crate_to_workspace(),
+                analyzer.crate_path_to_workspace(),
+                analyzer.crate_to_paths(),
+                progress.as_ref(),
+            )
+            .wrap_err("Failed to build cross-workspace dependency graph")?;
+    }
+
+    // Cycles that exist without the what-if dependencies, so they are
+    // not blamed on them
+    let cycles_before_what_if = if config.what_if.is_empty() {
+        Vec::new()
+    } else if dev_rule_needs_dev_edges && !config.kind_matrix {
+        cyclic_components(&DependencyFilter::new(true, false, false).apply(graph_builder.graph()))
+    } else {
+        cyclic_components(graph_builder.graph())
+    };
+    for edge in &config.what_if {
+        let added = graph_builder
+            .add_hypothetical_edge(edge)
+            .wrap_err_with(|| format!("Failed to add what-if dependency {edge}"))?;
+        if added {
+            eprintln!(
+                "{} What if: adding dependency {}",
+                style("🔮").magenta(),
+                style(edge).bold()
+            );
+        } else {
+            eprintln!(
+                "{} What if: {} stays within one node and cannot create a cycle here",
+                style("ℹ").blue(),
+                edge
+            );
+        }
+    }
+
+    // Like layers, the dev-dependency rule judges workspace boundaries, so
+    // only workspace graphs are checked against it
+    let dev_violations = if config.deny_cross_workspace_dev_deps
+        && !config.intra_workspace
+        && config.level != CycleLevel::Crate
+    {
+        cross_workspace_dev_dependencies(graph_builder.graph())
+            .into_diagnostic()
+            .wrap_err("Failed to check for cross-workspace dev-dependencies")?
+    } else {
+        Vec::new()
+    };
+
+    let filtered_graph;
+    let graph = if dev_rule_needs_dev_edges && !config.kind_matrix {
+        filtered_graph = DependencyFilter::new(true, false, false).apply(graph_builder.graph());
+        &filtered_graph
+    } else {
+        graph_builder.graph()
+    };
+
+    if config.kind_matrix {
+        let exit_code = run_kind_matrix(config, &analyzer, graph, deadline)?;
+        return Ok(CheckRun {
+            exit_code,
+            ..CheckRun::default()
+        });
+    }
+
+    if let Some(path) = &config.save_graph
+        && analyzer.skipped_workspaces() > 0
+    {
         eprintln!(
-            "  {} Exclude target dependencies: {}",
-            style("→").dim(),
-            if config.exclude_target {
-                style("yes").red()
-            } else {
-                style("no").green()
-            }
+            "{} Not saving a partial graph to {}",
+            style("ℹ").blue(),
+            path.display()
         );
-
-        let mut graph_builder = DependencyGraphBuilder::new(
+    } else if let Some(path) = &config.save_graph {
+        let settings = GraphCache::settings(
+            &config.paths,
+            &analyzer,
             config.exclude_dev,
             config.exclude_build,
             config.exclude_target,
+            config.target,
         );
+        GraphCache::new(settings, graph, analyzer.workspaces())
+            .write(path)
+            .into_diagnostic()
+            .wrap_err_with(|| format!("Failed to save graph to {}", path.display()))?;
+        eprintln!(
+            "{} Saved the workspace graph to {}",
+            style("💾").cyan(),
+            path.display()
+        );
+    }
 
-        if config.intra_workspace {
-            graph_builder
-                .build_intra_workspace_graph(analyzer.workspaces(), progress.as_ref())
-                .wrap_err("Failed to build intra-workspace dependency graph")?;
-        } else {
-            graph_builder
-                .build_cross_workspace_graph(
-                    analyzer.workspaces(),
-                    analyzer.crate_to_workspace(),
-                    analyzer.crate_path_to_workspace(),
-                    analyzer.crate_to_paths(),
-                    progress.as_ref(),
-                )
-                .wrap_err("Failed to build cross-workspace dependency graph")?;
+    // Detect cycles
+    if let Some(p) = progress.as_mut() {
+        p.start_cycle_detection();
+    }
+
+    let mut detector = match config.elementary_cycles {
+        Some(limit) => CycleDetector::new().with_elementary_cycles(limit),
+        None => CycleDetector::new(),
+    }
+    .with_severity_model(config.severity_model)
+    .with_effort_model(config.effort_model)
+    .with_allowlist(config.allowlist.clone())
+    .with_policy(config.policy)
+    .with_deadline(deadline)
+    .with_fail_fast(config.fail_fast);
+    detector
+        .detect_cycles_with(graph, |detector, cycle| {
+            emit(events, AnalysisEvent::cycle_found(detector, cycle));
+        })
+        .wrap_err("Failed to detect dependency cycles")?;
+    if config.effort_model.lines_per_point.is_some() {
+        let lines = CrateLines::count(analyzer.workspaces(), detector.cycles());
+        detector = detector.with_crate_lines(lines);
+    }
+
+    if let Some(p) = progress.as_ref() {
+        p.finish_cycle_detection(detector.cycle_count());
+    }
+
+    if detector.is_truncated() {
+        eprintln!(
+            "{} Stopped after {} elementary cycles in at least one component; raise \
+             --max-elementary-cycles to see more",
+            style("⚠").yellow(),
+            config.elementary_cycles.unwrap_or_default()
+        );
+    }
+    if detector.is_stopped_early() {
+        eprintln!(
+            "{} Stopped at the first failing cycle (--fail-fast); other cycles may exist",
+            style("⚠").yellow()
+        );
+    }
+    for entry in config.allowlist.expired() {
+        if let Some(expires) = entry.expiry_date() {
+            eprintln!(
+                "{} Allowance for {} expired on {expires}; fix the cycle or remove the entry",
+                style("⚠").yellow(),
+                entry.describe()
+            );
         }
+    }
+    let allowed = detector
+        .cycles()
+        .iter()
+        .filter(|cycle| detector.is_allowed(cycle))
+        .count();
+    if allowed > 0 {
+        eprintln!(
+            "{} {} {} allowed by the project configuration",
+            style("ℹ").blue(),
+            allowed,
+            pluralize("cycle", allowed)
+        );
+    }
+    let warn_only = detector
+        .cycles()
+        .iter()
+        .filter(|cycle| detector.is_warn_only(cycle))
+        .count();
+    if warn_only > 0 {
+        eprintln!(
+            "{} {} {} only closed by dependency types the policy lets warn",
+            style("ℹ").blue(),
+            warn_only,
+            pluralize("cycle", warn_only)
+        );
+    }
 
-        // Detect cycles
-        if let Some(p) = progress.as_mut() {
-            p.start_cycle_detection();
+    for edge in &config.what_if {
+        let cycles = detector.cycles_introduced_by(
+            edge.from_crate(),
+            edge.to_crate(),
+            &cycles_before_what_if,
+        );
+        if cycles.is_empty() {
+            eprintln!(
+                "{} What if: {} would not create a new cycle",
+                style("✅").green(),
+                edge
+            );
         }
+        for cycle in cycles {
+            eprintln!(
+                "{} What if: {} would create a cycle between {}",
+                style("🔮").magenta(),
+                style(edge).bold(),
+                cycle.workspace_names().join(", ")
+            );
+        }
+    }
 
-        let mut detector = CycleDetector::new();
-        detector
-            .detect_cycles(graph_builder.graph())
-            .wrap_err("Failed to detect dependency cycles")?;
+    let partial = partial_reason(config, &analyzer, &detector);
+    if let Some(reason) = &partial {
+        eprintln!("{} Partial results: {}", style("⏱").yellow(), reason);
+    }
+
+    let grade = ArchitectureGrade::compute(graph, &detector, &config.grade_weights);
 
-        if let Some(p) = progress.as_ref() {
-            p.finish_cycle_detection(detector.cycle_count());
+    // Layers are declared per workspace, so only workspace graphs are
+    // checked against them
+    let checked_layers = if config.layer_model.is_empty()
+        || config.intra_workspace
+        || config.level == CycleLevel::Crate
+    {
+        None
+    } else {
+        Some(
+            config
+                .layer_model
+                .violations(graph)
+                .into_diagnostic()
+                .wrap_err("Failed to check dependencies against declared layers")?,
+        )
+    };
+
+    let json_report = || {
+        let mut generator = JsonReportGenerator::new()
+            .with_run(run_metadata(config))
+            .with_grade(grade.clone())
+            .with_break_point_locations(ManifestLinks::from_workspaces(analyzer.workspaces()));
+        if let Some(reason) = partial.clone() {
+            generator = generator.with_partial(reason);
+        }
+        if let Some(violations) = checked_layers.clone() {
+            generator = generator.with_layer_violations(violations);
         }
+        generator
+    };
 
-        // Generate report based on format
-        let report_result = match config.format {
+    // Write a report per format, straight to its destination; a template
+    // replaces the formats
+    let destinations = match template {
+        Some(template) => {
+            let mut out = ReportOutput::open(config.output.as_deref())
+                .wrap_err("Failed to open the report output")?;
+            template
+                .clone()
+                .with_context(json_report())
+                .write_report(&detector, &mut out)
+                .and_then(|()| out.finish())
+                .map(report_written)
+                .into_diagnostic()
+                .wrap_err("Failed to render the report template")?;
+            Vec::new()
+        }
+        None => report_destinations(config)?,
+    };
+    for (format, path) in destinations {
+        let mut out =
+            ReportOutput::open(path.as_deref()).wrap_err("Failed to open the report output")?;
+        let report_result = match format {
             OutputFormat::Human => {
-                let generator = HumanReportGenerator::new(config.max_cycles);
-                generator.generate_report(&detector)
-            }
-            OutputFormat::Json => {
-                let generator = JsonReportGenerator::new();
-                generator.generate_report(&detector)
+                let mut generator = HumanReportGenerator::new()
+                    .with_verbosity(config.verbosity)
+                    .with_grade(grade.clone());
+                if out.is_stdout() && links::stdout_supports_hyperlinks() {
+                    generator = generator.with_links(links::manifest_links(
+                        &analyzer,
+                        config.url_template.as_ref(),
+                    ));
+                }
+                generator = generator.with_break_point_locations(ManifestLinks::from_workspaces(
+                    analyzer.workspaces(),
+                ));
+                if let Some(reason) = partial.clone() {
+                    generator = generator.with_partial(reason);
+                }
+                if let Some(violations) = checked_layers.clone() {
+                    generator = generator.with_layer_violations(violations);
+                }
+                generator.write_report(&detector, &mut out)
             }
+            OutputFormat::Json => json_report().write_report(&detector, &mut out),
             OutputFormat::Junit => {
-                let generator = JunitReportGenerator::new();
-                generator.generate_report(&detector)
+                let mut generator = JunitReportGenerator::new()
+                    .with_manifest_locations(ManifestLinks::from_workspaces(analyzer.workspaces()))
+                    .with_duration(started.elapsed());
+                if let Some(reason) = partial.clone() {
+                    generator = generator.with_partial(reason);
+                }
+                if let Some(violations) = checked_layers.clone() {
+                    generator = generator.with_layer_violations(violations);
+                }
+                generator.write_report(&detector, &mut out)
             }
             OutputFormat::GitHub => {
-                let generator = GitHubReportGenerator::new();
-                generator.generate_report(&detector)
+                let mut generator = GitHubReportGenerator::new().with_file_annotations(
+                    ManifestLinks::from_workspaces(analyzer.workspaces()),
+                    github::annotation_root(),
+                );
+                if config.url_template.is_some() {
+                    generator = generator.with_links(links::manifest_links(
+                        &analyzer,
+                        config.url_template.as_ref(),
+                    ));
+                }
+                if let Some(reason) = partial.clone() {
+                    generator = generator.with_partial(reason);
+                }
+                if let Some(violations) = checked_layers.clone() {
+                    generator = generator.with_layer_violations(violations);
+                }
+                generator.write_report(&detector, &mut out)
             }
         };
 
-        match report_result {
-            Ok(report) => print!("{report}"),
-            Err(e) => {
-                return Err(e)
-                    .into_diagnostic()
-                    .wrap_err("Failed to generate report");
+        report_result
+            .and_then(|()| out.finish())
+            .map(report_written)
+            .into_diagnostic()
+            .wrap_err("Failed to generate report")?;
+    }
+
+    if config.deny_cross_workspace_dev_deps {
+        report_dev_violations(&dev_violations, analyzer.workspaces());
+    }
+
+    let layer_violations = checked_layers.unwrap_or_default();
+
+    emit(
+        events,
+        AnalysisEvent::AnalysisFinished {
+            grade: grade.grade,
+            score: grade.score,
+            cycle_count: grade.cycle_count,
+            layer_violation_count: layer_violations.iter().map(|v| v.edges).sum(),
+            partial: partial.is_some(),
+            duration_ms: started.elapsed().as_millis() as u64,
+        },
+    );
+
+    let snapshot = if config.watch {
+        StructureSnapshot::from_graph(graph, detector.cycles())
+    } else {
+        StructureSnapshot::default()
+    };
+    if partial.is_some() {
+        // Partial runs are neither recorded nor judged on their cycles
+        if config.ledger.is_some() {
+            eprintln!(
+                "{} Not recording partial results in the trend ledger",
+                style("ℹ").blue()
+            );
+        }
+        if config.write_baseline.is_some() {
+            eprintln!(
+                "{} Not recording partial results in the baseline",
+                style("ℹ").blue()
+            );
+        }
+        return Ok(CheckRun {
+            snapshot,
+            exit_code: Some(crate::constants::exit::TIMEOUT),
+        });
+    }
+
+    let ledger_regressed = match &config.ledger {
+        Some(path) => record_in_ledger(path, &grade, layer_violations)?,
+        None => false,
+    };
+
+    if let Some(path) = &config.write_baseline
+        && !ledger_regressed
+    {
+        let baseline = Baseline::from_cycles(detector.cycles());
+        baseline
+            .write(path)
+            .into_diagnostic()
+            .wrap_err_with(|| format!("Failed to write baseline {}", path.display()))?;
+        eprintln!(
+            "{} Recorded {} {} in baseline {}",
+            style("📋").cyan(),
+            baseline.cycles.len(),
+            pluralize("cycle", baseline.cycles.len()),
+            path.display()
+        );
+    }
+
+    let failed = if ledger_regressed || !dev_violations.is_empty() {
+        true
+    } else if let Some(path) = &config.baseline {
+        check_against_baseline(path, &detector)?
+    } else if config.budget.is_set() {
+        check_against_budget(&config.budget, &detector)
+    } else {
+        // Fail if cycles were found and that was requested
+        (config.error_on_cycles || config.fail_fast) && detector.has_failing_cycles()
+    };
+    Ok(CheckRun {
+        snapshot,
+        exit_code: failed.then_some(1),
+    })
+}
+
+/// Check for cycles, then re-check whenever a manifest changes and print what
+/// changed since the previous check
+///
+/// Every check runs like a one-shot `inspect`: it writes the reports and
+/// events and judges the cycles, but reports a failure instead of exiting.
+fn run_watch(
+    config: &CheckCyclesConfig,
+    template: Option<&TemplateReportGenerator>,
+    events: &mut Option<EventSink>,
+) -> Result<()> {
+    let watcher = ManifestWatcher::new(&config.paths)
+        .into_diagnostic()
+        .wrap_err("Failed to watch for manifest changes")?;
+
+    let mut previous = check(config, template, events)?;
+    report_watch_status(&previous);
+
+    loop {
+        eprintln!(
+            "\n{} Watching for Cargo.toml and Cargo.lock changes (Ctrl-C to stop)...",
+            style("👀").cyan()
+        );
+        let changed = watcher
+            .wait_for_change()
+            .into_diagnostic()
+            .wrap_err("Failed to watch for manifest changes")?;
+        let changed = match changed.iter().next() {
+            Some(path) if changed.len() == 1 => path.display().to_string(),
+            _ => format!("{} manifests", changed.len()),
+        };
+
+        let started = Instant::now();
+        // Directories may have been created or removed since the last run
+        crate::utils::canonical::clear();
+        // A manifest saved halfway through an edit may not parse; keep
+        // watching and report once it does
+        match check(config, template, events) {
+            Ok(current) => {
+                let cycle_count = current.snapshot.cycles().len();
+                println!(
+                    "\n🔄 {changed} changed: {} {} ({} ms)",
+                    cycle_count,
+                    pluralize("cycle", cycle_count),
+                    started.elapsed().as_millis()
+                );
+                print!(
+                    "{}",
+                    previous
+                        .snapshot
+                        .diff(&current.snapshot)
+                        .to_delta()
+                        .into_diagnostic()
+                        .wrap_err("Failed to describe changes")?
+                );
+                report_watch_status(&current);
+                previous = current;
             }
+            Err(e) => eprintln!("{} Re-check failed: {e:?}", style("⚠").yellow()),
         }
+    }
+}
+
+/// Say whether a watched check passes, since watch mode does not exit on
+/// failure
+fn report_watch_status(run: &CheckRun) {
+    match run.exit_code {
+        Some(code) => eprintln!("{} This check fails (exit code {code})", style("❌").red()),
+        None => eprintln!("{} This check passes", style("✅").green()),
+    }
+}
 
-        // Exit with error code if cycles found and requested
-        if config.error_on_cycles && detector.has_cycles() {
-            std::process::exit(1);
+/// List each dev-dependency crossing a workspace boundary with the
+/// manifest entry declaring it
+fn report_dev_violations(
+    violations: &[CycleEdge],
+    workspaces: &std::collections::HashMap<std::path::PathBuf, crate::analyzer::WorkspaceInfo>,
+) {
+    let links = ManifestLinks::from_workspaces(workspaces);
+    eprintln!(
+        "\n{} {} cross-workspace {}",
+        style("🚫").red(),
+        violations.len(),
+        if violations.len() == 1 {
+            "dev-dependency"
+        } else {
+            "dev-dependencies"
         }
+    );
+    for violation in violations {
+        let location = links.edge_location(violation);
+        let declared = match &location {
+            Some(location) => match &location.workspace {
+                Some(workspace) => format!(" ({}, defined in {workspace})", location.member),
+                None => format!(" ({})", location.member),
+            },
+            None => String::new(),
+        };
+        eprintln!(
+            "  {} {}/{} → {}/{}{}",
+            style("→").dim(),
+            violation.from_workspace(),
+            violation.from_crate(),
+            violation.to_workspace(),
+            violation.to_crate(),
+            style(declared).dim()
+        );
+    }
+}
 
-        Ok(())
+/// Send an event to the stream, if any, dropping the stream once a write
+/// fails
+fn emit(events: &mut Option<EventSink>, event: AnalysisEvent) {
+    let Some(sink) = events.as_mut() else {
+        return;
+    };
+    if let Err(e) = sink.emit(&event) {
+        eprintln!(
+            "{} Stopped streaming events to {}: {}",
+            style("⚠").yellow(),
+            sink.path().display(),
+            e
+        );
+        *events = None;
+    }
+}
+
+/// Compare this run against the ledger and record it unless layer violations
+/// grew, returning whether they did
+fn record_in_ledger(
+    path: &Path,
+    grade: &ArchitectureGrade,
+    layer_violations: Vec<LayerViolation>,
+) -> Result<bool> {
+    let ledger = TrendLedger::new(path.to_path_buf());
+    let previous = ledger
+        .last_entry()
+        .into_diagnostic()
+        .wrap_err_with(|| format!("Failed to read trend ledger {}", path.display()))?;
+
+    let repo_dir = path
+        .parent()
+        .filter(|p| !p.as_os_str().is_empty())
+        .unwrap_or(Path::new("."));
+    let entry = LedgerEntry::new(grade, layer_violations).with_rev(ledger::current_rev(repo_dir));
+    let trend = LayerTrend::compare(previous.as_ref(), &entry);
+
+    if trend.previous_count.is_some() {
+        for violation in &trend.new_violations {
+            eprintln!(
+                "{} New layer violation since the last recorded run: {} ({}) → {} ({})",
+                style("⚠").yellow(),
+                violation.from_workspace,
+                violation.from_layer,
+                violation.to_workspace,
+                violation.to_layer
+            );
+        }
     }
+
+    if trend.is_regression() {
+        eprintln!(
+            "{} Layer violations grew from {} to {} crate edges since the last recorded run; not \
+             recording this run in {}",
+            style("❌").red(),
+            trend.previous_count.unwrap_or_default(),
+            trend.current_count,
+            ledger.path().display()
+        );
+        return Ok(true);
+    }
+
+    ledger
+        .append(&entry)
+        .into_diagnostic()
+        .wrap_err_with(|| format!("Failed to write trend ledger {}", path.display()))?;
+    eprintln!(
+        "{} Recorded run in trend ledger {}",
+        style("📒").cyan(),
+        ledger.path().display()
+    );
+
+    Ok(false)
+}
+
+/// Compare the failing cycles against a baseline of known cycles, returning
+/// whether any of them is new
+fn check_against_baseline(path: &Path, detector: &CycleDetector) -> Result<bool> {
+    let baseline = Baseline::load(path)
+        .into_diagnostic()
+        .wrap_err_with(|| format!("Failed to read baseline {}", path.display()))?;
+
+    let failing: Vec<&WorkspaceCycle> = detector
+        .cycles()
+        .iter()
+        .filter(|cycle| detector.is_failing(cycle))
+        .collect();
+    let new_cycles: Vec<&WorkspaceCycle> = failing
+        .iter()
+        .copied()
+        .filter(|cycle| !baseline.contains(cycle))
+        .collect();
+
+    eprintln!(
+        "{} {} of {} failing cycles are in baseline {}",
+        style("📋").cyan(),
+        failing.len() - new_cycles.len(),
+        failing.len(),
+        path.display()
+    );
+    for cycle in &new_cycles {
+        eprintln!(
+            "{} New cycle not in the baseline: {} ({})",
+            style("❌").red(),
+            cycle.workspace_names().join(" → "),
+            cycle.id()
+        );
+    }
+
+    let fixed = baseline.fixed(detector.cycles());
+    if !fixed.is_empty() {
+        eprintln!(
+            "{} {} baselined {} no longer found; re-record the baseline with --write-baseline to \
+             lock in the progress",
+            style("ℹ").blue(),
+            fixed.len(),
+            pluralize("cycle", fixed.len())
+        );
+    }
+
+    Ok(!new_cycles.is_empty())
+}
+
+/// Compare the failing cycles against the failure budget, returning whether
+/// they exceed it
+fn check_against_budget(budget: &FailureBudget, detector: &CycleDetector) -> bool {
+    let overruns = budget.overruns(detector);
+    for overrun in &overruns {
+        eprintln!("{} Over budget: {}", style("❌").red(), overrun);
+    }
+    if overruns.is_empty() {
+        let failing = detector
+            .cycles()
+            .iter()
+            .filter(|cycle| detector.is_failing(cycle))
+            .count();
+        eprintln!(
+            "{} {} failing {} within the failure budget",
+            style("✅").green(),
+            failing,
+            pluralize("cycle", failing)
+        );
+    }
+
+    !overruns.is_empty()
+}
+
+/// Detect cycles once per dependency-kind filter on the unfiltered graph and
+/// report them side by side, returning the exit code the run fails with, if
+/// any
+fn run_kind_matrix(
+    config: &CheckCyclesConfig,
+    analyzer: &WorkspaceAnalyzer,
+    graph: &DiGraph<WorkspaceNode, DependencyEdge>,
+    deadline: Deadline,
+) -> Result<Option<i32>> {
+    let mut results = Vec::with_capacity(KIND_FILTERS.len());
+    for filter in KIND_FILTERS {
+        let filtered = DependencyFilter::new(
+            !filter.include_dev,
+            !filter.include_build,
+            config.exclude_target,
+        )
+        .with_target(config.target)
+        .apply(graph);
+
+        let mut detector = match config.elementary_cycles {
+            Some(limit) => CycleDetector::new().with_elementary_cycles(limit),
+            None => CycleDetector::new(),
+        }
+        .with_severity_model(config.severity_model)
+        .with_allowlist(config.allowlist.clone())
+        .with_policy(config.policy)
+        .with_deadline(deadline);
+        detector
+            .detect_cycles(&filtered)
+            .wrap_err_with(|| format!("Failed to detect {} dependency cycles", filter.name))?;
+
+        results.push(KindFilterResult { filter, detector });
+    }
+
+    let generator = KindMatrixReportGenerator::new(&results);
+    for (format, path) in report_destinations(config)? {
+        let report = match format {
+            OutputFormat::Human => generator.generate_human_report(),
+            OutputFormat::Json => generator.generate_json_report(),
+            OutputFormat::Junit => generator.generate_junit_report(),
+            OutputFormat::GitHub => generator.generate_github_report(),
+        }
+        .into_diagnostic()
+        .wrap_err("Failed to generate report")?;
+        ReportOutput::write_rendered(path.as_deref(), &report)
+            .map(report_written)
+            .into_diagnostic()
+            .wrap_err("Failed to write report")?;
+    }
+
+    let partial = results
+        .iter()
+        .find_map(|result| partial_reason(config, analyzer, &result.detector));
+    if let Some(reason) = partial {
+        eprintln!("{} Partial results: {}", style("⏱").yellow(), reason);
+        return Ok(Some(crate::constants::exit::TIMEOUT));
+    }
+
+    let failed = config.error_on_cycles
+        && results
+            .iter()
+            .any(|result| result.detector.has_failing_cycles());
+    Ok(failed.then_some(1))
+}
+
+/// Why the run only covers part of the repository, if the deadline cut it
+/// short
+fn partial_reason(
+    config: &CheckCyclesConfig,
+    analyzer: &WorkspaceAnalyzer,
+    detector: &CycleDetector,
+) -> Option<String> {
+    let timeout = config.timeout.unwrap_or_default();
+    let skipped = analyzer.skipped_workspaces();
+
+    if skipped > 0 {
+        Some(format!(
+            "timed out after {timeout:?}; {skipped} {} not analyzed",
+            pluralize("workspace", skipped)
+        ))
+    } else if detector.is_timed_out() {
+        Some(format!(
+            "timed out after {timeout:?} during cycle detection; some cycles may be missing"
+        ))
+    } else {
+        None
+    }
+}
+
+/// Each report format with the file to write it to: a file per format in
+/// `--output-dir`, otherwise `--output` or stdout (None)
+fn report_destinations(config: &CheckCyclesConfig) -> Result<Vec<(OutputFormat, Option<PathBuf>)>> {
+    let Some(dir) = &config.output_dir else {
+        return Ok(config
+            .formats
+            .iter()
+            .map(|format| (*format, config.output.clone()))
+            .collect());
+    };
+
+    std::fs::create_dir_all(dir)
+        .into_diagnostic()
+        .wrap_err_with(|| format!("Failed to create report directory {}", dir.display()))?;
+    Ok(config
+        .formats
+        .iter()
+        .map(|format| (*format, Some(dir.join(report_file_name(*format)))))
+        .collect())
+}
+
+/// How this run was invoked, for the JSON report
+fn run_metadata(config: &CheckCyclesConfig) -> RunMetadata {
+    RunMetadata::new(
+        config.paths.clone(),
+        RunFilters {
+            exclude_dev: config.exclude_dev,
+            exclude_build: config.exclude_build,
+            exclude_target: config.exclude_target,
+            target: config.target.as_ref().map(ToString::to_string),
+            exclude_paths: config
+                .exclude_paths
+                .iter()
+                .map(ToString::to_string)
+                .collect(),
+            exclude_crates: config
+                .exclude_crates
+                .iter()
+                .map(ToString::to_string)
+                .collect(),
+            ..RunFilters::default()
+        }
+        .with_features(&config.analyzer.features),
+    )
 }
@@ -0,0 +1,18 @@
+//! Completions command executor
+
+use miette::Result;
+
+use crate::commands::completions::generate_completions;
+use crate::config::CompletionsConfig;
+use crate::executors::CommandExecutor;
+
+pub struct CompletionsExecutor;
+
+impl CommandExecutor for CompletionsExecutor {
+    type Config = CompletionsConfig;
+
+    fn execute(config: Self::Config) -> Result<()> {
+        print!("{}", generate_completions(config.shell));
+        Ok(())
+    }
+}
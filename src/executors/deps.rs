@@ -9,6 +9,7 @@ use crate::config::WorkspaceDepsConfig;
 use crate::executors::CommandExecutor;
 use crate::graph::DependencyGraphBuilder;
 use crate::progress::ProgressReporter;
+use crate::reports::output::{ReportOutput, report_written};
 
 pub struct DepsExecutor;
 
@@ -29,7 +30,16 @@ impl CommandExecutor for DepsExecutor {
         };
 
         // Discover and analyze workspaces
-        let mut analyzer = WorkspaceAnalyzer::new();
+        let mut analyzer = WorkspaceAnalyzer::new()
+            .with_backend(config.analyzer.backend)
+            .with_features(config.analyzer.features.clone())
+            .with_workspace_names(config.workspace_names.clone())
+            .with_max_workspaces(config.max_workspaces)
+            .with_excluded_paths(config.exclude_paths.clone())
+            .with_excluded_crates(config.exclude_crates.clone())
+            .with_follow_symlinks(config.follow_symlinks)
+            .with_cache(!config.no_cache)
+            .with_metadata_providers(config.metadata_providers.clone());
         analyzer
             .discover_workspaces(&config.paths, progress.as_mut())
             .wrap_err("Failed to discover and analyze workspaces")?;
@@ -40,11 +50,7 @@ impl CommandExecutor for DepsExecutor {
         }
 
         // Build dependency graph for workspace analysis
-        let mut graph_builder = DependencyGraphBuilder::new(
-            config.exclude_dev,
-            config.exclude_build,
-            config.exclude_target,
-        );
+        let mut graph_builder = DependencyGraphBuilder::filtered(config.filter.clone());
 
         graph_builder
             .build_cross_workspace_graph(
@@ -84,7 +90,12 @@ impl CommandExecutor for DepsExecutor {
         };
 
         match report_result {
-            Ok(report) => println!("{report}"),
+            Ok(report) => {
+                ReportOutput::write_rendered(config.output.as_deref(), &format!("{report}\n"))
+                    .map(report_written)
+                    .into_diagnostic()
+                    .wrap_err("Failed to write workspace dependency report")?;
+            }
             Err(e) => {
                 return Err(e)
                     .into_diagnostic()
@@ -0,0 +1,144 @@
+//! Diff command executor
+
+use std::path::{Path, PathBuf};
+
+use console::style;
+use miette::{IntoDiagnostic, Result, WrapErr};
+
+use crate::analyzer::WorkspaceAnalyzer;
+use crate::cli::OutputFormat;
+use crate::commands::changelog::StructureSnapshot;
+use crate::commands::diff::DiffReportGenerator;
+use crate::config::{DiffConfig, DiffSide};
+use crate::detector::CycleDetector;
+use crate::executors::CommandExecutor;
+use crate::git::{self, Worktree};
+use crate::graph::DependencyGraphBuilder;
+use crate::reports::output::{ReportOutput, report_written};
+
+pub struct DiffExecutor;
+
+impl CommandExecutor for DiffExecutor {
+    type Config = DiffConfig;
+
+    fn execute(config: Self::Config) -> Result<()> {
+        let from_label = config.from.to_string();
+        let to_label = config.to.to_string();
+        eprintln!(
+            "{} Comparing dependency cycles between {} and {}...\n",
+            style("🔀").cyan(),
+            style(&from_label).bold(),
+            style(&to_label).bold()
+        );
+
+        let old = snapshot_side(&config, &config.from)?;
+        let new = snapshot_side(&config, &config.to)?;
+        let changes = old.diff(&new);
+
+        let generator = DiffReportGenerator::new(&from_label, &to_label);
+        let report_result = match config.format {
+            OutputFormat::Human => generator.generate_human_report(&changes),
+            OutputFormat::Json => generator.generate_json_report(&changes),
+            OutputFormat::Junit => generator.generate_junit_report(&changes),
+            OutputFormat::GitHub => generator.generate_github_report(&changes),
+        };
+
+        match report_result {
+            Ok(output) => {
+                ReportOutput::write_rendered(config.output.as_deref(), &output)
+                    .map(report_written)
+                    .into_diagnostic()
+                    .wrap_err("Failed to write diff report")?;
+            }
+            Err(e) => {
+                return Err(e)
+                    .into_diagnostic()
+                    .wrap_err("Failed to generate diff report");
+            }
+        }
+
+        if !changes.introduced_cycles.is_empty() {
+            crate::timings::exit(1);
+        }
+
+        Ok(())
+    }
+}
+
+fn snapshot_side(config: &DiffConfig, side: &DiffSide) -> Result<StructureSnapshot> {
+    match side {
+        DiffSide::Directory(dir) => snapshot_paths(config, std::slice::from_ref(dir))
+            .wrap_err_with(|| format!("Failed to analyze directory {}", dir.display())),
+        DiffSide::Revision(rev) => {
+            let first_path = config
+                .paths
+                .first()
+                .cloned()
+                .unwrap_or_else(|| PathBuf::from("."));
+            let repo_root = git::repo_root(&first_path)
+                .into_diagnostic()
+                .wrap_err("Failed to locate the git repository")?;
+            snapshot_revision(config, &repo_root, rev)
+        }
+        DiffSide::WorkingTree => snapshot_paths(config, &config.paths),
+    }
+}
+
+fn snapshot_revision(
+    config: &DiffConfig,
+    repo_root: &Path,
+    rev: &str,
+) -> Result<StructureSnapshot> {
+    let worktree = Worktree::checkout(repo_root, rev)
+        .into_diagnostic()
+        .wrap_err_with(|| format!("Failed to check out revision '{rev}'"))?;
+
+    let paths: Vec<PathBuf> = config
+        .paths
+        .iter()
+        .map(|path| worktree.map_path(path))
+        .collect();
+
+    snapshot_paths(config, &paths).wrap_err_with(|| format!("Failed to analyze revision '{rev}'"))
+}
+
+fn snapshot_paths(config: &DiffConfig, paths: &[PathBuf]) -> Result<StructureSnapshot> {
+    let mut analyzer = WorkspaceAnalyzer::new()
+        .with_backend(config.analyzer.backend)
+        .with_features(config.analyzer.features.clone())
+        .with_workspace_names(config.workspace_names.clone())
+        .with_max_workspaces(config.max_workspaces)
+        .with_excluded_paths(config.exclude_paths.clone())
+        .with_excluded_crates(config.exclude_crates.clone())
+        .with_follow_symlinks(config.follow_symlinks)
+        .with_cache(!config.no_cache);
+    analyzer
+        .discover_workspaces(paths, None)
+        .wrap_err("Failed to discover and analyze workspaces")?;
+
+    let mut graph_builder = DependencyGraphBuilder::new(
+        config.exclude_dev,
+        config.exclude_build,
+        config.exclude_target,
+    )
+    .with_target(config.target);
+    graph_builder
+        .build_cross_workspace_graph(
+            analyzer.workspaces(),
+            analyzer.crate_to_workspace(),
+            analyzer.crate_path_to_workspace(),
+            analyzer.crate_to_paths(),
+            None,
+        )
+        .wrap_err("Failed to build cross-workspace dependency graph")?;
+
+    let mut detector = CycleDetector::new();
+    detector
+        .detect_cycles(graph_builder.graph())
+        .wrap_err("Failed to detect dependency cycles")?;
+
+    Ok(StructureSnapshot::from_graph(
+        graph_builder.graph(),
+        detector.cycles(),
+    ))
+}
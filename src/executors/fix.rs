@@ -0,0 +1,128 @@
+//! Fix command executor
+
+use console::style;
+use miette::{IntoDiagnostic, Result, WrapErr};
+
+use crate::analyzer::WorkspaceAnalyzer;
+use crate::cli::FixFormat;
+use crate::commands::fix::{FixReportGenerator, patch_file_name, plan_fixes};
+use crate::config::FixConfig;
+use crate::detector::CycleDetector;
+use crate::executors::CommandExecutor;
+use crate::graph::DependencyGraphBuilder;
+use crate::progress::ProgressReporter;
+use crate::reports::links::ManifestLinks;
+use crate::reports::output::{ReportOutput, report_written};
+
+pub struct FixExecutor;
+
+impl CommandExecutor for FixExecutor {
+    type Config = FixConfig;
+
+    fn execute(config: Self::Config) -> Result<()> {
+        eprintln!(
+            "{} Working out edits that break the cycles...\n",
+            style("🔧").cyan()
+        );
+
+        // Create progress reporter if we're in an interactive terminal
+        let mut progress = if console::Term::stderr().is_term() {
+            Some(ProgressReporter::new())
+        } else {
+            None
+        };
+
+        // Discover and analyze workspaces
+        let mut analyzer = WorkspaceAnalyzer::new()
+            .with_backend(config.analyzer.backend)
+            .with_features(config.analyzer.features.clone())
+            .with_workspace_names(config.workspace_names.clone())
+            .with_max_workspaces(config.max_workspaces)
+            .with_excluded_paths(config.exclude_paths.clone())
+            .with_excluded_crates(config.exclude_crates.clone())
+            .with_follow_symlinks(config.follow_symlinks)
+            .with_cache(!config.no_cache);
+        analyzer
+            .discover_workspaces(&config.paths, progress.as_mut())
+            .wrap_err("Failed to discover and analyze workspaces")?;
+
+        let mut graph_builder = DependencyGraphBuilder::new(
+            config.exclude_dev,
+            config.exclude_build,
+            config.exclude_target,
+        )
+        .with_target(config.target);
+        graph_builder
+            .build_cross_workspace_graph(
+                analyzer.workspaces(),
+                analyzer.crate_to_workspace(),
+                analyzer.crate_path_to_workspace(),
+                analyzer.crate_to_paths(),
+                progress.as_ref(),
+            )
+            .wrap_err("Failed to build dependency graph")?;
+
+        let mut detector = CycleDetector::new();
+        detector
+            .detect_cycles(graph_builder.graph())
+            .wrap_err("Failed to detect cycles")?;
+
+        let base = std::env::current_dir().into_diagnostic()?;
+        let fixes = plan_fixes(
+            detector.cycles(),
+            &ManifestLinks::from_workspaces(analyzer.workspaces()),
+            config.strategy,
+            config.exclude_dev,
+            &base,
+        )
+        .into_diagnostic()
+        .wrap_err("Failed to plan manifest edits")?;
+
+        if let Some(dir) = &config.write_patches {
+            std::fs::create_dir_all(dir)
+                .into_diagnostic()
+                .wrap_err_with(|| format!("Failed to create {}", dir.display()))?;
+            let mut written = 0;
+            for (i, fix) in fixes.iter().enumerate() {
+                if fix.patch.is_empty() {
+                    continue;
+                }
+                let path = dir.join(patch_file_name(i, fix));
+                std::fs::write(&path, &fix.patch)
+                    .into_diagnostic()
+                    .wrap_err_with(|| format!("Failed to write {}", path.display()))?;
+                written += 1;
+            }
+            eprintln!(
+                "{} Wrote {} patch {} to {}",
+                style("📝").green(),
+                written,
+                if written == 1 { "file" } else { "files" },
+                dir.display()
+            );
+        }
+
+        let generator = FixReportGenerator::new();
+        let report_result = match config.format {
+            FixFormat::Human => generator.generate_human_report(&fixes),
+            FixFormat::Json => generator.generate_json_report(&fixes),
+            FixFormat::Patch => generator.generate_patch_report(&fixes),
+        };
+
+        match report_result {
+            Ok(output) => {
+                ReportOutput::write_rendered(config.output.as_deref(), &output)
+                    .map(report_written)
+                    .into_diagnostic()
+                    .wrap_err("Failed to write fix report")?;
+            }
+            Err(e) => {
+                return Err(e)
+                    .into_diagnostic()
+                    .wrap_err("Failed to generate fix report");
+            }
+        }
+
+        Ok(())
+    }
+}
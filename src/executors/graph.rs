@@ -1,7 +1,6 @@
 //! Graph command executor
 
-use std::fs::File;
-use std::io::{self, BufWriter};
+use std::io::{self, IsTerminal, Write};
 
 use console::style;
 use miette::{IntoDiagnostic, Result, WrapErr};
@@ -11,7 +10,11 @@ use crate::cli::GraphFormat;
 use crate::config::GraphOptions;
 use crate::detector::CycleDetector;
 use crate::executors::CommandExecutor;
-use crate::graph::DependencyGraphBuilder;
+use crate::graph::{
+    DependencyGraphBuilder, GraphCache, ImageFormat, condense, cycles_only, neighborhood,
+    render_image,
+};
+use crate::reports::output::ReportOutput;
 
 pub struct GraphExecutor;
 
@@ -26,37 +29,120 @@ impl CommandExecutor for GraphExecutor {
         );
 
         // Discover and analyze workspaces
-        let mut analyzer = WorkspaceAnalyzer::new();
-        analyzer
-            .discover_workspaces(&config.paths, None)
-            .wrap_err("Failed to discover workspaces")?;
-
-        if analyzer.workspaces().is_empty() {
-            eprintln!("{} No workspaces found to visualize", style("ℹ").blue());
-            return Ok(());
-        }
-
-        // Build dependency graph
+        let mut analyzer = WorkspaceAnalyzer::new()
+            .with_backend(config.analyzer.backend)
+            .with_features(config.analyzer.features.clone())
+            .with_workspace_names(config.workspace_names.clone())
+            .with_max_workspaces(config.max_workspaces)
+            .with_excluded_paths(config.exclude_paths.clone())
+            .with_excluded_crates(config.exclude_crates.clone())
+            .with_follow_symlinks(config.follow_symlinks)
+            .with_cache(!config.no_cache);
         let mut graph_builder = DependencyGraphBuilder::new(
             config.exclude_dev,
             config.exclude_build,
             config.exclude_target,
+        )
+        .with_target(config.target);
+
+        let cache_settings = GraphCache::settings(
+            &config.paths,
+            &analyzer,
+            config.exclude_dev,
+            config.exclude_build,
+            config.exclude_target,
+            config.target,
         );
-        graph_builder
-            .build_cross_workspace_graph(
-                analyzer.workspaces(),
-                analyzer.crate_to_workspace(),
-                analyzer.crate_path_to_workspace(),
-                analyzer.crate_to_paths(),
-                None,
-            )
-            .wrap_err("Failed to build dependency graph")?;
-
-        // Detect cycles if highlighting is requested
-        let cycles = if config.highlight_cycles {
+        let cached = config
+            .from_cache
+            .as_ref()
+            .and_then(|path| GraphCache::load(path, &cache_settings).map(|graph| (path, graph)));
+
+        let cached_graph;
+        let full_graph = if let Some((path, graph)) = cached {
+            eprintln!(
+                "{} Using the graph saved in {}",
+                style("ℹ").blue(),
+                path.display()
+            );
+            cached_graph = graph;
+            &cached_graph
+        } else {
+            if let Some(path) = &config.from_cache {
+                eprintln!(
+                    "{} No up-to-date graph in {}; analyzing the workspaces",
+                    style("ℹ").blue(),
+                    path.display()
+                );
+            }
+            analyzer
+                .discover_workspaces(&config.paths, None)
+                .wrap_err("Failed to discover workspaces")?;
+
+            if analyzer.workspaces().is_empty() {
+                eprintln!("{} No workspaces found to visualize", style("ℹ").blue());
+                return Ok(());
+            }
+
+            // Build dependency graph
+            graph_builder
+                .build_cross_workspace_graph(
+                    analyzer.workspaces(),
+                    analyzer.crate_to_workspace(),
+                    analyzer.crate_path_to_workspace(),
+                    analyzer.crate_to_paths(),
+                    None,
+                )
+                .wrap_err("Failed to build dependency graph")?;
+
+            if let Some(path) = &config.from_cache {
+                GraphCache::new(cache_settings, graph_builder.graph(), analyzer.workspaces())
+                    .write(path)
+                    .into_diagnostic()
+                    .wrap_err_with(|| format!("Failed to save graph to {}", path.display()))?;
+            }
+            graph_builder.graph()
+        };
+
+        // Cut the graph down to the neighborhood of the focused workspace
+        let focused = match &config.focus {
+            Some((workspace, depth)) => {
+                let centers: Vec<_> = full_graph
+                    .node_indices()
+                    .filter(|&idx| full_graph[idx].name() == workspace)
+                    .collect();
+                if centers.is_empty() {
+                    return Err(crate::error::FerrisWheelError::ConfigurationError {
+                        message: format!("Unknown workspace '{workspace}'"),
+                    })
+                    .into_diagnostic();
+                }
+                Some(neighborhood(full_graph, &centers, *depth))
+            }
+            None => None,
+        };
+        let full_graph = focused.as_ref().unwrap_or(full_graph);
+
+        // Drop everything outside the cycles if requested
+        let tangled = config.cycles_only.then(|| cycles_only(full_graph));
+        let full_graph = tangled.as_ref().unwrap_or(full_graph);
+        if config.cycles_only && full_graph.node_count() == 0 {
+            eprintln!("{} No cycles to show", style("ℹ").blue());
+        }
+
+        // Collapse cycles into single nodes if requested
+        let condensed = if config.condensed {
+            Some(condense(full_graph).wrap_err("Failed to condense dependency graph")?)
+        } else {
+            None
+        };
+        let graph = condensed.as_ref().unwrap_or(full_graph);
+
+        // Detect cycles if highlighting is requested or they are animated
+        let cycles = if config.highlight_cycles || matches!(config.format, GraphFormat::Html) {
             let mut detector = CycleDetector::new();
             detector
-                .detect_cycles(graph_builder.graph())
+                .detect_cycles(graph)
                 .wrap_err("Failed to detect cycles")?;
             detector.cycles().to_vec()
         } else {
@@ -65,47 +151,103 @@ impl CommandExecutor for GraphExecutor {
 
         // Create renderer
         let renderer =
-            crate::graph::GraphRenderer::new(config.highlight_cycles, config.show_crates);
+            crate::graph::GraphRenderer::new(config.highlight_cycles, config.show_crates)
+                .with_mermaid_options(config.mermaid)
+                .with_edge_labels(config.edge_labels)
+                .with_severity_model(config.severity_model)
+                .with_nested_workspaces(config.nest_workspaces)
+                .with_grouping(config.grouping.clone())
+                .with_crate_subgraphs(config.crate_subgraphs)
+                .with_palette(config.palette.clone());
+
+        // Refuse to dump binary image data into a terminal
+        if matches!(config.format, GraphFormat::Png | GraphFormat::Pdf)
+            && config.output.is_none()
+            && io::stdout().is_terminal()
+        {
+            return Err(crate::error::FerrisWheelError::ConfigurationError {
+                message: "Image formats need --output or a redirected stdout".to_string(),
+            })
+            .into_diagnostic();
+        }
+
+        // Lay out images before creating the output file, so a missing layout
+        // engine does not leave an empty file behind
+        let image = match config.format {
+            GraphFormat::Png | GraphFormat::Pdf => {
+                let format = if matches!(config.format, GraphFormat::Png) {
+                    ImageFormat::Png
+                } else {
+                    ImageFormat::Pdf
+                };
+                let (engine, image) = render_image(&renderer, graph, &cycles, format)?;
+                eprintln!(
+                    "{} Laid out with {}",
+                    style("ℹ").blue(),
+                    style(engine.program()).bold()
+                );
+                Some(image)
+            }
+            _ => None,
+        };
 
         // Determine output destination
-        let mut output_writer: Box<dyn io::Write> =
-            if let Some(output_path) = config.output.as_ref() {
-                Box::new(BufWriter::new(
-                    File::create(output_path)
-                        .into_diagnostic()
-                        .wrap_err_with(|| {
-                            format!("Failed to create output file '{}'", output_path.display())
-                        })?,
-                ))
-            } else {
-                Box::new(io::stdout())
-            };
+        let mut output_writer = ReportOutput::open(config.output.as_deref())
+            .into_diagnostic()
+            .wrap_err_with(|| match &config.output {
+                Some(output_path) => {
+                    format!("Failed to create output file '{}'", output_path.display())
+                }
+                None => "Failed to open stdout".to_string(),
+            })?;
 
         // Render based on format
         match config.format {
             GraphFormat::Ascii => {
                 renderer
-                    .render_ascii(graph_builder.graph(), &cycles, output_writer.as_mut())
+                    .render_ascii(graph, &cycles, &mut output_writer)
                     .wrap_err("Failed to render ASCII graph")?;
             }
             GraphFormat::Mermaid => {
                 renderer
-                    .render_mermaid(graph_builder.graph(), &cycles, output_writer.as_mut())
+                    .render_mermaid(graph, &cycles, &mut output_writer)
                     .wrap_err("Failed to render Mermaid graph")?;
             }
             GraphFormat::Dot => {
                 renderer
-                    .render_dot(graph_builder.graph(), &cycles, output_writer.as_mut())
+                    .render_dot(graph, &cycles, &mut output_writer)
                     .wrap_err("Failed to render DOT graph")?;
             }
             GraphFormat::D2 => {
                 renderer
-                    .render_d2(graph_builder.graph(), &cycles, output_writer.as_mut())
+                    .render_d2(graph, &cycles, &mut output_writer)
                     .wrap_err("Failed to render D2 graph")?;
             }
+            GraphFormat::Adjacency => {
+                renderer
+                    .render_adjacency(graph, &mut output_writer)
+                    .wrap_err("Failed to render adjacency JSON")?;
+            }
+            GraphFormat::Html => {
+                renderer
+                    .render_html(&cycles, &mut output_writer)
+                    .wrap_err("Failed to render HTML cycle animation")?;
+            }
+            GraphFormat::Png | GraphFormat::Pdf => {
+                if let Some(image) = &image {
+                    output_writer
+                        .write_all(image)
+                        .into_diagnostic()
+                        .wrap_err("Failed to write rendered image")?;
+                }
+            }
         }
+        let written = output_writer
+            .finish()
+            .into_diagnostic()
+            .wrap_err("Failed to write graph output")?;
 
-        if let Some(output_path) = config.output {
+        if let Some(output_path) = written {
             eprintln!(
                 "{} Graph written to {}",
                 style("✓").green(),
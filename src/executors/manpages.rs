@@ -0,0 +1,37 @@
+//! Manpages command executor
+
+use console::style;
+use miette::{IntoDiagnostic, Result, WrapErr};
+
+use crate::commands::manpages::man_command;
+use crate::config::ManpagesConfig;
+use crate::executors::CommandExecutor;
+
+pub struct ManpagesExecutor;
+
+impl CommandExecutor for ManpagesExecutor {
+    type Config = ManpagesConfig;
+
+    fn execute(config: Self::Config) -> Result<()> {
+        let Some(dir) = &config.out_dir else {
+            return clap_mangen::Man::new(man_command())
+                .render(&mut std::io::stdout())
+                .into_diagnostic()
+                .wrap_err("Failed to render man page");
+        };
+
+        std::fs::create_dir_all(dir)
+            .into_diagnostic()
+            .wrap_err_with(|| format!("Failed to create {}", dir.display()))?;
+        clap_mangen::generate_to(man_command(), dir)
+            .into_diagnostic()
+            .wrap_err_with(|| format!("Failed to write man pages to {}", dir.display()))?;
+        eprintln!(
+            "{} Wrote man pages to {}",
+            style("📝").green(),
+            dir.display()
+        );
+
+        Ok(())
+    }
+}
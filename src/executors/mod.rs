@@ -2,9 +2,23 @@
 
 pub mod affected;
 pub mod analyze;
+pub mod between;
+pub mod carousel;
+pub mod chain;
+pub mod changelog;
 pub mod check;
+pub mod completions;
 pub mod deps;
+pub mod diff;
+pub mod fix;
 pub mod graph;
+pub mod manpages;
+pub mod path;
+pub mod schema;
+pub mod stats;
+pub mod sweep;
+pub mod tui;
+pub mod why;
 
 use miette::Result;
 
@@ -0,0 +1,98 @@
+//! Path command executor
+
+use console::style;
+use miette::{IntoDiagnostic, Result, WrapErr};
+
+use crate::analyzer::WorkspaceAnalyzer;
+use crate::cli::OutputFormat;
+use crate::commands::path::{PathReportGenerator, find_dependency_chains};
+use crate::config::WorkspacePathConfig;
+use crate::executors::CommandExecutor;
+use crate::graph::DependencyGraphBuilder;
+use crate::progress::ProgressReporter;
+use crate::reports::output::{ReportOutput, report_written};
+
+pub struct PathExecutor;
+
+impl CommandExecutor for PathExecutor {
+    type Config = WorkspacePathConfig;
+
+    fn execute(config: Self::Config) -> Result<()> {
+        eprintln!(
+            "{} Tracing dependency paths from {} to {}...\n",
+            style("🧭").cyan(),
+            style(&config.from).bold(),
+            style(&config.to).bold()
+        );
+
+        // Create progress reporter if we're in an interactive terminal
+        let mut progress = if console::Term::stderr().is_term() {
+            Some(ProgressReporter::new())
+        } else {
+            None
+        };
+
+        // Discover and analyze workspaces
+        let mut analyzer = WorkspaceAnalyzer::new()
+            .with_backend(config.analyzer.backend)
+            .with_features(config.analyzer.features.clone())
+            .with_workspace_names(config.workspace_names.clone())
+            .with_max_workspaces(config.max_workspaces)
+            .with_excluded_paths(config.exclude_paths.clone())
+            .with_excluded_crates(config.exclude_crates.clone())
+            .with_follow_symlinks(config.follow_symlinks)
+            .with_cache(!config.no_cache);
+        analyzer
+            .discover_workspaces(&config.paths, progress.as_mut())
+            .wrap_err("Failed to discover and analyze workspaces")?;
+
+        let mut graph_builder = DependencyGraphBuilder::new(
+            config.exclude_dev,
+            config.exclude_build,
+            config.exclude_target,
+        )
+        .with_target(config.target);
+        graph_builder
+            .build_cross_workspace_graph(
+                analyzer.workspaces(),
+                analyzer.crate_to_workspace(),
+                analyzer.crate_path_to_workspace(),
+                analyzer.crate_to_paths(),
+                progress.as_ref(),
+            )
+            .wrap_err("Failed to build cross-workspace dependency graph")?;
+
+        let chains = find_dependency_chains(
+            graph_builder.graph(),
+            &config.from,
+            &config.to,
+            config.max_paths,
+        )
+        .into_diagnostic()
+        .wrap_err("Failed to trace dependency paths")?;
+
+        let generator = PathReportGenerator::new(&config.from, &config.to);
+        let report_result = match config.format {
+            OutputFormat::Human => generator.generate_human_report(&chains),
+            OutputFormat::Json => generator.generate_json_report(&chains),
+            OutputFormat::Junit => generator.generate_junit_report(&chains),
+            OutputFormat::GitHub => generator.generate_github_report(&chains),
+        };
+
+        match report_result {
+            Ok(report) => {
+                ReportOutput::write_rendered(config.output.as_deref(), &report)
+                    .map(report_written)
+                    .into_diagnostic()
+                    .wrap_err("Failed to write path report")?;
+            }
+            Err(e) => {
+                return Err(e)
+                    .into_diagnostic()
+                    .wrap_err("Failed to generate path report");
+            }
+        }
+
+        Ok(())
+    }
+}
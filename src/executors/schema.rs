@@ -0,0 +1,18 @@
+//! Schema command executor
+
+use miette::Result;
+
+use crate::commands::schema::generate_schema;
+use crate::config::SchemaConfig;
+use crate::executors::CommandExecutor;
+
+pub struct SchemaExecutor;
+
+impl CommandExecutor for SchemaExecutor {
+    type Config = SchemaConfig;
+
+    fn execute(config: Self::Config) -> Result<()> {
+        println!("{}", generate_schema(config.document)?);
+        Ok(())
+    }
+}
@@ -0,0 +1,88 @@
+//! Stats command executor
+
+use console::style;
+use miette::{IntoDiagnostic, Result, WrapErr};
+
+use crate::analyzer::WorkspaceAnalyzer;
+use crate::cli::StatsFormat;
+use crate::commands::stats::{StatsReport, StatsReportGenerator};
+use crate::config::StatsConfig;
+use crate::executors::CommandExecutor;
+use crate::graph::DependencyGraphBuilder;
+use crate::progress::ProgressReporter;
+use crate::reports::output::{ReportOutput, report_written};
+
+pub struct StatsExecutor;
+
+impl CommandExecutor for StatsExecutor {
+    type Config = StatsConfig;
+
+    fn execute(config: Self::Config) -> Result<()> {
+        eprintln!(
+            "{} Computing workspace dependency metrics...\n",
+            style("📊").cyan()
+        );
+
+        // Create progress reporter if we're in an interactive terminal
+        let mut progress = if console::Term::stderr().is_term() {
+            Some(ProgressReporter::new())
+        } else {
+            None
+        };
+
+        // Discover and analyze workspaces
+        let mut analyzer = WorkspaceAnalyzer::new()
+            .with_backend(config.analyzer.backend)
+            .with_features(config.analyzer.features.clone())
+            .with_workspace_names(config.workspace_names.clone())
+            .with_max_workspaces(config.max_workspaces)
+            .with_excluded_paths(config.exclude_paths.clone())
+            .with_excluded_crates(config.exclude_crates.clone())
+            .with_follow_symlinks(config.follow_symlinks)
+            .with_cache(!config.no_cache);
+        analyzer
+            .discover_workspaces(&config.paths, progress.as_mut())
+            .wrap_err("Failed to discover and analyze workspaces")?;
+
+        let mut graph_builder = DependencyGraphBuilder::new(
+            config.exclude_dev,
+            config.exclude_build,
+            config.exclude_target,
+        )
+        .with_target(config.target);
+        graph_builder
+            .build_cross_workspace_graph(
+                analyzer.workspaces(),
+                analyzer.crate_to_workspace(),
+                analyzer.crate_path_to_workspace(),
+                analyzer.crate_to_paths(),
+                progress.as_ref(),
+            )
+            .wrap_err("Failed to build dependency graph")?;
+
+        let report = StatsReport::compute(graph_builder.graph());
+
+        let generator = StatsReportGenerator::new();
+        let report_result = match config.format {
+            StatsFormat::Human => generator.generate_human_report(&report),
+            StatsFormat::Json => generator.generate_json_report(&report),
+            StatsFormat::Csv => generator.generate_csv_report(&report),
+        };
+
+        match report_result {
+            Ok(output) => {
+                ReportOutput::write_rendered(config.output.as_deref(), &output)
+                    .map(report_written)
+                    .into_diagnostic()
+                    .wrap_err("Failed to write stats report")?;
+            }
+            Err(e) => {
+                return Err(e)
+                    .into_diagnostic()
+                    .wrap_err("Failed to generate stats report");
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,84 @@
+//! Sweep command executor
+
+use console::style;
+use miette::{IntoDiagnostic, Result, WrapErr};
+
+use crate::analyzer::WorkspaceAnalyzer;
+use crate::cli::OutputFormat;
+use crate::commands::sweep::{SweepReport, SweepReportGenerator, SweepScope};
+use crate::config::SweepConfig;
+use crate::executors::CommandExecutor;
+use crate::progress::ProgressReporter;
+use crate::reports::output::{ReportOutput, report_written};
+
+pub struct SweepExecutor;
+
+impl CommandExecutor for SweepExecutor {
+    type Config = SweepConfig;
+
+    fn execute(config: Self::Config) -> Result<()> {
+        eprintln!(
+            "{} Sweeping for internal dependencies the sources never use...\n",
+            style("🧹").cyan()
+        );
+
+        // Create progress reporter if we're in an interactive terminal
+        let mut progress = if console::Term::stderr().is_term() {
+            Some(ProgressReporter::new())
+        } else {
+            None
+        };
+
+        // Discover and analyze workspaces
+        let mut analyzer = WorkspaceAnalyzer::new()
+            .with_backend(config.analyzer.backend)
+            .with_features(config.analyzer.features.clone())
+            .with_workspace_names(config.workspace_names.clone())
+            .with_max_workspaces(config.max_workspaces)
+            .with_excluded_paths(config.exclude_paths.clone())
+            .with_excluded_crates(config.exclude_crates.clone())
+            .with_follow_symlinks(config.follow_symlinks)
+            .with_cache(!config.no_cache);
+        analyzer
+            .discover_workspaces(&config.paths, progress.as_mut())
+            .wrap_err("Failed to discover and analyze workspaces")?;
+
+        let report = SweepReport::scan(
+            analyzer.workspaces(),
+            analyzer.crate_to_paths(),
+            SweepScope {
+                dev: !config.exclude_dev,
+                build: !config.exclude_build,
+                target: !config.exclude_target,
+            },
+        );
+
+        let generator = SweepReportGenerator::new();
+        let report_result = match config.format {
+            OutputFormat::Human => generator.generate_human_report(&report),
+            OutputFormat::Json => generator.generate_json_report(&report),
+            OutputFormat::Junit => generator.generate_junit_report(&report),
+            OutputFormat::GitHub => generator.generate_github_report(&report),
+        };
+
+        match report_result {
+            Ok(output) => {
+                ReportOutput::write_rendered(config.output.as_deref(), &output)
+                    .map(report_written)
+                    .into_diagnostic()
+                    .wrap_err("Failed to write sweep report")?;
+            }
+            Err(e) => {
+                return Err(e)
+                    .into_diagnostic()
+                    .wrap_err("Failed to generate sweep report");
+            }
+        }
+
+        if report.has_unused() {
+            crate::timings::exit(1);
+        }
+
+        Ok(())
+    }
+}
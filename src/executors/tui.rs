@@ -0,0 +1,95 @@
+//! TUI command executor
+
+use console::style;
+use miette::{IntoDiagnostic, Result, WrapErr};
+use ratatui::DefaultTerminal;
+use ratatui::crossterm::event::{self, Event, KeyEventKind};
+
+use crate::analyzer::WorkspaceAnalyzer;
+use crate::commands::tui::App;
+use crate::config::TuiConfig;
+use crate::dependency_filter::DependencyFilter;
+use crate::error::FerrisWheelError;
+use crate::executors::CommandExecutor;
+use crate::graph::DependencyGraphBuilder;
+use crate::progress::ProgressReporter;
+
+pub struct TuiExecutor;
+
+impl CommandExecutor for TuiExecutor {
+    type Config = TuiConfig;
+
+    fn execute(config: Self::Config) -> Result<()> {
+        if !console::Term::stdout().is_term() {
+            return Err(FerrisWheelError::ConfigurationError {
+                message: "The tui command needs an interactive terminal".to_string(),
+            })
+            .into_diagnostic();
+        }
+
+        eprintln!("{} Loading the fairground...\n", style("🎡").cyan());
+
+        let mut progress = Some(ProgressReporter::new());
+
+        // Discover and analyze workspaces
+        let mut analyzer = WorkspaceAnalyzer::new()
+            .with_backend(config.analyzer.backend)
+            .with_features(config.analyzer.features.clone())
+            .with_workspace_names(config.workspace_names.clone())
+            .with_max_workspaces(config.max_workspaces)
+            .with_excluded_paths(config.exclude_paths.clone())
+            .with_excluded_crates(config.exclude_crates.clone())
+            .with_follow_symlinks(config.follow_symlinks)
+            .with_cache(!config.no_cache);
+        analyzer
+            .discover_workspaces(&config.paths, progress.as_mut())
+            .wrap_err("Failed to discover and analyze workspaces")?;
+
+        // Build the graph with every dependency kind, the explorer filters it
+        // as the kinds are toggled
+        let mut graph_builder =
+            DependencyGraphBuilder::filtered(DependencyFilter::all().with_target(config.target));
+        graph_builder
+            .build_cross_workspace_graph(
+                analyzer.workspaces(),
+                analyzer.crate_to_workspace(),
+                analyzer.crate_path_to_workspace(),
+                analyzer.crate_to_paths(),
+                progress.as_ref(),
+            )
+            .wrap_err("Failed to build dependency graph")?;
+        drop(progress);
+
+        let mut app = App::new(
+            graph_builder.graph().clone(),
+            config.exclude_dev,
+            config.exclude_build,
+            config.exclude_target,
+        )?;
+
+        let mut terminal = ratatui::try_init()
+            .into_diagnostic()
+            .wrap_err("Failed to initialize the terminal")?;
+        let result = run(&mut terminal, &mut app);
+        ratatui::restore();
+        result
+    }
+}
+
+/// Draw the explorer and handle key presses until it is closed
+fn run(terminal: &mut DefaultTerminal, app: &mut App) -> Result<()> {
+    while !app.should_quit() {
+        terminal
+            .draw(|frame| app.render(frame))
+            .into_diagnostic()
+            .wrap_err("Failed to draw the explorer")?;
+
+        if let Event::Key(key) = event::read().into_diagnostic()?
+            && key.kind == KeyEventKind::Press
+        {
+            app.handle_key(key)?;
+        }
+    }
+
+    Ok(())
+}
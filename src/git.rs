@@ -0,0 +1,263 @@
+//! Thin wrappers around the `git` command line
+//!
+//! Used by commands that need to look at the repository at another revision.
+
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use crate::error::FerrisWheelError;
+
+static WORKTREE_COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+/// Run `git` in `dir` and return its trimmed stdout
+pub fn run_git(dir: &Path, args: &[&str]) -> Result<String, FerrisWheelError> {
+    let output = Command::new("git")
+        .args(args)
+        .current_dir(dir)
+        .output()
+        .map_err(|e| FerrisWheelError::GitError {
+            message: format!("Failed to run git: {e}"),
+        })?;
+
+    if !output.status.success() {
+        return Err(FerrisWheelError::GitError {
+            message: format!(
+                "`git {}` failed: {}",
+                args.join(" "),
+                String::from_utf8_lossy(&output.stderr).trim()
+            ),
+        });
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// Find the top-level directory of the repository containing `path`
+pub fn repo_root(path: &Path) -> Result<PathBuf, FerrisWheelError> {
+    let dir = if path.is_dir() {
+        path
+    } else {
+        path.parent().unwrap_or(path)
+    };
+    let root = run_git(dir, &["rev-parse", "--show-toplevel"])?;
+    let root = PathBuf::from(root);
+    Ok(root.canonicalize().unwrap_or(root))
+}
+
+/// Name of the branch being built or checked out in `dir`
+///
+/// CI-provided branch names take precedence (`GITHUB_HEAD_REF` for pull
+/// requests, then `GITHUB_REF_NAME`), so detached CI checkouts still resolve
+/// to a branch. Returns `None` for a detached `HEAD` outside CI.
+pub fn current_branch(dir: &Path) -> Option<String> {
+    ["GITHUB_HEAD_REF", "GITHUB_REF_NAME"]
+        .iter()
+        .filter_map(|var| std::env::var(var).ok())
+        .find(|branch| !branch.is_empty())
+        .or_else(|| {
+            run_git(dir, &["rev-parse", "--abbrev-ref", "HEAD"])
+                .ok()
+                .filter(|branch| !branch.is_empty() && branch != "HEAD")
+        })
+}
+
+/// Files changed in `dir` since `rev`, relative to `dir`
+///
+/// Covers committed and uncommitted changes to tracked files, both sides of
+/// renames, and untracked files that are not ignored. Files outside `dir` are
+/// left out.
+pub fn changed_files(dir: &Path, rev: &str) -> Result<Vec<String>, FerrisWheelError> {
+    let changed = run_git(
+        dir,
+        &[
+            "diff",
+            "--name-only",
+            "--no-renames",
+            "--relative",
+            "-z",
+            rev,
+            "--",
+        ],
+    )?;
+    let untracked = run_git(dir, &["ls-files", "--others", "--exclude-standard", "-z"])?;
+
+    let mut files: Vec<String> = changed
+        .split('\0')
+        .chain(untracked.split('\0'))
+        .filter(|file| !file.is_empty())
+        .map(str::to_string)
+        .collect();
+    files.sort();
+    files.dedup();
+    Ok(files)
+}
+
+/// Contents of the file `path`, relative to `dir`, at revision `rev`
+pub fn show_file(dir: &Path, rev: &str, path: &str) -> Result<String, FerrisWheelError> {
+    run_git(dir, &["show", &format!("{rev}:./{path}")])
+}
+
+/// The best common ancestor of `rev` and `HEAD` in `dir`
+pub fn merge_base(dir: &Path, rev: &str) -> Result<String, FerrisWheelError> {
+    run_git(dir, &["merge-base", rev, "HEAD"])
+}
+
+/// A temporary, detached checkout of a revision
+///
+/// The worktree directory keeps the repository's directory name so that
+/// workspace names derived from directories match the main checkout. It is
+/// removed again when dropped.
+pub struct Worktree {
+    repo_root: PathBuf,
+    container: PathBuf,
+    path: PathBuf,
+}
+
+impl Worktree {
+    /// Check out `rev` from the repository at `repo_root` into a temporary
+    /// directory
+    pub fn checkout(repo_root: &Path, rev: &str) -> Result<Self, FerrisWheelError> {
+        let container = std::env::temp_dir().join(format!(
+            "ferris-wheel-{}-{}",
+            std::process::id(),
+            WORKTREE_COUNTER.fetch_add(1, Ordering::Relaxed)
+        ));
+        let path = container.join(repo_root.file_name().unwrap_or_else(|| "repo".as_ref()));
+        std::fs::create_dir_all(&container)?;
+
+        let path_str = path.to_string_lossy().to_string();
+        if let Err(e) = run_git(
+            repo_root,
+            &["worktree", "add", "--detach", "--quiet", &path_str, rev],
+        ) {
+            let _ = std::fs::remove_dir_all(&container);
+            return Err(e);
+        }
+
+        let path = path.canonicalize().unwrap_or(path);
+        Ok(Self {
+            repo_root: repo_root.to_path_buf(),
+            container,
+            path,
+        })
+    }
+
+    /// Map a path inside the main checkout to the same path in this worktree
+    pub fn map_path(&self, path: &Path) -> PathBuf {
+        let canonical = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+        match canonical.strip_prefix(&self.repo_root) {
+            Ok(relative) => self.path.join(relative),
+            Err(_) => self.path.clone(),
+        }
+    }
+}
+
+impl Drop for Worktree {
+    fn drop(&mut self) {
+        let path_str = self.path.to_string_lossy().to_string();
+        let _ = run_git(
+            &self.repo_root,
+            &["worktree", "remove", "--force", &path_str],
+        );
+        let _ = std::fs::remove_dir_all(&self.container);
+        let _ = run_git(&self.repo_root, &["worktree", "prune"]);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tempfile::TempDir;
+
+    use super::*;
+
+    fn init_repo(dir: &Path) {
+        run_git(dir, &["init", "--quiet"]).unwrap();
+        run_git(dir, &["config", "user.email", "test@example.com"]).unwrap();
+        run_git(dir, &["config", "user.name", "Test"]).unwrap();
+        run_git(dir, &["config", "commit.gpgsign", "false"]).unwrap();
+    }
+
+    #[test]
+    fn test_worktree_checkout_and_cleanup() {
+        let temp = TempDir::new().unwrap();
+        let repo = temp.path().join("monorepo");
+        std::fs::create_dir_all(repo.join("core")).unwrap();
+        init_repo(&repo);
+
+        std::fs::write(repo.join("core/file.txt"), "v1").unwrap();
+        run_git(&repo, &["add", "-A"]).unwrap();
+        run_git(&repo, &["commit", "--quiet", "-m", "one"]).unwrap();
+        run_git(&repo, &["tag", "v1"]).unwrap();
+        std::fs::write(repo.join("core/file.txt"), "v2").unwrap();
+        run_git(&repo, &["commit", "--quiet", "-am", "two"]).unwrap();
+
+        let root = repo_root(&repo.join("core")).unwrap();
+        assert_eq!(root, repo.canonicalize().unwrap());
+
+        let worktree_path;
+        {
+            let worktree = Worktree::checkout(&root, "v1").unwrap();
+            worktree_path = worktree.map_path(&repo);
+            assert_eq!(worktree_path.file_name().unwrap(), "monorepo");
+
+            let mapped = worktree.map_path(&repo.join("core"));
+            assert_eq!(
+                std::fs::read_to_string(mapped.join("file.txt")).unwrap(),
+                "v1"
+            );
+        }
+        assert!(!worktree_path.exists());
+    }
+
+    #[test]
+    fn test_changed_files_since_merge_base() {
+        let temp = TempDir::new().unwrap();
+        let repo = temp.path();
+        std::fs::create_dir_all(repo.join("core/src")).unwrap();
+        std::fs::create_dir_all(repo.join("api/src")).unwrap();
+        init_repo(repo);
+
+        std::fs::write(repo.join("core/src/lib.rs"), "v1").unwrap();
+        std::fs::write(repo.join("api/src/lib.rs"), "v1").unwrap();
+        std::fs::write(repo.join(".gitignore"), "target\n").unwrap();
+        run_git(repo, &["add", "-A"]).unwrap();
+        run_git(repo, &["commit", "--quiet", "-m", "one"]).unwrap();
+        run_git(repo, &["branch", "main"]).unwrap();
+        run_git(repo, &["checkout", "--quiet", "-b", "feature"]).unwrap();
+
+        // Committed, renamed, uncommitted, untracked and ignored changes
+        std::fs::write(repo.join("core/src/lib.rs"), "v2").unwrap();
+        run_git(repo, &["commit", "--quiet", "-am", "two"]).unwrap();
+        run_git(repo, &["mv", "api/src/lib.rs", "api/src/main.rs"]).unwrap();
+        std::fs::write(repo.join("api/src/new.rs"), "").unwrap();
+        std::fs::create_dir_all(repo.join("target")).unwrap();
+        std::fs::write(repo.join("target/out"), "").unwrap();
+
+        let base = merge_base(repo, "main").unwrap();
+        assert_eq!(
+            changed_files(repo, &base).unwrap(),
+            vec![
+                "api/src/lib.rs",
+                "api/src/main.rs",
+                "api/src/new.rs",
+                "core/src/lib.rs"
+            ]
+        );
+        assert_eq!(
+            changed_files(&repo.join("api"), &base).unwrap(),
+            vec!["src/lib.rs", "src/main.rs", "src/new.rs"]
+        );
+    }
+
+    #[test]
+    fn test_unknown_revision_is_an_error() {
+        let temp = TempDir::new().unwrap();
+        init_repo(temp.path());
+
+        assert!(matches!(
+            Worktree::checkout(temp.path(), "does-not-exist"),
+            Err(FerrisWheelError::GitError { .. })
+        ));
+    }
+}
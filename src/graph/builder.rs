@@ -1,16 +1,22 @@
 use std::collections::{BTreeSet, HashMap};
 use std::path::{Path, PathBuf};
+use std::sync::Arc;
 
 use miette::{Result, WrapErr};
 use petgraph::graph::{DiGraph, NodeIndex};
+use petgraph::visit::EdgeRef;
 
-use super::types::{DependencyEdge, DependencyType, WorkspaceNode};
+use super::types::{DependencyEdge, DependencyType, HypotheticalEdge, ResolvedEdge, WorkspaceNode};
 use crate::analyzer::{
-    CratePathToWorkspaceMap, CrateWorkspaceMap, Dependency, DependencyBuilder, WorkspaceInfo,
+    CratePathToWorkspaceMap, CrateWorkspaceMap, Dependency, TargetPlatform, WorkspaceInfo,
 };
 use crate::common::ConfigBuilder;
 use crate::dependency_filter::DependencyFilter;
+use crate::error::FerrisWheelError;
 use crate::progress::ProgressReporter;
+use crate::timings::{Phase, Timer};
+use crate::utils::canonical::{canonicalize, canonicalize_or_self};
+use crate::utils::intern::Interner;
 
 /// Builder for constructing dependency graphs
 ///
@@ -20,6 +26,8 @@ pub struct DependencyGraphBuilder {
     graph: DiGraph<WorkspaceNode, DependencyEdge>,
     workspace_indices: HashMap<PathBuf, NodeIndex>,
     filter: DependencyFilter,
+    /// Crate names and targets shared by the edges of this graph
+    names: Interner,
 }
 
 struct DependencyLookupContext<'a> {
@@ -40,21 +48,34 @@ impl DependencyGraphBuilder {
     /// * `exclude_build` - Exclude build dependencies from the graph
     /// * `exclude_target` - Exclude target-specific dependencies from the graph
     pub fn new(exclude_dev: bool, exclude_build: bool, exclude_target: bool) -> Self {
+        Self::filtered(DependencyFilter::new(
+            exclude_dev,
+            exclude_build,
+            exclude_target,
+        ))
+    }
+
+    /// Create a dependency graph builder keeping only the dependencies
+    /// `filter` includes
+    pub fn filtered(filter: DependencyFilter) -> Self {
         Self {
             graph: DiGraph::new(),
             workspace_indices: HashMap::new(),
-            filter: DependencyFilter::new(exclude_dev, exclude_build, exclude_target),
+            filter,
+            names: Interner::new(),
         }
     }
 
+    /// Only include target-specific dependencies that apply to `target`
+    pub fn with_target(mut self, target: Option<TargetPlatform>) -> Self {
+        self.filter = self.filter.with_target(target);
+        self
+    }
+
     /// Check if a dependency type should be included based on the filter
     /// settings
     fn should_include_dependency_type(&self, dep_type: &DependencyType) -> bool {
-        match dep_type {
-            DependencyType::Normal => true, // Normal deps are always included
-            DependencyType::Dev => self.filter.include_dev(),
-            DependencyType::Build => self.filter.include_build(),
-        }
+        self.filter.includes_kind(dep_type)
     }
 
     /// Build a graph showing dependencies between crates within workspaces
@@ -66,6 +87,7 @@ impl DependencyGraphBuilder {
         workspaces: &HashMap<PathBuf, WorkspaceInfo>,
         progress: Option<&ProgressReporter>,
     ) -> Result<()> {
+        let _timer = Timer::start(Phase::GraphBuild);
         // Create a crate-level graph for detecting cycles within workspaces
         // Each crate becomes a node, edges represent dependencies between crates in the
         // same workspace
@@ -125,15 +147,16 @@ impl DependencyGraphBuilder {
                                 .map(|(path, _)| path);
 
                             if dep_workspace == Some(ws_path) {
-                                let edge = DependencyEdge::builder()
-                                    .with_from_crate(member.name())
-                                    .with_to_crate(dep.name())
-                                    .with_dependency_type(dep_type.clone())
-                                    .with_target(dep.target().map(|t| t.to_string()))
-                                    .build()
-                                    .wrap_err("Failed to build DependencyEdge")?;
-
-                                self.graph.add_edge(from_idx, *dep_crate_idx, edge);
+                                let edge = DependencyEdge::from_interned(
+                                    self.names.intern(member.name()),
+                                    self.names.intern(dep.name()),
+                                    dep_type,
+                                    dep.target().map(|target| self.names.intern(target)),
+                                );
+
+                                if self.filter.includes(ws_info.name(), ws_info.name(), &edge) {
+                                    self.graph.add_edge(from_idx, *dep_crate_idx, edge);
+                                }
                             }
                         }
                     }
@@ -158,15 +181,17 @@ impl DependencyGraphBuilder {
                                 .map(|(path, _)| path);
 
                             if dep_workspace == Some(ws_path) {
-                                let edge = DependencyEdge::builder()
-                                    .with_from_crate(member.name())
-                                    .with_to_crate(dep.name())
-                                    .with_dependency_type(DependencyType::Normal) // Target deps are treated as normal
-                                    .with_target(Some(target.clone()))
-                                    .build()
-                                    .wrap_err("Failed to build DependencyEdge")?;
-
-                                self.graph.add_edge(from_idx, *dep_crate_idx, edge);
+                                let edge = DependencyEdge::from_interned(
+                                    self.names.intern(member.name()),
+                                    self.names.intern(dep.name()),
+                                    // Target deps are treated as normal
+                                    DependencyType::Normal,
+                                    Some(self.names.intern(target)),
+                                );
+
+                                if self.filter.includes(ws_info.name(), ws_info.name(), &edge) {
+                                    self.graph.add_edge(from_idx, *dep_crate_idx, edge);
+                                }
                             }
                         }
                     }
@@ -177,11 +202,24 @@ impl DependencyGraphBuilder {
         Ok(())
     }
 
-    fn resolve_dependency_targets(
+    fn resolve_dependency_targets<'a>(
         &self,
         dep: &Dependency,
-        ctx: &DependencyLookupContext<'_>,
-    ) -> Vec<PathBuf> {
+        ctx: &DependencyLookupContext<'a>,
+    ) -> impl Iterator<Item = &'a Path> + use<'a> {
+        let current_workspace_path = ctx.current_workspace_path;
+        self.resolve_dependency_workspaces(dep, ctx)
+            .into_iter()
+            .filter(move |&path| path != current_workspace_path)
+    }
+
+    /// Resolve the workspaces a dependency may point to, including the
+    /// workspace of the depending crate
+    fn resolve_dependency_workspaces<'a>(
+        &self,
+        dep: &Dependency,
+        ctx: &DependencyLookupContext<'a>,
+    ) -> BTreeSet<&'a Path> {
         let mut targets = BTreeSet::new();
 
         if let Some(dep_path) = dep.path() {
@@ -197,14 +235,12 @@ impl DependencyGraphBuilder {
                 base_path.join(dep_path)
             };
 
-            let canonical = absolute_path
-                .canonicalize()
-                .unwrap_or_else(|_| absolute_path.clone());
+            let canonical = canonicalize_or_self(&absolute_path);
 
             if let Some(ws_path) = ctx.crate_path_to_workspace.get(&canonical) {
-                targets.insert(ws_path.clone());
+                targets.insert(ws_path.as_path());
             } else if let Some(ws_path) = ctx.crate_path_to_workspace.get(&absolute_path) {
-                targets.insert(ws_path.clone());
+                targets.insert(ws_path.as_path());
             }
 
             if targets.is_empty()
@@ -217,16 +253,16 @@ impl DependencyGraphBuilder {
                     if matches_candidate
                         && let Some(ws) = ctx.crate_path_to_workspace.get(candidate)
                     {
-                        targets.insert(ws.clone());
+                        targets.insert(ws.as_path());
                         continue;
                     }
 
-                    if let Ok(candidate_canon) = candidate.canonicalize()
+                    if let Some(candidate_canon) = canonicalize(candidate)
                         && (canonical.starts_with(&candidate_canon)
                             || candidate_canon.starts_with(&canonical))
                         && let Some(ws) = ctx.crate_path_to_workspace.get(&candidate_canon)
                     {
-                        targets.insert(ws.clone());
+                        targets.insert(ws.as_path());
                     }
                 }
             }
@@ -236,13 +272,111 @@ impl DependencyGraphBuilder {
             && let Some(workspaces) = ctx.crate_to_workspaces.get(dep.name())
             && workspaces.len() == 1
         {
-            targets.extend(workspaces.iter().cloned());
+            targets.extend(workspaces.iter().map(PathBuf::as_path));
         }
 
         targets
-            .into_iter()
-            .filter(|path| path != ctx.current_workspace_path)
-            .collect()
+    }
+
+    /// Build a graph where every crate in every workspace is a node
+    ///
+    /// Unlike the intra-workspace graph, edges are created for dependencies
+    /// both within and across workspaces, so cycles are reported crate to
+    /// crate. Nodes are named `workspace/crate`.
+    pub fn build_crate_graph(
+        &mut self,
+        workspaces: &HashMap<PathBuf, WorkspaceInfo>,
+        crate_to_workspaces: &CrateWorkspaceMap,
+        crate_path_to_workspace: &CratePathToWorkspaceMap,
+        crate_to_paths: &HashMap<String, Vec<PathBuf>>,
+        progress: Option<&ProgressReporter>,
+    ) -> Result<()> {
+        let _timer = Timer::start(Phase::GraphBuild);
+        let mut crate_indices: HashMap<(PathBuf, String), NodeIndex> = HashMap::new();
+
+        for (ws_path, ws_info) in workspaces {
+            for member in ws_info.members() {
+                let node = WorkspaceNode::builder()
+                    .with_name(format!("{}/{}", ws_info.name(), member.name()))
+                    .with_path(member.path().clone())
+                    .with_crates(vec![member.name().to_string()])
+                    .build()
+                    .wrap_err("Failed to build WorkspaceNode")?;
+
+                let idx = self.graph.add_node(node);
+                crate_indices.insert((ws_path.clone(), member.name().to_string()), idx);
+            }
+        }
+
+        for (ws_path, ws_info) in workspaces {
+            if let Some(p) = progress {
+                p.analyzing_workspace(ws_info.name());
+            }
+
+            for member in ws_info.members() {
+                let from_idx = crate_indices[&(ws_path.clone(), member.name().to_string())];
+                let lookup_ctx = DependencyLookupContext {
+                    crate_to_workspaces,
+                    crate_path_to_workspace,
+                    crate_to_paths,
+                    current_workspace_path: ws_path.as_path(),
+                    from_crate_path: member.path(),
+                };
+
+                let mut all_deps: Vec<(&Dependency, DependencyType, Option<&str>)> = Vec::new();
+                for (deps, dep_type) in [
+                    (member.dependencies(), DependencyType::Normal),
+                    (member.dev_dependencies(), DependencyType::Dev),
+                    (member.build_dependencies(), DependencyType::Build),
+                ] {
+                    if self.should_include_dependency_type(&dep_type) {
+                        all_deps.extend(deps.iter().map(|dep| (dep, dep_type, dep.target())));
+                    }
+                }
+                if self.filter.include_target() {
+                    for (target, deps) in member.target_dependencies() {
+                        all_deps.extend(
+                            deps.iter()
+                                .map(|dep| (dep, DependencyType::Normal, Some(target.as_str()))),
+                        );
+                    }
+                }
+
+                for (dep, dep_type, target) in all_deps {
+                    if !self.filter.includes_target(target) {
+                        continue;
+                    }
+
+                    for target_ws_path in self.resolve_dependency_workspaces(dep, &lookup_ctx) {
+                        let Some(&to_idx) = crate_indices
+                            .get(&(target_ws_path.to_path_buf(), dep.name().to_string()))
+                        else {
+                            continue;
+                        };
+                        if to_idx == from_idx {
+                            continue;
+                        }
+
+                        let edge = DependencyEdge::from_interned(
+                            self.names.intern(member.name()),
+                            self.names.intern(dep.name()),
+                            dep_type,
+                            target.map(|target| self.names.intern(target)),
+                        );
+
+                        if self.filter.includes(
+                            ws_info.name(),
+                            workspaces[target_ws_path].name(),
+                            &edge,
+                        ) {
+                            self.graph.add_edge(from_idx, to_idx, edge);
+                        }
+                    }
+                }
+            }
+        }
+
+        Ok(())
     }
 
     pub fn build_cross_workspace_graph(
@@ -253,6 +387,7 @@ impl DependencyGraphBuilder {
         crate_to_paths: &HashMap<String, Vec<PathBuf>>,
         progress: Option<&ProgressReporter>,
     ) -> Result<()> {
+        let _timer = Timer::start(Phase::GraphBuild);
         // First, create nodes for all workspaces
         for (ws_path, ws_info) in workspaces {
             let node = WorkspaceNode::builder()
@@ -265,6 +400,12 @@ impl DependencyGraphBuilder {
                         .map(|m| m.name().to_string())
                         .collect(),
                 )
+                .with_parent(
+                    ws_info
+                        .parent()
+                        .and_then(|parent| workspaces.get(parent))
+                        .map(|parent| parent.name().to_string()),
+                )
                 .build()
                 .wrap_err("Failed to build WorkspaceNode")?;
 
@@ -282,6 +423,7 @@ impl DependencyGraphBuilder {
 
             // Check each crate in this workspace
             for member in ws_info.members() {
+                let from_crate = self.names.intern(member.name());
                 let lookup_ctx = DependencyLookupContext {
                     crate_to_workspaces,
                     crate_path_to_workspace,
@@ -290,13 +432,14 @@ impl DependencyGraphBuilder {
                     from_crate_path: member.path(),
                 };
 
-                // Process normal dependencies (always included)
+                // Process normal dependencies
                 for dep in member.dependencies() {
                     self.process_dependency(
                         from_idx,
-                        member.name(),
+                        &from_crate,
                         dep,
                         DependencyType::Normal,
+                        dep.target(),
                         &lookup_ctx,
                     )
                     .wrap_err_with(|| {
@@ -313,9 +456,10 @@ impl DependencyGraphBuilder {
                     for dep in member.dev_dependencies() {
                         self.process_dependency(
                             from_idx,
-                            member.name(),
+                            &from_crate,
                             dep,
                             DependencyType::Dev,
+                            dep.target(),
                             &lookup_ctx,
                         )
                         .wrap_err_with(|| {
@@ -333,9 +477,10 @@ impl DependencyGraphBuilder {
                     for dep in member.build_dependencies() {
                         self.process_dependency(
                             from_idx,
-                            member.name(),
+                            &from_crate,
                             dep,
                             DependencyType::Build,
+                            dep.target(),
                             &lookup_ctx,
                         )
                         .wrap_err_with(|| {
@@ -352,14 +497,12 @@ impl DependencyGraphBuilder {
                 if self.filter.include_target() {
                     for (target, deps) in member.target_dependencies() {
                         for dep in deps {
-                            let dep = DependencyBuilder::from(dep)
-                                .with_target(target.clone())
-                                .build()?;
                             self.process_dependency(
                                 from_idx,
-                                member.name(),
-                                &dep,
+                                &from_crate,
+                                dep,
                                 DependencyType::Normal,
+                                Some(target),
                                 &lookup_ctx,
                             )
                             .wrap_err_with(|| {
@@ -383,32 +526,45 @@ impl DependencyGraphBuilder {
     fn process_dependency(
         &mut self,
         from_ws_idx: NodeIndex,
-        from_crate: &str,
+        from_crate: &Arc<str>,
         dep: &Dependency,
         dep_type: DependencyType,
+        target: Option<&str>,
         ctx: &DependencyLookupContext<'_>,
     ) -> Result<()> {
         // Skip if this specific dependency should be filtered out (e.g.,
         // target-specific)
-        if !self.filter.should_include_dependency(dep) {
+        if !self.filter.includes_target(target) {
             return Ok(());
         }
 
-        let target_workspaces = self.resolve_dependency_targets(dep, ctx);
-
-        for target_ws_path in target_workspaces {
-            if let Some(&to_ws_idx) = self.workspace_indices.get(&target_ws_path)
+        // Most dependencies are on crates outside the analyzed workspaces, so
+        // names are only interned once an edge is actually added
+        let mut names = None;
+        for target_ws_path in self.resolve_dependency_targets(dep, ctx) {
+            if let Some(&to_ws_idx) = self.workspace_indices.get(target_ws_path)
                 && from_ws_idx != to_ws_idx
             {
-                let edge = DependencyEdge::builder()
-                    .with_from_crate(from_crate)
-                    .with_to_crate(dep.name())
-                    .with_dependency_type(dep_type.clone())
-                    .with_target(dep.target().map(|t| t.to_string()))
-                    .build()
-                    .wrap_err("Failed to build DependencyEdge")?;
-
-                self.graph.add_edge(from_ws_idx, to_ws_idx, edge);
+                let (to_crate, target) = names.get_or_insert_with(|| {
+                    (
+                        self.names.intern(dep.name()),
+                        target.map(|target| self.names.intern(target)),
+                    )
+                });
+                let edge = DependencyEdge::from_interned(
+                    Arc::clone(from_crate),
+                    Arc::clone(to_crate),
+                    dep_type,
+                    target.clone(),
+                );
+
+                if self.filter.includes(
+                    self.graph[from_ws_idx].name(),
+                    self.graph[to_ws_idx].name(),
+                    &edge,
+                ) {
+                    self.graph.add_edge(from_ws_idx, to_ws_idx, edge);
+                }
             }
         }
 
@@ -418,13 +574,72 @@ impl DependencyGraphBuilder {
     pub fn graph(&self) -> &DiGraph<WorkspaceNode, DependencyEdge> {
         &self.graph
     }
+
+    /// Every edge of the built graph with its endpoints resolved
+    pub fn edges(&self) -> impl Iterator<Item = ResolvedEdge<'_>> {
+        self.graph.edge_references().map(|edge| ResolvedEdge {
+            from: &self.graph[edge.source()],
+            to: &self.graph[edge.target()],
+            edge: edge.weight(),
+        })
+    }
+
+    /// Edges of the built graph between two different workspaces
+    ///
+    /// For crate graphs this skips dependencies between crates of the same
+    /// workspace; every edge of a cross-workspace graph is included.
+    pub fn cross_workspace_edges(&self) -> impl Iterator<Item = ResolvedEdge<'_>> {
+        self.edges().filter(ResolvedEdge::is_cross_workspace)
+    }
+
+    /// Add a dependency that does not exist yet to the built graph
+    ///
+    /// The edge is added as a normal dependency between the nodes containing
+    /// each crate. Returns `false` without changing the graph when both
+    /// crates belong to the same node, since such an edge cannot create a
+    /// cycle at this granularity.
+    pub fn add_hypothetical_edge(&mut self, edge: &HypotheticalEdge) -> Result<bool> {
+        let from_idx = self.node_containing(edge.from_crate())?;
+        let to_idx = self.node_containing(edge.to_crate())?;
+        if from_idx == to_idx {
+            return Ok(false);
+        }
+
+        let dependency = DependencyEdge::builder()
+            .with_from_crate(edge.from_crate())
+            .with_to_crate(edge.to_crate())
+            .with_dependency_type(DependencyType::Normal)
+            .build()
+            .wrap_err("Failed to build DependencyEdge")?;
+        self.graph.add_edge(from_idx, to_idx, dependency);
+
+        Ok(true)
+    }
+
+    fn node_containing(&self, crate_name: &str) -> Result<NodeIndex> {
+        let mut nodes = self
+            .graph
+            .node_indices()
+            .filter(|&idx| self.graph[idx].crates().iter().any(|c| c == crate_name));
+
+        match (nodes.next(), nodes.next()) {
+            (Some(idx), None) => Ok(idx),
+            (None, _) => Err(FerrisWheelError::ConfigurationError {
+                message: format!("Unknown crate '{crate_name}'"),
+            }
+            .into()),
+            (Some(_), Some(_)) => Err(FerrisWheelError::ConfigurationError {
+                message: format!("Crate '{crate_name}' exists in more than one workspace"),
+            }
+            .into()),
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use std::fs;
 
-    use petgraph::visit::EdgeRef;
     use tempfile::TempDir;
 
     use super::*;
@@ -521,6 +736,52 @@ mod tests {
         assert_eq!(builder.graph.edge_count(), 1);
     }
 
+    #[test]
+    fn test_add_hypothetical_edge() {
+        let mut builder = DependencyGraphBuilder::new(false, false, false);
+        for (name, crates) in [
+            ("workspace-a", vec!["crate-a", "crate-a2"]),
+            ("workspace-b", vec!["crate-b"]),
+            ("workspace-c", vec!["crate-a2"]),
+        ] {
+            builder.graph.add_node(
+                WorkspaceNode::builder()
+                    .with_name(name.to_string())
+                    .with_crates(crates.into_iter().map(String::from).collect())
+                    .build()
+                    .unwrap(),
+            );
+        }
+
+        assert!(
+            builder
+                .add_hypothetical_edge(&"crate-b:crate-a".parse().unwrap())
+                .unwrap()
+        );
+        let edge = builder.graph.edge_references().next().unwrap();
+        assert_eq!(builder.graph[edge.source()].name(), "workspace-b");
+        assert_eq!(builder.graph[edge.target()].name(), "workspace-a");
+        assert_eq!(edge.weight().dependency_type(), &DependencyType::Normal);
+
+        assert!(
+            !builder
+                .add_hypothetical_edge(&HypotheticalEdge::new("crate-b", "crate-b"))
+                .unwrap()
+        );
+        assert!(
+            builder
+                .add_hypothetical_edge(&HypotheticalEdge::new("crate-b", "missing"))
+                .is_err()
+        );
+        assert!(
+            builder
+                .add_hypothetical_edge(&HypotheticalEdge::new("crate-b", "crate-a2"))
+                .is_err()
+        );
+        assert_eq!(builder.graph.edge_count(), 1);
+        assert!("crate-a".parse::<HypotheticalEdge>().is_err());
+    }
+
     #[test]
     fn test_build_intra_workspace_graph() {
         let mut workspaces = HashMap::new();
@@ -753,7 +1014,7 @@ mod tests {
         let edge_types: Vec<_> = builder
             .graph
             .edge_weights()
-            .map(|edge| edge.dependency_type().clone())
+            .map(|edge| *edge.dependency_type())
             .collect();
 
         assert!(edge_types.contains(&DependencyType::Normal));
@@ -958,4 +1219,113 @@ mod tests {
         assert_eq!(from_node.name(), "workspace-a");
         assert_eq!(to_node.name(), "workspace-b");
     }
+
+    #[test]
+    fn test_build_crate_graph_spans_workspaces() {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path();
+
+        let write_crate = |dir: &Path, name: &str, deps: &str| {
+            fs::create_dir_all(dir.join("src")).unwrap();
+            fs::write(
+                dir.join("Cargo.toml"),
+                format!("[package]\nname = \"{name}\"\n\n[dependencies]\n{deps}"),
+            )
+            .unwrap();
+            fs::write(dir.join("src/lib.rs"), "").unwrap();
+        };
+
+        // workspace-a/a1 -> workspace-b/b1 -> workspace-b/b2 -> workspace-a/a1
+        let ws_a_path = root.join("workspace-a");
+        fs::create_dir_all(&ws_a_path).unwrap();
+        fs::write(
+            ws_a_path.join("Cargo.toml"),
+            "[workspace]\nmembers = [\"a1\"]\n",
+        )
+        .unwrap();
+        write_crate(
+            &ws_a_path.join("a1"),
+            "a1",
+            "b1 = { path = \"../../workspace-b/b1\" }\n",
+        );
+
+        let ws_b_path = root.join("workspace-b");
+        fs::create_dir_all(&ws_b_path).unwrap();
+        fs::write(
+            ws_b_path.join("Cargo.toml"),
+            "[workspace]\nmembers = [\"b1\", \"b2\"]\n",
+        )
+        .unwrap();
+        write_crate(&ws_b_path.join("b1"), "b1", "b2 = { path = \"../b2\" }\n");
+        write_crate(
+            &ws_b_path.join("b2"),
+            "b2",
+            "a1 = { path = \"../../workspace-a/a1\" }\n",
+        );
+
+        let mut analyzer = WorkspaceAnalyzer::new();
+        analyzer
+            .discover_workspaces(&[root.to_path_buf()], None)
+            .unwrap();
+
+        let mut builder = DependencyGraphBuilder::new(false, false, false);
+        builder
+            .build_crate_graph(
+                analyzer.workspaces(),
+                analyzer.crate_to_workspace(),
+                analyzer.crate_path_to_workspace(),
+                analyzer.crate_to_paths(),
+                None,
+            )
+            .unwrap();
+
+        let graph = builder.graph();
+        assert_eq!(graph.node_count(), 3);
+
+        let mut edges: Vec<(String, String)> = graph
+            .edge_references()
+            .map(|edge| {
+                (
+                    graph[edge.source()].name().to_string(),
+                    graph[edge.target()].name().to_string(),
+                )
+            })
+            .collect();
+        edges.sort();
+
+        assert_eq!(
+            edges,
+            vec![
+                ("workspace-a/a1".to_string(), "workspace-b/b1".to_string()),
+                ("workspace-b/b1".to_string(), "workspace-b/b2".to_string()),
+                ("workspace-b/b2".to_string(), "workspace-a/a1".to_string()),
+            ]
+        );
+
+        let mut detector = crate::detector::CycleDetector::new();
+        detector.detect_cycles(graph).unwrap();
+        assert_eq!(detector.cycle_count(), 1);
+        assert_eq!(detector.cycles()[0].workspace_names().len(), 3);
+
+        let mut cross: Vec<(&str, &str, &str, &str)> = builder
+            .cross_workspace_edges()
+            .map(|resolved| {
+                (
+                    resolved.from.workspace_name(),
+                    resolved.edge.from_crate(),
+                    resolved.to.workspace_name(),
+                    resolved.edge.to_crate(),
+                )
+            })
+            .collect();
+        cross.sort();
+        assert_eq!(builder.edges().count(), 3);
+        assert_eq!(
+            cross,
+            vec![
+                ("workspace-a", "a1", "workspace-b", "b1"),
+                ("workspace-b", "b2", "workspace-a", "a1"),
+            ]
+        );
+    }
 }
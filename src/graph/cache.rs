@@ -0,0 +1,314 @@
+//! Workspace graphs saved to disk
+//!
+//! `inspect --save-graph` writes the workspace graph it built, and
+//! `spectacle --from-cache` renders from it, so a CI job running several
+//! commands only analyzes the repository once. The file records the
+//! manifests the graph was built from and the settings it was built with; a
+//! graph whose manifests changed since, or that was built with other
+//! settings, is not used.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use petgraph::graph::{DiGraph, NodeIndex};
+use serde::{Deserialize, Serialize};
+
+use super::{DependencyEdge, DependencyType, WorkspaceNode};
+use crate::analyzer::{FileStamp, TargetPlatform, WorkspaceAnalyzer, WorkspaceInfo, stamp_files};
+use crate::common::ConfigBuilder;
+use crate::error::FerrisWheelError;
+
+#[derive(Debug, Serialize, Deserialize)]
+struct CachedNode {
+    name: String,
+    path: Option<PathBuf>,
+    crates: Vec<String>,
+    parent: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct CachedEdge {
+    from: usize,
+    to: usize,
+    from_crate: String,
+    to_crate: String,
+    dependency_type: DependencyType,
+    target: Option<String>,
+}
+
+/// A workspace graph together with what it was built from
+#[derive(Debug, Serialize, Deserialize)]
+pub struct GraphCache {
+    version: String,
+    settings: String,
+    /// Every file the graph depends on, including ones that did not exist
+    manifests: Vec<PathBuf>,
+    /// Stamps of those of `manifests` that existed
+    files: Vec<FileStamp>,
+    nodes: Vec<CachedNode>,
+    edges: Vec<CachedEdge>,
+}
+
+impl GraphCache {
+    /// Capture `graph`, built with `settings` from the analysis of
+    /// `workspaces`
+    pub fn new(
+        settings: String,
+        graph: &DiGraph<WorkspaceNode, DependencyEdge>,
+        workspaces: &HashMap<PathBuf, WorkspaceInfo>,
+    ) -> Self {
+        let nodes = graph
+            .node_weights()
+            .map(|node| CachedNode {
+                name: node.name().to_string(),
+                path: node.path().map(Path::to_path_buf),
+                crates: node.crates().to_vec(),
+                parent: node.parent().map(str::to_string),
+            })
+            .collect();
+        let edges = graph
+            .raw_edges()
+            .iter()
+            .map(|edge| CachedEdge {
+                from: edge.source().index(),
+                to: edge.target().index(),
+                from_crate: edge.weight.from_crate().to_string(),
+                to_crate: edge.weight.to_crate().to_string(),
+                dependency_type: *edge.weight.dependency_type(),
+                target: edge.weight.target().map(str::to_string),
+            })
+            .collect();
+        let manifests = graph_files(workspaces);
+
+        Self {
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            settings,
+            files: stamp_files(&manifests),
+            manifests,
+            nodes,
+            edges,
+        }
+    }
+
+    /// The settings a workspace graph of `paths` is built with, from the
+    /// discovery and analysis settings of `analyzer` and the dependency
+    /// filters
+    pub fn settings(
+        paths: &[PathBuf],
+        analyzer: &WorkspaceAnalyzer,
+        exclude_dev: bool,
+        exclude_build: bool,
+        exclude_target: bool,
+        target: Option<TargetPlatform>,
+    ) -> String {
+        let paths: Vec<PathBuf> = paths
+            .iter()
+            .map(|path| fs::canonicalize(path).unwrap_or_else(|_| path.clone()))
+            .collect();
+        format!(
+            "{paths:?} {} {exclude_dev} {exclude_build} {exclude_target} {:?}",
+            analyzer.graph_settings(),
+            target.map(|target| target.triple())
+        )
+    }
+
+    /// Load the graph saved at `path`, as long as it was built with
+    /// `settings` and none of its manifests changed since
+    ///
+    /// A missing, unreadable or stale file yields `None`.
+    pub fn load(path: &Path, settings: &str) -> Option<DiGraph<WorkspaceNode, DependencyEdge>> {
+        let cache: Self = serde_json::from_slice(&fs::read(path).ok()?).ok()?;
+        if cache.version != env!("CARGO_PKG_VERSION") || cache.settings != settings {
+            return None;
+        }
+        if stamp_files(&cache.manifests) != cache.files {
+            return None;
+        }
+        cache.into_graph().ok()
+    }
+
+    /// Write the graph to `path`, replacing the file atomically
+    pub fn write(&self, path: &Path) -> Result<(), FerrisWheelError> {
+        if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            fs::create_dir_all(parent)?;
+        }
+        let temp = path.with_file_name(format!(
+            "{}.{}.tmp",
+            path.file_name()
+                .map(|name| name.to_string_lossy())
+                .unwrap_or_default(),
+            std::process::id()
+        ));
+        fs::write(&temp, serde_json::to_vec(self)?)?;
+        fs::rename(&temp, path).inspect_err(|_| {
+            let _ = fs::remove_file(&temp);
+        })?;
+        Ok(())
+    }
+
+    fn into_graph(self) -> Result<DiGraph<WorkspaceNode, DependencyEdge>, FerrisWheelError> {
+        let mut graph = DiGraph::with_capacity(self.nodes.len(), self.edges.len());
+        for node in self.nodes {
+            let mut builder = WorkspaceNode::builder()
+                .with_name(node.name)
+                .with_crates(node.crates)
+                .with_parent(node.parent);
+            if let Some(path) = node.path {
+                builder = builder.with_path(path);
+            }
+            graph.add_node(builder.build()?);
+        }
+
+        let node_count = graph.node_count();
+        for edge in self.edges {
+            if edge.from >= node_count || edge.to >= node_count {
+                return Err(FerrisWheelError::ConfigurationError {
+                    message: "Cached graph has an edge to a missing node".to_string(),
+                });
+            }
+            graph.add_edge(
+                NodeIndex::new(edge.from),
+                NodeIndex::new(edge.to),
+                DependencyEdge::builder()
+                    .with_from_crate(&edge.from_crate)
+                    .with_to_crate(&edge.to_crate)
+                    .with_dependency_type(edge.dependency_type)
+                    .with_target(edge.target.as_deref())
+                    .build()?,
+            );
+        }
+
+        Ok(graph)
+    }
+}
+
+/// The files a graph of `workspaces` is built from: the manifest and lock
+/// file of each workspace and the manifests of its members
+fn graph_files(workspaces: &HashMap<PathBuf, WorkspaceInfo>) -> Vec<PathBuf> {
+    let mut files: Vec<PathBuf> = workspaces
+        .iter()
+        .flat_map(|(root, workspace)| {
+            [root.join("Cargo.toml"), root.join("Cargo.lock")]
+                .into_iter()
+                .chain(
+                    workspace
+                        .members()
+                        .iter()
+                        .map(|member| member.path().join("Cargo.toml")),
+                )
+        })
+        .collect();
+    files.sort();
+    files.dedup();
+    files
+}
+
+#[cfg(test)]
+mod tests {
+    use tempfile::TempDir;
+
+    use super::*;
+    use crate::analyzer::CrateMember;
+
+    #[test]
+    fn test_graph_cache_round_trip_and_invalidation() {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path().join("ws");
+        fs::create_dir_all(root.join("core")).unwrap();
+        fs::write(root.join("Cargo.toml"), "[workspace]\n").unwrap();
+        fs::write(root.join("core/Cargo.toml"), "[package]\nname = \"core\"\n").unwrap();
+        let workspaces = HashMap::from([(
+            root.clone(),
+            WorkspaceInfo::builder()
+                .with_name("ws")
+                .with_members(vec![
+                    CrateMember::builder()
+                        .with_name("core")
+                        .with_path(root.join("core"))
+                        .build()
+                        .unwrap(),
+                ])
+                .build()
+                .unwrap(),
+        )]);
+
+        let mut graph = DiGraph::new();
+        let app = graph.add_node(
+            WorkspaceNode::builder()
+                .with_name("app".to_string())
+                .with_crates(vec!["app-main".to_string()])
+                .build()
+                .unwrap(),
+        );
+        let ws = graph.add_node(
+            WorkspaceNode::builder()
+                .with_name("ws".to_string())
+                .with_path(root.clone())
+                .with_crates(vec!["core".to_string()])
+                .with_parent(Some("meta".to_string()))
+                .build()
+                .unwrap(),
+        );
+        graph.add_edge(
+            app,
+            ws,
+            DependencyEdge::builder()
+                .with_from_crate("app-main")
+                .with_to_crate("core")
+                .with_dependency_type(DependencyType::Build)
+                .with_target(Some("cfg(unix)"))
+                .build()
+                .unwrap(),
+        );
+
+        let path = temp.path().join("out/graph.json");
+        GraphCache::new("settings".to_string(), &graph, &workspaces)
+            .write(&path)
+            .unwrap();
+
+        let loaded = GraphCache::load(&path, "settings").unwrap();
+        assert_eq!(loaded.node_count(), 2);
+        assert_eq!(loaded[ws].path(), Some(root.as_path()));
+        assert_eq!(loaded[ws].parent(), Some("meta"));
+        let edge = &loaded.raw_edges()[0];
+        assert_eq!((edge.source(), edge.target()), (app, ws));
+        assert_eq!(edge.weight.dependency_type(), &DependencyType::Build);
+        assert_eq!(edge.weight.target(), Some("cfg(unix)"));
+
+        // Other settings, and edits to a member manifest, make the graph
+        // stale
+        assert!(GraphCache::load(&path, "other settings").is_none());
+        fs::write(
+            root.join("core/Cargo.toml"),
+            "[package]\nname = \"core\"\nversion = \"0.2.0\"\n",
+        )
+        .unwrap();
+        assert!(GraphCache::load(&path, "settings").is_none());
+    }
+
+    #[test]
+    fn test_graph_cache_notices_new_lock_files() {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path().join("ws");
+        fs::create_dir_all(&root).unwrap();
+        fs::write(root.join("Cargo.toml"), "[workspace]\n").unwrap();
+        let workspaces = HashMap::from([(
+            root.clone(),
+            WorkspaceInfo::builder()
+                .with_name("ws")
+                .with_members(Vec::new())
+                .build()
+                .unwrap(),
+        )]);
+
+        let path = temp.path().join("graph.json");
+        GraphCache::new(String::new(), &DiGraph::new(), &workspaces)
+            .write(&path)
+            .unwrap();
+        assert!(GraphCache::load(&path, "").is_some());
+
+        fs::write(root.join("Cargo.lock"), "# lock").unwrap();
+        assert!(GraphCache::load(&path, "").is_none());
+    }
+}
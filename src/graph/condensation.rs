@@ -0,0 +1,229 @@
+//! Condensation of workspace dependency graphs
+//!
+//! Collapsing each strongly connected component into a single super-node turns
+//! any dependency graph into a DAG. The result is the clearest high-level view
+//! of how tangled a monorepo is: every node with more than one member is a
+//! knot of workspaces that can only be built and released together.
+
+use std::collections::HashMap;
+
+use petgraph::algo::{condensation, tarjan_scc};
+use petgraph::graph::DiGraph;
+
+use crate::common::ConfigBuilder;
+use crate::error::FerrisWheelError;
+use crate::graph::{DependencyEdge, WorkspaceNode};
+
+/// Separator between member names of a collapsed component
+pub const CONDENSED_NAME_SEPARATOR: &str = " + ";
+
+/// Collapse every strongly connected component of `graph` into one node
+///
+/// Workspaces that are not part of a cycle are kept as they are. A component
+/// with several workspaces becomes a node named after its members, sorted and
+/// joined with [`CONDENSED_NAME_SEPARATOR`], that owns all of their crates.
+/// Dependencies inside a component are dropped and dependencies between
+/// components are kept, so the returned graph is always acyclic.
+pub fn condense(
+    graph: &DiGraph<WorkspaceNode, DependencyEdge>,
+) -> Result<DiGraph<WorkspaceNode, DependencyEdge>, FerrisWheelError> {
+    let condensed = condensation(graph.clone(), true);
+
+    let components: Vec<Vec<&WorkspaceNode>> = condensed
+        .node_weights()
+        .map(|members| {
+            let mut members: Vec<&WorkspaceNode> = members.iter().collect();
+            members.sort_by(|a, b| a.name().cmp(b.name()));
+            members
+        })
+        .collect();
+
+    // Parents that were merged into a component are replaced by it
+    let mut renamed: HashMap<&str, String> = HashMap::new();
+    for members in components.iter().filter(|members| members.len() > 1) {
+        let name = component_name(members);
+        for member in members {
+            renamed.insert(member.name(), name.clone());
+        }
+    }
+
+    let nodes = components
+        .iter()
+        .map(|members| {
+            if let [node] = members.as_slice() {
+                let parent = node
+                    .parent()
+                    .map(|parent| renamed.get(parent).map_or(parent, String::as_str));
+                let mut builder = WorkspaceNode::builder()
+                    .with_name(node.name().to_string())
+                    .with_crates(node.crates().to_vec())
+                    .with_parent(parent.map(str::to_string));
+                if let Some(path) = node.path() {
+                    builder = builder.with_path(path.to_path_buf());
+                }
+                return builder.build();
+            }
+
+            let mut crates: Vec<String> = members
+                .iter()
+                .flat_map(|member| member.crates().iter().cloned())
+                .collect();
+            crates.sort();
+
+            WorkspaceNode::builder()
+                .with_name(component_name(members))
+                .with_crates(crates)
+                .build()
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(condensed.map(|idx, _| nodes[idx.index()].clone(), |_, edge| edge.clone()))
+}
+
+/// Keep only the workspaces and dependencies that take part in a cycle
+///
+/// A dependency is part of a cycle when both ends are in the same strongly
+/// connected component, so dependencies between two separate cycles are
+/// dropped along with everything outside them.
+pub fn cycles_only(
+    graph: &DiGraph<WorkspaceNode, DependencyEdge>,
+) -> DiGraph<WorkspaceNode, DependencyEdge> {
+    let mut component = vec![None; graph.node_count()];
+    for (id, members) in tarjan_scc(graph).into_iter().enumerate() {
+        let cyclic = members.len() > 1 || graph.contains_edge(members[0], members[0]);
+        if cyclic {
+            for member in members {
+                component[member.index()] = Some(id);
+            }
+        }
+    }
+
+    graph.filter_map(
+        |idx, node| component[idx.index()].map(|_| node.clone()),
+        |idx, edge| {
+            let (from, to) = graph.edge_endpoints(idx)?;
+            let from = component[from.index()]?;
+            (component[to.index()] == Some(from)).then(|| edge.clone())
+        },
+    )
+}
+
+fn component_name(members: &[&WorkspaceNode]) -> String {
+    members
+        .iter()
+        .map(|member| member.name())
+        .collect::<Vec<_>>()
+        .join(CONDENSED_NAME_SEPARATOR)
+}
+
+#[cfg(test)]
+mod tests {
+    use petgraph::algo::is_cyclic_directed;
+
+    use super::*;
+    use crate::graph::DependencyType;
+
+    fn graph(
+        nodes: &[(&str, Option<&str>)],
+        edges: &[(usize, usize)],
+    ) -> DiGraph<WorkspaceNode, DependencyEdge> {
+        let mut graph = DiGraph::new();
+        let indices: Vec<_> = nodes
+            .iter()
+            .map(|&(name, parent)| {
+                graph.add_node(
+                    WorkspaceNode::builder()
+                        .with_name(name.to_string())
+                        .with_crates(vec![format!("{name}-lib")])
+                        .with_parent(parent.map(str::to_string))
+                        .build()
+                        .unwrap(),
+                )
+            })
+            .collect();
+        for &(from, to) in edges {
+            graph.add_edge(
+                indices[from],
+                indices[to],
+                DependencyEdge::builder()
+                    .with_from_crate(&format!("{}-lib", nodes[from].0))
+                    .with_to_crate(&format!("{}-lib", nodes[to].0))
+                    .with_dependency_type(DependencyType::Normal)
+                    .build()
+                    .unwrap(),
+            );
+        }
+        graph
+    }
+
+    #[test]
+    fn test_condense_collapses_cycles() {
+        // app -> (core <-> utils) -> base, plus a dependency inside the cycle
+        let graph = graph(
+            &[
+                ("app", None),
+                ("core", Some("platform")),
+                ("utils", None),
+                ("base", None),
+                ("platform", None),
+                ("tools", Some("utils")),
+            ],
+            &[(0, 1), (1, 2), (2, 1), (2, 1), (2, 3), (4, 3)],
+        );
+
+        let condensed = condense(&graph).unwrap();
+
+        assert!(!is_cyclic_directed(&condensed));
+        assert_eq!(condensed.node_count(), 5);
+        assert_eq!(condensed.edge_count(), 3);
+
+        let knot = condensed
+            .node_weights()
+            .find(|node| node.name() == "core + utils")
+            .unwrap();
+        assert_eq!(knot.crates(), ["core-lib", "utils-lib"]);
+        assert_eq!(knot.parent(), None);
+
+        let tools = condensed
+            .node_weights()
+            .find(|node| node.name() == "tools")
+            .unwrap();
+        assert_eq!(tools.parent(), Some("core + utils"));
+    }
+
+    #[test]
+    fn test_cycles_only_drops_acyclic_parts() {
+        // app -> (core <-> utils) -> (base <-> io), plus a lone self-loop
+        let graph = graph(
+            &[
+                ("app", None),
+                ("core", None),
+                ("utils", None),
+                ("base", None),
+                ("io", None),
+                ("tools", None),
+            ],
+            &[(0, 1), (1, 2), (2, 1), (2, 3), (3, 4), (4, 3), (5, 5)],
+        );
+
+        let tangled = cycles_only(&graph);
+
+        let mut names: Vec<_> = tangled.node_weights().map(|node| node.name()).collect();
+        names.sort();
+        assert_eq!(names, ["base", "core", "io", "tools", "utils"]);
+        // utils -> base links two cycles without being part of either
+        assert_eq!(tangled.edge_count(), 5);
+    }
+
+    #[test]
+    fn test_condense_acyclic_graph_is_unchanged() {
+        let graph = graph(&[("app", None), ("core", None)], &[(0, 1)]);
+
+        let condensed = condense(&graph).unwrap();
+
+        let mut names: Vec<_> = condensed.node_weights().map(|node| node.name()).collect();
+        names.sort();
+        assert_eq!(names, ["app", "core"]);
+        assert_eq!(condensed.edge_count(), 1);
+    }
+}
@@ -0,0 +1,218 @@
+//! Grouping of workspaces into Mermaid subgraphs and DOT clusters
+//!
+//! Workspaces are grouped by the text before the first dash of their name
+//! unless another strategy is chosen: a regex over the name, the directory
+//! the workspace lives in, or the layer `[layers]` assigns it to.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::str::FromStr;
+
+use petgraph::graph::{DiGraph, NodeIndex};
+use regex::Regex;
+
+use crate::detector::LayerModel;
+use crate::error::FerrisWheelError;
+use crate::graph::{DependencyEdge, WorkspaceNode};
+
+/// How to group workspaces, written `prefix`, `directory`, `layer` or
+/// `regex:<PATTERN>`
+#[derive(Debug, Clone)]
+pub enum WorkspaceGrouping {
+    /// Text before the first dash of the name; groups of one are dropped
+    Prefix,
+    /// First capture group of the pattern (or the whole match); workspaces
+    /// it does not match stay ungrouped
+    Pattern(Regex),
+    /// Name of the directory holding the workspace
+    Directory,
+    /// Layer the workspace belongs to; unassigned workspaces stay ungrouped
+    Layer(LayerModel),
+}
+
+impl WorkspaceGrouping {
+    /// Group the workspaces of `graph`, by group name
+    pub fn group(
+        &self,
+        graph: &DiGraph<WorkspaceNode, DependencyEdge>,
+    ) -> Result<BTreeMap<String, Vec<NodeIndex>>, FerrisWheelError> {
+        let layers = match self {
+            WorkspaceGrouping::Layer(model) => model.assignments(graph)?,
+            _ => BTreeMap::new(),
+        };
+
+        let mut groups: BTreeMap<String, Vec<NodeIndex>> = BTreeMap::new();
+        for node in graph.node_indices() {
+            let ws = &graph[node];
+            let group = match self {
+                WorkspaceGrouping::Prefix => Some(prefix_of(ws.name())),
+                WorkspaceGrouping::Pattern(pattern) => pattern.captures(ws.name()).map(|caps| {
+                    caps.get(1)
+                        .or_else(|| caps.get(0))
+                        .map_or_else(String::new, |m| m.as_str().to_string())
+                }),
+                WorkspaceGrouping::Directory => ws
+                    .path()
+                    .and_then(|path| path.parent())
+                    .and_then(|dir| dir.file_name())
+                    .map(|dir| dir.to_string_lossy().into_owned()),
+                WorkspaceGrouping::Layer(_) => layers.get(ws.name()).cloned(),
+            };
+            if let Some(group) = group.filter(|group| !group.is_empty()) {
+                groups.entry(group).or_default().push(node);
+            }
+        }
+
+        // The name prefix is only a guess, so it takes two workspaces to make
+        // a group
+        if matches!(self, WorkspaceGrouping::Prefix) {
+            groups.retain(|_, nodes| nodes.len() > 1);
+        }
+        Ok(groups)
+    }
+}
+
+/// Everything before the first dash, or "workspace"/"other" for names
+/// without one
+fn prefix_of(name: &str) -> String {
+    if let Some(dash_pos) = name.find('-') {
+        name[..dash_pos].to_string()
+    } else if name.contains("workspace") {
+        "workspace".to_string()
+    } else {
+        "other".to_string()
+    }
+}
+
+impl FromStr for WorkspaceGrouping {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "prefix" => Ok(WorkspaceGrouping::Prefix),
+            "directory" => Ok(WorkspaceGrouping::Directory),
+            "layer" => Ok(WorkspaceGrouping::Layer(LayerModel::default())),
+            _ => match s.strip_prefix("regex:") {
+                Some(pattern) => Regex::new(pattern)
+                    .map(WorkspaceGrouping::Pattern)
+                    .map_err(|err| format!("invalid regex: {err}")),
+                None => Err(format!(
+                    "expected prefix, directory, layer or regex:<PATTERN>, got '{s}'"
+                )),
+            },
+        }
+    }
+}
+
+impl fmt::Display for WorkspaceGrouping {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WorkspaceGrouping::Prefix => write!(f, "prefix"),
+            WorkspaceGrouping::Pattern(pattern) => write!(f, "regex:{pattern}"),
+            WorkspaceGrouping::Directory => write!(f, "directory"),
+            WorkspaceGrouping::Layer(_) => write!(f, "layer"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::*;
+    use crate::common::ConfigBuilder;
+
+    fn graph(workspaces: &[(&str, &str)]) -> DiGraph<WorkspaceNode, DependencyEdge> {
+        let mut graph = DiGraph::new();
+        for &(name, path) in workspaces {
+            graph.add_node(
+                WorkspaceNode::builder()
+                    .with_name(name.to_string())
+                    .with_crates(vec![format!("{name}-lib")])
+                    .with_path(PathBuf::from(path))
+                    .build()
+                    .unwrap(),
+            );
+        }
+        graph
+    }
+
+    fn names(
+        graph: &DiGraph<WorkspaceNode, DependencyEdge>,
+        groups: BTreeMap<String, Vec<NodeIndex>>,
+    ) -> Vec<(String, Vec<String>)> {
+        groups
+            .into_iter()
+            .map(|(group, nodes)| {
+                let mut members: Vec<String> = nodes
+                    .into_iter()
+                    .map(|node| graph[node].name().to_string())
+                    .collect();
+                members.sort();
+                (group, members)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_grouping_strategies() {
+        let graph = graph(&[
+            ("atlas-core", "/repo/platform/atlas-core"),
+            ("atlas-api", "/repo/services/atlas-api"),
+            ("billing-api", "/repo/services/billing-api"),
+            ("tools", "/repo/tools"),
+        ]);
+        let group = |spec: &str| {
+            let grouping: WorkspaceGrouping = spec.parse().unwrap();
+            names(&graph, grouping.group(&graph).unwrap())
+        };
+        let group_of = |name: &str, members: &[&str]| {
+            (
+                name.to_string(),
+                members.iter().map(|m| m.to_string()).collect::<Vec<_>>(),
+            )
+        };
+
+        assert_eq!(
+            group("prefix"),
+            [group_of("atlas", &["atlas-api", "atlas-core"])]
+        );
+        assert_eq!(
+            group("regex:-(api|core)$"),
+            [
+                group_of("api", &["atlas-api", "billing-api"]),
+                group_of("core", &["atlas-core"]),
+            ]
+        );
+        assert_eq!(
+            group("directory"),
+            [
+                group_of("platform", &["atlas-core"]),
+                group_of("repo", &["tools"]),
+                group_of("services", &["atlas-api", "billing-api"]),
+            ]
+        );
+
+        let layers = WorkspaceGrouping::Layer(LayerModel::from_chain("atlas-* < billing-*"));
+        assert_eq!(
+            names(&graph, layers.group(&graph).unwrap()),
+            [
+                group_of("atlas-*", &["atlas-api", "atlas-core"]),
+                group_of("billing-*", &["billing-api"]),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_grouping_parse_errors() {
+        assert!("regex:(".parse::<WorkspaceGrouping>().is_err());
+        assert!("size".parse::<WorkspaceGrouping>().is_err());
+        assert_eq!(
+            "regex:^(\\w+)-"
+                .parse::<WorkspaceGrouping>()
+                .unwrap()
+                .to_string(),
+            "regex:^(\\w+)-"
+        );
+    }
+}
@@ -0,0 +1,188 @@
+//! Rendering graphs to images through an external layout engine
+//!
+//! The graph is rendered as DOT or D2 source and handed to Graphviz `dot` or
+//! the `d2` CLI, whichever is installed, to lay it out as PNG or PDF.
+
+use std::io::{ErrorKind, Write};
+use std::path::PathBuf;
+use std::process::{Command, Stdio};
+
+use petgraph::graph::DiGraph;
+
+use super::{DependencyEdge, GraphRenderer, WorkspaceNode};
+use crate::detector::WorkspaceCycle;
+use crate::error::FerrisWheelError;
+
+/// Image formats produced by a layout engine
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ImageFormat {
+    Png,
+    Pdf,
+}
+
+impl ImageFormat {
+    /// File extension, also the output format name both engines accept
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ImageFormat::Png => "png",
+            ImageFormat::Pdf => "pdf",
+        }
+    }
+}
+
+/// External programs that can lay out a graph, in order of preference
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LayoutEngine {
+    /// Graphviz `dot`, fed the DOT rendering
+    Dot,
+    /// The `d2` CLI, fed the D2 rendering
+    D2,
+}
+
+impl LayoutEngine {
+    const ALL: [LayoutEngine; 2] = [LayoutEngine::Dot, LayoutEngine::D2];
+
+    /// Name of the engine's executable
+    pub fn program(&self) -> &'static str {
+        match self {
+            LayoutEngine::Dot => "dot",
+            LayoutEngine::D2 => "d2",
+        }
+    }
+
+    /// Render `graph` as this engine's source language
+    fn source(
+        &self,
+        renderer: &GraphRenderer,
+        graph: &DiGraph<WorkspaceNode, DependencyEdge>,
+        cycles: &[WorkspaceCycle],
+    ) -> Result<Vec<u8>, FerrisWheelError> {
+        let mut source = Vec::new();
+        let rendered = match self {
+            LayoutEngine::Dot => renderer.render_dot(graph, cycles, &mut source),
+            LayoutEngine::D2 => renderer.render_d2(graph, cycles, &mut source),
+        };
+        rendered.map_err(|e| FerrisWheelError::GraphError {
+            message: format!("Failed to render {} source: {e}", self.program()),
+        })?;
+        Ok(source)
+    }
+
+    /// Lay out `source` as an image, or `None` when the engine is not
+    /// installed
+    fn layout(
+        &self,
+        source: &[u8],
+        format: ImageFormat,
+    ) -> Result<Option<Vec<u8>>, FerrisWheelError> {
+        // d2 picks the output format from the file extension, so it writes to
+        // a temporary file instead of stdout
+        let d2_output = std::env::temp_dir().join(format!(
+            "cargo-ferris-wheel-{}.{}",
+            std::process::id(),
+            format.extension()
+        ));
+
+        let mut command = Command::new(self.program());
+        match self {
+            LayoutEngine::Dot => command.arg(format!("-T{}", format.extension())),
+            LayoutEngine::D2 => command.arg("-").arg(&d2_output),
+        };
+
+        let child = command
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn();
+        let mut child = match child {
+            Ok(child) => child,
+            Err(e) if e.kind() == ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(self.failure(e.to_string())),
+        };
+
+        if let Some(mut stdin) = child.stdin.take() {
+            stdin
+                .write_all(source)
+                .map_err(|e| self.failure(e.to_string()))?;
+        }
+        let output = child
+            .wait_with_output()
+            .map_err(|e| self.failure(e.to_string()))?;
+
+        if !output.status.success() {
+            let _ = std::fs::remove_file(&d2_output);
+            return Err(self.failure(String::from_utf8_lossy(&output.stderr).trim().to_string()));
+        }
+
+        match self {
+            LayoutEngine::Dot => Ok(Some(output.stdout)),
+            LayoutEngine::D2 => {
+                Ok(Some(read_and_remove(d2_output).map_err(|e| {
+                    self.failure(format!("Failed to read rendered image: {e}"))
+                })?))
+            }
+        }
+    }
+
+    fn failure(&self, message: String) -> FerrisWheelError {
+        FerrisWheelError::LayoutEngineFailed {
+            engine: self.program(),
+            message,
+        }
+    }
+}
+
+fn read_and_remove(path: PathBuf) -> std::io::Result<Vec<u8>> {
+    let bytes = std::fs::read(&path);
+    let _ = std::fs::remove_file(&path);
+    bytes
+}
+
+/// Render `graph` as an image with the first installed layout engine
+///
+/// Returns the engine that was used along with the image bytes.
+pub fn render_image(
+    renderer: &GraphRenderer,
+    graph: &DiGraph<WorkspaceNode, DependencyEdge>,
+    cycles: &[WorkspaceCycle],
+    format: ImageFormat,
+) -> Result<(LayoutEngine, Vec<u8>), FerrisWheelError> {
+    for engine in LayoutEngine::ALL {
+        let source = engine.source(renderer, graph, cycles)?;
+        if let Some(image) = engine.layout(&source, format)? {
+            return Ok((engine, image));
+        }
+    }
+
+    Err(FerrisWheelError::LayoutEngineMissing {
+        format: format.extension(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::ConfigBuilder;
+
+    #[test]
+    fn test_engines_receive_their_own_source_language() {
+        let mut graph = DiGraph::new();
+        graph.add_node(
+            WorkspaceNode::builder()
+                .with_name("core".to_string())
+                .with_crates(vec!["core-lib".to_string()])
+                .build()
+                .unwrap(),
+        );
+        let renderer = GraphRenderer::new(false, false);
+
+        let dot = LayoutEngine::Dot.source(&renderer, &graph, &[]).unwrap();
+        assert!(String::from_utf8(dot).unwrap().starts_with("digraph"));
+
+        let d2 = LayoutEngine::D2.source(&renderer, &graph, &[]).unwrap();
+        assert!(String::from_utf8(d2).unwrap().contains("core"));
+
+        assert_eq!(ImageFormat::Png.extension(), "png");
+        assert_eq!(ImageFormat::Pdf.extension(), "pdf");
+    }
+}
@@ -11,10 +11,19 @@
 //!   analysis
 //! - **WorkspaceNode**: Represents a workspace in the graph
 //! - **DependencyEdge**: Represents a dependency relationship between crates
+//! - **ResolvedEdge**: A dependency edge together with the nodes it connects
+//! - **GraphCache**: A workspace graph saved to disk, reused while its
+//!   manifests are unchanged
 //!
 //! ### Graph Rendering
 //! - **GraphRenderer**: Renders graphs in various formats (DOT, Mermaid)
 //! - Supports cycle highlighting and different visualization options
+//! - **WorkspaceGrouping**: Groups workspaces into subgraphs or clusters by
+//!   name prefix, regex, directory or layer
+//! - **Palette**: Colors for Mermaid, DOT and D2 graphs, from the `light`,
+//!   `dark` or `colorblind` preset plus `[theme]` overrides
+//! - **render_image**: Lays a graph out as PNG or PDF with an installed
+//!   Graphviz `dot` or `d2`
 //!
 //! ## Example
 //!
@@ -75,12 +84,25 @@
 //! - **Mermaid**: Markdown-compatible diagrams for documentation
 
 mod builder;
+mod cache;
+mod condensation;
+mod grouping;
+mod image;
+mod palette;
+mod paths;
 mod renderer;
 mod types;
 
 // Re-export main types and builders
 pub use builder::DependencyGraphBuilder;
-pub use renderer::GraphRenderer;
+pub use cache::GraphCache;
+pub use condensation::{CONDENSED_NAME_SEPARATOR, condense, cycles_only};
+pub use grouping::WorkspaceGrouping;
+pub use image::{ImageFormat, LayoutEngine, render_image};
+pub use palette::{Palette, ThemeChoice, ThemeConfig, ThemePreset};
+pub use paths::{neighborhood, shortest_paths, simple_paths};
+pub use renderer::{GraphRenderer, MermaidOptions};
 pub use types::{
-    DependencyEdge, DependencyEdgeBuilder, DependencyType, WorkspaceNode, WorkspaceNodeBuilder,
+    DependencyEdge, DependencyEdgeBuilder, DependencyType, HypotheticalEdge, ResolvedEdge,
+    WorkspaceNode, WorkspaceNodeBuilder,
 };
@@ -0,0 +1,331 @@
+//! Graph color palettes
+//!
+//! Mermaid, DOT and D2 graphs are colored from a [`Palette`]. Three presets
+//! ship with the crate: the default `light` blue-orange palette, `dark` for
+//! dark backgrounds, and `colorblind`, built from the Okabe–Ito palette that
+//! stays distinguishable under the common forms of color blindness. A
+//! palette also picks the theme Mermaid draws everything else with, `dark`
+//! for the `dark` preset. Any color, and the Mermaid theme, can be
+//! overridden in the `[theme]` section of `.ferris-wheel.toml`, or in a theme
+//! file passed to `spectacle --theme`:
+//!
+//! ```toml
+//! [theme]
+//! preset = "dark"
+//! cycle-edge = "#FF1744"
+//! mermaid = "base"
+//! ```
+
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use schemars::JsonSchema;
+use serde::Deserialize;
+
+use crate::cli::MermaidTheme;
+use crate::error::FerrisWheelError;
+
+/// Colors used to draw workspaces, dependencies and legends
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Palette {
+    pub normal_node_fill: String,
+    pub normal_node_stroke: String,
+    pub cycle_node_fill: String,
+    pub cycle_node_stroke: String,
+    pub normal_edge: String,
+    pub dev_edge: String,
+    pub build_edge: String,
+    pub cycle_edge: String,
+    pub legend_background: String,
+    pub legend_stroke: String,
+    /// Label color, if the renderer's default (black) does not fit
+    pub text: Option<String>,
+    /// Theme emitted in a Mermaid `%%{init}%%` block (Mermaid's default if
+    /// unset)
+    pub mermaid_theme: Option<MermaidTheme>,
+}
+
+impl Default for Palette {
+    fn default() -> Self {
+        ThemePreset::Light.palette()
+    }
+}
+
+/// Built-in palettes
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum ThemePreset {
+    /// Soft blue workspaces with orange cycles
+    #[default]
+    Light,
+    /// Muted fills and light labels for dark backgrounds
+    Dark,
+    /// Okabe–Ito colors, safe for color-blind readers
+    Colorblind,
+}
+
+impl ThemePreset {
+    pub fn palette(self) -> Palette {
+        let palette = |colors: [&str; 10], text: Option<&str>| {
+            let [
+                normal_node_fill,
+                normal_node_stroke,
+                cycle_node_fill,
+                cycle_node_stroke,
+                normal_edge,
+                dev_edge,
+                build_edge,
+                cycle_edge,
+                legend_background,
+                legend_stroke,
+            ] = colors.map(str::to_string);
+            Palette {
+                normal_node_fill,
+                normal_node_stroke,
+                cycle_node_fill,
+                cycle_node_stroke,
+                normal_edge,
+                dev_edge,
+                build_edge,
+                cycle_edge,
+                legend_background,
+                legend_stroke,
+                text: text.map(str::to_string),
+                mermaid_theme: None,
+            }
+        };
+
+        match self {
+            ThemePreset::Light => palette(
+                [
+                    "#E3F2FD", "#1976D2", "#FFF3E0", "#F57C00", "#64B5F6", "#90A4AE", "#81C784",
+                    "#FF6500", "#FAFAFA", "#ddd",
+                ],
+                None,
+            ),
+            ThemePreset::Dark => Palette {
+                mermaid_theme: Some(MermaidTheme::Dark),
+                ..palette(
+                    [
+                        "#263238", "#64B5F6", "#4E342E", "#FFB74D", "#90CAF9", "#B0BEC5",
+                        "#A5D6A7", "#FF8A65", "#212121", "#616161",
+                    ],
+                    Some("#ECEFF1"),
+                )
+            },
+            ThemePreset::Colorblind => palette(
+                [
+                    "#DCEBF7", "#0072B2", "#FBE5C8", "#D55E00", "#56B4E9", "#999999", "#009E73",
+                    "#D55E00", "#FFFFFF", "#BBBBBB",
+                ],
+                None,
+            ),
+        }
+    }
+}
+
+/// The `[theme]` section, or a theme file: a preset and the colors
+/// overriding it
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, JsonSchema)]
+#[serde(default, deny_unknown_fields, rename_all = "kebab-case")]
+pub struct ThemeConfig {
+    /// Palette the colors below override
+    pub preset: ThemePreset,
+    pub normal_node_fill: Option<String>,
+    pub normal_node_stroke: Option<String>,
+    pub cycle_node_fill: Option<String>,
+    pub cycle_node_stroke: Option<String>,
+    pub normal_edge: Option<String>,
+    pub dev_edge: Option<String>,
+    pub build_edge: Option<String>,
+    pub cycle_edge: Option<String>,
+    pub legend_background: Option<String>,
+    pub legend_stroke: Option<String>,
+    /// Label color
+    pub text: Option<String>,
+    /// Mermaid theme under the colors (the preset's if unset)
+    pub mermaid: Option<MermaidTheme>,
+}
+
+impl ThemeConfig {
+    /// Read a theme file
+    pub fn from_file(path: &Path) -> Result<Self, FerrisWheelError> {
+        let content =
+            std::fs::read_to_string(path).map_err(|source| FerrisWheelError::FileReadError {
+                path: path.to_path_buf(),
+                source,
+            })?;
+        toml::from_str(&content).map_err(|err| FerrisWheelError::ConfigurationError {
+            message: format!("Invalid theme file {}: {}", path.display(), err.message()),
+        })
+    }
+
+    /// The preset with this theme's colors applied, each checked to be a hex
+    /// color
+    pub fn palette(&self) -> Result<Palette, FerrisWheelError> {
+        let mut palette = self.preset.palette();
+        let overrides = [
+            (
+                "normal-node-fill",
+                &self.normal_node_fill,
+                &mut palette.normal_node_fill,
+            ),
+            (
+                "normal-node-stroke",
+                &self.normal_node_stroke,
+                &mut palette.normal_node_stroke,
+            ),
+            (
+                "cycle-node-fill",
+                &self.cycle_node_fill,
+                &mut palette.cycle_node_fill,
+            ),
+            (
+                "cycle-node-stroke",
+                &self.cycle_node_stroke,
+                &mut palette.cycle_node_stroke,
+            ),
+            ("normal-edge", &self.normal_edge, &mut palette.normal_edge),
+            ("dev-edge", &self.dev_edge, &mut palette.dev_edge),
+            ("build-edge", &self.build_edge, &mut palette.build_edge),
+            ("cycle-edge", &self.cycle_edge, &mut palette.cycle_edge),
+            (
+                "legend-background",
+                &self.legend_background,
+                &mut palette.legend_background,
+            ),
+            (
+                "legend-stroke",
+                &self.legend_stroke,
+                &mut palette.legend_stroke,
+            ),
+        ];
+        for (key, color, slot) in overrides {
+            if let Some(color) = color {
+                *slot = checked_color(key, color)?;
+            }
+        }
+        if let Some(text) = &self.text {
+            palette.text = Some(checked_color("text", text)?);
+        }
+        if let Some(theme) = self.mermaid {
+            palette.mermaid_theme = Some(theme);
+        }
+
+        Ok(palette)
+    }
+}
+
+/// `color` if it is a `#RGB`, `#RGBA`, `#RRGGBB` or `#RRGGBBAA` hex color
+fn checked_color(key: &str, color: &str) -> Result<String, FerrisWheelError> {
+    let is_hex = color.strip_prefix('#').is_some_and(|digits| {
+        matches!(digits.len(), 3 | 4 | 6 | 8) && digits.chars().all(|c| c.is_ascii_hexdigit())
+    });
+    if is_hex {
+        Ok(color.to_string())
+    } else {
+        Err(FerrisWheelError::ConfigurationError {
+            message: format!("Theme color {key} = '{color}' is not a hex color like #1976D2"),
+        })
+    }
+}
+
+/// A `--theme` value: a preset name or a theme file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ThemeChoice {
+    Preset(ThemePreset),
+    File(PathBuf),
+}
+
+impl ThemeChoice {
+    pub fn palette(&self) -> Result<Palette, FerrisWheelError> {
+        match self {
+            ThemeChoice::Preset(preset) => Ok(preset.palette()),
+            ThemeChoice::File(path) => ThemeConfig::from_file(path)?.palette(),
+        }
+    }
+}
+
+impl FromStr for ThemeChoice {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "light" => Ok(ThemeChoice::Preset(ThemePreset::Light)),
+            "dark" => Ok(ThemeChoice::Preset(ThemePreset::Dark)),
+            "colorblind" => Ok(ThemeChoice::Preset(ThemePreset::Colorblind)),
+            _ if s.ends_with(".toml") => Ok(ThemeChoice::File(PathBuf::from(s))),
+            _ => Err(format!(
+                "expected light, dark, colorblind or a .toml theme file, got '{s}'"
+            )),
+        }
+    }
+}
+
+impl fmt::Display for ThemeChoice {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ThemeChoice::Preset(ThemePreset::Light) => write!(f, "light"),
+            ThemeChoice::Preset(ThemePreset::Dark) => write!(f, "dark"),
+            ThemeChoice::Preset(ThemePreset::Colorblind) => write!(f, "colorblind"),
+            ThemeChoice::File(path) => write!(f, "{}", path.display()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_theme_overrides_preset() {
+        let theme: ThemeConfig = toml::from_str(
+            r##"
+            preset = "colorblind"
+            cycle-edge = "#FF1744"
+            text = "#000"
+            mermaid = "forest"
+            "##,
+        )
+        .unwrap();
+
+        let palette = theme.palette().unwrap();
+
+        assert_eq!(palette.cycle_edge, "#FF1744");
+        assert_eq!(palette.text.as_deref(), Some("#000"));
+        assert_eq!(palette.mermaid_theme, Some(MermaidTheme::Forest));
+        assert_eq!(
+            ThemePreset::Dark.palette().mermaid_theme,
+            Some(MermaidTheme::Dark)
+        );
+        assert_eq!(
+            palette.normal_node_stroke,
+            ThemePreset::Colorblind.palette().normal_node_stroke
+        );
+    }
+
+    #[test]
+    fn test_theme_rejects_non_hex_colors() {
+        let theme: ThemeConfig = toml::from_str(r#"dev-edge = "red\"; penwidth=9""#).unwrap();
+
+        let err = theme.palette().unwrap_err();
+
+        assert!(err.to_string().contains("dev-edge"), "{err}");
+        assert!(toml::from_str::<ThemeConfig>("edge = \"#fff\"").is_err());
+    }
+
+    #[test]
+    fn test_theme_choice_parsing() {
+        assert_eq!(
+            "dark".parse::<ThemeChoice>(),
+            Ok(ThemeChoice::Preset(ThemePreset::Dark))
+        );
+        assert_eq!(
+            "themes/brand.toml".parse::<ThemeChoice>(),
+            Ok(ThemeChoice::File(PathBuf::from("themes/brand.toml")))
+        );
+        assert!("solarized".parse::<ThemeChoice>().is_err());
+        assert_eq!(Palette::default(), ThemePreset::Light.palette());
+    }
+}
@@ -0,0 +1,313 @@
+//! Shortest path and neighborhood queries over dependency graphs
+
+use std::collections::hash_map::Entry;
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use petgraph::Direction;
+use petgraph::graph::{DiGraph, NodeIndex};
+
+/// Find the shortest paths from any of `sources` to any of `targets`
+///
+/// Every returned path has the same, minimal number of hops and starts at a
+/// source and ends at a target. At most `limit` paths are returned; paths
+/// are ordered by node index so the result is deterministic for a given
+/// graph.
+pub fn shortest_paths<N, E>(
+    graph: &DiGraph<N, E>,
+    sources: &[NodeIndex],
+    targets: &[NodeIndex],
+    limit: usize,
+) -> Vec<Vec<NodeIndex>> {
+    let targets: HashSet<NodeIndex> = targets.iter().copied().collect();
+    if limit == 0 || sources.is_empty() || targets.is_empty() {
+        return Vec::new();
+    }
+
+    // Breadth-first search, remembering every predecessor on a shortest path
+    let mut distance: HashMap<NodeIndex, usize> = HashMap::new();
+    let mut predecessors: HashMap<NodeIndex, Vec<NodeIndex>> = HashMap::new();
+    let mut queue = VecDeque::new();
+    let mut reached = Vec::new();
+    let mut found_at = None;
+
+    let mut sorted_sources = sources.to_vec();
+    sorted_sources.sort();
+    sorted_sources.dedup();
+    for &source in &sorted_sources {
+        distance.insert(source, 0);
+        queue.push_back(source);
+    }
+
+    while let Some(node) = queue.pop_front() {
+        let node_distance = distance[&node];
+        if found_at.is_some_and(|found| node_distance >= found) {
+            break;
+        }
+
+        let mut neighbors: Vec<NodeIndex> = graph
+            .neighbors_directed(node, Direction::Outgoing)
+            .collect();
+        neighbors.sort();
+        neighbors.dedup();
+
+        for next in neighbors {
+            match distance.get(&next) {
+                None => {
+                    distance.insert(next, node_distance + 1);
+                    predecessors.insert(next, vec![node]);
+                    queue.push_back(next);
+                    if targets.contains(&next) {
+                        found_at = Some(node_distance + 1);
+                        reached.push(next);
+                    }
+                }
+                Some(&d) if d == node_distance + 1 => {
+                    predecessors.entry(next).or_default().push(node);
+                }
+                Some(_) => {}
+            }
+        }
+    }
+
+    // Walk predecessors back from each reached target
+    let mut paths = Vec::new();
+    for target in reached {
+        let mut suffix = vec![target];
+        collect_paths(&predecessors, &mut suffix, limit, &mut paths);
+        if paths.len() >= limit {
+            break;
+        }
+    }
+
+    paths
+}
+
+/// Enumerate simple paths from any of `sources` to any of `targets`,
+/// shortest first
+///
+/// Paths end at the first target they reach and never visit a node twice.
+/// Paths of equal length are ordered by node index. Only nodes that can still
+/// reach a target are explored. At most `limit` paths are returned, or every
+/// path when `limit` is `None`.
+pub fn simple_paths<N, E>(
+    graph: &DiGraph<N, E>,
+    sources: &[NodeIndex],
+    targets: &[NodeIndex],
+    limit: Option<usize>,
+) -> Vec<Vec<NodeIndex>> {
+    let limit = limit.unwrap_or(usize::MAX);
+    let target_set: HashSet<NodeIndex> = targets.iter().copied().collect();
+    if limit == 0 || sources.is_empty() || target_set.is_empty() {
+        return Vec::new();
+    }
+
+    // Nodes from which some target is reachable
+    let mut reaches_target = target_set.clone();
+    let mut queue: VecDeque<NodeIndex> = target_set.iter().copied().collect();
+    while let Some(node) = queue.pop_front() {
+        for previous in graph.neighbors_directed(node, Direction::Incoming) {
+            if reaches_target.insert(previous) {
+                queue.push_back(previous);
+            }
+        }
+    }
+
+    let mut sorted_sources = sources.to_vec();
+    sorted_sources.sort();
+    sorted_sources.dedup();
+
+    // Breadth-first over partial paths yields them in order of length
+    let mut paths = Vec::new();
+    let mut queue: VecDeque<Vec<NodeIndex>> = sorted_sources
+        .into_iter()
+        .filter(|source| reaches_target.contains(source))
+        .map(|source| vec![source])
+        .collect();
+    while let Some(path) = queue.pop_front() {
+        let head = *path.last().expect("paths are never empty");
+        if path.len() > 1 && target_set.contains(&head) {
+            paths.push(path);
+            if paths.len() >= limit {
+                break;
+            }
+            continue;
+        }
+
+        let mut neighbors: Vec<NodeIndex> = graph
+            .neighbors_directed(head, Direction::Outgoing)
+            .filter(|next| reaches_target.contains(next) && !path.contains(next))
+            .collect();
+        neighbors.sort();
+        neighbors.dedup();
+
+        for next in neighbors {
+            let mut extended = path.clone();
+            extended.push(next);
+            queue.push_back(extended);
+        }
+    }
+
+    paths
+}
+
+/// Extract the part of `graph` within `depth` hops of any of `centers`
+///
+/// Hops are counted in both directions, so the neighborhood holds what the
+/// centers depend on as well as what depends on them. Every edge between two
+/// kept nodes is kept.
+pub fn neighborhood<N: Clone, E: Clone>(
+    graph: &DiGraph<N, E>,
+    centers: &[NodeIndex],
+    depth: usize,
+) -> DiGraph<N, E> {
+    let mut distance: HashMap<NodeIndex, usize> = HashMap::new();
+    let mut queue = VecDeque::new();
+    for &center in centers {
+        if distance.insert(center, 0).is_none() {
+            queue.push_back(center);
+        }
+    }
+
+    while let Some(node) = queue.pop_front() {
+        let node_distance = distance[&node];
+        if node_distance >= depth {
+            continue;
+        }
+        for next in graph.neighbors_undirected(node) {
+            if let Entry::Vacant(entry) = distance.entry(next) {
+                entry.insert(node_distance + 1);
+                queue.push_back(next);
+            }
+        }
+    }
+
+    graph.filter_map(
+        |idx, node| distance.contains_key(&idx).then(|| node.clone()),
+        |_, edge| Some(edge.clone()),
+    )
+}
+
+fn collect_paths(
+    predecessors: &HashMap<NodeIndex, Vec<NodeIndex>>,
+    suffix: &mut Vec<NodeIndex>,
+    limit: usize,
+    paths: &mut Vec<Vec<NodeIndex>>,
+) {
+    if paths.len() >= limit {
+        return;
+    }
+
+    let head = *suffix.last().expect("suffix is never empty");
+    match predecessors.get(&head) {
+        None => {
+            let mut path = suffix.clone();
+            path.reverse();
+            paths.push(path);
+        }
+        Some(previous) => {
+            for &prev in previous {
+                suffix.push(prev);
+                collect_paths(predecessors, suffix, limit, paths);
+                suffix.pop();
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn graph(edges: &[(u32, u32)]) -> DiGraph<(), ()> {
+        DiGraph::from_edges(edges)
+    }
+
+    #[test]
+    fn test_finds_all_shortest_paths() {
+        // 0 -> 1 -> 3, 0 -> 2 -> 3, 0 -> 4 -> 5 -> 3
+        let graph = graph(&[(0, 1), (1, 3), (0, 2), (2, 3), (0, 4), (4, 5), (5, 3)]);
+
+        let paths = shortest_paths(&graph, &[0.into()], &[3.into()], 10);
+
+        assert_eq!(
+            paths,
+            vec![
+                vec![0.into(), 1.into(), 3.into()],
+                vec![0.into(), 2.into(), 3.into()],
+            ]
+        );
+    }
+
+    #[test]
+    fn test_respects_limit_and_unreachable_targets() {
+        let graph = graph(&[(0, 1), (1, 3), (0, 2), (2, 3), (4, 0)]);
+
+        assert_eq!(shortest_paths(&graph, &[0.into()], &[3.into()], 1).len(), 1);
+        assert!(shortest_paths(&graph, &[0.into()], &[4.into()], 10).is_empty());
+    }
+
+    #[test]
+    fn test_multiple_sources_and_targets() {
+        let graph = graph(&[(0, 2), (2, 3), (1, 3)]);
+
+        let paths = shortest_paths(&graph, &[0.into(), 1.into()], &[2.into(), 3.into()], 10);
+
+        assert_eq!(
+            paths,
+            vec![vec![0.into(), 2.into()], vec![1.into(), 3.into()]]
+        );
+    }
+
+    #[test]
+    fn test_neighborhood_follows_both_directions() {
+        // 0 -> 1 -> 2 -> 3, 4 -> 1, 5 -> 4
+        let graph: DiGraph<u32, ()> = graph(&[(0, 1), (1, 2), (2, 3), (4, 1), (5, 4)])
+            .map(|idx, _| idx.index() as u32, |_, _| ());
+
+        let nodes = |depth| {
+            let mut nodes: Vec<u32> = neighborhood(&graph, &[1.into()], depth)
+                .node_weights()
+                .copied()
+                .collect();
+            nodes.sort();
+            nodes
+        };
+
+        assert_eq!(nodes(0), [1]);
+        assert_eq!(nodes(1), [0, 1, 2, 4]);
+        assert_eq!(nodes(2), [0, 1, 2, 3, 4, 5]);
+        assert_eq!(neighborhood(&graph, &[1.into()], 1).edge_count(), 3);
+    }
+
+    #[test]
+    fn test_simple_paths_are_listed_shortest_first() {
+        // 0 -> 1 -> 3, 0 -> 2 -> 3, 0 -> 4 -> 5 -> 3, 3 -> 0, 0 -> 6
+        let graph = graph(&[
+            (0, 1),
+            (1, 3),
+            (0, 2),
+            (2, 3),
+            (0, 4),
+            (4, 5),
+            (5, 3),
+            (3, 0),
+            (0, 6),
+        ]);
+
+        let paths = simple_paths(&graph, &[0.into()], &[3.into()], None);
+        assert_eq!(
+            paths,
+            vec![
+                vec![0.into(), 1.into(), 3.into()],
+                vec![0.into(), 2.into(), 3.into()],
+                vec![0.into(), 4.into(), 5.into(), 3.into()],
+            ]
+        );
+
+        assert_eq!(
+            simple_paths(&graph, &[0.into()], &[3.into()], Some(2)).len(),
+            2
+        );
+        assert!(simple_paths(&graph, &[6.into()], &[3.into()], None).is_empty());
+    }
+}
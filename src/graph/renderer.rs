@@ -1,26 +1,16 @@
-use std::collections::{BTreeMap, HashMap};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::io::Write;
+use std::sync::Arc;
 
 use miette::Result;
 use petgraph::graph::{DiGraph, NodeIndex};
 use petgraph::visit::EdgeRef;
+use serde::Serialize;
 
-use crate::detector::WorkspaceCycle;
+use crate::cli::{EdgeLabels, MermaidDirection};
+use crate::detector::{CycleSeverity, SeverityModel, WorkspaceCycle};
 use crate::error::FerrisWheelError;
-use crate::graph::{DependencyEdge, DependencyType, WorkspaceNode};
-
-// Blue-Orange Accessible Palette - Soothing colors with excellent contrast
-mod colors {
-    pub const NORMAL_NODE_FILL: &str = "#E3F2FD"; // Light blue
-    pub const NORMAL_NODE_STROKE: &str = "#1976D2"; // Medium blue
-    pub const CYCLE_NODE_FILL: &str = "#FFF3E0"; // Light orange
-    pub const CYCLE_NODE_STROKE: &str = "#F57C00"; // Vibrant orange
-    pub const NORMAL_EDGE: &str = "#64B5F6"; // Soft blue
-    pub const DEV_EDGE: &str = "#90A4AE"; // Blue-grey
-    pub const BUILD_EDGE: &str = "#81C784"; // Soft green
-    pub const CYCLE_EDGE: &str = "#FF6500"; // Deep orange
-    pub const LEGEND_BG: &str = "#FAFAFA"; // Off-white background
-}
+use crate::graph::{DependencyEdge, DependencyType, Palette, WorkspaceGrouping, WorkspaceNode};
 
 // Helper macro for write operations that converts IO errors
 macro_rules! writeln_out {
@@ -32,16 +22,54 @@ macro_rules! writeln_out {
     };
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-enum CycleSeverity {
-    Low,    // 2 workspaces, mostly dev/build deps
-    Medium, // 3-4 workspaces or mix of dependency types
-    High,   // 5+ workspaces or mostly normal deps
+/// Mermaid-specific rendering options
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MermaidOptions {
+    /// Flowchart layout direction
+    pub direction: MermaidDirection,
+}
+
+/// Dependency counts towards one target workspace, by dependency type
+#[derive(Debug, Default, Serialize)]
+struct AdjacencyEntry<'a> {
+    target: &'a str,
+    normal: usize,
+    build: usize,
+    dev: usize,
 }
 
+/// One cycle of the HTML animation, walked workspace by workspace
+#[derive(Debug, Serialize)]
+struct AnimatedCycle {
+    id: String,
+    /// Workspaces in walk order; each depends on the next, and the last on
+    /// the first
+    workspaces: Vec<String>,
+    hops: Vec<AnimatedHop>,
+}
+
+/// A dependency direction between consecutive workspaces of a cycle walk
+#[derive(Debug, Serialize)]
+struct AnimatedHop {
+    from: String,
+    to: String,
+    /// Crate edges behind the dependency, as `from-crate → to-crate`
+    edges: Vec<String>,
+}
+
+/// Page that steps through each cycle, filled in with the JSON cycle data
+const CYCLE_ANIMATION_TEMPLATE: &str = include_str!("cycle_animation.html");
+
 pub struct GraphRenderer {
     highlight_cycles: bool,
     show_crates: bool,
+    mermaid: MermaidOptions,
+    edge_labels: Option<EdgeLabels>,
+    grouping: Option<WorkspaceGrouping>,
+    crate_subgraphs: bool,
+    palette: Palette,
+    severity_model: SeverityModel,
+    nest_workspaces: bool,
 }
 
 impl GraphRenderer {
@@ -49,9 +77,61 @@ impl GraphRenderer {
         Self {
             highlight_cycles,
             show_crates,
+            mermaid: MermaidOptions::default(),
+            edge_labels: None,
+            grouping: None,
+            crate_subgraphs: false,
+            palette: Palette::default(),
+            severity_model: SeverityModel::default(),
+            nest_workspaces: false,
         }
     }
 
+    pub fn with_mermaid_options(mut self, mermaid: MermaidOptions) -> Self {
+        self.mermaid = mermaid;
+        self
+    }
+
+    /// Edge label verbosity for Mermaid, DOT and D2 (`None` follows
+    /// `show_crates`)
+    pub fn with_edge_labels(mut self, edge_labels: Option<EdgeLabels>) -> Self {
+        self.edge_labels = edge_labels;
+        self
+    }
+
+    /// Draw Mermaid workspaces as subgraphs of their crates, connected by
+    /// crate-to-crate edges
+    pub fn with_crate_subgraphs(mut self, crate_subgraphs: bool) -> Self {
+        self.crate_subgraphs = crate_subgraphs;
+        self
+    }
+
+    /// Color Mermaid, DOT and D2 graphs with `palette`
+    pub fn with_palette(mut self, palette: Palette) -> Self {
+        self.palette = palette;
+        self
+    }
+
+    /// Rate cycles with the same severity model as the reports
+    pub fn with_severity_model(mut self, severity_model: SeverityModel) -> Self {
+        self.severity_model = severity_model;
+        self
+    }
+
+    /// Group Mermaid nodes into subgraphs and DOT nodes into clusters with
+    /// `grouping`; without one, Mermaid groups by name prefix and DOT does
+    /// not group
+    pub fn with_grouping(mut self, grouping: Option<WorkspaceGrouping>) -> Self {
+        self.grouping = grouping;
+        self
+    }
+
+    /// Group Mermaid and DOT nodes by meta workspace instead of name prefix
+    pub fn with_nested_workspaces(mut self, nest_workspaces: bool) -> Self {
+        self.nest_workspaces = nest_workspaces;
+        self
+    }
+
     pub fn render_ascii(
         &self,
         graph: &DiGraph<WorkspaceNode, DependencyEdge>,
@@ -66,7 +146,7 @@ impl GraphRenderer {
         writeln_out!(output, "\n📊 Workspace Dependency Graph\n")?;
 
         // Build sets of workspace names involved in cycles for easy lookup
-        let cycles_ws_names: Vec<Vec<String>> = cycles
+        let cycles_ws_names: Vec<Vec<Arc<str>>> = cycles
             .iter()
             .map(|cycle| cycle.workspace_names().to_vec())
             .collect();
@@ -82,7 +162,7 @@ impl GraphRenderer {
             // Check if this workspace is involved in any cycle
             let in_cycle = cycles_ws_names
                 .iter()
-                .any(|cycle| cycle.iter().any(|c| c == ws_name));
+                .any(|cycle| cycle.iter().any(|c| &**c == ws_name));
 
             // Print workspace header with cycle indicator
             if in_cycle && self.highlight_cycles {
@@ -104,7 +184,7 @@ impl GraphRenderer {
 
             for edge in graph.edges(node_idx) {
                 let edge_data = edge.weight();
-                let key = (edge.target(), edge_data.dependency_type().clone());
+                let key = (edge.target(), *edge_data.dependency_type());
                 edge_groups.entry(key).or_default().push(edge_data);
             }
 
@@ -114,7 +194,7 @@ impl GraphRenderer {
                 // Sort groups by target workspace name and dependency type
                 let mut groups: Vec<_> = edge_groups.into_iter().collect();
                 groups.sort_by_key(|((target_idx, dep_type), _)| {
-                    (graph[*target_idx].name(), dep_type.clone())
+                    (graph[*target_idx].name(), *dep_type)
                 });
 
                 for (i, ((target_idx, dep_type), edges)) in groups.iter().enumerate() {
@@ -190,156 +270,67 @@ impl GraphRenderer {
         cycles: &[WorkspaceCycle],
         output: &mut dyn Write,
     ) -> Result<()> {
-        writeln_out!(output, "graph TD")?;
+        if let Some(theme) = self.palette.mermaid_theme {
+            writeln_out!(
+                output,
+                "%%{{init: {{'theme': '{}'}}}}%%",
+                theme.as_mermaid()
+            )?;
+        }
+        writeln_out!(output, "graph {}", self.mermaid.direction.as_mermaid())?;
 
         // Build sets of workspace names involved in cycles
-        let cycles_ws_names: Vec<Vec<String>> = cycles
+        let cycles_ws_names: Vec<Vec<Arc<str>>> = cycles
             .iter()
             .map(|cycle| cycle.workspace_names().to_vec())
             .collect();
 
-        // Group workspaces by prefix for subgraphs
-        let groups = self.group_workspaces_by_prefix(graph);
-        let mut ungrouped_nodes: Vec<NodeIndex> = graph.node_indices().collect();
+        if self.crate_subgraphs {
+            self.write_mermaid_crate_subgraphs(graph, &cycles_ws_names, output)?;
+            return self.write_mermaid_legend(cycles, output);
+        }
 
-        // Render subgraphs
-        for (prefix, nodes) in groups.iter() {
+        if self.nest_workspaces {
+            let (top_level, children) = self.nest_workspaces_by_parent(graph);
             writeln_out!(output)?;
-            writeln_out!(
-                output,
-                "    subgraph {}_group[\"{}\"*]",
-                self.mermaid_id(prefix),
-                prefix
-            )?;
-
-            for &node in nodes {
-                let ws = &graph[node];
-                let in_cycle = cycles_ws_names
-                    .iter()
-                    .any(|cycle| cycle.iter().any(|c| c == ws.name()));
-
-                let node_id = self.mermaid_id(ws.name());
-                let label = if self.show_crates {
-                    format!("{}\\n{} crates", ws.name(), ws.crates().len())
-                } else {
-                    ws.name().to_string()
-                };
+            for node in top_level {
+                self.write_mermaid_nested(graph, node, &children, &cycles_ws_names, 1, output)?;
+            }
+        } else {
+            // Group workspaces into subgraphs, by name prefix unless told
+            // otherwise
+            let groups = self
+                .grouping
+                .as_ref()
+                .unwrap_or(&WorkspaceGrouping::Prefix)
+                .group(graph)?;
+            let mut ungrouped_nodes: Vec<NodeIndex> = graph.node_indices().collect();
+
+            // Render subgraphs
+            for (prefix, nodes) in groups.iter() {
+                writeln_out!(output)?;
+                writeln_out!(
+                    output,
+                    "    subgraph {}_group[\"{}\"*]",
+                    self.mermaid_id(prefix),
+                    prefix
+                )?;
 
-                // Create tooltip text for click events
-                let tooltip = format!(
-                    "Workspace: {} - Crates: {} - Total: {}",
-                    ws.name(),
-                    if ws.crates().len() <= 3 {
-                        ws.crates().join(", ")
-                    } else {
-                        format!(
-                            "{}, ... ({} total)",
-                            ws.crates()[..3].join(", "),
-                            ws.crates().len()
-                        )
-                    },
-                    ws.crates().len()
-                );
-
-                // Use different shapes based on workspace characteristics
-                let node_shape = if ws.crates().len() > 5 {
-                    format!("{node_id}[\"{label}\"]") // Rectangle for large workspaces (even in cycles)
-                } else if in_cycle && self.highlight_cycles {
-                    format!("{node_id}((\"{label}\"))") // Double circle for cycles
-                } else if ws.crates().len() == 1 {
-                    format!("{node_id}([\"{label}\"])") // Stadium shape for single-crate workspaces
-                } else {
-                    format!("{node_id}[\"{label}\"]") // Default rectangle
-                };
-                writeln_out!(output, "        {}", node_shape)?;
-                writeln_out!(output, "        click {} \"{}\"", node_id, tooltip)?;
+                for &node in nodes {
+                    self.write_mermaid_node(&graph[node], &cycles_ws_names, "        ", output)?;
 
-                if in_cycle && self.highlight_cycles {
-                    writeln_out!(
-                        output,
-                        "        style {} fill:{},stroke:{},stroke-width:3px",
-                        node_id,
-                        colors::CYCLE_NODE_FILL,
-                        colors::CYCLE_NODE_STROKE
-                    )?;
-                } else {
-                    writeln_out!(
-                        output,
-                        "        style {} fill:{},stroke:{},stroke-width:2px",
-                        node_id,
-                        colors::NORMAL_NODE_FILL,
-                        colors::NORMAL_NODE_STROKE
-                    )?;
+                    // Remove from ungrouped nodes
+                    ungrouped_nodes.retain(|&n| n != node);
                 }
 
-                // Remove from ungrouped nodes
-                ungrouped_nodes.retain(|&n| n != node);
+                writeln_out!(output, "    end")?;
             }
 
-            writeln_out!(output, "    end")?;
-        }
-
-        // Render ungrouped nodes
-        if !ungrouped_nodes.is_empty() {
-            writeln_out!(output)?;
-            for node in ungrouped_nodes {
-                let ws = &graph[node];
-                let in_cycle = cycles_ws_names
-                    .iter()
-                    .any(|cycle| cycle.iter().any(|c| c == ws.name()));
-
-                let node_id = self.mermaid_id(ws.name());
-                let label = if self.show_crates {
-                    format!("{}\\n{} crates", ws.name(), ws.crates().len())
-                } else {
-                    ws.name().to_string()
-                };
-
-                // Create tooltip text for click events
-                let tooltip = format!(
-                    "Workspace: {} - Crates: {} - Total: {}",
-                    ws.name(),
-                    if ws.crates().len() <= 3 {
-                        ws.crates().join(", ")
-                    } else {
-                        format!(
-                            "{}, ... ({} total)",
-                            ws.crates()[..3].join(", "),
-                            ws.crates().len()
-                        )
-                    },
-                    ws.crates().len()
-                );
-
-                // Use different shapes based on workspace characteristics
-                let node_shape = if ws.crates().len() > 5 {
-                    format!("    {node_id}[\"{label}\"]") // Rectangle for large workspaces (even in cycles)
-                } else if in_cycle && self.highlight_cycles {
-                    format!("    {node_id}((\"{label}\"))") // Double circle for cycles
-                } else if ws.crates().len() == 1 {
-                    format!("    {node_id}([\"{label}\"])") // Stadium shape for single-crate workspaces
-                } else {
-                    format!("    {node_id}[\"{label}\"]") // Default rectangle
-                };
-                writeln_out!(output, "{}", node_shape)?;
-                writeln_out!(output, "    click {} \"{}\"", node_id, tooltip)?;
-
-                if in_cycle && self.highlight_cycles {
-                    writeln_out!(
-                        output,
-                        "    style {} fill:{},stroke:{},stroke-width:3px",
-                        node_id,
-                        colors::CYCLE_NODE_FILL,
-                        colors::CYCLE_NODE_STROKE
-                    )?;
-                } else {
-                    writeln_out!(
-                        output,
-                        "    style {} fill:{},stroke:{},stroke-width:2px",
-                        node_id,
-                        colors::NORMAL_NODE_FILL,
-                        colors::NORMAL_NODE_STROKE
-                    )?;
+            // Render ungrouped nodes
+            if !ungrouped_nodes.is_empty() {
+                writeln_out!(output)?;
+                for node in ungrouped_nodes {
+                    self.write_mermaid_node(&graph[node], &cycles_ws_names, "    ", output)?;
                 }
             }
         }
@@ -361,7 +352,7 @@ impl GraphRenderer {
                     message: "Edge weight not found for existing edge".to_string(),
                 }
             })?;
-            let key = (source, target, edge_data.dependency_type().clone());
+            let key = (source, target, *edge_data.dependency_type());
             edge_groups.entry(key).or_default().push(edge_data);
         }
 
@@ -375,39 +366,48 @@ impl GraphRenderer {
             let edge_in_cycle =
                 self.is_edge_in_cycle(source_ws.name(), target_ws.name(), &cycles_ws_names);
 
-            let label = if self.show_crates {
-                // Show all crate pairs when show_crates is true
-                let pairs: Vec<String> = edges
-                    .iter()
-                    .map(|e| format!("{} → {}", e.from_crate(), e.to_crate()))
-                    .collect();
-                if pairs.len() > 1 {
-                    let type_icon = match dep_type {
-                        DependencyType::Normal => "📦",
-                        DependencyType::Dev => "🔧",
-                        DependencyType::Build => "🏗️",
+            let label = match self.edge_labels() {
+                EdgeLabels::None => String::new(),
+                EdgeLabels::Full => {
+                    // Show all crate pairs
+                    let pairs: Vec<String> = edges
+                        .iter()
+                        .map(|e| format!("{} → {}", e.from_crate(), e.to_crate()))
+                        .collect();
+                    if pairs.len() > 1 {
+                        let type_icon = match dep_type {
+                            DependencyType::Normal => "📦",
+                            DependencyType::Dev => "🔧",
+                            DependencyType::Build => "🏗️",
+                        };
+                        format!(
+                            "{} {} ({})",
+                            type_icon,
+                            pairs.len(),
+                            format!("{dep_type:?}").to_lowercase()
+                        )
+                    } else {
+                        pairs[0].clone()
+                    }
+                }
+                EdgeLabels::Compact => {
+                    // Use icons and cleaner labels
+                    let (icon, type_label) = match dep_type {
+                        DependencyType::Normal => ("📦", "uses"),
+                        DependencyType::Dev => ("🔧", "dev"),
+                        DependencyType::Build => ("🏗️", "build"),
                     };
-                    format!(
-                        "{} {} ({})",
-                        type_icon,
-                        pairs.len(),
-                        format!("{dep_type:?}").to_lowercase()
-                    )
-                } else {
-                    pairs[0].clone()
+                    if edges.len() > 1 {
+                        format!("{} {} {}", icon, edges.len(), type_label)
+                    } else {
+                        format!("{icon} {type_label}")
+                    }
                 }
+            };
+            let label = if label.is_empty() {
+                label
             } else {
-                // When not showing crates, use icons and cleaner labels
-                let (icon, type_label) = match dep_type {
-                    DependencyType::Normal => ("📦", "uses"),
-                    DependencyType::Dev => ("🔧", "dev"),
-                    DependencyType::Build => ("🏗️", "build"),
-                };
-                if edges.len() > 1 {
-                    format!("{} {} {}", icon, edges.len(), type_label)
-                } else {
-                    format!("{icon} {type_label}")
-                }
+                format!("|{label}|")
             };
 
             // Choose arrow type based on dependency type
@@ -420,7 +420,7 @@ impl GraphRenderer {
             if edge_in_cycle && self.highlight_cycles {
                 writeln_out!(
                     output,
-                    "    {} {}|{}| {}",
+                    "    {} {}{} {}",
                     self.mermaid_id(source_ws.name()),
                     arrow_type,
                     label,
@@ -430,12 +430,12 @@ impl GraphRenderer {
                     output,
                     "    linkStyle {} stroke:{},stroke-width:3px",
                     link_style_index,
-                    colors::CYCLE_EDGE
+                    self.palette.cycle_edge.as_str()
                 )?;
             } else {
                 writeln_out!(
                     output,
-                    "    {} {}|{}| {}",
+                    "    {} {}{} {}",
                     self.mermaid_id(source_ws.name()),
                     arrow_type,
                     label,
@@ -443,9 +443,9 @@ impl GraphRenderer {
                 )?;
                 // Color edges based on dependency type
                 let edge_color = match dep_type {
-                    DependencyType::Normal => colors::NORMAL_EDGE,
-                    DependencyType::Dev => colors::DEV_EDGE,
-                    DependencyType::Build => colors::BUILD_EDGE,
+                    DependencyType::Normal => self.palette.normal_edge.as_str(),
+                    DependencyType::Dev => self.palette.dev_edge.as_str(),
+                    DependencyType::Build => self.palette.build_edge.as_str(),
                 };
                 writeln_out!(
                     output,
@@ -456,7 +456,16 @@ impl GraphRenderer {
             }
         }
 
-        // Add legend
+        self.write_mermaid_legend(cycles, output)
+    }
+
+    /// Legend and cycle severities below a Mermaid graph with highlighted
+    /// cycles
+    fn write_mermaid_legend(
+        &self,
+        cycles: &[WorkspaceCycle],
+        output: &mut dyn Write,
+    ) -> Result<()> {
         if !cycles.is_empty() && self.highlight_cycles {
             writeln_out!(output)?;
             writeln_out!(output, "    subgraph Legend")?;
@@ -464,20 +473,23 @@ impl GraphRenderer {
             writeln_out!(output, "        L2[Workspace in Cycle]")?;
             writeln_out!(
                 output,
-                "        style L1 fill:{},stroke:{},stroke-width:2px",
-                colors::NORMAL_NODE_FILL,
-                colors::NORMAL_NODE_STROKE
+                "        style L1 fill:{},stroke:{},stroke-width:2px{}",
+                self.palette.normal_node_fill.as_str(),
+                self.palette.normal_node_stroke.as_str(),
+                self.mermaid_text_style()
             )?;
             writeln_out!(
                 output,
-                "        style L2 fill:{},stroke:{},stroke-width:3px",
-                colors::CYCLE_NODE_FILL,
-                colors::CYCLE_NODE_STROKE
+                "        style L2 fill:{},stroke:{},stroke-width:3px{}",
+                self.palette.cycle_node_fill.as_str(),
+                self.palette.cycle_node_stroke.as_str(),
+                self.mermaid_text_style()
             )?;
             writeln_out!(
                 output,
-                "        style Legend fill:{},stroke:#ddd,stroke-width:1px",
-                colors::LEGEND_BG
+                "        style Legend fill:{},stroke:{},stroke-width:1px",
+                self.palette.legend_background,
+                self.palette.legend_stroke
             )?;
             writeln_out!(output, "    end")?;
 
@@ -485,7 +497,7 @@ impl GraphRenderer {
             writeln_out!(output)?;
             writeln_out!(output, "    subgraph CycleSeverity[\"Cycle Severity\"]")?;
             for (i, cycle) in cycles.iter().enumerate() {
-                let severity = self.calculate_cycle_severity(cycle);
+                let severity = self.severity_model.classify(cycle);
                 let severity_icon = match severity {
                     CycleSeverity::Low => "⚠️",
                     CycleSeverity::Medium => "⚠️⚠️",
@@ -504,8 +516,9 @@ impl GraphRenderer {
             }
             writeln_out!(
                 output,
-                "        style CycleSeverity fill:{},stroke:#ddd,stroke-width:1px",
-                colors::LEGEND_BG
+                "        style CycleSeverity fill:{},stroke:{},stroke-width:1px",
+                self.palette.legend_background,
+                self.palette.legend_stroke
             )?;
             writeln_out!(output, "    end")?;
         }
@@ -513,6 +526,124 @@ impl GraphRenderer {
         Ok(())
     }
 
+    /// Draw each workspace as a subgraph of its crates, with crate-to-crate
+    /// edges
+    fn write_mermaid_crate_subgraphs(
+        &self,
+        graph: &DiGraph<WorkspaceNode, DependencyEdge>,
+        cycles_ws_names: &[Vec<Arc<str>>],
+        output: &mut dyn Write,
+    ) -> Result<()> {
+        let mut nodes: Vec<NodeIndex> = graph.node_indices().collect();
+        nodes.sort_by_key(|&idx| graph[idx].name());
+
+        for &node in &nodes {
+            let ws = &graph[node];
+            let ws_id = self.mermaid_id(ws.name());
+            writeln_out!(output)?;
+            writeln_out!(output, "    subgraph {}[\"{}\"]", ws_id, ws.name())?;
+            let mut crates = ws.crates().to_vec();
+            crates.sort();
+            for krate in &crates {
+                writeln_out!(
+                    output,
+                    "        {}[\"{}\"]",
+                    self.mermaid_crate_id(ws.name(), krate),
+                    krate
+                )?;
+            }
+            writeln_out!(output, "    end")?;
+
+            let in_cycle = cycles_ws_names
+                .iter()
+                .any(|cycle| cycle.iter().any(|c| &**c == ws.name()));
+            let (fill, stroke) = if in_cycle && self.highlight_cycles {
+                (
+                    self.palette.cycle_node_fill.as_str(),
+                    self.palette.cycle_node_stroke.as_str(),
+                )
+            } else {
+                (
+                    self.palette.normal_node_fill.as_str(),
+                    self.palette.normal_node_stroke.as_str(),
+                )
+            };
+            writeln_out!(
+                output,
+                "    style {} fill:{},stroke:{}{}",
+                ws_id,
+                fill,
+                stroke,
+                self.mermaid_text_style()
+            )?;
+        }
+
+        writeln_out!(output)?;
+
+        // One edge per crate pair and dependency type, in a stable order
+        let mut edges: Vec<(&str, &str, &str, &str, DependencyType)> = graph
+            .edge_references()
+            .map(|edge| {
+                let data = edge.weight();
+                (
+                    graph[edge.source()].name(),
+                    data.from_crate(),
+                    graph[edge.target()].name(),
+                    data.to_crate(),
+                    *data.dependency_type(),
+                )
+            })
+            .collect();
+        edges.sort_by_key(|&(from_ws, from, to_ws, to, dep_type)| {
+            (from_ws, from, to_ws, to, dep_type.as_str())
+        });
+        edges.dedup();
+
+        for (link_style_index, (from_ws, from, to_ws, to, dep_type)) in
+            edges.into_iter().enumerate()
+        {
+            let (arrow_type, icon, type_label) = match dep_type {
+                DependencyType::Normal => ("-->", "📦", "uses"),
+                DependencyType::Dev => ("-.->", "🔧", "dev"),
+                DependencyType::Build => ("===>", "🏗️", "build"),
+            };
+            let label = match self.edge_labels() {
+                EdgeLabels::None => String::new(),
+                EdgeLabels::Compact | EdgeLabels::Full => format!("|{icon} {type_label}|"),
+            };
+            writeln_out!(
+                output,
+                "    {} {}{} {}",
+                self.mermaid_crate_id(from_ws, from),
+                arrow_type,
+                label,
+                self.mermaid_crate_id(to_ws, to)
+            )?;
+
+            let (color, width) = if self.highlight_cycles
+                && self.is_edge_in_cycle(from_ws, to_ws, cycles_ws_names)
+            {
+                (self.palette.cycle_edge.as_str(), 3)
+            } else {
+                let color = match dep_type {
+                    DependencyType::Normal => self.palette.normal_edge.as_str(),
+                    DependencyType::Dev => self.palette.dev_edge.as_str(),
+                    DependencyType::Build => self.palette.build_edge.as_str(),
+                };
+                (color, 2)
+            };
+            writeln_out!(
+                output,
+                "    linkStyle {} stroke:{},stroke-width:{}px",
+                link_style_index,
+                color,
+                width
+            )?;
+        }
+
+        Ok(())
+    }
+
     pub fn render_dot(
         &self,
         graph: &DiGraph<WorkspaceNode, DependencyEdge>,
@@ -522,41 +653,40 @@ impl GraphRenderer {
         writeln_out!(output, "digraph workspace_dependencies {{")?;
         writeln_out!(output, "    rankdir=LR;")?;
         writeln_out!(output, "    node [shape=box, style=rounded];")?;
+        if let Some(text) = &self.palette.text {
+            writeln_out!(output, r#"    node [fontcolor="{}"];"#, text)?;
+        }
         writeln_out!(output)?;
 
         // Build sets of workspace names involved in cycles
-        let cycles_ws_names: Vec<Vec<String>> = cycles
+        let cycles_ws_names: Vec<Vec<Arc<str>>> = cycles
             .iter()
             .map(|cycle| cycle.workspace_names().to_vec())
             .collect();
 
         // Define nodes
-        for node in graph.node_indices() {
-            let ws = &graph[node];
-            let in_cycle = cycles_ws_names
-                .iter()
-                .any(|cycle| cycle.iter().any(|c| c == ws.name()));
-
-            let (fill_color, stroke_color) = if in_cycle && self.highlight_cycles {
-                (colors::CYCLE_NODE_FILL, colors::CYCLE_NODE_STROKE)
-            } else {
-                (colors::NORMAL_NODE_FILL, colors::NORMAL_NODE_STROKE)
-            };
-
-            let label = if self.show_crates {
-                format!("{}\\n{} crates", ws.name(), ws.crates().len())
-            } else {
-                ws.name().to_string()
+        if self.nest_workspaces {
+            let (top_level, children) = self.nest_workspaces_by_parent(graph);
+            for node in top_level {
+                self.write_dot_nested(graph, node, &children, &cycles_ws_names, 1, output)?;
+            }
+        } else {
+            let groups = match &self.grouping {
+                Some(grouping) => grouping.group(graph)?,
+                None => BTreeMap::new(),
             };
-
-            writeln_out!(
-                output,
-                r#"    "{}" [label="{}", style=filled, fillcolor="{}", color="{}", penwidth=2];"#,
-                ws.name(),
-                label,
-                fill_color,
-                stroke_color
-            )?;
+            let grouped: HashSet<NodeIndex> = groups.values().flatten().copied().collect();
+            for (group, nodes) in &groups {
+                writeln_out!(output, r#"    subgraph "cluster_{}" {{"#, group)?;
+                writeln_out!(output, r#"        label="{}";"#, group)?;
+                for &node in nodes {
+                    self.write_dot_node(&graph[node], &cycles_ws_names, "        ", output)?;
+                }
+                writeln_out!(output, "    }}")?;
+            }
+            for node in graph.node_indices().filter(|node| !grouped.contains(node)) {
+                self.write_dot_node(&graph[node], &cycles_ws_names, "    ", output)?;
+            }
         }
 
         writeln_out!(output)?;
@@ -576,7 +706,7 @@ impl GraphRenderer {
                     message: "Edge weight not found for existing edge".to_string(),
                 }
             })?;
-            let key = (source, target, edge_data.dependency_type().clone());
+            let key = (source, target, *edge_data.dependency_type());
             edge_groups.entry(key).or_default().push(edge_data);
         }
 
@@ -588,44 +718,29 @@ impl GraphRenderer {
             let edge_in_cycle =
                 self.is_edge_in_cycle(source_ws.name(), target_ws.name(), &cycles_ws_names);
 
-            let label = if self.show_crates {
-                // Show all crate pairs when show_crates is true
-                let pairs: Vec<String> = edges
-                    .iter()
-                    .map(|e| format!("{} → {}", e.from_crate(), e.to_crate()))
-                    .collect();
-                if pairs.len() > 1 {
-                    format!("{:?} - {} deps", dep_type, pairs.len())
-                } else {
-                    pairs[0].clone()
-                }
-            } else {
-                // When not showing crates, aggregate by type and count
-                if edges.len() > 1 {
-                    format!("{:?} - {} deps", dep_type, edges.len())
-                } else {
-                    format!("{dep_type:?}")
-                }
-            };
+            let label = self
+                .plain_edge_label(dep_type, &edges)
+                .map(|label| format!(r#"label="{label}", "#))
+                .unwrap_or_default();
 
             if edge_in_cycle && self.highlight_cycles {
                 writeln_out!(
                     output,
-                    r#"    "{}" -> "{}" [label="{}", color="{}", penwidth=3];"#,
+                    r#"    "{}" -> "{}" [{}color="{}", penwidth=3];"#,
                     source_ws.name(),
                     target_ws.name(),
                     label,
-                    colors::CYCLE_EDGE
+                    self.palette.cycle_edge.as_str()
                 )?;
             } else {
                 let edge_color = match dep_type {
-                    DependencyType::Normal => colors::NORMAL_EDGE,
-                    DependencyType::Dev => colors::DEV_EDGE,
-                    DependencyType::Build => colors::BUILD_EDGE,
+                    DependencyType::Normal => self.palette.normal_edge.as_str(),
+                    DependencyType::Dev => self.palette.dev_edge.as_str(),
+                    DependencyType::Build => self.palette.build_edge.as_str(),
                 };
                 writeln_out!(
                     output,
-                    r#"    "{}" -> "{}" [label="{}", color="{}", penwidth=2];"#,
+                    r#"    "{}" -> "{}" [{}color="{}", penwidth=2];"#,
                     source_ws.name(),
                     target_ws.name(),
                     label,
@@ -647,7 +762,7 @@ impl GraphRenderer {
         writeln_out!(output, "# Workspace Dependency Graph\n")?;
 
         // Build sets of workspace names involved in cycles
-        let cycles_ws_names: Vec<Vec<String>> = cycles
+        let cycles_ws_names: Vec<Vec<Arc<str>>> = cycles
             .iter()
             .map(|cycle| cycle.workspace_names().to_vec())
             .collect();
@@ -657,7 +772,7 @@ impl GraphRenderer {
             let ws = &graph[node];
             let in_cycle = cycles_ws_names
                 .iter()
-                .any(|cycle| cycle.iter().any(|c| c == ws.name()));
+                .any(|cycle| cycle.iter().any(|c| &**c == ws.name()));
 
             let shape = if in_cycle && self.highlight_cycles {
                 "hexagon"
@@ -673,22 +788,25 @@ impl GraphRenderer {
 
             writeln_out!(output, "{}: {} {{", self.d2_id(ws.name()), label)?;
             writeln_out!(output, "  shape: {}", shape)?;
+            if let Some(text) = &self.palette.text {
+                writeln_out!(output, "  style.font-color: \"{}\"", text)?;
+            }
             writeln_out!(
                 output,
                 "  style.fill: \"{}\"",
                 if in_cycle && self.highlight_cycles {
-                    colors::CYCLE_NODE_FILL
+                    self.palette.cycle_node_fill.as_str()
                 } else {
-                    colors::NORMAL_NODE_FILL
+                    self.palette.normal_node_fill.as_str()
                 }
             )?;
             writeln_out!(
                 output,
                 "  style.stroke: \"{}\"",
                 if in_cycle && self.highlight_cycles {
-                    colors::CYCLE_NODE_STROKE
+                    self.palette.cycle_node_stroke.as_str()
                 } else {
-                    colors::NORMAL_NODE_STROKE
+                    self.palette.normal_node_stroke.as_str()
                 }
             )?;
             writeln_out!(output, "}}")?;
@@ -710,7 +828,7 @@ impl GraphRenderer {
                     message: "Edge weight not found for existing edge".to_string(),
                 }
             })?;
-            let key = (source, target, edge_data.dependency_type().clone());
+            let key = (source, target, *edge_data.dependency_type());
             edge_groups.entry(key).or_default().push(edge_data);
         }
 
@@ -722,42 +840,31 @@ impl GraphRenderer {
             let edge_in_cycle =
                 self.is_edge_in_cycle(source_ws.name(), target_ws.name(), &cycles_ws_names);
 
-            let label = if self.show_crates {
-                // Show all crate pairs when show_crates is true
-                let pairs: Vec<String> = edges
-                    .iter()
-                    .map(|e| format!("{} → {}", e.from_crate(), e.to_crate()))
-                    .collect();
-                if pairs.len() > 1 {
-                    format!("{:?} - {} deps", dep_type, pairs.len())
-                } else {
-                    pairs[0].clone()
-                }
-            } else {
-                // When not showing crates, aggregate by type and count
-                if edges.len() > 1 {
-                    format!("{:?} - {} deps", dep_type, edges.len())
-                } else {
-                    format!("{dep_type:?}")
-                }
-            };
+            let label = self
+                .plain_edge_label(dep_type, &edges)
+                .map(|label| format!(": {label}"))
+                .unwrap_or_default();
 
             writeln_out!(
                 output,
-                "{} -> {}: {} {{",
+                "{} -> {}{} {{",
                 self.d2_id(source_ws.name()),
                 self.d2_id(target_ws.name()),
                 label
             )?;
 
             if edge_in_cycle && self.highlight_cycles {
-                writeln_out!(output, "  style.stroke: \"{}\"", colors::CYCLE_EDGE)?;
+                writeln_out!(
+                    output,
+                    "  style.stroke: \"{}\"",
+                    self.palette.cycle_edge.as_str()
+                )?;
                 writeln_out!(output, "  style.stroke-width: 3")?;
             } else {
                 let edge_color = match dep_type {
-                    DependencyType::Normal => colors::NORMAL_EDGE,
-                    DependencyType::Dev => colors::DEV_EDGE,
-                    DependencyType::Build => colors::BUILD_EDGE,
+                    DependencyType::Normal => self.palette.normal_edge.as_str(),
+                    DependencyType::Dev => self.palette.dev_edge.as_str(),
+                    DependencyType::Build => self.palette.build_edge.as_str(),
                 };
                 writeln_out!(output, "  style.stroke: \"{}\"", edge_color)?;
                 writeln_out!(output, "  style.stroke-width: 2")?;
@@ -769,6 +876,102 @@ impl GraphRenderer {
         Ok(())
     }
 
+    /// Render the graph as canonical adjacency JSON
+    ///
+    /// The output maps every workspace name to its dependency targets, each
+    /// with crate edge counts by dependency type. Workspaces and targets are
+    /// sorted and nothing depends on rendering options, so the output of an
+    /// unchanged graph is byte-for-byte stable and diffs cleanly.
+    pub fn render_adjacency(
+        &self,
+        graph: &DiGraph<WorkspaceNode, DependencyEdge>,
+        output: &mut dyn Write,
+    ) -> Result<()> {
+        let mut adjacency: BTreeMap<&str, BTreeMap<&str, AdjacencyEntry>> = graph
+            .node_indices()
+            .map(|idx| (graph[idx].name(), BTreeMap::new()))
+            .collect();
+
+        for edge in graph.edge_references() {
+            let target = graph[edge.target()].name();
+            let entry = adjacency
+                .entry(graph[edge.source()].name())
+                .or_default()
+                .entry(target)
+                .or_insert_with(|| AdjacencyEntry {
+                    target,
+                    ..Default::default()
+                });
+
+            match edge.weight().dependency_type() {
+                DependencyType::Normal => entry.normal += 1,
+                DependencyType::Build => entry.build += 1,
+                DependencyType::Dev => entry.dev += 1,
+            }
+        }
+
+        let adjacency: BTreeMap<&str, Vec<AdjacencyEntry>> = adjacency
+            .into_iter()
+            .map(|(workspace, targets)| (workspace, targets.into_values().collect()))
+            .collect();
+
+        let json = serde_json::to_string_pretty(&adjacency).map_err(FerrisWheelError::from)?;
+        writeln_out!(output, "{}", json)?;
+
+        Ok(())
+    }
+
+    /// Render a self-contained HTML page that steps through each cycle
+    /// dependency by dependency, showing why no valid build order exists
+    ///
+    /// Cycles spanning more workspaces than one loop can visit are shown as
+    /// the first simple loop found from their alphabetically first
+    /// workspace.
+    pub fn render_html(&self, cycles: &[WorkspaceCycle], output: &mut dyn Write) -> Result<()> {
+        let animated: Vec<AnimatedCycle> = cycles
+            .iter()
+            .filter_map(|cycle| {
+                let workspaces = cycle_walk(cycle)?;
+                let hops = workspaces
+                    .iter()
+                    .zip(workspaces.iter().cycle().skip(1))
+                    .map(|(from, to)| {
+                        let mut edges: Vec<String> = cycle.edges_by_direction()
+                            [&(Arc::from(from.as_str()), Arc::from(to.as_str()))]
+                            .iter()
+                            .map(|edge| format!("{} → {}", edge.from_crate(), edge.to_crate()))
+                            .collect();
+                        edges.sort();
+                        edges.dedup();
+                        AnimatedHop {
+                            from: from.clone(),
+                            to: to.clone(),
+                            edges,
+                        }
+                    })
+                    .collect();
+                Some(AnimatedCycle {
+                    id: cycle.id(),
+                    workspaces,
+                    hops,
+                })
+            })
+            .collect();
+
+        // Keep `</script>` in names from closing the script element early
+        let data = serde_json::to_string(&animated)
+            .map_err(FerrisWheelError::from)?
+            .replace("</", "<\\/");
+        write!(
+            output,
+            "{}",
+            CYCLE_ANIMATION_TEMPLATE.replace("__CYCLE_DATA__", &data)
+        )
+        .map_err(FerrisWheelError::from)?;
+
+        Ok(())
+    }
+
     pub fn render_cycle_summary(
         &self,
         cycles: &[WorkspaceCycle],
@@ -782,7 +985,7 @@ impl GraphRenderer {
         }
 
         for (i, cycle) in cycles.iter().enumerate() {
-            let severity = self.calculate_cycle_severity(cycle);
+            let severity = self.severity_model.classify(cycle);
             let severity_icon = match severity {
                 CycleSeverity::Low => "⚠️",
                 CycleSeverity::Medium => "⚠️",
@@ -822,7 +1025,7 @@ impl GraphRenderer {
             for (from_ws, to_ws) in &directions {
                 if let Some(edges) = cycle
                     .edges_by_direction()
-                    .get(&(from_ws.to_string(), to_ws.to_string()))
+                    .get(&(Arc::clone(from_ws), Arc::clone(to_ws)))
                 {
                     writeln_out!(output, "    {} → {}: {} edges", from_ws, to_ws, edges.len())?;
                 }
@@ -836,7 +1039,7 @@ impl GraphRenderer {
             for (from_ws, to_ws) in &directions {
                 if let Some(edges) = cycle
                     .edges_by_direction()
-                    .get(&(from_ws.to_string(), to_ws.to_string()))
+                    .get(&(Arc::clone(from_ws), Arc::clone(to_ws)))
                 {
                     let non_normal_edges: Vec<_> = edges
                         .iter()
@@ -867,31 +1070,17 @@ impl GraphRenderer {
                 }
             }
 
-            // If no dev/build dependencies, suggest the direction with fewer edges
-            if !suggestions_found {
-                let mut min_edges = usize::MAX;
-                let mut best_direction = None;
-
-                for (from_ws, to_ws) in &directions {
-                    if let Some(edges) = cycle
-                        .edges_by_direction()
-                        .get(&(from_ws.to_string(), to_ws.to_string()))
-                        && edges.len() < min_edges
-                    {
-                        min_edges = edges.len();
-                        best_direction = Some((from_ws, to_ws));
-                    }
-                }
-
-                if let Some((from_ws, to_ws)) = best_direction {
-                    writeln_out!(
-                        output,
-                        "     - {} → {} ({} edges total)",
-                        from_ws,
-                        to_ws,
-                        min_edges
-                    )?;
-                }
+            // If no dev/build dependencies, suggest the direction most routes
+            // around the cycle run through
+            if !suggestions_found && let Some(best) = cycle.break_points().first() {
+                writeln_out!(
+                    output,
+                    "     - {} → {} ({} edges total, betweenness {:.1})",
+                    best.from_workspace(),
+                    best.to_workspace(),
+                    best.edges().len(),
+                    best.betweenness()
+                )?;
             }
 
             writeln_out!(output)?;
@@ -909,18 +1098,258 @@ impl GraphRenderer {
         )?;
         writeln_out!(
             output,
-            "  • Break cycles at the point with the fewest dependencies"
+            "  • Break cycles at the dependency most routes run through (highest betweenness)"
+        )?;
+
+        Ok(())
+    }
+
+    /// Edge label verbosity, following `show_crates` unless set explicitly
+    fn edge_labels(&self) -> EdgeLabels {
+        self.edge_labels.unwrap_or(if self.show_crates {
+            EdgeLabels::Full
+        } else {
+            EdgeLabels::Compact
+        })
+    }
+
+    /// Label for the `edges` of one type between two workspaces in DOT and D2,
+    /// `None` when labels are off
+    fn plain_edge_label(
+        &self,
+        dep_type: DependencyType,
+        edges: &[&DependencyEdge],
+    ) -> Option<String> {
+        let label = match self.edge_labels() {
+            EdgeLabels::None => return None,
+            _ if edges.len() > 1 => format!("{} - {} deps", dep_type.as_str(), edges.len()),
+            EdgeLabels::Full => format!("{} → {}", edges[0].from_crate(), edges[0].to_crate()),
+            EdgeLabels::Compact => format!("{dep_type:?}"),
+        };
+        Some(label)
+    }
+
+    fn write_mermaid_node(
+        &self,
+        ws: &WorkspaceNode,
+        cycles_ws_names: &[Vec<Arc<str>>],
+        indent: &str,
+        output: &mut dyn Write,
+    ) -> Result<()> {
+        let in_cycle = cycles_ws_names
+            .iter()
+            .any(|cycle| cycle.iter().any(|c| &**c == ws.name()));
+
+        let node_id = self.mermaid_id(ws.name());
+        let label = if self.show_crates {
+            format!("{}\\n{} crates", ws.name(), ws.crates().len())
+        } else {
+            ws.name().to_string()
+        };
+
+        // Create tooltip text for click events
+        let tooltip = format!(
+            "Workspace: {} - Crates: {} - Total: {}",
+            ws.name(),
+            if ws.crates().len() <= 3 {
+                ws.crates().join(", ")
+            } else {
+                format!(
+                    "{}, ... ({} total)",
+                    ws.crates()[..3].join(", "),
+                    ws.crates().len()
+                )
+            },
+            ws.crates().len()
+        );
+
+        // Use different shapes based on workspace characteristics
+        let node_shape = if ws.crates().len() > 5 {
+            format!("{node_id}[\"{label}\"]") // Rectangle for large workspaces (even in cycles)
+        } else if in_cycle && self.highlight_cycles {
+            format!("{node_id}((\"{label}\"))") // Double circle for cycles
+        } else if ws.crates().len() == 1 {
+            format!("{node_id}([\"{label}\"])") // Stadium shape for single-crate workspaces
+        } else {
+            format!("{node_id}[\"{label}\"]") // Default rectangle
+        };
+        writeln_out!(output, "{}{}", indent, node_shape)?;
+        writeln_out!(output, "{}click {} \"{}\"", indent, node_id, tooltip)?;
+
+        if in_cycle && self.highlight_cycles {
+            writeln_out!(
+                output,
+                "{}style {} fill:{},stroke:{},stroke-width:3px{}",
+                indent,
+                node_id,
+                self.palette.cycle_node_fill.as_str(),
+                self.palette.cycle_node_stroke.as_str(),
+                self.mermaid_text_style()
+            )?;
+        } else {
+            writeln_out!(
+                output,
+                "{}style {} fill:{},stroke:{},stroke-width:2px{}",
+                indent,
+                node_id,
+                self.palette.normal_node_fill.as_str(),
+                self.palette.normal_node_stroke.as_str(),
+                self.mermaid_text_style()
+            )?;
+        }
+
+        Ok(())
+    }
+
+    /// Render a workspace and, as a subgraph, everything nested inside it
+    fn write_mermaid_nested(
+        &self,
+        graph: &DiGraph<WorkspaceNode, DependencyEdge>,
+        node: NodeIndex,
+        children: &HashMap<NodeIndex, Vec<NodeIndex>>,
+        cycles_ws_names: &[Vec<Arc<str>>],
+        depth: usize,
+        output: &mut dyn Write,
+    ) -> Result<()> {
+        let ws = &graph[node];
+        let indent = "    ".repeat(depth);
+
+        let Some(nested) = children.get(&node) else {
+            return self.write_mermaid_node(ws, cycles_ws_names, &indent, output);
+        };
+
+        writeln_out!(
+            output,
+            "{}subgraph {}_nest[\"{}\"]",
+            indent,
+            self.mermaid_id(ws.name()),
+            ws.name()
+        )?;
+        // A meta workspace without crates of its own is just the subgraph
+        if !ws.crates().is_empty() {
+            self.write_mermaid_node(ws, cycles_ws_names, &"    ".repeat(depth + 1), output)?;
+        }
+        for &child in nested {
+            self.write_mermaid_nested(graph, child, children, cycles_ws_names, depth + 1, output)?;
+        }
+        writeln_out!(output, "{}end", indent)?;
+
+        Ok(())
+    }
+
+    fn write_dot_node(
+        &self,
+        ws: &WorkspaceNode,
+        cycles_ws_names: &[Vec<Arc<str>>],
+        indent: &str,
+        output: &mut dyn Write,
+    ) -> Result<()> {
+        let in_cycle = cycles_ws_names
+            .iter()
+            .any(|cycle| cycle.iter().any(|c| &**c == ws.name()));
+
+        let (fill_color, stroke_color) = if in_cycle && self.highlight_cycles {
+            (
+                self.palette.cycle_node_fill.as_str(),
+                self.palette.cycle_node_stroke.as_str(),
+            )
+        } else {
+            (
+                self.palette.normal_node_fill.as_str(),
+                self.palette.normal_node_stroke.as_str(),
+            )
+        };
+
+        let label = if self.show_crates {
+            format!("{}\\n{} crates", ws.name(), ws.crates().len())
+        } else {
+            ws.name().to_string()
+        };
+
+        writeln_out!(
+            output,
+            r#"{}"{}" [label="{}", style=filled, fillcolor="{}", color="{}", penwidth=2];"#,
+            indent,
+            ws.name(),
+            label,
+            fill_color,
+            stroke_color
         )?;
 
         Ok(())
     }
 
-    fn is_edge_in_cycle(&self, from: &str, to: &str, cycles_ws_names: &[Vec<String>]) -> bool {
+    /// Render a workspace and, as a cluster, everything nested inside it
+    fn write_dot_nested(
+        &self,
+        graph: &DiGraph<WorkspaceNode, DependencyEdge>,
+        node: NodeIndex,
+        children: &HashMap<NodeIndex, Vec<NodeIndex>>,
+        cycles_ws_names: &[Vec<Arc<str>>],
+        depth: usize,
+        output: &mut dyn Write,
+    ) -> Result<()> {
+        let ws = &graph[node];
+        let indent = "    ".repeat(depth);
+
+        let Some(nested) = children.get(&node) else {
+            return self.write_dot_node(ws, cycles_ws_names, &indent, output);
+        };
+
+        writeln_out!(output, r#"{}subgraph "cluster_{}" {{"#, indent, ws.name())?;
+        writeln_out!(output, r#"{}    label="{}";"#, indent, ws.name())?;
+        if !ws.crates().is_empty() {
+            self.write_dot_node(ws, cycles_ws_names, &"    ".repeat(depth + 1), output)?;
+        }
+        for &child in nested {
+            self.write_dot_nested(graph, child, children, cycles_ws_names, depth + 1, output)?;
+        }
+        writeln_out!(output, "{}}}", indent)?;
+
+        Ok(())
+    }
+
+    /// Split workspaces into top-level ones and the children of each meta
+    /// workspace, all sorted by name
+    fn nest_workspaces_by_parent(
+        &self,
+        graph: &DiGraph<WorkspaceNode, DependencyEdge>,
+    ) -> (Vec<NodeIndex>, HashMap<NodeIndex, Vec<NodeIndex>>) {
+        let by_name: HashMap<&str, NodeIndex> = graph
+            .node_indices()
+            .map(|node| (graph[node].name(), node))
+            .collect();
+
+        let mut nodes: Vec<NodeIndex> = graph.node_indices().collect();
+        nodes.sort_by(|&a, &b| graph[a].name().cmp(graph[b].name()));
+
+        let mut top_level = Vec::new();
+        let mut children: HashMap<NodeIndex, Vec<NodeIndex>> = HashMap::new();
+        for node in nodes {
+            match graph[node].parent().and_then(|parent| by_name.get(parent)) {
+                Some(&parent) => children.entry(parent).or_default().push(node),
+                None => top_level.push(node),
+            }
+        }
+
+        (top_level, children)
+    }
+
+    fn is_edge_in_cycle(&self, from: &str, to: &str, cycles_ws_names: &[Vec<Arc<str>>]) -> bool {
         // Check if both workspaces are in the same cycle
         // This will highlight ALL edges between workspaces that are part of a cycle
         cycles_ws_names
             .iter()
-            .any(|cycle| cycle.contains(&from.to_string()) && cycle.contains(&to.to_string()))
+            .any(|cycle| cycle.iter().any(|c| &**c == from) && cycle.iter().any(|c| &**c == to))
+    }
+
+    /// `,color:…` for Mermaid node styles when the palette sets a label color
+    fn mermaid_text_style(&self) -> String {
+        self.palette
+            .text
+            .as_ref()
+            .map(|text| format!(",color:{text}"))
+            .unwrap_or_default()
     }
 
     fn mermaid_id(&self, name: &str) -> String {
@@ -930,6 +1359,10 @@ impl GraphRenderer {
             .collect()
     }
 
+    fn mermaid_crate_id(&self, workspace: &str, krate: &str) -> String {
+        format!("{}__{}", self.mermaid_id(workspace), self.mermaid_id(krate))
+    }
+
     fn d2_id(&self, name: &str) -> String {
         // D2 supports more characters, but we'll quote if necessary
         if name.contains(' ') || name.contains('-') {
@@ -938,59 +1371,47 @@ impl GraphRenderer {
             name.to_string()
         }
     }
+}
 
-    // Group workspaces by common prefix (e.g., "atlas-" groups all atlas
-    // workspaces)
-    fn group_workspaces_by_prefix(
-        &self,
-        graph: &DiGraph<WorkspaceNode, DependencyEdge>,
-    ) -> BTreeMap<String, Vec<NodeIndex>> {
-        let mut groups: BTreeMap<String, Vec<NodeIndex>> = BTreeMap::new();
-
-        for node in graph.node_indices() {
-            let ws = &graph[node];
-            // Extract prefix (everything before the first dash, or "other" if no dash)
-            let prefix = if let Some(dash_pos) = ws.name().find('-') {
-                ws.name()[..dash_pos].to_string()
-            } else if ws.name().contains("workspace") {
-                "workspace".to_string()
-            } else {
-                "other".to_string()
-            };
-
-            groups.entry(prefix).or_default().push(node);
+/// Workspaces of a simple loop through `cycle`, starting from its
+/// alphabetically first workspace
+fn cycle_walk(cycle: &WorkspaceCycle) -> Option<Vec<String>> {
+    let mut adjacency: BTreeMap<&str, Vec<&str>> = BTreeMap::new();
+    for (from, to) in cycle.edges_by_direction().keys() {
+        if from != to {
+            adjacency.entry(from).or_default().push(to);
         }
-
-        // Only keep groups with more than one workspace
-        groups.retain(|_, nodes| nodes.len() > 1);
-        groups
+    }
+    for targets in adjacency.values_mut() {
+        targets.sort_unstable();
     }
 
-    fn calculate_cycle_severity(&self, cycle: &WorkspaceCycle) -> CycleSeverity {
-        let workspace_count = cycle.workspace_names().len();
-        let edges = cycle.edges();
-
-        // Count dependency types
-        let mut normal_deps = 0;
-        let mut dev_deps = 0;
-        let mut build_deps = 0;
-
-        for edge in edges {
-            match edge.dependency_type() {
-                "Normal" => normal_deps += 1,
-                "Dev" => dev_deps += 1,
-                "Build" => build_deps += 1,
-                _ => {}
-            }
+    let start = *adjacency.keys().next()?;
+    let mut path = vec![start];
+    // Index of the next target to try from each workspace on the path
+    let mut next = vec![0];
+
+    while let Some(&current) = path.last() {
+        let depth = path.len() - 1;
+        let targets = adjacency
+            .get(current)
+            .map(Vec::as_slice)
+            .unwrap_or_default();
+        let Some(&target) = targets.get(next[depth]) else {
+            path.pop();
+            next.pop();
+            continue;
+        };
+        next[depth] += 1;
+
+        if target == start {
+            return Some(path.into_iter().map(str::to_string).collect());
         }
-
-        // Calculate severity based on workspace count and dependency types
-        if workspace_count >= 5 || (normal_deps > dev_deps + build_deps) {
-            CycleSeverity::High
-        } else if workspace_count >= 3 || normal_deps > 0 {
-            CycleSeverity::Medium
-        } else {
-            CycleSeverity::Low
+        if !path.contains(&target) {
+            path.push(target);
+            next.push(0);
         }
     }
+
+    None
 }
@@ -3,14 +3,20 @@
 //! This module contains the fundamental data structures used in the dependency
 //! graph.
 
+use std::fmt;
 use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
 
 /// Represents a workspace node in the dependency graph
 #[derive(Debug, Clone)]
 pub struct WorkspaceNode {
-    name: String,
+    name: Arc<str>,
     path: Option<PathBuf>,
     crates: Vec<String>,
+    parent: Option<String>,
 }
 
 impl WorkspaceNode {
@@ -22,6 +28,11 @@ impl WorkspaceNode {
         &self.name
     }
 
+    /// The shared copy of the name, for edges that refer to this workspace
+    pub(crate) fn interned_name(&self) -> &Arc<str> {
+        &self.name
+    }
+
     pub fn path(&self) -> Option<&Path> {
         self.path.as_deref()
     }
@@ -29,6 +40,41 @@ impl WorkspaceNode {
     pub fn crates(&self) -> &[String] {
         &self.crates
     }
+
+    /// Name of the meta workspace this workspace is nested in, if any
+    pub fn parent(&self) -> Option<&str> {
+        self.parent.as_deref()
+    }
+
+    /// Name of the workspace this node belongs to
+    ///
+    /// This is the node name for workspace nodes and the workspace part for
+    /// crate nodes named `workspace/crate`.
+    pub fn workspace_name(&self) -> &str {
+        match self.crates.as_slice() {
+            [krate] => self
+                .name
+                .strip_suffix(krate.as_str())
+                .and_then(|name| name.strip_suffix('/'))
+                .unwrap_or(&self.name),
+            _ => &self.name,
+        }
+    }
+}
+
+/// A graph edge together with the nodes it connects
+#[derive(Debug, Clone, Copy)]
+pub struct ResolvedEdge<'a> {
+    pub from: &'a WorkspaceNode,
+    pub to: &'a WorkspaceNode,
+    pub edge: &'a DependencyEdge,
+}
+
+impl ResolvedEdge<'_> {
+    /// Whether the edge connects two different workspaces
+    pub fn is_cross_workspace(&self) -> bool {
+        self.from.workspace_name() != self.to.workspace_name()
+    }
 }
 
 #[derive(Default)]
@@ -36,6 +82,7 @@ pub struct WorkspaceNodeBuilder {
     name: Option<String>,
     path: Option<PathBuf>,
     crates: Option<Vec<String>>,
+    parent: Option<String>,
 }
 
 impl WorkspaceNodeBuilder {
@@ -44,6 +91,7 @@ impl WorkspaceNodeBuilder {
             name: None,
             path: None,
             crates: None,
+            parent: None,
         }
     }
 
@@ -61,6 +109,11 @@ impl WorkspaceNodeBuilder {
         self.crates = Some(crates);
         self
     }
+
+    pub fn with_parent(mut self, parent: Option<String>) -> Self {
+        self.parent = parent;
+        self
+    }
 }
 
 impl crate::common::ConfigBuilder for WorkspaceNodeBuilder {
@@ -68,17 +121,18 @@ impl crate::common::ConfigBuilder for WorkspaceNodeBuilder {
 
     fn build(self) -> Result<Self::Config, crate::error::FerrisWheelError> {
         Ok(WorkspaceNode {
-            name: self
-                .name
-                .ok_or_else(|| crate::error::FerrisWheelError::ConfigurationError {
+            name: self.name.as_deref().map(Arc::from).ok_or_else(|| {
+                crate::error::FerrisWheelError::ConfigurationError {
                     message: "Missing required field: name".to_string(),
-                })?,
+                }
+            })?,
             path: self.path,
             crates: self.crates.ok_or_else(|| {
                 crate::error::FerrisWheelError::ConfigurationError {
                     message: "Missing required field: crates".to_string(),
                 }
             })?,
+            parent: self.parent,
         })
     }
 }
@@ -86,10 +140,10 @@ impl crate::common::ConfigBuilder for WorkspaceNodeBuilder {
 /// Represents a dependency edge between crates
 #[derive(Debug, Clone)]
 pub struct DependencyEdge {
-    from_crate: String,
-    to_crate: String,
+    from_crate: Arc<str>,
+    to_crate: Arc<str>,
     dependency_type: DependencyType,
-    target: Option<String>,
+    target: Option<Arc<str>>,
 }
 
 impl DependencyEdge {
@@ -97,6 +151,22 @@ impl DependencyEdge {
         DependencyEdgeBuilder::new()
     }
 
+    /// An edge between crates whose names are already interned, for graph
+    /// builds that would otherwise copy the same name for every dependency
+    pub(crate) fn from_interned(
+        from_crate: Arc<str>,
+        to_crate: Arc<str>,
+        dependency_type: DependencyType,
+        target: Option<Arc<str>>,
+    ) -> Self {
+        Self {
+            from_crate,
+            to_crate,
+            dependency_type,
+            target,
+        }
+    }
+
     pub fn from_crate(&self) -> &str {
         &self.from_crate
     }
@@ -105,6 +175,11 @@ impl DependencyEdge {
         &self.to_crate
     }
 
+    /// The shared copies of the crate names, for edges derived from this one
+    pub(crate) fn interned_crates(&self) -> (&Arc<str>, &Arc<str>) {
+        (&self.from_crate, &self.to_crate)
+    }
+
     pub fn dependency_type(&self) -> &DependencyType {
         &self.dependency_type
     }
@@ -115,10 +190,10 @@ impl DependencyEdge {
 }
 
 pub struct DependencyEdgeBuilder {
-    from_crate: Option<String>,
-    to_crate: Option<String>,
+    from_crate: Option<Arc<str>>,
+    to_crate: Option<Arc<str>>,
     dependency_type: Option<DependencyType>,
-    target: Option<String>,
+    target: Option<Arc<str>>,
 }
 
 impl Default for DependencyEdgeBuilder {
@@ -138,12 +213,12 @@ impl DependencyEdgeBuilder {
     }
 
     pub fn with_from_crate(mut self, from_crate: &str) -> Self {
-        self.from_crate = Some(from_crate.to_string());
+        self.from_crate = Some(Arc::from(from_crate));
         self
     }
 
     pub fn with_to_crate(mut self, to_crate: &str) -> Self {
-        self.to_crate = Some(to_crate.to_string());
+        self.to_crate = Some(Arc::from(to_crate));
         self
     }
 
@@ -152,8 +227,8 @@ impl DependencyEdgeBuilder {
         self
     }
 
-    pub fn with_target(mut self, target: Option<String>) -> Self {
-        self.target = target;
+    pub fn with_target(mut self, target: Option<&str>) -> Self {
+        self.target = target.map(Arc::from);
         self
     }
 }
@@ -184,9 +259,88 @@ impl crate::common::ConfigBuilder for DependencyEdgeBuilder {
 }
 
 /// Type of dependency relationship
-#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
 pub enum DependencyType {
     Normal,
     Dev,
     Build,
 }
+
+impl DependencyType {
+    /// Every dependency type, in report order
+    pub const ALL: [DependencyType; 3] = [
+        DependencyType::Normal,
+        DependencyType::Dev,
+        DependencyType::Build,
+    ];
+
+    /// The name of the variant, as shown in reports
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DependencyType::Normal => "Normal",
+            DependencyType::Dev => "Dev",
+            DependencyType::Build => "Build",
+        }
+    }
+
+    /// The dependency type named `name`, as shown in reports, in any case
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|kind| kind.as_str().eq_ignore_ascii_case(name))
+    }
+
+    /// The manifest table declaring dependencies of this type
+    pub fn manifest_table(&self) -> &'static str {
+        match self {
+            DependencyType::Normal => "dependencies",
+            DependencyType::Dev => "dev-dependencies",
+            DependencyType::Build => "build-dependencies",
+        }
+    }
+}
+
+/// A crate dependency that does not exist yet, written `from-crate:to-crate`
+///
+/// Used to ask what would happen to the graph if the dependency were added.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HypotheticalEdge {
+    from_crate: String,
+    to_crate: String,
+}
+
+impl HypotheticalEdge {
+    pub fn new(from_crate: impl Into<String>, to_crate: impl Into<String>) -> Self {
+        Self {
+            from_crate: from_crate.into(),
+            to_crate: to_crate.into(),
+        }
+    }
+
+    pub fn from_crate(&self) -> &str {
+        &self.from_crate
+    }
+
+    pub fn to_crate(&self) -> &str {
+        &self.to_crate
+    }
+}
+
+impl FromStr for HypotheticalEdge {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.split_once(':') {
+            Some((from, to)) if !from.trim().is_empty() && !to.trim().is_empty() => {
+                Ok(Self::new(from.trim(), to.trim()))
+            }
+            _ => Err(format!("expected FROM_CRATE:TO_CRATE, got '{s}'")),
+        }
+    }
+}
+
+impl fmt::Display for HypotheticalEdge {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} → {}", self.from_crate, self.to_crate)
+    }
+}
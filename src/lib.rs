@@ -10,6 +10,8 @@
 //!   dependencies
 //! - **Detector**: Implements cycle detection algorithms (Tarjan's SCC)
 //! - **Graph**: Builds and manages the dependency graph representation
+//! - **Dependency filter**: Composable rules deciding which dependencies become
+//!   graph edges
 //! - **Reports**: Generates human-readable and machine-readable reports
 //!
 //! ## Usage
@@ -22,7 +24,9 @@
 //! use cargo_ferris_wheel::analyzer::WorkspaceAnalyzer;
 //! use cargo_ferris_wheel::detector::CycleDetector;
 //! use cargo_ferris_wheel::graph::DependencyGraphBuilder;
-//! use cargo_ferris_wheel::reports::{HumanReportGenerator, JsonReportGenerator, ReportGenerator};
+//! use cargo_ferris_wheel::reports::{
+//!     HumanReportGenerator, JsonReportGenerator, ReportGenerator, ReportVerbosity,
+//! };
 //! use miette::IntoDiagnostic;
 //!
 //! # fn main() -> miette::Result<()> {
@@ -60,13 +64,14 @@
 //!     );
 //!
 //!     // Human-readable report for console output
-//!     let human_report = HumanReportGenerator::new(Some(5)); // show max 5 cycles
+//!     let human_report = HumanReportGenerator::new().with_verbosity(ReportVerbosity::Cycles);
 //!     println!("{}", human_report.generate_report(&detector)?);
 //!
-//!     // JSON report for programmatic processing
+//!     // JSON report for programmatic processing, written straight into the
+//!     // file rather than built up in memory first
 //!     let json_report = JsonReportGenerator::new();
-//!     let json_output = json_report.generate_report(&detector)?;
-//!     std::fs::write("cycles.json", json_output).into_diagnostic()?;
+//!     let mut file = std::fs::File::create("cycles.json").into_diagnostic()?;
+//!     json_report.write_report(&detector, &mut file)?;
 //! } else {
 //!     println!("✅ No circular dependencies found!");
 //! }
@@ -129,6 +134,8 @@
 //! ### Example: Filtering Dependencies
 //!
 //! ```no_run
+//! use cargo_ferris_wheel::dependency_filter::DependencyFilter;
+//! use cargo_ferris_wheel::graph::DependencyType;
 //! # use std::path::PathBuf;
 //! # use cargo_ferris_wheel::{
 //! #     analyzer::WorkspaceAnalyzer,
@@ -138,12 +145,13 @@
 //! # fn main() -> miette::Result<()> {
 //! # let mut analyzer = WorkspaceAnalyzer::new();
 //! # analyzer.discover_workspaces(&[PathBuf::from(".")], None)?;
-//! // Check only production dependencies (exclude dev and build deps)
-//! let mut graph_builder = DependencyGraphBuilder::new(
-//!     true,  // exclude dev dependencies
-//!     true,  // exclude build dependencies
-//!     false, // include target-specific dependencies
-//! );
+//! // Check only production dependencies (exclude dev and build deps), and
+//! // ignore the known dependency of `app` on `legacy`
+//! let filter = DependencyFilter::all()
+//!     .exclude_kind(DependencyType::Dev)
+//!     .exclude_kind(DependencyType::Build)
+//!     .exclude_workspace_pair("app", "legacy");
+//! let mut graph_builder = DependencyGraphBuilder::filtered(filter);
 //!
 //! graph_builder.build_cross_workspace_graph(
 //!     analyzer.workspaces(),
@@ -192,7 +200,8 @@
 //!     .filter(|cycle| {
 //!         cycle
 //!             .workspace_names()
-//!             .contains(&target_workspace.to_string())
+//!             .iter()
+//!             .any(|name| &**name == target_workspace)
 //!     })
 //!     .collect();
 //!
@@ -219,13 +228,56 @@
 //! # Ok(())
 //! # }
 //! ```
+//!
+//! ### Example: Walking the Analyzed Model
+//!
+//! ```no_run
+//! # use std::path::PathBuf;
+//! # use cargo_ferris_wheel::{analyzer::WorkspaceAnalyzer, graph::DependencyGraphBuilder};
+//! # fn main() -> miette::Result<()> {
+//! # let mut analyzer = WorkspaceAnalyzer::new();
+//! # analyzer.discover_workspaces(&[PathBuf::from(".")], None)?;
+//! // List every crate with the workspace it belongs to
+//! for krate in analyzer.crates() {
+//!     println!("{} in {}", krate.member.name(), krate.workspace.name());
+//! }
+//!
+//! // Crates outside any `[workspace]`
+//! let standalone = analyzer.standalone_crates().count();
+//! println!("{standalone} standalone crates");
+//!
+//! // Crate dependencies that cross a workspace boundary
+//! let mut graph_builder = DependencyGraphBuilder::new(false, false, false);
+//! graph_builder.build_crate_graph(
+//!     analyzer.workspaces(),
+//!     analyzer.crate_to_workspace(),
+//!     analyzer.crate_path_to_workspace(),
+//!     analyzer.crate_to_paths(),
+//!     None,
+//! )?;
+//!
+//! for resolved in graph_builder.cross_workspace_edges() {
+//!     println!(
+//!         "{}/{} → {}/{}",
+//!         resolved.from.workspace_name(),
+//!         resolved.edge.from_crate(),
+//!         resolved.to.workspace_name(),
+//!         resolved.edge.to_crate()
+//!     );
+//! }
+//! # Ok(())
+//! # }
+//! ```
 
 // Private modules
 mod constants;
-mod dependency_filter;
+mod git;
+mod manifest_edit;
 mod progress;
+mod timings;
 mod toml_parser;
 mod utils;
+mod watch;
 mod workspace_discovery;
 
 // Public modules
@@ -235,6 +287,7 @@ pub mod commands;
 pub mod common;
 pub mod config;
 pub mod core;
+pub mod dependency_filter;
 pub mod detector;
 pub mod error;
 pub mod executors;
@@ -243,13 +296,49 @@ pub mod reports;
 
 // Main entry point for the library
 pub fn run() -> miette::Result<()> {
-    use clap::Parser;
+    use clap::FromArgMatches;
+    use miette::{IntoDiagnostic, WrapErr};
 
     use crate::cli::{CargoArgs, CargoCommand};
     use crate::commands::execute_command;
+    use crate::config::apply_project_defaults;
+
+    let matches = CargoArgs::command_with_env().get_matches();
+    let cargo_args = CargoArgs::from_arg_matches(&matches).unwrap_or_else(|e| e.exit());
+    let CargoCommand::FerrisWheel(mut cli) = cargo_args.command;
+    if cli.no_color {
+        disable_colors();
+    }
+
+    // Settings left unset on the command line come from the project's
+    // `[defaults]`; the commands reuse the configuration loaded for them
+    let project = match matches
+        .subcommand()
+        .and_then(|(_, ferris_wheel)| ferris_wheel.subcommand())
+        .map(|(_, command)| command)
+    {
+        Some(command_matches) => apply_project_defaults(&mut cli.command, command_matches)?,
+        None => None,
+    };
 
-    let cargo_args = CargoArgs::parse();
-    let CargoCommand::FerrisWheel(cli) = cargo_args.command;
+    if let Some(json) = cli.timings {
+        timings::enable(json);
+    }
+    let result = execute_command(cli.command, project.as_ref());
+
+    timings::report()
+        .into_diagnostic()
+        .wrap_err("Failed to write timings")?;
+
+    result
+}
 
-    execute_command(cli.command)
+/// Turn colors off in reports, progress output and error diagnostics
+fn disable_colors() {
+    console::set_colors_enabled(false);
+    console::set_colors_enabled_stderr(false);
+    // Ignored if a hook is already installed, e.g. by an embedding binary
+    let _ = miette::set_hook(Box::new(|_| {
+        Box::new(miette::MietteHandlerOpts::new().color(false).build())
+    }));
 }
@@ -0,0 +1,283 @@
+//! Format-preserving edits to dependency declarations in Cargo manifests
+//!
+//! Used by the `fix` command to turn suggested break points into patches.
+//! Edits keep the comments, ordering and formatting of everything they do
+//! not touch, so the resulting diffs stay reviewable.
+
+use similar::{DiffTag, TextDiff};
+use toml_edit::{DocumentMut, InlineTable, Item, TableLike, Value};
+
+use crate::graph::DependencyType;
+
+/// A change to the declarations of one dependency
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DependencyEdit {
+    /// Delete the declaration
+    Remove,
+    /// Move the declaration to the `[dev-dependencies]` table of the same
+    /// target
+    MoveToDev,
+    /// Mark the dependency `optional` and add a feature of the same name
+    /// enabling it
+    FeatureGate,
+}
+
+/// Apply `edit` to every declaration of the package `dependency` in the
+/// dependency tables of kind `kind`, including target-specific tables
+///
+/// Declarations are matched by key or by their `package` field, so renamed
+/// dependencies are found. Returns whether any declaration was changed.
+pub fn apply_edit(
+    document: &mut DocumentMut,
+    dependency: &str,
+    kind: &DependencyType,
+    edit: DependencyEdit,
+) -> bool {
+    let table = table_name(kind);
+
+    let mut keys = edit_scope(document.as_table_mut(), dependency, table, edit);
+    if let Some(targets) = document.get_mut("target").and_then(Item::as_table_like_mut) {
+        for (_, target) in targets.iter_mut() {
+            if let Some(scope) = target.as_table_like_mut() {
+                keys.extend(edit_scope(scope, dependency, table, edit));
+            }
+        }
+    }
+
+    if edit == DependencyEdit::FeatureGate && !keys.is_empty() {
+        let features = document
+            .entry("features")
+            .or_insert(toml_edit::table())
+            .as_table_like_mut();
+        if let Some(features) = features {
+            for key in &keys {
+                if !features.contains_key(key) {
+                    let mut enables = toml_edit::Array::new();
+                    enables.push(format!("dep:{key}"));
+                    features.insert(key, toml_edit::value(enables));
+                }
+            }
+        }
+    }
+
+    !keys.is_empty()
+}
+
+/// Apply `edit` within one scope (the manifest root or a `[target.*]`
+/// table), returning the keys of the edited declarations
+fn edit_scope(
+    scope: &mut dyn TableLike,
+    dependency: &str,
+    table: &str,
+    edit: DependencyEdit,
+) -> Vec<String> {
+    let Some(dependencies) = scope.get_mut(table).and_then(Item::as_table_like_mut) else {
+        return Vec::new();
+    };
+
+    let keys: Vec<String> = dependencies
+        .iter()
+        .filter(|(key, item)| declares(key, item, dependency))
+        .map(|(key, _)| key.to_string())
+        .collect();
+
+    let mut moved = Vec::new();
+    for key in &keys {
+        match edit {
+            DependencyEdit::Remove => {
+                dependencies.remove(key);
+            }
+            DependencyEdit::MoveToDev => {
+                if let Some(item) = dependencies.remove(key) {
+                    moved.push((key, item));
+                }
+            }
+            DependencyEdit::FeatureGate => {
+                if let Some(item) = dependencies.get_mut(key) {
+                    make_optional(item);
+                }
+            }
+        }
+    }
+
+    if !moved.is_empty()
+        && let Some(dev) = scope
+            .entry(table_name(&DependencyType::Dev))
+            .or_insert(toml_edit::table())
+            .as_table_like_mut()
+    {
+        for (key, item) in moved {
+            // A test-only declaration of the same dependency wins
+            if !dev.contains_key(key) {
+                dev.insert(key, item);
+            }
+        }
+    }
+
+    keys
+}
+
+/// Whether the entry `key = item` declares the package `dependency`
+fn declares(key: &str, item: &Item, dependency: &str) -> bool {
+    let package = item
+        .as_table_like()
+        .and_then(|table| table.get("package"))
+        .and_then(Item::as_str);
+    package.unwrap_or(key) == dependency
+}
+
+fn make_optional(item: &mut Item) {
+    match item {
+        Item::Value(Value::InlineTable(table)) => {
+            // Keep the padding before the closing brace after the new entry
+            let padding = table.iter_mut().last().and_then(|(_, value)| {
+                let padding = value.decor().suffix().cloned();
+                value.decor_mut().set_suffix("");
+                padding
+            });
+            let mut optional = Value::from(true);
+            optional.decor_mut().set_prefix(" ");
+            if let Some(padding) = padding {
+                optional.decor_mut().set_suffix(padding);
+            }
+            table.insert("optional", optional);
+        }
+        Item::Value(Value::String(version)) => {
+            let mut table = InlineTable::new();
+            table.insert("version", version.value().as_str().into());
+            table.insert("optional", true.into());
+            table.fmt();
+            *item = Item::Value(Value::InlineTable(table));
+        }
+        Item::Table(table) => {
+            table.insert("optional", toml_edit::value(true));
+        }
+        _ => {}
+    }
+}
+
+fn table_name(kind: &DependencyType) -> &'static str {
+    match kind {
+        DependencyType::Normal => "dependencies",
+        DependencyType::Dev => "dev-dependencies",
+        DependencyType::Build => "build-dependencies",
+    }
+}
+
+/// Render the changes from `old` to `new` as a unified diff of `path`, as
+/// `git apply` expects it, or an empty string when they are equal
+pub fn unified_diff(path: &str, old: &str, new: &str) -> String {
+    if old == new {
+        return String::new();
+    }
+
+    TextDiff::from_lines(old, new)
+        .unified_diff()
+        .header(&format!("a/{path}"), &format!("b/{path}"))
+        .to_string()
+}
+
+/// The 1-based line of `old` that is the first one deleted or replaced in
+/// `new`
+pub fn first_changed_line(old: &str, new: &str) -> Option<usize> {
+    TextDiff::from_lines(old, new)
+        .ops()
+        .iter()
+        .find(|op| matches!(op.tag(), DiffTag::Delete | DiffTag::Replace))
+        .map(|op| op.old_range().start + 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MANIFEST: &str = r#"[package]
+name = "api"
+
+[dependencies]
+# Shared types
+core = { path = "../core" }
+serde = "1"
+renamed = { package = "utils", path = "../utils" }
+
+[target.'cfg(unix)'.dependencies]
+core = { path = "../core" }
+
+[dev-dependencies]
+core = { path = "../core", features = ["testing"] }
+"#;
+
+    fn edit(dependency: &str, kind: DependencyType, edit: DependencyEdit) -> String {
+        let mut document: DocumentMut = MANIFEST.parse().unwrap();
+        assert!(apply_edit(&mut document, dependency, &kind, edit));
+        document.to_string()
+    }
+
+    #[test]
+    fn test_remove_keeps_the_rest_of_the_manifest() {
+        let edited = edit("core", DependencyType::Dev, DependencyEdit::Remove);
+        assert_eq!(
+            edited,
+            MANIFEST.replace(
+                "core = { path = \"../core\", features = [\"testing\"] }\n",
+                ""
+            )
+        );
+
+        let edited = edit("utils", DependencyType::Normal, DependencyEdit::Remove);
+        assert!(!edited.contains("renamed"));
+        assert!(edited.contains("serde = \"1\""));
+
+        let mut document: DocumentMut = MANIFEST.parse().unwrap();
+        assert!(!apply_edit(
+            &mut document,
+            "missing",
+            &DependencyType::Normal,
+            DependencyEdit::Remove
+        ));
+    }
+
+    #[test]
+    fn test_move_to_dev() {
+        let edited = edit("serde", DependencyType::Normal, DependencyEdit::MoveToDev);
+        assert!(!edited.contains("\nserde = \"1\"\n\n"));
+        assert!(edited.ends_with(
+            "[dev-dependencies]\ncore = { path = \"../core\", features = [\"testing\"] }\nserde = \
+             \"1\"\n"
+        ));
+
+        // Target-specific declarations move within their target, and an
+        // existing dev declaration is kept as is
+        let edited = edit("core", DependencyType::Normal, DependencyEdit::MoveToDev);
+        assert_eq!(edited.matches("core =").count(), 2);
+        assert!(
+            edited
+                .contains("[target.'cfg(unix)'.dev-dependencies]\ncore = { path = \"../core\" }\n")
+        );
+        assert!(edited.ends_with(
+            "[dev-dependencies]\ncore = { path = \"../core\", features = [\"testing\"] }\n"
+        ));
+    }
+
+    #[test]
+    fn test_feature_gate() {
+        let edited = edit("core", DependencyType::Normal, DependencyEdit::FeatureGate);
+        assert!(edited.contains("core = { path = \"../core\", optional = true }"));
+        assert!(edited.contains("core = { path = \"../core\", features = [\"testing\"] }"));
+        assert!(edited.ends_with("[features]\ncore = [\"dep:core\"]\n"));
+
+        let edited = edit("serde", DependencyType::Normal, DependencyEdit::FeatureGate);
+        assert!(edited.contains("serde = { version = \"1\", optional = true }"));
+    }
+
+    #[test]
+    fn test_diff_helpers() {
+        let edited = edit("serde", DependencyType::Normal, DependencyEdit::Remove);
+
+        assert_eq!(first_changed_line(MANIFEST, &edited), Some(7));
+        let diff = unified_diff("api/Cargo.toml", MANIFEST, &edited);
+        assert!(diff.starts_with("--- a/api/Cargo.toml\n+++ b/api/Cargo.toml\n@@ -4,7 +4,6 @@\n"));
+        assert!(diff.contains("\n-serde = \"1\"\n"));
+        assert_eq!(unified_diff("api/Cargo.toml", MANIFEST, MANIFEST), "");
+    }
+}
@@ -14,6 +14,7 @@ const SPINNER_TEMPLATE: &str = "{spinner:.cyan} {msg}";
 pub struct ProgressReporter {
     term: Term,
     spinner_position: AtomicUsize,
+    workspaces_found: AtomicUsize,
     multi_progress: MultiProgress,
     current_bar: Option<ProgressBar>,
 }
@@ -30,6 +31,7 @@ impl ProgressReporter {
         Self {
             term,
             spinner_position: AtomicUsize::new(0),
+            workspaces_found: AtomicUsize::new(0),
             multi_progress: MultiProgress::new(),
             current_bar: None,
         }
@@ -74,13 +76,27 @@ impl ProgressReporter {
     }
 
     pub fn checking_manifest(&self, path: &Path) {
+        self.discovery_status("Checking", path);
+    }
+
+    /// Report that discovery has found `count` workspace roots so far, the
+    /// latest at `path`
+    pub fn found_workspaces(&self, count: usize, path: &Path) {
+        self.workspaces_found.store(count, Ordering::Relaxed);
+        self.discovery_status("Found", path);
+    }
+
+    fn discovery_status(&self, action: &str, path: &Path) {
+        let found = self.workspaces_found.load(Ordering::Relaxed);
+        let found = format!("[{found} found]");
         if let Some(ref pb) = self.current_bar {
-            pb.set_message(format!("Checking: {}...", path.display()));
+            pb.set_message(format!("{found} {action}: {}...", path.display()));
         } else {
             let _ = self.term.clear_line();
             eprint!(
-                "\r{} Checking: {}... ",
+                "\r{} {} {action}: {}... ",
                 style(self.get_ferris_wheel_frame()).cyan(),
+                style(found).yellow(),
                 style(path.display()).dim()
             );
         }
@@ -112,6 +128,14 @@ impl ProgressReporter {
         }
     }
 
+    /// Clear the discovery spinner when discovery fails
+    pub fn abort_discovery(&mut self) {
+        if let Some(pb) = self.current_bar.take() {
+            pb.finish_and_clear();
+        }
+        let _ = self.term.clear_line();
+    }
+
     pub fn start_cycle_detection(&mut self) {
         eprintln!("\n{} Detecting dependency cycles...", style("🔄").yellow());
     }
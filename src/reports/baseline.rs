@@ -0,0 +1,148 @@
+//! Cycle baselines
+//!
+//! A baseline is a JSON file listing the IDs of cycles that are known and
+//! accepted for now. Running `inspect --baseline` against it only fails on
+//! cycles that are not listed, so a large repository with existing cycles can
+//! adopt the check without fixing everything first. Since a cycle's ID changes
+//! when its crate edges change, a known cycle that grows counts as new.
+
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::detector::WorkspaceCycle;
+use crate::error::FerrisWheelError;
+
+const BASELINE_VERSION: u32 = 1;
+
+/// One known cycle
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BaselineCycle {
+    /// Stable cycle ID, see [`WorkspaceCycle::id`]
+    pub id: String,
+    /// Workspaces in the cycle, for readers of the file
+    pub workspaces: Vec<String>,
+}
+
+/// A set of known cycles
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Baseline {
+    pub version: u32,
+    pub cycles: Vec<BaselineCycle>,
+}
+
+impl Baseline {
+    /// Record `cycles` as known, sorted by ID so the file diffs cleanly
+    pub fn from_cycles(cycles: &[WorkspaceCycle]) -> Self {
+        let mut cycles: Vec<BaselineCycle> = cycles
+            .iter()
+            .map(|cycle| {
+                let mut workspaces: Vec<String> = cycle
+                    .workspace_names()
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect();
+                workspaces.sort();
+                BaselineCycle {
+                    id: cycle.id(),
+                    workspaces,
+                }
+            })
+            .collect();
+        cycles.sort_by(|a, b| a.id.cmp(&b.id));
+        cycles.dedup_by(|a, b| a.id == b.id);
+
+        Self {
+            version: BASELINE_VERSION,
+            cycles,
+        }
+    }
+
+    pub fn load(path: &Path) -> Result<Self, FerrisWheelError> {
+        let content = fs::read_to_string(path).map_err(|e| FerrisWheelError::FileReadError {
+            path: path.to_path_buf(),
+            source: e,
+        })?;
+        Ok(serde_json::from_str(&content)?)
+    }
+
+    /// Write the baseline, creating parent directories if needed
+    pub fn write(&self, path: &Path) -> Result<(), FerrisWheelError> {
+        if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, format!("{}\n", serde_json::to_string_pretty(self)?))?;
+        Ok(())
+    }
+
+    /// Whether `cycle` is a known cycle
+    pub fn contains(&self, cycle: &WorkspaceCycle) -> bool {
+        let id = cycle.id();
+        self.cycles.iter().any(|known| known.id == id)
+    }
+
+    /// Known cycles that no longer occur in `cycles`
+    pub fn fixed<'a>(&'a self, cycles: &[WorkspaceCycle]) -> Vec<&'a BaselineCycle> {
+        let current: BTreeSet<String> = cycles.iter().map(WorkspaceCycle::id).collect();
+        self.cycles
+            .iter()
+            .filter(|known| !current.contains(&known.id))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tempfile::TempDir;
+
+    use super::*;
+    use crate::detector::CycleEdge;
+
+    fn cycle(from: &str, to: &str) -> WorkspaceCycle {
+        WorkspaceCycle::from(vec![
+            CycleEdge::builder()
+                .from_workspace(from)
+                .to_workspace(to)
+                .from_crate(&format!("{from}-lib"))
+                .to_crate(&format!("{to}-lib"))
+                .dependency_type("Normal")
+                .build()
+                .unwrap(),
+            CycleEdge::builder()
+                .from_workspace(to)
+                .to_workspace(from)
+                .from_crate(&format!("{to}-lib"))
+                .to_crate(&format!("{from}-lib"))
+                .dependency_type("Dev")
+                .build()
+                .unwrap(),
+        ])
+    }
+
+    #[test]
+    fn test_baseline_round_trip() {
+        let temp = TempDir::new().unwrap();
+        let path = temp.path().join("ci/ferris-baseline.json");
+        let baseline = Baseline::from_cycles(&[cycle("b", "a"), cycle("c", "d")]);
+        assert_eq!(baseline.cycles[0].workspaces.len(), 2);
+
+        baseline.write(&path).unwrap();
+        assert_eq!(Baseline::load(&path).unwrap(), baseline);
+    }
+
+    #[test]
+    fn test_baseline_matches_cycles_by_id() {
+        let baseline = Baseline::from_cycles(&[cycle("a", "b"), cycle("c", "d")]);
+
+        assert!(baseline.contains(&cycle("a", "b")));
+        // The same workspaces with different crate edges are a different cycle
+        assert!(!baseline.contains(&cycle("b", "a")));
+        assert!(!baseline.contains(&cycle("a", "e")));
+
+        let fixed = baseline.fixed(&[cycle("a", "b")]);
+        assert_eq!(fixed.len(), 1);
+        assert_eq!(fixed[0].workspaces, vec!["c", "d"]);
+    }
+}
@@ -0,0 +1,259 @@
+//! Analysis event stream
+//!
+//! With `--events <PATH>`, `inspect` writes one JSON object per line to a
+//! UNIX socket, a named pipe or a plain file while the analysis runs, so
+//! local tools (editor plugins, task runners, dev daemons) can follow results
+//! as they happen instead of polling. Each line is written and flushed as
+//! soon as its step completes, with one `cycle_found` per cycle as the
+//! cycle's component is searched. Each event carries an `event` tag:
+//!
+//! ```json
+//! {"event":"analysis_started","paths":["."]}
+//! {"event":"workspaces_discovered","count":12}
+//! {"event":"cycle_found","workspaces":["a","b"],"severity":"high","failing":true}
+//! {"event":"analysis_finished","grade":"C","score":74.0,"cycle_count":1,"layer_violation_count":0,"partial":false,"duration_ms":812}
+//! ```
+//!
+//! The stream is best effort: if the subscriber goes away, the analysis
+//! carries on without it.
+
+use std::fs::OpenOptions;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+use serde::Serialize;
+
+use crate::detector::{CycleDetector, CycleSeverity, Grade, WorkspaceCycle};
+use crate::error::FerrisWheelError;
+
+/// One step of an `inspect` run
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum AnalysisEvent {
+    AnalysisStarted {
+        paths: Vec<PathBuf>,
+    },
+    WorkspacesDiscovered {
+        count: usize,
+    },
+    CycleFound(CycleEvent),
+    AnalysisFinished {
+        grade: Grade,
+        score: f64,
+        cycle_count: usize,
+        /// Total crate-level dependencies pointing to a higher layer
+        layer_violation_count: usize,
+        /// Whether the run stopped early and results may be incomplete
+        partial: bool,
+        duration_ms: u64,
+    },
+}
+
+impl AnalysisEvent {
+    /// Report `cycle`, rated by `detector`
+    pub fn cycle_found(detector: &CycleDetector, cycle: &WorkspaceCycle) -> Self {
+        Self::CycleFound(CycleEvent {
+            workspaces: cycle
+                .workspace_names()
+                .iter()
+                .map(ToString::to_string)
+                .collect(),
+            severity: detector.severity(cycle),
+            failing: detector.is_failing(cycle),
+        })
+    }
+}
+
+/// A cycle as reported in the event stream
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct CycleEvent {
+    pub workspaces: Vec<String>,
+    pub severity: CycleSeverity,
+    /// Whether the cycle is severe enough to fail the run
+    pub failing: bool,
+}
+
+/// Destination of the event stream
+pub struct EventSink {
+    path: PathBuf,
+    writer: Box<dyn Write>,
+}
+
+impl EventSink {
+    /// Connect to a UNIX socket at `path`, or open a named pipe or file there
+    /// for appending
+    ///
+    /// Opening a named pipe blocks until a reader has it open.
+    pub fn open(path: &Path) -> Result<Self, FerrisWheelError> {
+        Ok(Self {
+            path: path.to_path_buf(),
+            writer: connect(path)?,
+        })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Write one event as a JSON line and flush it
+    pub fn emit(&mut self, event: &AnalysisEvent) -> Result<(), FerrisWheelError> {
+        let mut line = serde_json::to_string(event)?;
+        line.push('\n');
+        self.writer.write_all(line.as_bytes())?;
+        self.writer.flush()?;
+        Ok(())
+    }
+}
+
+#[cfg(unix)]
+fn connect(path: &Path) -> io::Result<Box<dyn Write>> {
+    use std::os::unix::fs::FileTypeExt;
+    use std::os::unix::net::UnixStream;
+
+    let is_socket = std::fs::metadata(path)
+        .map(|metadata| metadata.file_type().is_socket())
+        .unwrap_or(false);
+    if is_socket {
+        return Ok(Box::new(UnixStream::connect(path)?));
+    }
+
+    append(path)
+}
+
+#[cfg(not(unix))]
+fn connect(path: &Path) -> io::Result<Box<dyn Write>> {
+    append(path)
+}
+
+fn append(path: &Path) -> io::Result<Box<dyn Write>> {
+    Ok(Box::new(
+        OpenOptions::new().create(true).append(true).open(path)?,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{BufRead, BufReader};
+
+    use super::*;
+
+    #[test]
+    fn test_events_are_tagged_json_lines() {
+        let temp = tempfile::tempdir().unwrap();
+        let path = temp.path().join("events.jsonl");
+
+        let mut sink = EventSink::open(&path).unwrap();
+        sink.emit(&AnalysisEvent::AnalysisStarted {
+            paths: vec![PathBuf::from(".")],
+        })
+        .unwrap();
+        sink.emit(&AnalysisEvent::WorkspacesDiscovered { count: 3 })
+            .unwrap();
+
+        let contents = std::fs::read_to_string(&path).unwrap();
+        let lines: Vec<_> = contents.lines().collect();
+        assert_eq!(
+            lines,
+            vec![
+                r#"{"event":"analysis_started","paths":["."]}"#,
+                r#"{"event":"workspaces_discovered","count":3}"#,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_cycles_are_streamed_while_detection_runs() {
+        use petgraph::graph::DiGraph;
+
+        use crate::common::ConfigBuilder;
+        use crate::graph::{DependencyEdge, DependencyType, WorkspaceNode};
+
+        // Two separate two-workspace cycles
+        let mut graph = DiGraph::new();
+        let nodes: Vec<_> = ["a", "b", "c", "d"]
+            .iter()
+            .map(|name| {
+                graph.add_node(
+                    WorkspaceNode::builder()
+                        .with_name(name.to_string())
+                        .with_crates(vec![format!("{name}-lib")])
+                        .build()
+                        .unwrap(),
+                )
+            })
+            .collect();
+        for (from, to) in [(0, 1), (1, 0), (2, 3), (3, 2)] {
+            graph.add_edge(
+                nodes[from],
+                nodes[to],
+                DependencyEdge::builder()
+                    .with_from_crate(&format!("{}-lib", graph[nodes[from]].name()))
+                    .with_to_crate(&format!("{}-lib", graph[nodes[to]].name()))
+                    .with_dependency_type(DependencyType::Normal)
+                    .build()
+                    .unwrap(),
+            );
+        }
+
+        let temp = tempfile::tempdir().unwrap();
+        let path = temp.path().join("events.jsonl");
+        let mut sink = EventSink::open(&path).unwrap();
+        let mut reader = BufReader::new(std::fs::File::open(&path).unwrap());
+        let mut next_record = || {
+            let mut line = String::new();
+            reader.read_line(&mut line).unwrap();
+            serde_json::from_str::<serde_json::Value>(&line).unwrap()
+        };
+
+        sink.emit(&AnalysisEvent::AnalysisStarted {
+            paths: vec![PathBuf::from(".")],
+        })
+        .unwrap();
+        assert_eq!(next_record()["event"], "analysis_started");
+
+        // Each cycle is readable before detection moves on
+        let mut seen = Vec::new();
+        let mut detector = CycleDetector::new();
+        detector
+            .detect_cycles_with(&graph, |detector, cycle| {
+                sink.emit(&AnalysisEvent::cycle_found(detector, cycle))
+                    .unwrap();
+                let record = next_record();
+                assert_eq!(record["event"], "cycle_found");
+                assert_eq!(record["failing"], true);
+                let mut workspaces: Vec<String> =
+                    serde_json::from_value(record["workspaces"].clone()).unwrap();
+                workspaces.sort();
+                seen.push(workspaces);
+            })
+            .unwrap();
+        seen.sort();
+        assert_eq!(seen, [["a", "b"], ["c", "d"]]);
+        assert_eq!(detector.cycle_count(), 2);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_events_are_streamed_to_unix_socket() {
+        use std::os::unix::net::UnixListener;
+
+        let temp = tempfile::tempdir().unwrap();
+        let path = temp.path().join("ferris-wheel.sock");
+        let listener = UnixListener::bind(&path).unwrap();
+
+        let mut sink = EventSink::open(&path).unwrap();
+        sink.emit(&AnalysisEvent::WorkspacesDiscovered { count: 7 })
+            .unwrap();
+        drop(sink);
+
+        let (stream, _) = listener.accept().unwrap();
+        let lines: Vec<_> = BufReader::new(stream)
+            .lines()
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(
+            lines,
+            vec![r#"{"event":"workspaces_discovered","count":7}"#]
+        );
+    }
+}
@@ -1,12 +1,19 @@
 //! GitHub Actions format report generation
 
-use std::fmt::Write;
+use std::io::Write;
+use std::path::{Path, PathBuf};
 
 use super::ReportGenerator;
-use crate::detector::CycleDetector;
+use super::links::ManifestLinks;
+use crate::detector::{CycleDetector, CycleEdge, LayerViolation, WorkspaceCycle};
 use crate::error::FerrisWheelError;
 
-pub struct GitHubReportGenerator;
+pub struct GitHubReportGenerator {
+    links: Option<ManifestLinks>,
+    partial: Option<String>,
+    layer_violations: Vec<LayerViolation>,
+    annotations: Option<(ManifestLinks, PathBuf)>,
+}
 
 impl Default for GitHubReportGenerator {
     fn default() -> Self {
@@ -16,20 +23,157 @@ impl Default for GitHubReportGenerator {
 
 impl GitHubReportGenerator {
     pub fn new() -> Self {
-        Self
+        Self {
+            links: None,
+            partial: None,
+            layer_violations: Vec::new(),
+            annotations: None,
+        }
+    }
+
+    /// Annotate the Cargo.toml line declaring each cycle edge, with paths
+    /// relative to `root` (the repository checkout), so pull requests show
+    /// the cycle inline
+    pub fn with_file_annotations(mut self, locations: ManifestLinks, root: PathBuf) -> Self {
+        self.annotations = Some((locations, root));
+        self
+    }
+
+    /// Link each cycle edge to the manifest line declaring it
+    pub fn with_links(mut self, links: ManifestLinks) -> Self {
+        self.links = Some(links);
+        self
     }
+
+    /// Warn about each dependency that breaks the declared layers
+    pub fn with_layer_violations(mut self, violations: Vec<LayerViolation>) -> Self {
+        self.layer_violations = violations;
+        self
+    }
+
+    /// Warn that only part of the repository was analyzed, for `reason`
+    pub fn with_partial(mut self, reason: String) -> Self {
+        self.partial = Some(reason);
+        self
+    }
+}
+
+impl GitHubReportGenerator {
+    /// Write an annotation on the manifest line declaring `edge`, an error if
+    /// the cycle fails the run and a warning otherwise; returns whether the
+    /// line was found
+    fn write_edge_annotation(
+        &self,
+        output: &mut dyn Write,
+        detector: &CycleDetector,
+        cycle: &WorkspaceCycle,
+        edge: &CycleEdge,
+    ) -> Result<bool, FerrisWheelError> {
+        let Some((locations, root)) = &self.annotations else {
+            return Ok(false);
+        };
+        let Some(location) = locations.edge_location(edge) else {
+            return Ok(false);
+        };
+        let Some(line) = location.member.line else {
+            return Ok(false);
+        };
+
+        let mut workspace_names = cycle.workspace_names().to_vec();
+        workspace_names.sort();
+        let mut message = format!(
+            "{} → {} ({}) closes the cycle {}",
+            edge.from_crate(),
+            edge.to_crate(),
+            edge.dependency_type(),
+            workspace_names.join(" → ")
+        );
+        if let Some(workspace) = &location.workspace {
+            message.push_str(&format!(
+                "; inherited from {}",
+                relative_display(&workspace.manifest, root)
+            ));
+        }
+        if let Some(url) = self.links.as_ref().and_then(|links| links.edge_url(edge)) {
+            message.push_str(&format!(" {url}"));
+        }
+
+        writeln!(
+            output,
+            "::{} file={},line={},title={}::{}",
+            if detector.is_failing(cycle) {
+                "error"
+            } else {
+                "warning"
+            },
+            escape_property(&relative_display(&location.member.manifest, root)),
+            line,
+            escape_property(&format!("Dependency cycle {}", cycle.id())),
+            escape_data(&message)
+        )?;
+        Ok(true)
+    }
+}
+
+/// Directory annotation paths are relative to: the repository checkout on
+/// GitHub Actions (`GITHUB_WORKSPACE`), the current directory elsewhere
+pub fn annotation_root() -> PathBuf {
+    std::env::var_os("GITHUB_WORKSPACE")
+        .map(PathBuf::from)
+        .or_else(|| std::env::current_dir().ok())
+        .unwrap_or_default()
+}
+
+/// `path` relative to `root` when it is inside it
+fn relative_display(path: &Path, root: &Path) -> String {
+    path.strip_prefix(root)
+        .unwrap_or(path)
+        .display()
+        .to_string()
+}
+
+/// Escape the message of a workflow command
+fn escape_data(value: &str) -> String {
+    value
+        .replace('%', "%25")
+        .replace('\r', "%0D")
+        .replace('\n', "%0A")
+}
+
+/// Escape a property value (`file=`, `title=`) of a workflow command
+fn escape_property(value: &str) -> String {
+    escape_data(value).replace(':', "%3A").replace(',', "%2C")
 }
 
 impl ReportGenerator for GitHubReportGenerator {
-    fn generate_report(&self, detector: &CycleDetector) -> Result<String, FerrisWheelError> {
-        let mut output = String::new();
+    fn write_report(
+        &self,
+        detector: &CycleDetector,
+        output: &mut dyn Write,
+    ) -> Result<(), FerrisWheelError> {
+        if let Some(reason) = &self.partial {
+            writeln!(output, "::warning title=Partial Results::{reason}")?;
+        }
+
+        for violation in &self.layer_violations {
+            writeln!(
+                output,
+                "::warning title=Layer Violation::{} ({}) → {} ({}): {} crate edge{}",
+                violation.from_workspace,
+                violation.from_layer,
+                violation.to_workspace,
+                violation.to_layer,
+                violation.edges,
+                if violation.edges == 1 { "" } else { "s" }
+            )?;
+        }
 
         if !detector.has_cycles() {
             writeln!(
                 output,
                 "::notice title=Dependency Check::No workspace dependency cycles detected! ✅"
             )?;
-            return Ok(output);
+            return Ok(());
         }
 
         writeln!(
@@ -43,8 +187,8 @@ impl ReportGenerator for GitHubReportGenerator {
         sorted_cycles.sort_by(|a, b| {
             let a_names = a.workspace_names();
             let b_names = b.workspace_names();
-            let a_first = a_names.first().map(|s| s.as_str()).unwrap_or("");
-            let b_first = b_names.first().map(|s| s.as_str()).unwrap_or("");
+            let a_first = a_names.first().map(AsRef::as_ref).unwrap_or("");
+            let b_first = b_names.first().map(AsRef::as_ref).unwrap_or("");
             a_first.cmp(b_first)
         });
 
@@ -53,8 +197,16 @@ impl ReportGenerator for GitHubReportGenerator {
             workspace_names.sort();
             writeln!(
                 output,
-                "::warning title=Cycle {}::Workspaces: {}",
+                "::warning title=Cycle {} ({}{})::Workspaces: {}",
                 i + 1,
+                cycle.id(),
+                if detector.is_allowed(cycle) {
+                    ", allowed"
+                } else if detector.is_warn_only(cycle) {
+                    ", warn only"
+                } else {
+                    ""
+                },
                 workspace_names.join(" → ")
             )?;
 
@@ -65,13 +217,20 @@ impl ReportGenerator for GitHubReportGenerator {
             });
 
             for edge in sorted_edges {
-                writeln!(
+                if self.write_edge_annotation(output, detector, cycle, &edge)? {
+                    continue;
+                }
+                write!(
                     output,
                     "::notice::  {} → {} ({})",
                     edge.from_crate(),
                     edge.to_crate(),
                     edge.dependency_type()
                 )?;
+                if let Some(url) = self.links.as_ref().and_then(|links| links.edge_url(&edge)) {
+                    write!(output, " {url}")?;
+                }
+                writeln!(output)?;
             }
         }
 
@@ -81,6 +240,87 @@ impl ReportGenerator for GitHubReportGenerator {
              code into a separate workspace that both can depend on."
         )?;
 
-        Ok(output)
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+    use crate::analyzer::{CrateMember, WorkspaceInfo};
+
+    #[test]
+    fn test_edges_annotate_their_manifest_line() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let root = temp.path();
+        let crate_dir = root.join("core/types");
+        std::fs::create_dir_all(&crate_dir).unwrap();
+        std::fs::write(
+            crate_dir.join("Cargo.toml"),
+            "[package]\nname = \"core-types\"\n\n[dependencies]\napp-api = { path = \
+             \"../../app/api\" }\n",
+        )
+        .unwrap();
+
+        let mut workspaces = HashMap::new();
+        workspaces.insert(
+            root.join("core"),
+            WorkspaceInfo::builder()
+                .with_name("core")
+                .with_members(vec![
+                    CrateMember::builder()
+                        .with_name("core-types")
+                        .with_path(&crate_dir)
+                        .build()
+                        .unwrap(),
+                ])
+                .build()
+                .unwrap(),
+        );
+
+        let mut detector = CycleDetector::new();
+        detector.add_cycle(
+            WorkspaceCycle::builder()
+                .with_workspace_names(vec!["core".to_string(), "app".to_string()])
+                .add_edge()
+                .from_workspace("core")
+                .to_workspace("app")
+                .from_crate("core-types")
+                .to_crate("app-api")
+                .dependency_type("Normal")
+                .add_edge()
+                .unwrap()
+                .from_workspace("app")
+                .to_workspace("core")
+                .from_crate("app-api")
+                .to_crate("core-types")
+                .dependency_type("Normal")
+                .build()
+                .unwrap(),
+        );
+        let id = detector.cycles()[0].id().to_string();
+
+        let report = GitHubReportGenerator::new()
+            .with_file_annotations(
+                ManifestLinks::from_workspaces(&workspaces),
+                root.to_path_buf(),
+            )
+            .generate_report(&detector)
+            .unwrap();
+
+        assert!(report.contains(&format!(
+            "::error file=core/types/Cargo.toml,line=5,title=Dependency cycle {id}::core-types → \
+             app-api (Normal) closes the cycle app → core\n"
+        )));
+        // The app manifest isn't known, so its edge stays a plain notice
+        assert!(report.contains("::notice::  app-api → core-types (Normal)\n"));
+    }
+
+    #[test]
+    fn test_workflow_command_escaping() {
+        assert_eq!(escape_data("50%\nnext"), "50%25%0Anext");
+        assert_eq!(escape_property("C:\\a,b"), "C%3A\\a%2Cb");
     }
 }
@@ -1,74 +1,164 @@
 //! Human-readable console report generation
 
-use std::fmt::Write;
+use std::io::Write;
 
-use console::style;
+use console::{StyledObject, style};
 
 use super::ReportGenerator;
-use crate::detector::CycleDetector;
+use super::links::{ManifestLinks, hyperlink};
+use crate::detector::{
+    ArchitectureGrade, CycleDetector, CycleSeverity, Grade, LayerViolation, WorkspaceCycle,
+};
 use crate::error::FerrisWheelError;
 use crate::utils::string::pluralize;
 
-pub struct HumanReportGenerator {
-    max_cycles: Option<usize>,
+/// How much of each cycle the human report shows
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ReportVerbosity {
+    /// Cycle counts by severity
+    #[default]
+    Summary,
+    /// Each cycle with its workspaces, break points and effort, grouped by
+    /// severity (`-v`)
+    Cycles,
+    /// Also the crate dependencies forming each cycle and the manifest
+    /// entries to edit (`-vv`)
+    Edges,
 }
 
-impl HumanReportGenerator {
-    pub fn new(max_cycles: Option<usize>) -> Self {
-        Self { max_cycles }
+impl ReportVerbosity {
+    /// Level for `-v` given `occurrences` times
+    pub fn from_occurrences(occurrences: u8) -> Self {
+        match occurrences {
+            0 => Self::Summary,
+            1 => Self::Cycles,
+            _ => Self::Edges,
+        }
     }
 }
 
-impl ReportGenerator for HumanReportGenerator {
-    fn generate_report(&self, detector: &CycleDetector) -> Result<String, FerrisWheelError> {
-        let mut output = String::new();
+pub struct HumanReportGenerator {
+    verbosity: ReportVerbosity,
+    links: Option<ManifestLinks>,
+    locations: Option<ManifestLinks>,
+    grade: Option<ArchitectureGrade>,
+    partial: Option<String>,
+    layer_violations: Option<Vec<LayerViolation>>,
+}
 
-        if !detector.has_cycles() {
-            write!(
-                output,
-                "\n{} No dependency cycles detected! Your workspaces have a clean dependency \
-                 structure.\n",
-                style("✅").green().bold()
-            )?;
-            return Ok(output);
+impl Default for HumanReportGenerator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl HumanReportGenerator {
+    pub fn new() -> Self {
+        Self {
+            verbosity: ReportVerbosity::default(),
+            links: None,
+            locations: None,
+            grade: None,
+            partial: None,
+            layer_violations: None,
         }
+    }
 
-        write!(
-            output,
-            "\n{} Found {} dependency {}:\n\n",
-            style("❌").red().bold(),
-            style(detector.cycle_count()).red().bold(),
-            pluralize("cycle", detector.cycle_count())
-        )?;
+    /// Choose how much of each cycle to show
+    pub fn with_verbosity(mut self, verbosity: ReportVerbosity) -> Self {
+        self.verbosity = verbosity;
+        self
+    }
 
-        let cycles_to_show = match self.max_cycles {
-            Some(limit) => detector
-                .cycles()
-                .iter()
-                .take(limit)
-                .enumerate()
-                .collect::<Vec<_>>(),
-            None => detector.cycles().iter().enumerate().collect::<Vec<_>>(),
-        };
+    /// Print the architecture grade at the top of the report
+    pub fn with_grade(mut self, grade: ArchitectureGrade) -> Self {
+        self.grade = Some(grade);
+        self
+    }
 
-        let total_cycles = detector.cycle_count();
-        let showing_all = self.max_cycles.is_none_or(|limit| limit >= total_cycles);
+    /// Mark the report as covering only part of the repository, for `reason`
+    pub fn with_partial(mut self, reason: String) -> Self {
+        self.partial = Some(reason);
+        self
+    }
 
-        for (i, cycle) in cycles_to_show {
-            writeln!(output, "{} Cycle #{}", style("🔄").yellow(), i + 1)?;
-            writeln!(output, "  {} Workspaces involved:", style("📦").blue())?;
+    /// List the dependencies that break the declared layers
+    pub fn with_layer_violations(mut self, violations: Vec<LayerViolation>) -> Self {
+        self.layer_violations = Some(violations);
+        self
+    }
 
-            let mut workspace_names = cycle.workspace_names().to_vec();
-            workspace_names.sort();
-            for ws_name in workspace_names {
-                writeln!(
-                    output,
-                    "    {} {}",
-                    style("•").dim(),
-                    style(&ws_name).bold()
-                )?;
-            }
+    /// Render workspace and crate names as OSC 8 hyperlinks to their manifests
+    pub fn with_links(mut self, links: ManifestLinks) -> Self {
+        self.links = Some(links);
+        self
+    }
+
+    /// Name the manifest entries to edit for each suggested break point
+    pub fn with_break_point_locations(mut self, locations: ManifestLinks) -> Self {
+        self.locations = Some(locations);
+        self
+    }
+
+    fn link_workspace(&self, workspace: &str, text: String) -> String {
+        match &self.links {
+            Some(links) => match links.workspace_manifest(workspace) {
+                Some(manifest) => hyperlink(&text, &links.manifest_url(manifest, None)),
+                None => text,
+            },
+            None => text,
+        }
+    }
+
+    fn link_crate(&self, workspace: &str, crate_name: &str, text: String) -> String {
+        match &self.links {
+            Some(links) => match links.crate_manifest(workspace, crate_name) {
+                Some(manifest) => hyperlink(&text, &links.manifest_url(manifest, None)),
+                None => text,
+            },
+            None => text,
+        }
+    }
+
+    /// Write the details of `cycle`, numbered `number`; crate edges and the
+    /// manifest entries to edit are only included with `-vv`
+    fn write_cycle(
+        &self,
+        output: &mut dyn Write,
+        detector: &CycleDetector,
+        number: usize,
+        cycle: &WorkspaceCycle,
+    ) -> Result<(), FerrisWheelError> {
+        let show_edges = self.verbosity == ReportVerbosity::Edges;
+        let allowed = if detector.is_allowed(cycle) {
+            format!(", {}", style("allowed").green())
+        } else if detector.is_warn_only(cycle) {
+            format!(", {}", style("warn only").yellow())
+        } else {
+            String::new()
+        };
+        writeln!(
+            output,
+            "{} Cycle #{} ({} severity{allowed})",
+            style("🔄").yellow(),
+            number,
+            severity_label(detector.severity(cycle))
+        )?;
+        writeln!(output, "  {} ID: {}", style("🆔").dim(), cycle.id())?;
+        writeln!(output, "  {} Workspaces involved:", style("📦").blue())?;
+
+        let mut workspace_names = cycle.workspace_names().to_vec();
+        workspace_names.sort();
+        for ws_name in workspace_names {
+            writeln!(
+                output,
+                "    {} {}",
+                style("•").dim(),
+                self.link_workspace(&ws_name, style(&ws_name).bold().to_string())
+            )?;
+        }
 
+        if show_edges {
             writeln!(
                 output,
                 "\n  {} Dependencies creating this cycle:",
@@ -88,8 +178,8 @@ impl ReportGenerator for HumanReportGenerator {
                         output,
                         "\n    {} {} → {}:",
                         style("📦").blue(),
-                        style(from_ws).bold(),
-                        style(to_ws).bold()
+                        self.link_workspace(from_ws, style(from_ws).bold().to_string()),
+                        self.link_workspace(to_ws, style(to_ws).bold().to_string())
                     )?;
                     let mut sorted_edges = edges.clone();
                     sorted_edges.sort_by(|a, b| match a.from_crate().cmp(b.from_crate()) {
@@ -101,28 +191,220 @@ impl ReportGenerator for HumanReportGenerator {
                             output,
                             "      {} {} → {} ({})",
                             style("→").dim(),
-                            style(edge.from_crate()).yellow(),
-                            style(edge.to_crate()).yellow(),
+                            self.link_crate(
+                                edge.from_workspace(),
+                                edge.from_crate(),
+                                style(edge.from_crate()).yellow().to_string()
+                            ),
+                            self.link_crate(
+                                edge.to_workspace(),
+                                edge.to_crate(),
+                                style(edge.to_crate()).yellow().to_string()
+                            ),
                             style(edge.dependency_type()).dim()
                         )?;
                     }
                 }
             }
-            writeln!(output)?;
         }
 
-        if !showing_all {
+        let break_points = cycle.break_points();
+        if !break_points.is_empty() {
+            writeln!(
+                output,
+                "\n  {} Suggested break points (in priority order):",
+                style("✂️").magenta()
+            )?;
+            for (rank, break_point) in break_points.iter().enumerate() {
+                writeln!(
+                    output,
+                    "    {}. {} → {} ({} crate {}, betweenness {:.1})",
+                    rank + 1,
+                    self.link_workspace(
+                        break_point.from_workspace(),
+                        style(break_point.from_workspace()).bold().to_string()
+                    ),
+                    self.link_workspace(
+                        break_point.to_workspace(),
+                        style(break_point.to_workspace()).bold().to_string()
+                    ),
+                    break_point.edges().len(),
+                    pluralize("edge", break_point.edges().len()),
+                    break_point.betweenness()
+                )?;
+
+                let Some(locations) = self.locations.as_ref().filter(|_| show_edges) else {
+                    continue;
+                };
+                for edge in break_point.edges() {
+                    let Some(location) = locations.edge_location(edge) else {
+                        continue;
+                    };
+                    let target = format!("{} → {}", edge.from_crate(), edge.to_crate());
+                    match &location.workspace {
+                        Some(workspace) => writeln!(
+                            output,
+                            "       {} {}: edit {} (`workspace = true`; the shared definition in \
+                             {} only needs to go once no other member uses it)",
+                            style("✏️").dim(),
+                            target,
+                            style(&location.member).underlined(),
+                            workspace
+                        )?,
+                        None => writeln!(
+                            output,
+                            "       {} {}: edit {}",
+                            style("✏️").dim(),
+                            target,
+                            style(&location.member).underlined()
+                        )?,
+                    }
+                }
+            }
+        }
+
+        let effort = detector.effort(cycle);
+        writeln!(
+            output,
+            "\n  {} Estimated effort: {} (score {:.1})",
+            style("🛠️").cyan(),
+            style(effort.size()).bold(),
+            effort.score()
+        )?;
+        for reason in effort.rationale() {
+            writeln!(output, "    {} {}", style("•").dim(), reason)?;
+        }
+        writeln!(output)?;
+        Ok(())
+    }
+}
+
+impl ReportGenerator for HumanReportGenerator {
+    fn write_report(
+        &self,
+        detector: &CycleDetector,
+        output: &mut dyn Write,
+    ) -> Result<(), FerrisWheelError> {
+        if let Some(grade) = &self.grade {
+            let letter = match grade.grade {
+                Grade::A | Grade::B => style(grade.grade).green().bold(),
+                Grade::C | Grade::D => style(grade.grade).yellow().bold(),
+                Grade::F => style(grade.grade).red().bold(),
+            };
+            write!(
+                output,
+                "\n{} Architecture grade: {} ({:.1}/100)\n",
+                style("🏆").bold(),
+                letter,
+                grade.score
+            )?;
+        }
+
+        if let Some(reason) = &self.partial {
+            write!(
+                output,
+                "\n{} Partial results: {}\n",
+                style("⏱️").yellow().bold(),
+                reason
+            )?;
+        }
+
+        if let Some(violations) = self.layer_violations.as_ref().filter(|v| !v.is_empty()) {
+            write!(
+                output,
+                "\n{} {} layer {}:\n",
+                style("📐").cyan().bold(),
+                violations.len(),
+                pluralize("violation", violations.len())
+            )?;
+            for violation in violations {
+                writeln!(
+                    output,
+                    "  {} {} ({}) → {} ({}): {} crate {}",
+                    style("→").dim(),
+                    self.link_workspace(
+                        &violation.from_workspace,
+                        violation.from_workspace.clone()
+                    ),
+                    violation.from_layer,
+                    self.link_workspace(&violation.to_workspace, violation.to_workspace.clone()),
+                    violation.to_layer,
+                    violation.edges,
+                    pluralize("edge", violation.edges)
+                )?;
+            }
+        }
+
+        if !detector.has_cycles() {
+            write!(
+                output,
+                "\n{} No dependency cycles detected! Your workspaces have a clean dependency \
+                 structure.\n",
+                style("✅").green().bold()
+            )?;
+            return Ok(());
+        }
+
+        write!(
+            output,
+            "\n{} Found {} dependency {}:\n\n",
+            style("❌").red().bold(),
+            style(detector.cycle_count()).red().bold(),
+            pluralize("cycle", detector.cycle_count())
+        )?;
+
+        if self.verbosity == ReportVerbosity::Summary {
+            for severity in [
+                CycleSeverity::High,
+                CycleSeverity::Medium,
+                CycleSeverity::Low,
+            ] {
+                let count = detector
+                    .cycles()
+                    .iter()
+                    .filter(|cycle| detector.severity(cycle) == severity)
+                    .count();
+                if count > 0 {
+                    writeln!(
+                        output,
+                        "  {} {}: {} {}",
+                        style("•").dim(),
+                        severity_label(severity),
+                        count,
+                        pluralize("cycle", count)
+                    )?;
+                }
+            }
             writeln!(
                 output,
-                "\n{} Showing {} of {} cycles. Use --max-cycles to see more.",
-                style("ℹ️").blue(),
-                style(
-                    self.max_cycles
-                        .expect("max_cycles must be Some when !showing_all")
-                )
-                .yellow(),
-                style(total_cycles).yellow()
+                "\n{} Run with -v for each cycle, or -vv for the crate dependencies forming them.",
+                style("ℹ️").blue()
             )?;
+        } else {
+            let mut cycles: Vec<_> = detector.cycles().iter().collect();
+            cycles.sort_by_key(|cycle| std::cmp::Reverse(detector.severity(cycle)));
+
+            let mut current = None;
+            for (i, cycle) in cycles.into_iter().enumerate() {
+                let severity = detector.severity(cycle);
+                if current != Some(severity) {
+                    current = Some(severity);
+                    let count = detector
+                        .cycles()
+                        .iter()
+                        .filter(|cycle| detector.severity(cycle) == severity)
+                        .count();
+                    writeln!(
+                        output,
+                        "{} {} severity ({count} {}) {}\n",
+                        style("──").dim(),
+                        severity_label(severity),
+                        pluralize("cycle", count),
+                        style("──").dim()
+                    )?;
+                }
+                self.write_cycle(output, detector, i + 1, cycle)?;
+            }
         }
 
         writeln!(
@@ -142,6 +424,74 @@ impl ReportGenerator for HumanReportGenerator {
             style("💡").yellow()
         )?;
 
-        Ok(output)
+        Ok(())
+    }
+}
+
+/// `severity`, colored by how bad it is
+fn severity_label(severity: CycleSeverity) -> StyledObject<CycleSeverity> {
+    match severity {
+        CycleSeverity::Low => style(severity).yellow(),
+        CycleSeverity::Medium => style(severity).color256(208),
+        CycleSeverity::High => style(severity).red().bold(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn detector() -> CycleDetector {
+        let mut detector = CycleDetector::new();
+        detector.add_cycle(
+            WorkspaceCycle::builder()
+                .with_workspace_names(vec!["core".to_string(), "api".to_string()])
+                .add_edge()
+                .from_workspace("core")
+                .to_workspace("api")
+                .from_crate("core-lib")
+                .to_crate("api-lib")
+                .dependency_type("Normal")
+                .add_edge()
+                .unwrap()
+                .from_workspace("api")
+                .to_workspace("core")
+                .from_crate("api-lib")
+                .to_crate("core-lib")
+                .dependency_type("Dev")
+                .build()
+                .unwrap(),
+        );
+        detector
+    }
+
+    fn report(verbosity: ReportVerbosity) -> String {
+        HumanReportGenerator::new()
+            .with_verbosity(verbosity)
+            .generate_report(&detector())
+            .unwrap()
+    }
+
+    #[test]
+    fn test_verbosity_levels() {
+        let summary = report(ReportVerbosity::Summary);
+        assert!(summary.contains("• Medium: 1 cycle"));
+        assert!(!summary.contains("Cycle #1"));
+
+        let cycles = report(ReportVerbosity::Cycles);
+        assert!(cycles.contains("Medium severity (1 cycle)"));
+        assert!(cycles.contains("Cycle #1 (Medium severity)"));
+        assert!(cycles.contains("Suggested break points"));
+        assert!(!cycles.contains("api-lib → core-lib"));
+
+        let edges = report(ReportVerbosity::Edges);
+        assert!(edges.contains("Dependencies creating this cycle"));
+        assert!(edges.contains("api-lib → core-lib (Dev)"));
+
+        assert_eq!(
+            ReportVerbosity::from_occurrences(0),
+            ReportVerbosity::Summary
+        );
+        assert_eq!(ReportVerbosity::from_occurrences(3), ReportVerbosity::Edges);
     }
 }
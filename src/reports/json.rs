@@ -1,12 +1,95 @@
 //! JSON format report generation
 
-use serde_json::json;
+use std::io::Write;
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use schemars::JsonSchema;
+use serde::ser::{SerializeMap, SerializeSeq, SerializeStruct};
+use serde::{Serialize, Serializer};
 
 use super::ReportGenerator;
-use crate::detector::CycleDetector;
+use super::links::{EdgeLocation, ManifestLinks};
+use crate::analyzer::FeatureSelection;
+use crate::detector::{
+    ArchitectureGrade, BreakPoint, CycleDetector, CycleEdge, CycleSeverity, EffortEstimate,
+    LayerViolation, WorkspaceCycle,
+};
 use crate::error::FerrisWheelError;
 
-pub struct JsonReportGenerator;
+/// Version of the JSON report format, bumped whenever a field changes
+/// meaning or is removed; new fields don't bump it
+pub const JSON_REPORT_SCHEMA_VERSION: u32 = 1;
+
+/// How the run that wrote a report was invoked, so it can be reproduced
+#[derive(Debug, Clone, PartialEq, Serialize, JsonSchema)]
+pub struct RunMetadata {
+    /// Version of cargo-ferris-wheel that wrote the report
+    pub tool_version: String,
+    /// Seconds since the Unix epoch
+    pub generated_at: u64,
+    /// Paths searched for workspaces
+    pub paths: Vec<PathBuf>,
+    pub filters: RunFilters,
+}
+
+impl RunMetadata {
+    /// Metadata for a run of this version, now, over `paths`
+    pub fn new(paths: Vec<PathBuf>, filters: RunFilters) -> Self {
+        Self {
+            tool_version: env!("CARGO_PKG_VERSION").to_string(),
+            generated_at: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_secs())
+                .unwrap_or_default(),
+            paths,
+            filters,
+        }
+    }
+}
+
+/// Options that decided which dependencies were analyzed
+#[derive(Debug, Clone, Default, PartialEq, Serialize, JsonSchema)]
+pub struct RunFilters {
+    pub exclude_dev: bool,
+    pub exclude_build: bool,
+    pub exclude_target: bool,
+    /// Target triple target-specific dependencies were evaluated for
+    pub target: Option<String>,
+    /// Features optional dependencies were resolved with (None = all)
+    pub features: Option<Vec<String>>,
+    /// Whether each crate's `default` feature was enabled
+    pub default_features: bool,
+    /// Directory globs skipped during discovery
+    pub exclude_paths: Vec<String>,
+    /// Crate globs left out of the analysis
+    pub exclude_crates: Vec<String>,
+    /// Crate whose cycles were analyzed, for `spotlight`
+    pub crate_name: Option<String>,
+}
+
+impl RunFilters {
+    /// Record the feature selection optional dependencies were resolved with
+    pub fn with_features(mut self, selection: &FeatureSelection) -> Self {
+        (self.features, self.default_features) = match selection {
+            FeatureSelection::All => (None, true),
+            FeatureSelection::Selected {
+                features,
+                default_features,
+            } => (Some(features.clone()), *default_features),
+        };
+        self
+    }
+}
+
+#[derive(Clone)]
+pub struct JsonReportGenerator {
+    run: Option<RunMetadata>,
+    grade: Option<ArchitectureGrade>,
+    partial: Option<String>,
+    locations: Option<ManifestLinks>,
+    layer_violations: Option<Vec<LayerViolation>>,
+}
 
 impl Default for JsonReportGenerator {
     fn default() -> Self {
@@ -16,80 +99,337 @@ impl Default for JsonReportGenerator {
 
 impl JsonReportGenerator {
     pub fn new() -> Self {
-        Self
+        Self {
+            run: None,
+            grade: None,
+            partial: None,
+            locations: None,
+            layer_violations: None,
+        }
+    }
+
+    /// Describe the run under a top-level `run` key
+    pub fn with_run(mut self, run: RunMetadata) -> Self {
+        self.run = Some(run);
+        self
+    }
+
+    /// Include the architecture grade under a top-level `grade` key
+    pub fn with_grade(mut self, grade: ArchitectureGrade) -> Self {
+        self.grade = Some(grade);
+        self
+    }
+
+    /// Add the manifest entry to edit to each break point edge
+    pub fn with_break_point_locations(mut self, locations: ManifestLinks) -> Self {
+        self.locations = Some(locations);
+        self
+    }
+
+    /// Include the dependencies that break the declared layers under a
+    /// top-level `layer_violations` key
+    pub fn with_layer_violations(mut self, violations: Vec<LayerViolation>) -> Self {
+        self.layer_violations = Some(violations);
+        self
+    }
+
+    /// Mark the report as partial; `partial_reason` explains why
+    pub fn with_partial(mut self, reason: String) -> Self {
+        self.partial = Some(reason);
+        self
+    }
+
+    /// The crate edges behind `break_point`, with their manifest locations
+    fn break_point_edges<'a>(&self, break_point: &'a BreakPoint) -> Vec<EdgeEntry<'a>> {
+        break_point
+            .edges()
+            .iter()
+            .map(|edge| EdgeEntry {
+                location: self
+                    .locations
+                    .as_ref()
+                    .and_then(|locations| locations.edge_location(edge))
+                    .map(LocationEntry::from),
+                ..EdgeEntry::from(edge)
+            })
+            .collect()
+    }
+
+    /// Stream the report into `output`, one cycle at a time, with its
+    /// fields in the order [`JsonReport`] declares them
+    fn write_json(
+        &self,
+        detector: &CycleDetector,
+        output: &mut dyn Write,
+    ) -> serde_json::Result<()> {
+        let mut serializer = serde_json::Serializer::pretty(output);
+        let mut report = serializer.serialize_map(None)?;
+        report.serialize_entry("schema_version", &JSON_REPORT_SCHEMA_VERSION)?;
+        if let Some(run) = &self.run {
+            report.serialize_entry("run", run)?;
+        }
+        report.serialize_entry("has_cycles", &detector.has_cycles())?;
+        report.serialize_entry("cycle_count", &detector.cycle_count())?;
+        report.serialize_entry(
+            "cycles",
+            &CycleList {
+                report: self,
+                detector,
+            },
+        )?;
+        report.serialize_entry("partial", &self.partial.is_some())?;
+        if let Some(reason) = &self.partial {
+            report.serialize_entry("partial_reason", reason)?;
+        }
+        if let Some(violations) = &self.layer_violations {
+            report.serialize_entry("layer_violations", violations)?;
+        }
+        if let Some(grade) = &self.grade {
+            report.serialize_entry("grade", grade)?;
+        }
+        SerializeMap::end(report)
     }
 }
 
 impl ReportGenerator for JsonReportGenerator {
-    fn generate_report(&self, detector: &CycleDetector) -> Result<String, FerrisWheelError> {
-        let mut cycles: Vec<_> = detector
+    fn write_report(
+        &self,
+        detector: &CycleDetector,
+        output: &mut dyn Write,
+    ) -> Result<(), FerrisWheelError> {
+        self.write_json(detector, output)
+            .map_err(FerrisWheelError::Json)
+    }
+}
+
+/// The `cycles` array, serializing each cycle only when it is reached
+struct CycleList<'a> {
+    report: &'a JsonReportGenerator,
+    detector: &'a CycleDetector,
+}
+
+impl Serialize for CycleList<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        // Sort cycles by their first workspace name for consistent ordering
+        let mut cycles: Vec<(&str, &WorkspaceCycle)> = self
+            .detector
             .cycles()
             .iter()
             .map(|cycle| {
-                let mut workspace_names = cycle.workspace_names().to_vec();
-                workspace_names.sort();
-
-                let mut edges: Vec<_> = cycle
-                    .edges()
+                let first = cycle
+                    .workspace_names()
                     .iter()
-                    .map(|edge| {
-                        json!({
-                            "from_crate": edge.from_crate(),
-                            "to_crate": edge.to_crate(),
-                            "dependency_type": edge.dependency_type(),
-                        })
-                    })
-                    .collect();
-
-                // Sort edges by from_crate, then to_crate for consistent ordering
-                edges.sort_by(|a, b| {
-                    let a_from = a["from_crate"].as_str().unwrap_or("");
-                    let b_from = b["from_crate"].as_str().unwrap_or("");
-                    match a_from.cmp(b_from) {
-                        std::cmp::Ordering::Equal => {
-                            let a_to = a["to_crate"].as_str().unwrap_or("");
-                            let b_to = b["to_crate"].as_str().unwrap_or("");
-                            a_to.cmp(b_to)
-                        }
-                        other => other,
-                    }
-                });
-
-                json!({
-                    "workspaces": workspace_names,
-                    "edges": edges
-                })
+                    .min()
+                    .map_or("", AsRef::as_ref);
+                (first, cycle)
             })
             .collect();
+        cycles.sort_by_key(|&(first, _)| first);
+
+        let mut seq = serializer.serialize_seq(Some(cycles.len()))?;
+        for (_, cycle) in cycles {
+            seq.serialize_element(&CycleEntry {
+                report: self.report,
+                detector: self.detector,
+                cycle,
+            })?;
+        }
+        seq.end()
+    }
+}
 
-        // Sort cycles by their first workspace name for consistent ordering
-        cycles.sort_by(|a, b| {
-            let a_workspaces = a["workspaces"].as_array();
-            let b_workspaces = b["workspaces"].as_array();
-            let a_first = a_workspaces
-                .and_then(|arr| arr.first())
-                .and_then(|v| v.as_str())
-                .unwrap_or("");
-            let b_first = b_workspaces
-                .and_then(|arr| arr.first())
-                .and_then(|v| v.as_str())
-                .unwrap_or("");
-            a_first.cmp(b_first)
-        });
-
-        let report = json!({
-            "has_cycles": detector.has_cycles(),
-            "cycle_count": detector.cycle_count(),
-            "cycles": cycles,
-        });
-
-        serde_json::to_string_pretty(&report).map_err(FerrisWheelError::Json)
+/// One element of the `cycles` array
+struct CycleEntry<'a> {
+    report: &'a JsonReportGenerator,
+    detector: &'a CycleDetector,
+    cycle: &'a WorkspaceCycle,
+}
+
+impl Serialize for CycleEntry<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let (detector, cycle) = (self.detector, self.cycle);
+
+        let mut workspaces: Vec<&str> = cycle.workspace_names().iter().map(AsRef::as_ref).collect();
+        workspaces.sort();
+
+        // Sort edges by from_crate, then to_crate for consistent ordering
+        let mut edges: Vec<EdgeEntry> = cycle.edges().iter().map(EdgeEntry::from).collect();
+        edges.sort_by_key(|edge| (edge.from_crate, edge.to_crate));
+
+        let break_points = cycle.break_points();
+        let break_points: Vec<BreakPointEntry> = break_points
+            .iter()
+            .enumerate()
+            .map(|(rank, break_point)| BreakPointEntry {
+                priority: rank + 1,
+                from_workspace: break_point.from_workspace(),
+                to_workspace: break_point.to_workspace(),
+                betweenness: break_point.betweenness(),
+                edges: self.report.break_point_edges(break_point),
+                rationale: break_point.rationale(),
+            })
+            .collect();
+
+        let mut entry = serializer.serialize_struct("JsonCycle", 8)?;
+        entry.serialize_field("id", &cycle.id())?;
+        entry.serialize_field("workspaces", &workspaces)?;
+        entry.serialize_field("severity", &detector.severity(cycle))?;
+        entry.serialize_field("allowed", &detector.is_allowed(cycle))?;
+        entry.serialize_field("warn_only", &detector.is_warn_only(cycle))?;
+        entry.serialize_field("effort", &detector.effort(cycle))?;
+        entry.serialize_field("edges", &edges)?;
+        entry.serialize_field("break_points", &break_points)?;
+        entry.end()
+    }
+}
+
+/// A [`JsonEdge`] as written to the report
+#[derive(Serialize)]
+struct EdgeEntry<'a> {
+    from_crate: &'a str,
+    to_crate: &'a str,
+    dependency_type: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    location: Option<LocationEntry>,
+}
+
+impl<'a> From<&'a CycleEdge> for EdgeEntry<'a> {
+    fn from(edge: &'a CycleEdge) -> Self {
+        Self {
+            from_crate: edge.from_crate(),
+            to_crate: edge.to_crate(),
+            dependency_type: edge.dependency_type(),
+            location: None,
+        }
+    }
+}
+
+/// A [`JsonBreakPoint`] as written to the report
+#[derive(Serialize)]
+struct BreakPointEntry<'a> {
+    priority: usize,
+    from_workspace: &'a str,
+    to_workspace: &'a str,
+    betweenness: f64,
+    edges: Vec<EdgeEntry<'a>>,
+    rationale: String,
+}
+
+/// A [`JsonLocation`] as written to the report: `manifest` and `line` of
+/// the entry to edit, plus the inherited `[workspace.dependencies]`
+/// definition under `inherited_from`
+#[derive(Serialize)]
+struct LocationEntry {
+    manifest: PathBuf,
+    line: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    inherited_from: Option<ManifestLineEntry>,
+}
+
+impl From<EdgeLocation> for LocationEntry {
+    fn from(location: EdgeLocation) -> Self {
+        Self {
+            manifest: location.member.manifest,
+            line: location.member.line,
+            inherited_from: location.workspace.map(|workspace| ManifestLineEntry {
+                manifest: workspace.manifest,
+                line: workspace.line,
+            }),
+        }
     }
 }
 
+/// A [`JsonManifestLine`] as written to the report
+#[derive(Serialize)]
+struct ManifestLineEntry {
+    manifest: PathBuf,
+    line: Option<usize>,
+}
+
+/// Shape of the report written by [`JsonReportGenerator`], for `schema report`
+///
+/// The report is streamed field by field; these types only describe it.
+#[derive(JsonSchema)]
+pub struct JsonReport {
+    /// Version of the report format, see [`JSON_REPORT_SCHEMA_VERSION`]
+    pub schema_version: u32,
+    /// How the run was invoked
+    pub run: Option<RunMetadata>,
+    pub has_cycles: bool,
+    pub cycle_count: usize,
+    pub cycles: Vec<JsonCycle>,
+    /// Whether the run was cut short, e.g. by `--timeout`
+    pub partial: bool,
+    /// Why the report is partial
+    pub partial_reason: Option<String>,
+    /// Dependencies the declared layers do not allow
+    pub layer_violations: Option<Vec<LayerViolation>>,
+    pub grade: Option<ArchitectureGrade>,
+}
+
+/// One cycle of a [`JsonReport`]
+#[derive(JsonSchema)]
+pub struct JsonCycle {
+    /// Stable cycle ID
+    pub id: String,
+    /// Workspaces of the cycle, sorted
+    pub workspaces: Vec<String>,
+    pub severity: CycleSeverity,
+    /// Whether the cycle is accepted by `[[allow]]`
+    pub allowed: bool,
+    /// Whether the cycle only exists because of dependency types `[policy]`
+    /// lets warn
+    pub warn_only: bool,
+    pub effort: EffortEstimate,
+    pub edges: Vec<JsonEdge>,
+    /// Dependency directions suggested for removal, in priority order
+    pub break_points: Vec<JsonBreakPoint>,
+}
+
+/// A crate dependency of a [`JsonCycle`]
+#[derive(JsonSchema)]
+pub struct JsonEdge {
+    pub from_crate: String,
+    pub to_crate: String,
+    pub dependency_type: String,
+    /// Manifest entry to edit, for break point edges with known manifests
+    pub location: Option<JsonLocation>,
+}
+
+/// A workspace dependency direction suggested for removal, with the reason
+/// to cut it
+#[derive(JsonSchema)]
+pub struct JsonBreakPoint {
+    /// 1 for the direction to cut first
+    pub priority: usize,
+    pub from_workspace: String,
+    pub to_workspace: String,
+    pub betweenness: f64,
+    pub edges: Vec<JsonEdge>,
+    /// One sentence on why this direction is worth cutting
+    pub rationale: String,
+}
+
+/// Where a dependency is declared
+#[derive(JsonSchema)]
+pub struct JsonLocation {
+    pub manifest: PathBuf,
+    /// 1-based line, if the entry could be found
+    pub line: Option<usize>,
+    /// The `[workspace.dependencies]` definition the entry inherits
+    pub inherited_from: Option<JsonManifestLine>,
+}
+
+/// A line of a manifest
+#[derive(JsonSchema)]
+pub struct JsonManifestLine {
+    pub manifest: PathBuf,
+    pub line: Option<usize>,
+}
+
 #[cfg(test)]
 mod tests {
-    use serde_json::Value;
+    use serde_json::{Value, json};
 
     use super::*;
     use crate::detector::{CycleDetector, WorkspaceCycle};
@@ -155,6 +495,77 @@ mod tests {
 
         let edges = cycle["edges"].as_array().unwrap();
         assert_eq!(edges.len(), 2);
+
+        // One normal and one dev edge between two workspaces
+        assert_eq!(cycle["severity"], "medium");
+        // Cutting the dev edge is cheapest
+        assert_eq!(cycle["effort"]["size"], "S");
+        assert!(
+            cycle["effort"]["rationale"][0]
+                .as_str()
+                .unwrap()
+                .ends_with("(1 dev)")
+        );
+        assert_eq!(cycle["id"], detector.cycles()[0].id());
+    }
+
+    #[test]
+    fn test_json_report_partial_marker() {
+        let detector = create_test_detector_with_cycles();
+
+        let complete: Value = serde_json::from_str(
+            &JsonReportGenerator::new()
+                .generate_report(&detector)
+                .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(complete["partial"], false);
+        assert!(complete.get("partial_reason").is_none());
+
+        let generator = JsonReportGenerator::new().with_partial("timed out".to_string());
+        let partial: Value =
+            serde_json::from_str(&generator.generate_report(&detector).unwrap()).unwrap();
+        assert_eq!(partial["partial"], true);
+        assert_eq!(partial["partial_reason"], "timed out");
+    }
+
+    #[test]
+    fn test_json_report_envelope() {
+        let detector = CycleDetector::new();
+
+        let bare: Value = serde_json::from_str(
+            &JsonReportGenerator::new()
+                .generate_report(&detector)
+                .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(bare["schema_version"], JSON_REPORT_SCHEMA_VERSION);
+        assert!(bare.get("run").is_none());
+
+        let run = RunMetadata::new(
+            vec![PathBuf::from("monorepo")],
+            RunFilters {
+                exclude_dev: true,
+                target: Some("x86_64-unknown-linux-gnu".to_string()),
+                ..RunFilters::default()
+            },
+        );
+        let report: Value = serde_json::from_str(
+            &JsonReportGenerator::new()
+                .with_run(run.clone())
+                .generate_report(&detector)
+                .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(report["run"]["tool_version"], env!("CARGO_PKG_VERSION"));
+        assert_eq!(report["run"]["generated_at"], run.generated_at);
+        assert_eq!(report["run"]["paths"], json!(["monorepo"]));
+        assert_eq!(report["run"]["filters"]["exclude_dev"], true);
+        assert_eq!(
+            report["run"]["filters"]["target"],
+            "x86_64-unknown-linux-gnu"
+        );
+        assert_eq!(report["run"]["filters"]["features"], Value::Null);
     }
 
     #[test]
@@ -171,6 +582,25 @@ mod tests {
         assert!(edge.get("dependency_type").is_some());
     }
 
+    #[test]
+    fn test_json_report_break_points() {
+        let detector = create_test_detector_with_cycles();
+        let generator = JsonReportGenerator::new();
+
+        let report = generator.generate_report(&detector).unwrap();
+        let json: Value = serde_json::from_str(&report).unwrap();
+
+        let break_points = json["cycles"][0]["break_points"].as_array().unwrap();
+        assert_eq!(break_points.len(), 1);
+        assert!(break_points[0]["from_workspace"].is_string());
+        assert!(break_points[0]["betweenness"].as_f64().unwrap() > 0.0);
+        assert_eq!(break_points[0]["edges"].as_array().unwrap().len(), 1);
+        assert_eq!(break_points[0]["priority"], 1);
+        let rationale = break_points[0]["rationale"].as_str().unwrap();
+        assert!(rationale.starts_with("Drops 1 dependency from "));
+        assert!(json.get("suggested_breaks").is_none());
+    }
+
     #[test]
     fn test_json_report_pretty_formatting() {
         let detector = CycleDetector::new();
@@ -183,9 +613,42 @@ mod tests {
         assert!(report.contains("  "));
     }
 
+    #[test]
+    fn test_json_report_fields_follow_the_schema_order() {
+        let detector = create_test_detector_with_cycles();
+
+        let report = JsonReportGenerator::new()
+            .generate_report(&detector)
+            .unwrap();
+
+        assert!(
+            report.starts_with("{\n  \"schema_version\": 1,"),
+            "{report}"
+        );
+        let positions: Vec<usize> = ["\"id\"", "\"workspaces\"", "\"edges\"", "\"break_points\""]
+            .iter()
+            .map(|key| report.find(key).unwrap())
+            .collect();
+        assert!(positions.is_sorted(), "{report}");
+    }
+
+    #[test]
+    fn test_json_report_writes_what_it_generates() {
+        let detector = create_test_detector_with_cycles();
+        let generator = JsonReportGenerator::new();
+
+        let mut written = Vec::new();
+        generator.write_report(&detector, &mut written).unwrap();
+
+        assert_eq!(
+            String::from_utf8(written).unwrap(),
+            generator.generate_report(&detector).unwrap()
+        );
+    }
+
     #[test]
     fn test_json_report_default_trait() {
-        let generator1 = JsonReportGenerator;
+        let generator1 = JsonReportGenerator::default();
         let generator2 = JsonReportGenerator::new();
 
         // Both should produce the same results
@@ -195,4 +658,25 @@ mod tests {
 
         assert_eq!(report1, report2);
     }
+
+    #[test]
+    fn test_json_report_includes_grade() {
+        let detector = CycleDetector::new();
+        let graph = petgraph::graph::DiGraph::new();
+        let grade = crate::detector::ArchitectureGrade::compute(
+            &graph,
+            &detector,
+            &crate::detector::GradeWeights::default(),
+        );
+
+        let report = JsonReportGenerator::new()
+            .with_grade(grade)
+            .generate_report(&detector)
+            .unwrap();
+        let parsed: Value = serde_json::from_str(&report).unwrap();
+
+        assert_eq!(parsed["grade"]["grade"], "A");
+        assert_eq!(parsed["grade"]["score"], 100.0);
+        assert_eq!(parsed["grade"]["cycle_count"], 0);
+    }
 }
@@ -1,12 +1,19 @@
 //! JUnit XML format report generation
 
-use std::fmt::Write;
+use std::io::Write;
+use std::time::Duration;
 
 use super::ReportGenerator;
-use crate::detector::CycleDetector;
+use super::links::ManifestLinks;
+use crate::detector::{CycleDetector, LayerViolation, WorkspaceCycle};
 use crate::error::FerrisWheelError;
 
-pub struct JunitReportGenerator;
+pub struct JunitReportGenerator {
+    partial: Option<String>,
+    layer_violations: Option<Vec<LayerViolation>>,
+    locations: Option<ManifestLinks>,
+    duration: Option<Duration>,
+}
 
 impl Default for JunitReportGenerator {
     fn default() -> Self {
@@ -16,84 +23,336 @@ impl Default for JunitReportGenerator {
 
 impl JunitReportGenerator {
     pub fn new() -> Self {
-        Self
+        Self {
+            partial: None,
+            layer_violations: None,
+            locations: None,
+            duration: None,
+        }
     }
-}
 
-impl ReportGenerator for JunitReportGenerator {
-    fn generate_report(&self, detector: &CycleDetector) -> Result<String, FerrisWheelError> {
-        let mut output = String::new();
+    /// Record in the test suite properties that only part of the repository
+    /// was analyzed, for `reason`
+    pub fn with_partial(mut self, reason: String) -> Self {
+        self.partial = Some(reason);
+        self
+    }
+
+    /// Add a `layer-violations` test suite checking the declared layers
+    pub fn with_layer_violations(mut self, violations: Vec<LayerViolation>) -> Self {
+        self.layer_violations = Some(violations);
+        self
+    }
+
+    /// Name the Cargo.toml entry declaring each edge in the failure messages
+    pub fn with_manifest_locations(mut self, locations: ManifestLinks) -> Self {
+        self.locations = Some(locations);
+        self
+    }
+
+    /// Report how long the analysis took as the suites' `time`
+    pub fn with_duration(mut self, duration: Duration) -> Self {
+        self.duration = Some(duration);
+        self
+    }
+
+    /// ` time="…"` in seconds, if the duration is known
+    fn time_attribute(&self) -> String {
+        self.duration
+            .map(|duration| format!(r#" time="{:.3}""#, duration.as_secs_f64()))
+            .unwrap_or_default()
+    }
+
+    /// One test case named after the cycle's workspace chain, failing if the
+    /// cycle fails the run and skipped otherwise
+    fn write_cycle_case(
+        &self,
+        output: &mut dyn Write,
+        detector: &CycleDetector,
+        cycle: &WorkspaceCycle,
+    ) -> Result<(), FerrisWheelError> {
+        let mut workspace_names = cycle.workspace_names().to_vec();
+        workspace_names.sort();
+        let chain = escape_attribute(&workspace_names.join(" → "));
+        let severity = detector.severity(cycle);
+
+        if !detector.is_failing(cycle) {
+            let reason = if detector.is_allowed(cycle) {
+                "Allowed by the project configuration".to_string()
+            } else if detector.is_warn_only(cycle) {
+                "Only closed by dependency types the policy lets warn".to_string()
+            } else {
+                format!("{severity} severity is below the failure threshold")
+            };
+            writeln!(
+                output,
+                r#"    <testcase name="{chain}" classname="ferris-wheel.workspace-cycles">"#
+            )?;
+            writeln!(output, r#"      <skipped message="{reason}" />"#)?;
+            writeln!(output, r#"    </testcase>"#)?;
+            return Ok(());
+        }
 
-        writeln!(output, r#"<?xml version="1.0" encoding="UTF-8"?>"#)?;
         writeln!(
             output,
-            r#"<testsuites name="cargo-ferris-wheel" tests="1" failures="{}">"#,
-            if detector.has_cycles() { "1" } else { "0" }
+            r#"    <testcase name="{chain}" classname="ferris-wheel.workspace-cycles">"#
         )?;
         writeln!(
             output,
-            r#"  <testsuite name="workspace-cycles" tests="1" failures="{}">"#,
-            if detector.has_cycles() { "1" } else { "0" }
+            r#"      <failure message="Cycle {} ({} severity): {}" type="WorkspaceCycle">"#,
+            cycle.id(),
+            severity.to_string().to_lowercase(),
+            chain
         )?;
 
-        if detector.has_cycles() {
-            writeln!(
-                output,
-                r#"    <testcase name="check-workspace-cycles" classname="ferris-wheel">"#
-            )?;
-            writeln!(
-                output,
-                r#"      <failure message="Workspace dependency cycles detected">"#
-            )?;
+        let mut sorted_edges = cycle.edges().to_vec();
+        sorted_edges.sort_by(|a, b| match a.from_crate().cmp(b.from_crate()) {
+            std::cmp::Ordering::Equal => a.to_crate().cmp(b.to_crate()),
+            other => other,
+        });
+
+        for edge in sorted_edges {
             writeln!(
                 output,
-                "Found {} dependency cycles:",
-                detector.cycle_count()
+                "{} → {} ({})",
+                escape_text(edge.from_crate()),
+                escape_text(edge.to_crate()),
+                edge.dependency_type()
             )?;
-
-            let mut sorted_cycles: Vec<_> = detector.cycles().iter().collect();
-            sorted_cycles.sort_by(|a, b| {
-                let a_names = a.workspace_names();
-                let b_names = b.workspace_names();
-                let a_first = a_names.first().map(|s| s.as_str()).unwrap_or("");
-                let b_first = b_names.first().map(|s| s.as_str()).unwrap_or("");
-                a_first.cmp(b_first)
-            });
-
-            for (i, cycle) in sorted_cycles.iter().enumerate() {
-                let mut workspace_names = cycle.workspace_names().to_vec();
-                workspace_names.sort();
-                writeln!(output, "\nCycle {}: {}", i + 1, workspace_names.join(" → "))?;
-
-                let mut sorted_edges = cycle.edges().to_vec();
-                sorted_edges.sort_by(|a, b| match a.from_crate().cmp(b.from_crate()) {
-                    std::cmp::Ordering::Equal => a.to_crate().cmp(b.to_crate()),
-                    other => other,
-                });
-
-                for edge in sorted_edges {
-                    writeln!(
+            let location = self
+                .locations
+                .as_ref()
+                .and_then(|locations| locations.edge_location(&edge));
+            if let Some(location) = location {
+                write!(
+                    output,
+                    "  declared in {}",
+                    escape_text(&location.member.to_string())
+                )?;
+                if let Some(workspace) = &location.workspace {
+                    write!(
                         output,
-                        "  {} → {} ({})",
-                        edge.from_crate(),
-                        edge.to_crate(),
-                        edge.dependency_type()
+                        ", inherited from {}",
+                        escape_text(&workspace.to_string())
                     )?;
                 }
+                writeln!(output)?;
             }
+        }
 
-            writeln!(output, r#"      </failure>"#)?;
-            writeln!(output, r#"    </testcase>"#)?;
-        } else {
+        writeln!(output, r#"      </failure>"#)?;
+        writeln!(output, r#"    </testcase>"#)?;
+        Ok(())
+    }
+}
+
+impl ReportGenerator for JunitReportGenerator {
+    fn write_report(
+        &self,
+        detector: &CycleDetector,
+        output: &mut dyn Write,
+    ) -> Result<(), FerrisWheelError> {
+        let mut sorted_cycles: Vec<_> = detector.cycles().iter().collect();
+        sorted_cycles.sort_by(|a, b| {
+            let a_names = a.workspace_names();
+            let b_names = b.workspace_names();
+            let a_first = a_names.first().map(AsRef::as_ref).unwrap_or("");
+            let b_first = b_names.first().map(AsRef::as_ref).unwrap_or("");
+            a_first.cmp(b_first)
+        });
+        let failing = sorted_cycles
+            .iter()
+            .filter(|cycle| detector.is_failing(cycle))
+            .count();
+        // Without cycles, one passing test case keeps the suite from being
+        // empty
+        let cycle_tests = sorted_cycles.len().max(1);
+        let layers_failed = self
+            .layer_violations
+            .as_ref()
+            .is_some_and(|violations| !violations.is_empty());
+        let time = self.time_attribute();
+
+        writeln!(output, r#"<?xml version="1.0" encoding="UTF-8"?>"#)?;
+        writeln!(
+            output,
+            r#"<testsuites name="cargo-ferris-wheel" tests="{}" failures="{}"{time}>"#,
+            cycle_tests + usize::from(self.layer_violations.is_some()),
+            failing + usize::from(layers_failed)
+        )?;
+        writeln!(
+            output,
+            r#"  <testsuite name="workspace-cycles" tests="{}" failures="{}" skipped="{}"{time}>"#,
+            cycle_tests,
+            failing,
+            sorted_cycles.len() - failing
+        )?;
+
+        if let Some(reason) = &self.partial {
+            writeln!(output, r#"    <properties>"#)?;
+            writeln!(
+                output,
+                r#"      <property name="partial" value="{}" />"#,
+                escape_attribute(reason)
+            )?;
+            writeln!(output, r#"    </properties>"#)?;
+        }
+
+        if sorted_cycles.is_empty() {
             writeln!(
                 output,
                 r#"    <testcase name="check-workspace-cycles" classname="ferris-wheel" />"#
             )?;
         }
+        for cycle in sorted_cycles {
+            self.write_cycle_case(output, detector, cycle)?;
+        }
 
         writeln!(output, r#"  </testsuite>"#)?;
+
+        if let Some(violations) = &self.layer_violations {
+            write_layer_suite(output, violations)?;
+        }
+
         writeln!(output, r#"</testsuites>"#)?;
 
-        Ok(output)
+        Ok(())
+    }
+}
+
+/// A test suite whose one test case fails when any dependency breaks the
+/// declared layers
+fn write_layer_suite(
+    output: &mut dyn Write,
+    violations: &[LayerViolation],
+) -> Result<(), FerrisWheelError> {
+    let failed = !violations.is_empty();
+    writeln!(
+        output,
+        r#"  <testsuite name="layer-violations" tests="1" failures="{}">"#,
+        usize::from(failed)
+    )?;
+    if failed {
+        writeln!(
+            output,
+            r#"    <testcase name="check-layer-violations" classname="ferris-wheel">"#
+        )?;
+        writeln!(
+            output,
+            r#"      <failure message="Dependencies break the declared layers">"#
+        )?;
+        writeln!(output, "Found {} layer violations:", violations.len())?;
+        for violation in violations {
+            writeln!(
+                output,
+                "  {} ({}) → {} ({}): {} crate edges",
+                escape_text(&violation.from_workspace),
+                escape_text(&violation.from_layer),
+                escape_text(&violation.to_workspace),
+                escape_text(&violation.to_layer),
+                violation.edges
+            )?;
+        }
+        writeln!(output, r#"      </failure>"#)?;
+        writeln!(output, r#"    </testcase>"#)?;
+    } else {
+        writeln!(
+            output,
+            r#"    <testcase name="check-layer-violations" classname="ferris-wheel" />"#
+        )?;
+    }
+    writeln!(output, r#"  </testsuite>"#)?;
+    Ok(())
+}
+
+/// Escape a value for use in XML text
+fn escape_text(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+/// Escape a value for use inside a double-quoted XML attribute
+fn escape_attribute(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::detector::{CycleSeverity, SeverityModel};
+
+    fn cycle() -> WorkspaceCycle {
+        WorkspaceCycle::builder()
+            .with_workspace_names(vec!["core".to_string(), "api".to_string()])
+            .add_edge()
+            .from_workspace("core")
+            .to_workspace("api")
+            .from_crate("core-lib")
+            .to_crate("api-lib")
+            .dependency_type("Normal")
+            .add_edge()
+            .unwrap()
+            .from_workspace("api")
+            .to_workspace("core")
+            .from_crate("api-lib")
+            .to_crate("core-lib")
+            .dependency_type("Dev")
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_one_testcase_per_cycle() {
+        let mut detector = CycleDetector::new();
+        detector.add_cycle(cycle());
+
+        let report = JunitReportGenerator::new()
+            .with_duration(Duration::from_millis(1500))
+            .generate_report(&detector)
+            .unwrap();
+
+        assert!(report.contains(
+            r#"<testsuite name="workspace-cycles" tests="1" failures="1" skipped="0" time="1.500">"#
+        ));
+        assert!(
+            report.contains(
+                r#"<testcase name="api → core" classname="ferris-wheel.workspace-cycles">"#
+            )
+        );
+        assert!(report.contains(&format!(
+            r#"<failure message="Cycle {} (medium severity): api → core""#,
+            detector.cycles()[0].id()
+        )));
+        assert!(report.contains("api-lib → core-lib (Dev)\ncore-lib → api-lib (Normal)\n"));
+    }
+
+    #[test]
+    fn test_cycles_that_do_not_fail_are_skipped() {
+        let mut detector = CycleDetector::new().with_severity_model(SeverityModel {
+            fail_at: CycleSeverity::High,
+            ..SeverityModel::default()
+        });
+        detector.add_cycle(cycle());
+
+        let report = JunitReportGenerator::new()
+            .generate_report(&detector)
+            .unwrap();
+
+        assert!(
+            report.contains(r#"<testsuites name="cargo-ferris-wheel" tests="1" failures="0">"#)
+        );
+        assert!(report.contains(r#"failures="0" skipped="1">"#));
+        assert!(
+            report.contains(
+                r#"<skipped message="Medium severity is below the failure threshold" />"#
+            )
+        );
+        assert!(!report.contains("<failure"));
     }
 }
@@ -0,0 +1,303 @@
+//! Cycle results per dependency-kind filter
+//!
+//! CI often wants to know separately whether normal dependencies alone form
+//! cycles, and whether dev or build dependencies add more. Instead of running
+//! the whole analysis once per filter, the graph is built once with every
+//! dependency kind and each filter is applied to a copy of it.
+
+use std::fmt::Write;
+
+use console::style;
+use serde_json::json;
+
+use crate::detector::CycleDetector;
+use crate::error::FerrisWheelError;
+use crate::utils::string::pluralize;
+
+/// Dependency kinds considered in one run of the matrix; normal dependencies
+/// are always included
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KindFilter {
+    pub name: &'static str,
+    pub include_dev: bool,
+    pub include_build: bool,
+}
+
+/// Every combination of dev and build dependencies on top of normal ones
+pub const KIND_FILTERS: [KindFilter; 4] = [
+    KindFilter {
+        name: "normal",
+        include_dev: false,
+        include_build: false,
+    },
+    KindFilter {
+        name: "normal+dev",
+        include_dev: true,
+        include_build: false,
+    },
+    KindFilter {
+        name: "normal+build",
+        include_dev: false,
+        include_build: true,
+    },
+    KindFilter {
+        name: "all",
+        include_dev: true,
+        include_build: true,
+    },
+];
+
+/// Cycles detected under one filter
+pub struct KindFilterResult {
+    pub filter: KindFilter,
+    pub detector: CycleDetector,
+}
+
+/// Report generator for the dependency-kind matrix
+pub struct KindMatrixReportGenerator<'a> {
+    results: &'a [KindFilterResult],
+}
+
+impl<'a> KindMatrixReportGenerator<'a> {
+    pub fn new(results: &'a [KindFilterResult]) -> Self {
+        Self { results }
+    }
+
+    pub fn generate_human_report(&self) -> Result<String, FerrisWheelError> {
+        let mut output = String::new();
+
+        writeln!(
+            output,
+            "\n{} Cycles by dependency kind:\n",
+            style("🧮").cyan()
+        )?;
+
+        for result in self.results {
+            let count = result.detector.cycle_count();
+            if count == 0 {
+                writeln!(
+                    output,
+                    "  {:<14} {} no cycles",
+                    result.filter.name,
+                    style("✅").green()
+                )?;
+                continue;
+            }
+
+            writeln!(
+                output,
+                "  {:<14} {} {} {}",
+                result.filter.name,
+                style("❌").red(),
+                style(count).red().bold(),
+                pluralize("cycle", count)
+            )?;
+            for cycle in result.detector.cycles() {
+                let mut workspace_names = cycle.workspace_names().to_vec();
+                workspace_names.sort();
+                writeln!(
+                    output,
+                    "    {} {} {}",
+                    style("•").dim(),
+                    style(cycle.id()).dim(),
+                    workspace_names.join(", ")
+                )?;
+            }
+        }
+
+        Ok(output)
+    }
+
+    pub fn generate_json_report(&self) -> Result<String, FerrisWheelError> {
+        let kinds: Vec<_> = self
+            .results
+            .iter()
+            .map(|result| {
+                let cycles: Vec<_> = result
+                    .detector
+                    .cycles()
+                    .iter()
+                    .map(|cycle| {
+                        let mut workspace_names: Vec<&str> =
+                            cycle.workspace_names().iter().map(AsRef::as_ref).collect();
+                        workspace_names.sort();
+                        json!({
+                            "id": cycle.id(),
+                            "workspaces": workspace_names,
+                            "severity": result.detector.severity(cycle),
+                        })
+                    })
+                    .collect();
+
+                json!({
+                    "filter": result.filter.name,
+                    "include_dev": result.filter.include_dev,
+                    "include_build": result.filter.include_build,
+                    "has_cycles": result.detector.has_cycles(),
+                    "cycle_count": result.detector.cycle_count(),
+                    "cycles": cycles,
+                })
+            })
+            .collect();
+
+        Ok(serde_json::to_string_pretty(&json!({ "kinds": kinds }))?)
+    }
+
+    pub fn generate_junit_report(&self) -> Result<String, FerrisWheelError> {
+        let mut output = String::new();
+        let failures = self
+            .results
+            .iter()
+            .filter(|result| result.detector.has_cycles())
+            .count();
+
+        writeln!(output, r#"<?xml version="1.0" encoding="UTF-8"?>"#)?;
+        writeln!(
+            output,
+            r#"<testsuites name="cargo-ferris-wheel" tests="{}" failures="{failures}">"#,
+            self.results.len()
+        )?;
+        writeln!(
+            output,
+            r#"  <testsuite name="workspace-cycles-by-kind" tests="{}" failures="{failures}">"#,
+            self.results.len()
+        )?;
+
+        for result in self.results {
+            let name = format!("check-cycles-{}", result.filter.name);
+            if !result.detector.has_cycles() {
+                writeln!(
+                    output,
+                    r#"    <testcase name="{name}" classname="ferris-wheel" />"#
+                )?;
+                continue;
+            }
+
+            writeln!(
+                output,
+                r#"    <testcase name="{name}" classname="ferris-wheel">"#
+            )?;
+            writeln!(
+                output,
+                r#"      <failure message="Dependency cycles detected">"#
+            )?;
+            for cycle in result.detector.cycles() {
+                let mut workspace_names = cycle.workspace_names().to_vec();
+                workspace_names.sort();
+                writeln!(
+                    output,
+                    "Cycle [{}]: {}",
+                    cycle.id(),
+                    workspace_names.join(" → ")
+                )?;
+            }
+            writeln!(output, r#"      </failure>"#)?;
+            writeln!(output, r#"    </testcase>"#)?;
+        }
+
+        writeln!(output, r#"  </testsuite>"#)?;
+        writeln!(output, r#"</testsuites>"#)?;
+
+        Ok(output)
+    }
+
+    pub fn generate_github_report(&self) -> Result<String, FerrisWheelError> {
+        let mut output = String::new();
+
+        for result in self.results {
+            let count = result.detector.cycle_count();
+            if count == 0 {
+                writeln!(
+                    output,
+                    "::notice title=Dependency Cycles ({})::No workspace dependency cycles \
+                     detected",
+                    result.filter.name
+                )?;
+                continue;
+            }
+
+            let ids: Vec<String> = result
+                .detector
+                .cycles()
+                .iter()
+                .map(|cycle| cycle.id())
+                .collect();
+            writeln!(
+                output,
+                "::error title=Dependency Cycles ({})::Found {} workspace dependency {}: {}",
+                result.filter.name,
+                count,
+                pluralize("cycle", count),
+                ids.join(", ")
+            )?;
+        }
+
+        Ok(output)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::Value;
+
+    use super::*;
+    use crate::detector::WorkspaceCycle;
+
+    fn results() -> Vec<KindFilterResult> {
+        KIND_FILTERS
+            .iter()
+            .map(|&filter| {
+                let mut detector = CycleDetector::new();
+                if filter.include_dev {
+                    detector.add_cycle(
+                        WorkspaceCycle::builder()
+                            .with_workspace_names(vec!["b".to_string(), "a".to_string()])
+                            .add_edge()
+                            .from_workspace("a")
+                            .to_workspace("b")
+                            .from_crate("a-lib")
+                            .to_crate("b-lib")
+                            .dependency_type("Normal")
+                            .add_edge()
+                            .unwrap()
+                            .from_workspace("b")
+                            .to_workspace("a")
+                            .from_crate("b-lib")
+                            .to_crate("a-lib")
+                            .dependency_type("Dev")
+                            .build()
+                            .unwrap(),
+                    );
+                }
+                KindFilterResult { filter, detector }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_kind_matrix_reports() {
+        let results = results();
+        let generator = KindMatrixReportGenerator::new(&results);
+
+        let json: Value = serde_json::from_str(&generator.generate_json_report().unwrap()).unwrap();
+        let kinds = json["kinds"].as_array().unwrap();
+        assert_eq!(kinds.len(), 4);
+        assert_eq!(kinds[0]["filter"], "normal");
+        assert_eq!(kinds[0]["cycle_count"], 0);
+        assert_eq!(kinds[1]["filter"], "normal+dev");
+        assert_eq!(kinds[1]["cycles"][0]["workspaces"], json!(["a", "b"]));
+        assert_eq!(
+            kinds[1]["cycles"][0]["id"],
+            results[1].detector.cycles()[0].id()
+        );
+
+        let junit = generator.generate_junit_report().unwrap();
+        assert!(junit.contains(r#"tests="4" failures="2""#));
+        assert!(
+            junit.contains(r#"<testcase name="check-cycles-normal" classname="ferris-wheel" />"#)
+        );
+
+        let github = generator.generate_github_report().unwrap();
+        assert!(github.contains("::error title=Dependency Cycles (all)::Found 1"));
+    }
+}
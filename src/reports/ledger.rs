@@ -0,0 +1,222 @@
+//! Trend ledger
+//!
+//! The ledger is a JSON Lines file with one entry per recorded `inspect` run.
+//! Committing it to the repository (or caching it in CI) turns the grade,
+//! cycle count and layer violation count into a history that can be charted,
+//! and lets each run compare itself against the previous one.
+//!
+//! Layer violations are ratcheted: a run that has more violating crate edges
+//! than the last recorded run fails and is not recorded, so the count can only
+//! go down over time.
+
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+
+use crate::detector::{ArchitectureGrade, Grade, LayerViolation};
+use crate::error::FerrisWheelError;
+use crate::git;
+
+/// One recorded run
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LedgerEntry {
+    /// Seconds since the Unix epoch
+    pub recorded_at: u64,
+    /// Git commit the run was recorded at, if known
+    pub rev: Option<String>,
+    pub grade: Grade,
+    pub score: f64,
+    pub cycle_count: usize,
+    /// Total crate-level dependencies pointing to a higher layer
+    pub layer_violation_count: usize,
+    pub layer_violations: Vec<LayerViolation>,
+}
+
+impl LedgerEntry {
+    /// Build an entry for the current run
+    pub fn new(grade: &ArchitectureGrade, layer_violations: Vec<LayerViolation>) -> Self {
+        Self {
+            recorded_at: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_secs())
+                .unwrap_or_default(),
+            rev: None,
+            grade: grade.grade,
+            score: grade.score,
+            cycle_count: grade.cycle_count,
+            layer_violation_count: layer_violations.iter().map(|v| v.edges).sum(),
+            layer_violations,
+        }
+    }
+
+    pub fn with_rev(mut self, rev: Option<String>) -> Self {
+        self.rev = rev;
+        self
+    }
+}
+
+/// How layer violations changed since the last recorded run
+#[derive(Debug, Clone, PartialEq)]
+pub struct LayerTrend {
+    /// Violation count of the last recorded run (None = nothing recorded)
+    pub previous_count: Option<usize>,
+    pub current_count: usize,
+    /// Violating directions that were not present in the last recorded run
+    pub new_violations: Vec<LayerViolation>,
+}
+
+impl LayerTrend {
+    /// Compare the current run against the last recorded entry
+    pub fn compare(previous: Option<&LedgerEntry>, current: &LedgerEntry) -> Self {
+        let new_violations = current
+            .layer_violations
+            .iter()
+            .filter(|violation| {
+                previous.is_none_or(|entry| {
+                    !entry.layer_violations.iter().any(|old| {
+                        old.from_workspace == violation.from_workspace
+                            && old.to_workspace == violation.to_workspace
+                    })
+                })
+            })
+            .cloned()
+            .collect();
+
+        Self {
+            previous_count: previous.map(|entry| entry.layer_violation_count),
+            current_count: current.layer_violation_count,
+            new_violations,
+        }
+    }
+
+    /// Whether the violation count grew since the last recorded run
+    pub fn is_regression(&self) -> bool {
+        self.previous_count
+            .is_some_and(|previous| self.current_count > previous)
+    }
+}
+
+/// A JSON Lines trend ledger on disk
+#[derive(Debug, Clone)]
+pub struct TrendLedger {
+    path: PathBuf,
+}
+
+impl TrendLedger {
+    pub fn new(path: PathBuf) -> Self {
+        Self { path }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// The most recently recorded entry, if the ledger exists and is not
+    /// empty
+    pub fn last_entry(&self) -> Result<Option<LedgerEntry>, FerrisWheelError> {
+        if !self.path.exists() {
+            return Ok(None);
+        }
+
+        let content =
+            fs::read_to_string(&self.path).map_err(|e| FerrisWheelError::FileReadError {
+                path: self.path.clone(),
+                source: e,
+            })?;
+
+        match content.lines().rev().find(|line| !line.trim().is_empty()) {
+            Some(line) => Ok(Some(serde_json::from_str(line)?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Append an entry, creating the ledger if needed
+    pub fn append(&self, entry: &LedgerEntry) -> Result<(), FerrisWheelError> {
+        if let Some(parent) = self.path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            fs::create_dir_all(parent)?;
+        }
+
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+        writeln!(file, "{}", serde_json::to_string(entry)?)?;
+
+        Ok(())
+    }
+}
+
+/// The commit checked out in the ledger's repository, if any
+pub fn current_rev(dir: &Path) -> Option<String> {
+    git::run_git(dir, &["rev-parse", "HEAD"])
+        .ok()
+        .filter(|rev| !rev.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use tempfile::TempDir;
+
+    use super::*;
+
+    fn violation(from: &str, to: &str, edges: usize) -> LayerViolation {
+        LayerViolation {
+            from_workspace: from.to_string(),
+            to_workspace: to.to_string(),
+            from_layer: "core".to_string(),
+            to_layer: "apps".to_string(),
+            edges,
+        }
+    }
+
+    fn entry(violations: Vec<LayerViolation>) -> LedgerEntry {
+        LedgerEntry {
+            recorded_at: 0,
+            rev: None,
+            grade: Grade::A,
+            score: 95.0,
+            cycle_count: 0,
+            layer_violation_count: violations.iter().map(|v| v.edges).sum(),
+            layer_violations: violations,
+        }
+    }
+
+    #[test]
+    fn test_ledger_round_trip() {
+        let temp = TempDir::new().unwrap();
+        let ledger = TrendLedger::new(temp.path().join("trend/ledger.jsonl"));
+        assert_eq!(ledger.last_entry().unwrap(), None);
+
+        ledger.append(&entry(vec![violation("a", "b", 1)])).unwrap();
+        ledger.append(&entry(vec![])).unwrap();
+
+        assert_eq!(ledger.last_entry().unwrap(), Some(entry(vec![])));
+    }
+
+    #[test]
+    fn test_layer_trend_ratchets() {
+        let previous = entry(vec![violation("a", "b", 2)]);
+
+        let fewer = LayerTrend::compare(Some(&previous), &entry(vec![violation("a", "b", 1)]));
+        assert!(!fewer.is_regression());
+        assert!(fewer.new_violations.is_empty());
+
+        // Same count, but a new direction replaced an old one
+        let moved = LayerTrend::compare(Some(&previous), &entry(vec![violation("c", "d", 2)]));
+        assert!(!moved.is_regression());
+        assert_eq!(moved.new_violations, vec![violation("c", "d", 2)]);
+
+        let more = LayerTrend::compare(
+            Some(&previous),
+            &entry(vec![violation("a", "b", 2), violation("c", "d", 1)]),
+        );
+        assert!(more.is_regression());
+
+        let first = LayerTrend::compare(None, &entry(vec![violation("a", "b", 5)]));
+        assert!(!first.is_regression());
+        assert_eq!(first.previous_count, None);
+    }
+}
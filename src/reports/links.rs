@@ -0,0 +1,471 @@
+//! Manifest links and terminal hyperlink (OSC 8) helpers
+//!
+//! Terminals that understand OSC 8 escape sequences render the wrapped text as
+//! a clickable link. We use this to make workspace and crate names in human
+//! reports jump straight to their `Cargo.toml`, either on disk or in a code
+//! browser when a URL template is configured.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use supports_hyperlinks::Stream;
+
+use crate::analyzer::{WorkspaceAnalyzer, WorkspaceInfo};
+use crate::detector::CycleEdge;
+use crate::graph::DependencyType;
+use crate::toml_parser::{
+    find_dependency_declaration, find_dependency_declaration_of_kind,
+    find_workspace_dependency_line,
+};
+
+/// Check whether stdout is a terminal that renders OSC 8 hyperlinks
+///
+/// Honours the `FORCE_HYPERLINK` environment variable, so links can be forced
+/// on (`FORCE_HYPERLINK=1`) or off (`FORCE_HYPERLINK=0`).
+pub fn stdout_supports_hyperlinks() -> bool {
+    supports_hyperlinks::on(Stream::Stdout)
+}
+
+/// Wrap `text` in an OSC 8 hyperlink pointing at `url`
+pub fn hyperlink(text: &str, url: &str) -> String {
+    format!("\x1b]8;;{url}\x1b\\{text}\x1b]8;;\x1b\\")
+}
+
+/// Build a `file://` URL for an absolute path
+///
+/// Characters that would terminate or confuse the escape sequence are
+/// percent-encoded.
+pub fn file_url(path: &Path) -> String {
+    let raw = path.to_string_lossy().replace('\\', "/");
+    let mut url = String::with_capacity(raw.len() + 8);
+    url.push_str("file://");
+    if !raw.starts_with('/') {
+        url.push('/');
+    }
+    for byte in raw.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'/' | b'-' | b'_' | b'.' | b'~' | b':' => {
+                url.push(byte as char)
+            }
+            _ => url.push_str(&format!("%{byte:02X}")),
+        }
+    }
+    url
+}
+
+/// Code-browser URL template such as
+/// `https://github.com/org/repo/blob/{rev}/{path}#L{line}`
+///
+/// `{path}` is the manifest path relative to `root`, `{rev}` the configured
+/// revision and `{line}` the 1-based line within the manifest.
+#[derive(Debug, Clone, PartialEq)]
+pub struct UrlTemplate {
+    template: String,
+    root: PathBuf,
+    rev: String,
+}
+
+impl UrlTemplate {
+    pub fn new(
+        template: impl Into<String>,
+        root: impl Into<PathBuf>,
+        rev: impl Into<String>,
+    ) -> Self {
+        Self {
+            template: template.into(),
+            root: root.into(),
+            rev: rev.into(),
+        }
+    }
+
+    /// Render the URL for a manifest, or `None` if it lies outside the root
+    pub fn render(&self, manifest: &Path, line: Option<usize>) -> Option<String> {
+        let relative = manifest.strip_prefix(&self.root).ok()?;
+        let path = relative
+            .components()
+            .map(|c| c.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/");
+
+        Some(
+            self.template
+                .replace("{rev}", &self.rev)
+                .replace("{path}", &path)
+                .replace("{line}", &line.unwrap_or(1).to_string()),
+        )
+    }
+}
+
+/// A line in a manifest
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ManifestLocation {
+    pub manifest: PathBuf,
+    /// 1-based line, if the entry could be found
+    pub line: Option<usize>,
+}
+
+impl std::fmt::Display for ManifestLocation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.line {
+            Some(line) => write!(f, "{}:{line}", self.manifest.display()),
+            None => write!(f, "{}", self.manifest.display()),
+        }
+    }
+}
+
+/// Where the dependency behind a cycle edge is declared
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EdgeLocation {
+    /// The entry in the depending crate's manifest
+    pub member: ManifestLocation,
+    /// The `[workspace.dependencies]` definition the entry inherits with
+    /// `workspace = true`, if any
+    pub workspace: Option<ManifestLocation>,
+}
+
+/// Lookup table from workspace and crate names to their manifest paths
+#[derive(Debug, Clone, Default)]
+pub struct ManifestLinks {
+    workspaces: HashMap<String, PathBuf>,
+    crates: HashMap<(String, String), PathBuf>,
+    /// Workspace root manifest for every workspace and `workspace/crate` node
+    roots: HashMap<String, PathBuf>,
+    url_template: Option<UrlTemplate>,
+}
+
+impl ManifestLinks {
+    /// Collect manifest paths for every discovered workspace and member crate
+    ///
+    /// Intra-workspace graphs name their nodes `workspace/crate`, so those
+    /// names are registered as well.
+    pub fn from_workspaces(workspaces: &HashMap<PathBuf, WorkspaceInfo>) -> Self {
+        let mut links = Self::default();
+
+        for (ws_path, ws_info) in workspaces {
+            let root = ws_path.join("Cargo.toml");
+            links
+                .workspaces
+                .insert(ws_info.name().to_string(), root.clone());
+            links.roots.insert(ws_info.name().to_string(), root.clone());
+
+            for member in ws_info.members() {
+                let manifest = member.path().join("Cargo.toml");
+                let node_name = format!("{}/{}", ws_info.name(), member.name());
+
+                links.workspaces.insert(node_name.clone(), manifest.clone());
+                links.roots.insert(node_name.clone(), root.clone());
+                links.crates.insert(
+                    (ws_info.name().to_string(), member.name().to_string()),
+                    manifest.clone(),
+                );
+                links
+                    .crates
+                    .insert((node_name, member.name().to_string()), manifest);
+            }
+        }
+
+        links
+    }
+
+    /// Link to a code browser instead of local `file://` URLs
+    pub fn with_url_template(mut self, url_template: UrlTemplate) -> Self {
+        self.url_template = Some(url_template);
+        self
+    }
+
+    /// URL for a manifest, preferring the code-browser template when it applies
+    pub fn manifest_url(&self, manifest: &Path, line: Option<usize>) -> String {
+        self.url_template
+            .as_ref()
+            .and_then(|template| template.render(manifest, line))
+            .unwrap_or_else(|| file_url(manifest))
+    }
+
+    /// Code-browser URL for the manifest line declaring a cycle edge
+    ///
+    /// Returns `None` without a URL template, since local paths are of no use
+    /// to readers of CI output.
+    pub fn edge_url(&self, edge: &CycleEdge) -> Option<String> {
+        let template = self.url_template.as_ref()?;
+        let location = self.edge_location(edge)?;
+        template.render(&location.member.manifest, location.member.line)
+    }
+
+    /// Where the dependency behind a cycle edge is declared, following
+    /// `workspace = true` entries to their `[workspace.dependencies]`
+    /// definition
+    pub fn edge_location(&self, edge: &CycleEdge) -> Option<EdgeLocation> {
+        let manifest = self.crate_manifest(edge.from_workspace(), edge.from_crate())?;
+        let content = std::fs::read_to_string(manifest).ok();
+        let declaration = content.as_deref().and_then(|content| {
+            match DependencyType::from_name(edge.dependency_type()) {
+                Some(kind) => find_dependency_declaration_of_kind(content, edge.to_crate(), kind),
+                None => find_dependency_declaration(content, edge.to_crate()),
+            }
+        });
+
+        let workspace = declaration
+            .filter(|declaration| declaration.inherited)
+            .and_then(|_| self.roots.get(edge.from_workspace()))
+            .map(|root| ManifestLocation {
+                manifest: root.clone(),
+                line: std::fs::read_to_string(root)
+                    .ok()
+                    .and_then(|content| find_workspace_dependency_line(&content, edge.to_crate())),
+            });
+
+        Some(EdgeLocation {
+            member: ManifestLocation {
+                manifest: manifest.to_path_buf(),
+                line: declaration.map(|declaration| declaration.line),
+            },
+            workspace,
+        })
+    }
+
+    /// Manifest path for a workspace (or intra-workspace crate node)
+    pub fn workspace_manifest(&self, workspace: &str) -> Option<&Path> {
+        self.workspaces.get(workspace).map(PathBuf::as_path)
+    }
+
+    /// Manifest path for a crate within the given workspace
+    pub fn crate_manifest(&self, workspace: &str, crate_name: &str) -> Option<&Path> {
+        self.crates
+            .get(&(workspace.to_string(), crate_name.to_string()))
+            .map(PathBuf::as_path)
+    }
+}
+
+/// Manifest links for every analyzed workspace, pointing at the code browser
+/// when `url_template` is set
+pub fn manifest_links(
+    analyzer: &WorkspaceAnalyzer,
+    url_template: Option<&UrlTemplate>,
+) -> ManifestLinks {
+    let links = ManifestLinks::from_workspaces(analyzer.workspaces());
+    match url_template {
+        Some(template) => links.with_url_template(template.clone()),
+        None => links,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analyzer::CrateMember;
+
+    #[test]
+    fn test_hyperlink_wraps_text_in_osc8() {
+        assert_eq!(
+            hyperlink("core", "file:///repo/Cargo.toml"),
+            "\x1b]8;;file:///repo/Cargo.toml\x1b\\core\x1b]8;;\x1b\\"
+        );
+    }
+
+    #[test]
+    fn test_file_url_encodes_special_characters() {
+        assert_eq!(
+            file_url(Path::new("/repo/my crate/Cargo.toml")),
+            "file:///repo/my%20crate/Cargo.toml"
+        );
+    }
+
+    #[test]
+    fn test_manifest_links_from_workspaces() {
+        let mut workspaces = HashMap::new();
+        workspaces.insert(
+            PathBuf::from("/repo/core"),
+            WorkspaceInfo::builder()
+                .with_name("core")
+                .with_members(vec![
+                    CrateMember::builder()
+                        .with_name("core-types")
+                        .with_path("/repo/core/types")
+                        .build()
+                        .unwrap(),
+                ])
+                .build()
+                .unwrap(),
+        );
+
+        let links = ManifestLinks::from_workspaces(&workspaces);
+
+        assert_eq!(
+            links.workspace_manifest("core"),
+            Some(Path::new("/repo/core/Cargo.toml"))
+        );
+        assert_eq!(
+            links.crate_manifest("core", "core-types"),
+            Some(Path::new("/repo/core/types/Cargo.toml"))
+        );
+        assert_eq!(
+            links.workspace_manifest("core/core-types"),
+            Some(Path::new("/repo/core/types/Cargo.toml"))
+        );
+        assert_eq!(links.crate_manifest("apps", "core-types"), None);
+    }
+
+    #[test]
+    fn test_url_template_render() {
+        let template = UrlTemplate::new(
+            "https://github.com/org/repo/blob/{rev}/{path}#L{line}",
+            "/repo",
+            "abc123",
+        );
+
+        assert_eq!(
+            template.render(Path::new("/repo/core/Cargo.toml"), Some(12)),
+            Some("https://github.com/org/repo/blob/abc123/core/Cargo.toml#L12".to_string())
+        );
+        assert_eq!(
+            template.render(Path::new("/elsewhere/Cargo.toml"), None),
+            None
+        );
+    }
+
+    #[test]
+    fn test_manifest_url_falls_back_to_file_url() {
+        let links = ManifestLinks::default().with_url_template(UrlTemplate::new(
+            "https://example.com/{path}",
+            "/repo",
+            "main",
+        ));
+
+        assert_eq!(
+            links.manifest_url(Path::new("/repo/a/Cargo.toml"), None),
+            "https://example.com/a/Cargo.toml"
+        );
+        assert_eq!(
+            links.manifest_url(Path::new("/other/Cargo.toml"), None),
+            "file:///other/Cargo.toml"
+        );
+    }
+
+    #[test]
+    fn test_edge_url_points_at_dependency_line() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let root = temp.path();
+        let crate_dir = root.join("core/types");
+        std::fs::create_dir_all(&crate_dir).unwrap();
+        std::fs::write(
+            crate_dir.join("Cargo.toml"),
+            "[package]\nname = \"core-types\"\n\n[dependencies]\napp-api = { path = \
+             \"../../app/api\" }\n",
+        )
+        .unwrap();
+
+        let mut workspaces = HashMap::new();
+        workspaces.insert(
+            root.join("core"),
+            WorkspaceInfo::builder()
+                .with_name("core")
+                .with_members(vec![
+                    CrateMember::builder()
+                        .with_name("core-types")
+                        .with_path(&crate_dir)
+                        .build()
+                        .unwrap(),
+                ])
+                .build()
+                .unwrap(),
+        );
+
+        let cycle = crate::detector::WorkspaceCycle::builder()
+            .add_edge()
+            .from_workspace("core")
+            .to_workspace("app")
+            .from_crate("core-types")
+            .to_crate("app-api")
+            .dependency_type("Normal")
+            .build()
+            .unwrap();
+        let edge = &cycle.edges()[0];
+
+        let links = ManifestLinks::from_workspaces(&workspaces);
+        assert_eq!(links.edge_url(edge), None);
+
+        let links = links.with_url_template(UrlTemplate::new(
+            "https://github.com/org/repo/blob/{rev}/{path}#L{line}",
+            root,
+            "abc123",
+        ));
+        assert_eq!(
+            links.edge_url(edge),
+            Some("https://github.com/org/repo/blob/abc123/core/types/Cargo.toml#L5".to_string())
+        );
+    }
+
+    #[test]
+    fn test_edge_location_follows_workspace_inheritance() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let root = temp.path().join("core");
+        let crate_dir = root.join("types");
+        std::fs::create_dir_all(&crate_dir).unwrap();
+        std::fs::write(
+            root.join("Cargo.toml"),
+            "[workspace]\nmembers = [\"types\"]\n\n[workspace.dependencies]\napp-api = { path = \
+             \"../app/api\" }\n",
+        )
+        .unwrap();
+        std::fs::write(
+            crate_dir.join("Cargo.toml"),
+            "[package]\nname = \"core-types\"\n\n[dependencies]\napp-api.workspace = \
+             true\napp-cli = { path = \"../../app/cli\" }\n",
+        )
+        .unwrap();
+
+        let mut workspaces = HashMap::new();
+        workspaces.insert(
+            root.clone(),
+            WorkspaceInfo::builder()
+                .with_name("core")
+                .with_members(vec![
+                    CrateMember::builder()
+                        .with_name("core-types")
+                        .with_path(&crate_dir)
+                        .build()
+                        .unwrap(),
+                ])
+                .build()
+                .unwrap(),
+        );
+        let links = ManifestLinks::from_workspaces(&workspaces);
+
+        let cycle = crate::detector::WorkspaceCycle::builder()
+            .add_edge()
+            .from_workspace("core")
+            .to_workspace("app")
+            .from_crate("core-types")
+            .to_crate("app-api")
+            .dependency_type("Normal")
+            .add_edge()
+            .unwrap()
+            .from_workspace("core")
+            .to_workspace("app")
+            .from_crate("core-types")
+            .to_crate("app-cli")
+            .dependency_type("Normal")
+            .build()
+            .unwrap();
+
+        let inherited = links.edge_location(&cycle.edges()[0]).unwrap();
+        assert_eq!(
+            inherited.member,
+            ManifestLocation {
+                manifest: crate_dir.join("Cargo.toml"),
+                line: Some(5)
+            }
+        );
+        assert_eq!(
+            inherited.workspace,
+            Some(ManifestLocation {
+                manifest: root.join("Cargo.toml"),
+                line: Some(5)
+            })
+        );
+
+        let direct = links.edge_location(&cycle.edges()[1]).unwrap();
+        assert_eq!(direct.member.line, Some(6));
+        assert_eq!(direct.workspace, None);
+    }
+}
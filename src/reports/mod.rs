@@ -5,23 +5,56 @@
 //! - json: JSON format for programmatic use
 //! - junit: JUnit XML format for CI/CD integration
 //! - github: GitHub Actions format for PR comments
+//! - template: user-supplied templates rendered against the JSON report
+//!
+//! The links module turns workspace and crate names into hyperlinks to their
+//! manifests, and the ledger module records run metrics over time. The events
+//! module streams progress of a run as JSON lines to a socket or pipe. The
+//! kind_matrix module reports cycles separately for each dependency-kind
+//! filter, and the baseline module records known cycles so only new ones fail
+//! a run. The output module writes reports to stdout or, atomically, to a
+//! file.
 
+pub mod baseline;
+pub mod events;
 pub mod github;
 pub mod human;
 pub mod json;
 pub mod junit;
+pub mod kind_matrix;
+pub mod ledger;
+pub mod links;
+pub mod output;
+pub mod template;
+
+use std::io::Write;
 
 use crate::detector::CycleDetector;
 use crate::error::FerrisWheelError;
 
 /// Common trait for all report generators
+///
+/// Generators write into any [`Write`], so a report on a huge repository can
+/// go straight to stdout or a file without being buffered in memory first.
 pub trait ReportGenerator {
-    /// Generate a report from cycle detection results
-    fn generate_report(&self, detector: &CycleDetector) -> Result<String, FerrisWheelError>;
+    /// Write a report of cycle detection results into `output`
+    fn write_report(
+        &self,
+        detector: &CycleDetector,
+        output: &mut dyn Write,
+    ) -> Result<(), FerrisWheelError>;
+
+    /// Generate a report from cycle detection results as a string
+    fn generate_report(&self, detector: &CycleDetector) -> Result<String, FerrisWheelError> {
+        let mut output = Vec::new();
+        self.write_report(detector, &mut output)?;
+        Ok(String::from_utf8_lossy(&output).into_owned())
+    }
 }
 
 // Re-export for convenience
 pub use github::GitHubReportGenerator;
-pub use human::HumanReportGenerator;
+pub use human::{HumanReportGenerator, ReportVerbosity};
 pub use json::JsonReportGenerator;
 pub use junit::JunitReportGenerator;
+pub use template::TemplateReportGenerator;
@@ -0,0 +1,172 @@
+//! Report destination
+//!
+//! Reports go to stdout unless `--output <FILE>` names a file. A file report
+//! is written under a temporary name next to the file and only moved into
+//! place once it is complete, so a reader never sees half a report and a run
+//! that fails leaves the previous report intact. Progress and diagnostics
+//! stay on stderr either way.
+
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+use console::style;
+
+use crate::cli::OutputFormat;
+use crate::error::FerrisWheelError;
+
+/// Where a command writes its report
+pub struct ReportOutput {
+    writer: BufWriter<Box<dyn Write>>,
+    file: Option<PendingFile>,
+}
+
+/// A file report that has not been moved into place yet
+struct PendingFile {
+    temporary: PathBuf,
+    path: PathBuf,
+}
+
+impl Drop for PendingFile {
+    fn drop(&mut self) {
+        // Gone already once the report was moved into place
+        let _ = fs::remove_file(&self.temporary);
+    }
+}
+
+impl ReportOutput {
+    /// Write to `path`, or to stdout when there is none
+    ///
+    /// Colors are turned off for a file report, since the file isn't a
+    /// terminal.
+    pub fn open(path: Option<&Path>) -> Result<Self, FerrisWheelError> {
+        let Some(path) = path else {
+            return Ok(Self {
+                writer: BufWriter::new(Box::new(io::stdout().lock())),
+                file: None,
+            });
+        };
+
+        let file_name = path
+            .file_name()
+            .ok_or_else(|| FerrisWheelError::ConfigurationError {
+                message: format!("Output path '{}' is not a file", path.display()),
+            })?;
+        let temporary = path.with_file_name(format!(
+            ".{}.{}.tmp",
+            file_name.to_string_lossy(),
+            std::process::id()
+        ));
+        let file = File::create(&temporary)?;
+        console::set_colors_enabled(false);
+
+        Ok(Self {
+            writer: BufWriter::new(Box::new(file)),
+            file: Some(PendingFile {
+                temporary,
+                path: path.to_path_buf(),
+            }),
+        })
+    }
+
+    /// Write an already rendered `report` to `path`, or to stdout; returns
+    /// the path written to, if it is a file
+    pub fn write_rendered(
+        path: Option<&Path>,
+        report: &str,
+    ) -> Result<Option<PathBuf>, FerrisWheelError> {
+        let mut output = Self::open(path)?;
+        output.write_all(report.as_bytes())?;
+        output.finish()
+    }
+
+    /// Whether the report goes to stdout, where hyperlinks may render
+    pub fn is_stdout(&self) -> bool {
+        self.file.is_none()
+    }
+
+    /// Flush the report and move a file report into place; returns the path
+    /// written to, if it is a file
+    pub fn finish(self) -> Result<Option<PathBuf>, FerrisWheelError> {
+        let Self { writer, file } = self;
+        // Closes the file before it is renamed
+        drop(
+            writer
+                .into_inner()
+                .map_err(io::IntoInnerError::into_error)?,
+        );
+
+        let Some(file) = file else {
+            return Ok(None);
+        };
+        fs::rename(&file.temporary, &file.path)?;
+        Ok(Some(file.path.clone()))
+    }
+}
+
+/// Name of the `format` report in an output directory
+pub fn report_file_name(format: OutputFormat) -> &'static str {
+    match format {
+        OutputFormat::Human => "ferris-wheel.txt",
+        OutputFormat::Json => "ferris-wheel.json",
+        OutputFormat::Junit => "ferris-wheel.junit.xml",
+        OutputFormat::GitHub => "ferris-wheel.github.txt",
+    }
+}
+
+/// Tell the user where a file report went, given what
+/// [`ReportOutput::finish`] returned
+pub fn report_written(path: Option<PathBuf>) {
+    if let Some(path) = path {
+        eprintln!(
+            "{} Report written to {}",
+            style("✓").green(),
+            style(path.display()).bold()
+        );
+    }
+}
+
+impl Write for ReportOutput {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.writer.write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.writer.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_file_report_only_appears_once_finished() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let path = temp.path().join("report.json");
+        fs::write(&path, "previous").unwrap();
+
+        let mut output = ReportOutput::open(Some(&path)).unwrap();
+        assert!(!output.is_stdout());
+        write!(output, "{{}}").unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "previous");
+
+        assert_eq!(output.finish().unwrap(), Some(path.clone()));
+        assert_eq!(fs::read_to_string(&path).unwrap(), "{}");
+        assert_eq!(fs::read_dir(temp.path()).unwrap().count(), 1);
+    }
+
+    #[test]
+    fn test_abandoned_report_leaves_previous_file() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let path = temp.path().join("report.json");
+        fs::write(&path, "previous").unwrap();
+
+        let mut output = ReportOutput::open(Some(&path)).unwrap();
+        write!(output, "partial").unwrap();
+        drop(output);
+
+        assert_eq!(fs::read_to_string(&path).unwrap(), "previous");
+        assert_eq!(fs::read_dir(temp.path()).unwrap().count(), 1);
+    }
+}
@@ -0,0 +1,166 @@
+//! User-templated report generation
+//!
+//! Templates use [Tera](https://keats.github.io/tera/docs/) syntax and are
+//! rendered against the JSON report, so every field `schema report`
+//! describes is available: `{{ cycle_count }}`, `{% for cycle in cycles %}`,
+//! `{% if has_cycles %}` and so on. Values are written as-is; pipe them
+//! through `| escape` to escape them for HTML output.
+
+use std::error::Error;
+use std::fmt::Write as _;
+use std::io::Write;
+use std::path::Path;
+
+use tera::{Context, Tera};
+
+use super::{JsonReportGenerator, ReportGenerator};
+use crate::detector::CycleDetector;
+use crate::error::FerrisWheelError;
+
+/// Name the template is registered under, shown in render errors
+const TEMPLATE_NAME: &str = "report";
+
+#[derive(Clone)]
+pub struct TemplateReportGenerator {
+    tera: Tera,
+    context: JsonReportGenerator,
+}
+
+impl TemplateReportGenerator {
+    /// Render reports with the template `source`, failing if it does not
+    /// parse
+    pub fn new(source: String) -> Result<Self, FerrisWheelError> {
+        let mut tera = Tera::default();
+        tera.autoescape_on(Vec::new());
+        tera.add_raw_template(TEMPLATE_NAME, &source)
+            .map_err(template_error)?;
+        Ok(Self {
+            tera,
+            context: JsonReportGenerator::new(),
+        })
+    }
+
+    /// Read the template from `path`
+    pub fn from_file(path: &Path) -> Result<Self, FerrisWheelError> {
+        let source =
+            std::fs::read_to_string(path).map_err(|source| FerrisWheelError::FileReadError {
+                path: path.to_path_buf(),
+                source,
+            })?;
+        Self::new(source).map_err(|err| match err {
+            FerrisWheelError::ConfigurationError { message } => {
+                FerrisWheelError::ConfigurationError {
+                    message: format!("Template {}: {message}", path.display()),
+                }
+            }
+            other => other,
+        })
+    }
+
+    /// Render the template against the report `context` writes, so it sees
+    /// the run, grade and manifest locations `context` was given
+    pub fn with_context(mut self, context: JsonReportGenerator) -> Self {
+        self.context = context;
+        self
+    }
+}
+
+impl ReportGenerator for TemplateReportGenerator {
+    fn write_report(
+        &self,
+        detector: &CycleDetector,
+        output: &mut dyn Write,
+    ) -> Result<(), FerrisWheelError> {
+        let mut report = Vec::new();
+        self.context.write_report(detector, &mut report)?;
+        let report: serde_json::Value =
+            serde_json::from_slice(&report).map_err(FerrisWheelError::Json)?;
+        let context = Context::from_value(report).map_err(template_error)?;
+
+        self.tera
+            .render_to(TEMPLATE_NAME, &context, output)
+            .map_err(template_error)
+    }
+}
+
+/// Tera puts the details of an error in its sources, so spell out the chain
+fn template_error(err: tera::Error) -> FerrisWheelError {
+    let mut message = err.to_string();
+    let mut source = err.source();
+    while let Some(cause) = source {
+        let _ = write!(message, ": {cause}");
+        source = cause.source();
+    }
+    FerrisWheelError::ConfigurationError { message }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::detector::WorkspaceCycle;
+
+    fn detector_with_cycle() -> CycleDetector {
+        let mut detector = CycleDetector::new();
+        detector.add_cycle(
+            WorkspaceCycle::builder()
+                .with_workspace_names(vec!["core".to_string(), "<app>".to_string()])
+                .add_edge()
+                .from_workspace("core")
+                .to_workspace("<app>")
+                .from_crate("core-lib")
+                .to_crate("app-lib")
+                .dependency_type("Dev")
+                .add_edge()
+                .unwrap()
+                .from_workspace("<app>")
+                .to_workspace("core")
+                .from_crate("app-lib")
+                .to_crate("core-lib")
+                .dependency_type("Normal")
+                .build()
+                .unwrap(),
+        );
+        detector
+    }
+
+    #[test]
+    fn test_template_renders_json_report() {
+        let generator = TemplateReportGenerator::new(
+            "{{ cycle_count }} cycle(s)\n{% for cycle in cycles %}- {{ cycle.severity }}: {% for \
+             ws in cycle.workspaces %}{{ ws }} {% endfor %}\n{% endfor %}"
+                .to_string(),
+        )
+        .unwrap();
+
+        let report = generator.generate_report(&detector_with_cycle()).unwrap();
+
+        assert_eq!(report, "1 cycle(s)\n- medium: <app> core \n");
+    }
+
+    #[test]
+    fn test_template_escape_filter_escapes() {
+        let generator = TemplateReportGenerator::new(
+            "{% for ws in cycles.0.workspaces %}{{ ws | escape }};{{ ws }};{% endfor %}"
+                .to_string(),
+        )
+        .unwrap();
+
+        let report = generator.generate_report(&detector_with_cycle()).unwrap();
+
+        assert_eq!(report, "&lt;app&gt;;<app>;core;core;");
+    }
+
+    #[test]
+    fn test_template_errors_are_configuration_errors() {
+        assert!(matches!(
+            TemplateReportGenerator::new("{% for cycle in cycles %}".to_string()),
+            Err(FerrisWheelError::ConfigurationError { .. })
+        ));
+
+        let generator = TemplateReportGenerator::new("{{ no_such_field }}".to_string()).unwrap();
+        let err = generator
+            .generate_report(&CycleDetector::new())
+            .unwrap_err();
+        assert!(err.to_string().contains("no_such_field"), "{err}");
+    }
+}
@@ -0,0 +1,255 @@
+//! Per-phase timings for `--timings`
+//!
+//! The analyzer, graph builder and cycle detector record how long they take
+//! into a process-wide collector, which is a no-op until [`enable`] is
+//! called. Phases that run several times, such as detection in `diff`, add
+//! up. Reporting is the time from the end of the last other phase to the end
+//! of the command. Commands that end with a specific exit code go through
+//! [`exit`], so their timings are reported too.
+
+use std::fmt::Write as _;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use serde::Serialize;
+
+use crate::error::FerrisWheelError;
+
+/// A phase of a run
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Phase {
+    /// Finding workspace roots
+    Discovery,
+    /// Reading manifests (or `cargo metadata`) of each workspace
+    Parsing,
+    /// Building the dependency graph
+    GraphBuild,
+    /// Finding cycles
+    Detection,
+    /// Everything after the last other phase, mostly rendering the report
+    Reporting,
+}
+
+impl Phase {
+    const ALL: [Phase; 5] = [
+        Phase::Discovery,
+        Phase::Parsing,
+        Phase::GraphBuild,
+        Phase::Detection,
+        Phase::Reporting,
+    ];
+
+    fn label(self) -> &'static str {
+        match self {
+            Phase::Discovery => "discovery",
+            Phase::Parsing => "parsing",
+            Phase::GraphBuild => "graph build",
+            Phase::Detection => "detection",
+            Phase::Reporting => "reporting",
+        }
+    }
+}
+
+struct Collector {
+    json: Option<PathBuf>,
+    started: Instant,
+    last_phase_end: Option<Instant>,
+    phases: Vec<(Phase, Duration)>,
+}
+
+static COLLECTOR: Mutex<Option<Collector>> = Mutex::new(None);
+
+fn collector() -> std::sync::MutexGuard<'static, Option<Collector>> {
+    COLLECTOR
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+/// Start collecting timings, to be written as JSON to `json` if given
+pub fn enable(json: Option<PathBuf>) {
+    *collector() = Some(Collector {
+        json,
+        started: Instant::now(),
+        last_phase_end: None,
+        phases: Vec::new(),
+    });
+}
+
+/// Records the time until it is dropped against a phase
+#[must_use = "the phase is timed until the timer is dropped"]
+pub struct Timer {
+    phase: Phase,
+    started: Instant,
+}
+
+impl Timer {
+    pub fn start(phase: Phase) -> Self {
+        Self {
+            phase,
+            started: Instant::now(),
+        }
+    }
+}
+
+impl Drop for Timer {
+    fn drop(&mut self) {
+        if let Some(collector) = collector().as_mut() {
+            let now = Instant::now();
+            collector
+                .phases
+                .push((self.phase, now.duration_since(self.started)));
+            collector.last_phase_end = Some(now);
+        }
+    }
+}
+
+/// Time spent in one phase
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PhaseTiming {
+    pub phase: Phase,
+    pub duration_ms: f64,
+    /// How many times the phase ran
+    pub runs: usize,
+}
+
+/// Timings of a whole run
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct TimingReport {
+    pub total_ms: f64,
+    pub phases: Vec<PhaseTiming>,
+}
+
+/// Stop collecting, print the timings and write the JSON report if one was
+/// requested
+///
+/// Does nothing if collection was never enabled.
+pub fn report() -> Result<(), FerrisWheelError> {
+    let Some(collector) = collector().take() else {
+        return Ok(());
+    };
+    let now = Instant::now();
+    let mut phases = collector.phases;
+    if let Some(last_phase_end) = collector.last_phase_end {
+        phases.push((Phase::Reporting, now.duration_since(last_phase_end)));
+    }
+    let report = TimingReport::new(phases, now.duration_since(collector.started));
+
+    eprint!("\n{}", report.to_human());
+    if let Some(path) = &collector.json {
+        report.write_json(path)?;
+    }
+    Ok(())
+}
+
+/// Report timings, then exit with `code`
+pub fn exit(code: i32) -> ! {
+    if let Err(e) = report() {
+        eprintln!("Failed to write timings: {e}");
+    }
+    std::process::exit(code)
+}
+
+impl TimingReport {
+    fn new(phases: Vec<(Phase, Duration)>, total: Duration) -> Self {
+        let phases = Phase::ALL
+            .into_iter()
+            .filter_map(|phase| {
+                let durations: Vec<Duration> = phases
+                    .iter()
+                    .filter(|(recorded, _)| *recorded == phase)
+                    .map(|(_, duration)| *duration)
+                    .collect();
+                (!durations.is_empty()).then(|| PhaseTiming {
+                    phase,
+                    duration_ms: millis(durations.iter().sum()),
+                    runs: durations.len(),
+                })
+            })
+            .collect();
+
+        Self {
+            total_ms: millis(total),
+            phases,
+        }
+    }
+
+    /// A table of the phases for the terminal
+    pub fn to_human(&self) -> String {
+        let mut output = String::from("⏱ Timings\n");
+        for timing in &self.phases {
+            let runs = if timing.runs > 1 {
+                format!(" ({} runs)", timing.runs)
+            } else {
+                String::new()
+            };
+            let _ = writeln!(
+                output,
+                "  {:<12} {:>10.1} ms{runs}",
+                timing.phase.label(),
+                timing.duration_ms
+            );
+        }
+        let _ = writeln!(output, "  {:<12} {:>10.1} ms", "total", self.total_ms);
+        output
+    }
+
+    /// Write the timings to `path` as JSON
+    pub fn write_json(&self, path: &Path) -> Result<(), FerrisWheelError> {
+        std::fs::write(path, format!("{}\n", serde_json::to_string_pretty(self)?))?;
+        Ok(())
+    }
+}
+
+fn millis(duration: Duration) -> f64 {
+    duration.as_secs_f64() * 1000.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_timing_report_sums_repeated_phases() {
+        let report = TimingReport::new(
+            vec![
+                (Phase::Detection, Duration::from_millis(3)),
+                (Phase::Discovery, Duration::from_millis(10)),
+                (Phase::Detection, Duration::from_millis(4)),
+                (Phase::Reporting, Duration::from_millis(1)),
+            ],
+            Duration::from_millis(20),
+        );
+
+        assert_eq!(
+            report.phases,
+            vec![
+                PhaseTiming {
+                    phase: Phase::Discovery,
+                    duration_ms: 10.0,
+                    runs: 1,
+                },
+                PhaseTiming {
+                    phase: Phase::Detection,
+                    duration_ms: 7.0,
+                    runs: 2,
+                },
+                PhaseTiming {
+                    phase: Phase::Reporting,
+                    duration_ms: 1.0,
+                    runs: 1,
+                },
+            ]
+        );
+        assert!(
+            report
+                .to_human()
+                .contains("detection           7.0 ms (2 runs)")
+        );
+
+        let json = serde_json::to_value(&report).unwrap();
+        assert_eq!(json["phases"][1]["phase"], "detection");
+        assert_eq!(json["total_ms"], 20.0);
+    }
+}
@@ -1,4 +1,4 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::path::{Path, PathBuf};
 
 use miette::{IntoDiagnostic, NamedSource, Result, SourceSpan};
@@ -16,6 +16,14 @@ pub struct CargoToml {
     #[serde(rename = "build-dependencies")]
     pub build_dependencies: Option<HashMap<String, Dependency>>,
     pub target: Option<HashMap<String, TargetDependencies>>,
+    pub features: Option<BTreeMap<String, Vec<String>>>,
+    /// `[patch.<source>]` tables, keyed by source
+    pub patch: Option<HashMap<String, HashMap<String, Dependency>>>,
+    /// `[replace]` entries, keyed by package ID spec
+    pub replace: Option<HashMap<String, Dependency>>,
+    /// Suspicious constructs found while parsing
+    #[serde(skip)]
+    pub warnings: Vec<ManifestWarning>,
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -56,9 +64,12 @@ pub enum Dependency {
 #[derive(Debug, Clone, Deserialize)]
 pub struct DetailedDependency {
     pub path: Option<String>,
+    /// Name of the package when the dependency is renamed
+    pub package: Option<String>,
     pub workspace: Option<bool>,
     pub version: Option<String>,
     pub features: Option<Vec<String>>,
+    #[serde(rename = "default-features", alias = "default_features")]
     pub default_features: Option<bool>,
     pub optional: Option<bool>,
 }
@@ -73,6 +84,10 @@ impl CargoToml {
             .into_diagnostic()?;
 
         toml::from_str(&content)
+            .map(|cargo_toml: Self| Self {
+                warnings: manifest_warnings(&content),
+                ..cargo_toml
+            })
             .map_err(|e| {
                 // Try to extract span information from the error
                 let span = e
@@ -89,8 +104,10 @@ impl CargoToml {
             .into_diagnostic()
     }
 
+    /// Whether this manifest declares a workspace, whether or not it also
+    /// declares the workspace's root package
     pub fn is_workspace_root(&self) -> bool {
-        self.workspace.is_some() && self.package.is_none()
+        self.workspace.is_some()
     }
 
     pub fn get_workspace_members(&self) -> Vec<String> {
@@ -110,15 +127,18 @@ impl CargoToml {
             .unwrap_or_default()
     }
 
-    pub fn get_workspace_dependencies(&self) -> HashMap<String, PathBuf> {
+    /// `[workspace.dependencies]` entries that point at a local path
+    pub fn get_workspace_dependencies(&self) -> HashMap<String, DetailedDependency> {
         let mut deps = HashMap::new();
 
         if let Some(workspace) = &self.workspace
             && let Some(workspace_deps) = &workspace.dependencies
         {
             for (name, dep) in workspace_deps {
-                if let Some(path) = Self::extract_path(dep) {
-                    deps.insert(name.clone(), PathBuf::from(path));
+                if let Dependency::Detailed(detailed) = dep
+                    && detailed.path.is_some()
+                {
+                    deps.insert(name.clone(), detailed.clone());
                 }
             }
         }
@@ -126,6 +146,72 @@ impl CargoToml {
         deps
     }
 
+    /// Resolve a `workspace = true` entry against its
+    /// `[workspace.dependencies]` definition, the way Cargo does
+    ///
+    /// `path`, `version` and `default-features` come from the workspace;
+    /// `features` are the union of both entries and `optional` is taken from
+    /// the member. Cargo ignores any other key on the member entry.
+    pub fn inherit_dependency(dep: &Dependency, workspace: &DetailedDependency) -> Dependency {
+        let member = match dep {
+            Dependency::Detailed(member) => Some(member),
+            Dependency::Simple(_) => None,
+        };
+
+        let mut features = workspace.features.clone().unwrap_or_default();
+        for feature in member
+            .and_then(|m| m.features.as_ref())
+            .into_iter()
+            .flatten()
+        {
+            if !features.contains(feature) {
+                features.push(feature.clone());
+            }
+        }
+
+        Dependency::Detailed(DetailedDependency {
+            path: workspace.path.clone(),
+            package: workspace.package.clone(),
+            workspace: Some(true),
+            version: workspace.version.clone(),
+            features: (!features.is_empty()).then_some(features),
+            default_features: workspace.default_features,
+            optional: member.and_then(|m| m.optional),
+        })
+    }
+
+    /// Dependencies redirected to a local path by `[patch]` or `[replace]`,
+    /// with paths relative to the manifest's directory
+    pub fn get_patched_dependencies(&self) -> HashMap<String, PathBuf> {
+        let mut deps = HashMap::new();
+
+        let patches = self
+            .patch
+            .iter()
+            .flat_map(|sources| sources.values())
+            .flatten();
+        let replacements = self.replace.iter().flatten().map(|(spec, dep)| {
+            // Package ID specs look like `name:version`, `name@version` or
+            // `registry#name@version`
+            let spec = spec
+                .rsplit_once('#')
+                .map_or(spec.as_str(), |(_, spec)| spec);
+            let name = spec.split([':', '@']).next().unwrap_or(spec);
+            (name, dep)
+        });
+
+        for (name, dep) in patches
+            .map(|(name, dep)| (name.as_str(), dep))
+            .chain(replacements)
+        {
+            if let Some(path) = Self::extract_path(dep) {
+                deps.insert(name.to_string(), PathBuf::from(path));
+            }
+        }
+
+        deps
+    }
+
     pub fn get_all_dependencies(&self) -> Vec<(String, Dependency, DependencyType)> {
         let mut all_deps = Vec::new();
 
@@ -199,6 +285,277 @@ impl CargoToml {
             Dependency::Detailed(detailed) => detailed.workspace.unwrap_or(false),
         }
     }
+
+    pub fn is_optional_dependency(dep: &Dependency) -> bool {
+        match dep {
+            Dependency::Simple(_) => false,
+            Dependency::Detailed(detailed) => detailed.optional.unwrap_or(false),
+        }
+    }
+
+    /// Name of the package behind a dependency declared as `name`, which
+    /// differs from `name` when the dependency is renamed with `package`
+    pub fn package_name<'a>(name: &'a str, dep: &'a Dependency) -> &'a str {
+        match dep {
+            Dependency::Detailed(DetailedDependency {
+                package: Some(package),
+                ..
+            }) => package,
+            _ => name,
+        }
+    }
+
+    /// Features the dependency is requested with
+    pub fn dependency_features(dep: &Dependency) -> Vec<String> {
+        match dep {
+            Dependency::Simple(_) => Vec::new(),
+            Dependency::Detailed(detailed) => detailed.features.clone().unwrap_or_default(),
+        }
+    }
+
+    /// Whether the dependency's `default` feature is requested
+    pub fn uses_default_features(dep: &Dependency) -> bool {
+        match dep {
+            Dependency::Simple(_) => true,
+            Dependency::Detailed(detailed) => detailed.default_features.unwrap_or(true),
+        }
+    }
+}
+
+/// Dependency table names that may declare a crate dependency
+const DEPENDENCY_TABLES: [&str; 3] = ["dependencies", "dev-dependencies", "build-dependencies"];
+
+/// A construct that Cargo accepts but that usually signals a mistake, such as
+/// a botched merge
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ManifestWarning {
+    /// 1-based line of the construct
+    pub line: usize,
+    pub message: String,
+}
+
+/// Find suspicious constructs in a manifest
+///
+/// Reports dependencies declaring both `path` and `git`, and workspace
+/// members listed more than once.
+pub fn manifest_warnings(content: &str) -> Vec<ManifestWarning> {
+    let Ok(document) = toml::de::DeTable::parse(content) else {
+        return Vec::new();
+    };
+    let document = document.get_ref();
+    let mut warnings = Vec::new();
+
+    let mut tables = Vec::new();
+    for (key, value) in document.iter() {
+        let toml::de::DeValue::Table(table) = value.get_ref() else {
+            continue;
+        };
+        if DEPENDENCY_TABLES.contains(&key.get_ref().as_ref()) {
+            tables.push((key.get_ref().to_string(), table));
+        } else if key.get_ref() == "target" {
+            for (target, value) in table.iter() {
+                let toml::de::DeValue::Table(target_table) = value.get_ref() else {
+                    continue;
+                };
+                for (key, value) in target_table.iter() {
+                    if let toml::de::DeValue::Table(table) = value.get_ref()
+                        && DEPENDENCY_TABLES.contains(&key.get_ref().as_ref())
+                    {
+                        tables.push((
+                            format!("target.{}.{}", target.get_ref(), key.get_ref()),
+                            table,
+                        ));
+                    }
+                }
+            }
+        }
+    }
+    if let Some(dependencies) =
+        subtable(document, "workspace").and_then(|workspace| subtable(workspace, "dependencies"))
+    {
+        tables.push(("workspace.dependencies".to_string(), dependencies));
+    }
+
+    for (name, table) in tables {
+        for (key, value) in table.iter() {
+            let toml::de::DeValue::Table(dependency) = value.get_ref() else {
+                continue;
+            };
+            if subkey(dependency, "path") && subkey(dependency, "git") {
+                warnings.push(ManifestWarning {
+                    line: line_of(content, key.span().start),
+                    message: format!(
+                        "dependency '{}' in [{name}] sets both path and git",
+                        key.get_ref()
+                    ),
+                });
+            }
+        }
+    }
+
+    let members = subtable(document, "workspace").and_then(|workspace| {
+        workspace
+            .iter()
+            .find_map(|(key, value)| match value.get_ref() {
+                toml::de::DeValue::Array(members) if key.get_ref() == "members" => Some(members),
+                _ => None,
+            })
+    });
+    let mut seen = std::collections::HashSet::new();
+    for member in members.into_iter().flatten() {
+        if let toml::de::DeValue::String(name) = member.get_ref()
+            && !seen.insert(name.trim_end_matches('/'))
+        {
+            warnings.push(ManifestWarning {
+                line: line_of(content, member.span().start),
+                message: format!("workspace member '{name}' is listed more than once"),
+            });
+        }
+    }
+
+    warnings.sort_by_key(|warning| warning.line);
+    warnings
+}
+
+fn subkey(table: &toml::de::DeTable<'_>, name: &str) -> bool {
+    table.iter().any(|(key, _)| key.get_ref() == name)
+}
+
+/// Where a dependency is declared in a manifest
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DependencyDeclaration {
+    /// 1-based line of the declaration
+    pub line: usize,
+    /// Whether the entry is `workspace = true`, inheriting its definition
+    /// from `[workspace.dependencies]`
+    pub inherited: bool,
+}
+
+/// Find the 1-based line on which `dependency` is declared in a manifest
+///
+/// Searches the normal, dev and build dependency tables, including their
+/// target-specific variants, and returns the first declaration found.
+pub fn find_dependency_line(content: &str, dependency: &str) -> Option<usize> {
+    find_dependency_declaration(content, dependency).map(|declaration| declaration.line)
+}
+
+/// Find the first declaration of `dependency` in a manifest, like
+/// [`find_dependency_line`], and whether it is inherited from the workspace
+pub fn find_dependency_declaration(
+    content: &str,
+    dependency: &str,
+) -> Option<DependencyDeclaration> {
+    find_declaration_in(content, dependency, |_| true)
+}
+
+/// Find the declaration of `dependency` in the manifest tables for `kind`
+/// (`[dev-dependencies]` for dev dependencies, and so on), falling back to
+/// the first declaration in any table
+pub fn find_dependency_declaration_of_kind(
+    content: &str,
+    dependency: &str,
+    kind: crate::graph::DependencyType,
+) -> Option<DependencyDeclaration> {
+    find_declaration_in(content, dependency, |table| table == kind.manifest_table())
+        .or_else(|| find_dependency_declaration(content, dependency))
+}
+
+/// The first declaration of `dependency` in the dependency tables whose name
+/// `in_table` accepts, including their target-specific variants
+fn find_declaration_in(
+    content: &str,
+    dependency: &str,
+    in_table: impl Fn(&str) -> bool,
+) -> Option<DependencyDeclaration> {
+    let document = toml::de::DeTable::parse(content).ok()?;
+    let is_searched = |name: &str| DEPENDENCY_TABLES.contains(&name) && in_table(name);
+
+    let mut tables = Vec::new();
+    for (key, value) in document.get_ref().iter() {
+        let toml::de::DeValue::Table(table) = value.get_ref() else {
+            continue;
+        };
+        if is_searched(key.get_ref()) {
+            tables.push(table);
+        } else if key.get_ref() == "target" {
+            for target in table.values() {
+                let toml::de::DeValue::Table(target) = target.get_ref() else {
+                    continue;
+                };
+                for (key, value) in target.iter() {
+                    if let toml::de::DeValue::Table(table) = value.get_ref()
+                        && is_searched(key.get_ref())
+                    {
+                        tables.push(table);
+                    }
+                }
+            }
+        }
+    }
+
+    tables
+        .into_iter()
+        .flat_map(|table| table.iter())
+        .filter(|(key, value)| declares(key.get_ref(), value.get_ref(), dependency))
+        .min_by_key(|(key, _)| key.span().start)
+        .map(|(key, value)| DependencyDeclaration {
+            line: line_of(content, key.span().start),
+            inherited: is_workspace_inherited(value.get_ref()),
+        })
+}
+
+/// Find the 1-based line on which `dependency` is defined in the
+/// `[workspace.dependencies]` table of a workspace manifest
+pub fn find_workspace_dependency_line(content: &str, dependency: &str) -> Option<usize> {
+    let document = toml::de::DeTable::parse(content).ok()?;
+
+    let dependencies = subtable(document.get_ref(), "workspace")
+        .and_then(|workspace| subtable(workspace, "dependencies"))?;
+
+    dependencies
+        .iter()
+        .find(|(key, value)| declares(key.get_ref(), value.get_ref(), dependency))
+        .map(|(key, _)| line_of(content, key.span().start))
+}
+
+fn subtable<'a, 'de>(
+    table: &'a toml::de::DeTable<'de>,
+    name: &str,
+) -> Option<&'a toml::de::DeTable<'de>> {
+    table.iter().find_map(|(key, value)| match value.get_ref() {
+        toml::de::DeValue::Table(table) if key.get_ref() == name => Some(table),
+        _ => None,
+    })
+}
+
+/// Whether the dependency entry `key = value` declares the package
+/// `dependency`, either under its own name or renamed with `package`
+fn declares(key: &str, value: &toml::de::DeValue<'_>, dependency: &str) -> bool {
+    let package = match value {
+        toml::de::DeValue::Table(table) => {
+            table.iter().find_map(|(key, value)| match value.get_ref() {
+                toml::de::DeValue::String(package) if key.get_ref() == "package" => {
+                    Some(package.as_ref())
+                }
+                _ => None,
+            })
+        }
+        _ => None,
+    };
+    package.unwrap_or(key) == dependency
+}
+
+fn is_workspace_inherited(value: &toml::de::DeValue<'_>) -> bool {
+    let toml::de::DeValue::Table(table) = value else {
+        return false;
+    };
+    table.iter().any(|(key, value)| {
+        key.get_ref() == "workspace" && matches!(value.get_ref(), toml::de::DeValue::Boolean(true))
+    })
+}
+
+fn line_of(content: &str, offset: usize) -> usize {
+    content[..offset].matches('\n').count() + 1
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -244,10 +601,98 @@ serde = "1.0"
 
         let workspace_deps = cargo_toml.get_workspace_dependencies();
         assert_eq!(
-            workspace_deps.get("atlas-sdk"),
-            Some(&PathBuf::from("../sdk/sdk"))
+            workspace_deps["atlas-sdk"].path.as_deref(),
+            Some("../sdk/sdk")
         );
-        assert_eq!(workspace_deps.get("serde"), None); // No path
+        assert!(!workspace_deps.contains_key("serde")); // No path
+    }
+
+    #[test]
+    fn test_inherit_dependency() {
+        let toml_content = r#"
+[workspace]
+members = ["app"]
+
+[workspace.dependencies]
+sdk = { path = "../sdk", features = ["serde"], default-features = false }
+"#;
+
+        let mut file = NamedTempFile::new().unwrap();
+        file.write_all(toml_content.as_bytes()).unwrap();
+        let workspace_deps = CargoToml::parse_file(file.path())
+            .unwrap()
+            .get_workspace_dependencies();
+
+        let member: Dependency = toml::from_str(
+            r#"
+workspace = true
+features = ["tracing", "serde"]
+optional = true
+"#,
+        )
+        .unwrap();
+        let inherited = CargoToml::inherit_dependency(&member, &workspace_deps["sdk"]);
+
+        assert_eq!(
+            CargoToml::extract_path(&inherited).as_deref(),
+            Some("../sdk")
+        );
+        assert_eq!(
+            CargoToml::dependency_features(&inherited),
+            ["serde", "tracing"]
+        );
+        assert!(!CargoToml::uses_default_features(&inherited));
+        assert!(CargoToml::is_optional_dependency(&inherited));
+        assert!(CargoToml::is_workspace_dependency(&inherited));
+    }
+
+    #[test]
+    fn test_patched_dependencies() {
+        let toml_content = r#"
+[workspace]
+members = ["app"]
+
+[patch.crates-io]
+serde = { path = "vendor/serde" }
+tokio = { git = "https://github.com/tokio-rs/tokio" }
+
+[patch."https://github.com/org/repo"]
+shared = { path = "../shared" }
+
+[replace]
+"log:0.4.0" = { path = "vendor/log" }
+"#;
+
+        let mut file = NamedTempFile::new().unwrap();
+        file.write_all(toml_content.as_bytes()).unwrap();
+
+        let patched = CargoToml::parse_file(file.path())
+            .unwrap()
+            .get_patched_dependencies();
+
+        assert_eq!(patched.len(), 3);
+        assert_eq!(patched["serde"], PathBuf::from("vendor/serde"));
+        assert_eq!(patched["shared"], PathBuf::from("../shared"));
+        assert_eq!(patched["log"], PathBuf::from("vendor/log"));
+    }
+
+    #[test]
+    fn test_root_package_manifest_is_a_workspace_root() {
+        let toml_content = r#"
+[workspace]
+members = ["crates/*"]
+
+[package]
+name = "app"
+"#;
+
+        let mut file = NamedTempFile::new().unwrap();
+        file.write_all(toml_content.as_bytes()).unwrap();
+
+        let cargo_toml = CargoToml::parse_file(file.path()).unwrap();
+
+        assert!(cargo_toml.is_workspace_root());
+        assert_eq!(cargo_toml.package.as_ref().unwrap().name, "app");
     }
 
     #[test]
@@ -295,4 +740,170 @@ test-utils = { path = "./test-utils" }
             .1;
         assert!(CargoToml::is_workspace_dependency(serde_dep));
     }
+
+    #[test]
+    fn test_find_dependency_line() {
+        let toml_content = r#"[package]
+name = "my-crate"
+
+[dependencies]
+atlas-core = { path = "../core" }
+
+[dev-dependencies.test-utils]
+path = "./test-utils"
+
+[target.'cfg(unix)'.build-dependencies]
+atlas-build = { path = "../build" }
+core = { package = "atlas-core-v2", path = "../core-v2" }
+"#;
+
+        assert_eq!(find_dependency_line(toml_content, "atlas-core"), Some(5));
+        assert_eq!(find_dependency_line(toml_content, "test-utils"), Some(7));
+        assert_eq!(find_dependency_line(toml_content, "atlas-build"), Some(11));
+        // Renamed dependencies are found by their package name
+        assert_eq!(
+            find_dependency_line(toml_content, "atlas-core-v2"),
+            Some(12)
+        );
+        assert_eq!(find_dependency_line(toml_content, "core"), None);
+        assert_eq!(find_dependency_line(toml_content, "name"), None);
+        assert_eq!(find_dependency_line(toml_content, "missing"), None);
+    }
+
+    #[test]
+    fn test_find_inherited_dependency_declaration() {
+        let member = r#"[package]
+name = "api"
+
+[dependencies]
+core = { workspace = true }
+serde.workspace = true
+
+[dependencies.utils]
+path = "../utils"
+"#;
+        let workspace = r#"[workspace]
+members = ["api"]
+
+[workspace.dependencies]
+core = { path = "core" }
+"#;
+
+        assert_eq!(
+            find_dependency_declaration(member, "core"),
+            Some(DependencyDeclaration {
+                line: 5,
+                inherited: true
+            })
+        );
+        assert!(
+            find_dependency_declaration(member, "serde")
+                .unwrap()
+                .inherited
+        );
+        assert!(
+            !find_dependency_declaration(member, "utils")
+                .unwrap()
+                .inherited
+        );
+
+        assert_eq!(find_workspace_dependency_line(workspace, "core"), Some(5));
+        assert_eq!(find_workspace_dependency_line(workspace, "utils"), None);
+        assert_eq!(find_workspace_dependency_line(member, "core"), None);
+    }
+
+    #[test]
+    fn test_find_dependency_declaration_of_kind() {
+        let content = r#"[package]
+name = "api"
+
+[dependencies]
+core = { path = "../core" }
+
+[dev-dependencies]
+core = { path = "../core", features = ["testing"] }
+fixtures = { path = "../fixtures" }
+"#;
+        let line = |dependency, kind| {
+            find_dependency_declaration_of_kind(content, dependency, kind).map(|found| found.line)
+        };
+
+        assert_eq!(line("core", crate::graph::DependencyType::Normal), Some(5));
+        assert_eq!(line("core", crate::graph::DependencyType::Dev), Some(8));
+        // Falls back to any table when the kind's own table lacks it
+        assert_eq!(
+            line("fixtures", crate::graph::DependencyType::Build),
+            Some(9)
+        );
+        assert_eq!(line("missing", crate::graph::DependencyType::Dev), None);
+    }
+
+    #[test]
+    fn test_manifest_warnings() {
+        let content = r#"[workspace]
+members = ["core", "app", "core/"]
+
+[workspace.dependencies]
+
+[package]
+name = "root"
+
+[dev-dependencies]
+
+[dependencies]
+local = { path = "../local", git = "https://example.com/local" }
+serde = "1"
+
+[target.'cfg(unix)'.build-dependencies]
+"#;
+
+        let warnings: Vec<_> = manifest_warnings(content)
+            .into_iter()
+            .map(|warning| (warning.line, warning.message))
+            .collect();
+        assert_eq!(
+            warnings,
+            vec![
+                (
+                    2,
+                    "workspace member 'core/' is listed more than once".to_string()
+                ),
+                (
+                    12,
+                    "dependency 'local' in [dependencies] sets both path and git".to_string()
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_cargo_new_manifest_has_no_warnings() {
+        let content = r#"[package]
+name = "fresh"
+version = "0.1.0"
+edition = "2024"
+
+[dependencies]
+"#;
+
+        assert!(manifest_warnings(content).is_empty());
+    }
+
+    #[test]
+    fn test_empty_tables_are_not_warnings() {
+        // What `cargo add --dev` leaves in a fresh crate
+        let content = r#"[package]
+name = "fresh"
+version = "0.1.0"
+
+[dependencies]
+
+[dev-dependencies]
+pretty_assertions = "1"
+
+[build-dependencies]
+"#;
+
+        assert!(manifest_warnings(content).is_empty());
+    }
 }
@@ -0,0 +1,72 @@
+//! Memoized path canonicalization
+//!
+//! Canonicalizing a path costs a syscall per component. The analyzer, the
+//! graph builder and ripples resolve the same crate and workspace paths for
+//! every dependency and changed file, so results are cached for the whole
+//! process, failures included. Relative paths depend on the working directory
+//! and are never cached. Watch mode clears the cache before every re-run,
+//! since directories may have been created or removed in between.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{LazyLock, Mutex, MutexGuard};
+
+static PATHS: LazyLock<Mutex<HashMap<PathBuf, Option<PathBuf>>>> = LazyLock::new(Default::default);
+
+fn paths() -> MutexGuard<'static, HashMap<PathBuf, Option<PathBuf>>> {
+    PATHS
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+/// The canonical form of `path`, or `None` if it cannot be resolved
+pub fn canonicalize(path: &Path) -> Option<PathBuf> {
+    if path.is_relative() {
+        return path.canonicalize().ok();
+    }
+    if let Some(canonical) = paths().get(path) {
+        return canonical.clone();
+    }
+    // Resolved without holding the lock, so parallel workspace analysis does
+    // not wait on the filesystem of another thread
+    let canonical = path.canonicalize().ok();
+    paths().insert(path.to_path_buf(), canonical.clone());
+    canonical
+}
+
+/// The canonical form of `path`, or `path` itself if it cannot be resolved
+pub fn canonicalize_or_self(path: &Path) -> PathBuf {
+    canonicalize(path).unwrap_or_else(|| path.to_path_buf())
+}
+
+/// Forget every cached path
+pub fn clear() {
+    paths().clear();
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use tempfile::TempDir;
+
+    use super::*;
+
+    #[test]
+    fn test_canonicalize_caches_until_cleared() {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path().canonicalize().unwrap();
+        let missing = root.join("later");
+
+        assert_eq!(canonicalize(&missing), None);
+        assert_eq!(canonicalize_or_self(&missing), missing);
+
+        // The failure is remembered until the cache is cleared
+        fs::create_dir(&missing).unwrap();
+        assert_eq!(canonicalize(&missing), None);
+        clear();
+        assert_eq!(canonicalize(&missing), Some(missing.clone()));
+
+        assert_eq!(canonicalize(&root.join("later/../later")), Some(missing));
+    }
+}
@@ -0,0 +1,50 @@
+//! Interned names
+//!
+//! Crate names and targets are repeated on every dependency edge of a graph,
+//! and cycle edges share the graph's copies. Interning them keeps one shared
+//! allocation per distinct name, so cloning a name is a reference count bump
+//! instead of a copy. An [`Interner`] belongs to the graph build using it, so
+//! its names are freed with the graph and concurrent builds never share a
+//! lock.
+
+use std::collections::HashSet;
+use std::sync::Arc;
+
+/// Shared copies of the names seen so far
+#[derive(Debug, Default)]
+pub struct Interner {
+    names: HashSet<Arc<str>>,
+}
+
+impl Interner {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The shared copy of `name`
+    pub fn intern(&mut self, name: &str) -> Arc<str> {
+        if let Some(interned) = self.names.get(name) {
+            return Arc::clone(interned);
+        }
+        let interned: Arc<str> = Arc::from(name);
+        self.names.insert(Arc::clone(&interned));
+        interned
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_intern_shares_allocations() {
+        let mut names = Interner::new();
+        let a = names.intern("core-types");
+        let b = names.intern(&String::from("core-types"));
+        assert!(Arc::ptr_eq(&a, &b));
+        assert_eq!(&*a, "core-types");
+        assert!(!Arc::ptr_eq(&a, &names.intern("core-utils")));
+        // Separate interners keep separate copies
+        assert!(!Arc::ptr_eq(&a, &Interner::new().intern("core-types")));
+    }
+}
@@ -3,4 +3,6 @@
 //! This module contains utility functions that are used across the application
 //! but don't belong to any specific domain module.
 
+pub mod canonical;
+pub mod intern;
 pub mod string;
@@ -0,0 +1,163 @@
+//! Watching directory trees for manifest changes
+//!
+//! Used by `inspect --watch` to re-run the analysis whenever a `Cargo.toml`
+//! or `Cargo.lock` changes.
+
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::time::Duration;
+
+use notify::{Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+
+use crate::error::FerrisWheelError;
+
+/// How long to keep collecting changes after the first one, so that saving
+/// several manifests at once triggers a single re-run
+const DEBOUNCE: Duration = Duration::from_millis(300);
+
+/// Watches directory trees for changes to Cargo manifests and lock files
+pub struct ManifestWatcher {
+    // Dropping the watcher stops the events
+    _watcher: RecommendedWatcher,
+    events: Receiver<notify::Result<Event>>,
+}
+
+impl ManifestWatcher {
+    /// Start watching every path recursively
+    pub fn new(paths: &[PathBuf]) -> Result<Self, FerrisWheelError> {
+        let (sender, events) = mpsc::channel();
+        let mut watcher = notify::recommended_watcher(sender).map_err(watch_error)?;
+        for path in paths {
+            watcher
+                .watch(path, RecursiveMode::Recursive)
+                .map_err(watch_error)?;
+        }
+
+        Ok(Self {
+            _watcher: watcher,
+            events,
+        })
+    }
+
+    /// Block until a manifest or lock file changes and return the changed
+    /// files
+    pub fn wait_for_change(&self) -> Result<BTreeSet<PathBuf>, FerrisWheelError> {
+        // Without a timeout, waiting only ends with a change
+        Ok(self.next_change(None)?.unwrap_or_default())
+    }
+
+    /// Wait up to `timeout`, or indefinitely when `None`, for the first
+    /// manifest change, then collect the changes that follow it within
+    /// [`DEBOUNCE`]
+    fn next_change(
+        &self,
+        timeout: Option<Duration>,
+    ) -> Result<Option<BTreeSet<PathBuf>>, FerrisWheelError> {
+        let mut changed = BTreeSet::new();
+
+        while changed.is_empty() {
+            let event = match timeout {
+                Some(timeout) => match self.events.recv_timeout(timeout) {
+                    Ok(event) => event,
+                    Err(RecvTimeoutError::Timeout) => return Ok(None),
+                    Err(RecvTimeoutError::Disconnected) => return Err(disconnected()),
+                },
+                None => self.events.recv().map_err(|_| disconnected())?,
+            };
+            changed.extend(manifest_paths(event.map_err(watch_error)?));
+        }
+
+        loop {
+            match self.events.recv_timeout(DEBOUNCE) {
+                Ok(event) => changed.extend(manifest_paths(event.map_err(watch_error)?)),
+                Err(RecvTimeoutError::Timeout) => return Ok(Some(changed)),
+                Err(RecvTimeoutError::Disconnected) => return Err(disconnected()),
+            }
+        }
+    }
+}
+
+/// Manifests and lock files created, modified or removed by `event`
+fn manifest_paths(event: Event) -> Vec<PathBuf> {
+    if !matches!(
+        event.kind,
+        EventKind::Create(_) | EventKind::Modify(_) | EventKind::Remove(_)
+    ) {
+        return Vec::new();
+    }
+
+    event
+        .paths
+        .into_iter()
+        .filter(|path| is_manifest(path))
+        .collect()
+}
+
+/// Whether `path` is a `Cargo.toml` or `Cargo.lock` outside of a `target`
+/// directory
+fn is_manifest(path: &Path) -> bool {
+    path.file_name()
+        .is_some_and(|name| name == "Cargo.toml" || name == "Cargo.lock")
+        && !path
+            .components()
+            .any(|component| component.as_os_str() == "target")
+}
+
+fn watch_error(e: notify::Error) -> FerrisWheelError {
+    FerrisWheelError::WatchError {
+        message: e.to_string(),
+    }
+}
+
+fn disconnected() -> FerrisWheelError {
+    FerrisWheelError::WatchError {
+        message: "the file watcher stopped unexpectedly".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::*;
+
+    #[test]
+    fn test_is_manifest() {
+        assert!(is_manifest(Path::new("/repo/core/Cargo.toml")));
+        assert!(is_manifest(Path::new("/repo/Cargo.lock")));
+        assert!(!is_manifest(Path::new("/repo/core/src/lib.rs")));
+        assert!(!is_manifest(Path::new(
+            "/repo/target/package/core-0.1.0/Cargo.toml"
+        )));
+    }
+
+    #[test]
+    fn test_manifest_changes_are_reported() {
+        let temp = tempfile::tempdir().unwrap();
+        let root = temp.path().canonicalize().unwrap();
+        fs::create_dir_all(root.join("core/src")).unwrap();
+        fs::write(root.join("core/Cargo.toml"), "[package]\nname = \"core\"\n").unwrap();
+
+        let watcher = ManifestWatcher::new(std::slice::from_ref(&root)).unwrap();
+
+        fs::write(root.join("core/src/lib.rs"), "pub fn f() {}\n").unwrap();
+        assert_eq!(
+            watcher
+                .next_change(Some(Duration::from_millis(500)))
+                .unwrap(),
+            None
+        );
+
+        fs::write(
+            root.join("core/Cargo.toml"),
+            "[package]\nname = \"core\"\n\n[dependencies]\n",
+        )
+        .unwrap();
+        let changed = watcher
+            .next_change(Some(Duration::from_secs(5)))
+            .unwrap()
+            .unwrap();
+        assert_eq!(changed, BTreeSet::from([root.join("core/Cargo.toml")]));
+    }
+}
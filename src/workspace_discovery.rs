@@ -1,19 +1,32 @@
-use std::collections::HashSet;
+use std::collections::{BTreeMap, HashSet};
 use std::path::{Path, PathBuf};
 
 use miette::{Result, WrapErr};
 use rayon::prelude::*;
 use walkdir::WalkDir;
 
+use crate::error::FerrisWheelError;
 use crate::progress::ProgressReporter;
-use crate::toml_parser::CargoToml;
+use crate::toml_parser::{CargoToml, DetailedDependency};
+use crate::utils::canonical::canonicalize_or_self;
 
 pub struct WorkspaceDiscovery {
     discovered_roots: HashSet<PathBuf>,
+    /// Canonical paths of the discovered roots, so a workspace reachable
+    /// through several paths is only discovered once
+    discovered_identities: HashSet<PathBuf>,
     /// Warnings collected during discovery that didn't prevent processing
     warnings: Vec<String>,
+    /// Suspicious constructs in the manifests read during discovery
+    manifest_warnings: Vec<String>,
     /// Track discovered workspaces for member checking
     discovered_workspaces: Vec<DiscoveredWorkspace>,
+    /// Abort discovery once more than this many workspace roots are found
+    max_workspaces: Option<usize>,
+    /// Directories skipped while walking, relative to each searched path
+    excluded_paths: Vec<glob::Pattern>,
+    /// Descend into symlinked directories while walking
+    follow_symlinks: bool,
 }
 
 #[derive(Debug, Clone)]
@@ -27,16 +40,50 @@ impl WorkspaceDiscovery {
     pub fn new() -> Self {
         Self {
             discovered_roots: HashSet::new(),
+            discovered_identities: HashSet::new(),
             warnings: Vec::new(),
+            manifest_warnings: Vec::new(),
             discovered_workspaces: Vec::new(),
+            max_workspaces: None,
+            excluded_paths: Vec::new(),
+            follow_symlinks: false,
         }
     }
 
+    /// Skip directories whose path relative to the searched path matches
+    /// any of `excluded_paths`, along with everything below them
+    pub fn with_excluded_paths(mut self, excluded_paths: Vec<glob::Pattern>) -> Self {
+        self.excluded_paths = excluded_paths;
+        self
+    }
+
+    /// Descend into symlinked directories while walking
+    ///
+    /// Symlink loops are skipped, and roots are deduplicated by canonical
+    /// path either way.
+    pub fn with_follow_symlinks(mut self, follow_symlinks: bool) -> Self {
+        self.follow_symlinks = follow_symlinks;
+        self
+    }
+
+    /// Fail discovery with [`FerrisWheelError::TooManyWorkspaces`] as soon as
+    /// more than `max_workspaces` workspace roots have been found
+    pub fn with_max_workspaces(mut self, max_workspaces: Option<usize>) -> Self {
+        self.max_workspaces = max_workspaces;
+        self
+    }
+
     /// Get warnings collected during discovery
     pub fn warnings(&self) -> &[String] {
         &self.warnings
     }
 
+    /// Suspicious constructs found in the manifests read during discovery,
+    /// as `<manifest>:<line>: <message>`
+    pub fn manifest_warnings(&self) -> &[String] {
+        &self.manifest_warnings
+    }
+
     /// Check if a path is a member of any discovered workspace
     fn is_path_workspace_member(&self, crate_path: &Path) -> bool {
         for workspace in &self.discovered_workspaces {
@@ -125,6 +172,8 @@ impl WorkspaceDiscovery {
         // Sort by path for consistent output
         roots.sort_by(|a, b| a.path.cmp(&b.path));
 
+        link_nested_workspaces(&mut roots);
+
         Ok(roots)
     }
 
@@ -136,33 +185,35 @@ impl WorkspaceDiscovery {
     ) -> Result<()> {
         // First, look for Cargo.lock files as they indicate workspace roots or
         // standalone crates
-        let lock_files: Vec<PathBuf> = WalkDir::new(path)
+        let excluded_paths = self.excluded_paths.clone();
+        let lock_files = WalkDir::new(path)
+            .follow_links(self.follow_symlinks)
             .into_iter()
             .filter_entry(|e| {
                 let name = e.file_name();
                 // Skip common directories that won't contain Cargo.lock
-                name != "target" && name != ".git" && name != "node_modules"
+                name != "target"
+                    && name != ".git"
+                    && name != "node_modules"
+                    && !is_excluded(&excluded_paths, path, e.path())
             })
             .filter_map(|e| e.ok())
-            .filter(|e| e.file_name() == "Cargo.lock")
-            .map(|e| e.into_path())
-            .collect();
+            .filter(|e| e.file_name() == "Cargo.lock");
 
-        // Process each Cargo.lock location in parallel
-        // First, filter to unique directories
-        let unique_dirs: Vec<PathBuf> = lock_files
-            .into_iter()
-            .filter_map(|lock_path| {
-                let dir = lock_path.parent()?.to_path_buf();
-                if self.discovered_roots.insert(dir.clone()) {
-                    Some(dir)
-                } else {
-                    None
-                }
-            })
-            .collect();
+        // Record each new Cargo.lock directory as the walk finds it, so the
+        // count is reported and capped before any manifest is parsed
+        let mut unique_dirs = Vec::new();
+        for lock_file in lock_files {
+            let Some(dir) = lock_file.path().parent() else {
+                continue;
+            };
+            if self.record_root(path, dir, progress)? {
+                unique_dirs.push(dir.to_path_buf());
+            }
+        }
+
+        // Then process each Cargo.lock location in parallel
 
-        // Then process in parallel
         let results: Vec<(Option<WorkspaceRoot>, Vec<String>)> = unique_dirs
             .into_par_iter()
             .map(|dir| {
@@ -195,6 +246,7 @@ impl WorkspaceDiscovery {
                                     .member_patterns(cargo_toml.get_workspace_members())
                                     .exclude_patterns(cargo_toml.get_workspace_excludes())
                                     .workspace_dependencies(cargo_toml.get_workspace_dependencies())
+                                    .patched_dependencies(cargo_toml.get_patched_dependencies())
                                     .with_is_standalone(false)
                                     .build()
                                 {
@@ -209,6 +261,7 @@ impl WorkspaceDiscovery {
                             )
                         } else if let Some(package) = cargo_toml.package.clone() {
                             // This is a standalone crate
+                            let patched_dependencies = cargo_toml.get_patched_dependencies();
                             (
                                 match WorkspaceMember::builder()
                                     .path(dir.clone())
@@ -224,6 +277,7 @@ impl WorkspaceDiscovery {
                                             .member_patterns(vec![]) // Standalone crates have no member patterns
                                             .exclude_patterns(vec![]) // Standalone crates have no exclude patterns
                                             .workspace_dependencies(Default::default())
+                                            .patched_dependencies(patched_dependencies)
                                             .with_is_standalone(true)
                                             .build()
                                         {
@@ -316,6 +370,9 @@ impl WorkspaceDiscovery {
         for crate_root in potential_standalone_crates {
             if !self.is_path_workspace_member(&crate_root.path) {
                 // This is truly a standalone crate
+                for member in &crate_root.members {
+                    self.record_manifest_warnings(member.path(), member.cargo_toml());
+                }
                 roots.push(crate_root);
             } else {
                 // This is actually a workspace member, skip it
@@ -333,6 +390,63 @@ impl WorkspaceDiscovery {
         Ok(())
     }
 
+    /// Remember `dir` as a workspace root found while searching `search_path`
+    ///
+    /// Returns whether the root is new, comparing canonical paths so a root
+    /// reached through a symlink matches the same root reached directly. Fails
+    /// once more roots than `max_workspaces` have been found.
+    fn record_root(
+        &mut self,
+        search_path: &Path,
+        dir: &Path,
+        progress: Option<&ProgressReporter>,
+    ) -> Result<bool, FerrisWheelError> {
+        if !self.discovered_identities.insert(identity(dir)) {
+            return Ok(false);
+        }
+        self.discovered_roots.insert(dir.to_path_buf());
+
+        let found = self.discovered_roots.len();
+        if let Some(p) = progress {
+            p.found_workspaces(found, dir);
+        }
+
+        match self.max_workspaces {
+            Some(limit) if found > limit => Err(FerrisWheelError::TooManyWorkspaces {
+                limit,
+                path: search_path.to_path_buf(),
+                hotspots: self.hotspots(search_path),
+            }),
+            _ => Ok(true),
+        }
+    }
+
+    /// The top-level directories of `search_path` holding the most discovered
+    /// roots, for pointing at where discovery went astray
+    fn hotspots(&self, search_path: &Path) -> String {
+        let mut counts: BTreeMap<String, usize> = BTreeMap::new();
+        for root in &self.discovered_roots {
+            let Ok(relative) = root.strip_prefix(search_path) else {
+                continue;
+            };
+            let top = relative
+                .components()
+                .next()
+                .map(|c| c.as_os_str().to_string_lossy().to_string())
+                .unwrap_or_else(|| ".".to_string());
+            *counts.entry(top).or_default() += 1;
+        }
+
+        let mut counts: Vec<_> = counts.into_iter().collect();
+        counts.sort_by_key(|(_, count)| std::cmp::Reverse(*count));
+        counts
+            .iter()
+            .take(3)
+            .map(|(dir, count)| format!("'{dir}' ({count})"))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
     fn find_additional_workspaces(
         &mut self,
         path: &Path,
@@ -340,12 +454,17 @@ impl WorkspaceDiscovery {
         progress: Option<&ProgressReporter>,
     ) -> Result<()> {
         // Look for Cargo.toml files with [workspace] sections
+        let excluded_paths = self.excluded_paths.clone();
         for entry in WalkDir::new(path)
             .max_depth(3) // Don't go too deep
+            .follow_links(self.follow_symlinks)
             .into_iter()
             .filter_entry(|e| {
                 let name = e.file_name();
-                name != "target" && name != ".git" && name != "node_modules"
+                name != "target"
+                    && name != ".git"
+                    && name != "node_modules"
+                    && !is_excluded(&excluded_paths, path, e.path())
             })
             .filter_map(|e| e.ok())
             .filter(|e| e.file_name() == "Cargo.toml")
@@ -356,7 +475,7 @@ impl WorkspaceDiscovery {
             };
 
             // Skip if already processed
-            if self.discovered_roots.contains(dir) {
+            if self.discovered_identities.contains(&identity(dir)) {
                 continue;
             }
 
@@ -366,7 +485,7 @@ impl WorkspaceDiscovery {
 
             match CargoToml::parse_file(cargo_toml_path) {
                 Ok(cargo_toml) if cargo_toml.is_workspace_root() => {
-                    self.discovered_roots.insert(dir.to_path_buf());
+                    self.record_root(path, dir, progress)?;
                     let member_patterns = cargo_toml.get_workspace_members();
                     let exclude_patterns = cargo_toml.get_workspace_excludes();
 
@@ -390,7 +509,9 @@ impl WorkspaceDiscovery {
                                 member_patterns,
                                 exclude_patterns,
                                 workspace_dependencies: cargo_toml.get_workspace_dependencies(),
+                                patched_dependencies: cargo_toml.get_patched_dependencies(),
                                 is_standalone: false,
+                                parent: None,
                             });
                         }
                         Err(e) => {
@@ -497,9 +618,30 @@ impl WorkspaceDiscovery {
             self.warnings.extend(local_warnings);
         }
 
+        self.record_manifest_warnings(workspace_root, cargo_toml);
+        for member in &members {
+            if member.path() != workspace_root {
+                self.record_manifest_warnings(member.path(), member.cargo_toml());
+            }
+        }
+
         Ok(members)
     }
 
+    /// Report the suspicious constructs found in the manifest in `dir`
+    fn record_manifest_warnings(&mut self, dir: &Path, cargo_toml: &CargoToml) {
+        let manifest = dir.join("Cargo.toml");
+        self.manifest_warnings
+            .extend(cargo_toml.warnings.iter().map(|warning| {
+                format!(
+                    "{}:{}: {}",
+                    manifest.display(),
+                    warning.line,
+                    warning.message
+                )
+            }));
+    }
+
     fn load_member_single(&self, path: &Path) -> Result<Option<WorkspaceMember>> {
         let cargo_toml_path = path.join("Cargo.toml");
         if cargo_toml_path.exists() {
@@ -533,6 +675,45 @@ impl WorkspaceDiscovery {
     }
 }
 
+/// Record each root's nearest enclosing workspace
+///
+/// Meta workspaces are virtual workspaces whose members are directories
+/// holding sub-workspaces of their own. Cargo doesn't model that hierarchy, so
+/// a root's parent is the closest workspace root above it whose member
+/// patterns cover the root's directory (or one of the directories in between).
+/// Whether `path`, relative to the searched `root`, matches an excluded glob
+/// Canonical identity of a directory, or the path itself if it cannot be
+/// resolved
+fn identity(dir: &Path) -> PathBuf {
+    canonicalize_or_self(dir)
+}
+
+fn is_excluded(excluded_paths: &[glob::Pattern], root: &Path, path: &Path) -> bool {
+    match path.strip_prefix(root) {
+        Ok(relative) if !relative.as_os_str().is_empty() => excluded_paths
+            .iter()
+            .any(|pattern| pattern.matches_path(relative)),
+        _ => false,
+    }
+}
+
+fn link_nested_workspaces(roots: &mut [WorkspaceRoot]) {
+    let parents: Vec<Option<PathBuf>> = roots
+        .iter()
+        .map(|root| {
+            roots
+                .iter()
+                .filter(|candidate| candidate.nests(&root.path))
+                .max_by_key(|candidate| candidate.path.components().count())
+                .map(|candidate| candidate.path.clone())
+        })
+        .collect();
+
+    for (root, parent) in roots.iter_mut().zip(parents) {
+        root.parent = parent;
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct WorkspaceRoot {
     path: PathBuf,
@@ -540,8 +721,10 @@ pub struct WorkspaceRoot {
     members: Vec<WorkspaceMember>,
     member_patterns: Vec<String>,
     exclude_patterns: Vec<String>,
-    workspace_dependencies: std::collections::HashMap<String, PathBuf>,
+    workspace_dependencies: std::collections::HashMap<String, DetailedDependency>,
+    patched_dependencies: std::collections::HashMap<String, PathBuf>,
     is_standalone: bool,
+    parent: Option<PathBuf>,
 }
 
 impl WorkspaceRoot {
@@ -566,10 +749,16 @@ impl WorkspaceRoot {
     }
 
     /// Gets the workspace dependencies
-    pub fn workspace_dependencies(&self) -> &std::collections::HashMap<String, PathBuf> {
+    pub fn workspace_dependencies(&self) -> &std::collections::HashMap<String, DetailedDependency> {
         &self.workspace_dependencies
     }
 
+    /// Gets the dependencies redirected to local paths by `[patch]` or
+    /// `[replace]`, relative to the workspace root
+    pub fn patched_dependencies(&self) -> &std::collections::HashMap<String, PathBuf> {
+        &self.patched_dependencies
+    }
+
     /// Checks if this is a standalone crate
     pub fn is_standalone(&self) -> bool {
         self.is_standalone
@@ -584,6 +773,38 @@ impl WorkspaceRoot {
     pub fn exclude_patterns(&self) -> &[String] {
         &self.exclude_patterns
     }
+
+    /// Gets the path of the workspace this one is nested in, if any
+    pub fn parent(&self) -> Option<&PathBuf> {
+        self.parent.as_ref()
+    }
+
+    /// Checks if another root at `path` sits inside one of this workspace's
+    /// member directories
+    fn nests(&self, path: &Path) -> bool {
+        if self.is_standalone {
+            return false;
+        }
+
+        let Ok(relative) = path.strip_prefix(&self.path) else {
+            return false;
+        };
+        let covered_by = |patterns: &[String]| {
+            relative
+                .ancestors()
+                .filter(|dir| !dir.as_os_str().is_empty())
+                .any(|dir| {
+                    let dir = dir.to_string_lossy();
+                    patterns.iter().any(|pattern| {
+                        glob::Pattern::new(pattern)
+                            .map(|p| p.matches(&dir))
+                            .unwrap_or(dir == pattern.as_str())
+                    })
+                })
+        };
+
+        covered_by(&self.member_patterns) && !covered_by(&self.exclude_patterns)
+    }
 }
 
 /// Builder for WorkspaceRoot
@@ -594,7 +815,8 @@ pub struct WorkspaceRootBuilder {
     members: Vec<WorkspaceMember>,
     member_patterns: Vec<String>,
     exclude_patterns: Vec<String>,
-    workspace_dependencies: std::collections::HashMap<String, PathBuf>,
+    workspace_dependencies: std::collections::HashMap<String, DetailedDependency>,
+    patched_dependencies: std::collections::HashMap<String, PathBuf>,
     is_standalone: bool,
 }
 
@@ -620,12 +842,21 @@ impl WorkspaceRootBuilder {
     /// Sets the workspace dependencies
     pub fn workspace_dependencies(
         mut self,
-        deps: std::collections::HashMap<String, PathBuf>,
+        deps: std::collections::HashMap<String, DetailedDependency>,
     ) -> Self {
         self.workspace_dependencies = deps;
         self
     }
 
+    /// Sets the dependencies redirected by `[patch]` or `[replace]`
+    pub fn patched_dependencies(
+        mut self,
+        deps: std::collections::HashMap<String, PathBuf>,
+    ) -> Self {
+        self.patched_dependencies = deps;
+        self
+    }
+
     /// Sets whether this is a standalone crate
     pub fn with_is_standalone(mut self, is_standalone: bool) -> Self {
         self.is_standalone = is_standalone;
@@ -656,7 +887,9 @@ impl WorkspaceRootBuilder {
             member_patterns: self.member_patterns,
             exclude_patterns: self.exclude_patterns,
             workspace_dependencies: self.workspace_dependencies,
+            patched_dependencies: self.patched_dependencies,
             is_standalone: self.is_standalone,
+            parent: None,
         })
     }
 }
@@ -832,6 +1065,139 @@ name = "standalone-crate"
         assert!(workspace.workspace_dependencies.contains_key("shared"));
     }
 
+    #[test]
+    fn test_suspicious_manifests_are_reported_as_warnings() {
+        let temp = create_test_workspace();
+        fs::write(
+            temp.path().join("workspace/crate-b/Cargo.toml"),
+            r#"
+[package]
+name = "crate-b"
+
+[dev-dependencies]
+
+[dependencies]
+crate-a = { path = "../crate-a", git = "https://example.com/crate-a" }
+"#,
+        )
+        .unwrap();
+
+        let mut discovery = WorkspaceDiscovery::new();
+        discovery
+            .discover_all(&[temp.path().to_path_buf()], None)
+            .unwrap();
+
+        assert!(discovery.warnings().is_empty());
+        let manifest_warnings: Vec<_> = discovery
+            .manifest_warnings()
+            .iter()
+            .filter(|warning| warning.contains("crate-b"))
+            .collect();
+        assert_eq!(manifest_warnings.len(), 1, "{manifest_warnings:?}");
+        assert!(manifest_warnings[0].ends_with(
+            "Cargo.toml:8: dependency 'crate-a' in [dependencies] sets both path and git"
+        ));
+    }
+
+    #[test]
+    fn test_max_workspaces_aborts_discovery() {
+        let temp = create_test_workspace();
+        for fixture in ["a", "b", "c"] {
+            let dir = temp.path().join("vendor").join(fixture);
+            fs::create_dir_all(&dir).unwrap();
+            fs::write(dir.join("Cargo.toml"), "[package]\nname = \"fixture\"\n").unwrap();
+            fs::write(dir.join("Cargo.lock"), "# lock file").unwrap();
+        }
+
+        let error = WorkspaceDiscovery::new()
+            .with_max_workspaces(Some(4))
+            .discover_all(&[temp.path().to_path_buf()], None)
+            .unwrap_err();
+        let error = error.downcast_ref::<FerrisWheelError>().unwrap();
+        match error {
+            FerrisWheelError::TooManyWorkspaces {
+                limit, hotspots, ..
+            } => {
+                assert_eq!(*limit, 4);
+                assert!(hotspots.starts_with("'vendor' ("), "{hotspots}");
+            }
+            other => panic!("unexpected error: {other}"),
+        }
+
+        let roots = WorkspaceDiscovery::new()
+            .with_max_workspaces(Some(5))
+            .discover_all(&[temp.path().to_path_buf()], None)
+            .unwrap();
+        assert_eq!(roots.len(), 5);
+    }
+
+    #[test]
+    fn test_excluded_paths_skip_fixture_workspaces() {
+        let temp = create_test_workspace();
+        for fixture in ["tests/fixtures/a", "tests/fixtures/b", "vendor/c"] {
+            let dir = temp.path().join(fixture);
+            fs::create_dir_all(&dir).unwrap();
+            fs::write(dir.join("Cargo.toml"), "[workspace]\nmembers = []\n").unwrap();
+            fs::write(dir.join("Cargo.lock"), "# lock file").unwrap();
+        }
+        let discover = |patterns: &[&str]| {
+            let mut names: Vec<String> = WorkspaceDiscovery::new()
+                .with_excluded_paths(
+                    patterns
+                        .iter()
+                        .map(|p| glob::Pattern::new(p).unwrap())
+                        .collect(),
+                )
+                .discover_all(&[temp.path().to_path_buf()], None)
+                .unwrap()
+                .into_iter()
+                .map(|root| root.name)
+                .collect();
+            names.sort();
+            names
+        };
+
+        assert_eq!(discover(&[]).len(), 5);
+        assert_eq!(
+            discover(&["vendor", "**/fixtures"]),
+            vec!["standalone-crate", "workspace"]
+        );
+        assert_eq!(
+            discover(&["tests/fixtures/a"]),
+            vec!["b", "c", "standalone-crate", "workspace"]
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_symlinked_workspaces_are_followed_once() {
+        let temp = create_test_workspace();
+        let external = TempDir::new().unwrap();
+        fs::write(
+            external.path().join("Cargo.toml"),
+            "[workspace]\nmembers = []\n",
+        )
+        .unwrap();
+        fs::write(external.path().join("Cargo.lock"), "# lock file").unwrap();
+
+        let root = temp.path();
+        std::os::unix::fs::symlink(external.path(), root.join("link-a")).unwrap();
+        std::os::unix::fs::symlink(external.path(), root.join("link-b")).unwrap();
+        std::os::unix::fs::symlink(root.join("workspace"), root.join("workspace-link")).unwrap();
+        std::os::unix::fs::symlink(root, root.join("loop")).unwrap();
+
+        let discover = |follow_symlinks: bool| {
+            WorkspaceDiscovery::new()
+                .with_follow_symlinks(follow_symlinks)
+                .discover_all(&[root.to_path_buf()], None)
+                .unwrap()
+                .len()
+        };
+
+        assert_eq!(discover(false), 2);
+        assert_eq!(discover(true), 3);
+    }
+
     #[test]
     fn test_workspace_member_with_incorrect_cargo_lock() {
         let temp = TempDir::new().unwrap();
@@ -934,4 +1300,55 @@ name = "ignored"
         let standalone = roots.iter().find(|r| r.is_standalone).unwrap();
         assert_eq!(standalone.name, "ignored");
     }
+
+    #[test]
+    fn test_nested_meta_workspaces() {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path();
+
+        // A virtual meta workspace whose members hold sub-workspaces
+        fs::write(
+            root.join("Cargo.toml"),
+            r#"
+[workspace]
+members = ["platform", "products/*"]
+exclude = ["products/legacy"]
+"#,
+        )
+        .unwrap();
+
+        for (dir, members) in [
+            ("platform", "[\"core\"]"),
+            ("products/shop", "[\"web\"]"),
+            ("products/legacy", "[\"app\"]"),
+        ] {
+            fs::create_dir_all(root.join(dir)).unwrap();
+            fs::write(
+                root.join(dir).join("Cargo.toml"),
+                format!("[workspace]\nmembers = {members}\n"),
+            )
+            .unwrap();
+            fs::write(root.join(dir).join("Cargo.lock"), "# lock file").unwrap();
+        }
+
+        let mut discovery = WorkspaceDiscovery::new();
+        let roots = discovery.discover_all(&[root.to_path_buf()], None).unwrap();
+        let parent_of = |name: &str| {
+            roots
+                .iter()
+                .find(|r| r.name() == name)
+                .unwrap()
+                .parent()
+                .cloned()
+        };
+
+        assert_eq!(roots.len(), 4);
+        assert_eq!(parent_of("platform"), Some(root.to_path_buf()));
+        assert_eq!(parent_of("shop"), Some(root.to_path_buf()));
+        assert_eq!(parent_of("legacy"), None);
+        assert_eq!(
+            parent_of(&root.file_name().unwrap().to_string_lossy()),
+            None
+        );
+    }
 }
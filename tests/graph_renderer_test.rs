@@ -2,9 +2,12 @@
 
 use std::io::Cursor;
 
+use cargo_ferris_wheel::cli::{EdgeLabels, MermaidDirection};
 use cargo_ferris_wheel::common::ConfigBuilder;
 use cargo_ferris_wheel::detector::WorkspaceCycle;
-use cargo_ferris_wheel::graph::{DependencyEdge, DependencyType, GraphRenderer, WorkspaceNode};
+use cargo_ferris_wheel::graph::{
+    DependencyEdge, DependencyType, GraphRenderer, MermaidOptions, ThemePreset, WorkspaceNode,
+};
 use petgraph::graph::DiGraph;
 
 /// Create a test graph with duplicate edges between workspaces
@@ -187,6 +190,98 @@ fn test_mermaid_duplicate_edges_with_crates() {
     assert!(result.contains("sequencer-node → atlas-scheduler"));
 }
 
+#[test]
+fn test_mermaid_direction_and_theme() {
+    let graph = create_test_graph_with_duplicates();
+    let renderer = GraphRenderer::new(false, false)
+        .with_mermaid_options(MermaidOptions {
+            direction: MermaidDirection::LeftRight,
+        })
+        .with_palette(ThemePreset::Dark.palette());
+    let mut output = Cursor::new(Vec::new());
+
+    renderer.render_mermaid(&graph, &[], &mut output).unwrap();
+
+    let result = String::from_utf8(output.into_inner()).unwrap();
+    let mut lines = result.lines();
+    assert_eq!(lines.next(), Some("%%{init: {'theme': 'dark'}}%%"));
+    assert_eq!(lines.next(), Some("graph LR"));
+}
+
+#[test]
+fn test_mermaid_edge_label_verbosity() {
+    let graph = create_test_graph_with_duplicates();
+    let render = |edge_labels| {
+        let renderer = GraphRenderer::new(false, false).with_edge_labels(Some(edge_labels));
+        let mut output = Cursor::new(Vec::new());
+        renderer.render_mermaid(&graph, &[], &mut output).unwrap();
+        String::from_utf8(output.into_inner()).unwrap()
+    };
+
+    let none = render(EdgeLabels::None);
+    assert!(none.starts_with("graph TD\n"));
+    assert!(none.contains("nodes --> core"));
+    assert!(!none.contains("-->|"));
+
+    let full = render(EdgeLabels::Full);
+    assert!(full.contains("nodes -->|📦 4 (normal)| core"));
+    assert!(full.contains("tools -->|ferris-wheel → atlas-core| core"));
+
+    let compact = render(EdgeLabels::Compact);
+    assert!(compact.contains("nodes -->|📦 4 uses| core"));
+}
+
+#[test]
+fn test_dot_and_d2_edge_label_verbosity() {
+    let graph = create_test_graph_with_duplicates();
+    let render = |edge_labels, d2: bool| {
+        // Labels are set explicitly, so showing crates must not change them
+        let renderer = GraphRenderer::new(false, true).with_edge_labels(Some(edge_labels));
+        let mut output = Cursor::new(Vec::new());
+        if d2 {
+            renderer.render_d2(&graph, &[], &mut output).unwrap();
+        } else {
+            renderer.render_dot(&graph, &[], &mut output).unwrap();
+        }
+        String::from_utf8(output.into_inner()).unwrap()
+    };
+
+    let none = render(EdgeLabels::None, false);
+    assert!(none.contains(r#""nodes" -> "core" [color="#));
+    assert!(
+        none.lines()
+            .filter(|line| line.contains(" -> "))
+            .all(|line| !line.contains("label="))
+    );
+    let compact = render(EdgeLabels::Compact, false);
+    assert!(compact.contains(r#""tools" -> "core" [label="Normal", "#));
+    let full = render(EdgeLabels::Full, false);
+    assert!(full.contains(r#""nodes" -> "core" [label="Normal - 4 deps", "#));
+    assert!(full.contains(r#"[label="ferris-wheel → atlas-core", "#));
+
+    assert!(render(EdgeLabels::None, true).contains("nodes -> core {"));
+    assert!(render(EdgeLabels::Full, true).contains("tools -> core: ferris-wheel → atlas-core {"));
+}
+
+#[test]
+fn test_mermaid_crate_subgraphs() {
+    let graph = create_test_graph_with_duplicates();
+    let renderer = GraphRenderer::new(true, false).with_crate_subgraphs(true);
+    let mut output = Cursor::new(Vec::new());
+
+    renderer.render_mermaid(&graph, &[], &mut output).unwrap();
+
+    let result = String::from_utf8(output.into_inner()).unwrap();
+    assert!(
+        result.contains("    subgraph core[\"core\"]\n        core__atlas_core[\"atlas-core\"]")
+    );
+    assert!(result.contains("        nodes__sequencer_node[\"sequencer-node\"]"));
+    // Each crate pair gets its own edge instead of one per workspace pair
+    assert!(result.contains("    nodes__replay_node -->|📦 uses| core__atlas_core"));
+    assert!(result.contains("    nodes__sequencer_node -.->|🔧 dev| core__atlas_scheduler"));
+    assert!(!result.contains("nodes -->"));
+}
+
 #[test]
 fn test_ascii_duplicate_edges() {
     let graph = create_test_graph_with_duplicates();
@@ -523,6 +618,81 @@ fn test_d2_format_duplicate_edges() {
     assert!(result.contains("nodes -> core: Normal"));
 }
 
+#[test]
+fn test_adjacency_format() {
+    let graph = create_test_graph_with_duplicates();
+    let renderer = GraphRenderer::new(true, true);
+    let mut output = Cursor::new(Vec::new());
+
+    renderer.render_adjacency(&graph, &mut output).unwrap();
+
+    let result = String::from_utf8(output.into_inner()).unwrap();
+    let adjacency: serde_json::Value = serde_json::from_str(&result).unwrap();
+
+    assert_eq!(
+        adjacency,
+        serde_json::json!({
+            "core": [],
+            "nodes": [{ "target": "core", "normal": 4, "build": 0, "dev": 1 }],
+            "tools": [{ "target": "core", "normal": 1, "build": 0, "dev": 0 }],
+        })
+    );
+
+    // Workspaces are emitted in sorted order regardless of insertion order
+    let core = result.find("\"core\": [").unwrap();
+    let nodes = result.find("\"nodes\": [").unwrap();
+    let tools = result.find("\"tools\": [").unwrap();
+    assert!(core < nodes && nodes < tools);
+}
+
+#[test]
+fn test_html_cycle_animation() {
+    // c -> a -> b -> c, built in a different order than it is walked
+    let cycle = WorkspaceCycle::builder()
+        .add_edge()
+        .from_workspace("b")
+        .to_workspace("c")
+        .from_crate("b-lib")
+        .to_crate("c-lib")
+        .dependency_type("Normal")
+        .add_edge()
+        .unwrap()
+        .from_workspace("c")
+        .to_workspace("a")
+        .from_crate("c-lib")
+        .to_crate("a-lib")
+        .dependency_type("Normal")
+        .add_edge()
+        .unwrap()
+        .from_workspace("a")
+        .to_workspace("b")
+        .from_crate("a-lib")
+        .to_crate("</script>")
+        .dependency_type("Dev")
+        .build()
+        .unwrap();
+
+    let renderer = GraphRenderer::new(true, false);
+    let mut output = Cursor::new(Vec::new());
+    renderer.render_html(&[cycle], &mut output).unwrap();
+    let result = String::from_utf8(output.into_inner()).unwrap();
+
+    assert!(result.starts_with("<!DOCTYPE html>"));
+    assert!(!result.contains("__CYCLE_DATA__"));
+    assert_eq!(result.matches("</script>").count(), 1);
+
+    let data = result
+        .split("const CYCLES = ")
+        .nth(1)
+        .and_then(|rest| rest.split(";\n").next())
+        .unwrap();
+    let cycles: serde_json::Value = serde_json::from_str(data).unwrap();
+    assert_eq!(cycles[0]["workspaces"], serde_json::json!(["a", "b", "c"]));
+    assert_eq!(cycles[0]["hops"][0]["from"], "a");
+    assert_eq!(cycles[0]["hops"][0]["edges"][0], "a-lib → </script>");
+    assert_eq!(cycles[0]["hops"][2]["to"], "a");
+}
+
 #[test]
 fn test_mermaid_empty_graph() {
     let graph = DiGraph::new();
@@ -1009,6 +1179,57 @@ fn test_mermaid_with_subgraphs() {
     assert!(!result.contains("subgraph other_group"));
 }
 
+#[test]
+fn test_nested_meta_workspaces() {
+    let mut graph = DiGraph::new();
+
+    // monorepo (meta) > platform (meta) > core, and monorepo > shop
+    for (name, crates, parent) in [
+        ("monorepo", vec![], None),
+        ("platform", vec![], Some("monorepo")),
+        ("core", vec!["core-api"], Some("platform")),
+        ("shop", vec!["shop-web"], Some("monorepo")),
+        ("tools", vec!["tools-cli"], None),
+    ] {
+        graph.add_node(
+            WorkspaceNode::builder()
+                .with_name(name.to_string())
+                .with_crates(crates.into_iter().map(String::from).collect())
+                .with_parent(parent.map(String::from))
+                .build()
+                .unwrap(),
+        );
+    }
+
+    let renderer = GraphRenderer::new(false, false).with_nested_workspaces(true);
+
+    let mut output = Cursor::new(Vec::new());
+    renderer.render_mermaid(&graph, &[], &mut output).unwrap();
+    let mermaid = String::from_utf8(output.into_inner()).unwrap();
+
+    assert!(
+        mermaid.contains(
+            "    subgraph monorepo_nest[\"monorepo\"]\n        subgraph \
+             platform_nest[\"platform\"]\n            core([\"core\"])"
+        )
+    );
+    assert!(mermaid.contains("        end\n        shop([\"shop\"])"));
+    // Meta workspaces without crates are only drawn as subgraphs
+    assert!(!mermaid.contains("monorepo[\"monorepo\"]"));
+    assert!(!mermaid.contains("_group"));
+
+    let mut output = Cursor::new(Vec::new());
+    renderer.render_dot(&graph, &[], &mut output).unwrap();
+    let dot = String::from_utf8(output.into_inner()).unwrap();
+
+    assert!(dot.contains(
+        "    subgraph \"cluster_monorepo\" {\n        label=\"monorepo\";\n        subgraph \
+         \"cluster_platform\" {"
+    ));
+    assert!(dot.contains("            \"core\" [label=\"core\""));
+    assert!(dot.contains("\n    \"tools\" [label=\"tools\""));
+}
+
 #[test]
 fn test_mermaid_tooltips() {
     let mut graph = DiGraph::new();
@@ -1150,7 +1371,7 @@ fn test_mermaid_target_specific_dependencies() {
             .with_from_crate("my-crate")
             .with_to_crate("platform-crate")
             .with_dependency_type(DependencyType::Normal)
-            .with_target(Some("cfg(target_os = \"linux\")".to_string()))
+            .with_target(Some("cfg(target_os = \"linux\")"))
             .build()
             .unwrap(),
     );
@@ -1481,3 +1702,30 @@ fn test_mermaid_high_severity_cycle() -> miette::Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn test_palette_applies_to_every_format() {
+    let graph = create_test_graph_with_duplicates();
+    let palette = ThemePreset::Dark.palette();
+    let renderer = GraphRenderer::new(false, false).with_palette(palette.clone());
+    let render = |format: &str| {
+        let mut output = Cursor::new(Vec::new());
+        match format {
+            "mermaid" => renderer.render_mermaid(&graph, &[], &mut output).unwrap(),
+            "dot" => renderer.render_dot(&graph, &[], &mut output).unwrap(),
+            _ => renderer.render_d2(&graph, &[], &mut output).unwrap(),
+        }
+        String::from_utf8(output.into_inner()).unwrap()
+    };
+    let light = ThemePreset::Light.palette();
+
+    for format in ["mermaid", "dot", "d2"] {
+        let result = render(format);
+        assert!(result.contains(&palette.normal_node_fill), "{format}");
+        assert!(result.contains(&palette.normal_edge), "{format}");
+        assert!(!result.contains(&light.normal_node_fill), "{format}");
+    }
+    assert!(render("mermaid").contains(",color:#ECEFF1"));
+    assert!(render("dot").contains(r##"node [fontcolor="#ECEFF1"];"##));
+    assert!(render("d2").contains(r##"style.font-color: "#ECEFF1""##));
+}